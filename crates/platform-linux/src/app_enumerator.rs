@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use zerolaunch_plugin_api::common::{desktop_file_id, split_exec, DesktopEntry};
+use zerolaunch_plugin_api::services::app::{AppEnumerator, AppInfo};
+use zerolaunch_plugin_api::services::IconRequest;
+
+use crate::xdg;
+
+/// 子目录递归深度上限（`applications/` 下通常至多一两层厂商子目录）。
+const MAX_SCAN_DEPTH: usize = 4;
+
+/// Linux 应用枚举器实现。
+/// 遍历 XDG 数据目录下的 `applications/`，解析 freedesktop `.desktop` 文件。
+/// 同一桌面文件 ID 以优先级更高的目录为准（用户目录可覆盖/隐藏系统条目）。
+pub struct LinuxAppEnumerator {
+    /// 扫描的 `applications/` 目录（按优先级排列）
+    application_dirs: Vec<PathBuf>,
+}
+
+impl Default for LinuxAppEnumerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxAppEnumerator {
+    /// 以当前用户的 XDG 数据目录创建枚举器。
+    pub fn new() -> Self {
+        Self::with_dirs(xdg::application_dirs())
+    }
+
+    /// 以显式的 `applications/` 目录列表创建枚举器（靠前者优先）。
+    pub fn with_dirs(application_dirs: Vec<PathBuf>) -> Self {
+        Self { application_dirs }
+    }
+
+    /// 同步枚举全部可见应用。
+    /// 参数：desktops - 当前桌面环境；locale - 名称本地化所用 locale。
+    fn scan(&self, desktops: &[String], locale: Option<&str>) -> Vec<AppInfo> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut apps = Vec::new();
+        for dir in &self.application_dirs {
+            let mut files = Vec::new();
+            collect_desktop_files(dir, 0, &mut files);
+            files.sort();
+            for path in files {
+                let Some(app_id) = desktop_file_id(dir, &path) else {
+                    continue;
+                };
+                // 高优先级目录已出现的 ID（含被 Hidden 隐藏者）遮蔽低优先级同名条目
+                if !seen.insert(app_id.clone()) {
+                    continue;
+                }
+                let Some(entry) = DesktopEntry::load(&path) else {
+                    warn!("LinuxAppEnumerator: 无法解析桌面条目 {}", path.display());
+                    continue;
+                };
+                if !is_launchable(&entry, desktops) {
+                    continue;
+                }
+                let Some(display_name) = entry.name(locale) else {
+                    continue;
+                };
+                let path_str = path.to_string_lossy().to_string();
+                apps.push(AppInfo {
+                    app_id,
+                    display_name,
+                    // 图标由 LinuxIconExtractor 解析 .desktop 的 Icon= 键（主题查找推迟到取图标时）
+                    icon: IconRequest::Path(path_str.clone()),
+                    install_path: Some(path_str),
+                });
+            }
+        }
+        apps
+    }
+}
+
+/// 判断桌面条目是否为应在启动器中展示的应用：
+/// Type=Application、非 NoDisplay/Hidden、在当前桌面显示、TryExec 可执行。
+pub(crate) fn is_launchable(entry: &DesktopEntry, desktops: &[String]) -> bool {
+    if entry.entry_type().as_deref() != Some("Application") {
+        return false;
+    }
+    if entry.no_display() || entry.hidden() || !entry.is_shown_in(desktops) {
+        return false;
+    }
+    if entry.exec().is_none_or(|e| split_exec(&e).is_empty()) {
+        return false;
+    }
+    match entry.try_exec() {
+        Some(try_exec) => xdg::find_in_path(&try_exec).is_some(),
+        None => true,
+    }
+}
+
+/// 递归收集目录下的 `.desktop` 文件。
+fn collect_desktop_files(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_desktop_files(&path, depth + 1, out);
+        } else if path.extension().is_some_and(|e| e == "desktop") {
+            out.push(path);
+        }
+    }
+}
+
+#[async_trait]
+impl AppEnumerator for LinuxAppEnumerator {
+    /// 枚举所有已安装应用（文件 IO 在阻塞线程池中执行）。
+    async fn enumerate_apps(&self) -> Vec<AppInfo> {
+        let enumerator = Self::with_dirs(self.application_dirs.clone());
+        let desktops = xdg::current_desktops();
+        let locale = xdg::message_locale();
+        tokio::task::spawn_blocking(move || enumerator.scan(&desktops, locale.as_deref()))
+            .await
+            .unwrap_or_else(|e| {
+                warn!("LinuxAppEnumerator: 枚举任务异常退出 {}", e);
+                Vec::new()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 写入桌面条目文件。
+    fn write_entry(dir: &Path, relative: &str, body: &str) {
+        let path = dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("[Desktop Entry]\nType=Application\n{}", body)).unwrap();
+    }
+
+    /// 用户目录遮蔽系统同 ID 条目；NoDisplay / Hidden / OnlyShowIn / TryExec 过滤生效。
+    #[test]
+    fn scan_applies_precedence_and_filters() {
+        let user = tempfile::tempdir().unwrap();
+        let system = tempfile::tempdir().unwrap();
+        write_entry(
+            system.path(),
+            "editor.desktop",
+            "Name=System Editor\nExec=sh\n",
+        );
+        write_entry(user.path(), "editor.desktop", "Name=User Editor\nExec=sh\n");
+        write_entry(system.path(), "removed.desktop", "Name=Removed\nExec=sh\n");
+        write_entry(
+            user.path(),
+            "removed.desktop",
+            "Name=Removed\nHidden=true\n",
+        );
+        write_entry(
+            system.path(),
+            "vendor/tool.desktop",
+            "Name=Tool\nName[de]=Werkzeug\nExec=sh %F\n",
+        );
+        write_entry(
+            system.path(),
+            "helper.desktop",
+            "Name=Helper\nExec=sh\nNoDisplay=true\n",
+        );
+        write_entry(
+            system.path(),
+            "kde.desktop",
+            "Name=KDE Only\nExec=sh\nOnlyShowIn=KDE;\n",
+        );
+        write_entry(
+            system.path(),
+            "missing.desktop",
+            "Name=Missing\nExec=sh\nTryExec=/nonexistent/zerolaunch-binary\n",
+        );
+
+        let enumerator =
+            LinuxAppEnumerator::with_dirs(vec![user.path().into(), system.path().into()]);
+        let apps = enumerator.scan(&["GNOME".to_string()], Some("de_DE.UTF-8"));
+        let mut names: Vec<(String, String)> = apps
+            .iter()
+            .map(|a| (a.app_id.clone(), a.display_name.clone()))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                ("editor.desktop".to_string(), "User Editor".to_string()),
+                ("vendor-tool.desktop".to_string(), "Werkzeug".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use std::fs;
+use std::path::PathBuf;
+use tracing::{debug, info};
+use zerolaunch_plugin_api::common::DesktopEntry;
+use zerolaunch_plugin_api::host::HostApiError;
+use zerolaunch_plugin_api::services::autostart::AutoStartManager;
+
+use crate::xdg;
+
+/// 默认自启动条目名（对应 `~/.config/autostart/zerolaunch-rs.desktop`）。
+const DEFAULT_TASK_NAME: &str = "zerolaunch-rs";
+
+/// Linux 自启动管理器实现。
+/// 遵循 freedesktop Desktop Application Autostart 规范：
+/// 在 `$XDG_CONFIG_HOME/autostart/` 下写入 `.desktop` 条目。
+pub struct LinuxAutoStartManager {
+    /// 自启动目录覆盖（None 时使用 `$XDG_CONFIG_HOME/autostart`）
+    autostart_dir: Option<PathBuf>,
+}
+
+impl Default for LinuxAutoStartManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxAutoStartManager {
+    pub fn new() -> Self {
+        Self {
+            autostart_dir: None,
+        }
+    }
+
+    /// 以显式自启动目录创建管理器。
+    pub fn with_dir(autostart_dir: PathBuf) -> Self {
+        Self {
+            autostart_dir: Some(autostart_dir),
+        }
+    }
+
+    /// 解析自启动目录。
+    fn autostart_dir(&self) -> Result<PathBuf, HostApiError> {
+        self.autostart_dir
+            .clone()
+            .or_else(|| xdg::config_home().map(|c| c.join("autostart")))
+            .ok_or_else(|| HostApiError::AutoStartFailed {
+                reason: "无法确定 XDG 配置目录（HOME 未设置）".to_string(),
+            })
+    }
+
+    /// 任务名对应的自启动条目路径（非法文件名字符替换为 `-`）。
+    fn entry_path(&self, task_name: &str) -> Result<PathBuf, HostApiError> {
+        let file_stem: String = task_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        Ok(self.autostart_dir()?.join(format!("{}.desktop", file_stem)))
+    }
+}
+
+/// 按 Exec 键规则引用单个参数：含保留字符时加双引号并转义，`%` 写为 `%%`。
+/// 结果再按字符串值规则转义反斜杠，可直接写入 `.desktop` 文件。
+fn quote_exec_arg(arg: &str) -> String {
+    const RESERVED: &[char] = &[
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(',
+        ')', '`',
+    ];
+    let arg = arg.replace('%', "%%");
+    let quoted = if arg.contains(RESERVED) {
+        let mut out = String::from("\"");
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('"');
+        out
+    } else {
+        arg
+    };
+    quoted.replace('\\', "\\\\")
+}
+
+#[async_trait]
+impl AutoStartManager for LinuxAutoStartManager {
+    /// 启用自启动：写入（覆盖）自启动 `.desktop` 条目。
+    async fn enable(&self, task_name: &str, exe_path: &str) -> Result<(), HostApiError> {
+        let path = self.entry_path(task_name)?;
+        info!("正在启用自动启动，条目: {}", path.display());
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| HostApiError::AutoStartFailed {
+                reason: format!("创建自启动目录失败: {}", e),
+            })?;
+        }
+        let content = format!(
+            "[Desktop Entry]\nType=Application\nName=ZeroLaunch-rs\nExec={}\nTerminal=false\nNoDisplay=true\nX-GNOME-Autostart-enabled=true\n",
+            quote_exec_arg(exe_path)
+        );
+        fs::write(&path, content).map_err(|e| HostApiError::AutoStartFailed {
+            reason: format!("写入自启动条目失败: {}", e),
+        })
+    }
+
+    /// 禁用自启动：删除自启动条目（不存在视为成功）。
+    async fn disable(&self, task_name: &str) -> Result<(), HostApiError> {
+        let path = self.entry_path(task_name)?;
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                info!("已删除自启动条目: {}", path.display());
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("自启动条目不存在，无需删除: {}", path.display());
+                Ok(())
+            }
+            Err(e) => Err(HostApiError::AutoStartFailed {
+                reason: format!("删除自启动条目失败: {}", e),
+            }),
+        }
+    }
+
+    /// 检查自启动是否已启用：条目存在且未被 `Hidden` / GNOME 开关禁用。
+    async fn is_enabled(&self, task_name: &str) -> Result<bool, HostApiError> {
+        let path = self.entry_path(task_name)?;
+        if !path.is_file() {
+            return Ok(false);
+        }
+        Ok(DesktopEntry::load(&path).is_some_and(|entry| {
+            !entry.hidden() && entry.get("X-GNOME-Autostart-enabled").as_deref() != Some("false")
+        }))
+    }
+
+    fn default_task_name(&self) -> String {
+        DEFAULT_TASK_NAME.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerolaunch_plugin_api::common::split_exec;
+
+    /// 含空格与特殊字符的路径写入后能按 Exec 规则还原。
+    #[test]
+    fn quoted_exec_roundtrips() {
+        let path = r#"/opt/Zero Launch/$bin/"app""#;
+        let entry =
+            DesktopEntry::parse(&format!("[Desktop Entry]\nExec={}\n", quote_exec_arg(path)));
+        assert_eq!(split_exec(&entry.exec().unwrap()), vec![path]);
+        assert_eq!(quote_exec_arg("/usr/bin/zerolaunch"), "/usr/bin/zerolaunch");
+    }
+
+    /// 启用 → 检查 → 禁用 的完整流程，禁用不存在的条目视为成功。
+    #[tokio::test]
+    async fn enable_disable_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LinuxAutoStartManager::with_dir(dir.path().join("autostart"));
+        let name = manager.default_task_name();
+
+        assert!(!manager.is_enabled(&name).await.unwrap());
+        manager.enable(&name, "/usr/bin/zerolaunch").await.unwrap();
+        assert!(manager.is_enabled(&name).await.unwrap());
+        assert!(dir.path().join("autostart/zerolaunch-rs.desktop").is_file());
+
+        manager.disable(&name).await.unwrap();
+        assert!(!manager.is_enabled(&name).await.unwrap());
+        manager.disable(&name).await.unwrap();
+    }
+}
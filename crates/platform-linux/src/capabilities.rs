@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+pub use zerolaunch_plugin_api::{PlatformCapabilities, PlatformCapability};
+
+use crate::xdg;
+
+/// Linux 平台的能力集构造函数。
+/// 仅包含本 crate 已实现的能力：应用启动、窗口激活与全局按键监听在 Linux 上
+/// 依赖具体桌面环境 / 显示服务器（X11 / Wayland），暂不声明。
+/// 提权启动依赖 polkit 的 `pkexec`，仅在 PATH 中找到时声明。
+pub fn linux_capabilities() -> PlatformCapabilities {
+    capabilities(xdg::find_in_path("pkexec").is_some())
+}
+
+/// 按运行环境探测结果构造能力集。
+fn capabilities(has_pkexec: bool) -> PlatformCapabilities {
+    let mut capabilities = HashSet::from([
+        PlatformCapability::IconExtraction,
+        PlatformCapability::ShellOpen,
+        PlatformCapability::AppEnumeration,
+        PlatformCapability::AutoStart,
+        PlatformCapability::InstallationMonitoring,
+    ]);
+    if has_pkexec {
+        capabilities.insert(PlatformCapability::RunAsAdmin);
+    }
+    PlatformCapabilities::new(capabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 仅在找到 pkexec 时声明 RunAsAdmin，其余能力恒定声明。
+    #[test]
+    fn run_as_admin_requires_pkexec() {
+        let without = capabilities(false);
+        assert!(!without.has(PlatformCapability::RunAsAdmin));
+        assert!(without.has(PlatformCapability::ShellOpen));
+        assert!(capabilities(true).has(PlatformCapability::RunAsAdmin));
+
+        assert_eq!(
+            linux_capabilities().has(PlatformCapability::RunAsAdmin),
+            xdg::find_in_path("pkexec").is_some()
+        );
+    }
+}
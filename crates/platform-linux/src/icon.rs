@@ -0,0 +1,303 @@
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+use url::Url;
+use zerolaunch_plugin_api::common::image_utils::ImageUtils;
+use zerolaunch_plugin_api::common::DesktopEntry;
+use zerolaunch_plugin_api::host::HostApiError;
+use zerolaunch_plugin_api::services::icon::IconExtractor;
+
+use crate::icon_theme::IconThemeLookup;
+use crate::xdg;
+
+/// 未知类型文件的通用图标名。
+const GENERIC_FILE_ICON: &str = "text-x-generic";
+/// 可执行文件的通用图标名。
+const EXECUTABLE_ICON: &str = "application-x-executable";
+/// 目录图标名。
+const FOLDER_ICON: &str = "folder";
+
+/// Linux 平台图标提取器。
+/// 实现 IconExtractor trait 的 6 个平台原语：
+/// - `.desktop` 文件按 `Icon=` 键经图标主题解析；
+/// - 图片文件直接加载（SVG 经 resvg 渲染）；
+/// - 其他文件与扩展名按 shared-mime-info 映射到 MIME 图标。
+pub struct LinuxIconExtractor {
+    /// 默认应用图标路径
+    default_app_icon_path: String,
+    /// 默认网址图标路径
+    default_web_icon_path: String,
+    /// 是否启用在线图标获取
+    enable_online: RwLock<bool>,
+    /// 图标主题查找器（构造时读取一次用户主题配置）
+    theme: IconThemeLookup,
+    /// 图标名 → 主题解析结果缓存（主题目录遍历代价较高）
+    resolved: DashMap<String, Option<PathBuf>>,
+}
+
+impl LinuxIconExtractor {
+    /// 创建 Linux 平台图标提取器。
+    /// 参数：default_app_icon_path - 默认应用图标路径；default_web_icon_path - 默认网址图标路径。
+    /// 返回：初始化后的 LinuxIconExtractor。
+    pub fn new(default_app_icon_path: String, default_web_icon_path: String) -> Self {
+        Self {
+            default_app_icon_path,
+            default_web_icon_path,
+            enable_online: RwLock::new(true),
+            theme: IconThemeLookup::from_env(),
+            resolved: DashMap::new(),
+        }
+    }
+
+    /// 更新在线图标获取配置。
+    /// 参数：enable_online - 是否启用在线图标获取。
+    pub fn set_enable_online(&self, enable_online: bool) {
+        *self.enable_online.write() = enable_online;
+    }
+
+    /// 解析主题图标名（带缓存）。
+    fn resolve_icon(&self, name: &str) -> Option<PathBuf> {
+        if let Some(hit) = self.resolved.get(name) {
+            return hit.clone();
+        }
+        let path = self.theme.lookup(name);
+        self.resolved.insert(name.to_string(), path.clone());
+        path
+    }
+
+    /// 按候选图标名依次解析，返回首个命中的图标文件。
+    fn resolve_first(&self, names: &[String]) -> Option<PathBuf> {
+        names.iter().find_map(|n| self.resolve_icon(n))
+    }
+
+    /// 读取图标文件并转换为 PNG。
+    async fn load_icon_file(request: &str, icon_path: &Path) -> Result<Vec<u8>, HostApiError> {
+        let data =
+            tokio::fs::read(icon_path)
+                .await
+                .map_err(|e| HostApiError::IconExtractionFailed {
+                    request: request.to_string(),
+                    reason: format!("读取图标文件失败 ({}): {}", icon_path.display(), e),
+                })?;
+        ImageUtils::convert_image_to_png(data).await.map_err(|e| {
+            HostApiError::IconExtractionFailed {
+                request: request.to_string(),
+                reason: e.to_string(),
+            }
+        })
+    }
+
+    /// 将路径映射为图标文件：.desktop → Icon=；图片 → 自身；其他 → MIME 图标。
+    fn icon_file_for_path(&self, path: &str) -> Option<PathBuf> {
+        let p = Path::new(path);
+        let ext = p
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if ext == "desktop" {
+            let icon = DesktopEntry::load(p)?.icon()?;
+            return self.resolve_icon(&icon);
+        }
+        if is_image_extension(&ext) && p.is_file() {
+            return Some(p.to_path_buf());
+        }
+        if p.is_dir() {
+            return self.resolve_icon(FOLDER_ICON);
+        }
+        let mut names = mime_icon_names(&ext);
+        if is_executable_file(p) {
+            names.insert(0, EXECUTABLE_ICON.to_string());
+        }
+        self.resolve_first(&names)
+    }
+
+    /// 获取网站 favicon：依次尝试页面同源的 `/favicon.ico` 与父域名根。
+    async fn fetch_favicon(&self, url: &str) -> Result<Vec<u8>, HostApiError> {
+        let failed = |reason: String| HostApiError::IconExtractionFailed {
+            request: url.to_string(),
+            reason,
+        };
+        if !self.is_network_available() {
+            return Err(failed("No network connection available".to_string()));
+        }
+        let parsed = Url::parse(url).map_err(|e| failed(format!("无效网址: {}", e)))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| failed("网址缺少主机名".to_string()))?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| failed(format!("Failed to build reqwest client: {}", e)))?;
+
+        // sub.example.com → [sub.example.com, example.com]
+        let segments: Vec<&str> = host.split('.').collect();
+        for i in 0..segments.len().saturating_sub(1).max(1) {
+            let candidate = format!(
+                "{}://{}/favicon.ico",
+                parsed.scheme(),
+                segments[i..].join(".")
+            );
+            let Ok(resp) = client.get(&candidate).send().await else {
+                continue;
+            };
+            if !resp.status().is_success() {
+                continue;
+            }
+            let Ok(bytes) = resp.bytes().await else {
+                continue;
+            };
+            match ImageUtils::convert_image_to_png(bytes.to_vec()).await {
+                Ok(png) => return Ok(png),
+                Err(e) => debug!("favicon 解码失败 ({}): {}", candidate, e),
+            }
+        }
+        Err(failed("未能获取网站图标".to_string()))
+    }
+}
+
+/// 判断扩展名是否为可直接加载的图片。
+fn is_image_extension(ext: &str) -> bool {
+    matches!(
+        ext,
+        "png" | "svg" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "ico" | "tif" | "tiff"
+    )
+}
+
+/// 判断路径是否为带可执行位的普通文件。
+fn is_executable_file(path: &Path) -> bool {
+    path.is_file() && xdg::find_in_path(&path.to_string_lossy()).is_some()
+}
+
+/// 由扩展名生成候选图标名：`<mime>` 图标 → generic-icons 声明 → `<major>-x-generic` → 通用文件图标。
+fn mime_icon_names(ext: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(mime) = lookup_mime_type(ext) {
+        names.push(mime.replace('/', "-"));
+        if let Some(generic) = lookup_generic_icon(&mime) {
+            names.push(generic);
+        }
+        if let Some((major, _)) = mime.split_once('/') {
+            names.push(format!("{}-x-generic", major));
+        }
+    }
+    names.push(GENERIC_FILE_ICON.to_string());
+    names
+}
+
+/// 通过 shared-mime-info 的 `mime/globs2` 将扩展名映射为 MIME 类型（取权重最高者）。
+fn lookup_mime_type(ext: &str) -> Option<String> {
+    let ext = ext.trim_start_matches('.').to_lowercase();
+    if ext.is_empty() {
+        return None;
+    }
+    let pattern = format!("*.{}", ext);
+    xdg::data_dirs().iter().find_map(|dir| {
+        let content = fs::read_to_string(dir.join("mime").join("globs2")).ok()?;
+        parse_globs2(&content, &pattern)
+    })
+}
+
+/// 在 globs2 文本（`weight:mime:glob` 每行）中查找与 glob 完全匹配的最高权重 MIME。
+fn parse_globs2(content: &str, pattern: &str) -> Option<String> {
+    content
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, ':');
+            let weight = parts.next()?.parse::<u32>().ok()?;
+            let mime = parts.next()?;
+            let glob = parts.next()?;
+            glob.eq_ignore_ascii_case(pattern)
+                .then(|| (weight, mime.to_string()))
+        })
+        .max_by_key(|(weight, _)| *weight)
+        .map(|(_, mime)| mime)
+}
+
+/// 读取 `mime/generic-icons` 中 MIME 类型声明的通用图标名。
+fn lookup_generic_icon(mime: &str) -> Option<String> {
+    xdg::data_dirs().iter().find_map(|dir| {
+        let content = fs::read_to_string(dir.join("mime").join("generic-icons")).ok()?;
+        content.lines().find_map(|line| {
+            let (m, icon) = line.split_once(':')?;
+            (m == mime).then(|| icon.trim().to_string())
+        })
+    })
+}
+
+#[async_trait]
+impl IconExtractor for LinuxIconExtractor {
+    async fn extract_from_path(&self, path: &str) -> Result<Vec<u8>, HostApiError> {
+        let icon_path =
+            self.icon_file_for_path(path)
+                .ok_or_else(|| HostApiError::IconExtractionFailed {
+                    request: path.to_string(),
+                    reason: "未在图标主题中找到对应图标".to_string(),
+                })?;
+        debug!("路径 {} 解析到图标文件 {}", path, icon_path.display());
+        Self::load_icon_file(path, &icon_path).await
+    }
+
+    async fn extract_from_url(&self, url: &str) -> Result<Vec<u8>, HostApiError> {
+        let enable_online = *self.enable_online.read();
+        if !enable_online {
+            return Err(HostApiError::IconExtractionFailed {
+                request: url.to_string(),
+                reason: "Online icon fetching is disabled".to_string(),
+            });
+        }
+        self.fetch_favicon(url).await
+    }
+
+    async fn extract_from_extension(&self, ext: &str) -> Result<Vec<u8>, HostApiError> {
+        let icon_path = self.resolve_first(&mime_icon_names(ext)).ok_or_else(|| {
+            HostApiError::IconExtractionFailed {
+                request: ext.to_string(),
+                reason: "未找到扩展名关联的 MIME 图标".to_string(),
+            }
+        })?;
+        Self::load_icon_file(ext, &icon_path).await
+    }
+
+    fn default_app_icon_path(&self) -> &str {
+        &self.default_app_icon_path
+    }
+
+    fn default_web_icon_path(&self) -> &str {
+        &self.default_web_icon_path
+    }
+
+    /// 存在处于 up 状态的非回环网卡即视为网络可用（读取 sysfs，无网络请求）。
+    fn is_network_available(&self) -> bool {
+        let Ok(entries) = fs::read_dir("/sys/class/net") else {
+            return false;
+        };
+        entries.flatten().any(|entry| {
+            entry.file_name() != "lo"
+                && fs::read_to_string(entry.path().join("operstate"))
+                    .is_ok_and(|state| state.trim() == "up")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// globs2 取完全匹配 glob 中权重最高的 MIME 类型（大小写不敏感）。
+    #[test]
+    fn globs2_picks_highest_weight() {
+        let content =
+            "# comment\n50:text/plain:*.txt\n80:text/x-readme:*.TXT\n50:image/png:*.png\n";
+        assert_eq!(
+            parse_globs2(content, "*.txt").as_deref(),
+            Some("text/x-readme")
+        );
+        assert_eq!(parse_globs2(content, "*.png").as_deref(), Some("image/png"));
+        assert_eq!(parse_globs2(content, "*.doc"), None);
+    }
+}
@@ -0,0 +1,269 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::xdg;
+
+/// 所有主题的最终回退主题（freedesktop 图标主题规范）。
+const FALLBACK_THEME: &str = "hicolor";
+/// 未配置主题时的默认主题。
+const DEFAULT_THEME: &str = "Adwaita";
+/// 可加载的图标扩展名（XPM 不被 image crate 支持，忽略）。
+const ICON_EXTENSIONS: [&str; 2] = ["png", "svg"];
+/// 可缩放（SVG）目录的评分：高于任何位图尺寸。
+const SCALABLE_SCORE: u32 = 1024;
+/// Inherits 链最大深度，防止主题互相继承造成死循环。
+const MAX_INHERIT_DEPTH: usize = 8;
+
+/// freedesktop 图标主题查找器。
+/// 按"当前主题 → Inherits 链 → hicolor → pixmaps"的顺序将图标名解析为文件路径；
+/// 同一主题内优先可缩放图标，其次选择尺寸最大的位图。
+pub(crate) struct IconThemeLookup {
+    /// 图标基础目录（`~/.icons`、`<data_dir>/icons`），按优先级排列
+    base_dirs: Vec<PathBuf>,
+    /// 独立图标目录（`/usr/share/pixmaps`），主题全部未命中时查找
+    pixmap_dirs: Vec<PathBuf>,
+    /// 主题查找顺序（已展开 Inherits 链，末尾为 hicolor）
+    themes: Vec<String>,
+}
+
+impl IconThemeLookup {
+    /// 从当前用户环境构建查找器（读取 GTK/KDE 配置中的图标主题）。
+    pub(crate) fn from_env() -> Self {
+        let mut base_dirs: Vec<PathBuf> = xdg::home_dir()
+            .map(|h| h.join(".icons"))
+            .into_iter()
+            .collect();
+        base_dirs.extend(xdg::data_dirs().into_iter().map(|d| d.join("icons")));
+        let pixmap_dirs = xdg::system_data_dirs()
+            .into_iter()
+            .map(|d| d.join("pixmaps"))
+            .collect();
+        let theme = configured_theme().unwrap_or_else(|| DEFAULT_THEME.to_string());
+        Self::new(base_dirs, pixmap_dirs, &theme)
+    }
+
+    /// 以显式目录与主题名构建查找器，并展开主题继承链。
+    fn new(base_dirs: Vec<PathBuf>, pixmap_dirs: Vec<PathBuf>, theme: &str) -> Self {
+        let mut lookup = Self {
+            base_dirs,
+            pixmap_dirs,
+            themes: Vec::new(),
+        };
+        lookup.push_theme(theme, 0);
+        if !lookup.themes.iter().any(|t| t == FALLBACK_THEME) {
+            lookup.themes.push(FALLBACK_THEME.to_string());
+        }
+        lookup
+    }
+
+    /// 追加主题及其 `Inherits=` 声明的父主题（深度优先，去重）。
+    fn push_theme(&mut self, theme: &str, depth: usize) {
+        if depth > MAX_INHERIT_DEPTH || self.themes.iter().any(|t| t == theme) {
+            return;
+        }
+        self.themes.push(theme.to_string());
+        let parents: Vec<String> = self
+            .theme_dirs(theme)
+            .find_map(|dir| read_inherits(&dir.join("index.theme")))
+            .unwrap_or_default();
+        for parent in parents {
+            self.push_theme(&parent, depth + 1);
+        }
+    }
+
+    /// 主题在各基础目录下的实际存在目录。
+    fn theme_dirs<'a>(&'a self, theme: &'a str) -> impl Iterator<Item = PathBuf> + 'a {
+        self.base_dirs
+            .iter()
+            .map(move |base| base.join(theme))
+            .filter(|dir| dir.is_dir())
+    }
+
+    /// 将图标名（或绝对路径）解析为图标文件路径。
+    /// 参数：icon - `Icon=` 值：图标名（如 `firefox`）或绝对路径。
+    /// 返回：找到时返回文件路径，否则 None。
+    pub(crate) fn lookup(&self, icon: &str) -> Option<PathBuf> {
+        let icon = icon.trim();
+        if icon.is_empty() {
+            return None;
+        }
+        let as_path = Path::new(icon);
+        if as_path.is_absolute() {
+            return as_path.is_file().then(|| as_path.to_path_buf());
+        }
+        // 规范要求图标名不带扩展名，但不少条目仍写了 `foo.png`
+        let name = ICON_EXTENSIONS
+            .iter()
+            .find_map(|ext| icon.strip_suffix(&format!(".{}", ext)))
+            .unwrap_or(icon);
+        for theme in &self.themes {
+            let best = self
+                .theme_dirs(theme)
+                .filter_map(|dir| best_in_theme_dir(&dir, name))
+                .max_by_key(|(score, _)| *score);
+            if let Some((_, path)) = best {
+                return Some(path);
+            }
+        }
+        self.pixmap_dirs
+            .iter()
+            .flat_map(|dir| {
+                ICON_EXTENSIONS
+                    .iter()
+                    .map(move |ext| dir.join(format!("{}.{}", name, ext)))
+            })
+            .find(|p| p.is_file())
+    }
+}
+
+/// 在单个主题目录中查找图标，返回 (评分, 路径) 中评分最高者。
+/// 主题目录布局为两级（`48x48/apps/` 或 `apps/48/`），跳过 symbolic 单色图标。
+fn best_in_theme_dir(theme_dir: &Path, name: &str) -> Option<(u32, PathBuf)> {
+    let mut best: Option<(u32, PathBuf)> = None;
+    for first in read_subdirs(theme_dir) {
+        for second in read_subdirs(&first) {
+            let score = dir_score(theme_dir, &second);
+            if score == 0 || best.as_ref().is_some_and(|(b, _)| *b >= score) {
+                continue;
+            }
+            if let Some(path) = ICON_EXTENSIONS
+                .iter()
+                .map(|ext| second.join(format!("{}.{}", name, ext)))
+                .find(|p| p.is_file())
+            {
+                best = Some((score, path));
+            }
+        }
+    }
+    best
+}
+
+/// 列出目录下的子目录（读取失败返回空）。
+fn read_subdirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 根据目录名估算图标尺寸评分：`scalable` 最高，`48x48` / `48` / `48x48@2` 取数值；
+/// symbolic 目录返回 0（不参与选择）。
+fn dir_score(theme_dir: &Path, dir: &Path) -> u32 {
+    let Ok(relative) = dir.strip_prefix(theme_dir) else {
+        return 0;
+    };
+    let mut score = 0;
+    for component in relative.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if part.contains("symbolic") {
+            return 0;
+        }
+        if part == "scalable" {
+            score = score.max(SCALABLE_SCORE);
+            continue;
+        }
+        let (size, scale) = match part.split_once('@') {
+            Some((size, scale)) => (size, scale.parse::<u32>().unwrap_or(1)),
+            None => (part.as_ref(), 1),
+        };
+        let size = size.split('x').next().unwrap_or(size);
+        if let Ok(n) = size.parse::<u32>() {
+            score = score.max(n.saturating_mul(scale));
+        }
+    }
+    score
+}
+
+/// 读取主题 `index.theme` 的 `Inherits=` 列表。
+fn read_inherits(index_theme: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(index_theme).ok()?;
+    content.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("Inherits=")?;
+        Some(
+            value
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        )
+    })
+}
+
+/// 读取用户配置的图标主题：GTK（settings.ini）优先，其次 KDE（kdeglobals）。
+fn configured_theme() -> Option<String> {
+    let config = xdg::config_home()?;
+    let sources = [
+        (
+            config.join("gtk-4.0").join("settings.ini"),
+            "gtk-icon-theme-name",
+        ),
+        (
+            config.join("gtk-3.0").join("settings.ini"),
+            "gtk-icon-theme-name",
+        ),
+        (config.join("kdeglobals"), "Theme"),
+    ];
+    sources.iter().find_map(|(file, key)| {
+        let content = fs::read_to_string(file).ok()?;
+        content.lines().find_map(|line| {
+            let (k, v) = line.split_once('=')?;
+            let v = v.trim().trim_matches('"');
+            (k.trim() == *key && !v.is_empty()).then(|| v.to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在临时目录下创建空图标文件。
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"").unwrap();
+    }
+
+    /// 主题内优先 scalable，其次最大位图；主题未命中时沿 Inherits 链与 hicolor 回退。
+    #[test]
+    fn lookup_prefers_scalable_and_follows_inheritance() {
+        let tmp = tempfile::tempdir().unwrap();
+        let icons = tmp.path().join("icons");
+        let pixmaps = tmp.path().join("pixmaps");
+        fs::create_dir_all(icons.join("Child")).unwrap();
+        fs::write(
+            icons.join("Child").join("index.theme"),
+            "[Icon Theme]\nInherits=Parent\n",
+        )
+        .unwrap();
+        touch(&icons.join("Child/48x48/apps/editor.png"));
+        touch(&icons.join("Child/256x256/apps/editor.png"));
+        touch(&icons.join("Parent/scalable/apps/browser.svg"));
+        touch(&icons.join("Parent/apps/64/browser.png"));
+        touch(&icons.join("hicolor/32x32/apps/terminal.png"));
+        touch(&icons.join("Child/symbolic/apps/terminal-symbolic.svg"));
+        touch(&pixmaps.join("legacy.png"));
+
+        let lookup = IconThemeLookup::new(vec![icons.clone()], vec![pixmaps.clone()], "Child");
+        assert_eq!(lookup.themes, vec!["Child", "Parent", "hicolor"]);
+        assert_eq!(
+            lookup.lookup("editor"),
+            Some(icons.join("Child/256x256/apps/editor.png"))
+        );
+        assert_eq!(
+            lookup.lookup("browser"),
+            Some(icons.join("Parent/scalable/apps/browser.svg"))
+        );
+        assert_eq!(
+            lookup.lookup("terminal.png"),
+            Some(icons.join("hicolor/32x32/apps/terminal.png"))
+        );
+        assert_eq!(lookup.lookup("legacy"), Some(pixmaps.join("legacy.png")));
+        assert_eq!(lookup.lookup("terminal-symbolic"), None);
+        assert_eq!(lookup.lookup("missing"), None);
+    }
+}
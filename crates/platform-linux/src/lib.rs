@@ -0,0 +1,20 @@
+#![cfg(target_os = "linux")]
+//! Linux (freedesktop) platform implementations of zerolaunch-plugin-api traits.
+
+mod app_enumerator;
+mod autostart;
+mod capabilities;
+mod icon;
+mod icon_theme;
+mod installation_monitor;
+mod path_resolver;
+mod shell;
+mod xdg;
+
+pub use app_enumerator::LinuxAppEnumerator;
+pub use autostart::LinuxAutoStartManager;
+pub use capabilities::linux_capabilities;
+pub use icon::LinuxIconExtractor;
+pub use installation_monitor::LinuxInstallationMonitor;
+pub use path_resolver::LinuxPathResolver;
+pub use shell::LinuxShellExecutor;
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use zerolaunch_plugin_api::host::HostApiError;
+use zerolaunch_plugin_api::services::path::{KnownPath, PathResolver};
+
+use crate::xdg;
+
+/// 应用数据文件夹名称（与 Windows 平台一致：Unix 惯例 . 前缀表示隐藏目录）。
+/// 标准模式解析为 `$HOME/<APP_DATA_DIR_NAME>`，便携模式为可执行文件同级目录。
+#[cfg(not(feature = "portable"))]
+const APP_DATA_DIR_NAME: &str = ".ZeroLaunch-rs";
+
+/// 系统应用目录的兜底值（`XDG_DATA_DIRS` 中均不存在 applications/ 时使用）。
+const FALLBACK_SYSTEM_APPLICATIONS_DIR: &str = "/usr/share/applications";
+
+/// Linux 路径解析器实现。
+/// 通过 XDG Base Directory / user-dirs 规范将 KnownPath 枚举映射为文件系统路径：
+/// "开始菜单"对应 freedesktop 的 `applications/` 目录。
+pub struct LinuxPathResolver;
+
+impl Default for LinuxPathResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxPathResolver {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析系统路径（开始菜单 / 桌面 / 漫游配置目录）。
+    /// 参数：path - 非应用路径的 KnownPath。
+    /// 返回：解析后的路径，失败返回 HostApiError。
+    fn resolve_system_path(path: KnownPath) -> Result<PathBuf, HostApiError> {
+        let resolved = match path {
+            // 公共"开始菜单"：首个存在的系统 applications/ 目录
+            KnownPath::CommonStartMenu => Some(
+                xdg::system_data_dirs()
+                    .into_iter()
+                    .map(|d| d.join("applications"))
+                    .find(|d| d.is_dir())
+                    .unwrap_or_else(|| PathBuf::from(FALLBACK_SYSTEM_APPLICATIONS_DIR)),
+            ),
+            KnownPath::UserStartMenu => xdg::data_home().map(|d| d.join("applications")),
+            // 桌面目录：优先 user-dirs.dirs 配置（本地化目录名），缺省为 ~/Desktop
+            KnownPath::UserDesktop => {
+                xdg::user_dir("DESKTOP").or_else(|| xdg::home_dir().map(|h| h.join("Desktop")))
+            }
+            KnownPath::UserAppDataRoaming => xdg::config_home(),
+            KnownPath::AppDataDir
+            | KnownPath::AppLogDir
+            | KnownPath::AppIconCacheDir
+            | KnownPath::AppConfigDir => unreachable!("应用路径不应进入此方法"),
+        };
+        resolved.ok_or_else(|| {
+            warn!("获取已知路径失败 ({:?})：无法确定用户 Home 目录", path);
+            HostApiError::PathResolutionFailed {
+                path: format!("{:?}", path),
+                reason: "无法确定用户 Home 目录（HOME 未设置）".to_string(),
+            }
+        })
+    }
+
+    /// 解析应用数据根目录。
+    /// 便携模式（feature="portable"）：可执行文件所在目录。
+    /// 标准模式：$HOME/.ZeroLaunch-rs。
+    fn resolve_app_data_dir() -> Result<PathBuf, HostApiError> {
+        #[cfg(feature = "portable")]
+        {
+            Ok(std::env::current_exe()
+                .ok()
+                .and_then(|p| p.parent().map(|d| d.to_path_buf()))
+                .unwrap_or_else(|| PathBuf::from(".")))
+        }
+
+        #[cfg(not(feature = "portable"))]
+        {
+            xdg::home_dir()
+                .map(|home| home.join(APP_DATA_DIR_NAME))
+                .ok_or_else(|| {
+                    warn!("无法获取用户 Home 目录");
+                    HostApiError::PathResolutionFailed {
+                        path: format!("{:?}", KnownPath::AppDataDir),
+                        reason: "无法获取用户 Home 目录（HOME 未设置）".to_string(),
+                    }
+                })
+        }
+    }
+}
+
+impl PathResolver for LinuxPathResolver {
+    /// 根据 KnownPath 类型解析实际文件系统路径。
+    fn resolve_path(&self, path: KnownPath) -> Result<String, HostApiError> {
+        let resolved = match path {
+            KnownPath::AppDataDir => Self::resolve_app_data_dir()?,
+            KnownPath::AppLogDir => Self::resolve_app_data_dir()?.join("logs"),
+            KnownPath::AppIconCacheDir => Self::resolve_app_data_dir()?.join("icons"),
+            KnownPath::AppConfigDir => Self::resolve_app_data_dir()?.join("config"),
+            system => Self::resolve_system_path(system)?,
+        };
+        Ok(path_to_string(&resolved))
+    }
+}
+
+/// 路径转字符串（非 UTF-8 字节按替换字符处理）。
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 标准模式契约：应用数据目录为 $HOME/.ZeroLaunch-rs，子目录基于其展开。
+    #[test]
+    #[cfg(not(feature = "portable"))]
+    fn standard_mode_resolves_app_data_to_home() {
+        let resolver = LinuxPathResolver::new();
+        let home = xdg::home_dir().expect("测试环境应有 Home 目录");
+        let app_data = resolver
+            .resolve_path(KnownPath::AppDataDir)
+            .expect("标准模式解析应用数据目录应成功");
+        assert_eq!(app_data, path_to_string(&home.join(APP_DATA_DIR_NAME)));
+        let config = resolver
+            .resolve_path(KnownPath::AppConfigDir)
+            .expect("标准模式解析配置目录应成功");
+        assert_eq!(
+            config,
+            path_to_string(&home.join(APP_DATA_DIR_NAME).join("config"))
+        );
+    }
+
+    /// 用户"开始菜单"映射到 XDG 数据目录下的 applications/。
+    #[test]
+    fn user_start_menu_is_xdg_applications_dir() {
+        let resolver = LinuxPathResolver::new();
+        let expected = xdg::data_home()
+            .expect("测试环境应有数据目录")
+            .join("applications");
+        assert_eq!(
+            resolver.resolve_path(KnownPath::UserStartMenu).unwrap(),
+            path_to_string(&expected)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+use zerolaunch_plugin_api::host::{HostApiError, OpenTarget};
+use zerolaunch_plugin_api::services::shell::{
+    capture_command_output, CommandOutput, ShellExecutor,
+};
+
+use crate::xdg;
+
+/// 以分离方式启动子进程：独立进程组、标准流重定向到 /dev/null，
+/// 避免被启动程序随启动器退出或向启动器终端输出。
+/// 子进程由后台线程回收，防止退出后残留僵尸进程。
+fn spawn_detached(program: &str, args: &[&str]) -> std::io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()?;
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// 使用桌面环境默认程序打开目标：优先 `xdg-open`，不可用时回退 `gio open`。
+fn open_with_default(target: &str) -> Result<(), HostApiError> {
+    let mut last_error = "未找到 xdg-open 或 gio".to_string();
+    let openers: [(&str, &[&str]); 2] = [("xdg-open", &[]), ("gio", &["open"])];
+    for (program, prefix) in openers {
+        if xdg::find_in_path(program).is_none() {
+            continue;
+        }
+        let mut args = prefix.to_vec();
+        args.push(target);
+        match spawn_detached(program, &args) {
+            Ok(()) => {
+                debug!("已请求 {} 打开: {}", program, target);
+                return Ok(());
+            }
+            Err(e) => {
+                warn!("{} 启动失败: {:?}，尝试下一种方式", program, e);
+                last_error = format!("{} 启动失败: {}", program, e);
+            }
+        }
+    }
+    Err(HostApiError::ShellOperationFailed {
+        target: target.to_string(),
+        reason: last_error,
+    })
+}
+
+/// Linux 平台 Shell 执行器实现（freedesktop：xdg-open / gio / pkexec）。
+pub struct LinuxShellExecutor;
+
+impl Default for LinuxShellExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxShellExecutor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ShellExecutor for LinuxShellExecutor {
+    /// 使用系统默认方式打开目标（文件/网址/文件夹统一交给 xdg-open）。
+    async fn shell_open(&self, target: &OpenTarget) -> Result<(), HostApiError> {
+        match target {
+            OpenTarget::File(path) | OpenTarget::Url(path) | OpenTarget::Folder(path) => {
+                open_with_default(path)
+            }
+        }
+    }
+
+    /// 打开目标文件所在的文件夹。
+    /// 如果目标是目录则直接打开，否则打开其父目录（xdg-open 无"选中文件"语义）。
+    async fn shell_open_folder(&self, path: &str) -> Result<(), HostApiError> {
+        let target_path = Path::new(path);
+        let folder_to_open = if target_path.is_dir() {
+            target_path
+        } else {
+            target_path.parent().unwrap_or(target_path)
+        };
+
+        if !folder_to_open.exists() {
+            return Err(HostApiError::ShellOperationFailed {
+                target: path.to_string(),
+                reason: format!("目标文件夹不存在: {}", folder_to_open.display()),
+            });
+        }
+
+        open_with_default(&folder_to_open.to_string_lossy())
+    }
+
+    /// 以管理员权限启动程序：通过 polkit 的 pkexec 弹出认证对话框。
+    async fn shell_execute_elevation(&self, path: &str) -> Result<(), HostApiError> {
+        if xdg::find_in_path("pkexec").is_none() {
+            return Err(HostApiError::ShellOperationFailed {
+                target: path.to_string(),
+                reason: "未找到 pkexec（polkit），无法提权启动".to_string(),
+            });
+        }
+        spawn_detached("pkexec", &[path]).map_err(|e| HostApiError::ShellOperationFailed {
+            target: path.to_string(),
+            reason: format!("pkexec 启动失败: {}", e),
+        })
+    }
+
+    /// 执行命令字符串（后台运行，不继承终端）。
+    /// 使用 `sh -c` 执行命令；空命令或纯空白命令返回错误。
+    async fn shell_execute_command(&self, command: &str) -> Result<(), HostApiError> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(HostApiError::ShellOperationFailed {
+                target: String::new(),
+                reason: "命令为空".to_string(),
+            });
+        }
+
+        match spawn_detached("sh", &["-c", command]) {
+            Ok(()) => {
+                debug!("命令启动成功: {}", command);
+                Ok(())
+            }
+            Err(e) => {
+                let msg = format!("命令启动失败: {:?}", e);
+                warn!("{}", msg);
+                Err(HostApiError::ShellOperationFailed {
+                    target: command.to_string(),
+                    reason: msg,
+                })
+            }
+        }
+    }
+
+    /// 执行命令字符串并捕获输出：同样经 `sh -c` 执行，超时后终止 shell 进程。
+    async fn shell_capture_command(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<CommandOutput, HostApiError> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(HostApiError::ShellOperationFailed {
+                target: String::new(),
+                reason: "命令为空".to_string(),
+            });
+        }
+
+        let mut process = tokio::process::Command::new("sh");
+        process.args(["-c", command]);
+        let output = capture_command_output(process, timeout)
+            .await
+            .map_err(|e| {
+                let msg = format!("命令启动失败: {:?}", e);
+                warn!("{}", msg);
+                HostApiError::ShellOperationFailed {
+                    target: command.to_string(),
+                    reason: msg,
+                }
+            })?;
+        debug!(
+            "命令执行结束: {} (exit={:?}, timed_out={})",
+            command, output.exit_code, output.timed_out
+        );
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 空命令与纯空白命令被拒绝；正常命令分离启动后立即返回。
+    #[tokio::test]
+    async fn execute_command_rejects_blank_and_spawns_detached() {
+        let shell = LinuxShellExecutor::new();
+        assert!(shell.shell_execute_command("   ").await.is_err());
+        assert!(shell.shell_execute_command("true").await.is_ok());
+    }
+
+    /// 捕获命令的标准输出、标准错误与退出码。
+    #[tokio::test]
+    async fn capture_command_collects_output_and_exit_code() {
+        let shell = LinuxShellExecutor::new();
+        let output = shell
+            .shell_capture_command("echo out; echo err >&2; exit 3", Duration::from_secs(5))
+            .await
+            .expect("命令应能执行");
+        assert_eq!(output.stdout.trim(), "out");
+        assert_eq!(output.stderr.trim(), "err");
+        assert_eq!(output.exit_code, Some(3));
+        assert!(!output.timed_out);
+    }
+
+    /// 超时的命令被终止并标记 timed_out。
+    #[tokio::test]
+    async fn capture_command_times_out() {
+        let shell = LinuxShellExecutor::new();
+        let output = shell
+            .shell_capture_command("sleep 5", Duration::from_millis(200))
+            .await
+            .expect("命令应能启动");
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, None);
+    }
+
+    /// 目标文件夹不存在时报错，不调用 xdg-open。
+    #[tokio::test]
+    async fn open_folder_requires_existing_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing").join("file.txt");
+        let err = LinuxShellExecutor::new()
+            .shell_open_folder(&missing.to_string_lossy())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HostApiError::ShellOperationFailed { .. }));
+    }
+
+    /// 缺少 pkexec 时提权启动直接报错，不回退为普通启动。
+    #[tokio::test]
+    async fn elevation_without_pkexec_fails() {
+        if xdg::find_in_path("pkexec").is_some() {
+            return;
+        }
+        let err = LinuxShellExecutor::new()
+            .shell_execute_elevation("/usr/bin/true")
+            .await
+            .unwrap_err();
+        match err {
+            HostApiError::ShellOperationFailed { reason, .. } => assert!(reason.contains("pkexec")),
+            other => panic!("应为 ShellOperationFailed: {:?}", other),
+        }
+    }
+}
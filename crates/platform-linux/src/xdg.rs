@@ -0,0 +1,165 @@
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// `XDG_DATA_DIRS` 未设置时的规范默认值。
+const DEFAULT_DATA_DIRS: &str = "/usr/local/share:/usr/share";
+
+/// 用户主目录（`$HOME`，回退 dirs::home_dir）。
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::home_dir)
+}
+
+/// 读取绝对路径型 XDG 环境变量；未设置、为空或为相对路径时使用回退值。
+fn absolute_env_dir(var: &str, fallback: impl FnOnce() -> Option<PathBuf>) -> Option<PathBuf> {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(fallback)
+}
+
+/// 用户数据目录（`$XDG_DATA_HOME`，默认 `~/.local/share`）。
+pub(crate) fn data_home() -> Option<PathBuf> {
+    absolute_env_dir("XDG_DATA_HOME", || {
+        home_dir().map(|h| h.join(".local").join("share"))
+    })
+}
+
+/// 用户配置目录（`$XDG_CONFIG_HOME`，默认 `~/.config`）。
+pub(crate) fn config_home() -> Option<PathBuf> {
+    absolute_env_dir("XDG_CONFIG_HOME", || home_dir().map(|h| h.join(".config")))
+}
+
+/// 系统数据目录列表（`$XDG_DATA_DIRS`，默认 `/usr/local/share:/usr/share`）。
+pub(crate) fn system_data_dirs() -> Vec<PathBuf> {
+    let raw = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DATA_DIRS.to_string());
+    raw.split(':')
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .collect()
+}
+
+/// 按优先级排列的全部数据目录：用户数据目录在前，系统目录在后（去重）。
+pub(crate) fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = data_home().into_iter().collect();
+    for dir in system_data_dirs() {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// 按优先级排列的 `applications/` 目录（桌面条目所在位置）。
+pub(crate) fn application_dirs() -> Vec<PathBuf> {
+    data_dirs()
+        .into_iter()
+        .map(|d| d.join("applications"))
+        .collect()
+}
+
+/// 当前桌面环境列表（`$XDG_CURRENT_DESKTOP` 按冒号拆分）。
+pub(crate) fn current_desktops() -> Vec<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .map(|v| {
+            v.split(':')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 消息 locale（`LC_ALL` → `LC_MESSAGES` → `LANG`），用于本地化桌面条目键。
+pub(crate) fn message_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|v| !v.is_empty() && v != "C" && v != "POSIX")
+}
+
+/// 读取 XDG 用户目录（`user-dirs.dirs` 中的 `XDG_<NAME>_DIR`）。
+/// 参数：name - 目录名（如 `DESKTOP`）。
+/// 返回：配置存在时返回展开 `$HOME` 后的路径，否则 None。
+pub(crate) fn user_dir(name: &str) -> Option<PathBuf> {
+    let home = home_dir()?;
+    let content = fs::read_to_string(config_home()?.join("user-dirs.dirs")).ok()?;
+    parse_user_dir(&content, name, &home)
+}
+
+/// 解析 `user-dirs.dirs` 文本中的指定目录。
+/// 值形如 `"$HOME/Desktop"` 或绝对路径；规范规定 `$HOME/` 为唯一允许的变量。
+fn parse_user_dir(content: &str, name: &str, home: &Path) -> Option<PathBuf> {
+    let key = format!("XDG_{}_DIR", name);
+    content.lines().find_map(|line| {
+        let (k, v) = line.trim().split_once('=')?;
+        if k.trim() != key {
+            return None;
+        }
+        let value = v.trim().trim_matches('"');
+        if let Some(rest) = value.strip_prefix("$HOME") {
+            let rest = rest.trim_start_matches('/');
+            Some(if rest.is_empty() {
+                home.to_path_buf()
+            } else {
+                home.join(rest)
+            })
+        } else if value.starts_with('/') {
+            Some(PathBuf::from(value))
+        } else {
+            None
+        }
+    })
+}
+
+/// 在 `$PATH` 中查找可执行文件；参数为路径时直接检查该文件。
+/// 参数：program - 程序名或路径。
+/// 返回：找到时返回完整路径。
+pub(crate) fn find_in_path(program: &str) -> Option<PathBuf> {
+    let is_executable = |p: &Path| {
+        fs::metadata(p)
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    };
+    if program.contains('/') {
+        let path = PathBuf::from(program);
+        return is_executable(&path).then_some(path);
+    }
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .map(|dir| dir.join(program))
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `$HOME` 前缀展开为主目录，绝对路径原样返回，缺失键返回 None。
+    #[test]
+    fn parses_user_dirs_file() {
+        let content = "# comment\nXDG_DESKTOP_DIR=\"$HOME/桌面\"\nXDG_MUSIC_DIR=\"/data/music\"\nXDG_PUBLICSHARE_DIR=\"$HOME/\"\n";
+        let home = Path::new("/home/alice");
+        assert_eq!(
+            parse_user_dir(content, "DESKTOP", home),
+            Some(PathBuf::from("/home/alice/桌面"))
+        );
+        assert_eq!(
+            parse_user_dir(content, "MUSIC", home),
+            Some(PathBuf::from("/data/music"))
+        );
+        assert_eq!(
+            parse_user_dir(content, "PUBLICSHARE", home),
+            Some(PathBuf::from("/home/alice"))
+        );
+        assert_eq!(parse_user_dir(content, "VIDEOS", home), None);
+    }
+}
@@ -1,13 +1,57 @@
+use crate::window::process_name_by_id;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+use windows::Win32::System::DataExchange::{
+    GetClipboardOwner, GetClipboardSequenceNumber, IsClipboardFormatAvailable,
+    RegisterClipboardFormatW,
+};
+use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+use windows_core::HSTRING;
 use zerolaunch_plugin_api::host::HostApiError;
-use zerolaunch_plugin_api::services::clipboard::ClipboardManager;
+use zerolaunch_plugin_api::services::clipboard::{
+    ClipboardCallback, ClipboardEvent, ClipboardManager,
+};
+
+/// 剪贴板序列号轮询间隔。
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// 剪贴板被其他进程占用导致读取失败时，同一序列号最多重试的次数。
+const MAX_READ_RETRIES: u32 = 3;
+/// 约定的「请勿记录」剪贴板格式：密码管理器等写入敏感内容时附带，监听方应跳过。
+const EXCLUDE_FORMATS: [&str; 2] = [
+    "ExcludeClipboardContentFromMonitorProcessing",
+    "Clipboard Viewer Ignore",
+];
 
 /// Windows 剪贴板管理器实现。
-/// 基于 arboard 写入系统剪贴板。
-pub struct WindowsClipboardManager;
+/// 基于 arboard 读写系统剪贴板；变化通知由后台线程轮询
+/// `GetClipboardSequenceNumber` 实现（无需消息窗口），首个回调注册时启动、回调清空后退出。
+pub struct WindowsClipboardManager {
+    /// 已注册的变化回调
+    callbacks: Arc<DashMap<String, ClipboardCallback>>,
+    /// 轮询线程是否在运行
+    watching: Arc<AtomicBool>,
+}
 
 impl WindowsClipboardManager {
     pub fn new() -> Self {
-        Self
+        Self {
+            callbacks: Arc::new(DashMap::new()),
+            watching: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 启动轮询线程（已在运行时不重复启动）。
+    fn ensure_watching(&self) {
+        if self.watching.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let callbacks = self.callbacks.clone();
+        let watching = self.watching.clone();
+        thread::spawn(move || watch_clipboard(callbacks, watching));
     }
 }
 
@@ -19,11 +63,7 @@ impl Default for WindowsClipboardManager {
 
 impl ClipboardManager for WindowsClipboardManager {
     fn set_text(&self, text: &str) -> Result<(), HostApiError> {
-        let mut clipboard =
-            arboard::Clipboard::new().map_err(|e| HostApiError::ExecutionFailed {
-                service: "clipboard".to_string(),
-                reason: format!("剪贴板初始化失败: {}", e),
-            })?;
+        let mut clipboard = open_clipboard()?;
         clipboard
             .set_text(text)
             .map_err(|e| HostApiError::ExecutionFailed {
@@ -31,4 +71,114 @@ impl ClipboardManager for WindowsClipboardManager {
                 reason: format!("剪贴板写入失败: {}", e),
             })
     }
+
+    fn get_text(&self) -> Result<Option<String>, HostApiError> {
+        let mut clipboard = open_clipboard()?;
+        match clipboard.get_text() {
+            Ok(text) => Ok(Some(text)),
+            Err(arboard::Error::ContentNotAvailable) => Ok(None),
+            Err(e) => Err(HostApiError::ExecutionFailed {
+                service: "clipboard".to_string(),
+                reason: format!("剪贴板读取失败: {}", e),
+            }),
+        }
+    }
+
+    fn register_change_callback(&self, id: &str, callback: ClipboardCallback) {
+        self.callbacks.insert(id.to_string(), callback);
+        self.ensure_watching();
+    }
+
+    fn unregister_change_callback(&self, id: &str) {
+        // 轮询线程在下一轮发现回调为空后自行退出
+        self.callbacks.remove(id);
+    }
+}
+
+/// 打开系统剪贴板。
+fn open_clipboard() -> Result<arboard::Clipboard, HostApiError> {
+    arboard::Clipboard::new().map_err(|e| HostApiError::ExecutionFailed {
+        service: "clipboard".to_string(),
+        reason: format!("剪贴板初始化失败: {}", e),
+    })
+}
+
+/// 轮询线程主体：序列号变化时读取文本并分发给全部回调。
+/// 启动时的剪贴板内容不视为变化；回调清空后退出。
+fn watch_clipboard(callbacks: Arc<DashMap<String, ClipboardCallback>>, watching: Arc<AtomicBool>) {
+    debug!("剪贴板监听线程启动");
+    let exclude_formats: Vec<u32> = EXCLUDE_FORMATS
+        .iter()
+        .map(|name| unsafe { RegisterClipboardFormatW(&HSTRING::from(*name)) })
+        .filter(|format| *format != 0)
+        .collect();
+    let mut last_seq = unsafe { GetClipboardSequenceNumber() };
+    let mut retries = 0;
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        if callbacks.is_empty() {
+            watching.store(false, Ordering::Release);
+            // 退出前复查：停止标记写入前可能有新回调注册（其 ensure_watching 未启动新线程）
+            if callbacks.is_empty() || watching.swap(true, Ordering::AcqRel) {
+                break;
+            }
+        }
+
+        let seq = unsafe { GetClipboardSequenceNumber() };
+        if seq == last_seq {
+            continue;
+        }
+        if is_excluded(&exclude_formats) {
+            last_seq = seq;
+            continue;
+        }
+        let text = match arboard::Clipboard::new().and_then(|mut c| c.get_text()) {
+            Ok(text) => text,
+            Err(arboard::Error::ContentNotAvailable) => {
+                // 非文本内容（图片、文件等）不产生事件
+                last_seq = seq;
+                continue;
+            }
+            Err(e) => {
+                // 剪贴板可能正被写入方占用：保留旧序列号，下一轮重试
+                retries += 1;
+                if retries >= MAX_READ_RETRIES {
+                    warn!("读取剪贴板变化失败，跳过本次变化: {}", e);
+                    last_seq = seq;
+                    retries = 0;
+                }
+                continue;
+            }
+        };
+        last_seq = seq;
+        retries = 0;
+
+        let event = ClipboardEvent {
+            text,
+            source_app: clipboard_owner_process(),
+        };
+        for entry in callbacks.iter() {
+            (entry.value())(event.clone());
+        }
+    }
+    debug!("剪贴板监听线程退出");
+}
+
+/// 当前剪贴板内容是否带有「请勿记录」格式标记。
+fn is_excluded(exclude_formats: &[u32]) -> bool {
+    exclude_formats
+        .iter()
+        .any(|format| unsafe { IsClipboardFormatAvailable(*format).is_ok() })
+}
+
+/// 剪贴板所有者窗口所属进程的可执行文件名（如 "notepad.exe"）。
+fn clipboard_owner_process() -> Option<String> {
+    let owner = unsafe { GetClipboardOwner() }.ok()?;
+    let mut pid: u32 = 0;
+    unsafe { GetWindowThreadProcessId(owner, Some(&mut pid)) };
+    if pid == 0 {
+        return None;
+    }
+    process_name_by_id(pid)
 }
@@ -2,13 +2,16 @@ use crate::utils::get_u16_vec;
 use async_trait::async_trait;
 use std::os::windows::process::CommandExt;
 use std::path::Path;
+use std::time::Duration;
 use tracing::{debug, warn};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::{GetLastError, ERROR_CANCELLED};
 use windows::Win32::UI::Shell::{ShellExecuteExW, ShellExecuteW, SHELLEXECUTEINFOW};
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 use zerolaunch_plugin_api::host::{HostApiError, OpenTarget};
-use zerolaunch_plugin_api::services::shell::ShellExecutor;
+use zerolaunch_plugin_api::services::shell::{
+    capture_command_output, CommandOutput, ShellExecutor,
+};
 
 /// 使用 ShellExecuteW 以系统默认方式打开指定路径
 fn shell_execute_open(path: &str) -> Result<(), HostApiError> {
@@ -192,4 +195,44 @@ impl ShellExecutor for WindowsShellExecutor {
             }
         }
     }
+
+    /// 执行命令字符串并捕获输出。
+    /// 与 shell_execute_command 同样经 cmd /D /S /C 执行，仅使用 CREATE_NO_WINDOW
+    /// （不分离控制台，否则无法继承输出管道）；输出按 UTF-8 有损解码。
+    async fn shell_capture_command(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<CommandOutput, HostApiError> {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(HostApiError::ShellOperationFailed {
+                target: String::new(),
+                reason: "命令为空".to_string(),
+            });
+        }
+
+        let mut process = tokio::process::Command::new("cmd");
+        process
+            .args(["/D", "/S", "/C"])
+            .raw_arg(command)
+            .creation_flags(CREATE_NO_WINDOW);
+        let output = capture_command_output(process, timeout)
+            .await
+            .map_err(|e| {
+                let msg = format!("命令启动失败: {:?}", e);
+                warn!("{}", msg);
+                HostApiError::ShellOperationFailed {
+                    target: command.to_string(),
+                    reason: msg,
+                }
+            })?;
+        debug!(
+            "命令执行结束: {} (exit={:?}, timed_out={})",
+            command, output.exit_code, output.timed_out
+        );
+        Ok(output)
+    }
 }
@@ -61,6 +61,23 @@ impl WindowManager for WindowsWindowManager {
             Ok(false)
         }
     }
+
+    /// 查询窗口所属进程的进程名。
+    /// 通过 GetWindowThreadProcessId 取得进程 ID，再在进程快照中查找其 exe 名。
+    async fn process_name_of_window(&self, hwnd: &str) -> Result<Option<String>, HostApiError> {
+        let raw = match hwnd.trim().parse::<isize>() {
+            Ok(raw) if raw != 0 => raw,
+            _ => return Ok(None),
+        };
+        let mut pid = 0u32;
+        unsafe {
+            GetWindowThreadProcessId(HWND(raw as *mut std::ffi::c_void), Some(&mut pid));
+        }
+        if pid == 0 {
+            return Ok(None);
+        }
+        Ok(process_name_by_id(pid).map(|name| name.to_lowercase()))
+    }
 }
 
 /// 获取窗口标题
@@ -157,6 +174,47 @@ fn get_window_by_process_name(process_name: &str) -> Option<HWND> {
     result
 }
 
+/// 通过进程快照查找指定进程 ID 的可执行文件名（如 "notepad.exe"，保留原始大小写，
+/// 是否规范化由调用方决定）。
+pub(crate) fn process_name_by_id(process_id: u32) -> Option<String> {
+    let mut result: Option<String> = None;
+
+    unsafe {
+        let snapshot = match CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("创建进程快照失败: {:?}", e);
+                return None;
+            }
+        };
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                if entry.th32ProcessID == process_id {
+                    result = Some(
+                        String::from_utf16_lossy(&entry.szExeFile)
+                            .trim_end_matches('\0')
+                            .to_string(),
+                    );
+                    break;
+                }
+
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let _ = CloseHandle(snapshot);
+    }
+    result
+}
+
 /// 根据窗口标题的部分内容查找窗口句柄（不区分大小写）。
 fn get_window_by_title(title_part: &str) -> Option<HWND> {
     let windows = get_all_windows();
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 主条目分组名（freedesktop Desktop Entry 规范）。
+const DESKTOP_ENTRY_GROUP: &str = "Desktop Entry";
+/// 附加动作分组名前缀（`[Desktop Action <id>]`）。
+const DESKTOP_ACTION_PREFIX: &str = "Desktop Action ";
+
+/// freedesktop `.desktop` 文件解析结果。
+/// 平台无关的纯文本解析：Linux 平台层（应用枚举/图标）与桌面条目数据源共用。
+#[derive(Debug, Clone, Default)]
+pub struct DesktopEntry {
+    /// 分组名 → (键 → 原始值)。键保留本地化后缀（如 `Name[zh_CN]`），值未反转义。
+    groups: HashMap<String, HashMap<String, String>>,
+}
+
+/// `[Desktop Action <id>]` 分组描述的附加动作。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopAction {
+    /// 动作标识（`Actions=` 列表中的 id）
+    pub id: String,
+    /// 本地化后的动作名称
+    pub name: String,
+    /// 动作图标（图标名或绝对路径）
+    pub icon: Option<String>,
+    /// 动作的 Exec 命令行（未展开字段码）
+    pub exec: Option<String>,
+}
+
+impl DesktopEntry {
+    /// 解析 `.desktop` 文本内容；无法识别的行直接忽略（规范要求宽松解析）。
+    /// 参数：content - 文件文本内容。
+    /// 返回：解析后的 DesktopEntry。
+    pub fn parse(content: &str) -> Self {
+        let mut groups: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut current: Option<String> = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current = Some(name.to_string());
+                groups.entry(name.to_string()).or_default();
+                continue;
+            }
+            let (Some(group), Some((key, value))) = (&current, line.split_once('=')) else {
+                continue;
+            };
+            // 同一分组内重复键以首次出现为准
+            groups
+                .entry(group.clone())
+                .or_default()
+                .entry(key.trim().to_string())
+                .or_insert_with(|| value.trim().to_string());
+        }
+        Self { groups }
+    }
+
+    /// 读取并解析 `.desktop` 文件。
+    /// 参数：path - 文件路径。
+    /// 返回：读取失败或缺少 `[Desktop Entry]` 分组时返回 None。
+    pub fn load<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        let entry = Self::parse(&content);
+        entry
+            .groups
+            .contains_key(DESKTOP_ENTRY_GROUP)
+            .then_some(entry)
+    }
+
+    /// 读取主分组中的字符串值（已反转义）。
+    /// 参数：key - 键名（不含本地化后缀）。
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.group_value(DESKTOP_ENTRY_GROUP, key, None)
+    }
+
+    /// 读取主分组中的本地化字符串值，按规范回退链匹配 locale。
+    /// 参数：key - 键名；locale - POSIX locale（如 `zh_CN.UTF-8`），None 时取未本地化值。
+    pub fn get_localized(&self, key: &str, locale: Option<&str>) -> Option<String> {
+        self.group_value(DESKTOP_ENTRY_GROUP, key, locale)
+    }
+
+    /// 读取主分组中的布尔值，缺省为 false。
+    pub fn get_bool(&self, key: &str) -> bool {
+        self.get(key).is_some_and(|v| v == "true")
+    }
+
+    /// 读取主分组中的分号分隔列表（支持本地化与 `\;` 转义）。
+    pub fn get_list(&self, key: &str, locale: Option<&str>) -> Vec<String> {
+        let Some(raw) = self.raw_localized(DESKTOP_ENTRY_GROUP, key, locale) else {
+            return Vec::new();
+        };
+        split_list(raw)
+    }
+
+    /// 条目类型（`Type=`），应用条目为 `Application`。
+    pub fn entry_type(&self) -> Option<String> {
+        self.get("Type")
+    }
+
+    /// 本地化名称（`Name=`）。
+    pub fn name(&self, locale: Option<&str>) -> Option<String> {
+        self.get_localized("Name", locale)
+    }
+
+    /// 本地化通用名称（`GenericName=`，如"网页浏览器"）。
+    pub fn generic_name(&self, locale: Option<&str>) -> Option<String> {
+        self.get_localized("GenericName", locale)
+    }
+
+    /// 本地化关键词（`Keywords=`）。
+    pub fn keywords(&self, locale: Option<&str>) -> Vec<String> {
+        self.get_list("Keywords", locale)
+    }
+
+    /// 图标（`Icon=`）：图标主题中的名称或绝对路径。
+    pub fn icon(&self) -> Option<String> {
+        self.get("Icon")
+    }
+
+    /// 启动命令行（`Exec=`），字段码未展开。
+    pub fn exec(&self) -> Option<String> {
+        self.get("Exec")
+    }
+
+    /// 可执行探测（`TryExec=`）：所指程序不存在时条目应被忽略。
+    pub fn try_exec(&self) -> Option<String> {
+        self.get("TryExec")
+    }
+
+    /// 工作目录（`Path=`）。
+    pub fn working_dir(&self) -> Option<String> {
+        self.get("Path")
+    }
+
+    /// 是否要求在终端中运行（`Terminal=`）。
+    pub fn terminal(&self) -> bool {
+        self.get_bool("Terminal")
+    }
+
+    /// 是否不在菜单中显示（`NoDisplay=`）。
+    pub fn no_display(&self) -> bool {
+        self.get_bool("NoDisplay")
+    }
+
+    /// 是否视为已删除（`Hidden=`）。
+    pub fn hidden(&self) -> bool {
+        self.get_bool("Hidden")
+    }
+
+    /// 判断条目在给定桌面环境下是否应显示（`OnlyShowIn=` / `NotShowIn=`）。
+    /// 参数：desktops - 当前桌面环境列表（`XDG_CURRENT_DESKTOP` 按冒号拆分）。
+    pub fn is_shown_in(&self, desktops: &[String]) -> bool {
+        let matches = |list: Vec<String>| {
+            list.iter()
+                .any(|d| desktops.iter().any(|cur| cur.eq_ignore_ascii_case(d)))
+        };
+        let only = self.get_list("OnlyShowIn", None);
+        if !only.is_empty() && !matches(only) {
+            return false;
+        }
+        !matches(self.get_list("NotShowIn", None))
+    }
+
+    /// 附加动作列表，按 `Actions=` 声明顺序返回；缺少分组或名称的动作被跳过。
+    /// 参数：locale - 动作名称的本地化 locale。
+    pub fn actions(&self, locale: Option<&str>) -> Vec<DesktopAction> {
+        self.get_list("Actions", None)
+            .into_iter()
+            .filter_map(|id| {
+                let group = format!("{}{}", DESKTOP_ACTION_PREFIX, id);
+                let name = self.group_value(&group, "Name", locale)?;
+                Some(DesktopAction {
+                    icon: self.group_value(&group, "Icon", None),
+                    exec: self.group_value(&group, "Exec", None),
+                    id,
+                    name,
+                })
+            })
+            .collect()
+    }
+
+    /// 读取指定分组的值并反转义。
+    fn group_value(&self, group: &str, key: &str, locale: Option<&str>) -> Option<String> {
+        self.raw_localized(group, key, locale).map(unescape_value)
+    }
+
+    /// 按规范回退链查找本地化原始值：
+    /// `lang_COUNTRY@MODIFIER` → `lang_COUNTRY` → `lang@MODIFIER` → `lang` → 无后缀。
+    fn raw_localized(&self, group: &str, key: &str, locale: Option<&str>) -> Option<&str> {
+        let entries = self.groups.get(group)?;
+        if let Some(locale) = locale {
+            for candidate in locale_candidates(locale) {
+                if let Some(v) = entries.get(&format!("{}[{}]", key, candidate)) {
+                    return Some(v);
+                }
+            }
+        }
+        entries.get(key).map(String::as_str)
+    }
+}
+
+/// 由 POSIX locale 生成按优先级排列的匹配候选（去掉编码部分）。
+fn locale_candidates(locale: &str) -> Vec<String> {
+    let (base, modifier) = match locale.split_once('@') {
+        Some((b, m)) => (b, Some(m)),
+        None => (locale, None),
+    };
+    let base = base.split('.').next().unwrap_or(base);
+    let (lang, country) = match base.split_once('_') {
+        Some((l, c)) => (l, Some(c)),
+        None => (base, None),
+    };
+    let mut candidates = Vec::new();
+    if let (Some(c), Some(m)) = (country, modifier) {
+        candidates.push(format!("{}_{}@{}", lang, c, m));
+    }
+    if let Some(c) = country {
+        candidates.push(format!("{}_{}", lang, c));
+    }
+    if let Some(m) = modifier {
+        candidates.push(format!("{}@{}", lang, m));
+    }
+    if !lang.is_empty() {
+        candidates.push(lang.to_string());
+    }
+    candidates
+}
+
+/// 反转义字符串值：`\s` `\n` `\t` `\r` `\\`。
+fn unescape_value(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 拆分分号列表：`\;` 视为字面分号，空项忽略，各项再做常规反转义。
+fn split_list(raw: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(';') => current.push(';'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    items.push(current);
+    items
+        .into_iter()
+        .map(|s| unescape_value(s.trim()))
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// 按 Exec 键规则拆分命令行：双引号内保留空白，`\"` `\`` `\$` `\\` 为转义字符。
+/// 字段码（`%f` 等）原样保留，由调用方展开或剔除。
+/// 参数：exec - 已做常规反转义的 Exec 值。
+/// 返回：参数列表（首项为程序）。
+pub fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_token {
+                    args.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            _ => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        args.push(current);
+    }
+    args
+}
+
+/// 计算桌面文件 ID：相对 `applications/` 目录的路径，以 `-` 连接子目录。
+/// 参数：applications_dir - `<data_dir>/applications` 目录；path - `.desktop` 文件路径。
+/// 返回：如 `kde4-kate.desktop`；path 不在该目录下时返回 None。
+pub fn desktop_file_id(applications_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(applications_dir).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREFOX: &str = r#"
+# comment
+[Desktop Entry]
+Type=Application
+Name=Firefox
+Name[zh_CN]=火狐浏览器
+GenericName=Web Browser
+GenericName[de]=Webbrowser
+Keywords=web;browser\;like;internet;
+Exec=firefox %u
+Icon=firefox
+OnlyShowIn=GNOME;KDE;
+Actions=new-window;missing;private;
+
+[Desktop Action new-window]
+Name=New Window
+Name[zh_CN]=新建窗口
+Exec=firefox --new-window %u
+
+[Desktop Action private]
+Name=Private\sWindow
+Exec=firefox --private-window
+"#;
+
+    /// 本地化键按 lang_COUNTRY → lang → 无后缀回退。
+    #[test]
+    fn localized_values_follow_fallback_chain() {
+        let entry = DesktopEntry::parse(FIREFOX);
+        assert_eq!(
+            entry.name(Some("zh_CN.UTF-8")).as_deref(),
+            Some("火狐浏览器")
+        );
+        assert_eq!(entry.name(Some("fr_FR")).as_deref(), Some("Firefox"));
+        assert_eq!(
+            entry.generic_name(Some("de_AT@euro")).as_deref(),
+            Some("Webbrowser")
+        );
+        assert_eq!(entry.name(None).as_deref(), Some("Firefox"));
+    }
+
+    /// 列表拆分保留转义分号并忽略空项；动作按声明顺序且跳过缺失分组。
+    #[test]
+    fn lists_and_actions_are_parsed() {
+        let entry = DesktopEntry::parse(FIREFOX);
+        assert_eq!(
+            entry.keywords(None),
+            vec!["web", "browser;like", "internet"]
+        );
+        let actions = entry.actions(Some("zh_CN"));
+        let ids: Vec<&str> = actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["new-window", "private"]);
+        assert_eq!(actions[0].name, "新建窗口");
+        assert_eq!(actions[1].name, "Private Window");
+        assert_eq!(actions[1].exec.as_deref(), Some("firefox --private-window"));
+    }
+
+    /// OnlyShowIn / NotShowIn 按当前桌面环境过滤（大小写不敏感）。
+    #[test]
+    fn show_in_filters_by_desktop() {
+        let entry = DesktopEntry::parse(FIREFOX);
+        assert!(entry.is_shown_in(&["gnome".to_string()]));
+        assert!(!entry.is_shown_in(&["XFCE".to_string()]));
+
+        let not_show = DesktopEntry::parse("[Desktop Entry]\nNotShowIn=KDE;\n");
+        assert!(not_show.is_shown_in(&[]));
+        assert!(!not_show.is_shown_in(&["KDE".to_string()]));
+    }
+
+    /// Exec 拆分：引号内保留空白与转义字符，字段码原样保留。
+    #[test]
+    fn split_exec_handles_quotes() {
+        assert_eq!(
+            split_exec(r#""/opt/My App/bin/app" --title "say \"hi\"" %F"#),
+            vec!["/opt/My App/bin/app", "--title", "say \"hi\"", "%F"]
+        );
+        assert_eq!(split_exec("  gedit   %U "), vec!["gedit", "%U"]);
+        assert_eq!(split_exec(r#"app """#), vec!["app", ""]);
+    }
+
+    /// 桌面文件 ID 以 `-` 连接子目录。
+    #[test]
+    fn desktop_file_id_joins_subdirs() {
+        let dir = Path::new("/usr/share/applications");
+        assert_eq!(
+            desktop_file_id(dir, &dir.join("kde4").join("kate.desktop")).as_deref(),
+            Some("kde4-kate.desktop")
+        );
+        assert_eq!(desktop_file_id(dir, Path::new("/tmp/x.desktop")), None);
+    }
+}
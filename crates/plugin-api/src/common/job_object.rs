@@ -0,0 +1,101 @@
+//! Windows 作业对象（Job Object）封装 —— 进程树的统一限额与整体终止。
+//!
+//! 进程加入作业后，其后派生的子进程自动归属同一作业：终止作业即终止整棵进程树，
+//! 作业上的内存 / CPU 时间限额同样作用于每个成员进程。带限额的作业以
+//! `JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE` 创建，`JobObject` 被 drop（最后一个句柄关闭）时
+//! 作业内仍在运行的进程一并终止，不会留下孤儿进程；`grouping` 作业只用于整体终止，
+//! drop 时不影响成员进程。
+
+use std::ffi::c_void;
+use std::time::Duration;
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+    SetInformationJobObject, TerminateJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+    JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    JOB_OBJECT_LIMIT_PROCESS_TIME,
+};
+
+/// 作业对象句柄的所有者；drop 时关闭句柄（kill-on-close 作业同时终止剩余进程）。
+pub struct JobObject {
+    /// 作业对象句柄
+    handle: HANDLE,
+}
+
+// SAFETY: 作业对象句柄是内核对象引用，可在线程间传递与共享；
+// 本类型只调用线程安全的 Win32 作业 API。
+unsafe impl Send for JobObject {}
+unsafe impl Sync for JobObject {}
+
+impl JobObject {
+    /// 创建带限额的作业对象（kill-on-close）。
+    /// 参数：max_process_memory - 每个成员进程的提交内存上限（字节），超出后分配失败；
+    /// max_process_cpu_time - 每个成员进程的用户态 CPU 时间上限，超出后进程被终止。
+    pub fn new(
+        max_process_memory: Option<u64>,
+        max_process_cpu_time: Option<Duration>,
+    ) -> std::io::Result<Self> {
+        let job = Self::create()?;
+        let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        if let Some(bytes) = max_process_memory {
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+            info.ProcessMemoryLimit = usize::try_from(bytes).unwrap_or(usize::MAX);
+        }
+        if let Some(cpu_time) = max_process_cpu_time {
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+            // 单位为 100 纳秒
+            info.BasicLimitInformation.PerProcessUserTimeLimit =
+                i64::try_from(cpu_time.as_nanos() / 100).unwrap_or(i64::MAX);
+        }
+        // SAFETY: info 在调用期间有效，长度与 JobObjectExtendedLimitInformation 类别匹配。
+        unsafe {
+            SetInformationJobObject(
+                job.handle,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )
+        }
+        .map_err(std::io::Error::other)?;
+        Ok(job)
+    }
+
+    /// 创建不带限额、不随句柄关闭终止成员的作业，仅用于 `terminate` 整棵进程树。
+    pub fn grouping() -> std::io::Result<Self> {
+        Self::create()
+    }
+
+    /// 创建匿名作业对象（无限额）。
+    fn create() -> std::io::Result<Self> {
+        // SAFETY: 无安全属性、无名称的 CreateJobObjectW 调用，返回的句柄由 Self 独占。
+        let handle =
+            unsafe { CreateJobObjectW(None, PCWSTR::null()) }.map_err(std::io::Error::other)?;
+        Ok(Self { handle })
+    }
+
+    /// 将已启动的子进程加入作业；进程已退出（无句柄）时返回错误。
+    /// 进程在加入前派生的子进程不属于作业，调用方应在 spawn 后立即调用。
+    pub fn assign(&self, child: &tokio::process::Child) -> std::io::Result<()> {
+        let raw = child.raw_handle().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "process has already exited")
+        })?;
+        // SAFETY: raw 为 tokio 持有的有效进程句柄，调用期间 child 未被释放。
+        unsafe { AssignProcessToJobObject(self.handle, HANDLE(raw)) }.map_err(std::io::Error::other)
+    }
+
+    /// 立即终止作业内的全部进程（整棵进程树）。
+    pub fn terminate(&self) -> std::io::Result<()> {
+        // SAFETY: handle 在 self 生命周期内有效。
+        unsafe { TerminateJobObject(self.handle, 1) }.map_err(std::io::Error::other)
+    }
+}
+
+impl Drop for JobObject {
+    fn drop(&mut self) {
+        // SAFETY: handle 由 CreateJobObjectW 创建且只在此处关闭一次。
+        let _ = unsafe { CloseHandle(self.handle) };
+    }
+}
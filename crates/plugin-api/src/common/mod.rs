@@ -1,5 +1,11 @@
+pub mod desktop_entry;
 pub mod dir_utils;
 pub mod image_utils;
+#[cfg(windows)]
+pub mod job_object;
 
+pub use desktop_entry::{desktop_file_id, split_exec, DesktopAction, DesktopEntry};
 pub use dir_utils::DirUtils;
 pub use image_utils::{ImageUtils, ImageUtilsError};
+#[cfg(windows)]
+pub use job_object::JobObject;
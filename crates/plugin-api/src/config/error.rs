@@ -18,4 +18,10 @@ pub enum ConfigError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Remote sync failed: {0}")]
+    SyncFailed(String),
+
+    #[error("Secret store error: {0}")]
+    SecretStore(String),
 }
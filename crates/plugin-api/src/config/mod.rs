@@ -3,6 +3,7 @@ pub mod component_core;
 pub mod component_type;
 pub mod configurable;
 pub mod error;
+pub mod secret;
 pub mod setting_def;
 
 pub use action::{
@@ -12,7 +13,8 @@ pub use component_core::ComponentCore;
 pub use component_type::ComponentType;
 pub use configurable::Configurable;
 pub use error::ConfigError;
+pub use secret::{SecretString, SECRET_REDACTED};
 pub use setting_def::{
     CommitPolicy, FieldUiMetadata, PathMode, PrimitiveType, SchemaKind, SchemaNode,
-    SettingDefinition, SettingsContribution, WidgetHint, SETTINGS_SCHEMA_VERSION,
+    SettingDefinition, SettingIssue, SettingsContribution, WidgetHint, SETTINGS_SCHEMA_VERSION,
 };
@@ -0,0 +1,68 @@
+//! 机密值的脱敏约定。
+//!
+//! `SchemaKind::Secret` 字段的值由宿主机密存储保管，不进入持久化配置；
+//! 对外（CLI HTTP API）返回时以 `SECRET_REDACTED` 占位，写回占位值表示「保持不变」。
+//! 组件内部持有机密值时使用 `SecretString`，避免经 `Debug` 输出到日志。
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 机密字段脱敏后的占位值。
+pub const SECRET_REDACTED: &str = "********";
+
+/// 机密字符串 —— 序列化为普通字符串，`Debug` 输出时脱敏。
+///
+/// 只在真正需要明文的位置（发起认证请求等）调用 `expose()`。
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// 包装一个机密值
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// 取得明文
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否为空（未设置）
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    /// 空值原样输出，便于排查「未设置」；非空值一律输出占位符
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("\"\"")
+        } else {
+            f.write_str(SECRET_REDACTED)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Debug 输出不泄露明文，序列化仍为原始字符串。
+    #[test]
+    fn debug_output_is_redacted() {
+        let secret = SecretString::new("hunter2");
+        assert_eq!(format!("{:?}", secret), SECRET_REDACTED);
+        assert_eq!(format!("{:?}", SecretString::default()), "\"\"");
+        assert_eq!(serde_json::to_value(&secret).unwrap(), "hunter2");
+        let parsed: SecretString = serde_json::from_str("\"hunter2\"").unwrap();
+        assert_eq!(parsed.expose(), "hunter2");
+    }
+}
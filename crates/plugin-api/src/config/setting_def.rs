@@ -1,4 +1,4 @@
-use crate::config::{DetailActionDef, FieldAction};
+use crate::config::{DetailActionDef, FieldAction, SECRET_REDACTED};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -90,6 +90,9 @@ pub enum WidgetHint {
     /// 快捷键录制器 — 聚焦后按下组合键进行录制，值格式为修饰键 + 主键（如 "Alt+Space"）。
     #[serde(rename = "hotkey")]
     Hotkey,
+    /// 机密输入框 — 掩码显示，可临时切换为明文查看。
+    #[serde(rename = "secret")]
+    Secret,
     /// 普通列表编辑器（默认的数组 UI）。
     #[serde(rename = "list")]
     List,
@@ -166,6 +169,13 @@ pub enum SchemaKind {
     /// 布尔类型。
     #[serde(rename = "boolean")]
     Boolean,
+    /// 机密字符串（密码、API Key 等）。
+    ///
+    /// 值以字符串传递，但不写入持久化配置文件（也就不参与远程同步），
+    /// 由宿主的机密存储保管；CLI HTTP API 返回时替换为 `SECRET_REDACTED`。
+    /// 仅允许出现在顶层字段。
+    #[serde(rename = "secret")]
+    Secret,
     /// 数组类型。
     #[serde(rename = "array")]
     Array {
@@ -254,6 +264,14 @@ impl SchemaNode {
             default: None,
         }
     }
+
+    /// 创建一个机密字符串 schema 节点。
+    pub fn secret() -> Self {
+        Self {
+            kind: SchemaKind::Secret,
+            default: None,
+        }
+    }
 }
 
 /// 字段 UI 元数据 — 描述前端如何渲染和展示一个配置字段。
@@ -337,6 +355,17 @@ pub struct SettingsContribution {
     pub commit_policy: CommitPolicy,
 }
 
+/// 单条配置校验问题，供 CLI HTTP API 等外部调用方按字段定位错误。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingIssue {
+    /// 问题所在顶层字段的 JSON Pointer（`/<key>`）；settings 根本身不合法时为空串。
+    #[serde(rename = "pointer")]
+    pub pointer: String,
+    /// 问题描述（含出错的完整路径）。
+    #[serde(rename = "message")]
+    pub message: String,
+}
+
 impl SettingsContribution {
     /// 从 `SettingDefinition` 列表构建 `SettingsContribution`。
     ///
@@ -385,22 +414,64 @@ impl SettingsContribution {
         Value::Object(values)
     }
 
+    /// 收集 schema 中声明为 `Secret` 的顶层字段 key。
+    pub fn secret_keys(&self) -> Vec<String> {
+        self.properties
+            .iter()
+            .filter(|(_, node)| matches!(node.kind, SchemaKind::Secret))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// 将 settings 中非空的机密字段替换为 `SECRET_REDACTED`，空值保持原样
+    /// （调用方据此仍可判断「是否已设置」）。
+    pub fn redact_secrets(&self, settings: &mut Value) {
+        let Some(object) = settings.as_object_mut() else {
+            return;
+        };
+        for key in self.secret_keys() {
+            if let Some(value) = object.get_mut(&key) {
+                if value.as_str().is_some_and(|text| !text.is_empty()) {
+                    *value = Value::String(SECRET_REDACTED.to_string());
+                }
+            }
+        }
+    }
+
     /// 校验一个 settings JSON 值是否符合本 schema。
     pub fn validate_values(&self, value: &Value) -> Result<(), String> {
-        let object = value
-            .as_object()
-            .ok_or_else(|| "settings root must be an object".to_string())?;
+        match self.validation_issues(value).into_iter().next() {
+            Some(issue) => Err(issue.message),
+            None => Ok(()),
+        }
+    }
+
+    /// 校验 settings 并收集全部问题（每个顶层字段至多一条），顺序与 `validate_values` 一致。
+    pub fn validation_issues(&self, value: &Value) -> Vec<SettingIssue> {
+        let Some(object) = value.as_object() else {
+            return vec![SettingIssue {
+                pointer: String::new(),
+                message: "settings root must be an object".to_string(),
+            }];
+        };
+        let mut issues = Vec::new();
         for key in object.keys() {
             if !self.properties.contains_key(key) {
-                return Err(format!("unknown setting key: {}", key));
+                issues.push(SettingIssue {
+                    pointer: format!("/{}", escape_pointer(key)),
+                    message: format!("unknown setting key: {}", key),
+                });
             }
         }
         for (key, node) in &self.properties {
             if let Some(value) = object.get(key) {
-                validate_node(node, value, &format!("/{}", escape_pointer(key)), 0)?;
+                let pointer = format!("/{}", escape_pointer(key));
+                if let Err(message) = validate_node(node, value, &pointer, 0) {
+                    issues.push(SettingIssue { pointer, message });
+                }
             }
         }
-        Ok(())
+        issues
     }
 }
 
@@ -548,6 +619,11 @@ fn validate_schema_node(
             }
         }
         SchemaKind::Boolean => {}
+        SchemaKind::Secret => {
+            if depth > 0 {
+                return Err("secret fields are only supported at top level".to_string());
+            }
+        }
     }
     Ok(())
 }
@@ -654,6 +730,11 @@ fn validate_node(
                 return Err(format!("{} must be a boolean", pointer));
             }
         }
+        SchemaKind::Secret => {
+            if !value.is_string() {
+                return Err(format!("{} must be a string", pointer));
+            }
+        }
         // item_widget 是 UI 渲染提示，不影响值校验，此处无需关注。
         SchemaKind::Array {
             items,
@@ -927,6 +1008,93 @@ mod tests {
             .is_ok());
     }
 
+    /// 验证机密字段可被收集并脱敏，空值保持原样以表达「未设置」。
+    #[test]
+    fn redacts_secret_fields() {
+        let contribution = SettingsContribution::from_entries(vec![
+            SettingDefinition {
+                key: "token".into(),
+                schema: SchemaNode::secret(),
+                ui: test_ui("/token"),
+            },
+            SettingDefinition {
+                key: "password".into(),
+                schema: SchemaNode::secret(),
+                ui: test_ui("/password"),
+            },
+            SettingDefinition {
+                key: "account".into(),
+                schema: SchemaNode::string(),
+                ui: test_ui("/account"),
+            },
+        ])
+        .unwrap();
+        assert_eq!(contribution.secret_keys(), ["password", "token"]);
+        assert!(contribution
+            .validate_values(&serde_json::json!({"token": 42}))
+            .is_err());
+
+        let mut settings = serde_json::json!({"token": "t0k3n", "password": "", "account": "me"});
+        contribution.redact_secrets(&mut settings);
+        assert_eq!(
+            settings,
+            serde_json::json!({"token": SECRET_REDACTED, "password": "", "account": "me"})
+        );
+    }
+
+    /// 验证 validation_issues 收集全部出错字段，validate_values 报告其中第一条。
+    #[test]
+    fn collects_all_validation_issues() {
+        let contribution = SettingsContribution::from_entries(vec![
+            SettingDefinition {
+                key: "name".into(),
+                schema: SchemaNode::string(),
+                ui: test_ui("/name"),
+            },
+            SettingDefinition {
+                key: "enabled".into(),
+                schema: SchemaNode::boolean(),
+                ui: test_ui("/enabled"),
+            },
+        ])
+        .unwrap();
+        let settings = serde_json::json!({"name": 1, "enabled": "yes", "extra": true});
+        let issues = contribution.validation_issues(&settings);
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(pointers, ["/extra", "/enabled", "/name"]);
+        assert_eq!(
+            contribution.validate_values(&settings),
+            Err(issues[0].message.clone())
+        );
+        assert!(contribution
+            .validation_issues(&serde_json::json!({"name": "ok"}))
+            .is_empty());
+        assert_eq!(
+            contribution.validation_issues(&serde_json::json!([]))[0].pointer,
+            ""
+        );
+    }
+
+    /// 验证机密字段只允许出现在顶层，嵌套在 object 内时被拒绝。
+    #[test]
+    fn rejects_nested_secret_field() {
+        let mut properties = BTreeMap::new();
+        properties.insert("token".into(), SchemaNode::secret());
+        let result = SettingsContribution::from_entries(vec![SettingDefinition {
+            key: "profile".into(),
+            schema: SchemaNode {
+                kind: SchemaKind::Object {
+                    properties,
+                    ui: vec![test_ui("/token")],
+                    required: BTreeSet::new(),
+                },
+                default: None,
+            },
+            ui: test_ui("/profile"),
+        }]);
+        assert!(result.is_err());
+    }
+
     /// 验证嵌套 object 缺失 UI metadata 时被拒绝而不是静默降级。
     #[test]
     fn rejects_nested_object_without_ui_metadata() {
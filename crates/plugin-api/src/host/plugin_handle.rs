@@ -1,7 +1,8 @@
 use crate::host::{CacheLevel, HostApiError, OpenTarget};
 use crate::platform::capabilities::PlatformCapabilities;
+use crate::plugin::{PluginContext, QueryChannel, QueryResponse, QueryUpdate, QueryUpdateCallback};
 use crate::services::app::{AppEnumerator, AppInfo, AppLauncher};
-use crate::services::clipboard::ClipboardManager;
+use crate::services::clipboard::{ClipboardCallback, ClipboardManager};
 use crate::services::focus_monitor::{FocusCallback, FocusMonitor};
 use crate::services::hotkey::types::{HotkeyCallback, HotkeyEventFilter};
 use crate::services::hotkey::HotkeyManager;
@@ -10,12 +11,12 @@ use crate::services::icon::icon_extractor::IconExtractor;
 use crate::services::installation_monitor::types::InstallationCallback;
 use crate::services::installation_monitor::InstallationMonitor;
 use crate::services::parameter::resolver::ParameterResolver;
-use crate::services::parameter::types::ParameterSnapshot;
+use crate::services::parameter::types::{ParameterSnapshot, UserParameter};
 use crate::services::path::path_resolver::{KnownPath, PathResolver};
 use crate::services::resource::AppResourceService;
 use crate::services::shell::lnk_resolver::LnkResolver;
 use crate::services::shell::resource_loader::ResourceLoader;
-use crate::services::shell::ShellExecutor;
+use crate::services::shell::{CommandOutput, ShellExecutor};
 use crate::services::storage::storage_service::StorageService;
 use crate::services::timer::types::{TimerCallback, TimerId, TimerMode};
 use crate::services::timer::TimerManager;
@@ -23,6 +24,7 @@ use crate::services::window::WindowManager;
 use crate::services::IconRequest;
 use parking_lot::RwLock;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::sdk_config::PluginSdkConfig;
 /// 插件服务句柄，绑定插件身份与配置。
@@ -67,6 +69,8 @@ pub struct PluginHandle {
     focus_monitor: Arc<dyn FocusMonitor>,
     /// 剪贴板管理器，由 HostApi 注入
     clipboard_manager: Arc<dyn ClipboardManager>,
+    /// 增量查询结果接收端，由 HostApi 共享（宿主启动后注入，未注入时推送被丢弃）
+    query_update_sink: Arc<RwLock<Option<QueryUpdateCallback>>>,
 }
 
 impl PluginHandle {
@@ -93,6 +97,7 @@ impl PluginHandle {
         installation_monitor: Arc<dyn InstallationMonitor>,
         focus_monitor: Arc<dyn FocusMonitor>,
         clipboard_manager: Arc<dyn ClipboardManager>,
+        query_update_sink: Arc<RwLock<Option<QueryUpdateCallback>>>,
     ) -> Self {
         Self {
             plugin_id,
@@ -115,6 +120,7 @@ impl PluginHandle {
             installation_monitor,
             focus_monitor,
             clipboard_manager,
+            query_update_sink,
         }
     }
 
@@ -222,6 +228,19 @@ impl PluginHandle {
         self.shell_executor.shell_execute_command(command).await
     }
 
+    /// 执行命令字符串并等待结束，捕获输出与退出码（无窗口）。
+    /// 参数：command - 要执行的命令字符串；timeout - 最长运行时间。
+    /// 返回：成功返回 CommandOutput，无法启动时返回 HostApiError。
+    pub async fn shell_capture_command(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<CommandOutput, HostApiError> {
+        self.shell_executor
+            .shell_capture_command(command, timeout)
+            .await
+    }
+
     // ===== 窗口服务 =====
 
     /// 根据进程名（如 "chrome.exe"）激活已存在的窗口。
@@ -243,6 +262,13 @@ impl PluginHandle {
         self.window_manager.activate_window_by_title(title).await
     }
 
+    /// 查询窗口所属进程的进程名（如 `{hwnd}` 快照中的前台窗口）。
+    /// 参数：hwnd - 十进制窗口句柄字符串。
+    /// 返回：小写进程名，句柄无效返回 Ok(None)，失败返回 HostApiError。
+    pub async fn process_name_of_window(&self, hwnd: &str) -> Result<Option<String>, HostApiError> {
+        self.window_manager.process_name_of_window(hwnd).await
+    }
+
     // ===== 路径服务 =====
 
     /// 根据已知路径类型解析实际文件系统路径。
@@ -261,6 +287,12 @@ impl PluginHandle {
         self.clipboard_manager.set_text(text)
     }
 
+    /// 读取系统剪贴板中的文本。
+    /// 返回：剪贴板为空或不含文本时返回 Ok(None)，失败返回 HostApiError。
+    pub fn get_clipboard_text(&self) -> Result<Option<String>, HostApiError> {
+        self.clipboard_manager.get_text()
+    }
+
     // ===== 应用服务 =====
 
     /// 枚举当前平台已安装的应用。
@@ -369,6 +401,14 @@ impl PluginHandle {
         self.parameter_resolver.has_system_parameters(template)
     }
 
+    /// 列出模板需要用户输入的参数
+    ///
+    /// 参数：template - 模板字符串
+    /// 返回：按输入顺序排列的参数名称与默认值
+    pub fn user_parameters(&self, template: &str) -> Vec<UserParameter> {
+        self.parameter_resolver.user_parameters(template)
+    }
+
     // ===== 定时器服务 =====
 
     /// 创建一个一次性定时器，在指定延迟后触发回调。
@@ -518,6 +558,26 @@ impl PluginHandle {
             })
     }
 
+    // ===== 增量查询结果 =====
+
+    /// 为当前查询推送增量结果（List 条目或 CustomPanel 更新），供慢速数据源渐进展示。
+    /// 参数：ctx - 查询上下文（携带查询版本号）；response - 增量结果。
+    /// 特性：查询已被更新的查询取代或非 GUI 通道（CLI 无增量展示）时直接丢弃；
+    /// 宿主侧仍会按版本号二次校验。
+    pub fn push_query_update(&self, ctx: &PluginContext, response: QueryResponse) {
+        if !ctx.is_query_current() || ctx.query_channel != QueryChannel::Ui {
+            return;
+        }
+        let Some(sink) = self.query_update_sink.read().clone() else {
+            return;
+        };
+        sink(QueryUpdate {
+            plugin_id: self.plugin_id.clone(),
+            revision: ctx.query_revision(),
+            response,
+        });
+    }
+
     // ===== 推送式回调注册 =====
 
     /// 为回调 ID 添加插件前缀，避免不同插件间的 ID 冲突。
@@ -573,6 +633,21 @@ impl PluginHandle {
         let prefixed = self.prefix_callback_id(id);
         self.focus_monitor.unregister_callback(&prefixed);
     }
+
+    /// 注册剪贴板文本变化回调。
+    /// 参数：id - 回调标识（自动前缀化为 "{plugin_id}:{id}"）；callback - 回调函数。
+    pub fn register_clipboard_callback(&self, id: &str, callback: ClipboardCallback) {
+        let prefixed = self.prefix_callback_id(id);
+        self.clipboard_manager
+            .register_change_callback(&prefixed, callback);
+    }
+
+    /// 注销剪贴板文本变化回调。
+    /// 参数：id - 回调标识（自动前缀化为 "{plugin_id}:{id}"）。
+    pub fn unregister_clipboard_callback(&self, id: &str) {
+        let prefixed = self.prefix_callback_id(id);
+        self.clipboard_manager.unregister_change_callback(&prefixed);
+    }
 }
 
 /// 构建资源存储路径，校验文件名防止路径遍历攻击。
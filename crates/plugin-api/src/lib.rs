@@ -10,6 +10,6 @@ pub mod services;
 pub use host::*;
 pub use platform::*;
 
-#[cfg(feature = "mock")]
+#[cfg(any(test, feature = "mock"))]
 pub mod mock;
 pub use plugin::*;
@@ -2,6 +2,8 @@
 //!
 //! Enable with `features = ["mock"]` in dev-dependencies.
 //! All stubs return `Ok(Default::default())` or empty collections by default.
+//! `webdav_server` (with the `webdav` feature) drives the bun WebDAV fixture
+//! for integration tests.
 
 #[cfg(feature = "mock")]
 mod stubs;
@@ -11,3 +13,6 @@ pub use stubs::*;
 
 #[cfg(feature = "mock")]
 pub mod helpers;
+
+#[cfg(feature = "webdav")]
+pub mod webdav_server;
@@ -3,6 +3,7 @@
 
 use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use parking_lot::Mutex;
@@ -13,7 +14,7 @@ use crate::services::app::app_enumerator::AppEnumerator;
 use crate::services::app::app_launcher::AppLauncher;
 use crate::services::app::AppInfo;
 use crate::services::autostart::AutoStartManager;
-use crate::services::clipboard::ClipboardManager;
+use crate::services::clipboard::{ClipboardCallback, ClipboardManager};
 use crate::services::focus_monitor::{FocusCallback, FocusMonitor};
 use crate::services::hotkey::types::{Hotkey, HotkeyCallback, HotkeyEventFilter};
 use crate::services::hotkey::HotkeyManager;
@@ -21,11 +22,11 @@ use crate::services::icon::icon_extractor::IconExtractor;
 use crate::services::installation_monitor::{InstallationCallback, InstallationMonitor};
 use crate::services::parameter::provider::{ProviderError, SystemParameterProvider};
 use crate::services::parameter::resolver::ParameterResolver;
-use crate::services::parameter::types::{ParameterError, ParameterSnapshot};
+use crate::services::parameter::types::{ParameterError, ParameterSnapshot, UserParameter};
 use crate::services::path::{KnownPath, PathResolver};
 use crate::services::shell::lnk_resolver::LnkResolver;
 use crate::services::shell::resource_loader::ResourceLoader;
-use crate::services::shell::ShellExecutor;
+use crate::services::shell::{CommandOutput, ShellExecutor};
 use crate::services::storage::storage_error::StorageError;
 use crate::services::storage::storage_service::StorageService;
 use crate::services::window::window_positioner::{
@@ -81,6 +82,16 @@ impl ShellExecutor for StubShellExecutor {
     async fn shell_execute_command(&self, _cmd: &str) -> Result<(), HostApiError> {
         Ok(())
     }
+    async fn shell_capture_command(
+        &self,
+        _cmd: &str,
+        _timeout: Duration,
+    ) -> Result<CommandOutput, HostApiError> {
+        Ok(CommandOutput {
+            exit_code: Some(0),
+            ..CommandOutput::default()
+        })
+    }
 }
 
 // ===== Clipboard Manager =====
@@ -91,6 +102,11 @@ impl ClipboardManager for StubClipboardManager {
     fn set_text(&self, _text: &str) -> Result<(), HostApiError> {
         Ok(())
     }
+    fn get_text(&self) -> Result<Option<String>, HostApiError> {
+        Ok(None)
+    }
+    fn register_change_callback(&self, _id: &str, _callback: ClipboardCallback) {}
+    fn unregister_change_callback(&self, _id: &str) {}
 }
 
 // ===== Window Manager =====
@@ -105,6 +121,9 @@ impl WindowManager for StubWindowManager {
     async fn activate_window_by_title(&self, _title: &str) -> Result<bool, HostApiError> {
         Ok(false)
     }
+    async fn process_name_of_window(&self, _hwnd: &str) -> Result<Option<String>, HostApiError> {
+        Ok(None)
+    }
 }
 
 // ===== Window Positioner =====
@@ -197,6 +216,9 @@ impl ParameterResolver for StubParameterResolver {
     fn has_system_parameters(&self, _template: &str) -> bool {
         false
     }
+    fn user_parameters(&self, _template: &str) -> Vec<UserParameter> {
+        Vec::new()
+    }
 }
 
 // ===== System Parameter Provider =====
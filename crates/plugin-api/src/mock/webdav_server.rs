@@ -0,0 +1,108 @@
+//! bun WebDAV 测试服务器（tests/fixtures/webdav_server.ts）的启动与回收。
+//!
+//! 供本 crate 与宿主的 WebDAV 集成测试共用：固定监听 18080 端口，
+//! 经 `/__shutdown` 端点优雅退出，兜底 kill 进程树。
+
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use std::time::Duration;
+
+use crate::services::storage::webdav_storage::WebDAVConfig;
+
+/// 测试服务器地址（bun fixture 固定监听端口）。
+pub const SERVER_URL: &str = "http://127.0.0.1:18080";
+
+/// 运行中的 bun WebDAV 测试服务器；drop 时若进程仍在则强制结束。
+pub struct WebDavTestServer {
+    /// bun 子进程
+    child: Child,
+}
+
+impl WebDavTestServer {
+    /// 启动服务器并轮询 OPTIONS 直至就绪（单次请求 2 秒超时，总计约 5 秒后 panic）。
+    pub async fn start() -> Self {
+        let script =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/webdav_server.ts");
+        let child = std::process::Command::new("bun")
+            .arg("run")
+            .arg(&script)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("启动 WebDAV 测试服务器失败（需要 bun 可执行文件）");
+        let server = Self { child };
+
+        let client = client();
+        for _ in 0..25 {
+            if client
+                .request(reqwest::Method::OPTIONS, SERVER_URL)
+                .send()
+                .await
+                .map(|r| r.status().is_success())
+                .unwrap_or(false)
+            {
+                return server;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        panic!("WebDAV 测试服务器启动超时（5 秒）");
+    }
+
+    /// 指向本服务器的 WebDAV 配置（测试账号，目标目录为 `destination_dir`）。
+    pub fn config(&self, destination_dir: &str) -> WebDAVConfig {
+        WebDAVConfig {
+            host_url: SERVER_URL.into(),
+            account: "test".into(),
+            password: "test".into(),
+            destination_dir: destination_dir.into(),
+        }
+    }
+
+    /// 经 shutdown 端点优雅关闭，轮询等待退出（最多 5 秒），兜底杀进程树。
+    pub async fn shutdown(mut self) {
+        let _ = client()
+            .post(format!("{SERVER_URL}/__shutdown"))
+            .send()
+            .await;
+        for _ in 0..50 {
+            if self.child.try_wait().ok().flatten().is_some() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        self.kill();
+    }
+
+    /// 强制结束服务器进程（Windows 下连同 bun 进程树）。
+    fn kill(&mut self) {
+        if self.child.try_wait().ok().flatten().is_some() {
+            return;
+        }
+        #[cfg(windows)]
+        {
+            let _ = std::process::Command::new("taskkill")
+                .args(["/PID", &self.child.id().to_string(), "/T", "/F"])
+                .output();
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for WebDavTestServer {
+    fn drop(&mut self) {
+        // 断言 panic 或超时未走到 shutdown 时，避免残留进程占用端口
+        self.kill();
+    }
+}
+
+/// 单次请求 2 秒超时的 reqwest 客户端。
+fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .expect("创建 reqwest 客户端失败")
+}
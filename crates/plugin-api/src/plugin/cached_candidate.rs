@@ -1,7 +1,7 @@
 use crate::plugin::types::{CandidateId, ExecutionTarget, SearchCandidate};
 use dashmap::DashMap;
 use dashmap::Entry;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// 保存当前已经缓存的候选数据
 pub struct CachedCandidateData {
@@ -10,8 +10,8 @@ pub struct CachedCandidateData {
     /// 候选ID到索引的映射
     index: DashMap<CandidateId, usize>,
     /// 该方法用于去重，只有没有重复的候选项才会被添加到candidates中，重复的候选项会被丢弃掉
-    /// 判断的依据：执行目标
-    cached_targets: HashSet<ExecutionTarget>,
+    /// 判断的依据：执行目标；同时记录执行目标对应的候选ID，供增量刷新沿用旧ID
+    cached_targets: HashMap<ExecutionTarget, CandidateId>,
     /// 该方法用于去重，只有显示名不重复的候选项才会被添加到candidates中
     /// 判断的依据：候选项显示名（忽略大小写）
     cached_display_names: HashSet<String>,
@@ -30,25 +30,73 @@ impl CachedCandidateData {
         Self {
             candidates: Vec::new(),
             index: DashMap::new(),
-            cached_targets: HashSet::new(),
+            cached_targets: HashMap::new(),
             cached_display_names: HashSet::new(),
             next_candidate_id: 1,
         }
     }
 
+    /// 创建一个空的缓存，候选ID从指定值开始分配
+    /// 增量刷新时传入旧缓存的 next_candidate_id，保证新分配的ID不与沿用的旧ID冲突
+    pub fn with_next_candidate_id(next_candidate_id: CandidateId) -> Self {
+        Self {
+            next_candidate_id: next_candidate_id.max(1),
+            ..Self::new()
+        }
+    }
+
     /// 添加一个候选人
-    pub fn add_candidate(&mut self, mut candidate: SearchCandidate) {
+    pub fn add_candidate(&mut self, candidate: SearchCandidate) {
+        let candidate_id = self.next_candidate_id;
+        if self.insert_candidate(candidate, candidate_id) {
+            self.next_candidate_id += 1;
+        }
+    }
+
+    /// 以指定ID添加一个候选人（增量刷新沿用旧ID）
+    /// 返回是否添加成功：执行目标、显示名或ID重复时丢弃
+    pub fn add_candidate_with_id(
+        &mut self,
+        candidate: SearchCandidate,
+        candidate_id: CandidateId,
+    ) -> bool {
+        if self.index.contains_key(&candidate_id) {
+            return false;
+        }
+        if !self.insert_candidate(candidate, candidate_id) {
+            return false;
+        }
+        self.next_candidate_id = self.next_candidate_id.max(candidate_id + 1);
+        true
+    }
+
+    /// 去重后写入候选人，返回是否写入
+    fn insert_candidate(
+        &mut self,
+        mut candidate: SearchCandidate,
+        candidate_id: CandidateId,
+    ) -> bool {
         if self.has_target(&candidate.target) || self.has_display_name(&candidate.name) {
-            return;
+            return false;
         }
-        let candidate_id = self.next_candidate_id;
         candidate.id = candidate_id;
-        self.cached_targets.insert(candidate.target.clone());
+        self.cached_targets
+            .insert(candidate.target.clone(), candidate_id);
         self.cached_display_names
             .insert(candidate.name.to_lowercase());
         self.candidates.push(candidate);
         self.index.insert(candidate_id, self.candidates.len() - 1);
-        self.next_candidate_id += 1;
+        true
+    }
+
+    /// 根据执行目标查找候选ID
+    pub fn get_id_by_target(&self, target: &ExecutionTarget) -> Option<CandidateId> {
+        self.cached_targets.get(target).copied()
+    }
+
+    /// 下一个待分配的候选ID
+    pub fn next_candidate_id(&self) -> CandidateId {
+        self.next_candidate_id
     }
 
     /// 根据id获得指定的一个候选人
@@ -78,7 +126,7 @@ impl CachedCandidateData {
 
     /// 判断是否已经缓存了某个执行目标的候选项了
     fn has_target(&self, target: &ExecutionTarget) -> bool {
-        self.cached_targets.contains(target)
+        self.cached_targets.contains_key(target)
     }
 
     /// 判断是否已经缓存了某个显示名的候选项（忽略大小写）
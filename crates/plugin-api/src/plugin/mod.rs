@@ -5,9 +5,10 @@ pub mod types;
 pub use cached_candidate::CachedCandidateData;
 pub use plugin_trait::Plugin;
 pub use types::{
-    ActionExecutor, CandidateId, DataSource, ExecutionContext, ExecutionError, ExecutionTarget,
-    KeywordInjector, KeywordOptimizer, ListItem, PanelInteraction, PanelKeyAction, PanelKeyBinding,
-    PanelQueryTrigger, PluginContext, PluginError, PluginKind, PluginMetadata, PluginMode, Query,
-    QueryChannel, QueryResponse, QueryRevisionGate, RegistrationError, ResultAction, ScoreBooster,
-    ScoreDetail, ScoreDetailKind, ScoredCandidate, SearchCandidate, SearchEngine, TargetType,
+    ActionExecutor, CancellationToken, CandidateAction, CandidateId, DataSource, ExecutionContext,
+    ExecutionError, ExecutionTarget, KeywordInjector, KeywordOptimizer, ListItem, PanelInteraction,
+    PanelKeyAction, PanelKeyBinding, PanelQueryTrigger, PluginContext, PluginError, PluginKind,
+    PluginMetadata, PluginMode, Query, QueryChannel, QueryResponse, QueryRevisionGate, QueryUpdate,
+    QueryUpdateCallback, RegistrationError, ResultAction, ScoreBooster, ScoreDetail,
+    ScoreDetailKind, ScoredCandidate, SearchCandidate, SearchEngine, TargetType,
 };
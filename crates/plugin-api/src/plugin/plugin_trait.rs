@@ -1,7 +1,7 @@
 use crate::config::configurable::Configurable;
 use crate::host::plugin_handle::PluginHandle;
 use crate::plugin::types::{
-    PanelInteraction, PluginContext, PluginError, PluginMetadata, Query, QueryResponse,
+    ListItem, PanelInteraction, PluginContext, PluginError, PluginMetadata, Query, QueryResponse,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -27,6 +27,19 @@ pub trait Plugin: Configurable {
     async fn query(&self, ctx: &PluginContext, query: &Query)
         -> Result<QueryResponse, PluginError>;
 
+    /// 全局查询贡献：仅当 `metadata().global_query` 为 true 时，宿主在默认搜索
+    /// （未命中触发词）中对每次查询调用，返回的条目与检索结果按分数合并。
+    /// 宿主为每个贡献者设置单次查询时间预算，超时结果直接丢弃；
+    /// 条目被选中时经 `execute_action` 回调（payload.candidate_id 为此处返回的原始 id）。
+    /// 默认不贡献任何条目。
+    async fn global_query(
+        &self,
+        _ctx: &PluginContext,
+        _query: &Query,
+    ) -> Result<Vec<ListItem>, PluginError> {
+        Ok(Vec::new())
+    }
+
     async fn execute_action(
         &self,
         ctx: &PluginContext,
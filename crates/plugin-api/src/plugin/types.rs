@@ -1,11 +1,12 @@
 use crate::config::Configurable;
 use crate::plugin::cached_candidate::CachedCandidateData;
 use crate::services::icon_request::IconRequest;
-use crate::services::parameter::types::ParameterSnapshot;
+use crate::services::parameter::types::{ParameterSnapshot, UserParameter};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+pub use tokio_util::sync::CancellationToken;
 
 pub type CandidateId = u64;
 
@@ -146,6 +147,8 @@ pub struct SearchCandidate {
     #[serde(rename = "target")]
     pub target: ExecutionTarget,
     // 表示该候选项的关键词，即怎么可以确认用户想要启动这个候选项
+    // 数据源可预填额外的检索词（如桌面条目的 GenericName/Keywords），合并时与名称一起
+    // 经 KeywordOptimizer 展开；为空时仅由名称生成
     #[serde(rename = "keywords")]
     pub keywords: Vec<String>,
     // 固定的权重偏移，用于在计算分数时考虑该候选项的固定权重。由每个数据源来控制各自的权重
@@ -154,13 +157,28 @@ pub struct SearchCandidate {
     /// 触发关键词列表，用于行内模式的精确匹配
     #[serde(rename = "triggerKeywords")]
     pub trigger_keywords: Vec<String>,
+    /// 候选项自带的附加动作（追加在执行器按目标类型提供的动作之后）
+    #[serde(rename = "actions", default)]
+    pub actions: Vec<CandidateAction>,
+}
+
+/// 候选项自带的附加动作（如桌面条目的 `[Desktop Action]`）。
+/// 确认该动作时以其自身的执行目标替换候选项主目标，并交由该目标类型的默认动作执行。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateAction {
+    /// 展示在结果动作列表中的动作描述（ID 需与执行器动作 ID 区分，建议带数据源前缀）
+    #[serde(rename = "action")]
+    pub action: ResultAction,
+    /// 该动作的执行目标
+    #[serde(rename = "target")]
+    pub target: ExecutionTarget,
 }
 
 /// 分数明细的计入方式 —— 标识该项是加权加分还是乘法系数。
 ///
 /// 跨 IPC 序列化，由引擎/增强器构造，前端按 kind 渲染明细形态：
-/// 加法项显示 `score × weight = 乘积`，乘法项显示 `× 系数`，
-/// 避免把乘法系数误读为加分项导致总分无法核对。
+/// 加法项显示 `score × weight = 乘积`，乘法项显示 `× 系数`，说明项仅展示分值，
+/// 避免把乘法系数或说明项误读为加分项导致总分无法核对。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ScoreDetailKind {
     /// 加权加分项：该项的 score × weight 计入总分（默认）。
@@ -170,6 +188,9 @@ pub enum ScoreDetailKind {
     /// 乘法系数项：该项的 score 乘到当前累计分数上（如长度比率、溢出惩罚、抑制因子）。
     #[serde(rename = "multiply")]
     Multiply,
+    /// 说明项：不计入总分，仅用于解释（如多引擎融合时保留的各引擎原始明细）。
+    #[serde(rename = "info")]
+    Info,
 }
 
 // 这个是一个搜索候选项的详细分数
@@ -184,7 +205,7 @@ pub struct ScoreDetail {
     // 这个是什么分，以及这个分的来源
     #[serde(rename = "description")]
     pub description: String,
-    // 该项的计入方式：add = 加权加分，multiply = 乘法系数
+    // 该项的计入方式：add = 加权加分，multiply = 乘法系数，info = 仅说明不计分
     #[serde(rename = "kind", default)]
     pub kind: ScoreDetailKind,
 }
@@ -199,7 +220,7 @@ pub struct ScoredCandidate {
     #[serde(rename = "score")]
     pub score: f64,
     //表示该候选项得来的详细的分数：加法项按 sum(score × weight) 计入，
-    //乘法项按系数乘入（引擎先乘系数再加加法项，增强器仅产出加法项）
+    //乘法项按系数乘入（引擎先乘系数再加加法项，增强器仅产出加法项），说明项不计入
     #[serde(rename = "detailedScore")]
     pub detailed_score: Vec<ScoreDetail>,
 }
@@ -208,6 +229,10 @@ pub struct ScoredCandidate {
 #[async_trait]
 pub trait DataSource: Configurable {
     async fn fetch_candidates(&self) -> CachedCandidateData;
+    // 是否依赖已安装程序（开始菜单/应用列表）：为 true 时安装监控事件只重新采集这类数据源
+    fn tracks_installations(&self) -> bool {
+        false
+    }
 }
 
 // 表示对搜索的候选项的搜索关键字做优化的组件，通常是对搜索关键字进行扩展或者优化，以提高搜索的召回率
@@ -240,6 +265,9 @@ pub trait SearchEngine: Configurable {
         candidates: &CachedCandidateData,
         query: &str,
     ) -> Vec<ScoredCandidate>;
+    // 候选项缓存更新（或引擎加入搜索管道）时调用，供需要预建索引的引擎重建索引；
+    // 逐次线性扫描的引擎无需实现。索引须与传入 calculate_scores 的数据校验一致后再使用
+    fn index_candidates(&self, _candidates: &CachedCandidateData) {}
 }
 
 // 表示一个分数优化器，用于对搜索候选项的分数进行优化
@@ -250,6 +278,11 @@ pub trait ScoreBooster: Configurable {
     fn record(&self, candidate_id: CandidateId, data: &CachedCandidateData, query: &str);
     // 根据用户历史输入的查询与选择的候选项，优化当前查询所得到的所有候选项的分数
     fn boost(&self, candidates: &mut Vec<ScoredCandidate>, data: &CachedCandidateData, query: &str);
+    // 将尚未写盘的学习数据立即持久化，应用退出前调用；无持久化需求的增强器无需实现
+    fn persist(&self) {}
+    // 搜索栏唤醒时接收本次的系统参数快照（如 {hwnd} 前台窗口），用于感知查询上下文；
+    // 不依赖上下文的增强器无需实现
+    fn observe_wake(&self, _snapshot: &ParameterSnapshot) {}
 }
 
 /// 动作执行器 trait
@@ -336,6 +369,10 @@ pub struct PluginContext {
     /// 远端插件或直接构造的上下文为 None，此时视为始终为最新）。
     #[serde(skip)]
     pub query_revision_gate: Option<QueryRevisionGate>,
+    /// 当前查询的版本号（注入门控时同步写入；随 RPC 下发给远端插件，
+    /// 供其经 host/query.update 推送增量结果时回传）。无门控时为 0。
+    #[serde(default)]
+    pub query_revision: u64,
     /// 查询来源通道（宿主注入；远端插件经 RPC 反序列化时缺省视为 GUI 通道）。
     #[serde(default)]
     pub query_channel: QueryChannel,
@@ -343,6 +380,11 @@ pub struct PluginContext {
     /// 远端插件反序列化缺省为空串，可经 host/i18n.get_locale 主动查询。
     #[serde(default)]
     pub locale: String,
+    /// 请求取消令牌（#[serde(skip)] 不跨 RPC 传输）：远端插件的 SDK 在收到宿主
+    /// `$/cancelRequest`（查询被更新查询取代）时取消，插件可据此提前中止网络/磁盘工作。
+    /// 未被取消时 `cancelled()` 永不完成。
+    #[serde(skip)]
+    pub cancellation: CancellationToken,
 }
 
 impl PluginContext {
@@ -352,8 +394,10 @@ impl PluginContext {
             query_id: None,
             plugin_id: None,
             query_revision_gate: None,
+            query_revision: 0,
             query_channel: QueryChannel::Ui,
             locale: String::new(),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -367,6 +411,7 @@ impl PluginContext {
 
     /// 注入查询版本门控（宿主查询入口调用）。
     pub fn set_query_revision_gate(&mut self, gate: QueryRevisionGate) {
+        self.query_revision = gate.revision();
         self.query_revision_gate = Some(gate);
     }
 
@@ -377,11 +422,14 @@ impl PluginContext {
             .is_none_or(|g| g.is_current())
     }
 
-    /// 当前查询的版本号；无门控时为 0，仅供日志使用。
+    /// 当前请求是否已被宿主取消（远端插件收到 `$/cancelRequest` 后为 true）。
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// 当前查询的版本号；无门控且未经 RPC 下发时为 0。
     pub fn query_revision(&self) -> u64 {
-        self.query_revision_gate
-            .as_ref()
-            .map_or(0, |g| g.revision())
+        self.query_revision
     }
 }
 
@@ -540,9 +588,32 @@ pub enum QueryResponse {
         /// 该候选项要求的用户参数个数（前端据此校验输入完整性）。
         #[serde(rename = "userArgCount")]
         user_arg_count: usize,
+        /// 用户参数的名称与默认值（按输入顺序，长度等于 user_arg_count），供前端标注输入框。
+        #[serde(rename = "userArgs", default)]
+        user_args: Vec<UserParameter>,
     },
 }
 
+/// 插件推送的增量查询结果 —— 流式/渐进式结果通道的载荷。
+///
+/// 慢速数据源无需等到 `Plugin::query` 返回才展示：插件可在查询处理期间，
+/// 经 `PluginHandle::push_query_update`（内置插件）或 host/query.update 通知（远端插件）
+/// 推送 `List` 条目或 `CustomPanel` 更新。`query` 的返回值即最终结果：宿主按 `revision`
+/// 丢弃过期更新，查询返回后到达的更新同样丢弃，不会覆盖最终结果。
+#[derive(Debug, Clone)]
+pub struct QueryUpdate {
+    /// 推送更新的插件 ID（由宿主按句柄/连接身份填写，插件不可伪造）。
+    pub plugin_id: String,
+    /// 更新所属的查询版本号（取自 PluginContext::query_revision）。
+    pub revision: u64,
+    /// 增量结果：仅 List / CustomPanel 生效，覆盖该查询当前展示内容。
+    pub response: QueryResponse,
+}
+
+/// 增量查询结果回调类型。
+/// 由宿主注入（转发到 SessionDispatcher），插件推送更新时调用。
+pub type QueryUpdateCallback = Arc<dyn Fn(QueryUpdate) + Send + Sync>;
+
 /// 插件返回给宿主的搜索结果项。
 /// 服务于结果聚合、排序与 UI 渲染。
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -633,6 +704,10 @@ pub struct PluginMetadata {
     /// 缺省 Panel：旧插件无此字段时按 panel 处理（兼容旧热键插件行为）。
     #[serde(rename = "mode", default)]
     pub mode: PluginMode,
+    /// 是否参与全局查询：为 true 时宿主在默认搜索（无触发词）中调用 `global_query`，
+    /// 将插件贡献的 ListItem 与检索管线结果按分数合并。缺省 false（显式声明才生效）。
+    #[serde(rename = "globalQuery", default)]
+    pub global_query: bool,
 }
 
 /// 插件形态 —— 区分完全插件模式（trigger 类型）与行内插件。
@@ -724,13 +799,29 @@ mod tests {
     }
 
     #[test]
-    /// 验证门控与句柄字段不参与序列化（不跨 RPC 传输），反序列化后为 None。
+    /// 验证取消令牌：克隆的上下文共享令牌；反序列化后为全新未取消令牌。
+    fn plugin_context_cancellation_is_shared_and_not_serialized() {
+        let ctx = PluginContext::new("trace-3");
+        let cloned = ctx.clone();
+        assert!(!ctx.is_cancelled());
+
+        ctx.cancellation.cancel();
+        assert!(cloned.is_cancelled(), "克隆的上下文应共享取消状态");
+
+        let json = serde_json::to_string(&ctx).expect("上下文应可序列化");
+        let roundtrip: PluginContext = serde_json::from_str(&json).expect("上下文应可反序列化");
+        assert!(!roundtrip.is_cancelled(), "取消状态不应跨 RPC 传输");
+    }
+
+    #[test]
+    /// 验证门控与句柄字段不参与序列化（不跨 RPC 传输），反序列化后为 None；
+    /// 版本号本身随上下文下发。
     fn plugin_context_skips_gate_in_serialization() {
         let mut ctx = PluginContext::new("trace-2");
         ctx.set_query_revision_gate(QueryRevisionGate::new(1, Arc::new(AtomicU64::new(1))));
         let json = serde_json::to_string(&ctx).expect("上下文应可序列化");
         assert!(
-            !json.contains("revision") && !json.contains("gate"),
+            !json.contains("gate"),
             "门控字段不应出现在序列化结果中: {}",
             json
         );
@@ -746,6 +837,7 @@ mod tests {
             "反序列化后门控应为 None"
         );
         assert!(roundtrip.is_query_current());
+        assert_eq!(roundtrip.query_revision(), 1, "版本号应随上下文下发");
         assert_eq!(
             roundtrip.query_channel,
             QueryChannel::Ui,
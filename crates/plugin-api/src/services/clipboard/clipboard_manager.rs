@@ -1,10 +1,26 @@
 use crate::host::HostApiError;
+use crate::services::clipboard::types::ClipboardCallback;
 
-/// 剪贴板管理器 trait，定义平台剪贴板写入原语。
-/// 各平台实现通过系统 API 写入系统剪贴板。
+/// 剪贴板管理器 trait，定义平台剪贴板读写与变化通知原语。
+/// 各平台实现通过系统 API 访问系统剪贴板。
+/// 变化通知为推送式（push-based）服务，与 FocusMonitor / InstallationMonitor 一致：
+/// 首个回调注册时开始监听，最后一个回调注销后停止。
 pub trait ClipboardManager: Send + Sync {
     /// 将文本写入系统剪贴板。
     /// 参数：text - 要写入的文本内容。
     /// 返回：成功返回 Ok(())，失败返回 HostApiError。
     fn set_text(&self, text: &str) -> Result<(), HostApiError>;
+
+    /// 读取系统剪贴板中的文本。
+    /// 返回：剪贴板为空或不含文本时返回 Ok(None)，访问失败返回 HostApiError。
+    fn get_text(&self) -> Result<Option<String>, HostApiError>;
+
+    /// 注册剪贴板文本变化回调。
+    /// 平台实现应跳过声明不希望被记录的内容（如密码管理器设置的排除标记）。
+    /// 参数：id - 回调标识（用于注销）；callback - 回调函数。
+    fn register_change_callback(&self, id: &str, callback: ClipboardCallback);
+
+    /// 注销剪贴板文本变化回调。
+    /// 参数：id - 回调标识。
+    fn unregister_change_callback(&self, id: &str);
 }
@@ -1,5 +1,7 @@
-//! 剪贴板能力域：插件向系统剪贴板写入文本。
+//! 剪贴板能力域：插件读写系统剪贴板文本并订阅文本变化。
 
 mod clipboard_manager;
+mod types;
 
 pub use clipboard_manager::ClipboardManager;
+pub use types::{ClipboardCallback, ClipboardEvent};
@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+/// 剪贴板文本变化事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardEvent {
+    /// 变化后的剪贴板文本
+    pub text: String,
+    /// 写入剪贴板的来源进程名（如 "notepad.exe"）；平台无法确定时为 None
+    pub source_app: Option<String>,
+}
+
+/// 剪贴板变化回调类型。
+/// 剪贴板文本变化时所有已注册回调被依次调用（在平台监听线程上执行，回调应尽快返回）。
+pub type ClipboardCallback = Arc<dyn Fn(ClipboardEvent) + Send + Sync>;
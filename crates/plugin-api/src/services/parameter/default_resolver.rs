@@ -1,8 +1,12 @@
 //! 默认参数解析器实现
 
-use super::types::{ParameterError, ParameterSnapshot};
+use chrono::{DateTime, Local};
+
+use super::types::{ParameterError, ParameterSnapshot, UserParameter};
 use crate::services::parameter::resolver::ParameterResolver;
-use crate::services::parameter::template_parser::{Placeholder, TemplateParser};
+use crate::services::parameter::template_parser::{
+    ParamSpec, Placeholder, TemplateParser, ValueSource,
+};
 
 /// 默认参数解析器实现
 ///
@@ -16,6 +20,48 @@ impl DefaultParameterResolver {
     }
 }
 
+impl DefaultParameterResolver {
+    /// 取单个参数的值（应用过滤器之前）。
+    ///
+    /// 用户参数：非空输入优先，其次默认值；无默认值时空输入保留为空，未提供则报参数不足。
+    /// 其余来源：取值为空时使用默认值。
+    fn resolve_param(
+        spec: &ParamSpec,
+        user_args: &[String],
+        slot_defaults: &[UserParameter],
+        snapshot: &ParameterSnapshot,
+        now: &DateTime<Local>,
+    ) -> Result<String, ParameterError> {
+        let value = match &spec.source {
+            ValueSource::User { slot, .. } => {
+                let provided = user_args.get(*slot);
+                if let Some(arg) = provided.filter(|arg| !arg.is_empty()) {
+                    return Ok(arg.clone());
+                }
+                let default = spec
+                    .default
+                    .clone()
+                    .or_else(|| slot_defaults.get(*slot).and_then(|p| p.default.clone()));
+                return match (default, provided) {
+                    (Some(default), _) => Ok(default),
+                    (None, Some(arg)) => Ok(arg.clone()),
+                    (None, None) => Err(ParameterError::InsufficientArguments {
+                        required: slot + 1,
+                        actual: user_args.len(),
+                    }),
+                };
+            }
+            ValueSource::System(param) => snapshot.get(param.as_key()),
+            ValueSource::Env(name) => std::env::var(name).unwrap_or_default(),
+            ValueSource::DateTime(format) => now.format(format).to_string(),
+        };
+        match &spec.default {
+            Some(default) if value.is_empty() => Ok(default.clone()),
+            _ => Ok(value),
+        }
+    }
+}
+
 impl Default for DefaultParameterResolver {
     fn default() -> Self {
         Self::new()
@@ -30,27 +76,24 @@ impl ParameterResolver for DefaultParameterResolver {
         user_args: &[String],
         snapshot: &ParameterSnapshot,
     ) -> Result<String, ParameterError> {
-        let placeholders = TemplateParser::parse(template);
+        let placeholders = TemplateParser::parse(template)?;
+        // 同名命名参数共享默认值：任一出现处声明的默认值对所有出现处生效
+        let slot_defaults = TemplateParser::user_parameters(template);
+        // 同一模板内的 {date} / {time} 取同一时刻
+        let now = Local::now();
 
         let mut result = String::new();
-        let mut user_arg_index = 0;
-
         for placeholder in placeholders {
             match placeholder {
                 Placeholder::Text(text) => result.push_str(&text),
-                Placeholder::UserArg => {
-                    if user_arg_index < user_args.len() {
-                        result.push_str(&user_args[user_arg_index]);
-                        user_arg_index += 1;
-                    } else {
-                        return Err(ParameterError::InsufficientArguments {
-                            required: user_arg_index + 1,
-                            actual: user_args.len(),
-                        });
-                    }
-                }
-                Placeholder::System(param) => {
-                    result.push_str(&snapshot.get(param.as_key()));
+                Placeholder::Param(spec) => {
+                    let value =
+                        Self::resolve_param(&spec, user_args, &slot_defaults, snapshot, &now)?;
+                    let value = spec
+                        .filters
+                        .iter()
+                        .fold(value, |value, filter| filter.apply(&value));
+                    result.push_str(&value);
                 }
             }
         }
@@ -65,6 +108,10 @@ impl ParameterResolver for DefaultParameterResolver {
     fn has_system_parameters(&self, template: &str) -> bool {
         TemplateParser::has_system_params(template)
     }
+
+    fn user_parameters(&self, template: &str) -> Vec<UserParameter> {
+        TemplateParser::user_parameters(template)
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +203,75 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// 命名参数按名称共享输入，留空或未提供时使用默认值。
+    #[tokio::test]
+    async fn test_resolve_named_parameters_with_defaults() {
+        let resolver = DefaultParameterResolver::new();
+        let snapshot = ParameterSnapshot::empty();
+        let template = "git commit -m {msg} --author {who:me} # {msg}";
+        let result = resolver
+            .resolve(template, &["fix".to_string()], &snapshot)
+            .await;
+        assert_eq!(result.unwrap(), "git commit -m fix --author me # fix");
+
+        let result = resolver
+            .resolve(
+                template,
+                &["fix".to_string(), "alice".to_string()],
+                &snapshot,
+            )
+            .await;
+        assert_eq!(result.unwrap(), "git commit -m fix --author alice # fix");
+
+        let result = resolver
+            .resolve("search {q:rust}", &[String::new()], &snapshot)
+            .await;
+        assert_eq!(result.unwrap(), "search rust");
+    }
+
+    /// 过滤器按顺序作用于取值；系统参数为空时使用默认值。
+    #[tokio::test]
+    async fn test_resolve_filters_and_system_defaults() {
+        let resolver = DefaultParameterResolver::new();
+        let snapshot = make_snapshot("  Hello World ", "", "");
+        let result = resolver
+            .resolve(
+                "https://example.com/?q={clip|trim|lower|urlencode}&s={selection:none}",
+                &[],
+                &snapshot,
+            )
+            .await;
+        assert_eq!(
+            result.unwrap(),
+            "https://example.com/?q=hello%20world&s=none"
+        );
+    }
+
+    /// 环境变量缺失时使用默认值；日期按 strftime 格式输出。
+    #[tokio::test]
+    async fn test_resolve_env_and_date() {
+        let resolver = DefaultParameterResolver::new();
+        let snapshot = ParameterSnapshot::empty();
+        let result = resolver
+            .resolve(
+                "{env:ZEROLAUNCH_TEST_UNSET_VAR:fallback} {date:%Y}",
+                &[],
+                &snapshot,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, format!("fallback {}", Local::now().format("%Y")));
+    }
+
+    /// 格式错误的模板返回 InvalidPlaceholder。
+    #[tokio::test]
+    async fn test_resolve_invalid_placeholder() {
+        let resolver = DefaultParameterResolver::new();
+        let snapshot = ParameterSnapshot::empty();
+        let result = resolver.resolve("echo {clip|bogus}", &[], &snapshot).await;
+        assert!(matches!(result, Err(ParameterError::InvalidPlaceholder(_))));
+    }
+
     #[test]
     fn test_count_user_parameters() {
         let resolver = DefaultParameterResolver::new();
@@ -163,6 +279,7 @@ mod tests {
         assert_eq!(resolver.count_user_parameters("program {} {}"), 2);
         assert_eq!(resolver.count_user_parameters("program {clip} {hwnd}"), 0);
         assert_eq!(resolver.count_user_parameters("program {} {clip} {}"), 2);
+        assert_eq!(resolver.count_user_parameters("program {a} {b} {a}"), 2);
     }
 
     #[test]
@@ -2,9 +2,16 @@
 //!
 //! 负责解析和填充启动模板中的参数占位符，支持以下参数类型：
 //! - `{}` - 用户提供的位置参数
+//! - `{name}` - 用户提供的命名参数（同名参数只需输入一次）
 //! - `{clip}` - 剪贴板内容
 //! - `{hwnd}` - 当前活动窗口句柄
 //! - `{selection}` - 唤醒前活动窗口的选中文本
+//! - `{env:NAME}` - 环境变量
+//! - `{date}` / `{time}` / `{date:%Y%m%d}` - 当前本地日期时间（strftime 格式）
+//!
+//! 占位符可带默认值与过滤器：`{query:默认值|trim|urlencode}`；
+//! 过滤器有 trim / lower / upper / urlencode / shellquote。
+//! `{{` 与 `}}` 表示字面大括号。
 
 pub mod default_resolver;
 pub mod provider;
@@ -15,5 +22,5 @@ pub mod types;
 pub use default_resolver::DefaultParameterResolver;
 pub use provider::SystemParameterProvider;
 pub use resolver::ParameterResolver;
-pub use template_parser::{Placeholder, TemplateParser};
-pub use types::{ParameterError, ParameterSnapshot, SystemParameter};
+pub use template_parser::{Filter, ParamSpec, Placeholder, TemplateParser, ValueSource};
+pub use types::{ParameterError, ParameterSnapshot, SystemParameter, UserParameter};
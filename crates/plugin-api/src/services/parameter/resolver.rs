@@ -1,6 +1,6 @@
 //! 参数解析器 trait 定义
 
-use super::types::{ParameterError, ParameterSnapshot};
+use super::types::{ParameterError, ParameterSnapshot, UserParameter};
 
 /// 参数解析器 trait
 ///
@@ -30,4 +30,7 @@ pub trait ParameterResolver: Send + Sync {
 
     /// 检查模板是否包含系统参数（同步方法，纯计算）
     fn has_system_parameters(&self, template: &str) -> bool;
+
+    /// 列出模板需要用户输入的参数（名称与默认值，按输入顺序；同步方法，纯计算）
+    fn user_parameters(&self, template: &str) -> Vec<UserParameter>;
 }
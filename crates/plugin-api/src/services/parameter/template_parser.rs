@@ -1,20 +1,160 @@
 //! 模板解析器 - 解析模板字符串中的参数占位符
 
-use crate::services::parameter::types::SystemParameter;
+use chrono::format::{Item, StrftimeItems};
+use std::collections::HashMap;
+
+use crate::services::parameter::types::{ParameterError, SystemParameter, UserParameter};
+
+/// `{date}` 未指定格式时使用的格式。
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// `{time}` 未指定格式时使用的格式。
+const DEFAULT_TIME_FORMAT: &str = "%H:%M:%S";
 
 /// 模板中的占位符类型
 ///
 /// 解析模板后产生的占位符序列，按出现顺序排列。
-/// 新设计使用枚举替代旧版的带位置信息的 struct，从左到右顺序构建结果字符串，
-/// 避免旧方案中从后往前替换时的位置偏移问题。
+/// 使用枚举从左到右顺序构建结果字符串，避免从后往前替换时的位置偏移问题。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Placeholder {
-    /// 纯文本片段
+    /// 纯文本片段（`{{` / `}}` 转义已还原为单个大括号）
     Text(String),
-    /// 用户提供的位置参数 {}
-    UserArg,
-    /// 系统提供的参数 {clip} {hwnd} {selection}
+    /// 参数占位符
+    Param(ParamSpec),
+}
+
+impl Placeholder {
+    /// 是否为需要用户输入的参数。
+    pub fn is_user_arg(&self) -> bool {
+        matches!(
+            self,
+            Placeholder::Param(ParamSpec {
+                source: ValueSource::User { .. },
+                ..
+            })
+        )
+    }
+
+    /// 是否为依赖系统参数快照的参数（{clip} / {hwnd} / {selection}）。
+    pub fn is_system(&self) -> bool {
+        matches!(
+            self,
+            Placeholder::Param(ParamSpec {
+                source: ValueSource::System(_),
+                ..
+            })
+        )
+    }
+}
+
+/// 参数占位符：取值来源 + 默认值 + 过滤器链。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamSpec {
+    /// 取值来源
+    pub source: ValueSource,
+    /// 取值为空（或用户未提供）时使用的默认值：`{name:默认值}`
+    pub default: Option<String>,
+    /// 按顺序应用的过滤器：`{clip|trim|urlencode}`
+    pub filters: Vec<Filter>,
+}
+
+/// 参数的取值来源。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// 用户参数：位置参数 `{}` 或命名参数 `{name}`。
+    /// slot 为在用户输入列表中的序号；同名命名参数共享同一个 slot。
+    User { slot: usize, name: Option<String> },
+    /// 系统参数 {clip} {hwnd} {selection}
     System(SystemParameter),
+    /// 环境变量 `{env:NAME}`
+    Env(String),
+    /// 当前本地时间，按 strftime 格式输出：`{date:%Y-%m-%d}` / `{time:%H:%M}`
+    DateTime(String),
+}
+
+/// 占位符过滤器。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// 去除首尾空白
+    Trim,
+    /// 转小写
+    Lower,
+    /// 转大写
+    Upper,
+    /// URL 百分号编码（保留 RFC 3986 非保留字符）
+    UrlEncode,
+    /// 按当前平台 shell 规则加引号，作为单个参数传递
+    ShellQuote,
+}
+
+impl Filter {
+    /// 从过滤器名称解析。
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "trim" => Some(Filter::Trim),
+            "lower" => Some(Filter::Lower),
+            "upper" => Some(Filter::Upper),
+            "urlencode" => Some(Filter::UrlEncode),
+            "shellquote" => Some(Filter::ShellQuote),
+            _ => None,
+        }
+    }
+
+    /// 对取值应用过滤器。
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Filter::Trim => value.trim().to_string(),
+            Filter::Lower => value.to_lowercase(),
+            Filter::Upper => value.to_uppercase(),
+            Filter::UrlEncode => url_encode(value),
+            Filter::ShellQuote => shell_quote(value),
+        }
+    }
+}
+
+/// URL 百分号编码：字母数字与 `-_.~` 原样保留，其余字节编码为 `%XX`。
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// 按 POSIX shell 规则加单引号：内部单引号写作 `'\''`。
+#[cfg(not(windows))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 按 Windows 命令行（CommandLineToArgvW）规则加双引号：
+/// 双引号前的反斜杠加倍并转义双引号，末尾反斜杠加倍。
+/// 注意 cmd.exe 仍会展开引号内的 `%VAR%`。
+#[cfg(windows)]
+fn shell_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in value.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
 }
 
 /// 模板解析器
@@ -25,76 +165,187 @@ pub struct TemplateParser;
 impl TemplateParser {
     /// 解析模板，返回占位符序列
     ///
-    /// 支持的占位符格式：
-    /// - `{}` - 位置参数
-    /// - `{clip}` - 剪贴板内容
-    /// - `{hwnd}` - 窗口句柄
-    /// - `{selection}` - 选中文本
-    pub fn parse(template: &str) -> Vec<Placeholder> {
+    /// 支持的占位符格式（`:` 后为默认值，`|` 后为过滤器链）：
+    /// - `{}` / `{:默认值}` - 位置参数
+    /// - `{name}` / `{name:默认值}` - 命名参数（同名参数只需输入一次）
+    /// - `{clip}` / `{hwnd}` / `{selection}` - 剪贴板 / 窗口句柄 / 选中文本
+    /// - `{env:NAME}` / `{env:NAME:默认值}` - 环境变量
+    /// - `{date}` / `{date:%Y-%m-%d}` / `{time}` / `{time:%H:%M}` - 当前本地时间
+    /// - `{clip|trim|urlencode}` - 过滤器：trim / lower / upper / urlencode / shellquote
+    /// - `{{` / `}}` - 字面大括号
+    ///
+    /// 未闭合的 `{`、未知过滤器、非法参数名或时间格式返回 InvalidPlaceholder。
+    pub fn parse(template: &str) -> Result<Vec<Placeholder>, ParameterError> {
         let mut placeholders = Vec::new();
-        let chars: Vec<char> = template.chars().collect();
-        let mut i = 0;
-        let mut text_start = 0;
-
-        while i < chars.len() {
-            if chars[i] == '{' {
-                // 找到左大括号，先保存之前的文本
-                if i > text_start {
-                    let text: String = chars[text_start..i].iter().collect();
-                    placeholders.push(Placeholder::Text(text));
-                }
+        let mut text = String::new();
+        let mut named_slots: HashMap<String, usize> = HashMap::new();
+        let mut next_slot = 0;
+        let mut chars = template.chars().peekable();
 
-                // 查找右大括号
-                if let Some(end) = Self::find_closing_brace(&chars, i) {
-                    let content: String = chars[i + 1..end].iter().collect();
-
-                    let placeholder = if content.is_empty() {
-                        // {} - 位置参数
-                        Placeholder::UserArg
-                    } else if let Some(sys_param) = SystemParameter::from_name(&content) {
-                        // {clip} / {hwnd} / {selection} - 系统参数
-                        Placeholder::System(sys_param)
-                    } else {
-                        // 未知参数名，当作位置参数处理
-                        Placeholder::UserArg
-                    };
-
-                    placeholders.push(placeholder);
-                    i = end + 1;
-                    text_start = i;
-                    continue;
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
                 }
+                '{' => {
+                    let mut content = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        content.push(c);
+                    }
+                    if !closed {
+                        return Err(ParameterError::InvalidPlaceholder(format!(
+                            "{{{}：缺少右大括号",
+                            content
+                        )));
+                    }
+                    if !text.is_empty() {
+                        placeholders.push(Placeholder::Text(std::mem::take(&mut text)));
+                    }
+                    let spec = Self::parse_param(&content, &mut named_slots, &mut next_slot)?;
+                    placeholders.push(Placeholder::Param(spec));
+                }
+                _ => text.push(c),
             }
-            i += 1;
         }
 
-        // 保存末尾的文本
-        if text_start < chars.len() {
-            let text: String = chars[text_start..].iter().collect();
+        if !text.is_empty() {
             placeholders.push(Placeholder::Text(text));
         }
+        Ok(placeholders)
+    }
+
+    /// 解析单个占位符的内容（不含大括号）。
+    fn parse_param(
+        content: &str,
+        named_slots: &mut HashMap<String, usize>,
+        next_slot: &mut usize,
+    ) -> Result<ParamSpec, ParameterError> {
+        let invalid = |reason: &str| {
+            ParameterError::InvalidPlaceholder(format!("{{{}}}：{}", content, reason))
+        };
+
+        let mut parts = content.split('|');
+        let head = parts.next().unwrap_or_default();
+        let filters = parts
+            .map(|name| {
+                let name = name.trim();
+                Filter::from_name(name).ok_or_else(|| invalid(&format!("未知过滤器 {}", name)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (name, arg) = match head.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (head, None),
+        };
 
-        placeholders
+        let (source, default) = match name {
+            "" => {
+                let slot = *next_slot;
+                *next_slot += 1;
+                (ValueSource::User { slot, name: None }, arg)
+            }
+            "env" => {
+                let (var, default) = match arg.map(|a| a.split_once(':')) {
+                    Some(Some((var, default))) => (var, Some(default)),
+                    Some(None) => (arg.unwrap_or_default(), None),
+                    None => ("", None),
+                };
+                if var.is_empty() || var.contains('=') {
+                    return Err(invalid("环境变量名无效"));
+                }
+                (ValueSource::Env(var.to_string()), default)
+            }
+            "date" | "time" => {
+                let format = match arg {
+                    Some(format) if !format.is_empty() => format,
+                    _ if name == "date" => DEFAULT_DATE_FORMAT,
+                    _ => DEFAULT_TIME_FORMAT,
+                };
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return Err(invalid("时间格式无效"));
+                }
+                (ValueSource::DateTime(format.to_string()), None)
+            }
+            _ => {
+                if let Some(param) = SystemParameter::from_name(name) {
+                    (ValueSource::System(param), arg)
+                } else if Self::is_valid_name(name) {
+                    let slot = *named_slots.entry(name.to_string()).or_insert_with(|| {
+                        *next_slot += 1;
+                        *next_slot - 1
+                    });
+                    (
+                        ValueSource::User {
+                            slot,
+                            name: Some(name.to_string()),
+                        },
+                        arg,
+                    )
+                } else {
+                    return Err(invalid("未知参数名"));
+                }
+            }
+        };
+
+        Ok(ParamSpec {
+            source,
+            default: default.map(str::to_string),
+            filters,
+        })
     }
 
-    /// 查找匹配的右大括号
-    fn find_closing_brace(chars: &[char], start: usize) -> Option<usize> {
-        ((start + 1)..chars.len()).find(|&i| chars[i] == '}')
+    /// 命名参数名：字母或下划线开头，后续为字母、数字、下划线或连字符。
+    fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
     }
 
-    /// 统计模板中需要用户输入的参数数量（只统计位置参数）
+    /// 列出模板需要用户输入的参数（按输入顺序，同名命名参数只列一次）。
+    /// 模板无效时返回空列表（执行时由 resolve 报告 InvalidPlaceholder）。
+    pub fn user_parameters(template: &str) -> Vec<UserParameter> {
+        let mut parameters: Vec<UserParameter> = Vec::new();
+        for placeholder in Self::parse(template).unwrap_or_default() {
+            let Placeholder::Param(ParamSpec {
+                source: ValueSource::User { slot, name },
+                default,
+                ..
+            }) = placeholder
+            else {
+                continue;
+            };
+            if slot == parameters.len() {
+                parameters.push(UserParameter { name, default });
+            } else if parameters[slot].default.is_none() {
+                parameters[slot].default = default;
+            }
+        }
+        parameters
+    }
+
+    /// 统计模板中需要用户输入的参数数量（同名命名参数计一次）
     pub fn count_user_args(template: &str) -> usize {
-        Self::parse(template)
-            .iter()
-            .filter(|p| matches!(p, Placeholder::UserArg))
-            .count()
+        Self::user_parameters(template).len()
     }
 
     /// 检查模板是否包含系统参数
     pub fn has_system_params(template: &str) -> bool {
         Self::parse(template)
+            .unwrap_or_default()
             .iter()
-            .any(|p| matches!(p, Placeholder::System(_)))
+            .any(Placeholder::is_system)
     }
 }
 
@@ -102,15 +353,29 @@ impl TemplateParser {
 mod tests {
     use super::*;
 
+    /// 构造无默认值、无过滤器的参数占位符。
+    fn param(source: ValueSource) -> Placeholder {
+        Placeholder::Param(ParamSpec {
+            source,
+            default: None,
+            filters: Vec::new(),
+        })
+    }
+
+    /// 构造位置参数占位符。
+    fn positional(slot: usize) -> Placeholder {
+        param(ValueSource::User { slot, name: None })
+    }
+
     #[test]
     fn test_parse_empty_template() {
-        let placeholders = TemplateParser::parse("");
+        let placeholders = TemplateParser::parse("").unwrap();
         assert_eq!(placeholders.len(), 0);
     }
 
     #[test]
     fn test_parse_no_parameters() {
-        let placeholders = TemplateParser::parse("notepad.exe test.txt");
+        let placeholders = TemplateParser::parse("notepad.exe test.txt").unwrap();
         assert_eq!(placeholders.len(), 1);
         assert_eq!(
             placeholders[0],
@@ -120,60 +385,58 @@ mod tests {
 
     #[test]
     fn test_parse_positional_parameter() {
-        let placeholders = TemplateParser::parse("cmd /c echo {}");
+        let placeholders = TemplateParser::parse("cmd /c echo {}").unwrap();
         assert_eq!(placeholders.len(), 2);
         assert_eq!(
             placeholders[0],
             Placeholder::Text("cmd /c echo ".to_string())
         );
-        assert_eq!(placeholders[1], Placeholder::UserArg);
+        assert_eq!(placeholders[1], positional(0));
     }
 
     #[test]
     fn test_parse_multiple_positional() {
-        let placeholders = TemplateParser::parse("program {} {} {}");
+        let placeholders = TemplateParser::parse("program {} {} {}").unwrap();
         assert_eq!(placeholders.len(), 6);
-        let user_args: Vec<_> = placeholders
-            .iter()
-            .filter(|p| matches!(p, Placeholder::UserArg))
-            .collect();
+        let user_args: Vec<_> = placeholders.iter().filter(|p| p.is_user_arg()).collect();
         assert_eq!(user_args.len(), 3);
+        assert_eq!(placeholders[5], positional(2));
     }
 
     #[test]
     fn test_parse_clipboard_parameter() {
-        let placeholders = TemplateParser::parse("notepad {clip}");
+        let placeholders = TemplateParser::parse("notepad {clip}").unwrap();
         assert_eq!(placeholders.len(), 2);
         assert_eq!(placeholders[0], Placeholder::Text("notepad ".to_string()));
         assert_eq!(
             placeholders[1],
-            Placeholder::System(SystemParameter::Clipboard)
+            param(ValueSource::System(SystemParameter::Clipboard))
         );
     }
 
     #[test]
     fn test_parse_hwnd_parameter() {
-        let placeholders = TemplateParser::parse("tool --hwnd {hwnd}");
+        let placeholders = TemplateParser::parse("tool --hwnd {hwnd}").unwrap();
         assert_eq!(placeholders.len(), 2);
         assert_eq!(
             placeholders[1],
-            Placeholder::System(SystemParameter::WindowHandle)
+            param(ValueSource::System(SystemParameter::WindowHandle))
         );
     }
 
     #[test]
     fn test_parse_selection_parameter() {
-        let placeholders = TemplateParser::parse("translate {selection}");
+        let placeholders = TemplateParser::parse("translate {selection}").unwrap();
         assert_eq!(placeholders.len(), 2);
         assert_eq!(
             placeholders[1],
-            Placeholder::System(SystemParameter::Selection)
+            param(ValueSource::System(SystemParameter::Selection))
         );
     }
 
     #[test]
     fn test_parse_mixed_parameters() {
-        let placeholders = TemplateParser::parse("program {} {clip} {} {hwnd}");
+        let placeholders = TemplateParser::parse("program {} {clip} {} {hwnd}").unwrap();
         assert_eq!(placeholders.len(), 8);
     }
 
@@ -199,13 +462,114 @@ mod tests {
         assert!(TemplateParser::has_system_params("program {selection}"));
         assert!(TemplateParser::has_system_params("program {} {clip}"));
         assert!(!TemplateParser::has_system_params("program {}"));
+        assert!(!TemplateParser::has_system_params("program {env:HOME}"));
         assert!(!TemplateParser::has_system_params("program"));
     }
 
     #[test]
     fn test_parse_trailing_text() {
-        let placeholders = TemplateParser::parse("cmd /c echo {} --flag");
+        let placeholders = TemplateParser::parse("cmd /c echo {} --flag").unwrap();
         assert_eq!(placeholders.len(), 3);
         assert_eq!(placeholders[2], Placeholder::Text(" --flag".to_string()));
     }
+
+    /// 命名参数按首次出现分配输入序号，同名参数共享序号并只列出一次；默认值取任一出现处。
+    #[test]
+    fn test_user_parameters_with_names_and_defaults() {
+        let parameters =
+            TemplateParser::user_parameters("git commit -m {msg} --author {who:me} {} {msg:wip}");
+        assert_eq!(
+            parameters,
+            vec![
+                UserParameter {
+                    name: Some("msg".to_string()),
+                    default: Some("wip".to_string()),
+                },
+                UserParameter {
+                    name: Some("who".to_string()),
+                    default: Some("me".to_string()),
+                },
+                UserParameter {
+                    name: None,
+                    default: None,
+                },
+            ]
+        );
+    }
+
+    /// 默认值、环境变量、时间格式与过滤器链的解析结果。
+    #[test]
+    fn test_parse_extended_placeholders() {
+        let placeholders = TemplateParser::parse(
+            "{query:rust lang|trim|urlencode}{env:HOME:/tmp}{date:%H:%M}{time}",
+        )
+        .unwrap();
+        assert_eq!(
+            placeholders,
+            vec![
+                Placeholder::Param(ParamSpec {
+                    source: ValueSource::User {
+                        slot: 0,
+                        name: Some("query".to_string()),
+                    },
+                    default: Some("rust lang".to_string()),
+                    filters: vec![Filter::Trim, Filter::UrlEncode],
+                }),
+                Placeholder::Param(ParamSpec {
+                    source: ValueSource::Env("HOME".to_string()),
+                    default: Some("/tmp".to_string()),
+                    filters: Vec::new(),
+                }),
+                param(ValueSource::DateTime("%H:%M".to_string())),
+                param(ValueSource::DateTime(DEFAULT_TIME_FORMAT.to_string())),
+            ]
+        );
+    }
+
+    /// `{{` / `}}` 转义为字面大括号，不产生占位符。
+    #[test]
+    fn test_parse_escaped_braces() {
+        let placeholders = TemplateParser::parse("awk '{{print $1}}' {}").unwrap();
+        assert_eq!(
+            placeholders,
+            vec![
+                Placeholder::Text("awk '{print $1}' ".to_string()),
+                positional(0),
+            ]
+        );
+    }
+
+    /// 未知或格式错误的占位符返回 InvalidPlaceholder，而不是被当作位置参数。
+    #[test]
+    fn test_parse_rejects_invalid_placeholders() {
+        for template in [
+            "echo {print $1}",
+            "echo {clip|reverse}",
+            "echo {env:}",
+            "echo {date:%Q}",
+            "echo {unclosed",
+        ] {
+            assert!(
+                matches!(
+                    TemplateParser::parse(template),
+                    Err(ParameterError::InvalidPlaceholder(_))
+                ),
+                "{} 应被拒绝",
+                template
+            );
+        }
+        assert_eq!(TemplateParser::count_user_args("echo {print $1}"), 0);
+    }
+
+    /// 过滤器按平台规则编码取值。
+    #[test]
+    fn test_filters() {
+        assert_eq!(Filter::UrlEncode.apply("a b/ü"), "a%20b%2F%C3%BC");
+        assert_eq!(Filter::Lower.apply("MiXeD"), "mixed");
+        assert_eq!(Filter::Trim.apply("  x \n"), "x");
+        #[cfg(not(windows))]
+        assert_eq!(Filter::ShellQuote.apply("it's"), r"'it'\''s'");
+        #[cfg(windows)]
+        assert_eq!(Filter::ShellQuote.apply(r#"a "b"\"#), r#""a \"b\"\\""#);
+    }
 }
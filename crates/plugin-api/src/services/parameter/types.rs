@@ -50,6 +50,14 @@ impl ParameterSnapshot {
         })
     }
 
+    /// 获取参数值（不记录缺失日志）
+    ///
+    /// 参数：key - 参数键名
+    /// 返回：参数值，未捕获时返回 None（供可选使用系统参数的场景，如上下文感知排序）
+    pub fn try_get(&self, key: &str) -> Option<&str> {
+        self.inner.get(key).map(String::as_str)
+    }
+
     /// 插入参数值
     ///
     /// 参数：key - 参数键名；value - 参数值
@@ -58,6 +66,17 @@ impl ParameterSnapshot {
     }
 }
 
+/// 模板中需要用户输入的参数描述（跨 IPC 传递，供前端标注输入框）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserParameter {
+    /// 参数名：命名参数 `{name}` 的名称，位置参数 `{}` 为 None
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+    /// 默认值：`{name:默认值}`，用户留空时使用
+    #[serde(rename = "default", default)]
+    pub default: Option<String>,
+}
+
 /// 系统参数类型（crate 内部可见，不对外公开）
 ///
 /// 定义 SDK 支持的系统参数枚举，外部无法知道具体有哪些参数类型，保证封装性。
@@ -0,0 +1,264 @@
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command};
+
+/// 单路输出（stdout / stderr）最多保留的字节数，超出部分丢弃并标记截断。
+pub const MAX_CAPTURED_BYTES: usize = 256 * 1024;
+
+/// 进程结束（或超时被终止）后等待输出管道关闭的宽限期。
+/// 命令派生的后台子进程可能继承并长期持有管道，读取端不能无限等待。
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+/// 捕获型命令执行的结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// 标准输出（按 UTF-8 有损解码）
+    pub stdout: String,
+    /// 标准错误（按 UTF-8 有损解码）
+    pub stderr: String,
+    /// 退出码；超时被终止或被信号结束时为 None
+    pub exit_code: Option<i32>,
+    /// 是否因超时被终止
+    pub timed_out: bool,
+    /// 输出是否超过 MAX_CAPTURED_BYTES 被截断
+    pub truncated: bool,
+    /// 从启动到结束的耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+impl CommandOutput {
+    /// 命令是否正常结束且退出码为 0。
+    pub fn success(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+/// 运行已配置好程序与参数的命令，捕获 stdout/stderr 与退出码。
+/// 超过 timeout 仍未结束的命令连同其派生的后台子进程一起被终止，已读取的部分输出仍然返回。
+/// 参数：command - 平台实现构造的命令（stdin/stdout/stderr 由本函数接管）；timeout - 最长运行时间。
+/// 返回：成功返回 CommandOutput，进程无法启动时返回 io 错误。
+pub async fn capture_command_output(
+    mut command: Command,
+    timeout: Duration,
+) -> std::io::Result<CommandOutput> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // 命令自成进程组（组 ID = 命令 PID），超时时整组终止
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let started = Instant::now();
+    let mut child = command.spawn()?;
+    let tree = ProcessTree::attach(&child);
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let deadline = tokio::time::Instant::now() + timeout + READER_GRACE;
+
+    let (status, (stdout, stdout_truncated), (stderr, stderr_truncated)) = tokio::join!(
+        wait_or_kill(&mut child, &tree, timeout),
+        read_capped(stdout, deadline),
+        read_capped(stderr, deadline),
+    );
+    let (exit_code, timed_out) = status?;
+
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        exit_code,
+        timed_out,
+        truncated: stdout_truncated || stderr_truncated,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// 命令派生的整棵进程树：Unix 为以命令为组长的进程组，Windows 为作业对象。
+/// 只在超时时整体终止；命令正常结束后其脱离的子进程不受影响。
+struct ProcessTree {
+    /// 进程组 ID（即命令 PID）
+    #[cfg(unix)]
+    pgid: Option<i32>,
+    /// 容纳命令及其子进程的作业；创建或加入失败时为 None，退化为只终止命令本身
+    #[cfg(windows)]
+    job: Option<crate::common::JobObject>,
+}
+
+impl ProcessTree {
+    /// 记录刚启动的命令所在的进程树。
+    fn attach(child: &Child) -> Self {
+        #[cfg(unix)]
+        {
+            Self {
+                pgid: child.id().and_then(|pid| i32::try_from(pid).ok()),
+            }
+        }
+        #[cfg(windows)]
+        {
+            let job = crate::common::JobObject::grouping().and_then(|job| {
+                job.assign(child)?;
+                Ok(job)
+            });
+            Self {
+                job: job
+                    .map_err(|e| {
+                        tracing::warn!("命令无法加入作业对象，超时时只终止命令本身: {}", e)
+                    })
+                    .ok(),
+            }
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = child;
+            Self {}
+        }
+    }
+
+    /// 终止整棵进程树。
+    fn kill(&self) {
+        #[cfg(unix)]
+        if let Some(pgid) = self.pgid {
+            // SAFETY: 向本函数创建的进程组发送 SIGKILL，不涉及内存安全。
+            unsafe {
+                libc::kill(-pgid, libc::SIGKILL);
+            }
+        }
+        #[cfg(windows)]
+        if let Some(job) = &self.job {
+            let _ = job.terminate();
+        }
+    }
+}
+
+/// 等待进程结束；超时则终止整棵进程树。
+/// 返回：(退出码, 是否超时)。
+async fn wait_or_kill(
+    child: &mut Child,
+    tree: &ProcessTree,
+    timeout: Duration,
+) -> std::io::Result<(Option<i32>, bool)> {
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => Ok((status?.code(), false)),
+        Err(_) => {
+            tree.kill();
+            // 兜底终止命令本身并回收
+            let _ = child.kill().await;
+            Ok((None, true))
+        }
+    }
+}
+
+/// 读取输出管道直到 EOF 或到达 deadline，最多保留 MAX_CAPTURED_BYTES 字节。
+/// 超出上限后继续读取并丢弃，避免子进程因管道写满而阻塞。
+/// 返回：(已保留的字节, 是否截断)。
+async fn read_capped<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    deadline: tokio::time::Instant,
+) -> (Vec<u8>, bool) {
+    let mut buffer = Vec::new();
+    let mut truncated = false;
+    let Some(mut reader) = reader else {
+        return (buffer, truncated);
+    };
+    let mut chunk = [0u8; 8192];
+    loop {
+        match tokio::time::timeout_at(deadline, reader.read(&mut chunk)).await {
+            Ok(Ok(0)) | Ok(Err(_)) | Err(_) => break,
+            Ok(Ok(n)) => {
+                let room = MAX_CAPTURED_BYTES.saturating_sub(buffer.len());
+                if n > room {
+                    truncated = true;
+                }
+                buffer.extend_from_slice(&chunk[..n.min(room)]);
+            }
+        }
+    }
+    (buffer, truncated)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// 构造 `sh -c` 命令。
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    /// 分别捕获 stdout、stderr 与非零退出码。
+    #[tokio::test]
+    async fn captures_streams_and_exit_code() {
+        let output =
+            capture_command_output(sh("echo out; echo err >&2; exit 3"), Duration::from_secs(5))
+                .await
+                .unwrap();
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+        assert_eq!(output.exit_code, Some(3));
+        assert!(!output.timed_out);
+        assert!(!output.success());
+    }
+
+    /// 超时的进程被终止，超时前的输出仍然保留。
+    #[tokio::test]
+    async fn kills_on_timeout_and_keeps_partial_output() {
+        let output =
+            capture_command_output(sh("echo started; sleep 5"), Duration::from_millis(300))
+                .await
+                .unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, None);
+        assert_eq!(output.stdout, "started\n");
+    }
+
+    /// 超出上限的输出被截断，且不会因管道写满阻塞子进程。
+    #[tokio::test]
+    async fn truncates_oversized_output() {
+        let script = format!("head -c {} /dev/zero", MAX_CAPTURED_BYTES * 2);
+        let output = capture_command_output(sh(&script), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(output.truncated);
+        assert_eq!(output.stdout.len(), MAX_CAPTURED_BYTES);
+        assert_eq!(output.exit_code, Some(0));
+    }
+
+    /// 进程是否仍在运行（已退出或仅剩僵尸均视为不在运行）。
+    fn is_running(pid: &str) -> bool {
+        match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+            Ok(stat) => !stat
+                .rsplit(')')
+                .next()
+                .is_some_and(|rest| rest.trim_start().starts_with('Z')),
+            Err(_) => false,
+        }
+    }
+
+    /// 超时连同命令派生的后台子进程一起终止，输出管道随之关闭。
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn timeout_kills_background_children() {
+        let output =
+            capture_command_output(sh("sleep 30 & echo $!; wait"), Duration::from_millis(300))
+                .await
+                .unwrap();
+        assert!(output.timed_out);
+        let pid = output.stdout.trim().to_string();
+        assert!(!pid.is_empty());
+        assert!(
+            output.duration_ms < 300 + READER_GRACE.as_millis() as u64,
+            "后台子进程被终止后管道应立即关闭，不应等满宽限期"
+        );
+        for _ in 0..20 {
+            if !is_running(&pid) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("后台子进程 {pid} 在超时后仍在运行");
+    }
+}
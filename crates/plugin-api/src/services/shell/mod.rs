@@ -1,7 +1,9 @@
+pub mod command_capture;
 pub mod lnk_resolver;
 pub mod resource_loader;
 pub mod shell_executor;
 
+pub use command_capture::{capture_command_output, CommandOutput, MAX_CAPTURED_BYTES};
 pub use lnk_resolver::LnkResolver;
 pub use resource_loader::ResourceLoader;
 pub use shell_executor::ShellExecutor;
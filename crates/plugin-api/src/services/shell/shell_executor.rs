@@ -1,6 +1,9 @@
 use crate::host::error::HostApiError;
 use crate::host::open_target::OpenTarget;
 use async_trait::async_trait;
+use std::time::Duration;
+
+use super::command_capture::CommandOutput;
 
 /// Shell 执行器 trait，定义平台原语。
 /// 平台实现者实现各原语方法，PluginHandle 通过注入的 ShellExecutor 委托调用。
@@ -25,4 +28,13 @@ pub trait ShellExecutor: Send + Sync {
     /// 参数：command - 要执行的命令字符串。
     /// 返回：成功返回 Ok(())，失败返回 HostApiError。
     async fn shell_execute_command(&self, command: &str) -> Result<(), HostApiError>;
+
+    /// 执行命令字符串并等待结束，捕获 stdout/stderr 与退出码（无窗口）。
+    /// 参数：command - 要执行的命令字符串；timeout - 最长运行时间，超时进程被终止。
+    /// 返回：成功返回 CommandOutput（含非零退出码与超时情况），无法启动时返回 HostApiError。
+    async fn shell_capture_command(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<CommandOutput, HostApiError>;
 }
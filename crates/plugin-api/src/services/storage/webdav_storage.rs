@@ -207,71 +207,19 @@ impl StorageService for WebDAVStorageService {
 #[cfg(all(test, feature = "webdav"))]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
-    use std::process::{Child, Stdio};
+    use crate::mock::webdav_server::WebDavTestServer;
     use std::time::Duration;
 
-    /// WebDAV 集成测试服务器地址（bun fixture 固定监听端口）。
-    const SERVER_URL: &str = "http://127.0.0.1:18080";
-
-    /// 启动 bun WebDAV 测试服务器（tests/fixtures/webdav_server.ts）。
-    /// 轮询 OPTIONS 直至就绪，超时 panic。
-    fn start_server() -> Child {
-        let script =
-            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/webdav_server.ts");
-        let child = std::process::Command::new("bun")
-            .arg("run")
-            .arg(&script)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn()
-            .expect("启动 WebDAV 测试服务器失败（需要 bun 可执行文件）");
-        child
-    }
-
-    /// 轮询等待服务器就绪（OPTIONS 返回 200），单次请求 2 秒超时。
-    async fn wait_ready() {
-        for _ in 0..25 {
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_secs(2))
-                .build()
-                .expect("创建 reqwest 客户端失败");
-            if client
-                .request(reqwest::Method::OPTIONS, SERVER_URL)
-                .send()
-                .await
-                .map(|r| r.status().is_success())
-                .unwrap_or(false)
-            {
-                return;
-            }
-            tokio::time::sleep(Duration::from_millis(200)).await;
-        }
-        panic!("WebDAV 测试服务器启动超时（5 秒）");
-    }
-
-    /// 构造指向测试服务器的 WebDAVStorageService（目标目录为根）。
-    fn make_service() -> WebDAVStorageService {
-        WebDAVStorageService::new(&WebDAVConfig {
-            host_url: SERVER_URL.into(),
-            account: "test".into(),
-            password: "test".into(),
-            destination_dir: "/".into(),
-        })
-    }
-
     /// WebDAV 存储服务端到端契约：upload/download/delete/list/validate 全链路。
     ///
     /// 依赖 bun 与 tests/fixtures/webdav_server.ts；整体 30 秒超时保护，
     /// 服务器进程通过 shutdown 端点优雅退出（兜底 kill 进程树）。
     #[tokio::test]
     async fn webdav_storage_full_roundtrip() {
-        let mut child = start_server();
-
-        let assertions = tokio::time::timeout(Duration::from_secs(30), async {
-            wait_ready().await;
+        tokio::time::timeout(Duration::from_secs(30), async {
+            let server = WebDavTestServer::start().await;
 
-            let svc = make_service();
+            let svc = WebDAVStorageService::new(&server.config("/"));
             assert_eq!(svc.target_dir_path(), "/", "目标目录应返回 destination_dir");
 
             // 上传 → 下载往返，内容一致
@@ -322,39 +270,9 @@ mod tests {
             svc.delete("dir/a.txt").await.expect("清理失败");
             svc.delete("dir/b.txt").await.expect("清理失败");
 
-            // 优雅关闭测试服务器（避免 bun 进程树残留）
-            let _ = reqwest::Client::builder()
-                .timeout(Duration::from_secs(2))
-                .build()
-                .expect("创建 reqwest 客户端失败")
-                .post(format!("{SERVER_URL}/__shutdown"))
-                .send()
-                .await;
-            Ok::<(), ()>(())
+            server.shutdown().await;
         })
         .await
         .expect("WebDAV 端到端断言超时（30 秒）");
-
-        // 等待服务器退出（轮询最多 5 秒），兜底杀进程树
-        for _ in 0..50 {
-            if child.try_wait().ok().flatten().is_some() {
-                break;
-            }
-            tokio::time::sleep(Duration::from_millis(100)).await;
-        }
-        if child.try_wait().ok().flatten().is_none() {
-            #[cfg(windows)]
-            {
-                let _ = std::process::Command::new("taskkill")
-                    .args(["/PID", &child.id().to_string(), "/T", "/F"])
-                    .output();
-            }
-            #[cfg(not(windows))]
-            {
-                let _ = child.kill();
-            }
-        }
-
-        assertions.expect("WebDAV 端到端断言失败");
     }
 }
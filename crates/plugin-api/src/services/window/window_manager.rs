@@ -14,4 +14,9 @@ pub trait WindowManager: Send + Sync {
     /// 参数：title - 窗口标题的部分匹配文本（不区分大小写）。
     /// 返回：成功激活返回 Ok(true)，未找到窗口返回 Ok(false)，失败返回 HostApiError。
     async fn activate_window_by_title(&self, title: &str) -> Result<bool, HostApiError>;
+
+    /// 查询窗口所属进程的进程名。
+    /// 参数：hwnd - 十进制窗口句柄字符串（与 `{hwnd}` 系统参数格式一致）。
+    /// 返回：小写进程名（如 "code.exe"），句柄无效或进程已退出返回 Ok(None)，失败返回 HostApiError。
+    async fn process_name_of_window(&self, hwnd: &str) -> Result<Option<String>, HostApiError>;
 }
@@ -12,7 +12,7 @@ use zerolaunch_plugin_api::config::{
     ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, SettingDefinition,
 };
 use zerolaunch_plugin_api::{
-    ActionExecutor, CachedCandidateData, DataSource, ExecutionContext, ExecutionError,
+    ActionExecutor, CachedCandidateData, DataSource, ExecutionContext, ExecutionError, ListItem,
     PanelInteraction, Plugin, PluginContext, PluginError, PluginHandle, PluginMetadata, Query,
     QueryResponse, ResultAction, TargetType,
 };
@@ -419,9 +419,12 @@ impl Plugin for RemoteComponent {
         // 每次查询先刷新交互策略缓存（内置插件在会话推送时同步求值，远端以此对齐）。
         self.refresh_interaction_policy().await;
 
+        // 同一通道的新查询取代旧查询：旧请求仍在途时先通知插件取消。
+        let slot = format!("{}:{:?}", plugin_methods::QUERY, ctx.query_channel);
         self.client
-            .call::<_, QueryResponse>(
+            .call_superseding::<_, QueryResponse>(
                 plugin_methods::QUERY,
+                &slot,
                 QueryParams {
                     plugin_id: metadata.id.clone(),
                     ctx: ctx.clone(),
@@ -433,6 +436,45 @@ impl Plugin for RemoteComponent {
             .map_err(|e| PluginError::QueryFailed(e.to_string()))
     }
 
+    /// 全局查询贡献：宿主已按单次预算截断等待，此处的 RPC 超时仅作兜底。
+    /// 同一通道的新查询取代旧查询（旧请求仍在途时通知插件取消，避免超出预算的
+    /// 旧查询在插件内堆积）。METHOD_NOT_FOUND（旧 SDK 插件无此方法）视为不贡献条目。
+    async fn global_query(
+        &self,
+        ctx: &PluginContext,
+        query: &Query,
+    ) -> Result<Vec<ListItem>, PluginError> {
+        let metadata = match &self.kind {
+            RemoteComponentKind::Plugin { metadata, .. } => metadata,
+            _ => panic!(
+                "RemoteComponent {} is not a Plugin but global_query() was called",
+                self.core.component_id()
+            ),
+        };
+
+        let slot = format!("{}:{:?}", plugin_methods::GLOBAL_QUERY, ctx.query_channel);
+        let result: Result<Vec<ListItem>, _> = self
+            .client
+            .call_superseding(
+                plugin_methods::GLOBAL_QUERY,
+                &slot,
+                QueryParams {
+                    plugin_id: metadata.id.clone(),
+                    ctx: ctx.clone(),
+                    query: query.clone(),
+                },
+                Duration::from_secs(5),
+            )
+            .await;
+        match result {
+            Ok(items) => Ok(items),
+            Err(ProtocolError::Rpc { code, .. }) if code == codes::METHOD_NOT_FOUND => {
+                Ok(Vec::new())
+            }
+            Err(e) => Err(PluginError::QueryFailed(e.to_string())),
+        }
+    }
+
     async fn execute_action(
         &self,
         ctx: &PluginContext,
@@ -490,3 +532,134 @@ impl RemoteComponent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::PluginMetrics;
+    use crate::transport::codec;
+    use tokio::io::BufReader;
+    use tokio::sync::mpsc;
+    use zerolaunch_plugin_api::{PluginKind, PluginMode};
+    use zerolaunch_plugin_protocol::methods::notify;
+    use zerolaunch_plugin_protocol::{JsonRpcError, Message, Response};
+
+    fn metadata() -> PluginMetadata {
+        PluginMetadata {
+            id: "test.remote".to_string(),
+            name: "Remote".to_string(),
+            version: "0.1.0".to_string(),
+            description: String::new(),
+            author: String::new(),
+            trigger_keywords: Vec::new(),
+            supported_os: Vec::new(),
+            priority: 0,
+            kind: PluginKind::ThirdParty,
+            hotkey: None,
+            icon: None,
+            mode: PluginMode::Inline,
+            global_query: true,
+        }
+    }
+
+    fn query(term: &str) -> Query {
+        Query {
+            id: term.to_string(),
+            raw_query: term.to_string(),
+            search_term: term.to_string(),
+            confirm: false,
+        }
+    }
+
+    /// 读取插件侧收到的下一条消息。
+    async fn read_message<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> Message {
+        let frame = codec::read_frame(reader).await.unwrap();
+        serde_json::from_slice(&frame).unwrap()
+    }
+
+    /// 同一通道的新全局查询取代仍在途的旧查询：先为旧请求发送 `$/cancelRequest`。
+    #[tokio::test]
+    async fn global_query_cancels_superseded_call() {
+        let (host_io, plugin_io) = tokio::io::duplex(64 * 1024);
+        let (host_read, host_write) = tokio::io::split(host_io);
+        let (plugin_read, mut plugin_write) = tokio::io::split(plugin_io);
+        let (req_tx, _req_rx) = mpsc::channel(8);
+        let (notif_tx, _notif_rx) = mpsc::channel(8);
+        let client = JsonRpcClient::new(
+            BufReader::new(host_read),
+            host_write,
+            req_tx,
+            notif_tx,
+            Arc::new(PluginMetrics::default()),
+        );
+        let component = Arc::new(RemoteComponent::new(
+            "test.remote".to_string(),
+            "Remote".to_string(),
+            String::new(),
+            ComponentType::Plugin,
+            0,
+            client,
+            Vec::new(),
+            serde_json::json!({}),
+            Vec::new(),
+            true,
+            RemoteComponentKind::Plugin {
+                metadata: Arc::new(metadata()),
+                interaction_policy: RwLock::new(PanelInteraction::default()),
+            },
+        ));
+
+        let mut plugin_reader = BufReader::new(plugin_read);
+
+        let first_component = component.clone();
+        let first = tokio::spawn(async move {
+            first_component
+                .global_query(&PluginContext::new("a"), &query("a"))
+                .await
+        });
+        let first_id = match read_message(&mut plugin_reader).await {
+            Message::Request(req) => {
+                assert_eq!(req.method, plugin_methods::GLOBAL_QUERY);
+                req.id
+            }
+            other => panic!("应为首个全局查询请求: {:?}", other),
+        };
+
+        let second_component = component.clone();
+        let second = tokio::spawn(async move {
+            second_component
+                .global_query(&PluginContext::new("ab"), &query("ab"))
+                .await
+        });
+        match read_message(&mut plugin_reader).await {
+            Message::Notification(notif) => {
+                assert_eq!(notif.method, notify::CANCEL_REQUEST);
+                let params: CancelRequestParams = serde_json::from_value(notif.params).unwrap();
+                assert_eq!(params.id, first_id);
+            }
+            other => panic!("应先收到取消通知: {:?}", other),
+        }
+        let second_id = match read_message(&mut plugin_reader).await {
+            Message::Request(req) => req.id,
+            other => panic!("应为第二个全局查询请求: {:?}", other),
+        };
+
+        let cancelled = Response::err(
+            first_id,
+            JsonRpcError::new(codes::REQUEST_CANCELLED, "request cancelled"),
+        );
+        let ok = Response::ok(second_id, serde_json::json!([]));
+        for resp in [cancelled, ok] {
+            let payload = serde_json::to_vec(&Message::Response(resp)).unwrap();
+            codec::write_frame(&mut plugin_write, &payload)
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            first.await.unwrap(),
+            Err(PluginError::QueryFailed(_))
+        ));
+        assert!(second.await.unwrap().unwrap().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
 use serde::de::DeserializeOwned;
@@ -9,8 +9,12 @@ use tokio::io::{AsyncBufRead, AsyncWrite};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error, warn};
 
-use zerolaunch_plugin_protocol::{codes, JsonRpcError, Message, ProtocolError, Request, Response};
+use zerolaunch_plugin_protocol::methods::notify;
+use zerolaunch_plugin_protocol::{
+    codes, CancelRequestParams, JsonRpcError, Message, ProtocolError, Request, Response,
+};
 
+use crate::metrics::{CallOutcome, PluginMetrics};
 use crate::transport::codec;
 
 /// Incoming request from a plugin subprocess.
@@ -23,12 +27,23 @@ pub struct IncomingRequest {
     pub params: serde_json::Value,
 }
 
+/// How long a timed-out superseding call may stay unanswered after its
+/// `$/cancelRequest` before the plugin is reported as unresponsive.
+const UNRESPONSIVE_GRACE: Duration = Duration::from_secs(5);
+
 /// Bidirectional JSON-RPC 2.0 client over a framed stdio transport.
 #[derive(Debug)]
 pub struct JsonRpcClient {
     next_id: AtomicU64,
     pending: Arc<DashMap<u64, oneshot::Sender<Result<serde_json::Value, JsonRpcError>>>>,
     outbound_tx: mpsc::Sender<Message>,
+    /// Latest request id per supersession slot (see `call_superseding`).
+    superseding: DashMap<String, u64>,
+    /// Per-method latency / error metrics, shared with the host manager.
+    metrics: Arc<PluginMetrics>,
+    /// Receives the method name of a timed-out call the plugin never answered
+    /// (see `subscribe_unresponsive`).
+    unresponsive_tx: parking_lot::Mutex<Option<mpsc::UnboundedSender<String>>>,
     /// Handle to the read-loop task.
     _read_handle: tokio::task::JoinHandle<()>,
     /// Handle to the write-loop task.
@@ -41,12 +56,14 @@ impl JsonRpcClient {
     /// - `reader` / `writer`: the stdio handles from the child process.
     /// - `incoming_request_tx`: channel for forwarding plugin→host requests.
     /// - `incoming_notification_tx`: channel for forwarding plugin→host notifications.
+    /// - `metrics`: collector that every host→plugin call is recorded into.
     /// - Returns an `Arc<JsonRpcClient>` so it can be shared with host_dispatch.
     pub fn new<R, W>(
         reader: R,
         writer: W,
         incoming_request_tx: mpsc::Sender<IncomingRequest>,
         incoming_notification_tx: mpsc::Sender<(String, serde_json::Value)>,
+        metrics: Arc<PluginMetrics>,
     ) -> Arc<Self>
     where
         R: AsyncBufRead + Send + Unpin + 'static,
@@ -154,6 +171,9 @@ impl JsonRpcClient {
             next_id: AtomicU64::new(1),
             pending,
             outbound_tx,
+            superseding: DashMap::new(),
+            metrics,
+            unresponsive_tx: parking_lot::Mutex::new(None),
             _read_handle: read_handle,
             _write_handle: write_handle,
         })
@@ -167,6 +187,82 @@ impl JsonRpcClient {
         timeout: Duration,
     ) -> Result<R, ProtocolError> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.call_with_id(id, method, params, timeout, false).await
+    }
+
+    /// Send a request that supersedes the previous request on the same `slot`.
+    ///
+    /// If the previous request is still pending, a `$/cancelRequest` notification
+    /// is sent first so the plugin can abort its work early. The superseded caller
+    /// still receives whatever the plugin answers (typically `REQUEST_CANCELLED`).
+    ///
+    /// On timeout the call is cancelled as well; if the plugin still sends no
+    /// answer within `UNRESPONSIVE_GRACE`, it is reported through
+    /// `subscribe_unresponsive` so the owner can kill it.
+    pub async fn call_superseding<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        slot: &str,
+        params: P,
+        timeout: Duration,
+    ) -> Result<R, ProtocolError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        if let Some(previous) = self.superseding.insert(slot.to_string(), id) {
+            if self.pending.contains_key(&previous) {
+                debug!(
+                    "Cancelling superseded request {} on slot {}",
+                    previous, slot
+                );
+                if let Err(e) = self
+                    .notify(notify::CANCEL_REQUEST, CancelRequestParams { id: previous })
+                    .await
+                {
+                    debug!("Failed to send cancel notification: {}", e);
+                }
+            }
+        }
+
+        let result = self.call_with_id(id, method, params, timeout, true).await;
+        self.superseding.remove_if(slot, |_, latest| *latest == id);
+        result
+    }
+
+    /// Send a request with a pre-allocated id and wait for the response.
+    ///
+    /// The elapsed time and outcome are recorded into the client's metrics.
+    async fn call_with_id<P: Serialize, R: DeserializeOwned>(
+        &self,
+        id: u64,
+        method: &str,
+        params: P,
+        timeout: Duration,
+        cancel_on_timeout: bool,
+    ) -> Result<R, ProtocolError> {
+        let started = Instant::now();
+        let result = self
+            .send_and_wait(id, method, params, timeout, cancel_on_timeout)
+            .await;
+        let outcome = match &result {
+            Ok(_) => CallOutcome::Ok,
+            Err(ProtocolError::Timeout) => CallOutcome::Timeout,
+            Err(_) => CallOutcome::Error,
+        };
+        self.metrics.record_call(method, started.elapsed(), outcome);
+        result
+    }
+
+    /// Register the pending slot, write the request and await its response.
+    ///
+    /// With `cancel_on_timeout`, a timed-out request is cancelled and watched
+    /// (see `cancel_timed_out`) instead of simply being forgotten.
+    async fn send_and_wait<P: Serialize, R: DeserializeOwned>(
+        &self,
+        id: u64,
+        method: &str,
+        params: P,
+        timeout: Duration,
+        cancel_on_timeout: bool,
+    ) -> Result<R, ProtocolError> {
         let params_value = serde_json::to_value(params)?;
 
         let (tx, rx) = oneshot::channel();
@@ -192,12 +288,56 @@ impl JsonRpcClient {
                 Err(ProtocolError::TransportClosed)
             }
             Err(_) => {
-                self.pending.remove(&id);
+                if cancel_on_timeout {
+                    self.cancel_timed_out(id, method).await;
+                } else {
+                    self.pending.remove(&id);
+                }
                 Err(ProtocolError::Timeout)
             }
         }
     }
 
+    /// Send `$/cancelRequest` for a timed-out request and watch for its answer.
+    ///
+    /// The pending slot is swapped for a fresh one (never removed, so a late
+    /// answer cannot slip through unnoticed). A plugin that answers neither the
+    /// request nor the cancellation within `UNRESPONSIVE_GRACE` is hung; its
+    /// method name is sent to the `subscribe_unresponsive` receiver.
+    async fn cancel_timed_out(&self, id: u64, method: &str) {
+        let (tx, rx) = oneshot::channel();
+        self.pending.insert(id, tx);
+        warn!("Request {} ({}) timed out, cancelling", id, method);
+        if let Err(e) = self
+            .notify(notify::CANCEL_REQUEST, CancelRequestParams { id })
+            .await
+        {
+            debug!("Failed to send cancel notification: {}", e);
+        }
+
+        let pending = self.pending.clone();
+        let unresponsive_tx = self.unresponsive_tx.lock().clone();
+        let method = method.to_string();
+        tokio::spawn(async move {
+            if tokio::time::timeout(UNRESPONSIVE_GRACE, rx).await.is_ok() {
+                return;
+            }
+            pending.remove(&id);
+            if let Some(tx) = unresponsive_tx {
+                let _ = tx.send(method);
+            }
+        });
+    }
+
+    /// Subscribe to unresponsive-plugin reports: each message is the method of
+    /// a timed-out superseding call that got no answer even after cancellation.
+    /// Replaces any earlier subscriber.
+    pub fn subscribe_unresponsive(&self) -> mpsc::UnboundedReceiver<String> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        *self.unresponsive_tx.lock() = Some(tx);
+        rx
+    }
+
     /// Send a notification (fire-and-forget, no response expected).
     pub async fn notify<P: Serialize>(&self, method: &str, params: P) -> Result<(), ProtocolError> {
         let params_value = serde_json::to_value(params)?;
@@ -233,3 +373,211 @@ impl JsonRpcClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+    use zerolaunch_plugin_protocol::methods::plugin as plugin_methods;
+
+    /// 同一 slot 的新请求先为仍在途的旧请求发送 `$/cancelRequest`，再发出自身。
+    #[tokio::test]
+    async fn superseding_call_cancels_pending_request() {
+        let (host_io, plugin_io) = tokio::io::duplex(64 * 1024);
+        let (host_read, host_write) = tokio::io::split(host_io);
+        let (plugin_read, mut plugin_write) = tokio::io::split(plugin_io);
+        let (req_tx, _req_rx) = mpsc::channel(8);
+        let (notif_tx, _notif_rx) = mpsc::channel(8);
+        let metrics = Arc::new(PluginMetrics::default());
+        let client = JsonRpcClient::new(
+            BufReader::new(host_read),
+            host_write,
+            req_tx,
+            notif_tx,
+            metrics.clone(),
+        );
+
+        let first_client = client.clone();
+        let first = tokio::spawn(async move {
+            first_client
+                .call_superseding::<_, serde_json::Value>(
+                    plugin_methods::QUERY,
+                    "query:Ui",
+                    serde_json::json!({ "q": "a" }),
+                    Duration::from_secs(5),
+                )
+                .await
+        });
+
+        let mut plugin_reader = BufReader::new(plugin_read);
+        let read_message = |frame: Vec<u8>| serde_json::from_slice::<Message>(&frame).unwrap();
+        let first_id = match read_message(codec::read_frame(&mut plugin_reader).await.unwrap()) {
+            Message::Request(req) => req.id,
+            other => panic!("应为首个查询请求: {:?}", other),
+        };
+
+        let second_client = client.clone();
+        let second = tokio::spawn(async move {
+            second_client
+                .call_superseding::<_, serde_json::Value>(
+                    plugin_methods::QUERY,
+                    "query:Ui",
+                    serde_json::json!({ "q": "ab" }),
+                    Duration::from_secs(5),
+                )
+                .await
+        });
+
+        match read_message(codec::read_frame(&mut plugin_reader).await.unwrap()) {
+            Message::Notification(notif) => {
+                assert_eq!(notif.method, notify::CANCEL_REQUEST);
+                let params: CancelRequestParams = serde_json::from_value(notif.params).unwrap();
+                assert_eq!(params.id, first_id);
+            }
+            other => panic!("应先收到取消通知: {:?}", other),
+        }
+        let second_id = match read_message(codec::read_frame(&mut plugin_reader).await.unwrap()) {
+            Message::Request(req) => req.id,
+            other => panic!("应为第二个查询请求: {:?}", other),
+        };
+
+        // 插件按协议为被取消的请求回 REQUEST_CANCELLED，新请求正常返回。
+        let cancelled = Response::err(
+            first_id,
+            JsonRpcError::new(codes::REQUEST_CANCELLED, "request cancelled"),
+        );
+        let ok = Response::ok(second_id, serde_json::json!("done"));
+        for resp in [cancelled, ok] {
+            let payload = serde_json::to_vec(&Message::Response(resp)).unwrap();
+            codec::write_frame(&mut plugin_write, &payload)
+                .await
+                .unwrap();
+        }
+
+        match first.await.unwrap() {
+            Err(ProtocolError::Rpc { code, .. }) => assert_eq!(code, codes::REQUEST_CANCELLED),
+            other => panic!("首个请求应被取消: {:?}", other),
+        }
+        assert_eq!(second.await.unwrap().unwrap(), serde_json::json!("done"));
+        assert!(client.superseding.is_empty(), "完成后 slot 应被清理");
+
+        // 两次请求均记入指标：被取消的请求计为错误。
+        let snapshot = metrics.snapshot(0);
+        assert_eq!(snapshot.methods.len(), 1);
+        assert_eq!(snapshot.methods[0].method, plugin_methods::QUERY);
+        assert_eq!(snapshot.methods[0].count, 2);
+        assert_eq!(snapshot.methods[0].errors, 1);
+    }
+
+    /// 建立宿主端客户端与插件端读写半边（tokio duplex 模拟 stdio）。
+    fn duplex_client() -> (
+        Arc<JsonRpcClient>,
+        BufReader<tokio::io::ReadHalf<tokio::io::DuplexStream>>,
+        tokio::io::WriteHalf<tokio::io::DuplexStream>,
+    ) {
+        let (host_io, plugin_io) = tokio::io::duplex(64 * 1024);
+        let (host_read, host_write) = tokio::io::split(host_io);
+        let (plugin_read, plugin_write) = tokio::io::split(plugin_io);
+        let (req_tx, _req_rx) = mpsc::channel(8);
+        let (notif_tx, _notif_rx) = mpsc::channel(8);
+        let client = JsonRpcClient::new(
+            BufReader::new(host_read),
+            host_write,
+            req_tx,
+            notif_tx,
+            Arc::new(PluginMetrics::default()),
+        );
+        (client, BufReader::new(plugin_read), plugin_write)
+    }
+
+    /// 从插件端读取下一条消息。
+    async fn next_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Message {
+        serde_json::from_slice(&codec::read_frame(reader).await.unwrap()).unwrap()
+    }
+
+    /// 超时的查询先收到 `$/cancelRequest`；插件在宽限期内仍不应答则上报卡死。
+    #[tokio::test(start_paused = true)]
+    async fn timed_out_query_is_cancelled_then_reported_unresponsive() {
+        let (client, mut plugin_reader, _plugin_write) = duplex_client();
+        let mut unresponsive = client.subscribe_unresponsive();
+
+        let result = client
+            .call_superseding::<_, serde_json::Value>(
+                plugin_methods::QUERY,
+                "query:Ui",
+                serde_json::json!({ "q": "a" }),
+                Duration::from_secs(1),
+            )
+            .await;
+        assert!(matches!(result, Err(ProtocolError::Timeout)));
+
+        let id = match next_message(&mut plugin_reader).await {
+            Message::Request(req) => req.id,
+            other => panic!("应为查询请求: {:?}", other),
+        };
+        match next_message(&mut plugin_reader).await {
+            Message::Notification(notif) => {
+                assert_eq!(notif.method, notify::CANCEL_REQUEST);
+                let params: CancelRequestParams = serde_json::from_value(notif.params).unwrap();
+                assert_eq!(params.id, id);
+            }
+            other => panic!("超时后应发送取消通知: {:?}", other),
+        }
+
+        assert_eq!(
+            unresponsive.recv().await.as_deref(),
+            Some(plugin_methods::QUERY)
+        );
+        assert!(client.pending.is_empty(), "上报后不应残留在途请求");
+    }
+
+    /// 插件对取消作出应答（REQUEST_CANCELLED）时不视为卡死；普通 call 超时不发取消。
+    #[tokio::test(start_paused = true)]
+    async fn answered_cancellation_is_not_reported_unresponsive() {
+        let (client, mut plugin_reader, mut plugin_write) = duplex_client();
+        let mut unresponsive = client.subscribe_unresponsive();
+
+        let result = client
+            .call_superseding::<_, serde_json::Value>(
+                plugin_methods::QUERY,
+                "query:Ui",
+                serde_json::json!({ "q": "a" }),
+                Duration::from_secs(1),
+            )
+            .await;
+        assert!(matches!(result, Err(ProtocolError::Timeout)));
+        let id = match next_message(&mut plugin_reader).await {
+            Message::Request(req) => req.id,
+            other => panic!("应为查询请求: {:?}", other),
+        };
+        assert!(matches!(
+            next_message(&mut plugin_reader).await,
+            Message::Notification(_)
+        ));
+        let cancelled = Response::err(
+            id,
+            JsonRpcError::new(codes::REQUEST_CANCELLED, "request cancelled"),
+        );
+        let payload = serde_json::to_vec(&Message::Response(cancelled)).unwrap();
+        codec::write_frame(&mut plugin_write, &payload)
+            .await
+            .unwrap();
+
+        let result = client
+            .call::<_, serde_json::Value>(
+                plugin_methods::GET_METADATA,
+                serde_json::Value::Null,
+                Duration::from_secs(1),
+            )
+            .await;
+        assert!(matches!(result, Err(ProtocolError::Timeout)));
+        assert!(matches!(
+            next_message(&mut plugin_reader).await,
+            Message::Request(_)
+        ));
+
+        tokio::time::sleep(UNRESPONSIVE_GRACE * 2).await;
+        assert!(unresponsive.try_recv().is_err(), "已应答的取消不应上报");
+        assert!(client.pending.is_empty());
+    }
+}
@@ -15,6 +15,11 @@ pub trait HostCallHandler: Send + Sync {
         method: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value, JsonRpcError>;
+
+    /// Handle a plugin→host notification (no response is sent back).
+    /// Default implementation ignores it, so handlers only opt in to the
+    /// notifications they understand (e.g. host/query.update).
+    async fn handle_host_notification(&self, _method: &str, _params: serde_json::Value) {}
 }
 
 impl std::fmt::Debug for dyn HostCallHandler {
@@ -1,11 +1,13 @@
 //! ZeroLaunch third-party plugin host.
 //!
 //! Manages subprocess lifecycle, JSON-RPC transport (LSP-style framed stdio),
-//! bidirectional RPC client, and `RemoteComponent` implementations.
+//! bidirectional RPC client, per-plugin health metrics, and `RemoteComponent`
+//! implementations.
 
 pub mod adapter;
 pub mod client;
 pub mod host_dispatch;
 pub mod manager;
+pub mod metrics;
 pub mod process;
 pub mod transport;
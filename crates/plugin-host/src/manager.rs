@@ -21,6 +21,7 @@ use zerolaunch_plugin_protocol::ProtocolError;
 
 use crate::adapter::remote_component::{RemoteComponent, RemoteComponentKind};
 use crate::host_dispatch::HostCallHandler;
+use crate::metrics::{PluginMetrics, PluginMetricsSnapshot};
 use crate::process::force_kill_process;
 use crate::process::{PluginProcess, ProcessState};
 
@@ -147,6 +148,10 @@ pub struct PluginHostManager {
     plugins_dir: PathBuf,
     /// 每次加载插件时保存重启上下文，崩溃后可重新拉起。
     restart_contexts: Arc<DashMap<String, Arc<PluginRestartContext>>>,
+    /// 每个插件的健康指标（请求延迟/错误、内存采样）。
+    ///
+    /// 与重启上下文同生命周期：崩溃重启沿用同一份，卸载或放弃重启时移除。
+    metrics: Arc<DashMap<String, Arc<PluginMetrics>>>,
     /// 内置组件 id 集合（冲突预检数据源之一）。
     ///
     /// 由 src-tauri 在启动时注入一次（内置组件注册完毕、第三方加载之前）；
@@ -192,6 +197,7 @@ impl PluginHostManager {
             log_dir_root,
             plugins_dir,
             restart_contexts: Arc::new(DashMap::new()),
+            metrics: Arc::new(DashMap::new()),
             builtin_component_ids: RwLock::new(HashSet::new()),
             self_arc: OnceLock::new(),
         });
@@ -279,6 +285,10 @@ impl PluginHostManager {
         // 创建持久崩溃通知通道：管理器持有接收端，发送端跨多次重启共享。
         let (crash_tx, crash_rx) = mpsc::channel::<String>(4);
 
+        // 指标跨崩溃重启累计：已有则沿用（重启路径），否则新建。
+        // entry 守卫在语句末释放，不跨后续 spawn 的 .await。
+        let metrics = Arc::clone(self.metrics.entry(plugin_id.clone()).or_default().value());
+
         // 启动子进程并完成握手
         let process = PluginProcess::spawn(
             &manifest,
@@ -289,8 +299,12 @@ impl PluginHostManager {
             crash_tx.clone(),
             restart_count,
             locale,
+            metrics,
         )
-        .await?;
+        .await
+        .inspect_err(|_| {
+            self.metrics.remove(&plugin_id);
+        })?;
 
         // 在把进程移入 Arc 之前取出 client
         let client = process.client.clone();
@@ -326,6 +340,7 @@ impl PluginHostManager {
                 // 发现失败时清理登记
                 self.processes.remove(&plugin_id);
                 self.restart_contexts.remove(&plugin_id);
+                self.metrics.remove(&plugin_id);
                 return Err(PluginLoadError::Protocol(e));
             }
         };
@@ -416,6 +431,7 @@ impl PluginHostManager {
             }
         }
         self.plugins.remove(plugin_id);
+        self.metrics.remove(plugin_id);
 
         // Remove log file
         let log_file = self.log_dir_root.join(format!("{}.log", plugin_id));
@@ -452,7 +468,12 @@ impl PluginHostManager {
                     .get(&registration.plugin_id)
                     .map(|p| PluginRuntimeState::from(&*p.state.read()))
                     .unwrap_or_default();
-                build_plugin_info(registration, process_state, enabled_fn(registration))
+                build_plugin_info(
+                    registration,
+                    process_state,
+                    enabled_fn(registration),
+                    self.plugin_metrics(&registration.plugin_id),
+                )
             })
             .collect();
         result.sort_by_key(|p| (p.priority, p.plugin_id.clone()));
@@ -478,8 +499,22 @@ impl PluginHostManager {
             registration,
             process_state,
             enabled_fn(registration),
+            self.plugin_metrics(plugin_id),
         ))
     }
+
+    /// 单个插件的健康指标快照（请求延迟直方图、错误数、重启次数、内存）。
+    ///
+    /// 重启次数取自重启上下文的权威计数器；插件未加载时返回 None。
+    pub fn plugin_metrics(&self, plugin_id: &str) -> Option<PluginMetricsSnapshot> {
+        let metrics = self.metrics.get(plugin_id)?;
+        let restarts = self
+            .restart_contexts
+            .get(plugin_id)
+            .map(|ctx| ctx.restart_count.load(Ordering::SeqCst))
+            .unwrap_or_default();
+        Some(metrics.snapshot(restarts))
+    }
 }
 
 /// 由 PluginRegistration 构造运行时信息条目（list_plugin_info / get_plugin_info 共用）。
@@ -487,6 +522,7 @@ fn build_plugin_info(
     registration: &PluginRegistration,
     process_state: PluginRuntimeState,
     enabled: bool,
+    metrics: Option<PluginMetricsSnapshot>,
 ) -> InstalledPluginInfo {
     // 优先级统一取插件级元数据声明值（上层 list_plugins/plugin_info 不再覆盖，
     // 此即全部路径的唯一来源），不再取组件 priority 最小值。
@@ -509,9 +545,21 @@ fn build_plugin_info(
         hotkey: registration.metadata.hotkey.clone(),
         icon: registration.metadata.icon.clone(),
         mode: registration.metadata.mode,
+        permissions: granted_permission_keys(&registration.manifest),
+        metrics,
     }
 }
 
+/// manifest 已授予权限的键名列表（插件列表 / 安装结果共用）。
+pub fn granted_permission_keys(manifest: &Manifest) -> Vec<String> {
+    manifest
+        .permissions
+        .granted()
+        .into_iter()
+        .map(|p| p.key().to_string())
+        .collect()
+}
+
 /// Information about an installed plugin for the management UI / CLI.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InstalledPluginInfo {
@@ -548,6 +596,14 @@ pub struct InstalledPluginInfo {
     /// 行内/trigger 判定以此字段为唯一权威依据。
     #[serde(rename = "mode", default)]
     pub mode: PluginMode,
+    /// manifest `[permissions]` 已授予的权限键名（如 "shell"、"resources"）。
+    /// 内置插件不经 host/* RPC，恒为空。
+    #[serde(rename = "permissions", default)]
+    pub permissions: Vec<String>,
+    /// 宿主采集的健康指标（请求延迟/错误、重启次数、内存）。
+    /// 内置插件在宿主进程内运行，不采集，恒为空。
+    #[serde(rename = "metrics", default)]
+    pub metrics: Option<PluginMetricsSnapshot>,
 }
 
 // ─── Helpers ──────────────────────────────────────────────────────
@@ -603,6 +659,21 @@ fn validate_manifest(manifest: &Manifest, plugin_dir: &Path) -> Result<(), Plugi
         )));
     }
 
+    // 资源上限：声明为 0 的上限会让进程无法启动，视为配置错误
+    let limits = &manifest.runtime.limits;
+    for (key, value) in [
+        ("maxMemoryMb", limits.max_memory_mb),
+        ("maxCpuSeconds", limits.max_cpu_seconds),
+        ("maxOpenFiles", limits.max_open_files),
+    ] {
+        if value == Some(0) {
+            return Err(PluginLoadError::Manifest(format!(
+                "runtime.limits.{} must be greater than 0",
+                key
+            )));
+        }
+    }
+
     // Validate command path does not escape the plugin directory
     let cmd_path = plugin_dir.join(&manifest.runtime.command);
     let canonical_cmd = cmd_path
@@ -752,6 +823,9 @@ fn build_components(
         .icon
         .as_ref()
         .and_then(|icon| read_plugin_icon(plugin_dir, &icon.path));
+    // 全局查询资格：以 manifest [components].globalQuery 为准（宿主唯一源），
+    // 插件 RPC 自报值不采信——未在 manifest 声明的插件不会进入默认搜索链路
+    plugin_metadata.global_query = manifest.components.global_query;
     // name, description, supported_os, trigger_keywords, priority
     // 保留插件通过 plugin/get_metadata 自声明的值
     let plugin_metadata = Arc::new(plugin_metadata);
@@ -879,6 +953,7 @@ async fn handle_crash(mgr: &PluginHostManager, plugin_id: &str) {
             plugin_id, new_count, max_restart
         );
         mgr.restart_contexts.remove(plugin_id);
+        mgr.metrics.remove(plugin_id);
         return;
     }
 
@@ -907,6 +982,7 @@ async fn handle_crash(mgr: &PluginHostManager, plugin_id: &str) {
         Err(e) => {
             error!("Failed to restart plugin {}: {}", plugin_id, e);
             mgr.restart_contexts.remove(plugin_id);
+            mgr.metrics.remove(plugin_id);
         }
     }
 }
@@ -0,0 +1,255 @@
+//! PluginMetrics — per-plugin health metrics collected by the host.
+//!
+//! 每个第三方插件一份，跨崩溃重启保留（由 `PluginHostManager` 持有，
+//! 经 `PluginProcess::spawn` 交给 `JsonRpcClient` 记录请求耗时与结果）；
+//! 内存由资源监视任务周期采样写入。对外只暴露 `PluginMetricsSnapshot`。
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 请求延迟直方图的桶上界（毫秒，含上界）；超过最后一档的计入溢出桶。
+pub const LATENCY_BUCKETS_MS: [u64; 10] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 5000];
+
+/// 单次宿主 → 插件请求的结果分类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    /// 插件正常返回结果
+    Ok,
+    /// 插件返回 JSON-RPC 错误、响应无法解析或传输已断开
+    Error,
+    /// 超时未响应
+    Timeout,
+}
+
+/// 单个方法的累计统计（内部可变状态）。
+#[derive(Debug, Default)]
+struct MethodStats {
+    count: u64,
+    errors: u64,
+    timeouts: u64,
+    total_ms: u64,
+    max_ms: u64,
+    /// 与 `LATENCY_BUCKETS_MS` 一一对应，末位为溢出桶
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl MethodStats {
+    /// 估算分位数：返回累计计数首次达到 `q` 比例的桶上界；落入溢出桶时取最大观测值。
+    fn quantile_ms(&self, q: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let target = ((self.count as f64) * q).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= target {
+                return LATENCY_BUCKETS_MS
+                    .get(i)
+                    .map_or(self.max_ms, |bound| (*bound).min(self.max_ms));
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// 单个插件的健康指标收集器。
+///
+/// 请求统计在短临界区内更新（parking_lot::Mutex，不跨 .await）；
+/// 内存为原子量，值 0 表示尚无采样（或平台不支持采样）。
+#[derive(Debug, Default)]
+pub struct PluginMetrics {
+    methods: Mutex<HashMap<String, MethodStats>>,
+    memory_bytes: AtomicU64,
+    peak_memory_bytes: AtomicU64,
+}
+
+impl PluginMetrics {
+    /// 记录一次请求：按方法累计次数、耗时直方图与错误/超时计数。
+    pub fn record_call(&self, method: &str, elapsed: Duration, outcome: CallOutcome) {
+        let ms = elapsed.as_millis().min(u64::MAX as u128) as u64;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        let mut methods = self.methods.lock();
+        let stats = methods.entry(method.to_string()).or_default();
+        stats.count += 1;
+        stats.total_ms = stats.total_ms.saturating_add(ms);
+        stats.max_ms = stats.max_ms.max(ms);
+        stats.buckets[bucket] += 1;
+        match outcome {
+            CallOutcome::Ok => {}
+            CallOutcome::Error => stats.errors += 1,
+            CallOutcome::Timeout => stats.timeouts += 1,
+        }
+    }
+
+    /// 写入一次常驻内存采样（字节），同时刷新峰值。
+    pub fn record_memory(&self, bytes: u64) {
+        self.memory_bytes.store(bytes, Ordering::Relaxed);
+        self.peak_memory_bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    /// 进程退出后清除当前内存值（峰值保留），避免展示已失效的采样。
+    pub fn clear_memory(&self) {
+        self.memory_bytes.store(0, Ordering::Relaxed);
+    }
+
+    /// 生成可序列化快照；`restarts` 由调用方从重启上下文取得（权威计数不在此处）。
+    /// 方法条目按方法名排序，保证输出稳定。
+    pub fn snapshot(&self, restarts: u32) -> PluginMetricsSnapshot {
+        let non_zero = |v: u64| (v > 0).then_some(v);
+        let mut methods: Vec<MethodMetrics> = self
+            .methods
+            .lock()
+            .iter()
+            .map(|(method, stats)| MethodMetrics {
+                method: method.clone(),
+                count: stats.count,
+                errors: stats.errors,
+                timeouts: stats.timeouts,
+                avg_ms: stats.total_ms.checked_div(stats.count).unwrap_or(0),
+                max_ms: stats.max_ms,
+                p50_ms: stats.quantile_ms(0.5),
+                p95_ms: stats.quantile_ms(0.95),
+                buckets: stats
+                    .buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, count)| LatencyBucket {
+                        le_ms: LATENCY_BUCKETS_MS.get(i).copied(),
+                        count: *count,
+                    })
+                    .collect(),
+            })
+            .collect();
+        methods.sort_by(|a, b| a.method.cmp(&b.method));
+
+        PluginMetricsSnapshot {
+            restarts,
+            memory_bytes: non_zero(self.memory_bytes.load(Ordering::Relaxed)),
+            peak_memory_bytes: non_zero(self.peak_memory_bytes.load(Ordering::Relaxed)),
+            methods,
+        }
+    }
+}
+
+/// 插件健康指标快照（经 `InstalledPluginInfo.metrics` 与 CLI `/v1/plugins/{id}/metrics` 下发）。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PluginMetricsSnapshot {
+    /// 本次加载以来的崩溃重启次数
+    #[serde(rename = "restarts", default)]
+    pub restarts: u32,
+    /// 当前常驻内存（字节）；未采样或平台不支持时为空
+    #[serde(rename = "memoryBytes", default)]
+    pub memory_bytes: Option<u64>,
+    /// 观测到的常驻内存峰值（字节），跨重启保留
+    #[serde(rename = "peakMemoryBytes", default)]
+    pub peak_memory_bytes: Option<u64>,
+    /// 按方法名排序的请求统计
+    #[serde(rename = "methods", default)]
+    pub methods: Vec<MethodMetrics>,
+}
+
+/// 单个 JSON-RPC 方法的请求统计。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MethodMetrics {
+    /// 方法名，如 `plugin/query`
+    #[serde(rename = "method", default)]
+    pub method: String,
+    /// 请求总数（含失败与超时）
+    #[serde(rename = "count", default)]
+    pub count: u64,
+    /// 返回错误的次数（不含超时）
+    #[serde(rename = "errors", default)]
+    pub errors: u64,
+    /// 超时次数
+    #[serde(rename = "timeouts", default)]
+    pub timeouts: u64,
+    /// 平均耗时（毫秒）
+    #[serde(rename = "avgMs", default)]
+    pub avg_ms: u64,
+    /// 最大耗时（毫秒）
+    #[serde(rename = "maxMs", default)]
+    pub max_ms: u64,
+    /// 中位数耗时估计（毫秒，取所在桶上界）
+    #[serde(rename = "p50Ms", default)]
+    pub p50_ms: u64,
+    /// 95 分位耗时估计（毫秒，取所在桶上界）
+    #[serde(rename = "p95Ms", default)]
+    pub p95_ms: u64,
+    /// 延迟直方图（非累计），与 `LATENCY_BUCKETS_MS` 对应，末项为溢出桶
+    #[serde(rename = "buckets", default)]
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// 延迟直方图的一个桶。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LatencyBucket {
+    /// 桶上界（毫秒，含）；溢出桶为空
+    #[serde(rename = "leMs", default)]
+    pub le_ms: Option<u64>,
+    /// 落入该桶的请求数
+    #[serde(rename = "count", default)]
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 请求按耗时落入对应桶，错误与超时分别计数，方法按名称排序。
+    #[test]
+    fn records_calls_into_histogram() {
+        let metrics = PluginMetrics::default();
+        for ms in [0, 3, 3, 40, 7000] {
+            metrics.record_call("plugin/query", Duration::from_millis(ms), CallOutcome::Ok);
+        }
+        metrics.record_call("plugin/query", Duration::from_millis(2), CallOutcome::Error);
+        metrics.record_call(
+            "plugin/execute",
+            Duration::from_millis(5000),
+            CallOutcome::Timeout,
+        );
+
+        let snapshot = metrics.snapshot(2);
+        assert_eq!(snapshot.restarts, 2);
+        let names: Vec<&str> = snapshot.methods.iter().map(|m| m.method.as_str()).collect();
+        assert_eq!(names, ["plugin/execute", "plugin/query"]);
+
+        let query = &snapshot.methods[1];
+        assert_eq!(query.count, 6);
+        assert_eq!(query.errors, 1);
+        assert_eq!(query.timeouts, 0);
+        assert_eq!(query.max_ms, 7000);
+        let counts: Vec<u64> = query.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, [1, 3, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(query.buckets.last().unwrap().le_ms, None);
+        assert_eq!(query.p50_ms, 5);
+        assert_eq!(query.p95_ms, 7000);
+
+        assert_eq!(snapshot.methods[0].timeouts, 1);
+    }
+
+    /// 内存采样保留峰值；清除后当前值为空而峰值保留。
+    #[test]
+    fn memory_sampling_tracks_peak() {
+        let metrics = PluginMetrics::default();
+        assert_eq!(metrics.snapshot(0).memory_bytes, None);
+
+        metrics.record_memory(300);
+        metrics.record_memory(100);
+        let snapshot = metrics.snapshot(0);
+        assert_eq!(snapshot.memory_bytes, Some(100));
+        assert_eq!(snapshot.peak_memory_bytes, Some(300));
+
+        metrics.clear_memory();
+        let snapshot = metrics.snapshot(0);
+        assert_eq!(snapshot.memory_bytes, None);
+        assert_eq!(snapshot.peak_memory_bytes, Some(300));
+    }
+}
@@ -9,13 +9,14 @@ use tracing::{debug, error, info, warn};
 
 use zerolaunch_plugin_api::config::{ConfigActionDef, SettingDefinition};
 use zerolaunch_plugin_api::{PanelInteraction, PluginMetadata, ResultAction};
-use zerolaunch_plugin_protocol::manifest::Manifest;
+use zerolaunch_plugin_protocol::manifest::{Manifest, ResourceLimits};
 use zerolaunch_plugin_protocol::messages::*;
 use zerolaunch_plugin_protocol::methods::plugin as plugin_methods;
 use zerolaunch_plugin_protocol::{ProtocolError, PROTOCOL_VERSION};
 
 use crate::client::{IncomingRequest, JsonRpcClient};
 use crate::host_dispatch::HostCallHandler;
+use crate::metrics::PluginMetrics;
 use crate::transport::stdio::StdioTransport;
 
 /// Tracks the lifecycle state of a plugin subprocess.
@@ -64,6 +65,9 @@ pub struct PluginProcess {
     pub restart_count: u32,
     /// 子进程的 PID，用于强制终止（当优雅关闭超时时兜底）。
     pub pid: Option<u32>,
+    /// Windows 资源上限作业对象；随 PluginProcess 一同释放，释放时终止插件进程树。
+    #[cfg(windows)]
+    _job: Option<zerolaunch_plugin_api::common::JobObject>,
 }
 
 impl PluginProcess {
@@ -73,6 +77,8 @@ impl PluginProcess {
     /// `crash_tx` is a channel sender owned by the `PluginHostManager`.
     /// When the watchdog detects a crash, it sends the `plugin_id` on this
     /// channel so the manager can trigger a re-spawn.
+    /// `metrics` is owned by the manager and outlives restarts; the RPC client
+    /// records every call into it and the resource monitor samples memory.
     /// 启动插件子进程并完成握手与协议版本兼容性校验。
     /// 参数较多（进程/目录/回调/计数/locale），按先例 allow too_many_arguments。
     #[allow(clippy::too_many_arguments)]
@@ -87,6 +93,7 @@ impl PluginProcess {
         restart_count: u32,
         // 宿主当前界面语言（如 "zh-Hans"），随 initialize 握手下发。
         locale: &str,
+        metrics: Arc<PluginMetrics>,
     ) -> Result<Self, ProtocolError> {
         let plugin_id = manifest.plugin.id.clone();
 
@@ -113,9 +120,16 @@ impl PluginProcess {
         ];
 
         info!("Spawning plugin {}: {:?}", plugin_id, cmd_path);
+        warn_unenforced_limits(&plugin_id, &manifest.runtime.limits);
 
-        let transport =
-            StdioTransport::spawn(&cmd_path, &manifest.runtime.args, plugin_dir, &env).await?;
+        let transport = StdioTransport::spawn(
+            &cmd_path,
+            &manifest.runtime.args,
+            plugin_dir,
+            &env,
+            &manifest.runtime.limits,
+        )
+        .await?;
 
         let pid = transport.pid();
         // Split transport so we can keep the child handle for health monitoring
@@ -124,6 +138,8 @@ impl PluginProcess {
             stdin: child_stdin,
             stdout: child_stdout,
             stderr: child_stderr,
+            #[cfg(windows)]
+            job,
         } = transport;
         let child_handle = Arc::new(parking_lot::Mutex::new(Some(child)));
 
@@ -150,7 +166,7 @@ impl PluginProcess {
 
         // Set up incoming request/notification channels
         let (incoming_request_tx, mut incoming_request_rx) = mpsc::channel::<IncomingRequest>(64);
-        let (incoming_notification_tx, _incoming_notification_rx) =
+        let (incoming_notification_tx, mut incoming_notification_rx) =
             mpsc::channel::<(String, serde_json::Value)>(64);
 
         let client = JsonRpcClient::new(
@@ -158,8 +174,24 @@ impl PluginProcess {
             child_stdin,
             incoming_request_tx,
             incoming_notification_tx,
+            metrics.clone(),
         );
 
+        // 查询超时且取消后仍无应答的插件视为卡死：强杀进程，由 watchdog 按崩溃重启
+        let mut unresponsive_rx = client.subscribe_unresponsive();
+        let hung_plugin_id = plugin_id.clone();
+        tokio::spawn(async move {
+            if let Some(method) = unresponsive_rx.recv().await {
+                warn!(
+                    "Plugin {} did not answer cancelled {} call, killing process",
+                    hung_plugin_id, method
+                );
+                if let Some(pid) = pid {
+                    force_kill_process(pid);
+                }
+            }
+        });
+
         // Spawn task to handle incoming requests from the plugin (host/* calls)
         let hc = host_call_handler.clone();
         let cl = client.clone();
@@ -184,6 +216,14 @@ impl PluginProcess {
             }
         });
 
+        // Spawn task to handle incoming notifications from the plugin (e.g. host/query.update)
+        let hn = host_call_handler.clone();
+        tokio::spawn(async move {
+            while let Some((method, params)) = incoming_notification_rx.recv().await {
+                hn.handle_host_notification(&method, params).await;
+            }
+        });
+
         let host_version = env!("CARGO_PKG_VERSION").to_string();
         let protocol_version = zerolaunch_plugin_protocol::PROTOCOL_VERSION.to_string();
 
@@ -231,10 +271,14 @@ impl PluginProcess {
             crash_tx,
             restart_count,
             pid,
+            #[cfg(windows)]
+            _job: job,
         };
 
         // Start health monitoring
         process.spawn_watchdog();
+        #[cfg(target_os = "linux")]
+        process.spawn_resource_monitor(metrics);
 
         Ok(process)
     }
@@ -475,6 +519,35 @@ impl PluginProcess {
         });
     }
 
+    /// 生成资源监视任务：每 `RESOURCE_SAMPLE_INTERVAL` 采样一次子进程常驻内存写入指标。
+    /// 仅用于观测——`maxMemoryMb` 由 spawn 时设置的 RLIMIT_AS 强制，不依赖采样。
+    ///
+    /// 仅 Linux（读取 `/proc/<pid>/status`）。进程离开 Running 状态或 /proc 条目消失时
+    /// 任务退出，并清除当前内存值（峰值保留）。
+    #[cfg(target_os = "linux")]
+    fn spawn_resource_monitor(&self, metrics: Arc<PluginMetrics>) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+        let state = self.state.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(RESOURCE_SAMPLE_INTERVAL);
+            loop {
+                ticker.tick().await;
+                // 状态读取为短临界区，guard 在条件求值后即释放，不跨 .await
+                if !matches!(*state.read(), ProcessState::Running) {
+                    break;
+                }
+                let Some(rss) = read_rss_bytes(pid).await else {
+                    break;
+                };
+                metrics.record_memory(rss);
+            }
+            metrics.clear_memory();
+        });
+    }
+
     /// Graceful shutdown: send plugin/shutdown, wait, then force-kill if needed.
     pub async fn shutdown(self, timeout: Duration) {
         let plugin_id = self.plugin_id.clone();
@@ -530,6 +603,52 @@ pub(crate) fn force_kill_process(pid: u32) {
     }
 }
 
+/// 资源监视任务的内存采样间隔。
+#[cfg(target_os = "linux")]
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 读取进程当前常驻内存（字节）；进程已退出或 /proc 不可读时返回 None。
+#[cfg(target_os = "linux")]
+async fn read_rss_bytes(pid: u32) -> Option<u64> {
+    let status = tokio::fs::read_to_string(format!("/proc/{}/status", pid))
+        .await
+        .ok()?;
+    parse_vm_rss(&status)
+}
+
+/// 从 `/proc/<pid>/status` 内容中解析 `VmRSS`（单位 kB）并换算为字节。
+#[cfg(target_os = "linux")]
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line["VmRSS:".len()..]
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+/// 对当前平台无法强制的资源上限告警：声明了但不生效的上限不应静默放过。
+/// 内存上限与 CPU 时间依赖 Unix setrlimit 或 Windows 作业对象；文件数仅 Unix setrlimit 可限。
+fn warn_unenforced_limits(plugin_id: &str, limits: &ResourceLimits) {
+    let mut unenforced = Vec::new();
+    if limits.max_memory_mb.is_some() && !cfg!(any(unix, windows)) {
+        unenforced.push("maxMemoryMb");
+    }
+    if limits.max_cpu_seconds.is_some() && !cfg!(any(unix, windows)) {
+        unenforced.push("maxCpuSeconds");
+    }
+    if limits.max_open_files.is_some() && !cfg!(unix) {
+        unenforced.push("maxOpenFiles");
+    }
+    if !unenforced.is_empty() {
+        warn!(
+            "Plugin {} declares runtime.limits {:?}, which are not enforced on this platform",
+            plugin_id, unenforced
+        );
+    }
+}
+
 /// Maximum size of a plugin stderr log file (10 MB).
 const MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
 
@@ -596,4 +715,15 @@ mod tests {
         assert!(!protocol_version_compatible("", "1.0"));
         assert!(!protocol_version_compatible("1.0", ""));
     }
+
+    /// VmRSS 以 kB 给出，换算为字节；缺失该行（如内核线程）返回 None。
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_vm_rss_from_proc_status() {
+        use super::parse_vm_rss;
+
+        let status = "Name:\tplugin\nVmPeak:\t  20480 kB\nVmRSS:\t    1536 kB\nThreads:\t4\n";
+        assert_eq!(parse_vm_rss(status), Some(1536 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tkthreadd\nThreads:\t1\n"), None);
+    }
 }
@@ -2,6 +2,7 @@ use std::path::Path;
 use std::process::Stdio;
 use tokio::io::{BufReader, BufWriter};
 use tokio::process::Child;
+use zerolaunch_plugin_protocol::manifest::ResourceLimits;
 use zerolaunch_plugin_protocol::ProtocolError;
 
 /// 子进程传输层：启动插件进程并接好 stdin/stdout/stderr 三根管道。
@@ -21,6 +22,10 @@ pub struct StdioTransport {
     pub stdout: BufReader<tokio::process::ChildStdout>,
     /// 子进程的标准错误：子进程往这里写，宿主写入日志文件。
     pub stderr: tokio::process::ChildStderr,
+    /// Windows 上承载内存 / CPU 时间上限的作业对象（未声明相应上限时为 None）。
+    /// 持有者 drop 时作业关闭并终止插件进程树。
+    #[cfg(windows)]
+    pub job: Option<zerolaunch_plugin_api::common::JobObject>,
 }
 
 impl StdioTransport {
@@ -31,6 +36,8 @@ impl StdioTransport {
     /// - `args`: 启动参数（来自 manifest.runtime.args）
     /// - `cwd`: 工作目录（设为 plugin_dir）
     /// - `env`: 自定义环境变量（ZEROLAUNCH_PLUGIN_ID / ZEROLAUNCH_DATA_DIR / ZEROLAUNCH_LOG_DIR）
+    /// - `limits`: manifest `[runtime.limits]`；Unix 上内存、CPU 时间与文件数经 setrlimit 在 exec 前施加；
+    ///   Windows 上内存与 CPU 时间由作业对象施加
     ///
     /// # 返回
     /// 组装好的 StdioTransport，调用方拆解后分发三个管道。
@@ -39,6 +46,7 @@ impl StdioTransport {
         args: &[String],
         cwd: &Path,
         env: &[(String, String)],
+        limits: &ResourceLimits,
     ) -> Result<Self, ProtocolError> {
         // --- 1. 准备命令 ---
         let mut cmd = tokio::process::Command::new(command);
@@ -57,14 +65,28 @@ impl StdioTransport {
             cmd.env(k, v);
         }
 
-        // --- 3. 启动子进程 ---
+        // --- 3. 施加资源上限（Unix 经 setrlimit；Windows 在启动后加入作业对象） ---
+        #[cfg(unix)]
+        apply_rlimits(&mut cmd, limits);
+        #[cfg(not(any(unix, windows)))]
+        let _ = limits;
+
+        // --- 4. 启动子进程 ---
         // 此时子进程已经开始运行。它可能已经在尝试从 stdin 读取数据，
         // 或者向 stdout/stderr 写入数据了。
         let mut child = cmd.spawn().map_err(|e| {
             ProtocolError::InvalidFrame(format!("failed to spawn '{}': {}", command.display(), e))
         })?;
+        #[cfg(windows)]
+        let job = assign_job_object(&child, limits).map_err(|e| {
+            ProtocolError::InvalidFrame(format!(
+                "failed to apply resource limits to '{}': {}",
+                command.display(),
+                e
+            ))
+        })?;
 
-        // --- 4. 从子进程中取出三个管道的宿主侧句柄 ---
+        // --- 5. 从子进程中取出三个管道的宿主侧句柄 ---
         //
         // tokio::process::Command::spawn() 返回的 Child 结构体内部持有三个 Option<> 管道。
         // .take() 取出 Some(...) 值并将其替换为 None（所有权转移）。
@@ -103,6 +125,8 @@ impl StdioTransport {
             stdin,
             stdout,
             stderr,
+            #[cfg(windows)]
+            job,
         })
     }
 
@@ -111,3 +135,104 @@ impl StdioTransport {
         self.child.id()
     }
 }
+
+/// 声明了内存或 CPU 时间上限时创建作业对象并把子进程加入其中。
+/// 作业限额按进程生效：提交内存超限时分配失败，用户态 CPU 时间超限时进程被终止。
+/// 失败时返回错误（child 随之 drop 并被 kill_on_drop 回收），避免插件在未受限状态下运行。
+#[cfg(windows)]
+fn assign_job_object(
+    child: &Child,
+    limits: &ResourceLimits,
+) -> std::io::Result<Option<zerolaunch_plugin_api::common::JobObject>> {
+    if limits.max_memory_mb.is_none() && limits.max_cpu_seconds.is_none() {
+        return Ok(None);
+    }
+    let job = zerolaunch_plugin_api::common::JobObject::new(
+        limits.max_memory_mb.map(|mb| mb * 1024 * 1024),
+        limits.max_cpu_seconds.map(std::time::Duration::from_secs),
+    )?;
+    job.assign(child)?;
+    Ok(Some(job))
+}
+
+/// 在 fork 之后、exec 之前为子进程设置 RLIMIT_AS / RLIMIT_CPU / RLIMIT_NOFILE（软硬上限相同）。
+///
+/// 地址空间超限时插件的内存分配（mmap/brk）直接失败，由内核即时强制、没有采样间隙；
+/// 注意限制的是虚拟地址空间而非常驻内存，预留大块虚拟内存的运行时需声明更宽的上限。
+/// 超出 CPU 时间由内核以 SIGXCPU/SIGKILL 终止进程，宿主 watchdog 按崩溃处理；
+/// 文件数超限时插件的 open 调用返回 EMFILE。setrlimit 失败让 spawn 返回错误，
+/// 避免插件在未受限状态下运行。未声明任何相关上限时不注册 pre_exec。
+#[cfg(unix)]
+fn apply_rlimits(cmd: &mut tokio::process::Command, limits: &ResourceLimits) {
+    let rlimits: Vec<(libc::c_int, u64)> = [
+        (
+            libc::RLIMIT_AS as libc::c_int,
+            limits
+                .max_memory_mb
+                .map(|mb| mb.saturating_mul(1024 * 1024)),
+        ),
+        (libc::RLIMIT_CPU as libc::c_int, limits.max_cpu_seconds),
+        (libc::RLIMIT_NOFILE as libc::c_int, limits.max_open_files),
+    ]
+    .into_iter()
+    .filter_map(|(resource, value)| value.map(|v| (resource, v)))
+    .collect();
+    if rlimits.is_empty() {
+        return;
+    }
+
+    // SAFETY: 闭包在 fork 后的子进程中执行，只调用 async-signal-safe 的 setrlimit，
+    // 不分配内存、不加锁（rlimits 已在 fork 前构造完毕，仅按引用读取）。
+    unsafe {
+        cmd.pre_exec(move || {
+            for (resource, value) in &rlimits {
+                let limit = libc::rlimit {
+                    rlim_cur: *value as libc::rlim_t,
+                    rlim_max: *value as libc::rlim_t,
+                };
+                if libc::setrlimit(*resource as _, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    /// 声明的上限在 exec 前生效：子 shell 看到的 `ulimit` 与 manifest 一致（内存按 KiB 报告）。
+    #[tokio::test]
+    async fn rlimits_are_applied_before_exec() {
+        let limits = ResourceLimits {
+            max_memory_mb: Some(512),
+            max_cpu_seconds: Some(30),
+            max_open_files: Some(64),
+        };
+        let args = [
+            "-c".to_string(),
+            "ulimit -v; ulimit -t; ulimit -n".to_string(),
+        ];
+        let mut transport = StdioTransport::spawn(
+            Path::new("/bin/sh"),
+            &args,
+            &std::env::temp_dir(),
+            &[],
+            &limits,
+        )
+        .await
+        .expect("spawn sh");
+
+        let mut output = String::new();
+        transport
+            .stdout
+            .read_to_string(&mut output)
+            .await
+            .expect("read stdout");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines, ["524288", "30", "64"]);
+    }
+}
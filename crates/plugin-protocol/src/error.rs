@@ -71,4 +71,8 @@ pub mod codes {
     pub const PLUGIN_CRASHED: i32 = -32001;
     pub const TIMEOUT_ERROR: i32 = -32002;
     pub const UNSUPPORTED_COMPONENT: i32 = -32003;
+    /// 插件调用了 manifest `[permissions]` 未授予的 `host/*` 方法。
+    pub const PERMISSION_DENIED: i32 = -32004;
+    /// 请求已被 `$/cancelRequest` 取消（与 LSP RequestCancelled 同值）。
+    pub const REQUEST_CANCELLED: i32 = -32800;
 }
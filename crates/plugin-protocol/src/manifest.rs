@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::methods::host;
+
 /// 顶层插件 manifest，从 `manifest.toml` 反序列化，也可序列化为 JSON 返回给前端。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
@@ -12,6 +14,9 @@ pub struct Manifest {
     /// 组件声明（插件对外提供哪些能力）
     #[serde(default, rename = "components")]
     pub components: ComponentsSection,
+    /// 权限声明（未声明的权限一律拒绝，宿主在每次 `host/*` 调用前校验）
+    #[serde(default, rename = "permissions")]
+    pub permissions: PermissionsSection,
     /// 前端 UI 入口（第三方插件可选的 Vue 面板）
     #[serde(default, rename = "ui")]
     pub ui: Option<UiSection>,
@@ -67,6 +72,34 @@ pub struct RuntimeSection {
     /// 最大重启次数，超过后不再自动拉起，默认 3
     #[serde(default = "default_max_restart", rename = "maxRestart")]
     pub max_restart: u32,
+    /// 资源上限（`[runtime.limits]`，可选），未声明的项不设限
+    #[serde(default, rename = "limits")]
+    pub limits: ResourceLimits,
+}
+
+/// 资源上限段（`[runtime.limits]`）。
+/// 每项可选，缺省即不限制；宿主在启动子进程时施加，超限进程按崩溃处理（受 maxRestart 约束）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// 内存上限，单位 MiB；经 RLIMIT_AS（Unix，限制虚拟地址空间）或作业对象
+    /// （Windows，限制提交内存）由内核强制，超限时分配失败
+    #[serde(default, rename = "maxMemoryMb")]
+    pub max_memory_mb: Option<u64>,
+    /// 累计 CPU 时间上限，单位秒；经 RLIMIT_CPU（Unix）或作业对象（Windows）由内核强制
+    #[serde(default, rename = "maxCpuSeconds")]
+    pub max_cpu_seconds: Option<u64>,
+    /// 同时打开的文件描述符上限；经 RLIMIT_NOFILE 由内核强制（Unix）
+    #[serde(default, rename = "maxOpenFiles")]
+    pub max_open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// 是否未声明任何上限
+    pub fn is_unlimited(&self) -> bool {
+        self.max_memory_mb.is_none()
+            && self.max_cpu_seconds.is_none()
+            && self.max_open_files.is_none()
+    }
 }
 
 /// 组件声明段。
@@ -78,6 +111,143 @@ pub struct ComponentsSection {
     /// 每个能力在构建期对应一个 `RemoteConfigurableAdapter`。
     #[serde(default = "default_provides", rename = "provides")]
     pub provides: Vec<String>,
+    /// 是否参与全局查询（默认搜索中贡献结果，经 `plugin/global_query` 调用）。
+    /// 以 manifest 声明为准，宿主据此覆盖插件自报的 `PluginMetadata.global_query`。
+    #[serde(default, rename = "globalQuery")]
+    pub global_query: bool,
+}
+
+/// 权限声明段。
+/// 每项默认 false：未在 manifest 中显式授予的能力，插件经 `host/*` 调用时返回
+/// `codes::PERMISSION_DENIED`。安装时与 CLI 插件列表中展示已授予的权限。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionsSection {
+    /// 打开文件/文件夹、执行命令、激活其他进程窗口
+    #[serde(default, rename = "shell")]
+    pub shell: bool,
+    /// 以管理员权限启动程序（提权）
+    #[serde(default, rename = "elevation")]
+    pub elevation: bool,
+    /// 读写剪贴板
+    #[serde(default, rename = "clipboard")]
+    pub clipboard: bool,
+    /// 发送系统通知
+    #[serde(default, rename = "notifications")]
+    pub notifications: bool,
+    /// 枚举已安装应用
+    #[serde(default, rename = "appEnumeration")]
+    pub app_enumeration: bool,
+    /// 读写插件资源存储（host/resource.*）
+    #[serde(default, rename = "resources")]
+    pub resources: bool,
+    /// 访问网络（声明性权限：宿主暂无网络类 host 方法，仅用于安装时告知用户）
+    #[serde(default, rename = "network")]
+    pub network: bool,
+}
+
+impl PermissionsSection {
+    /// 判断是否已授予指定权限
+    pub fn allows(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::Shell => self.shell,
+            Permission::Elevation => self.elevation,
+            Permission::Clipboard => self.clipboard,
+            Permission::Notifications => self.notifications,
+            Permission::AppEnumeration => self.app_enumeration,
+            Permission::Resources => self.resources,
+            Permission::Network => self.network,
+        }
+    }
+
+    /// 已授予的权限列表（按 Permission::ALL 顺序）
+    pub fn granted(&self) -> Vec<Permission> {
+        Permission::ALL
+            .iter()
+            .copied()
+            .filter(|p| self.allows(*p))
+            .collect()
+    }
+}
+
+/// 插件可申请的权限种类（与 `PermissionsSection` 字段一一对应）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// 打开文件/文件夹、执行命令、激活其他进程窗口
+    Shell,
+    /// 以管理员权限启动程序
+    Elevation,
+    /// 读写剪贴板
+    Clipboard,
+    /// 发送系统通知
+    Notifications,
+    /// 枚举已安装应用
+    AppEnumeration,
+    /// 读写插件资源存储
+    Resources,
+    /// 访问网络
+    Network,
+}
+
+impl Permission {
+    /// 全部权限种类（展示顺序）
+    pub const ALL: [Permission; 7] = [
+        Permission::Shell,
+        Permission::Elevation,
+        Permission::Clipboard,
+        Permission::Notifications,
+        Permission::AppEnumeration,
+        Permission::Resources,
+        Permission::Network,
+    ];
+
+    /// manifest `[permissions]` 段中的键名
+    pub fn key(self) -> &'static str {
+        match self {
+            Permission::Shell => "shell",
+            Permission::Elevation => "elevation",
+            Permission::Clipboard => "clipboard",
+            Permission::Notifications => "notifications",
+            Permission::AppEnumeration => "appEnumeration",
+            Permission::Resources => "resources",
+            Permission::Network => "network",
+        }
+    }
+
+    /// `host/*` 方法的授权要求。
+    /// 新增 host 方法时必须在此归类：未归类的方法按未知方法处理，宿主一律拒绝调用。
+    pub fn required_for(method: &str) -> HostMethodAccess {
+        match method {
+            host::LOG
+            | host::ICON_GET
+            | host::PATH_RESOLVE
+            | host::PARAMETER_RESOLVE
+            | host::GET_LOCALE => HostMethodAccess::Open,
+            host::NOTIFY => HostMethodAccess::Requires(Permission::Notifications),
+            host::SHELL_OPEN
+            | host::SHELL_OPEN_FOLDER
+            | host::SHELL_EXECUTE_COMMAND
+            | host::WINDOW_ACTIVATE_BY_PROCESS => HostMethodAccess::Requires(Permission::Shell),
+            host::SHELL_EXECUTE_ELEVATION => HostMethodAccess::Requires(Permission::Elevation),
+            host::APP_ENUMERATE => HostMethodAccess::Requires(Permission::AppEnumeration),
+            host::RESOURCE_UPLOAD
+            | host::RESOURCE_PUT
+            | host::RESOURCE_GET
+            | host::RESOURCE_DELETE
+            | host::RESOURCE_LIST => HostMethodAccess::Requires(Permission::Resources),
+            _ => HostMethodAccess::Unknown,
+        }
+    }
+}
+
+/// `host/*` 方法的授权要求（`Permission::required_for` 的结果）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostMethodAccess {
+    /// 无需授权（日志、图标、路径、参数解析、语言查询）
+    Open,
+    /// 需要 manifest 授予指定权限
+    Requires(Permission),
+    /// 宿主未知的方法：按未授权处理，返回 METHOD_NOT_FOUND
+    Unknown,
 }
 
 /// 前端 UI 入口段（可选）。
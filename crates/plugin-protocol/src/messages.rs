@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use zerolaunch_plugin_api::config::ComponentType;
-use zerolaunch_plugin_api::{ExecutionContext, PluginContext, Query, SearchCandidate, TargetType};
+use zerolaunch_plugin_api::{
+    ExecutionContext, PluginContext, Query, QueryResponse, SearchCandidate, TargetType,
+};
 
 // ─── plugin/initialize ───────────────────────────────────────────
 
@@ -280,6 +282,31 @@ pub struct NotifyParams {
     pub message: String,
 }
 
+// ─── $/cancelRequest ─────────────────────────────────────────────
+
+/// $/cancelRequest 通知参数（宿主 → 插件，无响应）。
+/// 插件收到后取消对应请求的 PluginContext 取消令牌；请求已完成时忽略。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequestParams {
+    /// 被取消请求的 JSON-RPC id。
+    #[serde(rename = "id", default)]
+    pub id: u64,
+}
+
+// ─── host/query.update ───────────────────────────────────────────
+
+/// host/query.update 通知参数（插件 → 宿主，无响应）。
+/// 插件在 plugin/query 处理期间或返回后，按查询版本推送增量结果。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryUpdateParams {
+    /// 目标查询版本（取自 plugin/query 下发的 ctx.query_revision）；过期版本由宿主丢弃。
+    #[serde(rename = "revision", default)]
+    pub revision: u64,
+    /// 增量结果：仅 List / CustomPanel 生效，其余变体由宿主忽略。
+    #[serde(rename = "response")]
+    pub response: QueryResponse,
+}
+
 // ─── host/shell.open ─────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +11,7 @@ pub mod plugin {
     pub const CONFIG_ACTIONS: &str = "plugin/config_actions";
     pub const EXECUTE_CONFIG_ACTION: &str = "plugin/execute_config_action";
     pub const QUERY: &str = "plugin/query";
+    pub const GLOBAL_QUERY: &str = "plugin/global_query";
     pub const EXECUTE_ACTION: &str = "plugin/execute_action";
     pub const INIT: &str = "plugin/init";
     pub const INTERACTION_POLICY: &str = "plugin/interaction_policy";
@@ -46,4 +47,8 @@ pub mod host {
 pub mod notify {
     pub const PLUGIN_READY: &str = "plugin/ready";
     pub const PLUGIN_CRASHED: &str = "plugin/crashed";
+    /// 插件 → 宿主：推送当前查询版本的增量结果（List 条目或 CustomPanel 更新）。
+    pub const QUERY_UPDATE: &str = "host/query.update";
+    /// 宿主 → 插件：取消仍在处理中的请求（参数为原请求 id），用于被更新查询取代的 plugin/query。
+    pub const CANCEL_REQUEST: &str = "$/cancelRequest";
 }
@@ -107,3 +107,23 @@ fn test_component_descriptor_roundtrip() {
     assert_eq!(round.component_id, "com.example.test");
     assert_eq!(round.priority, 100);
 }
+
+#[test]
+fn test_host_method_access_fails_closed() {
+    use zerolaunch_plugin_protocol::methods::host;
+    use zerolaunch_plugin_protocol::{HostMethodAccess, Permission};
+
+    assert_eq!(Permission::required_for(host::LOG), HostMethodAccess::Open);
+    assert_eq!(
+        Permission::required_for(host::SHELL_EXECUTE_COMMAND),
+        HostMethodAccess::Requires(Permission::Shell)
+    );
+    assert_eq!(
+        Permission::required_for(host::RESOURCE_PUT),
+        HostMethodAccess::Requires(Permission::Resources)
+    );
+    // 未归类的方法（含拼写变体与其他命名空间）一律视为未知
+    for method in ["host/fs.read", "host/LOG", "plugin/query", ""] {
+        assert_eq!(Permission::required_for(method), HostMethodAccess::Unknown);
+    }
+}
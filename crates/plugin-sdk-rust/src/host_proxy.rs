@@ -12,7 +12,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 
+use zerolaunch_plugin_api::{PluginContext, QueryChannel, QueryResponse};
 use zerolaunch_plugin_protocol::codec::encode_frame;
+use zerolaunch_plugin_protocol::methods::notify;
+use zerolaunch_plugin_protocol::QueryUpdateParams;
 
 use base64::Engine as _;
 
@@ -110,6 +113,32 @@ impl HostProxy {
         }
     }
 
+    /// 为当前查询推送增量结果（host/query.update 通知，无响应）。
+    ///
+    /// 可在 `query` 处理期间多次调用，每次覆盖该查询当前展示内容；
+    /// 仅 `List` / `CustomPanel` 生效。宿主按 ctx 中的查询版本号丢弃过期更新，
+    /// `query` 返回后到达的更新同样丢弃（返回值即最终结果）。
+    /// 非 GUI 通道（CLI 查询无增量展示）或 outbound 通道已满时，本次更新被静默丢弃。
+    pub fn push_query_update(&self, ctx: &PluginContext, response: QueryResponse) {
+        if ctx.query_channel != QueryChannel::Ui {
+            return;
+        }
+        let params = QueryUpdateParams {
+            revision: ctx.query_revision(),
+            response,
+        };
+        let Ok(payload) = serde_json::to_vec(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": notify::QUERY_UPDATE,
+            "params": params,
+        })) else {
+            return;
+        };
+
+        let frame = encode_frame(&payload);
+        let _ = self.outbound_tx.try_send(frame);
+    }
+
     pub async fn shell_open(&self, target: &str) -> Result<(), String> {
         self.send_request("host/shell.open", serde_json::json!({ "target": target }))
             .await?;
@@ -5,7 +5,8 @@
 //!   转发请求到 dispatch_task。
 //! - `write_task`：唯一 stdout 写入者，将所有出站消息编码为 LSP 帧。
 //! - `dispatch_task`：处理 plugin/* 请求，调用用户 Plugin trait 实现，
-//!   将响应发到 write_task。
+//!   将响应发到 write_task。查询类请求（query / global_query）各自在独立任务中
+//!   运行：`$/cancelRequest` 到达即以 REQUEST_CANCELLED 应答，不阻塞后续请求。
 //!
 //! HostProxy 通过共享的 pending_map 和 outbound_tx 发送 host/* 请求，
 //! 避免了同步 BufReader 造成的死锁问题。
@@ -17,10 +18,11 @@ use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::{mpsc, oneshot};
 
 use zerolaunch_plugin_api::config::Configurable;
-use zerolaunch_plugin_api::{ActionExecutor, DataSource, Plugin};
+use zerolaunch_plugin_api::{ActionExecutor, CancellationToken, DataSource, Plugin};
 use zerolaunch_plugin_protocol::codec::{encode_frame, MAX_FRAME_SIZE, MAX_HEADER_SIZE};
 use zerolaunch_plugin_protocol::jsonrpc::{Message, Request, Response};
 use zerolaunch_plugin_protocol::messages::*;
+use zerolaunch_plugin_protocol::methods::notify;
 use zerolaunch_plugin_protocol::methods::plugin as plugin_methods;
 use zerolaunch_plugin_protocol::{codes, JsonRpcError, PROTOCOL_VERSION};
 
@@ -28,7 +30,8 @@ use crate::host_proxy::HostProxy;
 use crate::logging;
 
 // Tokio task-local HostProxy，由 `run()` 初始化。
-// 在 `run_async` scope 内 spawn 的所有任务都继承该值。
+// task-local 不随 `tokio::spawn` 传递：运行时 spawn 的分发任务与查询任务
+// 均显式以 `HOST_PROXY.scope` 包裹，插件代码中的 `host()` 才可用。
 tokio::task_local! {
     static HOST_PROXY: Arc<HostProxy>;
 }
@@ -44,6 +47,8 @@ struct IncomingRequest {
     id: u64,
     method: String,
     params: serde_json::Value,
+    /// 该请求的取消令牌：read task 收到对应 `$/cancelRequest` 时取消。
+    cancellation: CancellationToken,
 }
 
 /// SDK 组件集合：一个 Plugin 主组件 + 任意个 DataSource / ActionExecutor 附加组件。
@@ -124,7 +129,9 @@ impl PluginApp {
             .expect("failed to build tokio runtime");
 
         rt.block_on(async move {
-            run_async(self).await;
+            // 初始化日志系统（双写：stderr → 文件 + WARN/ERROR → host/log 转发）
+            let log_rx = logging::init_logging();
+            serve(self, tokio::io::stdin(), tokio::io::stdout(), log_rx).await;
         });
     }
 }
@@ -135,16 +142,25 @@ pub fn run(plugin: impl Plugin + 'static) {
     PluginApp::new(plugin).run()
 }
 
-async fn run_async(mut app: PluginApp) {
-    // 初始化日志系统（双写：stderr → 文件 + WARN/ERROR → host/log 转发）
-    let mut log_rx = logging::init_logging();
-    let stdin = tokio::io::stdin();
-    let stdout = tokio::io::stdout();
+/// 在给定的读写端上运行 JSON-RPC 循环，直到读端关闭。
+/// `run()` 以进程 stdin/stdout 调用；`log_rx` 中的日志条目转发到宿主。
+async fn serve<R, W>(
+    app: PluginApp,
+    stdin: R,
+    stdout: W,
+    mut log_rx: mpsc::UnboundedReceiver<logging::LogEntry>,
+) where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let app = Arc::new(app);
 
     // 通道
     let (request_tx, mut request_rx) = mpsc::channel::<IncomingRequest>(64);
     let (outbound_tx, mut outbound_rx) = mpsc::channel::<Vec<u8>>(64);
     let pending: Arc<DashMap<u64, oneshot::Sender<serde_json::Value>>> = Arc::new(DashMap::new());
+    // 在途请求的取消令牌（请求 id → 令牌），由 read task 登记/取消、dispatch task 完成后移除。
+    let inflight: Arc<DashMap<u64, CancellationToken>> = Arc::new(DashMap::new());
 
     // 创建 HostProxy。当 scope 退出时，HOST_PROXY 被 drop，
     // 从而释放 outbound_tx 的最后一个 clone，让 write task
@@ -167,6 +183,7 @@ async fn run_async(mut app: PluginApp) {
 
             // --- 读任务：stdin → pending_map（响应）或 request_tx（新请求）---
             let pending_r = pending.clone();
+            let inflight_r = inflight.clone();
             let request_tx_clone = request_tx.clone();
             let read_handle = tokio::spawn(async move {
                 let reader = BufReader::new(stdin);
@@ -196,11 +213,14 @@ async fn run_async(mut app: PluginApp) {
                             }
                         }
                         Message::Request(req) => {
+                            let cancellation = CancellationToken::new();
+                            inflight_r.insert(req.id, cancellation.clone());
                             let ret = request_tx_clone
                                 .send(IncomingRequest {
                                     id: req.id,
                                     method: req.method,
                                     params: req.params,
+                                    cancellation,
                                 })
                                 .await;
                             // 如果 dispatch task 已退出，说明插件可能已经崩溃或被关闭，无法处理请求。打印警告信息。
@@ -211,6 +231,15 @@ async fn run_async(mut app: PluginApp) {
                                 );
                             }
                         }
+                        // 宿主取消在途请求：只置位令牌，由插件实现自行决定何时中止。
+                        Message::Notification(notif) if notif.method == notify::CANCEL_REQUEST => {
+                            if let Ok(p) = serde_json::from_value::<CancelRequestParams>(notif.params)
+                            {
+                                if let Some(token) = inflight_r.get(&p.id) {
+                                    token.cancel();
+                                }
+                            }
+                        }
                         Message::Notification(_) => {
                             tracing::trace!("忽略通知");
                         }
@@ -219,19 +248,50 @@ async fn run_async(mut app: PluginApp) {
             });
 
             // --- 分发任务：plugin/* 请求 → 用户 Plugin → 响应到 outbound_tx ---
+            // 查询类请求 spawn 到独立任务并与取消令牌竞速；其余请求按到达顺序就地处理
+            // （配置应用、初始化等需保持先后次序）。
             let outbound_dispatch = outbound_tx.clone();
-            let dispatch_handle = tokio::spawn(async move {
+            let inflight_d = inflight.clone();
+            let dispatch_handle = tokio::spawn(HOST_PROXY.scope(host(), async move {
                 while let Some(incoming) = request_rx.recv().await {
                     let req = Request::new(incoming.id, &incoming.method, incoming.params);
+                    if is_cancellable(&req.method) {
+                        let app = app.clone();
+                        let outbound = outbound_dispatch.clone();
+                        let inflight = inflight_d.clone();
+                        let cancellation = incoming.cancellation;
+                        tokio::spawn(HOST_PROXY.scope(host(), async move {
+                            // 查询类请求不读写会话上下文
+                            let mut no_context = None;
+                            // 插件未观察令牌时，取消到达即丢弃查询 future 并应答
+                            let result = tokio::select! {
+                                result = handle_request(
+                                    &app,
+                                    &req,
+                                    &mut no_context,
+                                    cancellation.clone(),
+                                ) => result,
+                                _ = cancellation.cancelled() => cancelled_response(req.id),
+                            };
+                            inflight.remove(&req.id);
+                            if let Ok(payload) = serde_json::to_vec(&result) {
+                                let _ = outbound.send(payload).await;
+                            }
+                        }));
+                        continue;
+                    }
                     // 收到了一个请求，调用用户实现的 Plugin trait 处理，并将响应发送到 outbound_tx。
-                    let result = handle_request(&mut app, &req, &mut plugin_context).await;
+                    let result =
+                        handle_request(&app, &req, &mut plugin_context, incoming.cancellation)
+                            .await;
+                    inflight_d.remove(&incoming.id);
                     if let Ok(payload) = serde_json::to_vec(&result) {
                         if outbound_dispatch.send(payload).await.is_err() {
                             break;
                         }
                     }
                 }
-            });
+            }));
 
             // --- 写任务：outbound_rx → stdout ---
             let write_handle = tokio::spawn(async move {
@@ -308,14 +368,41 @@ async fn read_frame<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> Resul
     Ok(body)
 }
 
-// / 处理单条 plugin/* 请求，返回响应 Message。
+/// 可被宿主 `$/cancelRequest` 取消、在独立任务中并发处理的方法（查询类，只读）。
+fn is_cancellable(method: &str) -> bool {
+    matches!(method, plugin_methods::QUERY | plugin_methods::GLOBAL_QUERY)
+}
+
+/// 构造 REQUEST_CANCELLED 错误响应。
+fn cancelled_response(id: u64) -> Message {
+    Message::Response(Response::err(
+        id,
+        JsonRpcError::new(codes::REQUEST_CANCELLED, "request cancelled"),
+    ))
+}
+
+/// 处理单条 plugin/* 请求，返回响应 Message。
+/// 请求在排队期间已被取消时直接回 REQUEST_CANCELLED；处理中被取消且插件
+/// 返回错误时同样归为 REQUEST_CANCELLED，便于宿主区分取消与真实故障。
 async fn handle_request(
-    app: &mut PluginApp,
+    app: &PluginApp,
     req: &Request,
     plugin_ctx: &mut Option<zerolaunch_plugin_api::PluginContext>,
+    cancellation: CancellationToken,
 ) -> Message {
     let id = req.id;
-    let result = dispatch(app, &req.method, &req.params, plugin_ctx).await;
+    if cancellation.is_cancelled() {
+        return cancelled_response(id);
+    }
+    let result = dispatch(app, &req.method, &req.params, plugin_ctx, &cancellation)
+        .await
+        .map_err(|err| {
+            if cancellation.is_cancelled() {
+                JsonRpcError::new(codes::REQUEST_CANCELLED, err.message)
+            } else {
+                err
+            }
+        });
     match result {
         Ok(value) => Message::Response(Response::ok(id, value)),
         Err(err) => Message::Response(Response::err(id, err)),
@@ -323,10 +410,11 @@ async fn handle_request(
 }
 
 async fn dispatch(
-    app: &mut PluginApp,
+    app: &PluginApp,
     method: &str,
     params: &serde_json::Value,
     plugin_ctx: &mut Option<zerolaunch_plugin_api::PluginContext>,
+    cancellation: &CancellationToken,
 ) -> Result<serde_json::Value, JsonRpcError> {
     match method {
         // 初始化请求，设置 plugin_ctx 并返回插件版本信息。
@@ -341,10 +429,12 @@ async fn dispatch(
                 plugin_id: Some(p.plugin_id),
                 // 远端插件无宿主查询版本门控，恒视为最新。
                 query_revision_gate: None,
+                query_revision: 0,
                 // 远端插件会话由宿主经 RPC 下发通道，未收到时缺省视为 GUI 通道。
                 query_channel: zerolaunch_plugin_api::QueryChannel::Ui,
                 // 宿主语言在握手时下发（InitializeParams.locale），写入会话上下文。
                 locale: p.locale,
+                cancellation: CancellationToken::new(),
             });
             let result = InitializeResult {
                 plugin_version: app.plugin.metadata().version.clone(),
@@ -455,8 +545,10 @@ async fn dispatch(
                 .map_err(|e| JsonRpcError::new(codes::PLUGIN_ERROR, e.to_string()))
         }
         plugin_methods::QUERY => {
-            let p: QueryParams = serde_json::from_value(params.clone())
+            let mut p: QueryParams = serde_json::from_value(params.clone())
                 .map_err(|e| JsonRpcError::new(codes::INVALID_PARAMS, e.to_string()))?;
+            // 令牌不随 RPC 序列化，由本次请求的取消令牌注入。
+            p.ctx.cancellation = cancellation.clone();
             let response = app
                 .plugin
                 .query(&p.ctx, &p.query)
@@ -464,6 +556,18 @@ async fn dispatch(
                 .map_err(|e| JsonRpcError::new(codes::PLUGIN_ERROR, e.to_string()))?;
             Ok(serde_json::to_value(response).unwrap_or_default())
         }
+        // 全局查询贡献：默认搜索时宿主对声明 globalQuery 的插件下发，返回 ListItem 数组
+        plugin_methods::GLOBAL_QUERY => {
+            let mut p: QueryParams = serde_json::from_value(params.clone())
+                .map_err(|e| JsonRpcError::new(codes::INVALID_PARAMS, e.to_string()))?;
+            p.ctx.cancellation = cancellation.clone();
+            let items = app
+                .plugin
+                .global_query(&p.ctx, &p.query)
+                .await
+                .map_err(|e| JsonRpcError::new(codes::PLUGIN_ERROR, e.to_string()))?;
+            Ok(serde_json::to_value(items).unwrap_or_default())
+        }
         plugin_methods::EXECUTE_ACTION => {
             let p: ExecuteActionParams = serde_json::from_value(params.clone())
                 .map_err(|e| JsonRpcError::new(codes::INVALID_PARAMS, e.to_string()))?;
@@ -591,3 +695,237 @@ fn find_executor<'a>(
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::time::Duration;
+    use tokio::io::{AsyncWrite, DuplexStream, ReadHalf, WriteHalf};
+    use zerolaunch_plugin_api::config::{ComponentCore, ComponentType, SettingDefinition};
+    use zerolaunch_plugin_api::{
+        ListItem, PluginContext, PluginError, PluginHandle, PluginKind, PluginMetadata, PluginMode,
+        Query, QueryResponse,
+    };
+    use zerolaunch_plugin_protocol::Notification;
+
+    /// 按查询词决定行为的测试插件：
+    /// `hang` 永不返回且不观察令牌；`watch` 等待令牌后返回错误；其余立即返回空列表。
+    struct StubPlugin {
+        core: ComponentCore,
+        metadata: PluginMetadata,
+    }
+
+    impl StubPlugin {
+        fn new() -> Self {
+            Self {
+                core: ComponentCore::new(
+                    "test.stub".to_string(),
+                    "Stub".to_string(),
+                    String::new(),
+                    ComponentType::Plugin,
+                    0,
+                ),
+                metadata: PluginMetadata {
+                    id: "test.stub".to_string(),
+                    name: "Stub".to_string(),
+                    version: "0.1.0".to_string(),
+                    description: String::new(),
+                    author: String::new(),
+                    trigger_keywords: vec!["stub".to_string()],
+                    supported_os: Vec::new(),
+                    priority: 0,
+                    kind: PluginKind::ThirdParty,
+                    hotkey: None,
+                    icon: None,
+                    mode: PluginMode::Inline,
+                    global_query: true,
+                },
+            }
+        }
+
+        /// 按查询词模拟耗时行为。
+        async fn behave(ctx: &PluginContext, query: &Query) -> Result<(), PluginError> {
+            match query.search_term.as_str() {
+                "hang" => std::future::pending().await,
+                "watch" => {
+                    ctx.cancellation.cancelled().await;
+                    Err(PluginError::QueryFailed("aborted".to_string()))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Configurable for StubPlugin {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            vec![]
+        }
+    }
+
+    #[async_trait]
+    impl Plugin for StubPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+        async fn init(
+            &self,
+            _ctx: &PluginContext,
+            _handle: Option<Arc<PluginHandle>>,
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+        async fn query(
+            &self,
+            ctx: &PluginContext,
+            query: &Query,
+        ) -> Result<QueryResponse, PluginError> {
+            Self::behave(ctx, query).await?;
+            Ok(QueryResponse::List {
+                results: Vec::new(),
+            })
+        }
+        async fn global_query(
+            &self,
+            ctx: &PluginContext,
+            query: &Query,
+        ) -> Result<Vec<ListItem>, PluginError> {
+            Self::behave(ctx, query).await?;
+            Ok(Vec::new())
+        }
+        async fn execute_action(
+            &self,
+            _ctx: &PluginContext,
+            _action_id: &str,
+            _payload: serde_json::Value,
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+
+    /// 宿主侧测试端：经 duplex 管道与 `serve` 运行的插件收发帧。
+    struct HostEnd {
+        reader: BufReader<ReadHalf<DuplexStream>>,
+        writer: WriteHalf<DuplexStream>,
+    }
+
+    impl HostEnd {
+        /// 启动运行 StubPlugin 的运行时，返回宿主侧读写端。
+        fn start() -> Self {
+            let (host_io, plugin_io) = tokio::io::duplex(64 * 1024);
+            let (plugin_read, plugin_write) = tokio::io::split(plugin_io);
+            let (_log_tx, log_rx) = mpsc::unbounded_channel();
+            tokio::spawn(serve(
+                PluginApp::new(StubPlugin::new()),
+                plugin_read,
+                plugin_write,
+                log_rx,
+            ));
+            let (host_read, host_write) = tokio::io::split(host_io);
+            Self {
+                reader: BufReader::new(host_read),
+                writer: host_write,
+            }
+        }
+
+        async fn send(&mut self, message: Message) {
+            write_message(&mut self.writer, &message).await;
+        }
+
+        /// 下发查询类请求。
+        async fn query(&mut self, id: u64, method: &str, term: &str) {
+            let mut ctx = PluginContext::new("trace");
+            ctx.with_plugin_id("test.stub".to_string());
+            let params = QueryParams {
+                plugin_id: "test.stub".to_string(),
+                ctx,
+                query: Query {
+                    id: format!("q{id}"),
+                    raw_query: format!("stub {term}"),
+                    search_term: term.to_string(),
+                    confirm: false,
+                },
+            };
+            let params = serde_json::to_value(params).unwrap();
+            self.send(Message::Request(Request::new(id, method, params)))
+                .await;
+        }
+
+        async fn cancel(&mut self, id: u64) {
+            let params = serde_json::to_value(CancelRequestParams { id }).unwrap();
+            self.send(Message::Notification(Notification::new(
+                notify::CANCEL_REQUEST,
+                params,
+            )))
+            .await;
+        }
+
+        /// 读取下一条响应（跳过日志等插件发起的请求），2 秒超时。
+        async fn next_response(&mut self) -> Response {
+            tokio::time::timeout(Duration::from_secs(2), async {
+                loop {
+                    let body = read_frame(&mut self.reader).await.expect("读取响应失败");
+                    if let Message::Response(resp) = serde_json::from_slice(&body).unwrap() {
+                        return resp;
+                    }
+                }
+            })
+            .await
+            .expect("等待响应超时")
+        }
+    }
+
+    async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &Message) {
+        let payload = serde_json::to_vec(message).unwrap();
+        writer.write_all(&encode_frame(&payload)).await.unwrap();
+        writer.flush().await.unwrap();
+    }
+
+    fn error_code(resp: &Response) -> Option<i32> {
+        resp.error.as_ref().map(|e| e.code)
+    }
+
+    /// 卡住且不观察令牌的查询不阻塞后续请求；取消到达后立即以 REQUEST_CANCELLED 应答。
+    #[tokio::test]
+    async fn cancel_answers_hung_query_without_blocking_later_requests() {
+        let mut host = HostEnd::start();
+        host.query(1, plugin_methods::QUERY, "hang").await;
+        host.query(2, plugin_methods::QUERY, "fast").await;
+
+        let resp = host.next_response().await;
+        assert_eq!((resp.id, error_code(&resp)), (2, None), "后续查询应先完成");
+
+        host.cancel(1).await;
+        let resp = host.next_response().await;
+        assert_eq!(resp.id, 1);
+        assert_eq!(error_code(&resp), Some(codes::REQUEST_CANCELLED));
+    }
+
+    /// 观察令牌的全局查询在取消后返回错误，同样归为 REQUEST_CANCELLED。
+    #[tokio::test]
+    async fn cancelled_global_query_reports_request_cancelled() {
+        let mut host = HostEnd::start();
+        host.query(7, plugin_methods::GLOBAL_QUERY, "watch").await;
+        host.cancel(7).await;
+
+        let resp = host.next_response().await;
+        assert_eq!(resp.id, 7);
+        assert_eq!(error_code(&resp), Some(codes::REQUEST_CANCELLED));
+    }
+
+    /// 未知 id 的取消被忽略，不影响后续请求。
+    #[tokio::test]
+    async fn cancel_of_unknown_request_is_ignored() {
+        let mut host = HostEnd::start();
+        host.cancel(99).await;
+        host.query(3, plugin_methods::GLOBAL_QUERY, "fast").await;
+
+        let resp = host.next_response().await;
+        assert_eq!((resp.id, error_code(&resp)), (3, None));
+        assert_eq!(resp.result, Some(serde_json::json!([])));
+    }
+}
@@ -49,6 +49,8 @@ impl HelloWorldPlugin {
                 icon: None,
                 // 插件形态：行内插件填 Inline，完全插件模式（trigger 类型）填 Panel
                 mode: PluginMode::Inline,
+                // 是否在默认搜索中贡献结果（需同时在 manifest [components] 声明 globalQuery = true）
+                global_query: false,
             },
         }
     }
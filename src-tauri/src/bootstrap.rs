@@ -24,11 +24,16 @@ use zerolaunch_plugin_api::PluginContext;
 
 use crate::core::app_command;
 use crate::core::config::bias_settings::{bias_settings_to_rules, BiasSettings};
+use crate::core::config::config_sync_settings::{ConfigSyncSettings, CONFIG_SYNC_CONFIG_ID};
 use crate::core::config::event::create_plugin_event_bus;
+use crate::core::config::sync::SyncMode;
 use crate::core::config::{ConfigEvent, ConfigManager};
+use crate::core::host_event::HostEvent;
 use crate::core::i18n::I18nManager;
 use crate::plugin_framework::inspector::Inspector;
 use crate::plugin_framework::manager::PluginManager;
+use crate::plugin_framework::{CandidateIndexStore, RefreshScope};
+use crate::sdk::HostApi;
 use crate::state::app_state::AppState;
 use crate::tray::TrayManager;
 use crate::utils::trace_id::generate_trace_id;
@@ -68,25 +73,35 @@ fn spawn_auto_refresh_task(state: Arc<AppState>) {
     });
 }
 
-/// 将当前配置序列化并同步到远程存储后端（fire-and-forget）。
+/// 与远程存储双向同步配置（三方合并，失败与冲突仅记日志，不阻断）。
 ///
-/// 从 ConfigManager 构建 PersistentConfig，序列化为 JSON 字节，
-/// 通过 HostApi 的 StorageService 上传。失败仅记日志，不阻断。
-pub(crate) async fn sync_config_to_remote(
-    config_manager: &ConfigManager,
-    host_api: &crate::sdk::HostApi,
-) {
-    let config = config_manager.build_persistent_config();
-    let json_bytes = match serde_json::to_vec(&config) {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            tracing::warn!("配置序列化失败，跳过远程同步: {}", e);
-            return;
-        }
-    };
+/// config-sync 组件关闭自动同步时跳过。远端改动经 ConfigManager 应用到本机，
+/// 冲突暂存于 ConfigManager，由 config-sync 组件的配置动作裁决。
+/// 同步幂等：应用远端改动引发的配置事件再次触发同步时，远端修订与基线一致即为空操作。
+pub(crate) async fn sync_config_with_remote(config_manager: &ConfigManager, host_api: &HostApi) {
+    let auto_sync = config_manager
+        .get_settings(CONFIG_SYNC_CONFIG_ID)
+        .and_then(|v| serde_json::from_value::<ConfigSyncSettings>(v).ok())
+        .map(|s| s.auto_sync)
+        .unwrap_or(true);
+    if !auto_sync {
+        return;
+    }
     let storage = host_api.storage();
-    if let Err(e) = storage.upload("zerolaunch_config.json", &json_bytes).await {
-        tracing::warn!("配置远程同步失败: {}", e);
+    match config_manager.sync_config(&*storage, SyncMode::Merge).await {
+        Ok(report) if !report.conflicts.is_empty() => {
+            let ids: Vec<&str> = report
+                .conflicts
+                .iter()
+                .map(|c| c.component_id.as_str())
+                .collect();
+            warn!("配置远程同步存在冲突，等待裁决: {:?}", ids);
+        }
+        Ok(report) => debug!(
+            "配置远程同步完成: 修订 {}, 拉取 {:?}, 推送 {}",
+            report.revision, report.pulled, report.pushed
+        ),
+        Err(e) => warn!("配置远程同步失败: {}", e),
     }
 }
 
@@ -212,7 +227,10 @@ pub(crate) async fn init_app_state(
             let app_handle = install_event_handle.clone();
             tauri::async_runtime::spawn(async move {
                 let dispatcher = state.get_session_dispatcher();
-                dispatcher.refresh_candidates().await;
+                // 只重新采集依赖已安装程序的数据源，其余数据源沿用快照
+                dispatcher
+                    .refresh_candidates_scoped(RefreshScope::Installations)
+                    .await;
                 info!(
                     "安装监控事件（{:?}，{} 个路径）触发自动刷新，共 {} 个候选项",
                     event.kind,
@@ -274,12 +292,18 @@ pub(crate) async fn init_app_state(
     plugin_manager.set_plugin_event_tx(plugin_event_tx);
     plugin_manager.set_host_api(host_api.clone());
     plugin_manager.set_i18n_manager(state.get_i18n_manager());
+    plugin_manager.set_host_events(state.get_host_events());
     state.set_plugin_manager(plugin_manager.clone());
 
     // 将 config_manager 保存到 AppState（必须在 PluginManager 之后，因为 clone 语义）
     state.set_config_manager(config_manager);
-    // 初始化内置 + 第三方插件（返回内置组件 id 集合，供 init_host_manager 注入冲突预检）
-    let builtin_component_ids = init_plugin_system(&state).await;
+    // 候选项索引快照：每次刷新后落盘，冷启动时由 init_plugin_system 直接提供
+    state
+        .get_session_dispatcher()
+        .set_index_store(CandidateIndexStore::new(PathBuf::from(&app_data_dir)));
+    // 初始化内置 + 第三方插件（返回内置组件 id 集合，供 init_host_manager 注入冲突预检；
+    // 以及候选项是否来自索引快照，决定 Phase 4 的刷新是否转入后台）
+    let (builtin_component_ids, served_from_snapshot) = init_plugin_system(&state).await;
     info!("Phase 3 完成: 插件系统初始化就绪");
 
     info!("=== Phase 4: 第三方插件加载 ===");
@@ -292,7 +316,19 @@ pub(crate) async fn init_app_state(
     // 批量加载后刷新候选项缓存，确保第三方插件的数据源被纳入。
     // 各插件的 PluginRegistered 事件也会触发独立 refresh，但批量场景下
     // 可能存在事件尚未处理完的竞态，此处作为最终兜底保证缓存完整。
-    state.get_session_dispatcher().refresh_candidates().await;
+    // 候选项来自索引快照时搜索已可用，刷新转入后台与数据源对账（ID 按目标保留）。
+    if served_from_snapshot {
+        let dispatcher = state.get_session_dispatcher().clone();
+        tauri::async_runtime::spawn(async move {
+            dispatcher.refresh_candidates().await;
+            info!(
+                "索引快照后台对账完成，共 {} 个候选项",
+                dispatcher.get_cached_candidates_count()
+            );
+        });
+    } else {
+        state.get_session_dispatcher().refresh_candidates().await;
+    }
     info!(
         "Phase 4 完成: 第三方插件加载完成，共 {} 个候选项",
         state.get_session_dispatcher().get_cached_candidates_count()
@@ -348,8 +384,10 @@ fn sync_backend_language(state: &Arc<AppState>, config_manager: &ConfigManager)
 /// 核心流程：
 /// - Phase A: inventory 自动发现并注册所有内置组件 + 快捷键回调
 /// - Phase B: 加载持久化配置
-/// - Phase C: 构建候选项管道和搜索管道
-pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String> {
+/// - Phase C: 构建候选项管道和搜索管道（存在可用索引快照时直接提供快照，跳过首次采集）
+///
+/// 返回：(内置组件 id 集合, 候选项是否来自索引快照)。
+pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> (HashSet<String>, bool) {
     let session_dispatcher = state.get_session_dispatcher();
     let config_manager = state.get_config_manager();
     let plugin_manager = state.get_plugin_manager();
@@ -366,18 +404,35 @@ pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String>
     });
     session_dispatcher.set_session_emitter(policy_emitter);
 
+    // 注入增量查询结果回调：插件（内置经 PluginHandle、远端经 host/query.update）推送的
+    // List / CustomPanel 更新交由调度器校验版本后随 session-state 事件下发。
+    let dispatcher_for_updates = session_dispatcher.clone();
+    state
+        .get_host_api()
+        .set_query_update_callback(Arc::new(move |update| {
+            let dispatcher = dispatcher_for_updates.clone();
+            tauri::async_runtime::spawn(async move {
+                dispatcher.handle_query_update(update).await;
+            });
+        }));
+
     // 订阅配置事件
     let event_router = session_dispatcher.clone();
     let app_handle = state.get_main_handle();
     let cm_for_events = config_manager.clone();
     let host_api_for_events = state.get_host_api();
     let state_for_events = state.clone();
+    let host_events = state.get_host_events();
     let mut event_receiver = config_manager.event_sender().subscribe();
     tauri::async_runtime::spawn(async move {
         loop {
             match event_receiver.recv().await {
                 Ok(event) => {
                     event_router.handle_config_event(&event).await;
+                    // 组件级配置变更转为宿主可观测事件（CLI /v1/events）
+                    if let Some(host_event) = HostEvent::from_config_event(&event) {
+                        host_events.publish(host_event);
+                    }
                     // 将 SettingsChanged 事件桥接到 Tauri 前端，实现跨窗口同步。
                     // 注：Registered/Unregistered 仅启动时触发（前端窗口未创建），
                     // EnabledChanged 暂无前端消费者，故暂不转发。
@@ -400,11 +455,15 @@ pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String>
                         // 会话投影随配置变更重新推送（如面板内调整防抖延迟）
                         event_router.reemit_current_session();
                     }
-                    // 配置变更后自动触发远程同步（fire-and-forget）
+                    // 配置变更后自动触发远程同步（fire-and-forget，不阻塞事件循环）
                     match &event {
                         ConfigEvent::SettingsChanged { .. }
                         | ConfigEvent::EnabledChanged { .. } => {
-                            sync_config_to_remote(&cm_for_events, &host_api_for_events).await;
+                            let cm = cm_for_events.clone();
+                            let host_api = host_api_for_events.clone();
+                            tauri::async_runtime::spawn(async move {
+                                sync_config_with_remote(&cm, &host_api).await;
+                            });
                         }
                         _ => {}
                     }
@@ -588,6 +647,12 @@ pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String>
     }
     // 持久化语言在配置加载后才可知：同步后端翻译服务并重建托盘菜单
     sync_backend_language(state, &config_manager);
+    // 启动时拉取远端修订并合并（存储后端已随 storage-config 加载就绪；网络 I/O 不阻塞启动）
+    let cm_for_sync = config_manager.clone();
+    let host_api_for_sync = host_api.clone();
+    tauri::async_runtime::spawn(async move {
+        sync_config_with_remote(&cm_for_sync, &host_api_for_sync).await;
+    });
 
     // 内置插件全部注册后统一执行 init：向插件发放绑定身份的 PluginHandle
     // （插件在 init 中保存句柄，供 query/execute_action 访问平台能力）。
@@ -626,12 +691,26 @@ pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String>
     }
     candidate_pipeline.set_bias_rules(rules);
 
-    info!("正在收集候选项（此时各组件已持有用户持久化配置）...");
-    let candidates = candidate_pipeline.collect().await;
-    info!(
-        "候选项收集完成，共 {} 个",
-        candidates.get_candidates().len()
-    );
+    let snapshot = session_dispatcher.load_index_snapshot();
+    let served_from_snapshot = snapshot.is_some();
+    let candidates = match snapshot {
+        Some(candidates) => {
+            info!(
+                "已从索引快照恢复 {} 个候选项，跳过首次采集（稍后后台对账）",
+                candidates.get_candidates().len()
+            );
+            candidates
+        }
+        None => {
+            info!("正在收集候选项（此时各组件已持有用户持久化配置）...");
+            let candidates = candidate_pipeline.collect().await;
+            info!(
+                "候选项收集完成，共 {} 个",
+                candidates.get_candidates().len()
+            );
+            candidates
+        }
+    };
 
     info!("根据已注册且启用的搜索引擎与增强器重建搜索管道...");
     session_dispatcher.rebuild_search_pipeline();
@@ -655,7 +734,7 @@ pub(crate) async fn init_plugin_system(state: &Arc<AppState>) -> HashSet<String>
         config_manager.get_all_components().len(),
         session_dispatcher.get_cached_candidates_count()
     );
-    builtin_component_ids
+    (builtin_component_ids, served_from_snapshot)
 }
 
 /// 启动 AppCommand 消费者 task。
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, SettingDefinition,
+};
+
+use crate::core::config::config_sync_settings::{ConfigSyncSettings, CONFIG_SYNC_CONFIG_ID};
+use crate::core::config::setting_builders::SchemaBuilder;
+use crate::core::config::sync::{SyncMode, SyncSide};
+use crate::plugin_framework::SessionDispatcher;
+use crate::sdk::HostApi;
+
+// ============================================================================
+// ConfigSyncConfig — Core Configurable 组件
+// ============================================================================
+
+/// 配置远程同步组件。
+///
+/// 设置项仅有自动同步开关（由 bootstrap 读取）；手动同步、冲突查看与裁决、
+/// 「推送本地 / 拉取远端」整体覆盖通过配置动作提供。动作只改动其他组件的配置
+/// （经 `ConfigManager::sync_config` 应用远端值），不修改本组件自身设置。
+pub struct ConfigSyncConfig {
+    /// 组件身份核心
+    core: ComponentCore,
+    /// HostApi 引用，读取当前存储后端（Local / WebDAV）
+    host_api: Arc<HostApi>,
+    /// SessionDispatcher 引用，执行动作时取得已注入的 ConfigManager
+    session_dispatcher: Arc<SessionDispatcher>,
+    /// 当前配置状态
+    settings: RwLock<ConfigSyncSettings>,
+}
+
+impl ConfigSyncConfig {
+    /// 创建 ConfigSyncConfig 实例
+    pub fn new(host_api: Arc<HostApi>, session_dispatcher: Arc<SessionDispatcher>) -> Self {
+        Self {
+            core: ComponentCore::new(
+                CONFIG_SYNC_CONFIG_ID.to_string(),
+                t_key!("config-sync", "name").to_string(),
+                t_key!("config-sync", "description").to_string(),
+                ComponentType::Core,
+                35,
+            ),
+            host_api,
+            session_dispatcher,
+            settings: RwLock::new(ConfigSyncSettings::default()),
+        }
+    }
+
+    /// 以指定方式执行一次同步，返回序列化的同步报告
+    async fn run_sync(&self, mode: SyncMode) -> Result<serde_json::Value, String> {
+        let cm = self
+            .session_dispatcher
+            .config_manager()
+            .ok_or_else(|| "配置管理器未初始化".to_string())?;
+        let storage = self.host_api.storage();
+        let report = cm
+            .sync_config(&*storage, mode)
+            .await
+            .map_err(|e| e.to_string())?;
+        serde_json::to_value(report).map_err(|e| e.to_string())
+    }
+}
+
+/// 解析 `resolve_conflicts` 动作参数：`{ "choices": { "<componentId>": "local" | "remote" } }`
+fn parse_choices(params: &serde_json::Value) -> Result<HashMap<String, SyncSide>, String> {
+    let choices = params
+        .get("choices")
+        .cloned()
+        .ok_or_else(|| "缺少 choices 参数".to_string())?;
+    serde_json::from_value(choices).map_err(|e| format!("choices 参数无效: {}", e))
+}
+
+#[async_trait]
+impl Configurable for ConfigSyncConfig {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![SchemaBuilder::boolean(
+            "autoSync",
+            t_key!("config-sync", "fields.autoSync.label"),
+            t_key!("config-sync", "fields.autoSync.desc"),
+        )
+        .group(t_key!("config-sync", "groups.sync"))
+        .order(0)
+        .default(true)
+        .build()]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        *self.settings.write() = serde_json::from_value(settings).unwrap_or_default();
+        Ok(())
+    }
+
+    fn config_actions(&self) -> Vec<ConfigActionDef> {
+        vec![
+            ConfigActionDef {
+                action: "sync_now".to_string(),
+                label: t_key!("config-sync", "actions.sync_now.label").to_string(),
+                description: t_key!("config-sync", "actions.sync_now.description").to_string(),
+            },
+            ConfigActionDef {
+                action: "list_conflicts".to_string(),
+                label: t_key!("config-sync", "actions.list_conflicts.label").to_string(),
+                description: t_key!("config-sync", "actions.list_conflicts.description")
+                    .to_string(),
+            },
+            ConfigActionDef {
+                action: "resolve_conflicts".to_string(),
+                label: t_key!("config-sync", "actions.resolve_conflicts.label").to_string(),
+                description: t_key!("config-sync", "actions.resolve_conflicts.description")
+                    .to_string(),
+            },
+            ConfigActionDef {
+                action: "push_local".to_string(),
+                label: t_key!("config-sync", "actions.push_local.label").to_string(),
+                description: t_key!("config-sync", "actions.push_local.description").to_string(),
+            },
+            ConfigActionDef {
+                action: "pull_remote".to_string(),
+                label: t_key!("config-sync", "actions.pull_remote.label").to_string(),
+                description: t_key!("config-sync", "actions.pull_remote.description").to_string(),
+            },
+        ]
+    }
+
+    /// 同步动作：拉取/推送远端配置并经 ConfigManager 应用到其他组件，不修改本组件设置。
+    async fn execute_config_action(
+        &self,
+        action: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match action {
+            "sync_now" => self.run_sync(SyncMode::Merge).await,
+            "list_conflicts" => {
+                let cm = self
+                    .session_dispatcher
+                    .config_manager()
+                    .ok_or_else(|| "配置管理器未初始化".to_string())?;
+                serde_json::to_value(cm.sync_conflicts()).map_err(|e| e.to_string())
+            }
+            "resolve_conflicts" => {
+                self.run_sync(SyncMode::Resolve(parse_choices(params)?))
+                    .await
+            }
+            "push_local" => self.run_sync(SyncMode::PushLocal).await,
+            "pull_remote" => self.run_sync(SyncMode::PullRemote).await,
+            _ => Err(format!("未知动作: {}", action)),
+        }
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// 注册到 inventory（ConfigEntry）
+// ============================================================================
+
+use crate::plugin_framework::builtin_registry::{ConfigEntry, InventoryContext};
+
+fn build_config_sync_config(ctx: &InventoryContext) -> Arc<dyn Configurable> {
+    Arc::new(ConfigSyncConfig::new(
+        ctx.host_api().clone(),
+        ctx.session_dispatcher().clone(),
+    ))
+}
+
+inventory::submit! {
+    ConfigEntry {
+        component_id: "config-sync",
+        priority: 35,
+        factory: build_config_sync_config,
+    }
+}
@@ -2,9 +2,12 @@ pub mod appearance_config;
 pub mod auto_refresh_config;
 pub mod bias_config;
 pub mod candidate_registry_config;
+pub mod config_sync_config;
 pub mod general_config;
 pub mod hotkey_config;
 pub mod icon_override_config;
 pub mod installation_monitor_config;
+pub mod plugin_registry_config;
+pub mod search_fusion_config;
 pub mod storage_config;
 pub mod window_behavior_config;
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+};
+
+use crate::core::config::plugin_registry_settings::{
+    PluginRegistrySettings, PLUGIN_REGISTRY_CONFIG_ID,
+};
+use crate::core::config::setting_builders::SchemaBuilder;
+
+// ============================================================================
+// PluginRegistryConfig — 纯配置组件（ConfigEntry）
+// ============================================================================
+
+/// 插件注册表配置组件。
+///
+/// 作为纯 Configurable 组件注册到 ConfigManager，配置插件注册表索引地址
+/// 与受信任的发布者公钥；`PluginManager` 在检查更新、安装与升级时按需读取，
+/// 因此设置变更无需额外的响应逻辑。
+pub struct PluginRegistryConfig {
+    core: ComponentCore,
+    settings: RwLock<PluginRegistrySettings>,
+}
+
+impl PluginRegistryConfig {
+    /// 创建 PluginRegistryConfig 实例
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                PLUGIN_REGISTRY_CONFIG_ID.to_string(),
+                t_key!("plugin-registry-config", "name").to_string(),
+                t_key!("plugin-registry-config", "description").to_string(),
+                ComponentType::Core,
+                60,
+            ),
+            settings: RwLock::new(PluginRegistrySettings::default()),
+        }
+    }
+}
+
+impl Default for PluginRegistryConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Configurable for PluginRegistryConfig {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::text(
+                "source",
+                t_key!("plugin-registry-config", "fields.source.label"),
+                t_key!("plugin-registry-config", "fields.source.desc"),
+            )
+            .group(t_key!("plugin-registry-config", "groups.registry"))
+            .order(0)
+            .default("")
+            .build(),
+            SchemaBuilder::array(
+                "trustedKeys",
+                t_key!("plugin-registry-config", "fields.trustedKeys.label"),
+                t_key!("plugin-registry-config", "fields.trustedKeys.desc"),
+            )
+            .group(t_key!("plugin-registry-config", "groups.trust"))
+            .order(1)
+            .object_items(vec![
+                SchemaBuilder::text(
+                    "publisher",
+                    t_key!("plugin-registry-config", "fields.publisher.label"),
+                    t_key!("plugin-registry-config", "fields.publisher.desc"),
+                )
+                .default("")
+                .build_field(),
+                SchemaBuilder::text(
+                    "publicKey",
+                    t_key!("plugin-registry-config", "fields.publicKey.label"),
+                    t_key!("plugin-registry-config", "fields.publicKey.desc"),
+                )
+                .default("")
+                .build_field(),
+            ])
+            .table_ui()
+            .min_items(0)
+            .default(serde_json::json!([]))
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let mut parsed: PluginRegistrySettings =
+            serde_json::from_value(settings).unwrap_or_default();
+        parsed.source = parsed.source.trim().to_string();
+        for key in &mut parsed.trusted_keys {
+            key.publisher = key.publisher.trim().to_string();
+            key.public_key = key.public_key.trim().to_string();
+        }
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// 注册到 inventory（ConfigEntry）
+// ============================================================================
+
+use crate::plugin_framework::builtin_registry::{ConfigEntry, InventoryContext};
+
+fn build_plugin_registry_config(_ctx: &InventoryContext) -> Arc<dyn Configurable> {
+    Arc::new(PluginRegistryConfig::new())
+}
+
+inventory::submit! {
+    ConfigEntry {
+        component_id: "plugin-registry-config",
+        priority: 60,
+        factory: build_plugin_registry_config,
+    }
+}
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+};
+
+use crate::core::config::search_fusion_settings::{SearchFusionSettings, SEARCH_FUSION_CONFIG_ID};
+use crate::core::config::setting_builders::SchemaBuilder;
+
+// ============================================================================
+// SearchFusionConfig — 纯配置组件（ConfigEntry）
+// ============================================================================
+
+/// 多搜索引擎融合配置组件。
+///
+/// 作为纯 Configurable 组件注册到 ConfigManager，配置启用多个搜索引擎时
+/// 分数的融合策略与各引擎权重；设置变更由 SessionDispatcher 响应并重建搜索管道。
+/// 只启用一个搜索引擎时本组件的设置不生效。
+pub struct SearchFusionConfig {
+    core: ComponentCore,
+    settings: RwLock<SearchFusionSettings>,
+}
+
+impl SearchFusionConfig {
+    /// 创建 SearchFusionConfig 实例
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                SEARCH_FUSION_CONFIG_ID.to_string(),
+                t_key!("search-fusion-config", "name").to_string(),
+                t_key!("search-fusion-config", "description").to_string(),
+                ComponentType::Core,
+                55,
+            ),
+            settings: RwLock::new(SearchFusionSettings::default()),
+        }
+    }
+}
+
+impl Default for SearchFusionConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Configurable for SearchFusionConfig {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::select(
+                "strategy",
+                t_key!("search-fusion-config", "fields.strategy.label"),
+                t_key!("search-fusion-config", "fields.strategy.desc"),
+            )
+            .group(t_key!("search-fusion-config", "groups.fusion"))
+            .order(0)
+            .options_with_labels(&[
+                (
+                    "weighted_sum",
+                    t_key!("search-fusion-config", "options.strategy.weighted_sum"),
+                ),
+                (
+                    "max",
+                    t_key!("search-fusion-config", "options.strategy.max"),
+                ),
+                (
+                    "rrf",
+                    t_key!("search-fusion-config", "options.strategy.rrf"),
+                ),
+            ])
+            .default("weighted_sum")
+            .build(),
+            SchemaBuilder::integer(
+                "rrfK",
+                t_key!("search-fusion-config", "fields.rrfK.label"),
+                t_key!("search-fusion-config", "fields.rrfK.desc"),
+            )
+            .group(t_key!("search-fusion-config", "groups.fusion"))
+            .order(1)
+            .min(1.0)
+            .max(1000.0)
+            .default(60)
+            .build(),
+            SchemaBuilder::array(
+                "weights",
+                t_key!("search-fusion-config", "fields.weights.label"),
+                t_key!("search-fusion-config", "fields.weights.desc"),
+            )
+            .group(t_key!("search-fusion-config", "groups.fusion"))
+            .order(2)
+            .object_items(vec![
+                SchemaBuilder::select(
+                    "engineId",
+                    t_key!("search-fusion-config", "fields.engineId.label"),
+                    t_key!("search-fusion-config", "fields.engineId.desc"),
+                )
+                .options_with_labels(&[
+                    (
+                        "standard-search-model",
+                        t_key!("search-fusion-config", "options.engine.standard"),
+                    ),
+                    (
+                        "launchy-search-model",
+                        t_key!("search-fusion-config", "options.engine.launchy"),
+                    ),
+                    (
+                        "skim-search-model",
+                        t_key!("search-fusion-config", "options.engine.skim"),
+                    ),
+                    (
+                        "indexed-search-model",
+                        t_key!("search-fusion-config", "options.engine.indexed"),
+                    ),
+                ])
+                .default("standard-search-model")
+                .build_field(),
+                SchemaBuilder::number(
+                    "weight",
+                    t_key!("search-fusion-config", "fields.weight.label"),
+                    t_key!("search-fusion-config", "fields.weight.desc"),
+                )
+                .default(1.0)
+                .min(0.0)
+                .max(10.0)
+                .step(0.1)
+                .build_field(),
+            ])
+            .table_ui()
+            .min_items(0)
+            .default(serde_json::json!([]))
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let mut parsed: SearchFusionSettings = serde_json::from_value(settings).unwrap_or_default();
+        parsed.rrf_k = parsed.rrf_k.max(1);
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// 注册到 inventory（ConfigEntry）
+// ============================================================================
+
+use crate::plugin_framework::builtin_registry::{ConfigEntry, InventoryContext};
+
+fn build_search_fusion_config(_ctx: &InventoryContext) -> Arc<dyn Configurable> {
+    Arc::new(SearchFusionConfig::new())
+}
+
+inventory::submit! {
+    ConfigEntry {
+        component_id: "search-fusion-config",
+        priority: 55,
+        factory: build_search_fusion_config,
+    }
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{info, warn};
 use zerolaunch_plugin_api::config::{
-    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+    ComponentCore, ComponentType, ConfigError, Configurable, SecretString, SettingDefinition,
 };
 use zerolaunch_plugin_api::services::storage::local_storage::LocalStorageService;
 use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
@@ -28,8 +28,9 @@ pub struct StorageSettings {
     pub webdav_host_url: String,
     #[serde(rename = "webdav_account", default)]
     pub webdav_account: String,
+    /// WebDAV 密码（机密字段，不写入配置文件，`Debug` 输出时脱敏）
     #[serde(rename = "webdav_password", default)]
-    pub webdav_password: String,
+    pub webdav_password: SecretString,
     #[serde(
         rename = "webdav_destination_dir",
         default = "default_webdav_destination_dir"
@@ -44,7 +45,7 @@ impl Default for StorageSettings {
             custom_save_path: String::new(),
             webdav_host_url: String::new(),
             webdav_account: String::new(),
-            webdav_password: String::new(),
+            webdav_password: SecretString::default(),
             webdav_destination_dir: default_webdav_destination_dir(),
         }
     }
@@ -134,7 +135,7 @@ impl Configurable for StorageConfigComponent {
             .order(3)
             .default("")
             .build(),
-            SchemaBuilder::text(
+            SchemaBuilder::secret(
                 "webdav_password",
                 t_key!("storage-config", "fields.webdav_password.label"),
                 t_key!("storage-config", "fields.webdav_password.desc"),
@@ -211,7 +212,7 @@ impl Configurable for StorageConfigComponent {
                 let config = WebDAVConfig {
                     host_url: s.webdav_host_url,
                     account: s.webdav_account,
-                    password: s.webdav_password,
+                    password: s.webdav_password.expose().to_string(),
                     destination_dir: s.webdav_destination_dir,
                 };
                 Arc::new(WebDAVStorageService::new(&config))
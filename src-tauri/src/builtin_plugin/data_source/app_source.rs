@@ -59,6 +59,11 @@ impl Configurable for AppSource {
 
 #[async_trait]
 impl DataSource for AppSource {
+    /// 安装/卸载程序会改变采集结果，安装监控事件触发时重新采集。
+    fn tracks_installations(&self) -> bool {
+        true
+    }
+
     /// 枚举系统应用并转换为搜索候选项。
     /// 委托 PluginHandle::enumerate_apps() 获取应用列表，将 AppInfo 映射为 SearchCandidate。
     async fn fetch_candidates(&self) -> CachedCandidateData {
@@ -75,6 +80,7 @@ impl DataSource for AppSource {
                 keywords: Vec::new(),
                 bias: 0.0,
                 trigger_keywords: Vec::new(),
+                actions: Vec::new(),
             };
 
             result.add_candidate(candidate);
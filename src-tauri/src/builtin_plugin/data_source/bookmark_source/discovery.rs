@@ -0,0 +1,230 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::readers::BookmarkFormat;
+use super::BrowserInfo;
+
+/// Chromium 系浏览器的 User Data 目录（相对平台配置目录：Linux `~/.config`、
+/// macOS `~/Library/Application Support`）。Windows 版位于 `%LOCALAPPDATA%`，由目录扫描发现。
+const CHROMIUM_CONFIG_DIRS: [(&str, &str); 9] = [
+    ("Google Chrome", "google-chrome"),
+    ("Google Chrome", "Google/Chrome"),
+    ("Chromium", "chromium"),
+    ("Chromium", "Chromium"),
+    ("Microsoft Edge", "microsoft-edge"),
+    ("Microsoft Edge", "Microsoft Edge"),
+    ("Brave", "BraveSoftware/Brave-Browser"),
+    ("Vivaldi", "vivaldi"),
+    ("Vivaldi", "Vivaldi"),
+];
+
+/// Firefox 系浏览器的 profiles.ini 所在目录（相对平台配置目录：Windows `%APPDATA%`、
+/// macOS `~/Library/Application Support`）。
+const FIREFOX_CONFIG_DIRS: [(&str, &str); 6] = [
+    ("Firefox", "Mozilla/Firefox"),
+    ("Firefox", "Firefox"),
+    ("LibreWolf", "librewolf"),
+    ("LibreWolf", "LibreWolf"),
+    ("Waterfox", "Waterfox"),
+    ("Floorp", "Floorp"),
+];
+
+/// Firefox 系浏览器的 profiles.ini 所在目录（相对用户主目录，Linux 常规 / Snap / Flatpak 安装）。
+const FIREFOX_HOME_DIRS: [(&str, &str); 5] = [
+    ("Firefox", ".mozilla/firefox"),
+    ("Firefox", "snap/firefox/common/.mozilla/firefox"),
+    ("Firefox", ".var/app/org.mozilla.firefox/.mozilla/firefox"),
+    ("LibreWolf", ".librewolf"),
+    ("Waterfox", ".waterfox"),
+];
+
+/// 自动检测系统已安装浏览器的书签文件（Chromium 系 `Bookmarks`、Firefox 系 `places.sqlite`、
+/// Konqueror XBEL），按书签文件路径去重。
+pub(super) fn detect_installed_browsers() -> Vec<BrowserInfo> {
+    let mut browsers = Vec::new();
+
+    if let Some(local_app_data) = dirs::data_local_dir() {
+        scan_chromium_user_data_dirs(&local_app_data, &mut browsers);
+    }
+    if let Some(config) = dirs::config_dir() {
+        for (name, relative) in CHROMIUM_CONFIG_DIRS {
+            find_chromium_profiles(&mut browsers, name, &config.join(relative));
+        }
+        for (name, relative) in FIREFOX_CONFIG_DIRS {
+            find_firefox_profiles(&mut browsers, name, &config.join(relative));
+        }
+    }
+    if let Some(home) = dirs::home_dir() {
+        for (name, relative) in FIREFOX_HOME_DIRS {
+            find_firefox_profiles(&mut browsers, name, &home.join(relative));
+        }
+    }
+    if let Some(data) = dirs::data_dir() {
+        let konqueror = data.join("konqueror").join("bookmarks.xml");
+        if konqueror.is_file() {
+            browsers.push(BrowserInfo {
+                name: "Konqueror".to_string(),
+                bookmarks_path: konqueror.to_string_lossy().to_string(),
+                format: BookmarkFormat::Xbel,
+            });
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    browsers.retain(|b| seen.insert(b.bookmarks_path.clone()));
+    browsers
+}
+
+/// 扫描 `%LOCALAPPDATA%` 下的 Chromium 系 User Data 目录：
+/// 深度 1 `<Browser>/User Data`，深度 2 `<Vendor>/<Browser>/User Data`。
+fn scan_chromium_user_data_dirs(base_path: &Path, browsers: &mut Vec<BrowserInfo>) {
+    let Ok(entries) = fs::read_dir(base_path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Depth 1: %LOCALAPPDATA%/<Browser>/User Data
+        let user_data = path.join("User Data");
+        if user_data.is_dir() {
+            find_chromium_profiles(browsers, &name, &user_data);
+        }
+
+        // Depth 2: %LOCALAPPDATA%/<Vendor>/<Browser>/User Data
+        if let Ok(sub_entries) = fs::read_dir(&path) {
+            for sub_entry in sub_entries.flatten() {
+                let sub_user_data = sub_entry.path().join("User Data");
+                if sub_user_data.is_dir() {
+                    let sub_name = sub_entry.file_name().to_string_lossy().to_string();
+                    let full_name = format!("{} {}", name, sub_name);
+                    find_chromium_profiles(browsers, &full_name, &sub_user_data);
+                }
+            }
+        }
+    }
+}
+
+/// 在 Chromium User Data 目录下查找含 `Bookmarks` 文件的配置文件目录（Default、Profile 1…）。
+fn find_chromium_profiles(browsers: &mut Vec<BrowserInfo>, browser_name: &str, user_data: &Path) {
+    let Ok(entries) = fs::read_dir(user_data) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let bookmarks = entry.path().join("Bookmarks");
+        if bookmarks.is_file() {
+            let profile_name = entry.file_name().to_string_lossy().to_string();
+            browsers.push(BrowserInfo {
+                name: format!("{} ({})", browser_name, profile_name),
+                bookmarks_path: bookmarks.to_string_lossy().to_string(),
+                format: BookmarkFormat::Chromium,
+            });
+        }
+    }
+}
+
+/// 按 profiles.ini 列出 Firefox 系浏览器含 `places.sqlite` 的配置文件。
+fn find_firefox_profiles(browsers: &mut Vec<BrowserInfo>, browser_name: &str, root: &Path) {
+    let Ok(content) = fs::read_to_string(root.join("profiles.ini")) else {
+        return;
+    };
+    for (profile_name, profile_dir) in parse_profiles_ini(&content, root) {
+        let places = profile_dir.join("places.sqlite");
+        if places.is_file() {
+            browsers.push(BrowserInfo {
+                name: format!("{} ({})", browser_name, profile_name),
+                bookmarks_path: places.to_string_lossy().to_string(),
+                format: BookmarkFormat::Firefox,
+            });
+        }
+    }
+}
+
+/// 解析 profiles.ini 的 `[ProfileN]` 节，返回 (配置文件名, 配置文件目录)。
+/// `IsRelative=1`（缺省）时 Path 相对 profiles.ini 所在目录。
+fn parse_profiles_ini(content: &str, root: &Path) -> Vec<(String, PathBuf)> {
+    /// 单个 `[ProfileN]` 节的已读字段。
+    #[derive(Default)]
+    struct Section {
+        /// 配置文件显示名（Name=）
+        name: Option<String>,
+        /// 配置文件目录（Path=）
+        path: Option<String>,
+        /// Path 是否相对 profiles.ini 所在目录（IsRelative=）
+        relative: bool,
+    }
+
+    let mut profiles = Vec::new();
+    let mut current: Option<Section> = None;
+    let mut flush = |section: Option<Section>| {
+        if let Some(Section {
+            name,
+            path: Some(path),
+            relative,
+        }) = section
+        {
+            let dir = if relative {
+                root.join(&path)
+            } else {
+                PathBuf::from(&path)
+            };
+            profiles.push((name.unwrap_or(path), dir));
+        }
+    };
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            flush(current.take());
+            if line.starts_with("[Profile") {
+                current = Some(Section {
+                    relative: true,
+                    ..Section::default()
+                });
+            }
+            continue;
+        }
+        let (Some(section), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        match key.trim() {
+            "Name" => section.name = Some(value.trim().to_string()),
+            "Path" => section.path = Some(value.trim().to_string()),
+            "IsRelative" => section.relative = value.trim() != "0",
+            _ => {}
+        }
+    }
+    flush(current);
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 只解析 Profile 节；相对 / 绝对路径均正确展开，缺省 Name 时以 Path 代替。
+    #[test]
+    fn parses_profiles_ini_sections() {
+        let content = "[General]\nStartWithLastProfile=1\n\n\
+            [Profile0]\nName=default-release\nIsRelative=1\nPath=Profiles/abc.default-release\n\n\
+            [Profile1]\nIsRelative=0\nPath=/data/firefox/work\n\n\
+            [Install4F96D1932A9F858E]\nDefault=Profiles/abc.default-release\n";
+        let root = Path::new("/home/u/.mozilla/firefox");
+        assert_eq!(
+            parse_profiles_ini(content, root),
+            vec![
+                (
+                    "default-release".to_string(),
+                    root.join("Profiles/abc.default-release")
+                ),
+                (
+                    "/data/firefox/work".to_string(),
+                    PathBuf::from("/data/firefox/work")
+                ),
+            ]
+        );
+    }
+}
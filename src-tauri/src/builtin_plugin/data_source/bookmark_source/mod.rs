@@ -3,8 +3,7 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, warn};
 use zerolaunch_plugin_api::config::{
@@ -15,23 +14,10 @@ use zerolaunch_plugin_api::host::PluginHandle;
 use zerolaunch_plugin_api::services::IconRequest;
 use zerolaunch_plugin_api::{CachedCandidateData, DataSource, ExecutionTarget, SearchCandidate};
 
-// ============ Chrome 书签解析相关结构 ============
+mod discovery;
+pub mod readers;
 
-/// Chrome 书签 JSON 中的节点结构
-#[derive(Debug, Deserialize)]
-struct ChromeBookmarkNode {
-    name: Option<String>,
-    url: Option<String>,
-    #[serde(rename = "type")]
-    node_type: String,
-    children: Option<Vec<ChromeBookmarkNode>>,
-}
-
-/// Chrome 书签 JSON 根结构
-#[derive(Debug, Deserialize)]
-struct ChromeBookmarksRoot {
-    roots: HashMap<String, ChromeBookmarkNode>,
-}
+pub use readers::BookmarkFormat;
 
 // ============ 配置相关结构 ============
 
@@ -44,6 +30,9 @@ pub struct BookmarkSourceConfig {
     /// 书签文件路径
     #[serde(rename = "bookmarks_path", default)]
     pub bookmarks_path: String,
+    /// 书签文件格式
+    #[serde(rename = "format", default)]
+    pub format: BookmarkFormat,
     /// 是否启用
     #[serde(rename = "enabled", default = "default_enabled_true")]
     pub enabled: bool,
@@ -74,6 +63,8 @@ pub struct BrowserInfo {
     pub name: String,
     #[serde(rename = "bookmarks_path")]
     pub bookmarks_path: String,
+    #[serde(rename = "format")]
+    pub format: BookmarkFormat,
 }
 
 /// 单个书签数据
@@ -98,7 +89,7 @@ pub struct BookmarkSourceSettings {
 
 /// 规范化 URL 以支持宽松匹配。
 /// 移除末尾的 `/`（除了 `://` 后的），转换为小写。
-fn normalize_url(url: &str) -> String {
+pub(crate) fn normalize_url(url: &str) -> String {
     let url = url.trim();
     let url = if url.ends_with('/') && !url.ends_with("://") {
         &url[..url.len() - 1]
@@ -132,132 +123,32 @@ impl BookmarkSource {
         }
     }
 
-    /// 从指定路径读取书签文件并解析。
-    /// 若文件被占用，则复制到临时文件再读取。
-    fn read_bookmarks_from_path(bookmarks_path: &str) -> Result<Vec<Bookmark>, String> {
-        let path = PathBuf::from(bookmarks_path);
+    /// 按格式读取指定路径的书签文件。
+    fn read_bookmarks_from_path(
+        bookmarks_path: &str,
+        format: BookmarkFormat,
+    ) -> Result<Vec<Bookmark>, String> {
+        let path = Path::new(bookmarks_path);
         if !path.exists() {
             return Err("Bookmarks file not found".to_string());
         }
-
-        let content = match fs::read_to_string(&path) {
-            Ok(c) => c,
-            Err(_) => {
-                let temp_dir = std::env::temp_dir();
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis();
-                let temp_path = temp_dir.join(format!("zl_bookmarks_{}.tmp", timestamp));
-
-                match fs::copy(&path, &temp_path) {
-                    Ok(_) => {
-                        let c = fs::read_to_string(&temp_path);
-                        let _ = fs::remove_file(&temp_path);
-                        c.map_err(|e| format!("读取临时书签副本失败: {}", e))?
-                    }
-                    Err(e) => return Err(format!("读取书签失败(文件可能被占用且无法复制): {}", e)),
-                }
-            }
-        };
-
-        if content.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let root: ChromeBookmarksRoot = match serde_json::from_str(&content) {
-            Ok(r) => r,
-            Err(e) => return Err(format!("Failed to parse bookmarks: {}", e)),
-        };
-
-        let mut bookmarks = Vec::new();
-        for (_, node) in root.roots {
-            Self::traverse_bookmark_node(&node, &mut bookmarks);
-        }
-        Ok(bookmarks)
-    }
-
-    /// 递归遍历书签树，提取所有 URL 节点
-    fn traverse_bookmark_node(node: &ChromeBookmarkNode, list: &mut Vec<Bookmark>) {
-        if node.node_type == "url" {
-            if let (Some(title), Some(url)) = (&node.name, &node.url) {
-                list.push(Bookmark {
-                    title: title.clone(),
-                    url: url.clone(),
-                });
-            }
-        } else if let Some(children) = &node.children {
-            for child in children {
-                Self::traverse_bookmark_node(child, list);
-            }
-        }
+        readers::reader_for(format).read(path)
     }
 
     /// 自动检测系统已安装的浏览器书签路径
     pub fn detect_installed_browsers() -> Vec<BrowserInfo> {
-        let mut browsers = Vec::new();
-        let local_app_data = std::env::var("LOCALAPPDATA").unwrap_or_default();
-        if local_app_data.is_empty() {
-            return browsers;
-        }
-
-        let base_path = PathBuf::from(&local_app_data);
-
-        let find_profiles =
-            |browsers: &mut Vec<BrowserInfo>, browser_name: String, user_data_path: PathBuf| {
-                if let Ok(entries) = fs::read_dir(&user_data_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_dir() {
-                            let bookmarks = path.join("Bookmarks");
-                            if bookmarks.exists() {
-                                let profile_name = entry.file_name().to_string_lossy().to_string();
-                                let display_name = format!("{} ({})", browser_name, profile_name);
-                                browsers.push(BrowserInfo {
-                                    name: display_name,
-                                    bookmarks_path: bookmarks.to_string_lossy().to_string(),
-                                });
-                            }
-                        }
-                    }
-                }
-            };
-
-        if let Ok(entries) = fs::read_dir(&base_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if !path.is_dir() {
-                    continue;
-                }
-
-                let name = entry.file_name().to_string_lossy().to_string();
-
-                // Depth 1: %LOCALAPPDATA%/<Browser>/User Data
-                let user_data = path.join("User Data");
-                if user_data.is_dir() {
-                    find_profiles(&mut browsers, name.clone(), user_data);
-                }
-
-                // Depth 2: %LOCALAPPDATA%/<Vendor>/<Browser>/User Data
-                if let Ok(sub_entries) = fs::read_dir(&path) {
-                    for sub_entry in sub_entries.flatten() {
-                        let sub_path = sub_entry.path();
-                        if !sub_path.is_dir() {
-                            continue;
-                        }
-
-                        let sub_user_data = sub_path.join("User Data");
-                        if sub_user_data.is_dir() {
-                            let sub_name = sub_entry.file_name().to_string_lossy().to_string();
-                            let full_name = format!("{} {}", name, sub_name);
-                            find_profiles(&mut browsers, full_name, sub_user_data);
-                        }
-                    }
-                }
-            }
-        }
+        discovery::detect_installed_browsers()
+    }
 
-        browsers
+    /// 预览书签时确定文件格式：优先取已保存书签源中同路径条目的格式，否则按文件名推断。
+    fn resolve_format(&self, bookmarks_path: &str) -> BookmarkFormat {
+        self.settings
+            .read()
+            .sources
+            .iter()
+            .find(|src| src.bookmarks_path == bookmarks_path)
+            .map(|src| src.format)
+            .unwrap_or_else(|| BookmarkFormat::guess_from_path(Path::new(bookmarks_path)))
     }
 }
 
@@ -311,6 +202,28 @@ impl Configurable for BookmarkSource {
                 .default("")
                 .editable(false)
                 .build_field(),
+                SchemaBuilder::select(
+                    "format",
+                    t_key!("bookmark-source", "fields.format.label"),
+                    t_key!("bookmark-source", "fields.format.desc"),
+                )
+                .options_with_labels(&[
+                    (
+                        "chromium",
+                        t_key!("bookmark-source", "options.format.chromium"),
+                    ),
+                    (
+                        "firefox",
+                        t_key!("bookmark-source", "options.format.firefox"),
+                    ),
+                    (
+                        "netscape_html",
+                        t_key!("bookmark-source", "options.format.netscape_html"),
+                    ),
+                    ("xbel", t_key!("bookmark-source", "options.format.xbel")),
+                ])
+                .default("chromium")
+                .build_field(),
                 SchemaBuilder::boolean(
                     "enabled",
                     t_key!("bookmark-source", "fields.enabled.label"),
@@ -399,6 +312,7 @@ impl Configurable for BookmarkSource {
                         serde_json::json!({
                             "name": b.name,
                             "bookmarks_path": b.bookmarks_path,
+                            "format": b.format,
                             "enabled": false,
                         })
                     })
@@ -410,7 +324,7 @@ impl Configurable for BookmarkSource {
                     .get("bookmarks_path")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| "缺少参数 bookmarks_path".to_string())?;
-                let bookmarks = Self::read_bookmarks_from_path(path)?;
+                let bookmarks = Self::read_bookmarks_from_path(path, self.resolve_format(path))?;
                 serde_json::to_value(bookmarks).map_err(|e| e.to_string())
             }
             _ => Err(format!("Unknown config action: {}", action)),
@@ -444,7 +358,7 @@ impl DataSource for BookmarkSource {
             .collect();
 
         for source in enabled_sources {
-            match Self::read_bookmarks_from_path(&source.bookmarks_path) {
+            match Self::read_bookmarks_from_path(&source.bookmarks_path, source.format) {
                 Ok(bookmark_list) => {
                     debug!("从 {} 加载了 {} 个书签", source.name, bookmark_list.len());
                     for bookmark in bookmark_list {
@@ -477,6 +391,7 @@ impl DataSource for BookmarkSource {
                             keywords: Vec::new(),
                             bias: 0.0,
                             trigger_keywords: Vec::new(),
+                            actions: Vec::new(),
                         };
 
                         result.add_candidate(candidate);
@@ -511,3 +426,178 @@ pub(crate) fn build_bookmark_source(
         factory: build_bookmark_source,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use zerolaunch_plugin_api::mock::helpers::mock_plugin_handle;
+
+    /// 写入一个含两条书签的 Chromium `Bookmarks` 文件。
+    fn write_chromium(path: &Path) {
+        fs::write(
+            path,
+            r#"{"roots": {"bookmark_bar": {"name": "Bar", "type": "folder", "children": [
+                {"name": "Docs", "type": "url", "url": "https://example.com/docs/"},
+                {"name": "Tracker", "type": "url", "url": "https://example.com/issues"}
+            ]}}}"#,
+        )
+        .unwrap();
+    }
+
+    /// 写入一个含一条书签的 Netscape HTML 导出文件。
+    fn write_netscape(path: &Path) {
+        fs::write(
+            path,
+            r#"<DL><p><DT><A HREF="https://example.org/wiki">Wiki</A></DL><p>"#,
+        )
+        .unwrap();
+    }
+
+    /// 候选项的 (名称, URL) 列表（按 URL 排序）。
+    fn candidates(data: &CachedCandidateData) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = data
+            .get_candidates()
+            .iter()
+            .map(|c| match &c.target {
+                ExecutionTarget::Url(url) => (c.name.clone(), url.clone()),
+                other => panic!("书签候选项应为 URL 目标: {:?}", other),
+            })
+            .collect();
+        pairs.sort_by(|a, b| a.1.cmp(&b.1));
+        pairs
+    }
+
+    /// 旧配置未记录格式与启用状态：缺省按 Chromium、启用处理；无法解析的配置回退为空。
+    #[tokio::test]
+    async fn apply_settings_fills_legacy_defaults() {
+        let source = BookmarkSource::new(mock_plugin_handle());
+        source
+            .apply_settings(serde_json::json!({
+                "sources": [{ "name": "Chrome", "bookmarks_path": "/tmp/Bookmarks" }],
+            }))
+            .await
+            .unwrap();
+
+        let settings = source.get_settings();
+        assert_eq!(settings["sources"][0]["format"], "chromium");
+        assert_eq!(settings["sources"][0]["enabled"], true);
+        assert_eq!(settings["overrides"], serde_json::json!([]));
+
+        source
+            .apply_settings(serde_json::json!({ "sources": "invalid" }))
+            .await
+            .unwrap();
+        assert_eq!(source.get_settings()["sources"], serde_json::json!([]));
+    }
+
+    /// 刷新：按各源格式读取已启用的源，跳过停用与缺失的文件，并应用排除与自定义标题覆盖。
+    #[tokio::test]
+    async fn fetch_candidates_reads_enabled_sources_with_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let chromium = dir.path().join("Bookmarks");
+        let netscape = dir.path().join("export.html");
+        write_chromium(&chromium);
+        write_netscape(&netscape);
+
+        let source = BookmarkSource::new(mock_plugin_handle());
+        source
+            .apply_settings(serde_json::json!({
+                "sources": [
+                    { "name": "Chrome", "bookmarks_path": chromium.to_string_lossy() },
+                    {
+                        "name": "Export",
+                        "bookmarks_path": netscape.to_string_lossy(),
+                        "format": "netscape_html",
+                    },
+                    {
+                        "name": "Disabled",
+                        "bookmarks_path": netscape.to_string_lossy(),
+                        "format": "chromium",
+                        "enabled": false,
+                    },
+                    {
+                        "name": "Missing",
+                        "bookmarks_path": dir.path().join("gone").to_string_lossy(),
+                    },
+                ],
+                "overrides": [
+                    { "url": "https://EXAMPLE.com/docs", "custom_title": "Manual" },
+                    { "url": "https://example.com/issues", "excluded": true },
+                ],
+            }))
+            .await
+            .unwrap();
+
+        let data = source.fetch_candidates().await;
+        assert_eq!(
+            candidates(&data),
+            vec![
+                (
+                    "Manual".to_string(),
+                    "https://example.com/docs/".to_string()
+                ),
+                ("Wiki".to_string(), "https://example.org/wiki".to_string()),
+            ]
+        );
+
+        // 停用全部源后刷新为空
+        source
+            .apply_settings(serde_json::json!({
+                "sources": [{
+                    "name": "Chrome",
+                    "bookmarks_path": chromium.to_string_lossy(),
+                    "enabled": false,
+                }],
+            }))
+            .await
+            .unwrap();
+        assert!(source.fetch_candidates().await.get_candidates().is_empty());
+    }
+
+    /// 预览读取：同路径的已保存源决定格式，未保存的路径按文件名推断；缺参数与未知动作报错。
+    #[tokio::test]
+    async fn read_bookmarks_action_resolves_format() {
+        let dir = tempfile::tempdir().unwrap();
+        // 扩展名推断为 Chromium，已保存的源将其声明为 Netscape HTML
+        let saved = dir.path().join("saved.json");
+        let guessed = dir.path().join("guessed.html");
+        write_netscape(&saved);
+        write_netscape(&guessed);
+
+        let source = BookmarkSource::new(mock_plugin_handle());
+        source
+            .apply_settings(serde_json::json!({
+                "sources": [{
+                    "name": "Export",
+                    "bookmarks_path": saved.to_string_lossy(),
+                    "format": "netscape_html",
+                }],
+            }))
+            .await
+            .unwrap();
+
+        for path in [&saved, &guessed] {
+            let preview = source
+                .execute_config_action(
+                    "read_bookmarks",
+                    &serde_json::json!({ "bookmarks_path": path.to_string_lossy() }),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                preview,
+                serde_json::json!([{ "title": "Wiki", "url": "https://example.org/wiki" }])
+            );
+        }
+
+        assert!(source
+            .execute_config_action("read_bookmarks", &serde_json::json!({}))
+            .await
+            .is_err());
+        assert!(source
+            .execute_config_action("unknown", &serde_json::json!({}))
+            .await
+            .is_err());
+    }
+}
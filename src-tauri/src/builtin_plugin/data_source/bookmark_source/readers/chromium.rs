@@ -0,0 +1,128 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::{read_text_unlocked, Bookmark, BookmarkFormat, BookmarkReader};
+
+/// Chrome 书签 JSON 中的节点结构
+#[derive(Debug, Deserialize)]
+struct ChromeBookmarkNode {
+    name: Option<String>,
+    url: Option<String>,
+    #[serde(rename = "type")]
+    node_type: String,
+    children: Option<Vec<ChromeBookmarkNode>>,
+}
+
+/// Chrome 书签 JSON 根结构
+#[derive(Debug, Deserialize)]
+struct ChromeBookmarksRoot {
+    roots: HashMap<String, ChromeBookmarkNode>,
+}
+
+/// Chromium 系浏览器 `Bookmarks` JSON 读取器。
+pub(super) struct ChromiumReader;
+
+impl ChromiumReader {
+    /// 递归遍历书签树，提取所有 URL 节点
+    fn traverse_bookmark_node(node: &ChromeBookmarkNode, list: &mut Vec<Bookmark>) {
+        if node.node_type == "url" {
+            if let (Some(title), Some(url)) = (&node.name, &node.url) {
+                list.push(Bookmark {
+                    title: title.clone(),
+                    url: url.clone(),
+                });
+            }
+        } else if let Some(children) = &node.children {
+            for child in children {
+                Self::traverse_bookmark_node(child, list);
+            }
+        }
+    }
+}
+
+impl BookmarkReader for ChromiumReader {
+    fn format(&self) -> BookmarkFormat {
+        BookmarkFormat::Chromium
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<Bookmark>, String> {
+        let content = read_text_unlocked(path)?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let root: ChromeBookmarksRoot = match serde_json::from_str(&content) {
+            Ok(r) => r,
+            Err(e) => return Err(format!("Failed to parse bookmarks: {}", e)),
+        };
+
+        let mut bookmarks = Vec::new();
+        for (_, node) in root.roots {
+            Self::traverse_bookmark_node(&node, &mut bookmarks);
+        }
+        Ok(bookmarks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 遍历各根节点下的嵌套文件夹，只收集 url 节点；空文件返回空列表，非法 JSON 报错。
+    #[test]
+    fn reads_chromium_bookmarks_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Bookmarks");
+        fs::write(
+            &path,
+            r#"{
+  "checksum": "0",
+  "roots": {
+    "bookmark_bar": {
+      "name": "Bookmarks bar", "type": "folder",
+      "children": [
+        { "name": "Docs", "type": "url", "url": "https://example.com/docs" },
+        { "name": "Work", "type": "folder", "children": [
+          { "name": "Tracker", "type": "url", "url": "https://example.com/issues" },
+          { "name": "Untitled", "type": "url" }
+        ] }
+      ]
+    },
+    "other": {
+      "name": "Other bookmarks", "type": "folder",
+      "children": [
+        { "name": "Example", "type": "url", "url": "https://example.org/" }
+      ]
+    },
+    "synced": { "name": "Mobile bookmarks", "type": "folder", "children": [] }
+  },
+  "version": 1
+}"#,
+        )
+        .unwrap();
+
+        let bookmarks = ChromiumReader.read(&path).unwrap();
+        // roots 为无序映射，按 URL 排序后比较
+        let mut pairs: Vec<(&str, &str)> = bookmarks
+            .iter()
+            .map(|b| (b.title.as_str(), b.url.as_str()))
+            .collect();
+        pairs.sort_by_key(|(_, url)| *url);
+        assert_eq!(
+            pairs,
+            vec![
+                ("Docs", "https://example.com/docs"),
+                ("Tracker", "https://example.com/issues"),
+                ("Example", "https://example.org/"),
+            ]
+        );
+
+        fs::write(&path, "  \n").unwrap();
+        assert!(ChromiumReader.read(&path).unwrap().is_empty());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(ChromiumReader.read(&path).is_err());
+    }
+}
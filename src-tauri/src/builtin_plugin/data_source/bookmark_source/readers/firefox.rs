@@ -0,0 +1,86 @@
+use rusqlite::Connection;
+use std::path::Path;
+
+use super::{
+    copy_sqlite_snapshot, is_openable_url, title_or_url, Bookmark, BookmarkFormat, BookmarkReader,
+};
+
+/// 书签查询：moz_bookmarks 中 type=1 为书签（2 为文件夹、3 为分隔符），URL 位于 moz_places。
+const BOOKMARKS_QUERY: &str = "SELECT b.title, p.url FROM moz_bookmarks b \
+     JOIN moz_places p ON p.id = b.fk \
+     WHERE b.type = 1 \
+     ORDER BY b.parent, b.position";
+
+/// Firefox 系浏览器 `places.sqlite` 读取器。
+/// 浏览器运行时独占数据库锁，因此只读取临时目录中的快照副本，绝不直接打开原文件。
+pub(super) struct FirefoxReader;
+
+impl BookmarkReader for FirefoxReader {
+    fn format(&self) -> BookmarkFormat {
+        BookmarkFormat::Firefox
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<Bookmark>, String> {
+        let temp_dir = tempfile::tempdir().map_err(|e| format!("创建临时目录失败: {}", e))?;
+        let copy = copy_sqlite_snapshot(path, temp_dir.path())?;
+        let conn =
+            Connection::open(&copy).map_err(|e| format!("打开 places.sqlite 快照失败: {}", e))?;
+        let mut stmt = conn
+            .prepare(BOOKMARKS_QUERY)
+            .map_err(|e| format!("查询书签失败: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| format!("查询书签失败: {}", e))?;
+
+        let mut bookmarks = Vec::new();
+        for row in rows {
+            let (title, url) = row.map_err(|e| format!("读取书签记录失败: {}", e))?;
+            if is_openable_url(&url) {
+                bookmarks.push(Bookmark {
+                    title: title_or_url(title.as_deref().unwrap_or_default(), &url),
+                    url,
+                });
+            }
+        }
+        Ok(bookmarks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 只读取 type=1 的书签，跳过 place: 智能文件夹；无标题书签以 URL 为标题。
+    #[test]
+    fn reads_bookmarks_from_places_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("places.sqlite");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT);
+             CREATE TABLE moz_bookmarks (id INTEGER PRIMARY KEY, type INTEGER, fk INTEGER,
+                 parent INTEGER, position INTEGER, title TEXT);
+             INSERT INTO moz_places VALUES (1, 'https://example.com/'), (2, 'place:sort=8'),
+                 (3, 'https://untitled.example/');
+             INSERT INTO moz_bookmarks VALUES (1, 2, NULL, 0, 0, 'Toolbar'),
+                 (2, 1, 1, 1, 0, 'Example'), (3, 1, 2, 1, 1, 'Recent'),
+                 (4, 1, 3, 1, 2, NULL);",
+        )
+        .unwrap();
+
+        let bookmarks = FirefoxReader.read(&path).unwrap();
+        let pairs: Vec<(&str, &str)> = bookmarks
+            .iter()
+            .map(|b| (b.title.as_str(), b.url.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Example", "https://example.com/"),
+                ("https://untitled.example/", "https://untitled.example/"),
+            ]
+        );
+    }
+}
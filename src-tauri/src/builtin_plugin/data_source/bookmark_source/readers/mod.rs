@@ -0,0 +1,131 @@
+mod chromium;
+mod firefox;
+mod netscape;
+mod xbel;
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Bookmark;
+
+/// 书签文件格式：每个书签源配置一种，决定使用哪个读取器。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BookmarkFormat {
+    /// Chromium 系浏览器的 `Bookmarks` JSON（Chrome / Edge / Brave 等）；
+    /// 旧配置未记录格式，缺省按此处理
+    #[default]
+    #[serde(rename = "chromium")]
+    Chromium,
+    /// Firefox 系浏览器的 `places.sqlite`
+    #[serde(rename = "firefox")]
+    Firefox,
+    /// Netscape HTML 书签导出格式（各浏览器与 Safari 的"导出书签"）
+    #[serde(rename = "netscape_html")]
+    NetscapeHtml,
+    /// XBEL（XML 书签交换格式，Konqueror 等使用）
+    #[serde(rename = "xbel")]
+    Xbel,
+}
+
+impl BookmarkFormat {
+    /// 按文件名推断格式（手动添加的书签文件预览时未指定格式）。
+    pub fn guess_from_path(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "sqlite" => Self::Firefox,
+            "html" | "htm" => Self::NetscapeHtml,
+            "xbel" | "xml" => Self::Xbel,
+            _ => Self::Chromium,
+        }
+    }
+}
+
+/// 书签读取器：将一种格式的书签文件解析为书签列表。
+/// 新增格式只需实现此 trait 并加入 [`READERS`]。
+pub trait BookmarkReader: Send + Sync {
+    /// 读取器处理的文件格式。
+    fn format(&self) -> BookmarkFormat;
+
+    /// 解析书签文件，返回其中全部书签（不含文件夹与分隔符）。
+    fn read(&self, path: &Path) -> Result<Vec<Bookmark>, String>;
+}
+
+/// 已注册的书签读取器。
+static READERS: [&dyn BookmarkReader; 4] = [
+    &chromium::ChromiumReader,
+    &firefox::FirefoxReader,
+    &netscape::NetscapeHtmlReader,
+    &xbel::XbelReader,
+];
+
+/// 按格式获取读取器。
+pub fn reader_for(format: BookmarkFormat) -> &'static dyn BookmarkReader {
+    READERS
+        .iter()
+        .copied()
+        .find(|r| r.format() == format)
+        .expect("每种书签格式都必须注册读取器")
+}
+
+/// 读取文本书签文件。
+/// 若文件被占用，则复制到临时文件再读取。
+fn read_text_unlocked(path: &Path) -> Result<String, String> {
+    if let Ok(content) = fs::read_to_string(path) {
+        return Ok(content);
+    }
+    let temp_dir = std::env::temp_dir();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let temp_path = temp_dir.join(format!("zl_bookmarks_{}.tmp", timestamp));
+
+    match fs::copy(path, &temp_path) {
+        Ok(_) => {
+            let content = fs::read_to_string(&temp_path);
+            let _ = fs::remove_file(&temp_path);
+            content.map_err(|e| format!("读取临时书签副本失败: {}", e))
+        }
+        Err(e) => Err(format!("读取书签失败(文件可能被占用且无法复制): {}", e)),
+    }
+}
+
+/// 将 SQLite 数据库（连同 `-wal` 文件）复制到 `temp_dir`，返回副本路径。
+/// 浏览器运行时独占数据库锁，且最近的写入可能仍在 WAL 中：只读取快照，避免锁冲突。
+pub(crate) fn copy_sqlite_snapshot(path: &Path, temp_dir: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("无效的数据库路径: {}", path.display()))?;
+    let copy = temp_dir.join(file_name);
+    fs::copy(path, &copy).map_err(|e| format!("复制数据库快照失败: {}", e))?;
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    let wal = PathBuf::from(wal);
+    if wal.is_file() {
+        let mut wal_copy = copy.as_os_str().to_owned();
+        wal_copy.push("-wal");
+        // WAL 复制失败只会丢失最近的改动，不影响读取
+        let _ = fs::copy(&wal, PathBuf::from(wal_copy));
+    }
+    Ok(copy)
+}
+
+/// 书签标题为空时以 URL 代替（Firefox / HTML 导出中无标题书签很常见）。
+fn title_or_url(title: &str, url: &str) -> String {
+    let title = title.trim();
+    if title.is_empty() {
+        url.to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+/// 过滤非网页书签：Firefox 的 `place:` 智能文件夹与 `javascript:` 小书签不可直接打开。
+fn is_openable_url(url: &str) -> bool {
+    let url = url.trim();
+    !url.is_empty() && !url.starts_with("place:") && !url.starts_with("javascript:")
+}
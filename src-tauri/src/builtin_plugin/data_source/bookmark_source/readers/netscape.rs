@@ -0,0 +1,79 @@
+use scraper::{Html, Selector};
+use std::path::Path;
+
+use super::{
+    is_openable_url, read_text_unlocked, title_or_url, Bookmark, BookmarkFormat, BookmarkReader,
+};
+
+/// Netscape HTML 书签导出格式读取器（`<DT><A HREF="…">标题</A>`）。
+/// 该格式是未闭合标签的"标签汤"，交由容错的 HTML5 解析器处理。
+pub(super) struct NetscapeHtmlReader;
+
+impl BookmarkReader for NetscapeHtmlReader {
+    fn format(&self) -> BookmarkFormat {
+        BookmarkFormat::NetscapeHtml
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<Bookmark>, String> {
+        let content = read_text_unlocked(path)?;
+        let document = Html::parse_document(&content);
+        let selector = Selector::parse("a[href]").expect("书签链接选择器必须合法");
+        Ok(document
+            .select(&selector)
+            .filter_map(|link| {
+                let url = link.value().attr("href")?.trim();
+                if !is_openable_url(url) {
+                    return None;
+                }
+                let title: String = link.text().collect();
+                Some(Bookmark {
+                    title: title_or_url(&title, url),
+                    url: url.to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 解析嵌套文件夹中的链接，跳过 place: 链接与文件夹标题。
+    #[test]
+    fn reads_netscape_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bookmarks.html");
+        fs::write(
+            &path,
+            r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1">Work</H3>
+    <DL><p>
+        <DT><A HREF="https://example.com/docs" ADD_DATE="1">Docs &amp; Guides</A>
+        <DT><A HREF="place:sort=8">Recent</A>
+    </DL><p>
+    <DT><A HREF="https://example.org/"></A>
+</DL><p>
+"#,
+        )
+        .unwrap();
+
+        let bookmarks = NetscapeHtmlReader.read(&path).unwrap();
+        let pairs: Vec<(&str, &str)> = bookmarks
+            .iter()
+            .map(|b| (b.title.as_str(), b.url.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Docs & Guides", "https://example.com/docs"),
+                ("https://example.org/", "https://example.org/"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use roxmltree::{Document, ParsingOptions};
+use std::path::Path;
+
+use super::{
+    is_openable_url, read_text_unlocked, title_or_url, Bookmark, BookmarkFormat, BookmarkReader,
+};
+
+/// XBEL（XML Bookmark Exchange Language）读取器：`<bookmark href="…"><title>…</title></bookmark>`。
+pub(super) struct XbelReader;
+
+impl BookmarkReader for XbelReader {
+    fn format(&self) -> BookmarkFormat {
+        BookmarkFormat::Xbel
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<Bookmark>, String> {
+        let content = read_text_unlocked(path)?;
+        // XBEL 文件通常带 DOCTYPE 声明，需显式允许 DTD
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let document = Document::parse_with_options(&content, options)
+            .map_err(|e| format!("Failed to parse bookmarks: {}", e))?;
+        Ok(document
+            .descendants()
+            .filter(|node| node.has_tag_name("bookmark"))
+            .filter_map(|node| {
+                let url = node.attribute("href")?.trim();
+                if !is_openable_url(url) {
+                    return None;
+                }
+                let title = node
+                    .children()
+                    .find(|child| child.has_tag_name("title"))
+                    .and_then(|title| title.text())
+                    .unwrap_or_default();
+                Some(Bookmark {
+                    title: title_or_url(title, url),
+                    url: url.to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 解析嵌套文件夹中的书签；文件夹标题不会被当作书签标题。
+    #[test]
+    fn reads_xbel_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bookmarks.xml");
+        fs::write(
+            &path,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE xbel>
+<xbel version="1.0">
+  <folder>
+    <title>Work</title>
+    <bookmark href="https://example.com/docs"><title>Docs</title></bookmark>
+    <separator/>
+    <folder>
+      <title>Nested</title>
+      <bookmark href="https://example.org/"/>
+    </folder>
+  </folder>
+</xbel>
+"#,
+        )
+        .unwrap();
+
+        let bookmarks = XbelReader.read(&path).unwrap();
+        let pairs: Vec<(&str, &str)> = bookmarks
+            .iter()
+            .map(|b| (b.title.as_str(), b.url.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Docs", "https://example.com/docs"),
+                ("https://example.org/", "https://example.org/"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,719 @@
+use crate::builtin_plugin::data_source::bookmark_source::readers::{
+    copy_sqlite_snapshot, reader_for,
+};
+use crate::builtin_plugin::data_source::bookmark_source::{
+    normalize_url, BookmarkFormat, BookmarkSource,
+};
+use crate::core::config::setting_builders::SchemaBuilder;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+use url::Url;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, DataActionBinding,
+    PrimitiveType, SettingDefinition,
+};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::IconRequest;
+use zerolaunch_plugin_api::{CachedCandidateData, DataSource, ExecutionTarget, SearchCandidate};
+
+/// 每个配置文件最多读取的历史记录条数（按最近访问排序），避免超大历史库拖慢采集。
+const SCAN_LIMIT: i64 = 20_000;
+/// 频率-新近度得分换算出的最大偏移量（得分最高的记录获得此偏移）。
+const MAX_BIAS: f64 = 1.0;
+/// 手动输入网址的访问额外计入的次数（与 Firefox frecency 一致，输入比点击链接更能表达意图）。
+const TYPED_VISIT_WEIGHT: f64 = 2.0;
+/// Chromium 时间戳纪元（1601-01-01）与 Unix 纪元之间的微秒差。
+const CHROMIUM_EPOCH_OFFSET_MICROS: i64 = 11_644_473_600_000_000;
+/// 一天的微秒数。
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Chromium `History` 数据库查询：urls 表，last_visit_time 为 1601 纪元微秒。
+const CHROMIUM_QUERY: &str = "SELECT url, title, visit_count, typed_count, last_visit_time \
+     FROM urls \
+     WHERE hidden = 0 AND visit_count > 0 AND last_visit_time >= ?1 \
+     ORDER BY last_visit_time DESC LIMIT ?2";
+/// Firefox `places.sqlite` 查询：moz_places 表，last_visit_date 为 Unix 纪元微秒。
+const FIREFOX_QUERY: &str = "SELECT url, title, visit_count, typed, last_visit_date \
+     FROM moz_places \
+     WHERE hidden = 0 AND visit_count > 0 AND last_visit_date >= ?1 \
+     ORDER BY last_visit_date DESC LIMIT ?2";
+
+/// 浏览器历史数据库格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HistoryFormat {
+    /// Chromium 系浏览器的 `History`（SQLite）
+    #[default]
+    #[serde(rename = "chromium")]
+    Chromium,
+    /// Firefox 系浏览器的 `places.sqlite`
+    #[serde(rename = "firefox")]
+    Firefox,
+}
+
+/// 单个浏览器配置文件的历史源配置。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryProfileConfig {
+    /// 浏览器及配置文件名称
+    #[serde(rename = "name", default)]
+    pub name: String,
+    /// 历史数据库路径
+    #[serde(rename = "history_path", default)]
+    pub history_path: String,
+    /// 历史数据库格式
+    #[serde(rename = "format", default)]
+    pub format: HistoryFormat,
+    /// 是否启用
+    #[serde(rename = "enabled", default = "default_enabled_true")]
+    pub enabled: bool,
+}
+
+fn default_enabled_true() -> bool {
+    true
+}
+
+fn default_max_age_days() -> u32 {
+    90
+}
+
+fn default_max_items() -> u32 {
+    300
+}
+
+/// 浏览器历史数据源的强类型配置结构。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserHistorySourceSettings {
+    /// 浏览器配置文件列表
+    #[serde(rename = "profiles", default)]
+    pub profiles: Vec<HistoryProfileConfig>,
+    /// 域名白名单：非空时只收录这些域名（含子域名）的历史
+    #[serde(rename = "allowed_domains", default)]
+    pub allowed_domains: Vec<String>,
+    /// 域名黑名单：这些域名（含子域名）的历史一律不收录，优先于白名单
+    #[serde(rename = "denied_domains", default)]
+    pub denied_domains: Vec<String>,
+    /// 只收录最近多少天内访问过的网址，0 表示不限
+    #[serde(rename = "max_age_days", default = "default_max_age_days")]
+    pub max_age_days: u32,
+    /// 最多收录的网址条数（按频率-新近度得分取前若干条）
+    #[serde(rename = "max_items", default = "default_max_items")]
+    pub max_items: u32,
+}
+
+impl Default for BrowserHistorySourceSettings {
+    fn default() -> Self {
+        Self {
+            profiles: Vec::new(),
+            allowed_domains: Vec::new(),
+            denied_domains: Vec::new(),
+            max_age_days: default_max_age_days(),
+            max_items: default_max_items(),
+        }
+    }
+}
+
+/// 从历史数据库读出的单条访问记录（同一网址跨配置文件合并后亦用此结构）。
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    /// 网址
+    url: String,
+    /// 页面标题，可能为空
+    title: String,
+    /// 访问次数
+    visit_count: i64,
+    /// 手动输入网址访问的次数
+    typed_count: i64,
+    /// 最近访问时间（Unix 纪元微秒）
+    last_visit: i64,
+}
+
+impl HistoryEntry {
+    /// 频率-新近度（frecency）得分：访问次数按最近访问时间分档加权，
+    /// 分档与 Firefox 的默认 frecency 桶一致。
+    fn frecency(&self, now_micros: i64) -> f64 {
+        let age_days = (now_micros - self.last_visit).max(0) / MICROS_PER_DAY;
+        let recency_weight = match age_days {
+            0..=4 => 100.0,
+            5..=14 => 70.0,
+            15..=31 => 50.0,
+            32..=90 => 30.0,
+            _ => 10.0,
+        };
+        (self.visit_count as f64 + self.typed_count as f64 * TYPED_VISIT_WEIGHT) * recency_weight
+    }
+}
+
+/// 域名过滤器：白名单为空表示不限制；黑名单优先。
+struct DomainFilter {
+    /// 规范化后的白名单域名
+    allowed: Vec<String>,
+    /// 规范化后的黑名单域名
+    denied: Vec<String>,
+}
+
+impl DomainFilter {
+    /// 由配置构造，域名统一小写并去掉 `*.` / `.` 前缀。
+    fn new(allowed: &[String], denied: &[String]) -> Self {
+        let normalize = |list: &[String]| -> Vec<String> {
+            list.iter()
+                .map(|d| {
+                    d.trim()
+                        .trim_start_matches("*.")
+                        .trim_start_matches('.')
+                        .to_lowercase()
+                })
+                .filter(|d| !d.is_empty())
+                .collect()
+        };
+        Self {
+            allowed: normalize(allowed),
+            denied: normalize(denied),
+        }
+    }
+
+    /// 判断网址是否应收录：仅收录 http(s) 网址，且域名通过黑白名单。
+    fn accepts(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return false;
+        };
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return false;
+        }
+        let Some(host) = parsed.host_str().map(str::to_lowercase) else {
+            return false;
+        };
+        let matches = |domain: &String| {
+            host == *domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        };
+        if self.denied.iter().any(matches) {
+            return false;
+        }
+        self.allowed.is_empty() || self.allowed.iter().any(matches)
+    }
+}
+
+/// 浏览器历史数据源 - 从 Chromium `History` 与 Firefox `places.sqlite` 的快照副本读取访问历史，
+/// 按频率-新近度得分换算候选项偏移量。与同一配置文件中的书签按 `normalize_url` 去重，
+/// 书签由 BookmarkSource 提供。
+///
+/// 仅在 candidate_pipeline 的 DataSource 阶段使用，经 inventory 注册。
+pub struct BrowserHistorySource {
+    /// 组件 ID、名称、类型等元数据。
+    core: ComponentCore,
+    /// 通过 RwLock 提供内部可变性，仅在 apply_settings 时写入。
+    settings: RwLock<BrowserHistorySourceSettings>,
+    #[allow(dead_code)]
+    handle: Arc<PluginHandle>,
+}
+
+impl BrowserHistorySource {
+    pub fn new(handle: Arc<PluginHandle>) -> Self {
+        BrowserHistorySource {
+            core: ComponentCore::new(
+                "browser-history-source".to_string(),
+                t_key!("browser-history-source", "name").to_string(),
+                t_key!("browser-history-source", "description").to_string(),
+                ComponentType::DataSource,
+                35,
+            ),
+            settings: RwLock::new(BrowserHistorySourceSettings::default()),
+            handle,
+        }
+    }
+
+    /// 自动检测浏览器配置文件的历史数据库：复用书签检测结果，
+    /// Chromium 取 `Bookmarks` 同目录的 `History`，Firefox 的历史与书签同在 `places.sqlite`。
+    fn detect_history_profiles() -> Vec<HistoryProfileConfig> {
+        BookmarkSource::detect_installed_browsers()
+            .into_iter()
+            .filter_map(|browser| {
+                let bookmarks = Path::new(&browser.bookmarks_path);
+                let (history_path, format) = match browser.format {
+                    BookmarkFormat::Chromium => {
+                        (bookmarks.with_file_name("History"), HistoryFormat::Chromium)
+                    }
+                    BookmarkFormat::Firefox => (bookmarks.to_path_buf(), HistoryFormat::Firefox),
+                    _ => return None,
+                };
+                history_path.is_file().then(|| HistoryProfileConfig {
+                    name: browser.name,
+                    history_path: history_path.to_string_lossy().to_string(),
+                    format,
+                    enabled: false,
+                })
+            })
+            .collect()
+    }
+
+    /// 读取单个配置文件的历史记录（快照副本），只返回 `min_visit` 之后访问过的网址。
+    fn read_history(
+        profile: &HistoryProfileConfig,
+        min_visit: i64,
+    ) -> Result<Vec<HistoryEntry>, String> {
+        let path = Path::new(&profile.history_path);
+        if !path.exists() {
+            return Err("History database not found".to_string());
+        }
+        let temp_dir = tempfile::tempdir().map_err(|e| format!("创建临时目录失败: {}", e))?;
+        let copy = copy_sqlite_snapshot(path, temp_dir.path())?;
+        let conn = Connection::open(&copy).map_err(|e| format!("打开历史数据库快照失败: {}", e))?;
+
+        let (query, min_time, epoch_offset) = match profile.format {
+            HistoryFormat::Chromium => (
+                CHROMIUM_QUERY,
+                min_visit + CHROMIUM_EPOCH_OFFSET_MICROS,
+                CHROMIUM_EPOCH_OFFSET_MICROS,
+            ),
+            HistoryFormat::Firefox => (FIREFOX_QUERY, min_visit, 0),
+        };
+        let mut stmt = conn
+            .prepare(query)
+            .map_err(|e| format!("查询历史记录失败: {}", e))?;
+        let rows = stmt
+            .query_map(params![min_time, SCAN_LIMIT], |row| {
+                Ok(HistoryEntry {
+                    url: row.get(0)?,
+                    title: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                    visit_count: row.get(2)?,
+                    typed_count: row.get(3)?,
+                    last_visit: row.get::<_, i64>(4)? - epoch_offset,
+                })
+            })
+            .map_err(|e| format!("查询历史记录失败: {}", e))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("读取历史记录失败: {}", e))
+    }
+
+    /// 读取与历史数据库同属一个配置文件的书签，返回规范化后的网址集合。
+    fn profile_bookmark_urls(profile: &HistoryProfileConfig) -> HashSet<String> {
+        let history = Path::new(&profile.history_path);
+        let (bookmarks, format) = match profile.format {
+            HistoryFormat::Chromium => (
+                history.with_file_name("Bookmarks"),
+                BookmarkFormat::Chromium,
+            ),
+            HistoryFormat::Firefox => (history.to_path_buf(), BookmarkFormat::Firefox),
+        };
+        if !bookmarks.is_file() {
+            return HashSet::new();
+        }
+        match reader_for(format).read(&bookmarks) {
+            Ok(list) => list.iter().map(|b| normalize_url(&b.url)).collect(),
+            Err(e) => {
+                warn!("读取书签失败，跳过书签去重 ({}): {}", profile.name, e);
+                HashSet::new()
+            }
+        }
+    }
+}
+
+/// 按规范化网址合并各配置文件的历史记录：访问次数累加，标题与访问时间取最近一次。
+fn merge_entries(
+    merged: &mut HashMap<String, HistoryEntry>,
+    entries: Vec<HistoryEntry>,
+    bookmarked: &HashSet<String>,
+    filter: &DomainFilter,
+) {
+    for entry in entries {
+        if !filter.accepts(&entry.url) {
+            continue;
+        }
+        let key = normalize_url(&entry.url);
+        if bookmarked.contains(&key) {
+            continue;
+        }
+        match merged.get_mut(&key) {
+            Some(existing) => {
+                existing.visit_count += entry.visit_count;
+                existing.typed_count += entry.typed_count;
+                if entry.last_visit > existing.last_visit {
+                    existing.last_visit = entry.last_visit;
+                    if !entry.title.trim().is_empty() {
+                        existing.title = entry.title;
+                    }
+                }
+            }
+            None => {
+                merged.insert(key, entry);
+            }
+        }
+    }
+}
+
+/// 取频率-新近度得分最高的 `max_items` 条记录，按对数缩放换算为 `[0, MAX_BIAS]` 的偏移量，
+/// 返回 (记录, 偏移量)，得分高者在前。
+fn rank_entries(
+    entries: impl IntoIterator<Item = HistoryEntry>,
+    now_micros: i64,
+    max_items: usize,
+) -> Vec<(HistoryEntry, f64)> {
+    let mut scored: Vec<(HistoryEntry, f64)> = entries
+        .into_iter()
+        .map(|entry| {
+            let score = entry.frecency(now_micros);
+            (entry, score)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(max_items);
+
+    let top = scored.first().map_or(0.0, |(_, score)| *score);
+    let scale = (1.0 + top).ln();
+    for (_, score) in &mut scored {
+        *score = if scale > 0.0 {
+            MAX_BIAS * (1.0 + *score).ln() / scale
+        } else {
+            0.0
+        };
+    }
+    scored
+}
+
+/// 当前时间（Unix 纪元微秒）。
+fn now_micros() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_micros() as i64)
+}
+
+#[async_trait]
+impl Configurable for BrowserHistorySource {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::array(
+                "profiles",
+                t_key!("browser-history-source", "fields.profiles.label"),
+                t_key!("browser-history-source", "fields.profiles.desc"),
+            )
+            .group(t_key!("browser-history-source", "groups.profiles"))
+            .order(1)
+            .data_action(DataActionBinding {
+                action: "detect_profiles".into(),
+                component: None,
+                label_field: "name".into(),
+                label_field_label: "浏览器名称".into(),
+                value_field: "profiles".into(),
+                merge_key: Some("history_path".into()),
+                field_mapping: vec![],
+            })
+            .object_items(vec![
+                SchemaBuilder::text(
+                    "name",
+                    t_key!("browser-history-source", "fields.name.label"),
+                    t_key!("browser-history-source", "fields.name.desc"),
+                )
+                .default("")
+                .editable(false)
+                .build_field(),
+                SchemaBuilder::path(
+                    "history_path",
+                    t_key!("browser-history-source", "fields.history_path.label"),
+                    t_key!("browser-history-source", "fields.history_path.desc"),
+                )
+                .file()
+                .default("")
+                .editable(false)
+                .build_field(),
+                SchemaBuilder::select(
+                    "format",
+                    t_key!("browser-history-source", "fields.format.label"),
+                    t_key!("browser-history-source", "fields.format.desc"),
+                )
+                .options_with_labels(&[
+                    (
+                        "chromium",
+                        t_key!("browser-history-source", "options.format.chromium"),
+                    ),
+                    (
+                        "firefox",
+                        t_key!("browser-history-source", "options.format.firefox"),
+                    ),
+                ])
+                .default("chromium")
+                .editable(false)
+                .build_field(),
+                SchemaBuilder::boolean(
+                    "enabled",
+                    t_key!("browser-history-source", "fields.enabled.label"),
+                    t_key!("browser-history-source", "fields.enabled.desc"),
+                )
+                .default(true)
+                .build_field(),
+            ])
+            .master_detail_ui()
+            .default(serde_json::json!([]))
+            .build(),
+            SchemaBuilder::array(
+                "allowed_domains",
+                t_key!("browser-history-source", "fields.allowed_domains.label"),
+                t_key!("browser-history-source", "fields.allowed_domains.desc"),
+            )
+            .group(t_key!("browser-history-source", "groups.filter"))
+            .order(2)
+            .primitive_item(PrimitiveType::Text)
+            .tags_ui()
+            .min_items(0)
+            .default(serde_json::json!([]))
+            .build(),
+            SchemaBuilder::array(
+                "denied_domains",
+                t_key!("browser-history-source", "fields.denied_domains.label"),
+                t_key!("browser-history-source", "fields.denied_domains.desc"),
+            )
+            .group(t_key!("browser-history-source", "groups.filter"))
+            .order(3)
+            .primitive_item(PrimitiveType::Text)
+            .tags_ui()
+            .min_items(0)
+            .default(serde_json::json!([]))
+            .build(),
+            SchemaBuilder::integer(
+                "max_age_days",
+                t_key!("browser-history-source", "fields.max_age_days.label"),
+                t_key!("browser-history-source", "fields.max_age_days.desc"),
+            )
+            .group(t_key!("browser-history-source", "groups.filter"))
+            .order(4)
+            .default(default_max_age_days())
+            .min(0.0)
+            .max(3650.0)
+            .build(),
+            SchemaBuilder::integer(
+                "max_items",
+                t_key!("browser-history-source", "fields.max_items.label"),
+                t_key!("browser-history-source", "fields.max_items.desc"),
+            )
+            .group(t_key!("browser-history-source", "groups.filter"))
+            .order(5)
+            .default(default_max_items())
+            .min(1.0)
+            .max(5000.0)
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let parsed: BrowserHistorySourceSettings =
+            serde_json::from_value(settings).unwrap_or_default();
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+
+    fn config_actions(&self) -> Vec<ConfigActionDef> {
+        vec![ConfigActionDef {
+            action: "detect_profiles".to_string(),
+            label: "自动检测浏览器".to_string(),
+            description: "扫描系统中已安装浏览器的历史记录数据库".to_string(),
+        }]
+    }
+
+    async fn execute_config_action(
+        &self,
+        action: &str,
+        _params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match action {
+            "detect_profiles" => {
+                let profiles = Self::detect_history_profiles();
+                Ok(serde_json::json!({ "profiles": profiles }))
+            }
+            _ => Err(format!("Unknown config action: {}", action)),
+        }
+    }
+}
+
+#[async_trait]
+impl DataSource for BrowserHistorySource {
+    async fn fetch_candidates(&self) -> CachedCandidateData {
+        let mut result = CachedCandidateData::new();
+
+        // Clone data and release the lock guard before any I/O operations.
+        let settings = self.settings.read().clone();
+        let enabled_profiles: Vec<&HistoryProfileConfig> =
+            settings.profiles.iter().filter(|p| p.enabled).collect();
+        if enabled_profiles.is_empty() || settings.max_items == 0 {
+            return result;
+        }
+
+        let now = now_micros();
+        let min_visit = match settings.max_age_days {
+            0 => 0,
+            days => now - i64::from(days) * MICROS_PER_DAY,
+        };
+        let filter = DomainFilter::new(&settings.allowed_domains, &settings.denied_domains);
+
+        let mut merged: HashMap<String, HistoryEntry> = HashMap::new();
+        for profile in enabled_profiles {
+            match Self::read_history(profile, min_visit) {
+                Ok(entries) => {
+                    debug!("从 {} 读取了 {} 条历史记录", profile.name, entries.len());
+                    let bookmarked = Self::profile_bookmark_urls(profile);
+                    merge_entries(&mut merged, entries, &bookmarked, &filter);
+                }
+                Err(e) => {
+                    warn!("读取浏览器历史失败 ({}): {}", profile.name, e);
+                }
+            }
+        }
+
+        for (entry, bias) in rank_entries(merged.into_values(), now, settings.max_items as usize) {
+            let name = if entry.title.trim().is_empty() {
+                entry.url.clone()
+            } else {
+                entry.title.trim().to_string()
+            };
+            result.add_candidate(SearchCandidate {
+                id: 0,
+                name,
+                icon: IconRequest::Url(entry.url.clone()),
+                target: ExecutionTarget::Url(entry.url),
+                keywords: Vec::new(),
+                bias,
+                trigger_keywords: Vec::new(),
+                actions: Vec::new(),
+            });
+        }
+
+        result
+    }
+}
+
+use crate::plugin_framework::builtin_registry::{DataSourceEntry, InventoryContext};
+
+pub(crate) fn build_browser_history_source(
+    ctx: &InventoryContext,
+) -> (Arc<dyn Configurable>, Arc<dyn DataSource>) {
+    let handle = ctx.get_handle("browser-history-source");
+    let source: Arc<dyn DataSource> = Arc::new(BrowserHistorySource::new(handle));
+    let configurable: Arc<dyn Configurable> = source.clone();
+    (configurable, source)
+}
+
+::inventory::submit! {
+    DataSourceEntry {
+        component_id: "browser-history-source",
+        handle_key: "browser-history-source",
+        // 排在书签之后：执行目标相同时书签候选项优先
+        priority: 35,
+        factory: build_browser_history_source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造一条历史记录，`age_days` 天前最近访问。
+    fn entry(url: &str, visits: i64, typed: i64, age_days: i64, now: i64) -> HistoryEntry {
+        HistoryEntry {
+            url: url.to_string(),
+            title: String::new(),
+            visit_count: visits,
+            typed_count: typed,
+            last_visit: now - age_days * MICROS_PER_DAY,
+        }
+    }
+
+    /// 黑名单优先于白名单，子域名随父域名匹配，非 http(s) 网址一律跳过。
+    #[test]
+    fn domain_filter_matches_subdomains() {
+        let filter = DomainFilter::new(
+            &["*.corp.example".to_string()],
+            &["secret.corp.example".to_string()],
+        );
+        assert!(filter.accepts("https://corp.example/"));
+        assert!(filter.accepts("https://grafana.corp.example/d/abc"));
+        assert!(!filter.accepts("https://secret.corp.example/"));
+        assert!(!filter.accepts("https://notcorp.example/"));
+        assert!(!filter.accepts("file:///etc/hosts"));
+        assert!(DomainFilter::new(&[], &[]).accepts("http://anything.test/"));
+    }
+
+    /// 近期常访问者得分最高；同一网址跨配置文件合并，书签中已有的网址被剔除。
+    #[test]
+    fn merges_and_ranks_by_frecency() {
+        let now = 1_000 * MICROS_PER_DAY;
+        let filter = DomainFilter::new(&[], &[]);
+        let bookmarked = HashSet::from([normalize_url("https://bookmarked.test/")]);
+        let mut merged = HashMap::new();
+        merge_entries(
+            &mut merged,
+            vec![
+                entry("https://dash.test/", 5, 0, 1, now),
+                entry("https://old.test/", 50, 0, 200, now),
+                entry("https://bookmarked.test", 99, 0, 1, now),
+            ],
+            &bookmarked,
+            &filter,
+        );
+        merge_entries(
+            &mut merged,
+            vec![entry("https://DASH.test", 5, 1, 2, now)],
+            &HashSet::new(),
+            &filter,
+        );
+
+        let ranked = rank_entries(merged.into_values(), now, 10);
+        let urls: Vec<&str> = ranked.iter().map(|(e, _)| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://dash.test/", "https://old.test/"]);
+        assert_eq!(ranked[0].0.visit_count, 10);
+        assert!((ranked[0].1 - MAX_BIAS).abs() < 1e-9);
+        assert!(ranked[1].1 > 0.0 && ranked[1].1 < MAX_BIAS);
+
+        let truncated = rank_entries(vec![entry("https://a.test/", 1, 0, 0, now)], now, 0);
+        assert!(truncated.is_empty());
+    }
+
+    /// 从 Chromium History 快照读取：按最大天数过滤并换算时间纪元。
+    #[test]
+    fn reads_chromium_history_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("History");
+        let conn = Connection::open(&path).unwrap();
+        let now = now_micros();
+        let recent = now - MICROS_PER_DAY + CHROMIUM_EPOCH_OFFSET_MICROS;
+        let stale = now - 400 * MICROS_PER_DAY + CHROMIUM_EPOCH_OFFSET_MICROS;
+        conn.execute_batch(
+            "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT, title TEXT, visit_count INTEGER,
+                 typed_count INTEGER, last_visit_time INTEGER, hidden INTEGER);",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO urls VALUES (1, 'https://dash.test/', 'Dash', 3, 1, ?1, 0),
+                 (2, 'https://stale.test/', 'Stale', 9, 0, ?2, 0),
+                 (3, 'https://hidden.test/', 'Hidden', 9, 0, ?1, 1)",
+            params![recent, stale],
+        )
+        .unwrap();
+        drop(conn);
+
+        let profile = HistoryProfileConfig {
+            name: "Chrome (Default)".to_string(),
+            history_path: path.to_string_lossy().to_string(),
+            format: HistoryFormat::Chromium,
+            enabled: true,
+        };
+        let entries =
+            BrowserHistorySource::read_history(&profile, now - 90 * MICROS_PER_DAY).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "Dash");
+        assert_eq!(entries[0].last_visit, now - MICROS_PER_DAY);
+    }
+}
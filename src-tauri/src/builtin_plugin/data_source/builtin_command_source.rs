@@ -110,6 +110,7 @@ impl DataSource for BuiltinCommandSource {
                     .iter()
                     .map(|s| s.to_lowercase())
                     .collect(),
+                actions: Vec::new(),
             };
 
             tracing::debug!(
@@ -2,8 +2,9 @@ use crate::core::config::setting_builders::SchemaBuilder;
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use tracing::debug;
 use zerolaunch_plugin_api::config::{
     ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
@@ -12,6 +13,56 @@ use zerolaunch_plugin_api::host::PluginHandle;
 use zerolaunch_plugin_api::services::IconRequest;
 use zerolaunch_plugin_api::{CachedCandidateData, DataSource, ExecutionTarget, SearchCandidate};
 
+/// 捕获型命令的默认超时（秒）。
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// 命令模板 → 输出选项的进程级登记表：数据源加载候选项时写入，命令执行器按模板查找。
+/// ExecutionTarget::Command 只携带命令模板，执行器经此获取所属命令条目的输出模式。
+static OUTPUT_OPTIONS: LazyLock<RwLock<HashMap<String, CommandOutputOptions>>> =
+    LazyLock::new(Default::default);
+
+/// 查找命令模板登记的输出选项；未登记（如配置已变更）时按后台模式执行。
+pub(crate) fn output_options_for(command: &str) -> CommandOutputOptions {
+    OUTPUT_OPTIONS
+        .read()
+        .get(command)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// 自定义命令的输出模式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CommandOutputMode {
+    /// 后台运行，不捕获输出（默认，保持原有行为）
+    #[default]
+    #[serde(rename = "background")]
+    Background,
+    /// 捕获输出，结束后在输出面板中展示
+    #[serde(rename = "show_output")]
+    ShowOutput,
+    /// 捕获输出，结束后复制 stdout 到剪贴板并通知退出状态
+    #[serde(rename = "copy_output")]
+    CopyOutput,
+}
+
+/// 单条命令的输出选项（由 CommandEntry 派生，供命令执行器使用）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CommandOutputOptions {
+    /// 输出模式
+    pub mode: CommandOutputMode,
+    /// 捕获模式下的最长运行时间（秒）
+    pub timeout_secs: u64,
+}
+
+impl Default for CommandOutputOptions {
+    fn default() -> Self {
+        Self {
+            mode: CommandOutputMode::default(),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        }
+    }
+}
+
 /// 单条自定义命令的配置项。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandEntry {
@@ -22,6 +73,30 @@ pub struct CommandEntry {
     /// 触发关键词，逗号分隔。为空时使用 name 作为默认触发词
     #[serde(rename = "triggerKeywords", default)]
     pub trigger_keywords: String,
+    /// 输出模式：后台运行 / 显示输出 / 复制输出
+    #[serde(rename = "outputMode", default)]
+    pub output_mode: CommandOutputMode,
+    /// 捕获模式下的超时（秒），0 视为默认值
+    #[serde(rename = "timeoutSecs", default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+impl CommandEntry {
+    /// 派生命令执行器使用的输出选项。
+    fn output_options(&self) -> CommandOutputOptions {
+        CommandOutputOptions {
+            mode: self.output_mode,
+            timeout_secs: if self.timeout_secs == 0 {
+                DEFAULT_TIMEOUT_SECS
+            } else {
+                self.timeout_secs
+            },
+        }
+    }
 }
 
 /// 自定义命令数据源的强类型配置结构。
@@ -132,6 +207,35 @@ impl Configurable for CommandSource {
             )
             .default("")
             .build_field(),
+            SchemaBuilder::select(
+                "outputMode",
+                t_key!("command-source", "fields.outputMode.label"),
+                t_key!("command-source", "fields.outputMode.desc"),
+            )
+            .options_with_labels(&[
+                (
+                    "background",
+                    t_key!("command-source", "options.outputMode.background"),
+                ),
+                (
+                    "show_output",
+                    t_key!("command-source", "options.outputMode.show_output"),
+                ),
+                (
+                    "copy_output",
+                    t_key!("command-source", "options.outputMode.copy_output"),
+                ),
+            ])
+            .default("background")
+            .build_field(),
+            SchemaBuilder::integer(
+                "timeoutSecs",
+                t_key!("command-source", "fields.timeoutSecs.label"),
+                t_key!("command-source", "fields.timeoutSecs.desc"),
+            )
+            .default(DEFAULT_TIMEOUT_SECS)
+            .min(1.0)
+            .build_field(),
         ])
         .table_ui()
         .default(serde_json::json!([]))
@@ -154,11 +258,13 @@ impl DataSource for CommandSource {
     async fn fetch_candidates(&self) -> CachedCandidateData {
         let mut result = CachedCandidateData::new();
         let s = self.settings.read();
+        let mut output_options = HashMap::new();
 
         for entry in &s.commands {
             if entry.name.is_empty() || entry.command.is_empty() {
                 continue;
             }
+            output_options.insert(entry.command.clone(), entry.output_options());
 
             let icon = self.resolve_icon(&entry.command);
             // 解析触发关键词：逗号分隔，去空白，过滤空值；为空时默认使用名称
@@ -180,6 +286,7 @@ impl DataSource for CommandSource {
                 keywords: Vec::new(),
                 bias: 0.0,
                 trigger_keywords,
+                actions: Vec::new(),
             };
 
             debug!(
@@ -189,6 +296,7 @@ impl DataSource for CommandSource {
             result.add_candidate(candidate);
         }
 
+        *OUTPUT_OPTIONS.write() = output_options;
         result
     }
 }
@@ -0,0 +1,550 @@
+use crate::core::config::setting_builders::SchemaBuilder;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+use zerolaunch_plugin_api::common::{desktop_file_id, split_exec, DesktopEntry};
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigError, Configurable, PrimitiveType, SettingDefinition,
+};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::parameter::TemplateParser;
+use zerolaunch_plugin_api::services::path::path_resolver::KnownPath;
+use zerolaunch_plugin_api::services::IconRequest;
+use zerolaunch_plugin_api::{
+    CachedCandidateData, CandidateAction, DataSource, ExecutionTarget, ResultAction,
+    SearchCandidate,
+};
+
+/// `applications/` 目录的递归深度上限（厂商子目录通常至多一两层）。
+const MAX_SCAN_DEPTH: usize = 4;
+/// 附加动作 ID 前缀，避免与执行器提供的动作 ID 冲突。
+const ACTION_ID_PREFIX: &str = "desktop-action:";
+/// 文件/网址字段码展开成的用户参数：取值由解析器按 shell 规则加引号，用户输入不会成为 shell 语法。
+const USER_ARG_PLACEHOLDER: &str = "{|shellquote}";
+
+/// 桌面条目数据源的强类型配置结构。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct DesktopEntrySourceSettings {
+    /// 扫描的 `applications/` 目录，靠前者优先（同一桌面文件 ID 只取首个）
+    #[serde(rename = "directories", default)]
+    directories: Vec<String>,
+    /// 是否将 `%f/%u/%F/%U` 字段码展开为用户参数（启动时填写文件/网址）；
+    /// 关闭时剔除字段码，直接无参启动
+    #[serde(rename = "prompt_field_codes", default)]
+    prompt_field_codes: bool,
+}
+
+/// 桌面条目数据源 - 解析 freedesktop `.desktop` 文件。
+/// 遵循 NoDisplay/Hidden/OnlyShowIn/TryExec 过滤规则，Exec 转换为命令模板交由命令执行器
+/// 经 ParameterResolver 展开；`[Desktop Action]` 作为候选项的附加动作。
+///
+/// 生成的命令为 POSIX shell 语法，模块仅在 Unix 上编译；
+/// 仅在 candidate_pipeline 的 DataSource 阶段使用，经 inventory 注册。
+pub struct DesktopEntrySource {
+    /// 组件 ID、名称、类型等元数据。
+    core: ComponentCore,
+    /// 通过 RwLock 提供内部可变性，仅在 apply_settings 时写入。
+    settings: RwLock<DesktopEntrySourceSettings>,
+    /// 用于解析默认应用目录（KnownPath）。
+    handle: Arc<PluginHandle>,
+}
+
+impl DesktopEntrySource {
+    pub fn new(handle: Arc<PluginHandle>) -> Self {
+        DesktopEntrySource {
+            core: ComponentCore::new(
+                "desktop-entry-source".to_string(),
+                t_key!("desktop-entry-source", "name").to_string(),
+                t_key!("desktop-entry-source", "description").to_string(),
+                ComponentType::DataSource,
+                5,
+            ),
+            settings: RwLock::new(DesktopEntrySourceSettings::default()),
+            handle,
+        }
+    }
+
+    /// 将单个桌面条目转换为搜索候选项；不应展示的条目返回 None。
+    /// 参数：path - `.desktop` 文件路径；desktops - 当前桌面环境；locale - 本地化 locale；
+    /// prompt - 是否将文件/网址字段码展开为用户参数。
+    fn build_candidate(
+        path: &Path,
+        desktops: &[String],
+        locale: Option<&str>,
+        prompt: bool,
+    ) -> Option<SearchCandidate> {
+        let entry = DesktopEntry::load(path)?;
+        if entry.entry_type().as_deref() != Some("Application")
+            || entry.no_display()
+            || entry.hidden()
+            || !entry.is_shown_in(desktops)
+        {
+            return None;
+        }
+        if entry.try_exec().is_some_and(|p| !program_exists(&p)) {
+            debug!("DesktopEntrySource: TryExec 不可用，跳过 {:?}", path);
+            return None;
+        }
+        let name = entry.name(locale)?;
+        let path_str = path.to_string_lossy().to_string();
+        let command = exec_to_template(&entry, &entry.exec()?, &path_str, locale, prompt)?;
+
+        // 检索词：未本地化的名称（跨语言检索）+ 本地化的 GenericName 与 Keywords
+        let mut seen: HashSet<String> = HashSet::from([name.to_lowercase()]);
+        let keywords: Vec<String> = entry
+            .name(None)
+            .into_iter()
+            .chain(entry.generic_name(locale))
+            .chain(entry.keywords(locale))
+            .filter(|k| !k.trim().is_empty() && seen.insert(k.to_lowercase()))
+            .collect();
+
+        let actions = entry
+            .actions(locale)
+            .into_iter()
+            .filter_map(|action| {
+                // 附加动作不经参数面板：字段码一律剔除
+                let command = exec_to_template(&entry, &action.exec?, &path_str, locale, false)?;
+                Some(CandidateAction {
+                    action: ResultAction {
+                        id: format!("{}{}", ACTION_ID_PREFIX, action.id),
+                        label: action.name,
+                        icon: IconRequest::Path(String::new()),
+                        is_default: false,
+                        shortcut_key: String::new(),
+                    },
+                    target: ExecutionTarget::Command(command),
+                })
+            })
+            .collect();
+
+        // 需要用户参数时以名称作为行内参数触发词（与自定义命令一致）
+        let trigger_keywords = if TemplateParser::count_user_args(&command) > 0 {
+            vec![name.to_lowercase()]
+        } else {
+            Vec::new()
+        };
+
+        Some(SearchCandidate {
+            id: 0,
+            name,
+            icon: IconRequest::Path(path_str),
+            target: ExecutionTarget::Command(command),
+            keywords,
+            bias: 0.0,
+            trigger_keywords,
+            actions,
+        })
+    }
+}
+
+/// 将 Exec 值转换为可交给 shell 执行的命令模板。
+/// 字段码处理：`%f/%u/%F/%U` → `{|shellquote}`（prompt 为 true）或剔除；`%i` → `--icon <Icon>`；
+/// `%c` → 本地化名称；`%k` → 桌面文件路径；`%%` → `%`；其余（已废弃）字段码剔除。
+/// `Path=` 工作目录以 `cd` 前缀表达。字面文本（含 `%c/%k` 展开值）先按 shell 规则引用，
+/// 再将大括号转义为 `{{`/`}}`，避免被模板解析器当作占位符。
+/// 返回：命令模板；Exec 为空时返回 None。
+fn exec_to_template(
+    entry: &DesktopEntry,
+    exec: &str,
+    desktop_path: &str,
+    locale: Option<&str>,
+    prompt: bool,
+) -> Option<String> {
+    let mut args: Vec<String> = Vec::new();
+    for arg in split_exec(exec) {
+        if arg == "%i" {
+            if let Some(icon) = entry.icon() {
+                args.push("--icon".to_string());
+                args.push(quote_literal(&icon));
+            }
+            continue;
+        }
+        // template 为已引用/转义的部分，literal 为尚未引用的字面文本
+        let mut template = String::new();
+        let mut literal = String::new();
+        let mut has_code = false;
+        let mut chars = arg.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('%') => literal.push('%'),
+                Some(code) => {
+                    has_code = true;
+                    match code {
+                        'f' | 'u' | 'F' | 'U' if prompt => {
+                            if !literal.is_empty() {
+                                template.push_str(&quote_literal(&std::mem::take(&mut literal)));
+                            }
+                            template.push_str(USER_ARG_PLACEHOLDER);
+                        }
+                        'c' => literal.push_str(&entry.name(locale).unwrap_or_default()),
+                        'k' => literal.push_str(desktop_path),
+                        _ => {}
+                    }
+                }
+                None => literal.push('%'),
+            }
+        }
+        // 仅由被剔除的字段码构成的参数整体丢弃
+        if has_code && template.is_empty() && literal.is_empty() {
+            continue;
+        }
+        if !literal.is_empty() || template.is_empty() {
+            template.push_str(&quote_literal(&literal));
+        }
+        args.push(template);
+    }
+    if args.is_empty() {
+        return None;
+    }
+    let command = args.join(" ");
+    Some(match entry.working_dir() {
+        Some(dir) if !dir.is_empty() => format!("cd {} && {}", quote_literal(&dir), command),
+        _ => command,
+    })
+}
+
+/// 字面文本转为模板片段：按 shell 规则引用后转义大括号。
+fn quote_literal(text: &str) -> String {
+    shell_quote(text).replace('{', "{{").replace('}', "}}")
+}
+
+/// 按 POSIX shell 规则引用单个参数：仅含安全字符时原样返回，否则以单引号包裹。
+fn shell_quote(arg: &str) -> String {
+    let is_safe = |c: char| c.is_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// 检查 TryExec 指定的程序是否存在：含路径分隔符时检查该文件，否则在 PATH 中查找。
+fn program_exists(program: &str) -> bool {
+    if program.contains('/') {
+        return Path::new(program).is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// 当前桌面环境列表（`XDG_CURRENT_DESKTOP` 按冒号拆分）。
+fn current_desktops() -> Vec<String> {
+    std::env::var("XDG_CURRENT_DESKTOP")
+        .map(|v| {
+            v.split(':')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 名称本地化所用的 POSIX locale（LC_ALL → LC_MESSAGES → LANG，忽略 C/POSIX）。
+fn message_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|v| !v.is_empty())
+        .filter(|v| v != "C" && v != "POSIX")
+}
+
+#[async_trait]
+impl Configurable for DesktopEntrySource {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        // 通过 PathResolver 动态解析应用目录（Linux 上"开始菜单"即 applications/）
+        let default_directories: Vec<String> =
+            [KnownPath::UserStartMenu, KnownPath::CommonStartMenu]
+                .into_iter()
+                .filter_map(|known| {
+                    self.handle
+                        .resolve_path(known)
+                        .map_err(|e| warn!("解析应用目录失败 ({:?}): {}", known, e))
+                        .ok()
+                })
+                .collect();
+
+        vec![
+            SchemaBuilder::array(
+                "directories",
+                t_key!("desktop-entry-source", "fields.directories.label"),
+                t_key!("desktop-entry-source", "fields.directories.desc"),
+            )
+            .group(t_key!("desktop-entry-source", "groups.scan"))
+            .order(1)
+            .primitive_item(PrimitiveType::Text)
+            .tags_ui()
+            .min_items(0)
+            .default(serde_json::json!(default_directories))
+            .build(),
+            SchemaBuilder::boolean(
+                "prompt_field_codes",
+                t_key!("desktop-entry-source", "fields.prompt_field_codes.label"),
+                t_key!("desktop-entry-source", "fields.prompt_field_codes.desc"),
+            )
+            .group(t_key!("desktop-entry-source", "groups.scan"))
+            .order(2)
+            .default(false)
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let parsed: DesktopEntrySourceSettings =
+            serde_json::from_value(settings).unwrap_or_default();
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DataSource for DesktopEntrySource {
+    /// 安装/卸载应用会增删 .desktop 文件，安装监控事件触发时重新采集。
+    fn tracks_installations(&self) -> bool {
+        true
+    }
+
+    async fn fetch_candidates(&self) -> CachedCandidateData {
+        let mut result = CachedCandidateData::new();
+        let settings = self.settings.read().clone();
+        let desktops = current_desktops();
+        let locale = message_locale();
+        // 同一桌面文件 ID 以优先级更高的目录为准（含被 Hidden 隐藏者）
+        let mut seen_ids: HashSet<String> = HashSet::new();
+
+        for directory in &settings.directories {
+            let root = PathBuf::from(directory);
+            let mut files: Vec<PathBuf> = WalkDir::new(&root)
+                .max_depth(MAX_SCAN_DEPTH)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.into_path())
+                .filter(|p| p.extension().is_some_and(|e| e == "desktop") && p.is_file())
+                .collect();
+            files.sort();
+
+            let mut count = 0usize;
+            for path in files {
+                let Some(id) = desktop_file_id(&root, &path) else {
+                    continue;
+                };
+                if !seen_ids.insert(id) {
+                    continue;
+                }
+                if let Some(candidate) = Self::build_candidate(
+                    &path,
+                    &desktops,
+                    locale.as_deref(),
+                    settings.prompt_field_codes,
+                ) {
+                    result.add_candidate(candidate);
+                    count += 1;
+                }
+            }
+            debug!(
+                "DesktopEntrySource: 扫描目录 {}，找到 {} 个应用",
+                directory, count
+            );
+        }
+
+        result
+    }
+}
+
+use crate::plugin_framework::builtin_registry::{DataSourceEntry, InventoryContext};
+
+pub(crate) fn build_desktop_entry_source(
+    ctx: &InventoryContext,
+) -> (Arc<dyn Configurable>, Arc<dyn DataSource>) {
+    let handle = ctx.get_handle("desktop-entry-source");
+    let source: Arc<dyn DataSource> = Arc::new(DesktopEntrySource::new(handle));
+    let configurable: Arc<dyn Configurable> = source.clone();
+    (configurable, source)
+}
+
+::inventory::submit! {
+    DataSourceEntry {
+        component_id: "desktop-entry-source",
+        handle_key: "desktop-entry-source",
+        priority: 5,
+        factory: build_desktop_entry_source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerolaunch_plugin_api::services::parameter::{
+        DefaultParameterResolver, ParameterResolver, ParameterSnapshot,
+    };
+
+    /// 由 Exec 构造模板（locale 固定为 None）。
+    fn template(body: &str, exec: &str, prompt: bool) -> Option<String> {
+        let entry = DesktopEntry::parse(&format!("[Desktop Entry]\n{}", body));
+        exec_to_template(&entry, exec, "/apps/app.desktop", None, prompt)
+    }
+
+    /// 在临时目录写入桌面文件并构造候选项（当前桌面为 GNOME）。
+    fn candidate(content: &str, prompt: bool) -> Option<SearchCandidate> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.desktop");
+        std::fs::write(&path, content).unwrap();
+        DesktopEntrySource::build_candidate(&path, &["GNOME".to_string()], Some("de"), prompt)
+    }
+
+    /// 文件/网址字段码按配置展开为用户参数或剔除；%i/%c/%k/%% 按规范展开；Path 加 cd 前缀。
+    #[test]
+    fn expands_field_codes() {
+        assert_eq!(
+            template("", "firefox %u", false).as_deref(),
+            Some("firefox")
+        );
+        assert_eq!(
+            template("", "firefox %u", true).as_deref(),
+            Some("firefox {|shellquote}")
+        );
+        assert_eq!(
+            template("", "code --file=%F", true).as_deref(),
+            Some("code --file={|shellquote}")
+        );
+        assert_eq!(
+            template("", "open \"my dir/%f\"", true).as_deref(),
+            Some("open 'my dir/'{|shellquote}")
+        );
+        assert_eq!(
+            template(
+                "Name=My App\nIcon=app\n",
+                "app %i --title %c %k 50%%",
+                false
+            )
+            .as_deref(),
+            Some("app --icon app --title 'My App' /apps/app.desktop 50%")
+        );
+        assert_eq!(
+            template("Path=/opt/my app\n", "\"/opt/my app/run\" %d", false).as_deref(),
+            Some("cd '/opt/my app' && '/opt/my app/run'")
+        );
+        assert_eq!(template("", "%f", false), None);
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    /// 字面大括号（含 %c 展开值）转义后不会被当作占位符，解析结果还原为原文。
+    #[tokio::test]
+    async fn escapes_literal_braces() {
+        let command = template("Name=Set {x}\n", "app --fmt {} %c", false).unwrap();
+        assert_eq!(command, "app --fmt '{{}}' 'Set {{x}}'");
+        assert_eq!(TemplateParser::count_user_args(&command), 0);
+        let resolved = DefaultParameterResolver::new()
+            .resolve(&command, &[], &ParameterSnapshot::empty())
+            .await
+            .unwrap();
+        assert_eq!(resolved, "app --fmt '{}' 'Set {x}'");
+    }
+
+    /// 用户参数经 shellquote 引用：含单引号的输入仍作为单个参数，不会成为 shell 语法。
+    #[tokio::test]
+    async fn user_argument_is_quoted_by_resolver() {
+        let command = template("", "firefox %u", true).unwrap();
+        let resolved = DefaultParameterResolver::new()
+            .resolve(
+                &command,
+                &["Bob's file; rm -rf ~".to_string()],
+                &ParameterSnapshot::empty(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resolved, r"firefox 'Bob'\''s file; rm -rf ~'");
+    }
+
+    /// NoDisplay / Hidden / 非 Application / OnlyShowIn / NotShowIn / TryExec 不可用的条目被过滤。
+    #[test]
+    fn build_candidate_filters_entries() {
+        let base = "[Desktop Entry]\nType=Application\nName=App\nExec=app\n";
+        assert!(candidate(base, false).is_some());
+        for extra in [
+            "NoDisplay=true\n",
+            "Hidden=true\n",
+            "OnlyShowIn=KDE;\n",
+            "NotShowIn=GNOME;\n",
+            "TryExec=/nonexistent/zerolaunch-test-program\n",
+        ] {
+            assert!(
+                candidate(&format!("{}{}", base, extra), false).is_none(),
+                "{}",
+                extra
+            );
+        }
+        assert!(candidate(&format!("{}OnlyShowIn=KDE;GNOME;\n", base), false).is_some());
+        assert!(candidate(
+            "[Desktop Entry]\nType=Link\nName=Site\nURL=https://example.com\n",
+            false
+        )
+        .is_none());
+        assert!(candidate("[Desktop Entry]\nType=Application\nName=App\n", false).is_none());
+    }
+
+    /// 本地化名称作为显示名，原始名称、GenericName、Keywords 去重后作为检索词；
+    /// 附加动作带前缀 ID 且剔除字段码；需要用户参数时名称成为行内触发词。
+    #[test]
+    fn build_candidate_collects_keywords_and_actions() {
+        let content = "[Desktop Entry]\n\
+            Type=Application\n\
+            Name=Files\n\
+            Name[de]=Dateien\n\
+            GenericName[de]=Dateimanager\n\
+            Keywords=folder;dateien;\n\
+            Exec=nautilus --new-window %U\n\
+            Actions=new-window;broken;\n\
+            \n\
+            [Desktop Action new-window]\n\
+            Name=New Window\n\
+            Name[de]=Neues Fenster\n\
+            Exec=nautilus --new-window %U\n\
+            \n\
+            [Desktop Action broken]\n\
+            Exec=nautilus\n";
+
+        let item = candidate(content, true).unwrap();
+        assert_eq!(item.name, "Dateien");
+        assert_eq!(item.keywords, vec!["Files", "Dateimanager", "folder"]);
+        assert_eq!(
+            item.target,
+            ExecutionTarget::Command("nautilus --new-window {|shellquote}".to_string())
+        );
+        assert_eq!(item.trigger_keywords, vec!["dateien"]);
+
+        assert_eq!(item.actions.len(), 1);
+        let action = &item.actions[0];
+        assert_eq!(action.action.id, "desktop-action:new-window");
+        assert_eq!(action.action.label, "Neues Fenster");
+        assert!(!action.action.is_default);
+        assert_eq!(
+            action.target,
+            ExecutionTarget::Command("nautilus --new-window".to_string())
+        );
+
+        // 关闭字段码提示：无用户参数，也不注册触发词
+        let item = candidate(content, false).unwrap();
+        assert_eq!(
+            item.target,
+            ExecutionTarget::Command("nautilus --new-window".to_string())
+        );
+        assert!(item.trigger_keywords.is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 pub mod app_source;
 pub mod bookmark_source;
+pub mod browser_history_source;
 pub mod builtin_command_source;
 pub mod command_source;
+// 生成 POSIX shell 命令，仅在 Unix 上注册
+#[cfg(unix)]
+pub mod desktop_entry_source;
 pub mod program_source;
 pub mod url_source;
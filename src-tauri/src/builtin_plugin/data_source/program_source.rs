@@ -415,6 +415,11 @@ impl Configurable for ProgramSource {
 
 #[async_trait]
 impl DataSource for ProgramSource {
+    /// 安装/卸载程序会改变采集结果，安装监控事件触发时重新采集。
+    fn tracks_installations(&self) -> bool {
+        true
+    }
+
     async fn fetch_candidates(&self) -> CachedCandidateData {
         let mut result = CachedCandidateData::new();
         let directory_configs = self.parse_directory_configs();
@@ -524,6 +529,7 @@ impl DataSource for ProgramSource {
                         keywords: Vec::new(), //  这个值的内容由 KeywordOptimizer 负责填充
                         bias: 0.0,
                         trigger_keywords: Vec::new(),
+                        actions: Vec::new(),
                     };
 
                     result.add_candidate(candidate);
@@ -137,6 +137,7 @@ impl DataSource for UrlSource {
                 keywords: Vec::new(),
                 bias: 0.0,
                 trigger_keywords,
+                actions: Vec::new(),
             };
 
             debug!("UrlSource: 加载网页候选项: {} -> {}", entry.name, entry.url);
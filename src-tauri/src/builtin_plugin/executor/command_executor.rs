@@ -1,5 +1,7 @@
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
 use zerolaunch_plugin_api::config::{
     ComponentCore, ComponentType, Configurable, SettingDefinition,
 };
@@ -9,15 +11,31 @@ use zerolaunch_plugin_api::{
     ActionExecutor, ExecutionContext, ExecutionError, ExecutionTarget, ResultAction, TargetType,
 };
 
+use crate::builtin_plugin::data_source::command_source::{output_options_for, CommandOutputMode};
+use crate::builtin_plugin::triggerable::command_output::{
+    run_history, CommandRun, COMMAND_OUTPUT_PLUGIN_ID,
+};
+use crate::plugin_framework::SessionDispatcher;
+use crate::sdk::HostApi;
+
 /// 命令执行器 - 负责执行自定义命令
-/// 通过 PluginHandle::shell_execute_command 委托 SDK 层执行，不直接调用平台 API
+/// 通过 PluginHandle::shell_execute_command 委托 SDK 层执行，不直接调用平台 API；
+/// 捕获模式经 shell_capture_command 在后台等待命令结束，再展示或复制输出。
 pub struct CommandExecutor {
     core: ComponentCore,
     plugin_handle: Arc<PluginHandle>,
+    /// 宿主 API：捕获结束后发送通知、显示窗口
+    host_api: Arc<HostApi>,
+    /// 会话调度器：显示输出模式下唤醒命令输出面板
+    session_dispatcher: Arc<SessionDispatcher>,
 }
 
 impl CommandExecutor {
-    pub fn new(plugin_handle: Arc<PluginHandle>) -> Self {
+    pub fn new(
+        plugin_handle: Arc<PluginHandle>,
+        host_api: Arc<HostApi>,
+        session_dispatcher: Arc<SessionDispatcher>,
+    ) -> Self {
         Self {
             core: ComponentCore::new(
                 "command-executor".to_string(),
@@ -27,8 +45,52 @@ impl CommandExecutor {
                 40,
             ),
             plugin_handle,
+            host_api,
+            session_dispatcher,
         }
     }
+
+    /// 在后台运行捕获型命令：启动器窗口在确认后即隐藏，命令结束后再按模式反馈，
+    /// 执行本身不阻塞确认流程。结果写入运行历史供输出面板查看。
+    fn spawn_capture(
+        &self,
+        name: String,
+        command: String,
+        mode: CommandOutputMode,
+        timeout: Duration,
+    ) {
+        let handle = self.plugin_handle.clone();
+        let host_api = self.host_api.clone();
+        let session_dispatcher = self.session_dispatcher.clone();
+        tauri::async_runtime::spawn(async move {
+            let run = match handle.shell_capture_command(&command, timeout).await {
+                Ok(output) => CommandRun::from_output(&name, &command, output),
+                Err(e) => CommandRun::failed(&name, &command, e.to_string()),
+            };
+            let run = run_history().record(run);
+
+            if mode == CommandOutputMode::CopyOutput {
+                let status = match handle.set_clipboard_text(&run.stdout) {
+                    Ok(()) => format!("{}，输出已复制", run.status_text()),
+                    Err(e) => format!("{}，复制失败: {}", run.status_text(), e),
+                };
+                host_api.notify(&run.name, &status).await;
+                return;
+            }
+
+            match session_dispatcher
+                .wake_plugin(COMMAND_OUTPUT_PLUGIN_ID)
+                .await
+            {
+                Ok(()) => host_api.show_window().await,
+                Err(e) => {
+                    // 输出面板不可用（如被禁用）时退化为通知退出状态
+                    warn!("唤醒命令输出面板失败: {}", e);
+                    host_api.notify(&run.name, &run.status_text()).await;
+                }
+            }
+        });
+    }
 }
 
 #[async_trait]
@@ -49,13 +111,29 @@ impl ActionExecutor for CommandExecutor {
     }
 
     fn supported_actions(&self) -> Vec<ResultAction> {
-        vec![ResultAction {
-            id: "execute".to_string(),
-            label: "执行".to_string(),
-            icon: IconRequest::Path(String::new()),
-            is_default: true,
-            shortcut_key: String::new(),
-        }]
+        vec![
+            ResultAction {
+                id: "execute".to_string(),
+                label: "执行".to_string(),
+                icon: IconRequest::Path(String::new()),
+                is_default: true,
+                shortcut_key: String::new(),
+            },
+            ResultAction {
+                id: "show_output".to_string(),
+                label: "执行并显示输出".to_string(),
+                icon: IconRequest::Path(String::new()),
+                is_default: false,
+                shortcut_key: String::new(),
+            },
+            ResultAction {
+                id: "copy_output".to_string(),
+                label: "执行并复制输出".to_string(),
+                icon: IconRequest::Path(String::new()),
+                is_default: false,
+                shortcut_key: String::new(),
+            },
+        ]
     }
 
     async fn execute(&self, ctx: &ExecutionContext, action_id: &str) -> Result<(), ExecutionError> {
@@ -68,26 +146,42 @@ impl ActionExecutor for CommandExecutor {
             }
         };
 
-        match action_id {
-            "execute" => {
-                // 解析模板，替换 {} 用户参数和 {clip}/{hwnd}/{selection} 系统参数
-                let resolved = self
-                    .plugin_handle
-                    .resolve_parameters(template, &ctx.user_args, &ctx.parameter_snapshot)
-                    .await
-                    .map_err(|e| ExecutionError::Failed(format!("命令参数解析失败: {}", e)))?;
-
-                let handle = self.plugin_handle.clone();
-                handle
-                    .shell_execute_command(&resolved)
-                    .await
-                    .map_err(|e| ExecutionError::Failed(format!("命令执行失败: {}", e)))
+        // 默认动作按命令条目配置的输出模式执行，显式动作覆盖配置的模式
+        let options = output_options_for(template);
+        let mode = match action_id {
+            "execute" => options.mode,
+            "show_output" => CommandOutputMode::ShowOutput,
+            "copy_output" => CommandOutputMode::CopyOutput,
+            _ => {
+                return Err(ExecutionError::UnsupportedAction(
+                    TargetType::Command,
+                    action_id.to_string(),
+                ))
             }
-            _ => Err(ExecutionError::UnsupportedAction(
-                TargetType::Command,
-                action_id.to_string(),
-            )),
+        };
+
+        // 解析模板，替换 {} 用户参数和 {clip}/{hwnd}/{selection} 系统参数
+        let resolved = self
+            .plugin_handle
+            .resolve_parameters(template, &ctx.user_args, &ctx.parameter_snapshot)
+            .await
+            .map_err(|e| ExecutionError::Failed(format!("命令参数解析失败: {}", e)))?;
+
+        if mode == CommandOutputMode::Background {
+            let handle = self.plugin_handle.clone();
+            return handle
+                .shell_execute_command(&resolved)
+                .await
+                .map_err(|e| ExecutionError::Failed(format!("命令执行失败: {}", e)));
         }
+
+        self.spawn_capture(
+            ctx.display_name.clone(),
+            resolved,
+            mode,
+            Duration::from_secs(options.timeout_secs),
+        );
+        Ok(())
     }
 }
 
@@ -97,7 +191,11 @@ pub(crate) fn build_command_executor(
     ctx: &InventoryContext,
 ) -> (Arc<dyn Configurable>, Arc<dyn ActionExecutor>) {
     let handle = ctx.get_handle("command-executor");
-    let exec: Arc<dyn ActionExecutor> = Arc::new(CommandExecutor::new(handle));
+    let exec: Arc<dyn ActionExecutor> = Arc::new(CommandExecutor::new(
+        handle,
+        ctx.host_api().clone(),
+        ctx.session_dispatcher().clone(),
+    ));
     let configurable: Arc<dyn Configurable> = exec.clone();
     (configurable, exec)
 }
@@ -0,0 +1,805 @@
+//! 上下文感知分数增强器。
+//!
+//! 记录每次启动时所处的上下文（时段、星期几、唤醒搜索栏前的前台进程），
+//! 查询时按当前上下文与候选项历史启动分布的吻合程度加分：
+//! 每天早上使用的程序在早上排名靠前、到了晚上不再占优；
+//! 常在某个程序里唤醒并启动的候选项，在该程序位于前台时排名靠前。
+
+use super::learning_store::LearningStore;
+use crate::core::config::setting_builders::SchemaBuilder;
+use crate::utils::get_current_time;
+use async_trait::async_trait;
+use chrono::{Datelike, Local, Timelike};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, EffectActionBinding,
+    SettingDefinition,
+};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::ParameterSnapshot;
+use zerolaunch_plugin_api::{
+    CachedCandidateData, CandidateId, ScoreBooster, ScoreDetail, ScoreDetailKind, ScoredCandidate,
+};
+
+/// 置信度先验：有效启动次数较少时压低加分，避免一两次偶然启动就产生明显偏向。
+const CONFIDENCE_PRIOR: f64 = 3.0;
+/// 信号分数的缩放系数，使完全吻合时的分数与其他增强器处于同一量级。
+const SIGNAL_SCALE: f64 = 10.0;
+/// 单个候选项最多保留的前台进程条目数，超出时淘汰有效次数最低的进程。
+const MAX_PROCESSES_PER_CANDIDATE: usize = 16;
+/// 衰减后低于该值的前台进程条目视为已遗忘并删除。
+const MIN_PROCESS_COUNT: f64 = 0.01;
+/// 基础分抑制阈值，与 HistoryBooster 一致：基础匹配分低于该值时按比例抑制加分。
+const SUPPRESSION_BASE: f64 = 15.0;
+/// 星期名称，下标 0 为周一。
+const WEEKDAY_NAMES: [&str; 7] = ["周一", "周二", "周三", "周四", "周五", "周六", "周日"];
+
+/// 上下文感知增强器的强类型配置结构。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextBoosterSettings {
+    #[serde(rename = "hour_weight", default = "default_hour_weight")]
+    pub hour_weight: f64,
+    #[serde(rename = "weekday_weight", default = "default_weekday_weight")]
+    pub weekday_weight: f64,
+    #[serde(rename = "process_weight", default = "default_process_weight")]
+    pub process_weight: f64,
+    #[serde(rename = "hour_bucket", default = "default_hour_bucket")]
+    pub hour_bucket: u32,
+    #[serde(
+        rename = "track_foreground_process",
+        default = "default_track_foreground_process"
+    )]
+    pub track_foreground_process: bool,
+    #[serde(rename = "context_time_decay", default = "default_context_time_decay")]
+    pub context_time_decay: f64,
+}
+
+impl Default for ContextBoosterSettings {
+    fn default() -> Self {
+        Self {
+            hour_weight: default_hour_weight(),
+            weekday_weight: default_weekday_weight(),
+            process_weight: default_process_weight(),
+            hour_bucket: default_hour_bucket(),
+            track_foreground_process: default_track_foreground_process(),
+            context_time_decay: default_context_time_decay(),
+        }
+    }
+}
+
+fn default_hour_weight() -> f64 {
+    1.0
+}
+fn default_weekday_weight() -> f64 {
+    0.5
+}
+fn default_process_weight() -> f64 {
+    1.0
+}
+fn default_hour_bucket() -> u32 {
+    3
+}
+fn default_track_foreground_process() -> bool {
+    true
+}
+fn default_context_time_decay() -> f64 {
+    1209600.0
+}
+
+/// 一次启动或查询所处的上下文。
+#[derive(Debug, Clone)]
+struct LaunchContext {
+    /// 本地时间的小时（0-23）
+    hour: usize,
+    /// 星期几（0 为周一）
+    weekday: usize,
+    /// 唤醒搜索栏前的前台进程名（小写），未知或未启用时为 None
+    process: Option<String>,
+    /// 当前时间戳
+    now: i64,
+}
+
+impl LaunchContext {
+    /// 以当前本地时间与指定前台进程构造上下文。
+    fn current(process: Option<String>) -> Self {
+        let now = Local::now();
+        Self {
+            hour: now.hour() as usize,
+            weekday: now.weekday().num_days_from_monday() as usize,
+            process,
+            now: get_current_time(),
+        }
+    }
+}
+
+/// 单个候选项的启动上下文统计 —— 各计数均为截至 `updated_at` 的时间衰减后有效次数。
+///
+/// 同一候选项的所有计数按同一系数衰减，因此各上下文的占比不受衰减影响，
+/// 衰减只作用于决定置信度的总有效次数。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ContextStats {
+    /// 按小时（0-23）统计的启动次数
+    #[serde(rename = "hours", default)]
+    hours: [f64; 24],
+    /// 按星期（0 为周一）统计的启动次数
+    #[serde(rename = "weekdays", default)]
+    weekdays: [f64; 7],
+    /// 按唤醒前前台进程统计的启动次数
+    #[serde(rename = "processes", default)]
+    processes: HashMap<String, f64>,
+    /// 总启动次数
+    #[serde(rename = "total", default)]
+    total: f64,
+    /// 计数的衰减基准时间戳
+    #[serde(rename = "updated_at", default)]
+    updated_at: i64,
+}
+
+/// 单个候选项在当前上下文下的各项信号分数（0 至 SIGNAL_SCALE）。
+#[derive(Debug, Clone, Copy, Default)]
+struct ContextScores {
+    /// 时段吻合分数
+    hour: f64,
+    /// 星期吻合分数
+    weekday: f64,
+    /// 前台进程吻合分数
+    process: f64,
+}
+
+impl ContextStats {
+    /// 将全部计数衰减到 now，并把衰减基准推进到 now。
+    fn decay_to(&mut self, now: i64, time_decay: f64) {
+        let factor = decay_factor(now - self.updated_at, time_decay);
+        self.hours
+            .iter_mut()
+            .chain(self.weekdays.iter_mut())
+            .chain(self.processes.values_mut())
+            .for_each(|count| *count *= factor);
+        self.processes
+            .retain(|_, count| *count >= MIN_PROCESS_COUNT);
+        self.total *= factor;
+        self.updated_at = now;
+    }
+
+    /// 记录一次在指定上下文中的启动。
+    fn record(&mut self, ctx: &LaunchContext, time_decay: f64) {
+        self.decay_to(ctx.now, time_decay);
+        self.hours[ctx.hour] += 1.0;
+        self.weekdays[ctx.weekday] += 1.0;
+        self.total += 1.0;
+
+        let Some(process) = &ctx.process else {
+            return;
+        };
+        *self.processes.entry(process.clone()).or_insert(0.0) += 1.0;
+        if self.processes.len() > MAX_PROCESSES_PER_CANDIDATE {
+            let weakest = self
+                .processes
+                .iter()
+                .filter(|(name, _)| *name != process)
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(name, _)| name.clone());
+            if let Some(weakest) = weakest {
+                self.processes.remove(&weakest);
+            }
+        }
+    }
+
+    /// 计算当前上下文下的各项信号分数。
+    fn scores(&self, ctx: &LaunchContext, hour_bucket: usize, time_decay: f64) -> ContextScores {
+        if self.total <= 0.0 {
+            return ContextScores::default();
+        }
+        let factor = decay_factor(ctx.now - self.updated_at, time_decay);
+
+        let (start, width) = hour_window(ctx.hour, hour_bucket);
+        let in_window: f64 = (0..width).map(|i| self.hours[(start + i) % 24]).sum();
+        let hour = signal_score(
+            in_window / self.total,
+            width as f64 / 24.0,
+            self.total * factor,
+        );
+        let weekday = signal_score(
+            self.weekdays[ctx.weekday] / self.total,
+            1.0 / 7.0,
+            self.total * factor,
+        );
+
+        // 前台进程占比只在记录到前台进程的启动中计算，未知进程的启动不稀释占比
+        let process_total: f64 = self.processes.values().sum();
+        let process = match &ctx.process {
+            Some(name) if process_total > 0.0 => signal_score(
+                self.processes.get(name).copied().unwrap_or(0.0) / process_total,
+                0.0,
+                process_total * factor,
+            ),
+            _ => 0.0,
+        };
+
+        ContextScores {
+            hour,
+            weekday,
+            process,
+        }
+    }
+}
+
+/// 指数衰减系数：exp(-(时间差/时间常数))，时间差为负（时钟回拨）时不衰减。
+fn decay_factor(elapsed: i64, time_decay: f64) -> f64 {
+    (-(elapsed.max(0) as f64) / (time_decay + 1.0)).exp()
+}
+
+/// 以当前小时为中心、长度为 hour_bucket 小时的时段窗口（跨零点回绕），
+/// 使 8:55 与 9:05 的启动落在彼此的窗口内，而非被固定分段边界切开。
+/// 返回：(起始小时, 窗口长度)。
+fn hour_window(hour: usize, hour_bucket: usize) -> (usize, usize) {
+    let width = hour_bucket.clamp(1, 24);
+    let start = (hour + 24 - (width - 1) / 2) % 24;
+    (start, width)
+}
+
+/// 单项上下文信号分数：启动分布在当前上下文上超出均匀分布的部分（0-1），
+/// 乘以由有效启动次数决定的置信度，再缩放到与其他增强器相近的量级。
+/// 参数：share - 当前上下文的启动占比；expected - 均匀使用时的期望占比；effective_total - 衰减后的有效次数。
+fn signal_score(share: f64, expected: f64, effective_total: f64) -> f64 {
+    if effective_total <= 0.0 || expected >= 1.0 {
+        return 0.0;
+    }
+    let lift = ((share - expected) / (1.0 - expected)).clamp(0.0, 1.0);
+    let confidence = effective_total / (effective_total + CONFIDENCE_PRIOR);
+    lift * confidence * SIGNAL_SCALE
+}
+
+/// 上下文感知增强器的持久化学习数据。
+///
+/// 仅限本文件内使用，由 `LearningStore` 写入 `AppDataDir/learning/context_booster.json`
+/// 或用户导出的文件。
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ContextLearningData {
+    /// launch_method_text -> 启动上下文统计
+    #[serde(rename = "candidates", default)]
+    candidates: HashMap<String, ContextStats>,
+}
+
+/// 本次唤醒的前台进程状态，与后台解析任务共享。
+#[derive(Debug, Default)]
+struct ForegroundState {
+    /// 唤醒代次，每次唤醒递增；解析任务仅在代次未变时写回，防止慢解析覆盖新唤醒
+    generation: u64,
+    /// 唤醒前的前台进程名（小写）
+    process: Option<String>,
+}
+
+/// 上下文感知增强器 - 基于启动时段、星期和前台程序的使用习惯对候选项进行分数增强
+pub struct ContextBooster {
+    core: ComponentCore,
+    data: RwLock<ContextLearningData>,
+    settings: RwLock<ContextBoosterSettings>,
+    /// 本次唤醒的前台进程，由 observe_wake 后台解析 `{hwnd}` 后写入
+    foreground: Arc<RwLock<ForegroundState>>,
+    /// 用于把窗口句柄解析为进程名，为 None 时不感知前台进程（如测试环境）
+    handle: Option<Arc<PluginHandle>>,
+    /// 学习数据快照存储，record 后防抖写盘，退出前 flush
+    store: LearningStore,
+}
+
+impl Default for ContextBooster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContextBooster {
+    /// 创建不持久化学习数据、不感知前台进程的增强器。
+    pub fn new() -> Self {
+        Self::with_store(LearningStore::new("context-booster", None), None)
+    }
+
+    /// 使用指定的学习数据存储创建增强器，并从快照恢复上下文统计。
+    /// 参数：store - 学习数据存储；handle - 用于解析前台窗口进程的句柄。
+    pub fn with_store(store: LearningStore, handle: Option<Arc<PluginHandle>>) -> Self {
+        let data = match store.load::<ContextLearningData>() {
+            Some(data) => {
+                info!(
+                    "[ContextBooster] 已加载 {} 个候选项的持久化启动上下文",
+                    data.candidates.len()
+                );
+                data
+            }
+            None => ContextLearningData::default(),
+        };
+        ContextBooster {
+            core: ComponentCore::new(
+                "context-booster".to_string(),
+                t_key!("context-booster", "name").to_string(),
+                t_key!("context-booster", "description").to_string(),
+                ComponentType::ScoreBooster,
+                20,
+            ),
+            data: RwLock::new(data),
+            settings: RwLock::new(ContextBoosterSettings::default()),
+            foreground: Arc::new(RwLock::new(ForegroundState::default())),
+            handle,
+            store,
+        }
+    }
+
+    /// 构造当前查询/启动的上下文（未启用前台进程感知时不带进程）。
+    fn current_context(&self, settings: &ContextBoosterSettings) -> LaunchContext {
+        let process = if settings.track_foreground_process {
+            self.foreground.read().process.clone()
+        } else {
+            None
+        };
+        LaunchContext::current(process)
+    }
+
+    /// 导出学习数据到指定目录。
+    fn export_learning_data(&self, dir: &str) -> serde_json::Value {
+        let data = self.data.read();
+        match self.store.export_to_dir(&*data, Path::new(dir)) {
+            Ok(target) => serde_json::json!({
+                "success": true,
+                "message": format!("学习数据已导出到 {}", target.display()),
+                "export_dir": "",
+            }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 从快照文件导入学习数据，替换当前数据并立即写盘。
+    fn import_learning_data(&self, file: &str) -> serde_json::Value {
+        let imported = match self
+            .store
+            .import_from::<ContextLearningData>(Path::new(file))
+        {
+            Ok(data) => data,
+            Err(e) => return serde_json::json!({ "success": false, "message": e }),
+        };
+        let mut data = self.data.write();
+        *data = imported;
+        match self.store.save_now(&*data) {
+            Ok(()) => serde_json::json!({ "success": true, "import_file": "" }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 清空学习数据并立即写盘。需先打开确认开关。
+    fn clear_learning_data(&self, confirmed: bool) -> serde_json::Value {
+        if !confirmed {
+            return serde_json::json!({
+                "success": false,
+                "message": "请先打开确认开关再清空学习数据",
+            });
+        }
+        let mut data = self.data.write();
+        *data = ContextLearningData::default();
+        match self.store.save_now(&*data) {
+            Ok(()) => serde_json::json!({ "success": true, "clear_confirm": false }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+}
+
+#[async_trait]
+impl Configurable for ContextBooster {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::number(
+                "hour_weight",
+                t_key!("context-booster", "fields.hour_weight.label"),
+                t_key!("context-booster", "fields.hour_weight.desc"),
+            )
+            .group(t_key!("context-booster", "groups.weight"))
+            .order(0)
+            .default(1.0)
+            .min(0.0)
+            .max(10.0)
+            .step(0.1)
+            .build(),
+            SchemaBuilder::number(
+                "weekday_weight",
+                t_key!("context-booster", "fields.weekday_weight.label"),
+                t_key!("context-booster", "fields.weekday_weight.desc"),
+            )
+            .group(t_key!("context-booster", "groups.weight"))
+            .order(1)
+            .default(0.5)
+            .min(0.0)
+            .max(10.0)
+            .step(0.1)
+            .build(),
+            SchemaBuilder::number(
+                "process_weight",
+                t_key!("context-booster", "fields.process_weight.label"),
+                t_key!("context-booster", "fields.process_weight.desc"),
+            )
+            .group(t_key!("context-booster", "groups.weight"))
+            .order(2)
+            .default(1.0)
+            .min(0.0)
+            .max(10.0)
+            .step(0.1)
+            .build(),
+            SchemaBuilder::integer(
+                "hour_bucket",
+                t_key!("context-booster", "fields.hour_bucket.label"),
+                t_key!("context-booster", "fields.hour_bucket.desc"),
+            )
+            .group(t_key!("context-booster", "groups.context"))
+            .order(3)
+            .default(3)
+            .min(1.0)
+            .max(12.0)
+            .build(),
+            SchemaBuilder::boolean(
+                "track_foreground_process",
+                t_key!("context-booster", "fields.track_foreground_process.label"),
+                t_key!("context-booster", "fields.track_foreground_process.desc"),
+            )
+            .group(t_key!("context-booster", "groups.context"))
+            .order(4)
+            .default(true)
+            .build(),
+            SchemaBuilder::number(
+                "context_time_decay",
+                t_key!("context-booster", "fields.context_time_decay.label"),
+                t_key!("context-booster", "fields.context_time_decay.desc"),
+            )
+            .group(t_key!("context-booster", "groups.decay"))
+            .order(5)
+            .default(1209600.0)
+            .min(86400.0)
+            .max(31536000.0)
+            .step(3600.0)
+            .build(),
+            SchemaBuilder::path(
+                "export_dir",
+                t_key!("context-booster", "fields.export_dir.label"),
+                t_key!("context-booster", "fields.export_dir.desc"),
+            )
+            .directory()
+            .group(t_key!("context-booster", "groups.learning_data"))
+            .order(6)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "export_learning_data".into(),
+                component: None,
+                field_mapping: vec![("export_dir".into(), "export_dir".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::path(
+                "import_file",
+                t_key!("context-booster", "fields.import_file.label"),
+                t_key!("context-booster", "fields.import_file.desc"),
+            )
+            .file()
+            .group(t_key!("context-booster", "groups.learning_data"))
+            .order(7)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "import_learning_data".into(),
+                component: None,
+                field_mapping: vec![("import_file".into(), "import_file".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::boolean(
+                "clear_confirm",
+                t_key!("context-booster", "fields.clear_confirm.label"),
+                t_key!("context-booster", "fields.clear_confirm.desc"),
+            )
+            .group(t_key!("context-booster", "groups.learning_data"))
+            .order(8)
+            .default(false)
+            .effect_action(EffectActionBinding {
+                action: "clear_learning_data".into(),
+                component: None,
+                field_mapping: vec![("clear_confirm".into(), "clear_confirm".into())],
+                transient: true,
+            })
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let mut parsed: ContextBoosterSettings =
+            serde_json::from_value(settings).unwrap_or_default();
+        parsed.hour_bucket = parsed.hour_bucket.clamp(1, 12);
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+
+    fn config_actions(&self) -> Vec<ConfigActionDef> {
+        vec![
+            ConfigActionDef {
+                action: "export_learning_data".to_string(),
+                label: t_key!("context-booster", "actions.export_learning_data.label").to_string(),
+                description: t_key!(
+                    "context-booster",
+                    "actions.export_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "import_learning_data".to_string(),
+                label: t_key!("context-booster", "actions.import_learning_data.label").to_string(),
+                description: t_key!(
+                    "context-booster",
+                    "actions.import_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "clear_learning_data".to_string(),
+                label: t_key!("context-booster", "actions.clear_learning_data.label").to_string(),
+                description: t_key!("context-booster", "actions.clear_learning_data.description")
+                    .to_string(),
+            },
+        ]
+    }
+
+    /// 学习数据的导出/导入/清空动作。
+    /// 副作用：直接读写学习数据快照文件，不修改组件配置，字段值不持久化。
+    async fn execute_config_action(
+        &self,
+        action: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match action {
+            "export_learning_data" => {
+                let dir = params
+                    .get("export_dir")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 export_dir".to_string())?;
+                Ok(self.export_learning_data(dir))
+            }
+            "import_learning_data" => {
+                let file = params
+                    .get("import_file")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 import_file".to_string())?;
+                Ok(self.import_learning_data(file))
+            }
+            "clear_learning_data" => {
+                let confirmed = params
+                    .get("clear_confirm")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Ok(self.clear_learning_data(confirmed))
+            }
+            _ => Err(format!("未知动作: {}", action)),
+        }
+    }
+}
+
+impl ScoreBooster for ContextBooster {
+    /// 记录候选项在当前上下文中被启动
+    fn record(&self, candidate_id: CandidateId, data: &CachedCandidateData, _query: &str) {
+        let Some(search_candidate) = data.get_candidate(candidate_id) else {
+            error!(
+                "[ContextBooster] 无法找到候选项数据，无法记录启动上下文，candidate_id: {}",
+                candidate_id
+            );
+            return;
+        };
+        let settings = self.settings.read().clone();
+        let ctx = self.current_context(&settings);
+        let mut learned = self.data.write();
+        learned
+            .candidates
+            .entry(search_candidate.target.payload().to_string())
+            .or_default()
+            .record(&ctx, settings.context_time_decay);
+        self.store.schedule_save(&*learned);
+    }
+
+    /// 基于当前上下文与历史启动上下文的吻合程度增强候选项分数
+    fn boost(
+        &self,
+        candidates: &mut Vec<ScoredCandidate>,
+        data: &CachedCandidateData,
+        query: &str,
+    ) {
+        let settings = self.settings.read().clone();
+        let learned = self.data.read();
+        if learned.candidates.is_empty() {
+            return;
+        }
+        let ctx = self.current_context(&settings);
+        let hour_bucket = settings.hour_bucket as usize;
+        let (window_start, window_width) = hour_window(ctx.hour, hour_bucket);
+        let hour_label = format!(
+            "时段习惯分数({}-{}时)",
+            window_start,
+            (window_start + window_width) % 24
+        );
+        let weekday_label = format!("星期习惯分数({})", WEEKDAY_NAMES[ctx.weekday]);
+        let process_label = format!(
+            "前台程序习惯分数({})",
+            ctx.process.as_deref().unwrap_or("未知")
+        );
+
+        for candidate in candidates.iter_mut() {
+            let method_text = match data.get_candidate(candidate.candidate_id) {
+                Some(sc) => sc.target.payload(),
+                None => continue,
+            };
+            let Some(stats) = learned.candidates.get(method_text) else {
+                continue;
+            };
+            let scores = stats.scores(&ctx, hour_bucket, settings.context_time_decay);
+
+            // 基础分抑制因子：与 HistoryBooster 相同，避免上下文习惯把无关候选项顶到前面；
+            // 因子直接乘进各加分项的权重，明细中的 score × weight 之和即为实际加分
+            let suppression = if query.is_empty() {
+                1.0
+            } else {
+                (candidate.score / SUPPRESSION_BASE).clamp(0.0, 1.0)
+            };
+
+            let parts = [
+                (scores.hour, settings.hour_weight, &hour_label),
+                (scores.weekday, settings.weekday_weight, &weekday_label),
+                (scores.process, settings.process_weight, &process_label),
+            ];
+            for (score, weight, description) in parts {
+                let weight = weight * suppression;
+                candidate.score += score * weight;
+                candidate.detailed_score.push(ScoreDetail {
+                    score,
+                    weight,
+                    description: description.clone(),
+                    kind: ScoreDetailKind::Add,
+                });
+            }
+            candidate.detailed_score.push(ScoreDetail {
+                score: suppression,
+                weight: 1.0,
+                description: "上下文基础分抑制因子(已计入权重)".to_string(),
+                kind: ScoreDetailKind::Info,
+            });
+        }
+    }
+
+    /// 退出前将等待中的学习数据立即写盘
+    fn persist(&self) {
+        self.store.flush();
+    }
+
+    /// 搜索栏唤醒时在后台将 `{hwnd}` 前台窗口解析为进程名，供本次唤醒的查询与启动使用
+    fn observe_wake(&self, snapshot: &ParameterSnapshot) {
+        let generation = {
+            let mut foreground = self.foreground.write();
+            foreground.generation += 1;
+            foreground.process = None;
+            foreground.generation
+        };
+        if !self.settings.read().track_foreground_process {
+            return;
+        }
+        let (Some(handle), Some(hwnd)) = (self.handle.clone(), snapshot.try_get("hwnd")) else {
+            return;
+        };
+        let hwnd = hwnd.to_string();
+        let foreground = self.foreground.clone();
+        tauri::async_runtime::spawn(async move {
+            let process = match handle.process_name_of_window(&hwnd).await {
+                Ok(process) => process,
+                Err(e) => {
+                    debug!("[ContextBooster] 解析前台窗口进程失败: {}", e);
+                    return;
+                }
+            };
+            let mut foreground = foreground.write();
+            if foreground.generation == generation {
+                foreground.process = process;
+            }
+        });
+    }
+}
+
+use crate::plugin_framework::builtin_registry::{InventoryContext, ScoreBoosterEntry};
+
+pub(crate) fn build_context_booster(
+    ctx: &InventoryContext,
+) -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>) {
+    let handle = ctx.get_handle("context-booster");
+    let store = LearningStore::from_handle("context-booster", &handle, "context_booster.json");
+    let booster: Arc<dyn ScoreBooster> = Arc::new(ContextBooster::with_store(store, Some(handle)));
+    let configurable: Arc<dyn Configurable> = booster.clone();
+    (configurable, booster)
+}
+
+::inventory::submit! {
+    ScoreBoosterEntry {
+        component_id: "context-booster",
+        priority: 20,
+        factory: build_context_booster,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECAY: f64 = 1209600.0;
+
+    fn ctx(hour: usize, weekday: usize, process: Option<&str>, now: i64) -> LaunchContext {
+        LaunchContext {
+            hour,
+            weekday,
+            process: process.map(str::to_string),
+            now,
+        }
+    }
+
+    #[test]
+    fn matching_context_is_boosted() {
+        let mut stats = ContextStats::default();
+        for _ in 0..6 {
+            stats.record(&ctx(8, 0, Some("code.exe"), 1000), DECAY);
+        }
+
+        let morning = stats.scores(&ctx(9, 0, Some("code.exe"), 1000), 3, DECAY);
+        assert!(morning.hour > 0.0);
+        assert!(morning.weekday > 0.0);
+        assert!(morning.process > 0.0);
+
+        let evening = stats.scores(&ctx(21, 6, Some("explorer.exe"), 1000), 3, DECAY);
+        assert_eq!(evening.hour, 0.0);
+        assert_eq!(evening.weekday, 0.0);
+        assert_eq!(evening.process, 0.0);
+    }
+
+    #[test]
+    fn uniform_usage_gets_no_hour_boost() {
+        let mut stats = ContextStats::default();
+        for hour in 0..24 {
+            stats.record(&ctx(hour, hour % 7, None, 1000), DECAY);
+        }
+        let scores = stats.scores(&ctx(8, 0, None, 1000), 3, DECAY);
+        assert!(scores.hour.abs() < 1e-9);
+        assert_eq!(scores.process, 0.0);
+    }
+
+    #[test]
+    fn decay_lowers_confidence_but_keeps_shares() {
+        let mut stats = ContextStats::default();
+        for _ in 0..4 {
+            stats.record(&ctx(8, 0, None, 0), DECAY);
+        }
+        let fresh = stats.scores(&ctx(8, 0, None, 0), 3, DECAY);
+        let stale = stats.scores(&ctx(8, 0, None, 4 * DECAY as i64), 3, DECAY);
+        assert!(stale.hour > 0.0);
+        assert!(stale.hour < fresh.hour);
+    }
+
+    #[test]
+    fn process_entries_are_capped() {
+        let mut stats = ContextStats::default();
+        for i in 0..(MAX_PROCESSES_PER_CANDIDATE + 5) {
+            let name = format!("app{}.exe", i);
+            stats.record(&ctx(8, 0, Some(&name), 1000), DECAY);
+        }
+        assert_eq!(stats.processes.len(), MAX_PROCESSES_PER_CANDIDATE);
+        let last = format!("app{}.exe", MAX_PROCESSES_PER_CANDIDATE + 4);
+        assert!(stats.processes.contains_key(&last));
+    }
+}
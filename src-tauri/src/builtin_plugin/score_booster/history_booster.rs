@@ -1,13 +1,16 @@
+use super::learning_store::LearningStore;
 use crate::core::config::setting_builders::SchemaBuilder;
 use crate::utils::{generate_current_date, get_current_time, is_date_current};
 use async_trait::async_trait;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
-use tracing::error;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use tracing::{error, info};
 use zerolaunch_plugin_api::config::{
-    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+    ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, EffectActionBinding,
+    SettingDefinition,
 };
 use zerolaunch_plugin_api::{
     CachedCandidateData, CandidateId, ScoreBooster, ScoreDetail, ScoreDetailKind, ScoredCandidate,
@@ -53,6 +56,26 @@ fn default_temporal_decay() -> f64 {
     10800.0
 }
 
+/// 历史记录增强器的持久化学习数据 — `HistoryBoosterInner` 的可序列化快照。
+///
+/// 仅限本文件内使用，由 `LearningStore` 写入 `AppDataDir/learning/history_booster.json`
+/// 或用户导出的文件。
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryLearningData {
+    /// 最近7天的启动次数记录，下标 0 为最近一天
+    #[serde(rename = "launch_time", default)]
+    launch_time: Vec<HashMap<String, u64>>,
+    /// 历史总启动次数
+    #[serde(rename = "history_launch_time", default)]
+    history_launch_time: HashMap<String, u64>,
+    /// 上次更新日期，空字符串表示未知（加载时视为今天）
+    #[serde(rename = "last_update_data", default)]
+    last_update_data: String,
+    /// 最近一次启动时间（时间戳）
+    #[serde(rename = "latest_launch_time", default)]
+    latest_launch_time: HashMap<String, i64>,
+}
+
 /// 历史记录增强器内部实现
 #[derive(Debug)]
 struct HistoryBoosterInner {
@@ -78,6 +101,52 @@ impl HistoryBoosterInner {
         }
     }
 
+    /// 从持久化快照恢复内部状态，按天记录最多保留 7 天。
+    fn from_data(data: HistoryLearningData) -> Self {
+        let mut launch_time: VecDeque<DashMap<String, u64>> = data
+            .launch_time
+            .into_iter()
+            .take(7)
+            .map(|day| day.into_iter().collect())
+            .collect();
+        if launch_time.is_empty() {
+            launch_time.push_front(DashMap::new());
+        }
+        let last_update_data = if data.last_update_data.is_empty() {
+            generate_current_date()
+        } else {
+            data.last_update_data
+        };
+        HistoryBoosterInner {
+            launch_time,
+            history_launch_time: data.history_launch_time.into_iter().collect(),
+            last_update_data,
+            latest_launch_time: data.latest_launch_time.into_iter().collect(),
+        }
+    }
+
+    /// 生成当前内部状态的持久化快照。
+    fn to_data(&self) -> HistoryLearningData {
+        HistoryLearningData {
+            launch_time: self
+                .launch_time
+                .iter()
+                .map(|day| day.iter().map(|e| (e.key().clone(), *e.value())).collect())
+                .collect(),
+            history_launch_time: self
+                .history_launch_time
+                .iter()
+                .map(|e| (e.key().clone(), *e.value()))
+                .collect(),
+            last_update_data: self.last_update_data.clone(),
+            latest_launch_time: self
+                .latest_launch_time
+                .iter()
+                .map(|e| (e.key().clone(), *e.value()))
+                .collect(),
+        }
+    }
+
     /// 记录程序启动，更新所有统计数据
     fn record_launch(&mut self, method_text: &str) {
         // 确保日期信息已更新
@@ -153,6 +222,8 @@ pub struct HistoryBooster {
     core: ComponentCore,
     inner: RwLock<HistoryBoosterInner>,
     settings: RwLock<HistoryBoosterSettings>,
+    /// 学习数据快照存储，record 后防抖写盘，退出前 flush
+    store: LearningStore,
 }
 
 impl Default for HistoryBooster {
@@ -162,7 +233,20 @@ impl Default for HistoryBooster {
 }
 
 impl HistoryBooster {
+    /// 创建不持久化学习数据的增强器。
     pub fn new() -> Self {
+        Self::with_store(LearningStore::new("history-booster", None))
+    }
+
+    /// 使用指定的学习数据存储创建增强器，并从快照恢复历史数据。
+    pub fn with_store(store: LearningStore) -> Self {
+        let inner = match store.load::<HistoryLearningData>() {
+            Some(data) => {
+                info!("[HistoryBooster] 已加载持久化的历史启动数据");
+                HistoryBoosterInner::from_data(data)
+            }
+            None => HistoryBoosterInner::new(),
+        };
         HistoryBooster {
             core: ComponentCore::new(
                 "history-booster".to_string(),
@@ -171,8 +255,61 @@ impl HistoryBooster {
                 ComponentType::ScoreBooster,
                 0,
             ),
-            inner: RwLock::new(HistoryBoosterInner::new()),
+            inner: RwLock::new(inner),
             settings: RwLock::new(HistoryBoosterSettings::default()),
+            store,
+        }
+    }
+
+    /// 导出学习数据到指定目录。
+    fn export_learning_data(&self, dir: &str) -> serde_json::Value {
+        let data = self.inner.read().to_data();
+        match self.store.export_to_dir(&data, Path::new(dir)) {
+            Ok(target) => serde_json::json!({
+                "success": true,
+                "message": format!("学习数据已导出到 {}", target.display()),
+                "export_dir": "",
+            }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 从快照文件导入学习数据，替换当前数据并立即写盘。
+    fn import_learning_data(&self, file: &str) -> serde_json::Value {
+        let data = match self
+            .store
+            .import_from::<HistoryLearningData>(Path::new(file))
+        {
+            Ok(data) => data,
+            Err(e) => return serde_json::json!({ "success": false, "message": e }),
+        };
+        let snapshot = {
+            let mut inner = self.inner.write();
+            *inner = HistoryBoosterInner::from_data(data);
+            inner.to_data()
+        };
+        match self.store.save_now(&snapshot) {
+            Ok(()) => serde_json::json!({ "success": true, "import_file": "" }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 清空学习数据并立即写盘。需先打开确认开关。
+    fn clear_learning_data(&self, confirmed: bool) -> serde_json::Value {
+        if !confirmed {
+            return serde_json::json!({
+                "success": false,
+                "message": "请先打开确认开关再清空学习数据",
+            });
+        }
+        let snapshot = {
+            let mut inner = self.inner.write();
+            *inner = HistoryBoosterInner::new();
+            inner.to_data()
+        };
+        match self.store.save_now(&snapshot) {
+            Ok(()) => serde_json::json!({ "success": true, "clear_confirm": false }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
         }
     }
 }
@@ -233,6 +370,53 @@ impl Configurable for HistoryBooster {
             .max(86400.0)
             .step(60.0)
             .build(),
+            SchemaBuilder::path(
+                "export_dir",
+                t_key!("history-booster", "fields.export_dir.label"),
+                t_key!("history-booster", "fields.export_dir.desc"),
+            )
+            .directory()
+            .group(t_key!("history-booster", "groups.learning_data"))
+            .order(4)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "export_learning_data".into(),
+                component: None,
+                field_mapping: vec![("export_dir".into(), "export_dir".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::path(
+                "import_file",
+                t_key!("history-booster", "fields.import_file.label"),
+                t_key!("history-booster", "fields.import_file.desc"),
+            )
+            .file()
+            .group(t_key!("history-booster", "groups.learning_data"))
+            .order(5)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "import_learning_data".into(),
+                component: None,
+                field_mapping: vec![("import_file".into(), "import_file".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::boolean(
+                "clear_confirm",
+                t_key!("history-booster", "fields.clear_confirm.label"),
+                t_key!("history-booster", "fields.clear_confirm.desc"),
+            )
+            .group(t_key!("history-booster", "groups.learning_data"))
+            .order(6)
+            .default(false)
+            .effect_action(EffectActionBinding {
+                action: "clear_learning_data".into(),
+                component: None,
+                field_mapping: vec![("clear_confirm".into(), "clear_confirm".into())],
+                transient: true,
+            })
+            .build(),
         ]
     }
 
@@ -245,6 +429,70 @@ impl Configurable for HistoryBooster {
         *self.settings.write() = parsed;
         Ok(())
     }
+
+    fn config_actions(&self) -> Vec<ConfigActionDef> {
+        vec![
+            ConfigActionDef {
+                action: "export_learning_data".to_string(),
+                label: t_key!("history-booster", "actions.export_learning_data.label").to_string(),
+                description: t_key!(
+                    "history-booster",
+                    "actions.export_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "import_learning_data".to_string(),
+                label: t_key!("history-booster", "actions.import_learning_data.label").to_string(),
+                description: t_key!(
+                    "history-booster",
+                    "actions.import_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "clear_learning_data".to_string(),
+                label: t_key!("history-booster", "actions.clear_learning_data.label").to_string(),
+                description: t_key!("history-booster", "actions.clear_learning_data.description")
+                    .to_string(),
+            },
+        ]
+    }
+
+    /// 学习数据的导出/导入/清空动作。
+    /// 副作用：直接读写学习数据快照文件，不修改组件配置，字段值不持久化。
+    async fn execute_config_action(
+        &self,
+        action: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match action {
+            "export_learning_data" => {
+                let dir = params
+                    .get("export_dir")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 export_dir".to_string())?;
+                Ok(self.export_learning_data(dir))
+            }
+            "import_learning_data" => {
+                let file = params
+                    .get("import_file")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 import_file".to_string())?;
+                Ok(self.import_learning_data(file))
+            }
+            "clear_learning_data" => {
+                let confirmed = params
+                    .get("clear_confirm")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Ok(self.clear_learning_data(confirmed))
+            }
+            _ => Err(format!("未知动作: {}", action)),
+        }
+    }
 }
 
 impl ScoreBooster for HistoryBooster {
@@ -252,7 +500,12 @@ impl ScoreBooster for HistoryBooster {
     fn record(&self, candidate_id: CandidateId, data: &CachedCandidateData, _query: &str) {
         if let Some(search_candidate) = data.get_candidate(candidate_id) {
             let method_text = search_candidate.target.payload();
-            self.inner.write().record_launch(method_text);
+            let snapshot = {
+                let mut inner = self.inner.write();
+                inner.record_launch(method_text);
+                inner.to_data()
+            };
+            self.store.schedule_save(&snapshot);
         } else {
             error!(
                 "[HistoryBooster] 无法找到候选项数据，无法记录启动，candidate_id: {}",
@@ -326,13 +579,22 @@ impl ScoreBooster for HistoryBooster {
             });
         }
     }
+
+    /// 退出前将等待中的学习数据立即写盘
+    fn persist(&self) {
+        self.store.flush();
+    }
 }
 
-use crate::plugin_framework::builtin_registry::ScoreBoosterEntry;
+use crate::plugin_framework::builtin_registry::{InventoryContext, ScoreBoosterEntry};
 use std::sync::Arc;
 
-pub(crate) fn build_history_booster() -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>) {
-    let booster: Arc<dyn ScoreBooster> = Arc::new(HistoryBooster::new());
+pub(crate) fn build_history_booster(
+    ctx: &InventoryContext,
+) -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>) {
+    let handle = ctx.get_handle("history-booster");
+    let store = LearningStore::from_handle("history-booster", &handle, "history_booster.json");
+    let booster: Arc<dyn ScoreBooster> = Arc::new(HistoryBooster::with_store(store));
     let configurable: Arc<dyn Configurable> = booster.clone();
     (configurable, booster)
 }
@@ -344,3 +606,137 @@ pub(crate) fn build_history_booster() -> (Arc<dyn Configurable>, Arc<dyn ScoreBo
         factory: build_history_booster,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin_plugin::score_booster::test_support::candidates;
+
+    fn scored(candidate_id: CandidateId, score: f64) -> ScoredCandidate {
+        ScoredCandidate {
+            candidate_id,
+            score,
+            detailed_score: Vec::new(),
+        }
+    }
+
+    /// 今天的快照：按天记录从今天起依次往前。
+    fn today_data(launch_time: Vec<HashMap<String, u64>>) -> HistoryLearningData {
+        HistoryLearningData {
+            launch_time,
+            last_update_data: generate_current_date(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_launches_raise_score() {
+        let booster = HistoryBooster::new();
+        let data = candidates();
+        booster.record(1, &data, "");
+        booster.record(1, &data, "");
+
+        let mut results = vec![scored(1, 0.0), scored(2, 0.0)];
+        booster.boost(&mut results, &data, "");
+        assert!(results[0].score > 0.0);
+        assert_eq!(results[1].score, 0.0, "未启动过的候选项不加分");
+
+        // 历史分 = ln(1 + 次数)，今日习惯分 = 次数，刚启动的热度分接近满分 6
+        let details = &results[0].detailed_score;
+        assert_eq!(details.len(), 4);
+        assert!((details[0].score - 2f64.ln_1p()).abs() < 1e-9);
+        assert_eq!(details[1].score, 2.0);
+        assert!(details[2].score > 5.9 && details[2].score <= 6.0);
+        assert_eq!(details[3].kind, ScoreDetailKind::Multiply);
+    }
+
+    /// 近期习惯分按天衰减：越早的启动权重越低（每天 ÷1.3）。
+    #[test]
+    fn recent_habit_decays_by_day() {
+        let inner = HistoryBoosterInner::from_data(today_data(vec![
+            HashMap::from([("notepad.exe".to_string(), 1)]),
+            HashMap::from([("notepad.exe".to_string(), 1)]),
+            HashMap::new(),
+            HashMap::from([("notepad.exe".to_string(), 2)]),
+        ]));
+        let expected = 1.0 + 1.0 / 1.3 + 2.0 / 1.3f64.powi(3);
+        assert!((inner.calculate_recent_habit_score("notepad.exe") - expected).abs() < 1e-9);
+        assert_eq!(inner.calculate_recent_habit_score("calc.exe"), 0.0);
+    }
+
+    /// 热度分随距上次启动的时间衰减：经过一个衰减周期约降为一半。
+    #[test]
+    fn temporal_score_decays_with_time() {
+        let decay = 3600;
+        let now = get_current_time();
+        let inner = HistoryBoosterInner::from_data(HistoryLearningData {
+            latest_launch_time: HashMap::from([
+                ("fresh.exe".to_string(), now),
+                ("stale.exe".to_string(), now - decay),
+            ]),
+            ..today_data(Vec::new())
+        });
+        let fresh = inner.calculate_temporal_score("fresh.exe", decay);
+        let stale = inner.calculate_temporal_score("stale.exe", decay);
+        assert!(fresh > 5.9);
+        assert!((stale - 3.0).abs() < 0.01, "stale = {stale}");
+        assert_eq!(inner.calculate_temporal_score("never.exe", decay), 0.0);
+    }
+
+    /// 跨天后新开一天的记录，按天记录最多保留 7 天，历史总数不受影响。
+    #[test]
+    fn day_rollover_keeps_seven_days() {
+        let mut data = today_data(vec![HashMap::from([("notepad.exe".to_string(), 1)]); 7]);
+        data.last_update_data = "2000-01-01".to_string();
+        data.history_launch_time = HashMap::from([("notepad.exe".to_string(), 7)]);
+        let mut inner = HistoryBoosterInner::from_data(data);
+
+        inner.record_launch("notepad.exe");
+        assert_eq!(inner.launch_time.len(), 7);
+        assert_eq!(*inner.launch_time[0].get("notepad.exe").unwrap(), 1);
+        assert_eq!(*inner.history_launch_time.get("notepad.exe").unwrap(), 8);
+        assert_eq!(inner.last_update_data, generate_current_date());
+    }
+
+    /// 有查询词时基础分过低的候选项被抑制，空查询不抑制。
+    #[tokio::test]
+    async fn low_base_score_suppresses_boost() {
+        let booster = HistoryBooster::new();
+        let data = candidates();
+        booster.record(1, &data, "note");
+
+        let mut weak = vec![scored(1, 0.0)];
+        booster.boost(&mut weak, &data, "note");
+        assert_eq!(weak[0].score, 0.0);
+
+        let mut strong = vec![scored(1, 15.0)];
+        booster.boost(&mut strong, &data, "note");
+        assert!(strong[0].score > 15.0);
+    }
+
+    /// 学习数据经 LearningStore 持久化，重建增强器后恢复。
+    #[tokio::test]
+    async fn learning_data_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learning/history_booster.json");
+        let data = candidates();
+        {
+            let booster = HistoryBooster::with_store(LearningStore::new(
+                "history-booster",
+                Some(path.clone()),
+            ));
+            booster.record(2, &data, "");
+            booster.record(2, &data, "");
+            booster.record(2, &data, "");
+            booster.persist();
+        }
+
+        let restored =
+            HistoryBooster::with_store(LearningStore::new("history-booster", Some(path)));
+        let inner = restored.inner.read();
+        assert_eq!(*inner.history_launch_time.get("calc.exe").unwrap(), 3);
+        assert_eq!(*inner.launch_time[0].get("calc.exe").unwrap(), 3);
+        assert!(inner.latest_launch_time.contains_key("calc.exe"));
+        assert!(inner.history_launch_time.get("notepad.exe").is_none());
+    }
+}
@@ -0,0 +1,471 @@
+//! 分数增强器学习数据的持久化层。
+//!
+//! 学习数据以带版本号的 JSON 快照形式保存在 `AppDataDir/learning/` 下，
+//! 经共享的 `config::store::write_atomic` 写入（与配置文件同一原子写入实现）。
+//! 无法解析的快照经共享的 `backup_corrupted` 备份为 `.json.bak` 后忽略；
+//! 由更新版本写出的快照原样保留，本次运行也不再覆盖它，降级后再升级不会丢失学习数据。
+
+use crate::core::config::store::{backup_corrupted, write_atomic};
+use crate::utils::get_current_time;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::path::path_resolver::KnownPath;
+
+/// 当前快照格式版本，数据结构不兼容变更时递增。
+pub const LEARNING_SNAPSHOT_VERSION: u32 = 1;
+
+/// 记录学习数据后延迟写盘的时间，期间的多次记录合并为一次写入。
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(30);
+
+/// 学习数据快照的磁盘格式。
+///
+/// 由 `LearningStore` 写入 `AppDataDir/learning/*.json` 及用户导出文件，
+/// `data` 为各增强器自定义的可序列化学习状态。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LearningSnapshot<T> {
+    /// 快照格式版本
+    pub version: u32,
+    /// 写出快照的组件 ID，导入时用于防止误用其他组件的数据
+    pub component_id: String,
+    /// 保存时间（时间戳）
+    pub saved_at: i64,
+    /// 组件自定义的学习数据
+    pub data: T,
+}
+
+/// LearningStore 的共享状态，仅限本文件内使用。
+///
+/// 通过 Arc 在增强器与延迟写盘任务之间共享。
+struct LearningStoreInner {
+    /// 组件 ID
+    component_id: String,
+    /// 快照文件路径，为 None 时不进行持久化（如测试环境）
+    path: Option<PathBuf>,
+    /// 等待写盘的最新快照；Mutex 保护防抖期间的覆盖写入，None 表示无待写数据
+    pending: Mutex<Option<serde_json::Value>>,
+    /// 是否已有延迟写盘任务在等待
+    scheduled: AtomicBool,
+    /// 快照文件由更新版本写出：原样保留，本次运行不写盘
+    preserved: AtomicBool,
+}
+
+/// 单个分数增强器的学习数据存储 — 负责快照的加载、防抖保存、导入导出。
+///
+/// 仅供 `score_booster` 模块内的增强器持有，学习状态本身仍由增强器管理。
+pub struct LearningStore {
+    inner: Arc<LearningStoreInner>,
+}
+
+impl std::fmt::Debug for LearningStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LearningStore")
+            .field("component_id", &self.inner.component_id)
+            .field("path", &self.inner.path)
+            .finish()
+    }
+}
+
+impl LearningStore {
+    /// 创建学习数据存储。
+    /// 参数：component_id - 组件 ID；path - 快照文件路径，None 表示不持久化。
+    pub fn new(component_id: &str, path: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(LearningStoreInner {
+                component_id: component_id.to_string(),
+                path,
+                pending: Mutex::new(None),
+                scheduled: AtomicBool::new(false),
+                preserved: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// 通过 PluginHandle 解析 `AppDataDir/learning/<file_name>` 作为快照路径。
+    /// 解析失败或路径为空时返回不持久化的存储。
+    pub fn from_handle(component_id: &str, handle: &PluginHandle, file_name: &str) -> Self {
+        let path = match handle.resolve_path(KnownPath::AppDataDir) {
+            Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join("learning").join(file_name)),
+            Ok(_) => None,
+            Err(e) => {
+                warn!(
+                    "[{}] 解析应用数据目录失败，学习数据将不会持久化: {}",
+                    component_id, e
+                );
+                None
+            }
+        };
+        Self::new(component_id, path)
+    }
+
+    /// 从快照文件加载学习数据。
+    /// 文件不存在或不可用时返回 None：无法解析的快照备份后忽略；更新版本写出的快照
+    /// 原样保留，并停止本存储的写盘，避免以旧格式覆盖。
+    pub fn load<T: DeserializeOwned>(&self) -> Option<T> {
+        let path = self.inner.path.as_ref()?;
+        if !path.exists() {
+            debug!("学习数据快照不存在: {:?}", path);
+            return None;
+        }
+
+        match read_snapshot::<T>(path, &self.inner.component_id) {
+            Ok(data) => {
+                debug!("成功加载学习数据快照: {:?}", path);
+                Some(data)
+            }
+            Err(SnapshotError::Corrupt(e)) => {
+                warn!("学习数据快照损坏: {:?}, 错误: {}", path, e);
+                if let Err(e) = backup_corrupted(path) {
+                    warn!("备份损坏的学习数据快照失败: {}", e);
+                }
+                None
+            }
+            Err(SnapshotError::NewerVersion(version)) => {
+                warn!(
+                    "[{}] 学习数据快照由更新版本写出（快照版本 {}，当前 {}），保留原文件且本次不写盘: {:?}",
+                    self.inner.component_id, version, LEARNING_SNAPSHOT_VERSION, path
+                );
+                self.inner.preserved.store(true, Ordering::Release);
+                None
+            }
+            Err(e) => {
+                warn!("学习数据快照不可用: {:?}, 错误: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// 立即将学习数据写入快照文件，并清除等待中的延迟写盘。
+    pub fn save_now<T: Serialize>(&self, data: &T) -> Result<(), String> {
+        let Some(path) = self.inner.path.as_ref() else {
+            return Ok(());
+        };
+        self.inner.pending.lock().take();
+        if self.inner.preserved.load(Ordering::Acquire) {
+            return Err("快照文件由更新版本写出，不予覆盖".to_string());
+        }
+        let snapshot = self.inner.snapshot_value(data)?;
+        write_snapshot(path, &snapshot)
+    }
+
+    /// 延迟保存学习数据：记录最新快照，并在防抖时间后统一写盘。
+    pub fn schedule_save<T: Serialize>(&self, data: &T) {
+        if self.inner.path.is_none() {
+            return;
+        }
+        let snapshot = match self.inner.snapshot_value(data) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!("[{}] 序列化学习数据失败: {}", self.inner.component_id, e);
+                return;
+            }
+        };
+        *self.inner.pending.lock() = Some(snapshot);
+
+        if self.inner.scheduled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let inner = self.inner.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DEBOUNCE).await;
+            inner.scheduled.store(false, Ordering::Release);
+            inner.flush();
+        });
+    }
+
+    /// 将等待中的快照立即写盘（退出前调用）。
+    pub fn flush(&self) {
+        self.inner.flush();
+    }
+
+    /// 导出学习数据快照到指定文件。
+    pub fn export_to<T: Serialize>(&self, data: &T, target: &Path) -> Result<(), String> {
+        let snapshot = self.inner.snapshot_value(data)?;
+        write_snapshot(target, &snapshot)
+    }
+
+    /// 导出学习数据快照到指定目录，文件名为 `<组件ID>-<时间戳>.json`。
+    /// 返回：导出文件的完整路径。
+    pub fn export_to_dir<T: Serialize>(&self, data: &T, dir: &Path) -> Result<PathBuf, String> {
+        if !dir.is_dir() {
+            return Err(format!("导出目录不存在: {}", dir.display()));
+        }
+        let target = dir.join(format!(
+            "{}-{}.json",
+            self.inner.component_id,
+            get_current_time()
+        ));
+        self.export_to(data, &target)?;
+        Ok(target)
+    }
+
+    /// 从指定文件读取学习数据快照（校验版本与组件 ID）。
+    pub fn import_from<T: DeserializeOwned>(&self, source: &Path) -> Result<T, String> {
+        read_snapshot(source, &self.inner.component_id).map_err(|e| e.to_string())
+    }
+}
+
+impl LearningStoreInner {
+    fn snapshot_value<T: Serialize>(&self, data: &T) -> Result<serde_json::Value, String> {
+        let snapshot = LearningSnapshot {
+            version: LEARNING_SNAPSHOT_VERSION,
+            component_id: self.component_id.clone(),
+            saved_at: get_current_time(),
+            data,
+        };
+        serde_json::to_value(&snapshot).map_err(|e| e.to_string())
+    }
+
+    fn flush(&self) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let Some(snapshot) = self.pending.lock().take() else {
+            return;
+        };
+        if self.preserved.load(Ordering::Acquire) {
+            return;
+        }
+        if let Err(e) = write_snapshot(path, &snapshot) {
+            warn!("[{}] 保存学习数据失败: {}", self.component_id, e);
+        }
+    }
+}
+
+/// 快照读取失败的原因；加载时据此决定备份、保留还是忽略。
+#[derive(Debug)]
+enum SnapshotError {
+    /// 文件读取失败
+    Io(std::io::Error),
+    /// 内容无法解析（损坏）
+    Corrupt(serde_json::Error),
+    /// 由更新版本写出，当前版本无法识别
+    NewerVersion(u32),
+    /// 可解析但不适用：旧版本或其他组件的快照
+    Incompatible(String),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Corrupt(e) => write!(f, "{}", e),
+            Self::NewerVersion(version) => write!(
+                f,
+                "快照版本 {} 高于当前版本 {}",
+                version, LEARNING_SNAPSHOT_VERSION
+            ),
+            Self::Incompatible(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// 读取并校验快照文件，返回其中的学习数据。
+fn read_snapshot<T: DeserializeOwned>(path: &Path, component_id: &str) -> Result<T, SnapshotError> {
+    let content = std::fs::read_to_string(path).map_err(SnapshotError::Io)?;
+    let snapshot: LearningSnapshot<serde_json::Value> =
+        serde_json::from_str(&content).map_err(SnapshotError::Corrupt)?;
+    if snapshot.version > LEARNING_SNAPSHOT_VERSION {
+        return Err(SnapshotError::NewerVersion(snapshot.version));
+    }
+    if snapshot.version != LEARNING_SNAPSHOT_VERSION {
+        return Err(SnapshotError::Incompatible(format!(
+            "不支持的快照版本: {}（当前版本 {}）",
+            snapshot.version, LEARNING_SNAPSHOT_VERSION
+        )));
+    }
+    if snapshot.component_id != component_id {
+        return Err(SnapshotError::Incompatible(format!(
+            "快照属于组件 {}，与当前组件 {} 不匹配",
+            snapshot.component_id, component_id
+        )));
+    }
+    serde_json::from_value(snapshot.data).map_err(SnapshotError::Corrupt)
+}
+
+/// 序列化快照并原子写入目标文件。
+fn write_snapshot(path: &Path, snapshot: &serde_json::Value) -> Result<(), String> {
+    let content = serde_json::to_vec(snapshot).map_err(|e| e.to_string())?;
+    write_atomic(path, &content).map_err(|e| e.to_string())?;
+    debug!("学习数据已保存到: {:?}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn temp_store() -> (LearningStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let path = dir.path().join("learning").join("test_booster.json");
+        (LearningStore::new("test-booster", Some(path)), dir)
+    }
+
+    /// 学习目录下的全部文件名（排序后）。
+    fn learning_files(dir: &tempfile::TempDir) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir.path().join("learning"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let (store, dir) = temp_store();
+        let data: HashMap<String, u64> = HashMap::from([("notepad.exe".to_string(), 3)]);
+        store.save_now(&data).expect("保存失败");
+
+        // 只留下快照本身，不残留临时文件
+        assert_eq!(learning_files(&dir), vec!["test_booster.json"]);
+        let raw: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join("learning/test_booster.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(raw["version"], LEARNING_SNAPSHOT_VERSION);
+        assert_eq!(raw["component_id"], "test-booster");
+        assert!(raw["saved_at"].as_i64().unwrap() > 0);
+
+        let loaded: HashMap<String, u64> = store.load().expect("应能加载快照");
+        assert_eq!(loaded, data);
+
+        // 覆盖写入后读到的是新数据
+        let updated: HashMap<String, u64> = HashMap::from([("code.exe".to_string(), 1)]);
+        store.save_now(&updated).expect("保存失败");
+        assert_eq!(store.load::<HashMap<String, u64>>(), Some(updated));
+        assert_eq!(learning_files(&dir), vec!["test_booster.json"]);
+    }
+
+    /// 延迟保存只保留最后一次的数据，flush 后立即落盘。
+    #[tokio::test]
+    async fn scheduled_save_flushes_latest_snapshot() {
+        let (store, dir) = temp_store();
+        store.schedule_save(&HashMap::from([("a".to_string(), 1u64)]));
+        store.schedule_save(&HashMap::from([("b".to_string(), 2u64)]));
+        assert!(
+            !dir.path().join("learning/test_booster.json").exists(),
+            "防抖期间不应写盘"
+        );
+
+        store.flush();
+        assert_eq!(
+            store.load::<HashMap<String, u64>>(),
+            Some(HashMap::from([("b".to_string(), 2u64)]))
+        );
+        assert_eq!(learning_files(&dir), vec!["test_booster.json"]);
+    }
+
+    /// 导出到目录后可由同一组件导入。
+    #[test]
+    fn export_and_import_roundtrip() {
+        let (store, dir) = temp_store();
+        let data: HashMap<String, u64> = HashMap::from([("notepad.exe".to_string(), 3)]);
+        let exported = store.export_to_dir(&data, dir.path()).expect("导出失败");
+        assert!(exported
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("test-booster-"));
+
+        let imported: HashMap<String, u64> = store.import_from(&exported).expect("导入失败");
+        assert_eq!(imported, data);
+        assert!(store
+            .export_to_dir(&data, &dir.path().join("missing"))
+            .is_err());
+    }
+
+    #[test]
+    fn corrupt_snapshot_is_backed_up() {
+        let (store, dir) = temp_store();
+        let path = dir.path().join("learning/test_booster.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(store.load::<HashMap<String, u64>>().is_none());
+        assert!(!path.exists(), "损坏的快照应被移走");
+        assert!(dir.path().join("learning/test_booster.json.bak").exists());
+    }
+
+    /// 更新版本写出的快照原样保留：不备份、不移动，本次运行的保存也不覆盖它。
+    #[tokio::test]
+    async fn newer_version_snapshot_is_preserved() {
+        let (store, dir) = temp_store();
+        let path = dir.path().join("learning/test_booster.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let snapshot = serde_json::json!({
+            "version": LEARNING_SNAPSHOT_VERSION + 1,
+            "component_id": "test-booster",
+            "saved_at": 0,
+            "data": { "future": true }
+        })
+        .to_string();
+        std::fs::write(&path, &snapshot).unwrap();
+
+        assert!(store.load::<HashMap<String, u64>>().is_none());
+        assert_eq!(learning_files(&dir), vec!["test_booster.json"]);
+
+        let data: HashMap<String, u64> = HashMap::from([("notepad.exe".to_string(), 3)]);
+        assert!(store.save_now(&data).is_err());
+        store.schedule_save(&data);
+        store.flush();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), snapshot);
+    }
+
+    /// 旧版本或学习数据结构不符的快照：前者忽略但保留，后者视为损坏备份。
+    #[test]
+    fn older_version_is_ignored_and_bad_data_is_backed_up() {
+        let (store, dir) = temp_store();
+        let path = dir.path().join("learning/test_booster.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let older = serde_json::json!({
+            "version": 0,
+            "component_id": "test-booster",
+            "saved_at": 0,
+            "data": {}
+        });
+        std::fs::write(&path, older.to_string()).unwrap();
+        assert!(store.load::<HashMap<String, u64>>().is_none());
+        assert_eq!(learning_files(&dir), vec!["test_booster.json"]);
+
+        let bad_data = serde_json::json!({
+            "version": LEARNING_SNAPSHOT_VERSION,
+            "component_id": "test-booster",
+            "saved_at": 0,
+            "data": "not a map"
+        });
+        std::fs::write(&path, bad_data.to_string()).unwrap();
+        assert!(store.load::<HashMap<String, u64>>().is_none());
+        assert_eq!(learning_files(&dir), vec!["test_booster.json.bak"]);
+    }
+
+    #[test]
+    fn import_rejects_other_component_snapshot() {
+        let (store, dir) = temp_store();
+        let other = LearningStore::new("other-booster", None);
+        let export_path = dir.path().join("export.json");
+        other
+            .export_to(&HashMap::<String, u64>::new(), &export_path)
+            .expect("导出失败");
+
+        assert!(store
+            .import_from::<HashMap<String, u64>>(&export_path)
+            .is_err());
+    }
+
+    #[test]
+    fn store_without_path_is_noop() {
+        let store = LearningStore::new("test-booster", None);
+        store.save_now(&1u32).expect("无路径时保存应直接成功");
+        store.schedule_save(&1u32);
+        store.flush();
+        assert!(store.load::<u32>().is_none());
+    }
+}
@@ -1,2 +1,29 @@
+pub mod context_booster;
 pub mod history_booster;
+pub mod learning_store;
 pub mod query_affinity;
+
+/// 增强器测试共用的候选项数据。
+#[cfg(test)]
+pub(crate) mod test_support {
+    use zerolaunch_plugin_api::services::IconRequest;
+    use zerolaunch_plugin_api::{CachedCandidateData, ExecutionTarget, SearchCandidate};
+
+    /// 构造两条程序候选项（ID 1 = notepad，ID 2 = calc）。
+    pub fn candidates() -> CachedCandidateData {
+        let mut data = CachedCandidateData::new();
+        for (name, path) in [("Notepad", "notepad.exe"), ("Calc", "calc.exe")] {
+            data.add_candidate(SearchCandidate {
+                id: 0,
+                name: name.to_string(),
+                icon: IconRequest::Path(String::new()),
+                target: ExecutionTarget::Path(path.to_string()),
+                keywords: Vec::new(),
+                bias: 0.0,
+                trigger_keywords: Vec::new(),
+                actions: Vec::new(),
+            });
+        }
+        data
+    }
+}
@@ -1,12 +1,15 @@
+use super::learning_store::LearningStore;
 use crate::core::config::setting_builders::SchemaBuilder;
 use crate::utils::get_current_time;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use tracing::error;
+use std::path::Path;
+use tracing::{error, info};
 use zerolaunch_plugin_api::config::{
-    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+    ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable, EffectActionBinding,
+    SettingDefinition,
 };
 use zerolaunch_plugin_api::{
     CachedCandidateData, CandidateId, ScoreBooster, ScoreDetail, ScoreDetailKind, ScoredCandidate,
@@ -16,13 +19,33 @@ use zerolaunch_plugin_api::{
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct QueryAffinityData {
     /// 衰减后的有效次数（浮点数，支持衰减累积）
+    #[serde(rename = "effective_count", default)]
     effective_count: f64,
     /// 最后一次启动时间（用于计算时的衰减）
+    #[serde(rename = "last_launch_time", default)]
     last_launch_time: i64,
     /// 最后一次记录计数的时间（用于冷却机制）
+    #[serde(rename = "last_record_time", default)]
     last_record_time: i64,
 }
 
+/// 单条查询亲和度记录的持久化形式。
+///
+/// 仅限本文件内使用。内存中以 (查询词, launch_method_text) 元组为键，
+/// JSON 不支持元组键，因此快照中展开为记录列表。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueryAffinityEntry {
+    /// 查询词（已转小写）
+    #[serde(rename = "query", default)]
+    query: String,
+    /// 候选项的启动目标文本
+    #[serde(rename = "method_text", default)]
+    method_text: String,
+    /// 亲和度统计数据
+    #[serde(rename = "data")]
+    data: QueryAffinityData,
+}
+
 impl QueryAffinityData {
     fn new(current_time: i64) -> Self {
         Self {
@@ -87,6 +110,28 @@ impl QueryAffinityBoosterInner {
         }
     }
 
+    /// 从持久化快照恢复亲和度映射。
+    fn from_entries(entries: Vec<QueryAffinityEntry>) -> Self {
+        QueryAffinityBoosterInner {
+            query_affinity_map: entries
+                .into_iter()
+                .map(|e| ((e.query, e.method_text), e.data))
+                .collect(),
+        }
+    }
+
+    /// 生成当前亲和度映射的持久化快照。
+    fn to_entries(&self) -> Vec<QueryAffinityEntry> {
+        self.query_affinity_map
+            .iter()
+            .map(|e| QueryAffinityEntry {
+                query: e.key().0.clone(),
+                method_text: e.key().1.clone(),
+                data: e.value().clone(),
+            })
+            .collect()
+    }
+
     /// 记录查询-程序启动关联（衰减累积 + 冷却机制）
     fn record_query_launch(
         &mut self,
@@ -151,6 +196,8 @@ pub struct QueryAffinityBooster {
     core: ComponentCore,
     inner: RwLock<QueryAffinityBoosterInner>,
     settings: RwLock<QueryAffinitySettings>,
+    /// 学习数据快照存储，record 后防抖写盘，退出前 flush
+    store: LearningStore,
 }
 
 impl Default for QueryAffinityBooster {
@@ -160,7 +207,23 @@ impl Default for QueryAffinityBooster {
 }
 
 impl QueryAffinityBooster {
+    /// 创建不持久化学习数据的增强器。
     pub fn new() -> Self {
+        Self::with_store(LearningStore::new("query-affinity-booster", None))
+    }
+
+    /// 使用指定的学习数据存储创建增强器，并从快照恢复查询亲和度数据。
+    pub fn with_store(store: LearningStore) -> Self {
+        let inner = match store.load::<Vec<QueryAffinityEntry>>() {
+            Some(entries) => {
+                info!(
+                    "[QueryAffinityBooster] 已加载 {} 条持久化的查询亲和度记录",
+                    entries.len()
+                );
+                QueryAffinityBoosterInner::from_entries(entries)
+            }
+            None => QueryAffinityBoosterInner::new(),
+        };
         QueryAffinityBooster {
             core: ComponentCore::new(
                 "query-affinity-booster".to_string(),
@@ -169,8 +232,57 @@ impl QueryAffinityBooster {
                 ComponentType::ScoreBooster,
                 10,
             ),
-            inner: RwLock::new(QueryAffinityBoosterInner::new()),
+            inner: RwLock::new(inner),
             settings: RwLock::new(QueryAffinitySettings::default()),
+            store,
+        }
+    }
+
+    /// 导出学习数据到指定目录。
+    fn export_learning_data(&self, dir: &str) -> serde_json::Value {
+        let entries = self.inner.read().to_entries();
+        match self.store.export_to_dir(&entries, Path::new(dir)) {
+            Ok(target) => serde_json::json!({
+                "success": true,
+                "message": format!("学习数据已导出到 {}", target.display()),
+                "export_dir": "",
+            }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 从快照文件导入学习数据，替换当前数据并立即写盘。
+    fn import_learning_data(&self, file: &str) -> serde_json::Value {
+        let entries = match self
+            .store
+            .import_from::<Vec<QueryAffinityEntry>>(Path::new(file))
+        {
+            Ok(entries) => entries,
+            Err(e) => return serde_json::json!({ "success": false, "message": e }),
+        };
+        let snapshot = {
+            let mut inner = self.inner.write();
+            *inner = QueryAffinityBoosterInner::from_entries(entries);
+            inner.to_entries()
+        };
+        match self.store.save_now(&snapshot) {
+            Ok(()) => serde_json::json!({ "success": true, "import_file": "" }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
+        }
+    }
+
+    /// 清空学习数据并立即写盘。需先打开确认开关。
+    fn clear_learning_data(&self, confirmed: bool) -> serde_json::Value {
+        if !confirmed {
+            return serde_json::json!({
+                "success": false,
+                "message": "请先打开确认开关再清空学习数据",
+            });
+        }
+        *self.inner.write() = QueryAffinityBoosterInner::new();
+        match self.store.save_now(&Vec::<QueryAffinityEntry>::new()) {
+            Ok(()) => serde_json::json!({ "success": true, "clear_confirm": false }),
+            Err(e) => serde_json::json!({ "success": false, "message": e }),
         }
     }
 }
@@ -237,6 +349,53 @@ impl Configurable for QueryAffinityBooster {
             .max(300.0)
             .step(1.0)
             .build(),
+            SchemaBuilder::path(
+                "export_dir",
+                t_key!("query-affinity-booster", "fields.export_dir.label"),
+                t_key!("query-affinity-booster", "fields.export_dir.desc"),
+            )
+            .directory()
+            .group(t_key!("query-affinity-booster", "groups.learning_data"))
+            .order(3)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "export_learning_data".into(),
+                component: None,
+                field_mapping: vec![("export_dir".into(), "export_dir".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::path(
+                "import_file",
+                t_key!("query-affinity-booster", "fields.import_file.label"),
+                t_key!("query-affinity-booster", "fields.import_file.desc"),
+            )
+            .file()
+            .group(t_key!("query-affinity-booster", "groups.learning_data"))
+            .order(4)
+            .default("")
+            .effect_action(EffectActionBinding {
+                action: "import_learning_data".into(),
+                component: None,
+                field_mapping: vec![("import_file".into(), "import_file".into())],
+                transient: true,
+            })
+            .build(),
+            SchemaBuilder::boolean(
+                "clear_confirm",
+                t_key!("query-affinity-booster", "fields.clear_confirm.label"),
+                t_key!("query-affinity-booster", "fields.clear_confirm.desc"),
+            )
+            .group(t_key!("query-affinity-booster", "groups.learning_data"))
+            .order(5)
+            .default(false)
+            .effect_action(EffectActionBinding {
+                action: "clear_learning_data".into(),
+                component: None,
+                field_mapping: vec![("clear_confirm".into(), "clear_confirm".into())],
+                transient: true,
+            })
+            .build(),
         ]
     }
 
@@ -249,6 +408,85 @@ impl Configurable for QueryAffinityBooster {
         *self.settings.write() = parsed;
         Ok(())
     }
+
+    fn config_actions(&self) -> Vec<ConfigActionDef> {
+        vec![
+            ConfigActionDef {
+                action: "export_learning_data".to_string(),
+                label: t_key!(
+                    "query-affinity-booster",
+                    "actions.export_learning_data.label"
+                )
+                .to_string(),
+                description: t_key!(
+                    "query-affinity-booster",
+                    "actions.export_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "import_learning_data".to_string(),
+                label: t_key!(
+                    "query-affinity-booster",
+                    "actions.import_learning_data.label"
+                )
+                .to_string(),
+                description: t_key!(
+                    "query-affinity-booster",
+                    "actions.import_learning_data.description"
+                )
+                .to_string(),
+            },
+            ConfigActionDef {
+                action: "clear_learning_data".to_string(),
+                label: t_key!(
+                    "query-affinity-booster",
+                    "actions.clear_learning_data.label"
+                )
+                .to_string(),
+                description: t_key!(
+                    "query-affinity-booster",
+                    "actions.clear_learning_data.description"
+                )
+                .to_string(),
+            },
+        ]
+    }
+
+    /// 学习数据的导出/导入/清空动作。
+    /// 副作用：直接读写学习数据快照文件，不修改组件配置，字段值不持久化。
+    async fn execute_config_action(
+        &self,
+        action: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match action {
+            "export_learning_data" => {
+                let dir = params
+                    .get("export_dir")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 export_dir".to_string())?;
+                Ok(self.export_learning_data(dir))
+            }
+            "import_learning_data" => {
+                let file = params
+                    .get("import_file")
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| "缺少参数 import_file".to_string())?;
+                Ok(self.import_learning_data(file))
+            }
+            "clear_learning_data" => {
+                let confirmed = params
+                    .get("clear_confirm")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Ok(self.clear_learning_data(confirmed))
+            }
+            _ => Err(format!("未知动作: {}", action)),
+        }
+    }
 }
 
 impl ScoreBooster for QueryAffinityBooster {
@@ -260,12 +498,17 @@ impl ScoreBooster for QueryAffinityBooster {
         if let Some(search_candidate) = data.get_candidate(candidate_id) {
             let method_text = search_candidate.target.payload();
             let settings = self.settings.read();
-            self.inner.write().record_query_launch(
-                query,
-                method_text,
-                settings.query_affinity_cooldown as i64,
-                settings.query_affinity_time_decay as i64,
-            );
+            let snapshot = {
+                let mut inner = self.inner.write();
+                inner.record_query_launch(
+                    query,
+                    method_text,
+                    settings.query_affinity_cooldown as i64,
+                    settings.query_affinity_time_decay as i64,
+                );
+                inner.to_entries()
+            };
+            self.store.schedule_save(&snapshot);
         } else {
             error!(
                 "[QueryAffinityBooster] 无法找到候选项数据，无法记录查询启动关联，candidate_id: {}",
@@ -310,13 +553,23 @@ impl ScoreBooster for QueryAffinityBooster {
             });
         }
     }
+
+    /// 退出前将等待中的学习数据立即写盘
+    fn persist(&self) {
+        self.store.flush();
+    }
 }
 
-use crate::plugin_framework::builtin_registry::ScoreBoosterEntry;
+use crate::plugin_framework::builtin_registry::{InventoryContext, ScoreBoosterEntry};
 use std::sync::Arc;
 
-pub(crate) fn build_query_affinity_booster() -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>) {
-    let booster: Arc<dyn ScoreBooster> = Arc::new(QueryAffinityBooster::new());
+pub(crate) fn build_query_affinity_booster(
+    ctx: &InventoryContext,
+) -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>) {
+    let handle = ctx.get_handle("query-affinity-booster");
+    let store =
+        LearningStore::from_handle("query-affinity-booster", &handle, "query_affinity.json");
+    let booster: Arc<dyn ScoreBooster> = Arc::new(QueryAffinityBooster::with_store(store));
     let configurable: Arc<dyn Configurable> = booster.clone();
     (configurable, booster)
 }
@@ -328,3 +581,161 @@ pub(crate) fn build_query_affinity_booster() -> (Arc<dyn Configurable>, Arc<dyn
         factory: build_query_affinity_booster,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin_plugin::score_booster::test_support::candidates;
+
+    const DECAY: i64 = 259200;
+
+    fn scored(candidate_id: CandidateId) -> ScoredCandidate {
+        ScoredCandidate {
+            candidate_id,
+            score: 0.0,
+            detailed_score: Vec::new(),
+        }
+    }
+
+    fn entry(query: &str, method_text: &str, data: QueryAffinityData) -> QueryAffinityEntry {
+        QueryAffinityEntry {
+            query: query.to_string(),
+            method_text: method_text.to_string(),
+            data,
+        }
+    }
+
+    /// 亲和度按（小写查询词，候选项）记录：只对同一查询词的同一候选项加分。
+    #[tokio::test]
+    async fn affinity_is_scoped_to_query_and_candidate() {
+        let booster = QueryAffinityBooster::new();
+        let data = candidates();
+        booster.record(1, &data, "NOTE");
+
+        let mut results = vec![scored(1), scored(2)];
+        booster.boost(&mut results, &data, "note");
+        let expected = 1f64.ln_1p() * 10.0;
+        assert!((results[0].detailed_score[0].score - expected).abs() < 1e-3);
+        assert!((results[0].score - expected * 3.0).abs() < 1e-2);
+        assert_eq!(results[1].score, 0.0);
+
+        let mut other_query = vec![scored(1)];
+        booster.boost(&mut other_query, &data, "calc");
+        assert_eq!(other_query[0].score, 0.0);
+
+        let mut empty_query = vec![scored(1)];
+        booster.boost(&mut empty_query, &data, "");
+        assert!(
+            empty_query[0].detailed_score.is_empty(),
+            "空查询不参与亲和度"
+        );
+    }
+
+    /// 空查询的启动不记录亲和度。
+    #[tokio::test]
+    async fn blank_query_is_not_recorded() {
+        let booster = QueryAffinityBooster::new();
+        booster.record(1, &candidates(), "  ");
+        assert!(booster.inner.read().query_affinity_map.is_empty());
+    }
+
+    /// 冷却时间内的重复启动不累计次数，但刷新最后启动时间。
+    #[test]
+    fn cooldown_prevents_double_counting() {
+        let mut inner = QueryAffinityBoosterInner::new();
+        inner.record_query_launch("note", "notepad.exe", 15, DECAY);
+        inner.record_query_launch("note", "notepad.exe", 15, DECAY);
+        let key = ("note".to_string(), "notepad.exe".to_string());
+        assert_eq!(
+            inner.query_affinity_map.get(&key).unwrap().effective_count,
+            1.0
+        );
+    }
+
+    /// 冷却结束后先衰减旧次数再累加：旧记录越久，累积越少。
+    #[test]
+    fn recording_after_cooldown_accumulates_with_decay() {
+        let now = get_current_time();
+        let mut inner = QueryAffinityBoosterInner::from_entries(vec![
+            entry(
+                "note",
+                "notepad.exe",
+                QueryAffinityData {
+                    effective_count: 2.0,
+                    last_launch_time: now - 100,
+                    last_record_time: now - 100,
+                },
+            ),
+            entry(
+                "note",
+                "old.exe",
+                QueryAffinityData {
+                    effective_count: 2.0,
+                    last_launch_time: now - DECAY,
+                    last_record_time: now - DECAY,
+                },
+            ),
+        ]);
+        inner.record_query_launch("note", "notepad.exe", 15, DECAY);
+        inner.record_query_launch("note", "old.exe", 15, DECAY);
+
+        let count = |method: &str| {
+            inner
+                .query_affinity_map
+                .get(&("note".to_string(), method.to_string()))
+                .unwrap()
+                .effective_count
+        };
+        assert!(count("notepad.exe") > 2.99 && count("notepad.exe") < 3.0);
+        let old_expected = 2.0 * (-(DECAY as f64) / (DECAY as f64 + 1.0)).exp() + 1.0;
+        assert!((count("old.exe") - old_expected).abs() < 1e-3);
+    }
+
+    /// 亲和分随距上次启动的时间指数衰减。
+    #[test]
+    fn score_decays_with_time_since_launch() {
+        let now = get_current_time();
+        let inner = QueryAffinityBoosterInner::from_entries(vec![
+            entry("note", "fresh.exe", QueryAffinityData::new(now)),
+            entry("note", "stale.exe", QueryAffinityData::new(now - DECAY)),
+        ]);
+        let fresh = inner.calculate_query_affinity_score("note", "fresh.exe", DECAY);
+        let stale = inner.calculate_query_affinity_score("note", "stale.exe", DECAY);
+        assert!((fresh - 1f64.ln_1p() * 10.0).abs() < 1e-3);
+        let stale_expected = (-(DECAY as f64) / (DECAY as f64 + 1.0)).exp().ln_1p() * 10.0;
+        assert!((stale - stale_expected).abs() < 1e-3);
+        assert_eq!(
+            inner.calculate_query_affinity_score("note", "never.exe", DECAY),
+            0.0
+        );
+    }
+
+    /// 元组键展开为记录列表持久化，重建增强器后恢复。
+    #[tokio::test]
+    async fn learning_data_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learning/query_affinity.json");
+        let data = candidates();
+        {
+            let booster = QueryAffinityBooster::with_store(LearningStore::new(
+                "query-affinity-booster",
+                Some(path.clone()),
+            ));
+            booster.record(1, &data, "note");
+            booster.record(2, &data, "calc");
+            booster.persist();
+        }
+
+        let restored = QueryAffinityBooster::with_store(LearningStore::new(
+            "query-affinity-booster",
+            Some(path),
+        ));
+        let inner = restored.inner.read();
+        assert_eq!(inner.query_affinity_map.len(), 2);
+        let key = ("calc".to_string(), "calc.exe".to_string());
+        assert_eq!(
+            inner.query_affinity_map.get(&key).unwrap().effective_count,
+            1.0
+        );
+    }
+}
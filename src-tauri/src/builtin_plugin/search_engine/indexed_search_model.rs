@@ -0,0 +1,601 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigError, Configurable, SettingDefinition,
+};
+use zerolaunch_plugin_api::{
+    CachedCandidateData, CandidateId, ScoreDetail, ScoreDetailKind, ScoredCandidate,
+    SearchCandidate, SearchEngine,
+};
+
+use crate::core::config::setting_builders::SchemaBuilder;
+
+/// 词首标记：与词首字符组成 gram，使单字符查询也能命中索引，并用于词边界定位。
+const WORD_START: char = '\0';
+/// 每个匹配字符的得分。
+const MATCHED_CHAR_SCORE: f64 = 10.0;
+/// 每处拼写容错的扣分。
+const TYPO_PENALTY: f64 = 5.0;
+/// 匹配从关键字开头开始的加分。
+const KEYWORD_PREFIX_BONUS: f64 = 15.0;
+/// 匹配从关键字中间的词首开始的加分。
+const WORD_BOUNDARY_BONUS: f64 = 8.0;
+/// 覆盖率（查询长度 / 关键字长度）满分。
+const COVERAGE_SCORE: f64 = 10.0;
+
+/// 索引搜索引擎
+///
+/// 候选项缓存更新时为全部关键字建立 gram 倒排索引（相邻字符二元组 + 词首标记），
+/// 查询时先按 gram 命中数筛出候选，再以有界 Damerau 编辑距离（OSA）校验并打分，
+/// 避免每次按键都对全部候选项的全部关键字做线性扫描。
+/// 评分综合匹配字符数、拼写容错扣分、前缀/词边界加成与覆盖率。
+pub struct IndexedSearchModel {
+    core: ComponentCore,
+    settings: RwLock<IndexedSearchSettings>,
+    index: RwLock<Option<CandidateIndex>>,
+}
+
+/// 索引搜索引擎设置。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedSearchSettings {
+    /// 允许的最大拼写错误数（实际上限还随查询长度收紧）
+    #[serde(
+        rename = "max_typos",
+        default = "IndexedSearchSettings::default_max_typos"
+    )]
+    max_typos: usize,
+}
+
+impl IndexedSearchSettings {
+    fn default_max_typos() -> usize {
+        2
+    }
+}
+
+impl Default for IndexedSearchSettings {
+    fn default() -> Self {
+        Self {
+            max_typos: Self::default_max_typos(),
+        }
+    }
+}
+
+/// 候选项关键字的 gram 倒排索引。
+struct CandidateIndex {
+    /// 建索引时的候选项数量与下一个候选 ID，用于校验索引与搜索时传入的数据一致
+    fingerprint: (usize, CandidateId),
+    /// gram → 关键字含该 gram 的候选项下标（升序、去重）
+    postings: HashMap<(char, char), Vec<u32>>,
+}
+
+impl CandidateIndex {
+    /// 为候选项缓存建立索引。
+    fn build(candidates: &CachedCandidateData) -> Self {
+        let mut postings: HashMap<(char, char), Vec<u32>> = HashMap::new();
+        for (pos, candidate) in candidates.get_candidates().iter().enumerate() {
+            let pos = pos as u32;
+            for keyword in &candidate.keywords {
+                let chars: Vec<char> = keyword.to_lowercase().chars().collect();
+                for gram in grams(&chars) {
+                    let list = postings.entry(gram).or_default();
+                    if list.last() != Some(&pos) {
+                        list.push(pos);
+                    }
+                }
+            }
+        }
+        Self {
+            fingerprint: fingerprint(candidates),
+            postings,
+        }
+    }
+
+    /// 筛选可能匹配的候选项下标。
+    /// 每处编辑最多破坏 3 个 gram（换位），词首前缀匹配的命中数不少于 `gram 数 - 3 × 容错数`；
+    /// 词内子串匹配为精确匹配，只少查询首字符的词首 gram。命中数低于两者较小值的候选项
+    /// 不可能通过校验；该下限不为正时无法安全剪枝，退化为全量校验。
+    fn shortlist(&self, query: &[char], max_typos: usize, len: usize) -> Vec<bool> {
+        let mut query_grams = grams(query);
+        query_grams.sort_unstable();
+        query_grams.dedup();
+        let gram_count = query_grams.len();
+        let mut required = gram_count.saturating_sub(3 * max_typos);
+        if allows_inner_match(query) {
+            required = required.min(gram_count - 1);
+        }
+        if required == 0 {
+            return vec![true; len];
+        }
+
+        let mut hits = vec![0u16; len];
+        for gram in &query_grams {
+            if let Some(list) = self.postings.get(gram) {
+                for &pos in list {
+                    hits[pos as usize] += 1;
+                }
+            }
+        }
+        hits.into_iter()
+            .map(|count| count as usize >= required)
+            .collect()
+    }
+}
+
+impl IndexedSearchModel {
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                "indexed-search-model".to_string(),
+                t_key!("indexed-search-model", "name").to_string(),
+                t_key!("indexed-search-model", "description").to_string(),
+                ComponentType::SearchEngine,
+                30,
+            ),
+            settings: RwLock::new(IndexedSearchSettings::default()),
+            index: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for IndexedSearchModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Configurable for IndexedSearchModel {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![SchemaBuilder::integer(
+            "max_typos",
+            t_key!("indexed-search-model", "fields.max_typos.label"),
+            t_key!("indexed-search-model", "fields.max_typos.desc"),
+        )
+        .group(t_key!("indexed-search-model", "groups.matching"))
+        .order(0)
+        .min(0.0)
+        .max(2.0)
+        .default(2)
+        .build()]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let mut parsed: IndexedSearchSettings =
+            serde_json::from_value(settings).unwrap_or_default();
+        parsed.max_typos = parsed.max_typos.min(2);
+        *self.settings.write() = parsed;
+        Ok(())
+    }
+
+    fn default_enabled(&self) -> bool {
+        false
+    }
+}
+
+impl SearchEngine for IndexedSearchModel {
+    /// 批量计算候选项与查询的匹配分数
+    ///
+    /// 索引与传入数据一致时只校验索引筛出的候选项，其余候选项仅保留固定偏移；
+    /// 尚未建索引或数据不一致时退化为全量校验，结果与使用索引时相同。
+    ///
+    /// # Arguments
+    /// * `candidates` - 缓存的候选数据
+    /// * `query` - 用户输入的搜索字符串（已预处理为小写）
+    ///
+    /// # Returns
+    /// * 按原始数据排列的 `ScoredCandidate` 列表，包含详细评分明细
+    fn calculate_scores(
+        &self,
+        candidates: &CachedCandidateData,
+        query: &str,
+    ) -> Vec<ScoredCandidate> {
+        let list = candidates.get_candidates();
+        let query: Vec<char> = query.chars().collect();
+        if query.iter().all(|c| is_separator(*c)) {
+            return list.iter().map(unmatched).collect();
+        }
+        let max_typos = allowed_typos(query.len(), self.settings.read().max_typos);
+
+        let shortlist = self
+            .index
+            .read()
+            .as_ref()
+            .filter(|index| index.fingerprint == fingerprint(candidates))
+            .map(|index| index.shortlist(&query, max_typos, list.len()))
+            .unwrap_or_else(|| vec![true; list.len()]);
+
+        list.iter()
+            .zip(shortlist)
+            .map(|(candidate, listed)| {
+                if listed {
+                    score_candidate(candidate, &query, max_typos)
+                } else {
+                    unmatched(candidate)
+                }
+            })
+            .collect()
+    }
+
+    /// 候选项缓存更新时重建 gram 倒排索引
+    fn index_candidates(&self, candidates: &CachedCandidateData) {
+        let index = CandidateIndex::build(candidates);
+        *self.index.write() = Some(index);
+    }
+}
+
+/// 索引与数据一致性的校验值：候选项数量 + 下一个候选 ID。
+/// 候选项缓存每次刷新都会重建索引，此处只需识别「不是同一份数据」（如插件自带的候选集）。
+fn fingerprint(candidates: &CachedCandidateData) -> (usize, CandidateId) {
+    (
+        candidates.get_candidates().len(),
+        candidates.next_candidate_id(),
+    )
+}
+
+/// 按查询长度收紧的容错上限：3 个字符以内不容错，6 个字符以内最多 1 处。
+/// 短查询的 gram 太少，容错后索引无法有效剪枝，且短查询的拼写错误多为尚未输完。
+fn allowed_typos(query_len: usize, max_typos: usize) -> usize {
+    let by_len = match query_len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    };
+    by_len.min(max_typos)
+}
+
+/// 词分隔符：其后的字符视为词首。
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '-' | '_' | '.' | '/' | '\\' | '(' | ')' | '[' | ']')
+}
+
+/// 生成字符串的 gram：每个词首字符与词首标记组成一个，词内相邻字符各组成一个。
+fn grams(chars: &[char]) -> Vec<(char, char)> {
+    let mut out = Vec::with_capacity(chars.len() * 2);
+    for (i, &c) in chars.iter().enumerate() {
+        if is_separator(c) {
+            continue;
+        }
+        if i == 0 || is_separator(chars[i - 1]) {
+            out.push((WORD_START, c));
+        }
+        if let Some(&next) = chars.get(i + 1) {
+            if !is_separator(next) {
+                out.push((c, next));
+            }
+        }
+    }
+    out
+}
+
+/// 匹配的起始位置类别（越靠前越优）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchStart {
+    /// 关键字开头
+    Keyword,
+    /// 关键字中间的词首
+    WordBoundary,
+    /// 词内部（仅精确子串）
+    Inner,
+}
+
+/// 查询在单个关键字中的最佳匹配。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeywordMatch {
+    /// Damerau 编辑距离（拼写错误数）
+    distance: usize,
+    /// 起始位置类别
+    start: MatchStart,
+}
+
+/// 在关键字中查找查询的最佳匹配：词首位置允许有界拼写容错的前缀匹配，
+/// 词内部位置只接受精确子串；先比较编辑距离，再比较起始位置类别。
+fn match_keyword(keyword: &[char], query: &[char], max_typos: usize) -> Option<KeywordMatch> {
+    let mut best: Option<KeywordMatch> = None;
+    for s in 0..keyword.len() {
+        if is_separator(keyword[s]) {
+            continue;
+        }
+        let found = if s == 0 || is_separator(keyword[s - 1]) {
+            prefix_distance(query, &keyword[s..], max_typos).map(|distance| KeywordMatch {
+                distance,
+                start: if s == 0 {
+                    MatchStart::Keyword
+                } else {
+                    MatchStart::WordBoundary
+                },
+            })
+        } else if allows_inner_match(query) && keyword[s..].starts_with(query) {
+            Some(KeywordMatch {
+                distance: 0,
+                start: MatchStart::Inner,
+            })
+        } else {
+            None
+        };
+        if let Some(found) = found {
+            if best.is_none_or(|b| (found.distance, found.start) < (b.distance, b.start)) {
+                best = Some(found);
+            }
+        }
+        if best.is_some_and(|b| b.distance == 0 && b.start == MatchStart::Keyword) {
+            break;
+        }
+    }
+    best
+}
+
+/// 是否允许词内子串匹配：查询须以两个非分隔字符开头，
+/// 保证子串匹配至少保留一个词内 gram，索引才能召回（单字符查询只匹配词首）。
+fn allows_inner_match(query: &[char]) -> bool {
+    query.len() > 1 && !is_separator(query[0]) && !is_separator(query[1])
+}
+
+/// 查询与目标任一前缀之间的最小 Damerau 编辑距离（OSA：相邻换位计 1 次编辑）。
+/// 超过 `max_typos` 时返回 None，逐行剪枝。
+fn prefix_distance(query: &[char], target: &[char], max_typos: usize) -> Option<usize> {
+    let m = query.len();
+    let n = target.len().min(m + max_typos);
+    let width = n + 1;
+    // 三行滚动：transposition 需要 i-2 行
+    let mut rows = vec![vec![0usize; width]; 3];
+    for (j, value) in rows[0].iter_mut().enumerate() {
+        *value = j;
+    }
+    for i in 1..=m {
+        let (cur, prev, prev2) = (i % 3, (i + 2) % 3, (i + 1) % 3);
+        rows[cur][0] = i;
+        let mut row_min = i;
+        for j in 1..width {
+            let cost = usize::from(query[i - 1] != target[j - 1]);
+            let mut value = (rows[prev][j - 1] + cost)
+                .min(rows[prev][j] + 1)
+                .min(rows[cur][j - 1] + 1);
+            if i > 1 && j > 1 && query[i - 1] == target[j - 2] && query[i - 2] == target[j - 1] {
+                value = value.min(rows[prev2][j - 2] + 1);
+            }
+            rows[cur][j] = value;
+            row_min = row_min.min(value);
+        }
+        if row_min > max_typos {
+            return None;
+        }
+    }
+    let distance = rows[m % 3].iter().copied().min().unwrap_or(m);
+    (distance <= max_typos).then_some(distance)
+}
+
+/// 计算单个候选项的匹配分数，生成带明细的 ScoredCandidate（取得分最高的关键字）
+fn score_candidate(
+    candidate: &SearchCandidate,
+    query: &[char],
+    max_typos: usize,
+) -> ScoredCandidate {
+    let mut best: Option<(f64, Vec<ScoreDetail>)> = None;
+
+    for keyword in &candidate.keywords {
+        let chars: Vec<char> = keyword.to_lowercase().chars().collect();
+        let Some(found) = match_keyword(&chars, query, max_typos) else {
+            continue;
+        };
+        let details = keyword_details(&found, query.len(), chars.len());
+        let score: f64 = details.iter().map(|d| d.score * d.weight).sum();
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, details));
+        }
+    }
+
+    let Some((mut score, mut details)) = best else {
+        return unmatched(candidate);
+    };
+    if candidate.bias.abs() > f64::EPSILON {
+        details.push(ScoreDetail {
+            score: candidate.bias,
+            weight: 1.0,
+            description: "固定偏移".to_string(),
+            kind: ScoreDetailKind::Add,
+        });
+        score += candidate.bias;
+    }
+    ScoredCandidate {
+        candidate_id: candidate.id,
+        score,
+        detailed_score: details,
+    }
+}
+
+/// 单个关键字匹配的评分明细（均为加法项）。
+fn keyword_details(found: &KeywordMatch, query_len: usize, keyword_len: usize) -> Vec<ScoreDetail> {
+    let mut details = vec![ScoreDetail {
+        score: query_len.saturating_sub(found.distance) as f64,
+        weight: MATCHED_CHAR_SCORE,
+        description: "匹配字符分".to_string(),
+        kind: ScoreDetailKind::Add,
+    }];
+    if found.distance > 0 {
+        details.push(ScoreDetail {
+            score: found.distance as f64,
+            weight: -TYPO_PENALTY,
+            description: "拼写容错扣分".to_string(),
+            kind: ScoreDetailKind::Add,
+        });
+    }
+    let start_bonus = match found.start {
+        MatchStart::Keyword => Some(("前缀加成", KEYWORD_PREFIX_BONUS)),
+        MatchStart::WordBoundary => Some(("词边界加成", WORD_BOUNDARY_BONUS)),
+        MatchStart::Inner => None,
+    };
+    if let Some((description, bonus)) = start_bonus {
+        details.push(ScoreDetail {
+            score: bonus,
+            weight: 1.0,
+            description: description.to_string(),
+            kind: ScoreDetailKind::Add,
+        });
+    }
+    details.push(ScoreDetail {
+        score: (query_len as f64 / keyword_len.max(1) as f64).min(1.0),
+        weight: COVERAGE_SCORE,
+        description: "覆盖率".to_string(),
+        kind: ScoreDetailKind::Add,
+    });
+    details
+}
+
+/// 未匹配的候选项：仅保留固定偏移（与其他引擎一致）。
+fn unmatched(candidate: &SearchCandidate) -> ScoredCandidate {
+    ScoredCandidate {
+        candidate_id: candidate.id,
+        score: candidate.bias,
+        detailed_score: vec![ScoreDetail {
+            score: candidate.bias,
+            weight: 1.0,
+            description: "固定偏移(无匹配)".to_string(),
+            kind: ScoreDetailKind::Add,
+        }],
+    }
+}
+
+use crate::plugin_framework::builtin_registry::SearchEngineEntry;
+use std::sync::Arc;
+
+pub(crate) fn build_indexed_search_model() -> (Arc<dyn Configurable>, Arc<dyn SearchEngine>) {
+    let engine: Arc<dyn SearchEngine> = Arc::new(IndexedSearchModel::new());
+    let configurable: Arc<dyn Configurable> = engine.clone();
+    (configurable, engine)
+}
+
+::inventory::submit! {
+    SearchEngineEntry {
+        component_id: "indexed-search-model",
+        priority: 30,
+        factory: build_indexed_search_model,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerolaunch_plugin_api::services::IconRequest;
+    use zerolaunch_plugin_api::ExecutionTarget;
+
+    /// 构造候选项缓存：每项为 (名称, 关键字)。
+    fn data(items: &[(&str, &[&str])]) -> CachedCandidateData {
+        let mut data = CachedCandidateData::new();
+        for (name, keywords) in items {
+            data.add_candidate(SearchCandidate {
+                id: 0,
+                name: name.to_string(),
+                icon: IconRequest::Url(String::new()),
+                target: ExecutionTarget::Url(format!("https://{}", name)),
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                bias: 0.0,
+                trigger_keywords: Vec::new(),
+                actions: Vec::new(),
+            });
+        }
+        data
+    }
+
+    /// 按分数降序返回得分为正的候选项名称。
+    fn ranked(engine: &IndexedSearchModel, data: &CachedCandidateData, query: &str) -> Vec<String> {
+        let mut scored = engine.calculate_scores(data, query);
+        scored.retain(|s| s.score > 0.0);
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored
+            .iter()
+            .filter_map(|s| data.get_candidate(s.candidate_id))
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    /// 有界 Damerau 距离：换位计 1 次编辑，超出上限返回 None。
+    #[test]
+    fn prefix_distance_counts_transposition_once() {
+        let chars = |s: &str| s.chars().collect::<Vec<char>>();
+        assert_eq!(
+            prefix_distance(&chars("chrome"), &chars("chrome browser"), 2),
+            Some(0)
+        );
+        assert_eq!(
+            prefix_distance(&chars("hcrome"), &chars("chrome"), 1),
+            Some(1)
+        );
+        assert_eq!(
+            prefix_distance(&chars("chrme"), &chars("chrome"), 1),
+            Some(1)
+        );
+        assert_eq!(
+            prefix_distance(&chars("firefox"), &chars("chrome"), 2),
+            None
+        );
+    }
+
+    /// 拼写容错、前缀优先与词边界匹配；建索引前后结果一致。
+    #[test]
+    fn indexed_and_full_scan_agree() {
+        let data = data(&[
+            ("Google Chrome", &["google chrome", "chrome"]),
+            ("Chromium", &["chromium"]),
+            ("Visual Studio Code", &["visual studio code", "code"]),
+            ("Firefox", &["firefox"]),
+        ]);
+        let engine = IndexedSearchModel::new();
+        let queries = ["chrme", "chrom", "studio", "fierfox", "ode", "x"];
+        let full_scan: Vec<Vec<String>> =
+            queries.iter().map(|q| ranked(&engine, &data, q)).collect();
+
+        engine.index_candidates(&data);
+        let indexed: Vec<Vec<String>> = queries.iter().map(|q| ranked(&engine, &data, q)).collect();
+        assert_eq!(full_scan, indexed);
+
+        // 拼写错误仍能命中
+        assert_eq!(indexed[0][0], "Google Chrome");
+        assert_eq!(indexed[3], vec!["Firefox"]);
+        // 前缀匹配：完整关键字 "chrome" 覆盖率更高，排在 "chromium" 之前
+        assert_eq!(indexed[1], vec!["Google Chrome", "Chromium"]);
+        // 词边界匹配
+        assert_eq!(indexed[2], vec!["Visual Studio Code"]);
+        // 词内部子串只接受精确匹配
+        assert_eq!(indexed[4], vec!["Visual Studio Code"]);
+        // 单字符只匹配词首
+        assert!(indexed[5].is_empty());
+    }
+
+    /// 传入的数据与索引不一致时退化为全量校验，不遗漏结果。
+    #[test]
+    fn stale_index_falls_back_to_full_scan() {
+        let engine = IndexedSearchModel::new();
+        engine.index_candidates(&data(&[("Firefox", &["firefox"])]));
+        let other = data(&[("Notepad", &["notepad"]), ("Paint", &["paint"])]);
+        assert_eq!(ranked(&engine, &other, "notepd"), vec!["Notepad"]);
+    }
+
+    /// 评分明细的加法项之和等于总分，未匹配项仅保留固定偏移。
+    #[test]
+    fn details_sum_to_score() {
+        let data = data(&[
+            ("Google Chrome", &["google chrome"]),
+            ("Firefox", &["firefox"]),
+        ]);
+        let engine = IndexedSearchModel::new();
+        engine.index_candidates(&data);
+        for scored in engine.calculate_scores(&data, "chorme") {
+            let sum: f64 = scored
+                .detailed_score
+                .iter()
+                .map(|d| d.score * d.weight)
+                .sum();
+            assert!((sum - scored.score).abs() < 1e-9);
+        }
+    }
+}
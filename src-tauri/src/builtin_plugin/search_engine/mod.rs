@@ -1,3 +1,4 @@
+pub mod indexed_search_model;
 pub mod launchy_search_model;
 pub mod skim_search_model;
 pub mod standard_search_model;
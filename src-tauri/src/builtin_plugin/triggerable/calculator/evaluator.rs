@@ -0,0 +1,782 @@
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, FromPrimitive, Num, One, RoundingMode, Signed, ToPrimitive, Zero};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::units::{self, Unit};
+
+/// π（50 位小数）。
+const PI: &str = "3.14159265358979323846264338327950288419716939937511";
+/// 自然常数 e（50 位小数）。
+const E: &str = "2.71828182845904523536028747135266249775724709369996";
+/// 精确整数幂的指数上限：超出时改用浮点幂，避免超大整数运算阻塞查询。
+const MAX_EXACT_EXPONENT: i64 = 1024;
+/// 结果整数部分的位数上限：超出即拒绝。
+/// 每步运算的操作数都受此限制，单次运算的代价有界（逐键求值不会因超大数阻塞）。
+const MAX_INTEGER_DIGITS: i64 = 1000;
+/// 结果小数位数上限：超出部分四舍五入。
+const MAX_FRACTION_DIGITS: i64 = 1000;
+/// 内置函数名（仅在后跟 `(` 时识别为函数，不可用作变量名）。
+const FUNCTIONS: [&str; 12] = [
+    "sin", "cos", "tan", "ln", "log", "sqrt", "abs", "round", "floor", "ceil", "min", "max",
+];
+/// 保留字：常量、上一结果与语句关键字，不可用作变量名。
+const RESERVED: [&str; 8] = ["pi", "e", "ans", "to", "in", "as", "of", "π"];
+
+/// 三角函数的角度单位。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AngleUnit {
+    Radians,
+    Degrees,
+}
+
+/// 数值的输出进制（`to hex` / `as bin` 等显式请求）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Radix {
+    Hex,
+    Binary,
+    Octal,
+}
+
+impl Radix {
+    /// 按换算目标名称识别进制。
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "hex" | "hexadecimal" => Some(Self::Hex),
+            "bin" | "binary" => Some(Self::Binary),
+            "oct" | "octal" => Some(Self::Octal),
+            _ => None,
+        }
+    }
+}
+
+/// 结果的展示形式（由语句末尾的换算目标决定）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Presentation {
+    /// 普通十进制数
+    Decimal,
+    /// 指定进制（`to hex`）
+    Radix(Radix),
+    /// 百分比（`to %`）
+    Percent,
+}
+
+/// 计算会话状态：上一结果（ans）与用户变量，随进程存活、不持久化。
+#[derive(Debug, Default)]
+pub(super) struct Session {
+    /// 上一次确认的结果
+    pub(super) ans: Option<BigDecimal>,
+    /// 用户变量（`x = 1 + 2` 确认后写入）
+    pub(super) variables: HashMap<String, BigDecimal>,
+}
+
+/// 单条语句的求值结果。
+#[derive(Debug, Clone)]
+pub(super) struct Evaluation {
+    /// 数值（带单位时为最终单位下的数值）
+    pub(super) value: BigDecimal,
+    /// 结果单位（带单位的数量或单位换算结果）
+    pub(super) unit: Option<&'static Unit>,
+    /// 结果的展示形式
+    pub(super) presentation: Presentation,
+    /// 赋值语句的变量名，确认时写入会话变量
+    pub(super) assign_to: Option<String>,
+}
+
+/// 对一条计算语句求值。
+/// 语法：`[name =] 表达式 [单位] [to|in|as 目标]`，目标为单位、进制（hex/bin/oct）或 `%`。
+/// 参数：input - 用户输入；session - 会话状态（ans 与变量）；angle - 三角函数角度单位。
+/// 返回：求值结果，失败时返回描述性错误。
+pub(super) fn evaluate(
+    input: &str,
+    session: &Session,
+    angle: AngleUnit,
+) -> Result<Evaluation, String> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        session,
+        angle,
+        arg_depth: 0,
+    };
+    parser.parse_statement()
+}
+
+/// 判断名称能否用作变量名：不与函数、常量、关键字、进制名冲突。
+/// 单位名不受限制：变量只出现在表达式中，单位只出现在表达式之后。
+fn is_assignable(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    !FUNCTIONS.contains(&lower.as_str())
+        && !RESERVED.contains(&lower.as_str())
+        && Radix::from_name(name).is_none()
+}
+
+/// 判断字符能否作为标识符的组成部分。
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// 递归下降表达式解析器，边解析边以 BigDecimal 求值。
+/// 优先级（低→高）：加减 → 乘除取模 / `of` → 一元正负 → 幂（右结合）→ 后缀 `%` → 原子。
+struct Parser<'a> {
+    /// 输入字符序列
+    chars: Vec<char>,
+    /// 当前读取位置
+    pos: usize,
+    /// 会话状态（解析 ans 与变量引用）
+    session: &'a Session,
+    /// 三角函数角度单位
+    angle: AngleUnit,
+    /// 函数实参的嵌套深度：大于 0 时逗号为参数分隔符，否则为千位分隔符
+    arg_depth: usize,
+}
+
+impl Parser<'_> {
+    /// 查看当前字符。
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// 查看当前位置之后第 offset 个字符。
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    /// 读取并前进一个字符。
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    /// 跳过空白字符。
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// 读取当前位置的标识符（不前进），返回标识符文本。
+    fn peek_ident(&self) -> Option<String> {
+        let first = self.peek()?;
+        if !(first.is_alphabetic() || first == '_') {
+            return None;
+        }
+        Some(
+            self.chars[self.pos..]
+                .iter()
+                .take_while(|c| is_ident_char(**c))
+                .collect(),
+        )
+    }
+
+    /// 当前位置是否为指定关键字（整词匹配，大小写不敏感）。
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.peek_ident()
+            .is_some_and(|w| w.eq_ignore_ascii_case(keyword))
+    }
+
+    /// 解析完整语句：可选赋值前缀 + 表达式 + 可选单位与换算目标。
+    fn parse_statement(&mut self) -> Result<Evaluation, String> {
+        self.skip_whitespace();
+        let assign_to = self.parse_assignment_target()?;
+        let mut value = self.parse_expr()?;
+        self.skip_whitespace();
+
+        let rest: String = self.chars[self.pos..].iter().collect();
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let is_conversion_keyword =
+            |w: &str| ["to", "in", "as"].iter().any(|k| k.eq_ignore_ascii_case(w));
+
+        // 单独的 `in`（如 `5 in`）为英寸；后跟目标时为换算关键字
+        let (source, target) = match words.as_slice() {
+            [] => (None, None),
+            [unit] => (Some(*unit), None),
+            [keyword, target] if is_conversion_keyword(keyword) => (None, Some(*target)),
+            [unit, keyword, target] if is_conversion_keyword(keyword) => {
+                (Some(*unit), Some(*target))
+            }
+            _ => return Err(format!("无法识别的内容: {}", rest.trim())),
+        };
+
+        let mut unit = match source {
+            Some(name) => Some(units::lookup(name).ok_or_else(|| format!("未知单位: {}", name))?),
+            None => None,
+        };
+        let mut presentation = Presentation::Decimal;
+        if let Some(target) = target {
+            if target == "%" {
+                presentation = Presentation::Percent;
+            } else if let Some(radix) = Radix::from_name(target) {
+                presentation = Presentation::Radix(radix);
+            } else {
+                let to = units::lookup(target).ok_or_else(|| format!("未知单位: {}", target))?;
+                let from = unit.ok_or_else(|| format!("换算到 {} 需要指定原单位", to.symbol))?;
+                value = bounded(units::convert(&value, from, to)?)?;
+                unit = Some(to);
+            }
+        }
+        if presentation != Presentation::Decimal && unit.is_some() {
+            return Err("带单位的数量不支持进制或百分比换算".to_string());
+        }
+
+        Ok(Evaluation {
+            value,
+            unit,
+            presentation,
+            assign_to,
+        })
+    }
+
+    /// 解析可选的赋值前缀 `name =`；不是赋值时回退读取位置。
+    fn parse_assignment_target(&mut self) -> Result<Option<String>, String> {
+        let start = self.pos;
+        let Some(name) = self.peek_ident() else {
+            return Ok(None);
+        };
+        self.pos += name.chars().count();
+        self.skip_whitespace();
+        if self.peek() != Some('=') {
+            self.pos = start;
+            return Ok(None);
+        }
+        if !is_assignable(&name) {
+            return Err(format!("不能赋值给保留名称: {}", name));
+        }
+        self.advance();
+        self.skip_whitespace();
+        Ok(Some(name))
+    }
+
+    /// 加减层：右操作数为百分比时按左值的相对比例增减（`200 + 10%` = 220）。
+    fn parse_expr(&mut self) -> Result<BigDecimal, String> {
+        let mut left = self.parse_term()?.0;
+        loop {
+            self.skip_whitespace();
+            let sign = match self.peek() {
+                Some('+') => 1,
+                Some('-') | Some('−') => -1,
+                _ => break,
+            };
+            self.advance();
+            let (rhs, is_percent) = self.parse_term()?;
+            let delta = if is_percent { &left * rhs } else { rhs };
+            left = bounded(if sign > 0 { left + delta } else { left - delta })?;
+        }
+        Ok(left)
+    }
+
+    /// 乘除层：`*` `/` `%`（取模）与 `of`（`20% of 340` = 68）。
+    /// 返回：(值, 是否为单独的百分比项)。
+    fn parse_term(&mut self) -> Result<(BigDecimal, bool), String> {
+        let (mut left, mut is_percent) = self.parse_unary()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('*') | Some('×') => {
+                    self.advance();
+                    left = bounded(left * self.parse_unary()?.0)?;
+                }
+                Some('/') | Some('÷') => {
+                    self.advance();
+                    let rhs = self.parse_unary()?.0;
+                    if rhs.is_zero() {
+                        return Err("Division by zero".to_string());
+                    }
+                    left = bounded(left / rhs)?;
+                }
+                Some('%') => {
+                    self.advance();
+                    let rhs = self.parse_unary()?.0;
+                    if rhs.is_zero() {
+                        return Err("Modulo by zero".to_string());
+                    }
+                    left = bounded(left % rhs)?;
+                }
+                _ if self.at_keyword("of") => {
+                    self.pos += 2;
+                    left = bounded(left * self.parse_unary()?.0)?;
+                }
+                _ => break,
+            }
+            is_percent = false;
+        }
+        Ok((left, is_percent))
+    }
+
+    /// 一元正负号（优先级低于幂：`-2^2` = -4）。
+    fn parse_unary(&mut self) -> Result<(BigDecimal, bool), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('-') | Some('−') => {
+                self.advance();
+                let (value, is_percent) = self.parse_unary()?;
+                Ok((-value, is_percent))
+            }
+            Some('+') => {
+                self.advance();
+                self.parse_unary()
+            }
+            _ => self.parse_power(),
+        }
+    }
+
+    /// 幂运算（右结合）。
+    fn parse_power(&mut self) -> Result<(BigDecimal, bool), String> {
+        let (base, is_percent) = self.parse_postfix()?;
+        self.skip_whitespace();
+        if self.peek() == Some('^') || (self.peek() == Some('*') && self.peek_at(1) == Some('*')) {
+            self.pos += if self.peek() == Some('^') { 1 } else { 2 };
+            let exponent = self.parse_unary()?.0;
+            return Ok((bounded(power(&base, &exponent)?)?, false));
+        }
+        Ok((base, is_percent))
+    }
+
+    /// 后缀百分号：`%` 后不接操作数时为百分比（除以 100），否则留给乘除层作取模。
+    fn parse_postfix(&mut self) -> Result<(BigDecimal, bool), String> {
+        let value = self.parse_primary()?;
+        self.skip_whitespace();
+        if self.peek() != Some('%') {
+            return Ok((value, false));
+        }
+        let start = self.pos;
+        self.advance();
+        self.skip_whitespace();
+        let operand_follows = match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' || c == '(' => true,
+            Some(_) => self.peek_ident().is_some_and(|w| {
+                !["of", "to", "in", "as"]
+                    .iter()
+                    .any(|k| k.eq_ignore_ascii_case(&w))
+            }),
+            None => false,
+        };
+        if operand_follows {
+            self.pos = start;
+            return Ok((value, false));
+        }
+        Ok((bounded(value / BigDecimal::from(100))?, true))
+    }
+
+    /// 原子：数字、括号、函数调用、常量、ans 与变量。
+    fn parse_primary(&mut self) -> Result<BigDecimal, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.advance();
+                // 括号内恢复千位分隔符语义（如 `max((1,000), 2)` 中的内层括号）
+                let saved_depth = std::mem::replace(&mut self.arg_depth, 0);
+                let result = self.parse_expr();
+                self.arg_depth = saved_depth;
+                let result = result?;
+                self.skip_whitespace();
+                match self.advance() {
+                    Some(')') => Ok(result),
+                    _ => Err("Missing closing ')'".to_string()),
+                }
+            }
+            Some('π') => {
+                self.advance();
+                Ok(constant(PI))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.parse_number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.parse_identifier(),
+            Some(c) => Err(format!("Unexpected character '{}'", c)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    /// 解析标识符：后跟 `(` 为函数调用，否则为常量 / ans / 变量。
+    fn parse_identifier(&mut self) -> Result<BigDecimal, String> {
+        let name = self.peek_ident().unwrap_or_default();
+        self.pos += name.chars().count();
+        let lower = name.to_ascii_lowercase();
+
+        let call_start = self.pos;
+        self.skip_whitespace();
+        if self.peek() == Some('(') && FUNCTIONS.contains(&lower.as_str()) {
+            self.advance();
+            let args = self.parse_arguments()?;
+            return bounded(self.call_function(&lower, &args)?);
+        }
+        self.pos = call_start;
+
+        match lower.as_str() {
+            "pi" => Ok(constant(PI)),
+            "e" => Ok(constant(E)),
+            "ans" => self
+                .session
+                .ans
+                .clone()
+                .ok_or_else(|| "尚无上一次结果 (ans)".to_string()),
+            _ => self
+                .session
+                .variables
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| format!("未定义的变量: {}", name)),
+        }
+    }
+
+    /// 解析函数实参列表（已消费左括号），逗号分隔。
+    fn parse_arguments(&mut self) -> Result<Vec<BigDecimal>, String> {
+        let mut args = Vec::new();
+        self.arg_depth += 1;
+        let result = loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') && args.is_empty() {
+                self.advance();
+                break Ok(());
+            }
+            match self.parse_expr() {
+                Ok(value) => args.push(value),
+                Err(e) => break Err(e),
+            }
+            self.skip_whitespace();
+            match self.advance() {
+                Some(',') => continue,
+                Some(')') => break Ok(()),
+                _ => break Err("Missing closing ')'".to_string()),
+            }
+        };
+        self.arg_depth -= 1;
+        result.map(|_| args)
+    }
+
+    /// 调用内置函数。
+    fn call_function(&self, name: &str, args: &[BigDecimal]) -> Result<BigDecimal, String> {
+        let arity_error = || format!("函数 {} 的参数个数不正确", name);
+        let single = || match args {
+            [x] => Ok(x),
+            _ => Err(arity_error()),
+        };
+        match name {
+            "sin" | "cos" | "tan" => {
+                let mut x = to_f64(single()?)?;
+                if self.angle == AngleUnit::Degrees {
+                    x = x.to_radians();
+                }
+                let result = match name {
+                    "sin" => x.sin(),
+                    "cos" => x.cos(),
+                    _ => x.tan(),
+                };
+                from_f64(result)
+            }
+            "ln" => {
+                let x = single()?;
+                if !x.is_positive() {
+                    return Err("对数的真数必须为正数".to_string());
+                }
+                from_f64(to_f64(x)?.ln())
+            }
+            "log" => {
+                let (x, base) = match args {
+                    [x] => (x, 10.0),
+                    [x, base] => (x, to_f64(base)?),
+                    _ => return Err(arity_error()),
+                };
+                if !x.is_positive() || base <= 0.0 || base == 1.0 {
+                    return Err("对数的真数必须为正数且底数为不等于 1 的正数".to_string());
+                }
+                let x = to_f64(x)?;
+                from_f64(if base == 10.0 { x.log10() } else { x.log(base) })
+            }
+            "sqrt" => single()?.sqrt().ok_or_else(|| "负数不能开平方".to_string()),
+            "abs" => Ok(single()?.abs()),
+            "round" => {
+                let (x, digits) = match args {
+                    [x] => (x, 0),
+                    [x, digits] => (
+                        x,
+                        digits
+                            .to_i64()
+                            .filter(|_| digits.is_integer())
+                            .ok_or_else(|| "round 的位数必须为整数".to_string())?,
+                    ),
+                    _ => return Err(arity_error()),
+                };
+                // 操作数本身受位数上限约束，超出范围的位数与边界值舍入结果相同
+                let digits = digits.clamp(-MAX_INTEGER_DIGITS, MAX_FRACTION_DIGITS);
+                Ok(x.with_scale_round(digits, RoundingMode::HalfUp))
+            }
+            "floor" => Ok(single()?.with_scale_round(0, RoundingMode::Floor)),
+            "ceil" => Ok(single()?.with_scale_round(0, RoundingMode::Ceiling)),
+            "min" => args.iter().min().cloned().ok_or_else(arity_error),
+            "max" => args.iter().max().cloned().ok_or_else(arity_error),
+            _ => Err(format!("未知函数: {}", name)),
+        }
+    }
+
+    /// 解析数字字面量：十进制（支持 `,` 千位分隔与 `_` 分隔、科学计数法）
+    /// 或 `0x` / `0b` / `0o` 前缀的整数。
+    fn parse_number(&mut self) -> Result<BigDecimal, String> {
+        if self.peek() == Some('0') {
+            let radix = match self.peek_at(1).map(|c| c.to_ascii_lowercase()) {
+                Some('x') => Some(16),
+                Some('b') => Some(2),
+                Some('o') => Some(8),
+                _ => None,
+            };
+            if let Some(radix) = radix {
+                if self.peek_at(2).is_some_and(|c| c.is_digit(radix)) {
+                    self.pos += 2;
+                    return self.parse_radix_integer(radix);
+                }
+            }
+        }
+
+        let mut text = String::new();
+        let mut has_dot = false;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() {
+                text.push(c);
+            } else if c == '.' && !has_dot {
+                has_dot = true;
+                text.push(c);
+            } else if c == '_' && self.peek_at(1).is_some_and(|n| n.is_ascii_digit()) {
+                // 数字分隔符：跳过
+            } else if c == ',' && !has_dot && self.arg_depth == 0 && self.is_thousands_group() {
+                // 千位分隔符：跳过
+            } else {
+                break;
+            }
+            self.advance();
+        }
+
+        // 科学计数法：e 后紧跟（带符号的）数字
+        if matches!(self.peek(), Some('e') | Some('E')) {
+            let sign_len = usize::from(matches!(self.peek_at(1), Some('+') | Some('-')));
+            if self
+                .peek_at(1 + sign_len)
+                .is_some_and(|c| c.is_ascii_digit())
+            {
+                text.push('e');
+                self.advance();
+                while let Some(c) = self.peek() {
+                    if c.is_ascii_digit() || (c == '+' || c == '-') && text.ends_with('e') {
+                        text.push(c);
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+        bounded(BigDecimal::from_str(&text).map_err(|_| format!("Invalid number: {}", text))?)
+    }
+
+    /// 当前逗号之后是否恰好为三位数字（千位分组），用于区分千位分隔符。
+    fn is_thousands_group(&self) -> bool {
+        (1..=3).all(|i| self.peek_at(i).is_some_and(|c| c.is_ascii_digit()))
+            && !self.peek_at(4).is_some_and(|c| c.is_ascii_digit())
+    }
+
+    /// 解析指定进制的整数（前缀已消费），允许 `_` 分隔。
+    fn parse_radix_integer(&mut self, radix: u32) -> Result<BigDecimal, String> {
+        let mut digits = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_digit(radix) {
+                digits.push(c);
+            } else if c != '_' {
+                break;
+            }
+            self.advance();
+        }
+        if self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("Invalid number: 非法的 {} 进制数字", radix));
+        }
+        bounded(
+            BigInt::from_str_radix(&digits, radix)
+                .map(BigDecimal::from)
+                .map_err(|_| format!("Invalid number: {}", digits))?,
+        )
+    }
+}
+
+/// 约束数值规模：整数部分超出位数上限时拒绝，小数部分超出上限时四舍五入。
+/// 字面量、每步运算与函数结果都经此检查，科学计数法（`1e999999999`）也按展开后的位数计。
+fn bounded(value: BigDecimal) -> Result<BigDecimal, String> {
+    if value.is_zero() {
+        return Ok(value);
+    }
+    if value.order_of_magnitude() >= MAX_INTEGER_DIGITS {
+        return Err(format!(
+            "结果超出计算范围（整数部分最多 {} 位）",
+            MAX_INTEGER_DIGITS
+        ));
+    }
+    if value.fractional_digit_count() > MAX_FRACTION_DIGITS {
+        return Ok(value.with_scale_round(MAX_FRACTION_DIGITS, RoundingMode::HalfUp));
+    }
+    Ok(value)
+}
+
+/// 解析常量字符串。
+fn constant(text: &str) -> BigDecimal {
+    BigDecimal::from_str(text).expect("常量必须是合法十进制数")
+}
+
+/// 转换为 f64（超越函数经浮点计算）。
+fn to_f64(value: &BigDecimal) -> Result<f64, String> {
+    value
+        .to_f64()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| "数值超出浮点计算范围".to_string())
+}
+
+/// 浮点结果转回 BigDecimal：经最短十进制表示转换，避免引入二进制尾数噪声。
+fn from_f64(value: f64) -> Result<BigDecimal, String> {
+    if !value.is_finite() {
+        return Err("结果不是有限数值".to_string());
+    }
+    BigDecimal::from_str(&value.to_string())
+        .ok()
+        .or_else(|| BigDecimal::from_f64(value))
+        .ok_or_else(|| "结果不是有限数值".to_string())
+}
+
+/// 幂运算：整数指数精确计算（负指数取倒数），其余经浮点计算。
+fn power(base: &BigDecimal, exponent: &BigDecimal) -> Result<BigDecimal, String> {
+    if exponent.is_integer() {
+        if let Some(n) = exponent.to_i64().filter(|n| n.abs() <= MAX_EXACT_EXPONENT) {
+            if n < 0 && base.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+            let result = base.powi(n.abs());
+            return Ok(if n < 0 {
+                BigDecimal::one() / result
+            } else {
+                result
+            });
+        }
+    }
+    from_f64(to_f64(base)?.powf(to_f64(exponent)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以空会话、弧度制求值，返回保留 10 位小数的规范化十进制字符串。
+    fn eval(input: &str) -> String {
+        eval_in(input, &Session::default())
+    }
+
+    /// 在给定会话中求值并格式化。
+    fn eval_in(input: &str, session: &Session) -> String {
+        let result = evaluate(input, session, AngleUnit::Radians).unwrap();
+        result.value.round(10).normalized().to_plain_string()
+    }
+
+    /// 四则运算、优先级、幂与任意精度小数。
+    #[test]
+    fn evaluates_arithmetic_exactly() {
+        assert_eq!(eval("1 + 2 * 3"), "7");
+        assert_eq!(eval("(1 + 2) * 3"), "9");
+        assert_eq!(eval("-2^2"), "-4");
+        assert_eq!(eval("2^3^2"), "512");
+        assert_eq!(eval("0.1 + 0.2"), "0.3");
+        assert_eq!(eval("7 % 3"), "1");
+        assert_eq!(eval("2^-2"), "0.25");
+        assert_eq!(
+            evaluate("2^100", &Session::default(), AngleUnit::Radians)
+                .unwrap()
+                .value
+                .to_string(),
+            "1267650600228229401496703205376"
+        );
+        assert!(evaluate("1 / 0", &Session::default(), AngleUnit::Radians).is_err());
+    }
+
+    /// 函数、常量、进制字面量与千位分隔符。
+    #[test]
+    fn evaluates_functions_constants_and_literals() {
+        assert_eq!(eval("sqrt(16) + abs(-3)"), "7");
+        assert_eq!(eval("max(1, 5, 3) - min(4, 2)"), "3");
+        assert_eq!(eval("round(2.567, 2)"), "2.57");
+        assert_eq!(eval("log(1000)"), "3");
+        assert_eq!(eval("log(8, 2)"), "3");
+        assert_eq!(eval("round(pi, 4)"), "3.1416");
+        assert_eq!(eval("ln(e)"), "1");
+        assert_eq!(eval("0xff + 0b101 + 0o17"), "275");
+        assert_eq!(eval("1,234,567 + 1_000"), "1235567");
+        assert_eq!(eval("max(1,000, 2)"), "2");
+        assert_eq!(eval("1.5e3"), "1500");
+        let degrees = evaluate("sin(30)", &Session::default(), AngleUnit::Degrees).unwrap();
+        assert_eq!(
+            degrees.value.round(10).normalized().to_plain_string(),
+            "0.5"
+        );
+    }
+
+    /// 百分比：`X% of Y`、相对增减与取模的区分。
+    #[test]
+    fn evaluates_percentages() {
+        assert_eq!(eval("20% of 340"), "68");
+        assert_eq!(eval("200 + 10%"), "220");
+        assert_eq!(eval("200 - 25%"), "150");
+        assert_eq!(eval("50%"), "0.5");
+        assert_eq!(eval("10 % 4"), "2");
+        let percent = evaluate("0.25 to %", &Session::default(), AngleUnit::Radians).unwrap();
+        assert_eq!(percent.presentation, Presentation::Percent);
+    }
+
+    /// ans、变量引用与赋值语句；保留名称不可赋值。
+    #[test]
+    fn resolves_session_state() {
+        let session = Session {
+            ans: Some(BigDecimal::from(10)),
+            variables: HashMap::from([("rate".to_string(), BigDecimal::from(3))]),
+        };
+        assert_eq!(eval_in("ans * rate", &session), "30");
+        let assignment = evaluate("total = ans + 1", &session, AngleUnit::Radians).unwrap();
+        assert_eq!(assignment.assign_to.as_deref(), Some("total"));
+        assert_eq!(assignment.value, BigDecimal::from(11));
+        assert!(evaluate("pi = 3", &session, AngleUnit::Radians).is_err());
+        assert!(evaluate("sqrt = 3", &session, AngleUnit::Radians).is_err());
+        assert!(evaluate("unknown + 1", &session, AngleUnit::Radians).is_err());
+        assert!(evaluate("ans", &Session::default(), AngleUnit::Radians).is_err());
+    }
+
+    /// 超出位数上限的结果被拒绝（不会生成数十亿位的整数），舍入位数被钳制。
+    #[test]
+    fn rejects_oversized_results() {
+        let session = Session::default();
+        let eval_err = |input: &str| evaluate(input, &session, AngleUnit::Radians).is_err();
+        assert!(eval_err("((9^1024)^1024)^1024"));
+        assert!(eval_err("10^1000"));
+        assert!(eval_err("1e999999999 + 1"));
+        assert!(eval_err("1e500 * 1e500"));
+        assert!(eval_err("10^999 * 10"));
+        assert_eq!(eval("10^998 / 10^998"), "1");
+        assert_eq!(eval("round(1.25, 999999999999)"), "1.25");
+        assert_eq!(eval("round(1.25, -999999999999)"), "0");
+        let tiny = evaluate("1e-5000", &session, AngleUnit::Radians).unwrap();
+        assert!(tiny.value.is_zero());
+        let third = evaluate("1 / 3", &session, AngleUnit::Radians).unwrap();
+        assert!(third.value.fractional_digit_count() <= MAX_FRACTION_DIGITS);
+    }
+
+    /// 单位数量、单位换算与进制换算目标。
+    #[test]
+    fn parses_conversion_targets() {
+        let session = Session::default();
+        let miles = evaluate("5 km to mi", &session, AngleUnit::Radians).unwrap();
+        assert_eq!(miles.unit.map(|u| u.symbol), Some("mi"));
+        assert_eq!(miles.value.round(4).to_string(), "3.1069");
+        let inches = evaluate("2 in in cm", &session, AngleUnit::Radians).unwrap();
+        assert_eq!(inches.value.normalized().to_string(), "5.08");
+        let quantity = evaluate("(1 + 2) kg", &session, AngleUnit::Radians).unwrap();
+        assert_eq!(quantity.unit.map(|u| u.symbol), Some("kg"));
+        let hex = evaluate("255 to hex", &session, AngleUnit::Radians).unwrap();
+        assert_eq!(hex.presentation, Presentation::Radix(Radix::Hex));
+        assert!(evaluate("5 to km", &session, AngleUnit::Radians).is_err());
+        assert!(evaluate("5 kg to mi", &session, AngleUnit::Radians).is_err());
+        assert!(evaluate("1 + 2 foo bar baz qux", &session, AngleUnit::Radians).is_err());
+    }
+}
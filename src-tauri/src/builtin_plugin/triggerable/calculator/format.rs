@@ -0,0 +1,184 @@
+use bigdecimal::num_bigint::Sign;
+use bigdecimal::{BigDecimal, RoundingMode};
+use serde::Serialize;
+
+use super::evaluator::{Evaluation, Presentation, Radix};
+
+/// 附加进制表示的位数上限：超出时不再给出二进制等冗长表示。
+const MAX_RADIX_BITS: u64 = 128;
+/// 主结果的表示 ID（对应默认动作 copy_result）。
+pub(super) const PRIMARY_ID: &str = "result";
+
+/// 结果的一种文本表示（面板逐条展示，每条对应一个复制动作）。
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct Representation {
+    /// 表示 ID（result / value / decimal / grouped / hex / binary / octal）
+    #[serde(rename = "id")]
+    pub(super) id: &'static str,
+    /// 展示标签
+    #[serde(rename = "label")]
+    pub(super) label: &'static str,
+    /// 表示文本（即复制内容）
+    #[serde(rename = "text")]
+    pub(super) text: String,
+}
+
+/// 生成求值结果的全部文本表示，首项为主结果。
+/// 参数：evaluation - 求值结果；precision - 十进制小数位数上限。
+/// 返回：表示列表；显式请求的进制无法表示（非整数）时返回错误。
+pub(super) fn representations(
+    evaluation: &Evaluation,
+    precision: i64,
+) -> Result<Vec<Representation>, String> {
+    let value = &evaluation.value;
+    let decimal = format_decimal(value, precision);
+    let mut items = Vec::new();
+    let mut push = |id, label, text: String| {
+        if !items.iter().any(|r: &Representation| r.text == text) {
+            items.push(Representation { id, label, text });
+        }
+    };
+
+    let primary = match (evaluation.presentation, evaluation.unit) {
+        (Presentation::Radix(radix), _) => {
+            to_radix(value, radix).ok_or_else(|| "进制换算仅支持整数".to_string())?
+        }
+        (Presentation::Percent, _) => {
+            format!(
+                "{}%",
+                format_decimal(&(value * BigDecimal::from(100)), precision)
+            )
+        }
+        (Presentation::Decimal, Some(unit)) => format!("{} {}", decimal, unit.symbol),
+        (Presentation::Decimal, None) => decimal.clone(),
+    };
+    push(PRIMARY_ID, "结果", primary);
+    let (decimal_id, decimal_label) = if evaluation.unit.is_some() {
+        ("value", "数值")
+    } else {
+        ("decimal", "十进制")
+    };
+    push(decimal_id, decimal_label, decimal.clone());
+    push("grouped", "千分位", group_thousands(&decimal));
+    if evaluation.unit.is_none() {
+        for (id, label, radix) in [
+            ("hex", "十六进制", Radix::Hex),
+            ("binary", "二进制", Radix::Binary),
+            ("octal", "八进制", Radix::Octal),
+        ] {
+            if let Some(text) = to_radix(value, radix) {
+                push(id, label, text);
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// 按小数位数上限四舍五入并去除末尾零，输出非科学计数法的十进制字符串。
+pub(super) fn format_decimal(value: &BigDecimal, precision: i64) -> String {
+    value
+        .with_scale_round(precision, RoundingMode::HalfUp)
+        .normalized()
+        .to_plain_string()
+}
+
+/// 为十进制字符串的整数部分插入千位分隔符（`1234567.5` → `1,234,567.5`）。
+fn group_thousands(plain: &str) -> String {
+    let (sign, unsigned) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", plain),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let mut grouped = String::with_capacity(plain.len() + integer.len() / 3);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    match fraction {
+        Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+/// 将整数格式化为带前缀的指定进制（`0xFF` / `0b101` / `0o17`）。
+/// 非整数或超出位数上限时返回 None。
+fn to_radix(value: &BigDecimal, radix: Radix) -> Option<String> {
+    if !value.is_integer() {
+        return None;
+    }
+    let (integer, _) = value.with_scale(0).into_bigint_and_exponent();
+    if integer.bits() > MAX_RADIX_BITS {
+        return None;
+    }
+    let sign = if integer.sign() == Sign::Minus {
+        "-"
+    } else {
+        ""
+    };
+    let magnitude = integer.magnitude();
+    Some(match radix {
+        Radix::Hex => format!("{}0x{:X}", sign, magnitude),
+        Radix::Binary => format!("{}0b{:b}", sign, magnitude),
+        Radix::Octal => format!("{}0o{:o}", sign, magnitude),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::evaluator::{evaluate, AngleUnit, Session};
+    use super::*;
+
+    /// 求值并返回 (表示 ID, 文本) 列表。
+    fn render(input: &str) -> Vec<(&'static str, String)> {
+        let evaluation = evaluate(input, &Session::default(), AngleUnit::Radians).unwrap();
+        representations(&evaluation, 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.id, r.text))
+            .collect()
+    }
+
+    /// 整数结果附带千分位与各进制表示；重复文本去重。
+    #[test]
+    fn integer_results_include_grouping_and_radixes() {
+        assert_eq!(
+            render("1234567"),
+            vec![
+                ("result", "1234567".to_string()),
+                ("grouped", "1,234,567".to_string()),
+                ("hex", "0x12D687".to_string()),
+                ("binary", "0b100101101011010000111".to_string()),
+                ("octal", "0o4553207".to_string()),
+            ]
+        );
+        assert_eq!(render("-255 to hex")[0], ("result", "-0xFF".to_string()));
+        assert_eq!(render("-255 to hex")[1], ("decimal", "-255".to_string()));
+    }
+
+    /// 小数按精度舍入；单位结果附带不含单位的数值；百分比展示。
+    #[test]
+    fn formats_decimals_units_and_percentages() {
+        assert_eq!(
+            render("1 / 3"),
+            vec![("result", "0.3333333333".to_string())]
+        );
+        assert_eq!(
+            render("1 mi to m"),
+            vec![
+                ("result", "1609.344 m".to_string()),
+                ("value", "1609.344".to_string()),
+                ("grouped", "1,609.344".to_string()),
+            ]
+        );
+        assert_eq!(render("0.125 as %")[0], ("result", "12.5%".to_string()));
+        assert_eq!(group_thousands("-1234.5678"), "-1,234.5678");
+        assert_eq!(group_thousands("123"), "123");
+        let evaluation = evaluate("1.5 to bin", &Session::default(), AngleUnit::Radians).unwrap();
+        assert!(representations(&evaluation, 10).is_err());
+    }
+}
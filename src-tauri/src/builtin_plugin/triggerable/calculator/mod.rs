@@ -0,0 +1,6 @@
+mod evaluator;
+mod format;
+mod plugin;
+mod units;
+
+pub use plugin::CalculatorPlugin;
@@ -0,0 +1,612 @@
+use crate::core::config::setting_builders::SchemaBuilder;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::SettingDefinition;
+use zerolaunch_plugin_api::config::{ComponentCore, ComponentType, ConfigError, Configurable};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::IconRequest;
+use zerolaunch_plugin_api::{
+    ListItem, PanelInteraction, PanelKeyAction, PanelKeyBinding, Plugin, PluginContext,
+    PluginError, PluginKind, PluginMetadata, PluginMode, Query, QueryChannel, QueryResponse,
+    ResultAction,
+};
+
+use super::evaluator::{self, AngleUnit, Session};
+use super::format::{self, Representation, PRIMARY_ID};
+
+/// 全局查询贡献条目的分数：可求值的表达式几乎必然是计算意图，置于检索结果之前。
+const GLOBAL_RESULT_SCORE: f64 = 1.0e6;
+/// 复制动作 ID 前缀：`copy_<表示 ID>`，主结果即 `copy_result`。
+const COPY_ACTION_PREFIX: &str = "copy_";
+/// 角度单位选项：弧度。
+const ANGLE_UNIT_RADIANS: &str = "radians";
+/// 角度单位选项：角度。
+const ANGLE_UNIT_DEGREES: &str = "degrees";
+/// 小数位数上限（设置值超出时截断）。
+const MAX_PRECISION: u32 = 100;
+
+pub struct CalculatorPlugin {
+    core: ComponentCore,
+    metadata: PluginMetadata,
+    inner: RwLock<CalculatorSettings>,
+    /// 最近一次 GUI 查询的求值结果，供 execute_action 复制并提交到会话状态。
+    pending: RwLock<Option<PendingResult>>,
+    /// 会话状态（ans 与用户变量）：仅在确认（复制）时提交，逐键查询不会改写。
+    session: RwLock<Session>,
+    /// PluginHandle（init 时发放），供 execute_action 经句柄访问平台能力。
+    handle: RwLock<Option<Arc<PluginHandle>>>,
+}
+
+/// 计算器插件的强类型配置结构。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CalculatorSettings {
+    /// 十进制结果保留的最大小数位数（末尾零自动去除）
+    #[serde(rename = "precision", default = "default_precision")]
+    precision: u32,
+    /// 三角函数的角度单位（radians / degrees）
+    #[serde(rename = "angle_unit", default = "default_angle_unit")]
+    angle_unit: String,
+}
+
+impl Default for CalculatorSettings {
+    fn default() -> Self {
+        Self {
+            precision: default_precision(),
+            angle_unit: default_angle_unit(),
+        }
+    }
+}
+
+fn default_precision() -> u32 {
+    10
+}
+
+fn default_angle_unit() -> String {
+    ANGLE_UNIT_RADIANS.to_string()
+}
+
+/// 待提交的求值结果：查询时生成，确认（复制）时写入剪贴板并提交到会话状态。
+#[derive(Debug, Clone)]
+struct PendingResult {
+    /// 结果的全部文本表示（首项为主结果）
+    representations: Vec<Representation>,
+    /// 结果数值（提交后成为 ans）
+    value: BigDecimal,
+    /// 赋值语句的变量名（提交后写入会话变量）
+    assign_to: Option<String>,
+}
+
+impl Default for CalculatorPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalculatorPlugin {
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                "calculator".to_string(),
+                t_key!("calculator", "name").to_string(),
+                t_key!("calculator", "description").to_string(),
+                ComponentType::Plugin,
+                0,
+            ),
+            metadata: PluginMetadata {
+                id: "calculator".to_string(),
+                // name/description 与 ComponentCore 同用 i18n key（key-or-literal），
+                // 消除插件级与组件级元数据的双源硬编码
+                name: t_key!("calculator", "name").to_string(),
+                // 内置插件无独立版本/作者（随应用分发），UI 按内置标识展示
+                version: String::new(),
+                description: t_key!("calculator", "description").to_string(),
+                author: String::new(),
+                trigger_keywords: vec!["=".to_string()],
+                supported_os: vec![
+                    "windows".to_string(),
+                    "macos".to_string(),
+                    "linux".to_string(),
+                ],
+                priority: 100,
+                kind: PluginKind::Builtin,
+                // 行内插件：仅关键词（=）唤醒，无全局热键，不展示图标
+                hotkey: None,
+                icon: None,
+                mode: PluginMode::Inline,
+                // 参与全局查询：默认搜索输入可求值的表达式时直接给出计算结果
+                global_query: true,
+            },
+            inner: RwLock::new(CalculatorSettings::default()),
+            pending: RwLock::new(None),
+            session: RwLock::new(Session::default()),
+            handle: RwLock::new(None),
+        }
+    }
+
+    /// 按当前设置对语句求值，生成全部文本表示。
+    /// 错误时返回描述性字符串。
+    fn evaluate(&self, input: &str) -> Result<PendingResult, String> {
+        let (precision, angle) = {
+            let settings = self.inner.read();
+            let angle = if settings.angle_unit == ANGLE_UNIT_DEGREES {
+                AngleUnit::Degrees
+            } else {
+                AngleUnit::Radians
+            };
+            (settings.precision.min(MAX_PRECISION), angle)
+        };
+        let evaluation = evaluator::evaluate(input, &self.session.read(), angle)?;
+        let representations = format::representations(&evaluation, i64::from(precision))?;
+        Ok(PendingResult {
+            representations,
+            value: evaluation.value,
+            assign_to: evaluation.assign_to,
+        })
+    }
+
+    /// 全局查询的表达式判定：含运算符或括号（首字符的正负号不算），
+    /// 或以数字开头且含换算关键字（`5 km to mi`、`20% of 340`）/ 进制前缀（`0xff`），
+    /// 避免纯数字输入（如搜索 "7"）也被当作计算。
+    fn looks_like_expression(expr: &str) -> bool {
+        let lower = expr.to_ascii_lowercase();
+        let numeric_start = lower.starts_with(|c: char| c.is_ascii_digit() || c == '.');
+        let has_keyword = [" to ", " in ", " as ", " of "]
+            .iter()
+            .any(|k| lower.contains(k));
+        let has_radix_prefix = ["0x", "0b", "0o"].iter().any(|p| lower.starts_with(p));
+        expr.chars()
+            .skip(1)
+            .any(|c| matches!(c, '+' | '-' | '*' | '/' | '%' | '^' | '('))
+            || expr.starts_with('(')
+            || (numeric_start && (has_keyword || has_radix_prefix))
+    }
+
+    /// 复制动作（计算面板与全局查询条目共用）：每种表示一个，主结果为默认动作。
+    fn copy_actions(representations: &[Representation]) -> Vec<ResultAction> {
+        representations
+            .iter()
+            .map(|r| {
+                let is_primary = r.id == PRIMARY_ID;
+                ResultAction {
+                    id: format!("{}{}", COPY_ACTION_PREFIX, r.id),
+                    label: format!("复制{}", r.label),
+                    icon: IconRequest::Path("copy".to_string()),
+                    is_default: is_primary,
+                    shortcut_key: if is_primary {
+                        "Enter".to_string()
+                    } else {
+                        String::new()
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// 缓存求值结果，供 execute_action 复制。
+    /// 仅 GUI 通道且查询仍最新可写入：CLI/调试查询为只读辅助路径，
+    /// 不得改写 GUI 剪贴板缓存（复制动作无通道区分）。
+    fn remember(&self, ctx: &PluginContext, pending: &PendingResult) {
+        if ctx.is_query_current() && ctx.query_channel == QueryChannel::Ui {
+            *self.pending.write() = Some(pending.clone());
+        }
+    }
+
+    /// 提交结果到会话状态：结果成为 ans，赋值语句写入变量。
+    fn commit(&self, pending: &PendingResult) {
+        let mut session = self.session.write();
+        session.ans = Some(pending.value.clone());
+        if let Some(name) = &pending.assign_to {
+            session
+                .variables
+                .insert(name.clone(), pending.value.clone());
+        }
+    }
+
+    /// 会话变量快照（按名称排序），供空面板展示。
+    fn variables_snapshot(&self) -> serde_json::Value {
+        let precision = i64::from(self.inner.read().precision.min(MAX_PRECISION));
+        let session = self.session.read();
+        let mut variables: Vec<(&String, &BigDecimal)> = session.variables.iter().collect();
+        variables.sort_by(|a, b| a.0.cmp(b.0));
+        json!({
+            "ans": session.ans.as_ref().map(|v| format::format_decimal(v, precision)),
+            "variables": variables
+                .into_iter()
+                .map(|(name, value)| json!({
+                    "name": name,
+                    "value": format::format_decimal(value, precision),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+// ---- Configurable impl ----
+
+#[async_trait]
+impl Configurable for CalculatorPlugin {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::integer(
+                "precision",
+                t_key!("calculator", "fields.precision.label"),
+                t_key!("calculator", "fields.precision.desc"),
+            )
+            .group(t_key!("calculator", "groups.basic"))
+            .order(0)
+            .default(default_precision())
+            .min(0.0)
+            .max(f64::from(MAX_PRECISION))
+            .build(),
+            SchemaBuilder::select(
+                "angle_unit",
+                t_key!("calculator", "fields.angle_unit.label"),
+                t_key!("calculator", "fields.angle_unit.desc"),
+            )
+            .options_with_labels(&[
+                (
+                    ANGLE_UNIT_RADIANS,
+                    t_key!("calculator", "options.angle_unit.radians"),
+                ),
+                (
+                    ANGLE_UNIT_DEGREES,
+                    t_key!("calculator", "options.angle_unit.degrees"),
+                ),
+            ])
+            .group(t_key!("calculator", "groups.basic"))
+            .order(1)
+            .default(ANGLE_UNIT_RADIANS)
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.inner.read().clone()).unwrap_or_default()
+    }
+
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let parsed: CalculatorSettings = serde_json::from_value(settings).unwrap_or_default();
+        *self.inner.write() = parsed;
+        Ok(())
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+// ---- Plugin impl ----
+
+#[async_trait]
+impl Plugin for CalculatorPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    /// CalculatorPlugin 无需异步初始化，所有状态在构造时已就绪；
+    /// 仅保存 init 发放的服务句柄，供 execute_action 访问平台能力。
+    async fn init(
+        &self,
+        _ctx: &PluginContext,
+        handle: Option<Arc<PluginHandle>>,
+    ) -> Result<(), PluginError> {
+        *self.handle.write() = handle;
+        Ok(())
+    }
+
+    fn interaction_policy(&self) -> PanelInteraction {
+        PanelInteraction {
+            // 计算面板为行内形态（保留搜索栏），按键声明即接管：
+            // - Enter：Confirm —— 宿主三分支：面板有可执行动作（计算成功，actions=[复制各表示]）
+            //   时执行默认动作（复制主结果并提交 ans/变量）；无动作（空/错误面板）时发起确认查询（重新计算）；
+            // - Escape：GoBack —— 退出计算面板（返回默认搜索）。
+            bindings: vec![
+                PanelKeyBinding {
+                    key: "Enter".to_string(),
+                    action: PanelKeyAction::Confirm,
+                },
+                PanelKeyBinding {
+                    key: "Escape".to_string(),
+                    action: PanelKeyAction::GoBack,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    async fn query(
+        &self,
+        ctx: &PluginContext,
+        query: &Query,
+    ) -> Result<QueryResponse, PluginError> {
+        let expr = query.search_term.trim().to_string();
+
+        if expr.is_empty() {
+            return Ok(QueryResponse::CustomPanel {
+                panel_type: "calculator".to_string(),
+                data: json!({
+                    "expression": "",
+                    "result": null,
+                    "session": self.variables_snapshot(),
+                }),
+                actions: vec![],
+                keep_search_bar: true,
+            });
+        }
+
+        match self.evaluate(&expr) {
+            Ok(pending) => {
+                self.remember(ctx, &pending);
+                Ok(QueryResponse::CustomPanel {
+                    panel_type: "calculator".to_string(),
+                    data: json!({
+                        "expression": expr,
+                        "result": pending.representations[0].text,
+                        "rawValue": pending.value.to_f64(),
+                        "assignTo": pending.assign_to,
+                        "representations": pending.representations,
+                    }),
+                    actions: Self::copy_actions(&pending.representations),
+                    keep_search_bar: true,
+                })
+            }
+            Err(error) => Ok(QueryResponse::CustomPanel {
+                panel_type: "calculator".to_string(),
+                data: json!({
+                    "expression": expr,
+                    "result": null,
+                    "error": error,
+                }),
+                actions: vec![],
+                keep_search_bar: true,
+            }),
+        }
+    }
+
+    /// 默认搜索中输入可求值的表达式时贡献一条计算结果（无触发词也能直接计算）。
+    /// 求值失败、非表达式输入或赋值语句（变量仅在计算面板中定义）不贡献条目。
+    async fn global_query(
+        &self,
+        ctx: &PluginContext,
+        query: &Query,
+    ) -> Result<Vec<ListItem>, PluginError> {
+        let expr = query.search_term.trim();
+        if !Self::looks_like_expression(expr) {
+            return Ok(Vec::new());
+        }
+        let Ok(pending) = self.evaluate(expr) else {
+            return Ok(Vec::new());
+        };
+        if pending.assign_to.is_some() {
+            return Ok(Vec::new());
+        }
+
+        // 与计算面板同一缓存：选中条目时复制动作取此结果。
+        self.remember(ctx, &pending);
+
+        Ok(vec![ListItem {
+            id: 0,
+            title: pending.representations[0].text.clone(),
+            subtitle: format!("{} =", expr),
+            icon: IconRequest::Path(String::new()),
+            score: GLOBAL_RESULT_SCORE,
+            actions: Self::copy_actions(&pending.representations),
+            target_type: "calculator".to_string(),
+            user_arg_count: 0,
+            has_system_params: false,
+            trigger_keywords: Vec::new(),
+        }])
+    }
+
+    /// 复制动作：将对应表示写入剪贴板，并把结果提交为 ans（赋值语句同时写入变量）。
+    async fn execute_action(
+        &self,
+        _ctx: &PluginContext,
+        action_id: &str,
+        _payload: serde_json::Value,
+    ) -> Result<(), PluginError> {
+        let Some(representation_id) = action_id.strip_prefix(COPY_ACTION_PREFIX) else {
+            return Err(PluginError::ActionFailed(format!(
+                "Unknown action: {}",
+                action_id
+            )));
+        };
+        let pending = self.pending.read().clone();
+        let Some(pending) = pending else {
+            return Ok(());
+        };
+        let text = pending
+            .representations
+            .iter()
+            .find(|r| r.id == representation_id)
+            .map(|r| r.text.clone())
+            .ok_or_else(|| PluginError::ActionFailed(format!("Unknown action: {}", action_id)))?;
+        // 经 PluginHandle 访问剪贴板能力（init 时发放）。
+        let handle = self
+            .handle
+            .read()
+            .clone()
+            .ok_or_else(|| PluginError::ActionFailed("插件服务句柄不可用".to_string()))?;
+        handle
+            .set_clipboard_text(&text)
+            .map_err(|e| PluginError::ActionFailed(format!("剪贴板写入失败: {}", e)))?;
+        self.commit(&pending);
+        Ok(())
+    }
+}
+
+use crate::plugin_framework::builtin_registry::PluginEntry;
+
+fn build_calculator_plugin() -> (Arc<dyn Configurable>, Arc<dyn Plugin>) {
+    let plugin: Arc<dyn Plugin> = Arc::new(CalculatorPlugin::new());
+    let configurable: Arc<dyn Configurable> = plugin.clone();
+    (configurable, plugin)
+}
+
+::inventory::submit! {
+    PluginEntry {
+        component_id: "calculator",
+        priority: 0,
+        factory: build_calculator_plugin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造仅含搜索词的查询。
+    fn query(term: &str) -> Query {
+        Query {
+            id: "test".to_string(),
+            raw_query: format!("= {}", term),
+            search_term: term.to_string(),
+            confirm: false,
+        }
+    }
+
+    /// 全局查询的表达式判定：纯数字与普通文本不视为计算。
+    #[test]
+    fn detects_expressions_for_global_query() {
+        for expr in ["1+2", "(3)", "2^10", "5 km to mi", "20% of 340", "0xff"] {
+            assert!(CalculatorPlugin::looks_like_expression(expr), "{}", expr);
+        }
+        for expr in ["7", "-3", "chrome", "to do", "notes 2024"] {
+            assert!(!CalculatorPlugin::looks_like_expression(expr), "{}", expr);
+        }
+    }
+
+    /// 全局查询贡献一条计算结果；非表达式、求值失败、超出范围与赋值语句不贡献条目。
+    #[tokio::test]
+    async fn global_query_contributes_single_result() {
+        let plugin = CalculatorPlugin::new();
+        let ctx = PluginContext::new("test");
+        let items = plugin
+            .global_query(&ctx, &query("1 + 2 * 3"))
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "7");
+        assert_eq!(items[0].subtitle, "1 + 2 * 3 =");
+        assert_eq!(items[0].score, GLOBAL_RESULT_SCORE);
+        assert!(items[0]
+            .actions
+            .iter()
+            .any(|a| a.id == "copy_result" && a.is_default));
+
+        for term in ["7", "1 +", "((9^1024)^1024)^1024", "x = 1 + 2"] {
+            let items = plugin.global_query(&ctx, &query(term)).await.unwrap();
+            assert!(items.is_empty(), "{}", term);
+        }
+    }
+
+    /// 计算面板：空输入展示会话变量，成功给出各表示与复制动作，失败给出错误。
+    #[tokio::test]
+    async fn query_builds_calculator_panel() {
+        let plugin = CalculatorPlugin::new();
+        let ctx = PluginContext::new("test");
+
+        let QueryResponse::CustomPanel { data, actions, .. } =
+            plugin.query(&ctx, &query("")).await.unwrap()
+        else {
+            panic!("期望 CustomPanel");
+        };
+        assert!(data["result"].is_null());
+        assert!(data["session"]["variables"].as_array().unwrap().is_empty());
+        assert!(actions.is_empty());
+
+        let QueryResponse::CustomPanel { data, actions, .. } =
+            plugin.query(&ctx, &query("255 to hex")).await.unwrap()
+        else {
+            panic!("期望 CustomPanel");
+        };
+        assert_eq!(data["result"], "0xFF");
+        assert!(actions.iter().any(|a| a.id == "copy_decimal"));
+
+        let QueryResponse::CustomPanel { data, actions, .. } =
+            plugin.query(&ctx, &query("10^1000")).await.unwrap()
+        else {
+            panic!("期望 CustomPanel");
+        };
+        assert!(data["result"].is_null());
+        assert!(data["error"].as_str().unwrap().contains("超出计算范围"));
+        assert!(actions.is_empty());
+    }
+
+    /// 设置的小数位数生效且不超过上限；角度单位切换三角函数的输入单位。
+    #[tokio::test]
+    async fn applies_precision_and_angle_settings() {
+        let plugin = CalculatorPlugin::new();
+        plugin
+            .apply_settings(json!({ "precision": 3, "angle_unit": ANGLE_UNIT_DEGREES }))
+            .await
+            .unwrap();
+        let pending = plugin.evaluate("2 / 3").unwrap();
+        assert_eq!(pending.representations[0].text, "0.667");
+        let pending = plugin.evaluate("sin(90)").unwrap();
+        assert_eq!(pending.representations[0].text, "1");
+
+        plugin
+            .apply_settings(json!({ "precision": 100000 }))
+            .await
+            .unwrap();
+        let pending = plugin.evaluate("1 / 3").unwrap();
+        let digits = pending.representations[0].text.len() - "0.".len();
+        assert_eq!(digits, MAX_PRECISION as usize);
+    }
+
+    /// 仅 GUI 通道缓存结果；提交后结果成为 ans，赋值语句写入变量。
+    #[tokio::test]
+    async fn commits_ans_and_variables_from_ui_results() {
+        let plugin = CalculatorPlugin::new();
+        let cli_ctx = PluginContext {
+            query_channel: QueryChannel::Cli,
+            ..PluginContext::new("test")
+        };
+        plugin.query(&cli_ctx, &query("x = 2 * 3")).await.unwrap();
+        assert!(plugin.pending.read().is_none(), "CLI 查询不得写入缓存");
+
+        let ctx = PluginContext::new("test");
+        plugin.query(&ctx, &query("x = 2 * 3")).await.unwrap();
+        let pending = plugin.pending.read().clone().unwrap();
+        assert_eq!(pending.assign_to.as_deref(), Some("x"));
+        plugin.commit(&pending);
+
+        assert_eq!(
+            plugin.evaluate("x + ans").unwrap().representations[0].text,
+            "12"
+        );
+        let snapshot = plugin.variables_snapshot();
+        assert_eq!(snapshot["ans"], "6");
+        assert_eq!(snapshot["variables"][0]["name"], "x");
+    }
+
+    /// 非复制动作与缺少服务句柄时执行失败。
+    #[tokio::test]
+    async fn execute_action_rejects_unknown_actions() {
+        let plugin = CalculatorPlugin::new();
+        let ctx = PluginContext::new("test");
+        assert!(plugin
+            .execute_action(&ctx, "open", serde_json::Value::Null)
+            .await
+            .is_err());
+
+        plugin.query(&ctx, &query("1 + 1")).await.unwrap();
+        assert!(plugin
+            .execute_action(&ctx, "copy_result", serde_json::Value::Null)
+            .await
+            .is_err());
+        assert!(
+            plugin.session.read().ans.is_none(),
+            "复制失败时不得提交结果"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+/// 物理量纲：仅同一量纲内的单位可以互相换算。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Data,
+    Volume,
+    Speed,
+    Temperature,
+}
+
+/// 单位定义：基准值 = (数值 + offset) × numerator / denominator。
+/// 系数以十进制字符串表示，保证换算全程在 BigDecimal 下精确进行。
+#[derive(Debug)]
+pub(super) struct Unit {
+    /// 结果展示用符号
+    pub(super) symbol: &'static str,
+    /// 可识别的名称（大小写不敏感匹配，不含 symbol 本身）
+    aliases: &'static [&'static str],
+    /// 所属量纲
+    pub(super) dimension: Dimension,
+    /// 换算到基准单位的系数分子
+    numerator: &'static str,
+    /// 换算到基准单位的系数分母（无法用有限小数表示的系数，如 km/h）
+    denominator: &'static str,
+    /// 乘系数前的偏移量（仅温度使用）
+    offset: &'static str,
+}
+
+/// 构造线性单位（无偏移、分母为 1）。
+const fn linear(
+    symbol: &'static str,
+    aliases: &'static [&'static str],
+    dimension: Dimension,
+    factor: &'static str,
+) -> Unit {
+    Unit {
+        symbol,
+        aliases,
+        dimension,
+        numerator: factor,
+        denominator: "1",
+        offset: "0",
+    }
+}
+
+/// 单位表。各量纲基准单位：m、kg、s、B、L、m/s、K。
+/// 十进制数据单位（KB/MB…）按 1000 进位，二进制单位（KiB/MiB…）按 1024 进位。
+static UNITS: &[Unit] = &[
+    // 长度
+    linear(
+        "mm",
+        &["millimeter", "millimeters"],
+        Dimension::Length,
+        "0.001",
+    ),
+    linear(
+        "cm",
+        &["centimeter", "centimeters"],
+        Dimension::Length,
+        "0.01",
+    ),
+    linear(
+        "m",
+        &["meter", "meters", "metre", "metres"],
+        Dimension::Length,
+        "1",
+    ),
+    linear(
+        "km",
+        &["kilometer", "kilometers", "kilometre", "kilometres"],
+        Dimension::Length,
+        "1000",
+    ),
+    linear("in", &["inch", "inches", "\""], Dimension::Length, "0.0254"),
+    linear("ft", &["foot", "feet", "'"], Dimension::Length, "0.3048"),
+    linear("yd", &["yard", "yards"], Dimension::Length, "0.9144"),
+    linear("mi", &["mile", "miles"], Dimension::Length, "1609.344"),
+    linear(
+        "nmi",
+        &["nautical-mile", "nautical-miles"],
+        Dimension::Length,
+        "1852",
+    ),
+    // 质量
+    linear(
+        "mg",
+        &["milligram", "milligrams"],
+        Dimension::Mass,
+        "0.000001",
+    ),
+    linear("g", &["gram", "grams"], Dimension::Mass, "0.001"),
+    linear(
+        "kg",
+        &["kilogram", "kilograms", "kilo", "kilos"],
+        Dimension::Mass,
+        "1",
+    ),
+    linear(
+        "t",
+        &["tonne", "tonnes", "ton", "tons"],
+        Dimension::Mass,
+        "1000",
+    ),
+    linear(
+        "oz",
+        &["ounce", "ounces"],
+        Dimension::Mass,
+        "0.028349523125",
+    ),
+    linear(
+        "lb",
+        &["lbs", "pound", "pounds"],
+        Dimension::Mass,
+        "0.45359237",
+    ),
+    linear("st", &["stone", "stones"], Dimension::Mass, "6.35029318"),
+    // 时间
+    linear(
+        "ms",
+        &["millisecond", "milliseconds"],
+        Dimension::Time,
+        "0.001",
+    ),
+    linear(
+        "s",
+        &["sec", "secs", "second", "seconds"],
+        Dimension::Time,
+        "1",
+    ),
+    linear("min", &["mins", "minute", "minutes"], Dimension::Time, "60"),
+    linear(
+        "h",
+        &["hr", "hrs", "hour", "hours"],
+        Dimension::Time,
+        "3600",
+    ),
+    linear("d", &["day", "days"], Dimension::Time, "86400"),
+    linear("wk", &["week", "weeks"], Dimension::Time, "604800"),
+    // 儒略年（365.25 天）
+    linear("yr", &["year", "years"], Dimension::Time, "31557600"),
+    // 数据
+    linear("bit", &["bits"], Dimension::Data, "0.125"),
+    linear("B", &["byte", "bytes"], Dimension::Data, "1"),
+    linear("KB", &["kilobyte", "kilobytes"], Dimension::Data, "1000"),
+    linear("MB", &["megabyte", "megabytes"], Dimension::Data, "1000000"),
+    linear(
+        "GB",
+        &["gigabyte", "gigabytes"],
+        Dimension::Data,
+        "1000000000",
+    ),
+    linear(
+        "TB",
+        &["terabyte", "terabytes"],
+        Dimension::Data,
+        "1000000000000",
+    ),
+    linear("KiB", &["kibibyte", "kibibytes"], Dimension::Data, "1024"),
+    linear(
+        "MiB",
+        &["mebibyte", "mebibytes"],
+        Dimension::Data,
+        "1048576",
+    ),
+    linear(
+        "GiB",
+        &["gibibyte", "gibibytes"],
+        Dimension::Data,
+        "1073741824",
+    ),
+    linear(
+        "TiB",
+        &["tebibyte", "tebibytes"],
+        Dimension::Data,
+        "1099511627776",
+    ),
+    // 体积
+    linear(
+        "ml",
+        &["milliliter", "milliliters", "millilitre", "millilitres"],
+        Dimension::Volume,
+        "0.001",
+    ),
+    linear(
+        "L",
+        &["liter", "liters", "litre", "litres"],
+        Dimension::Volume,
+        "1",
+    ),
+    linear(
+        "m3",
+        &["m³", "cubic-meter", "cubic-meters"],
+        Dimension::Volume,
+        "1000",
+    ),
+    linear(
+        "gal",
+        &["gallon", "gallons"],
+        Dimension::Volume,
+        "3.785411784",
+    ),
+    linear("qt", &["quart", "quarts"], Dimension::Volume, "0.946352946"),
+    linear("pt", &["pint", "pints"], Dimension::Volume, "0.473176473"),
+    linear("cup", &["cups"], Dimension::Volume, "0.2365882365"),
+    linear("floz", &["fl-oz"], Dimension::Volume, "0.0295735295625"),
+    // 速度
+    linear("m/s", &["mps"], Dimension::Speed, "1"),
+    Unit {
+        symbol: "km/h",
+        aliases: &["kmh", "kph"],
+        dimension: Dimension::Speed,
+        numerator: "1000",
+        denominator: "3600",
+        offset: "0",
+    },
+    linear("mph", &["mi/h"], Dimension::Speed, "0.44704"),
+    Unit {
+        symbol: "kn",
+        aliases: &["knot", "knots"],
+        dimension: Dimension::Speed,
+        numerator: "1852",
+        denominator: "3600",
+        offset: "0",
+    },
+    // 温度
+    linear("K", &["kelvin"], Dimension::Temperature, "1"),
+    Unit {
+        symbol: "°C",
+        aliases: &["c", "celsius"],
+        dimension: Dimension::Temperature,
+        numerator: "1",
+        denominator: "1",
+        offset: "273.15",
+    },
+    Unit {
+        symbol: "°F",
+        aliases: &["f", "fahrenheit"],
+        dimension: Dimension::Temperature,
+        numerator: "5",
+        denominator: "9",
+        offset: "459.67",
+    },
+];
+
+/// 按名称查找单位：先精确匹配符号（区分 `B`/`b` 等大小写敏感的写法），
+/// 再大小写不敏感匹配符号与别名。
+pub(super) fn lookup(name: &str) -> Option<&'static Unit> {
+    UNITS.iter().find(|u| u.symbol == name).or_else(|| {
+        UNITS.iter().find(|u| {
+            u.symbol.eq_ignore_ascii_case(name)
+                || u.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+        })
+    })
+}
+
+/// 将数值从 from 单位换算到 to 单位。
+/// 返回：量纲不一致时返回错误描述。
+pub(super) fn convert(value: &BigDecimal, from: &Unit, to: &Unit) -> Result<BigDecimal, String> {
+    if from.dimension != to.dimension {
+        return Err(format!("无法将 {} 换算为 {}", from.symbol, to.symbol));
+    }
+    let base = (value + decimal(from.offset)) * decimal(from.numerator) * decimal(to.denominator)
+        / (decimal(from.denominator) * decimal(to.numerator));
+    Ok(base - decimal(to.offset))
+}
+
+/// 解析单位表中的系数字符串（表内常量，解析失败属于编码错误）。
+fn decimal(text: &str) -> BigDecimal {
+    BigDecimal::from_str(text).expect("单位表系数必须是合法十进制数")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 换算结果与期望值在给定小数位数下一致。
+    fn assert_converts(value: &str, from: &str, to: &str, expected: &str) {
+        let from = lookup(from).unwrap();
+        let to = lookup(to).unwrap();
+        let result = convert(&decimal(value), from, to).unwrap();
+        assert_eq!(
+            result.round(6),
+            decimal(expected).round(6),
+            "{} -> {}",
+            from.symbol,
+            to.symbol
+        );
+    }
+
+    /// 线性、分数系数与带偏移（温度）的单位换算；跨量纲换算报错。
+    #[test]
+    fn converts_within_dimension() {
+        assert_converts("5", "km", "mi", "3.106856");
+        assert_converts("1", "GiB", "MB", "1073.741824");
+        assert_converts("36", "km/h", "m/s", "10");
+        assert_converts("100", "C", "fahrenheit", "212");
+        assert_converts("32", "°F", "K", "273.15");
+        assert!(convert(&decimal("1"), lookup("kg").unwrap(), lookup("m").unwrap()).is_err());
+        assert_eq!(lookup("b").unwrap().symbol, "B");
+        assert_eq!(lookup("Bytes").unwrap().symbol, "B");
+        assert!(lookup("parsec").is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::warn;
+
+/// 一天的毫秒数。
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// 一条剪贴板历史记录（同时是持久化格式）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ClipEntry {
+    /// 记录 ID（进程内单调递增，内容重复复制时沿用）
+    #[serde(rename = "id", default)]
+    pub id: u64,
+    /// 剪贴板文本
+    #[serde(rename = "text", default)]
+    pub text: String,
+    /// 写入剪贴板的来源进程名（平台无法确定时为 None）
+    #[serde(rename = "sourceApp", default)]
+    pub source_app: Option<String>,
+    /// 最近一次复制的时间（Unix 毫秒时间戳）
+    #[serde(rename = "copiedAt", default)]
+    pub copied_at: i64,
+}
+
+/// 历史记录的容量与保留期限制。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HistoryLimits {
+    /// 最多保留的条数（超出后淘汰最早的记录）
+    pub max_entries: usize,
+    /// 单条记录的最大字符数（超出的内容不记录）
+    pub max_entry_chars: usize,
+    /// 保留天数，0 表示不按时间淘汰
+    pub retention_days: u32,
+}
+
+/// 记录排除规则：来源应用与内容正则，任一命中即不记录。
+#[derive(Debug, Default)]
+pub(crate) struct ExclusionRules {
+    /// 排除的来源应用名（小写、去除 .exe 后缀）
+    apps: Vec<String>,
+    /// 排除的内容正则
+    patterns: Vec<Regex>,
+}
+
+impl ExclusionRules {
+    /// 由配置构造排除规则；无效的正则记录警告后忽略。
+    pub fn new(apps: &[String], patterns: &[String]) -> Self {
+        let apps = apps
+            .iter()
+            .map(|app| normalize_app_name(app))
+            .filter(|app| !app.is_empty())
+            .collect();
+        let patterns = patterns
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+            .filter_map(|p| match Regex::new(p) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("剪贴板历史排除规则无效，已忽略: {} ({})", p, e);
+                    None
+                }
+            })
+            .collect();
+        Self { apps, patterns }
+    }
+
+    /// 判断内容是否应被排除（来源应用名忽略大小写与 .exe 后缀）。
+    pub fn excludes(&self, text: &str, source_app: Option<&str>) -> bool {
+        if let Some(source) = source_app {
+            let source = normalize_app_name(source);
+            if self.apps.contains(&source) {
+                return true;
+            }
+        }
+        self.patterns.iter().any(|regex| regex.is_match(text))
+    }
+}
+
+/// 归一化应用名：去除首尾空白、转小写并去除 .exe 后缀。
+fn normalize_app_name(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    lower
+        .strip_suffix(".exe")
+        .map(str::to_string)
+        .unwrap_or(lower)
+}
+
+/// 有界剪贴板历史（新记录在前）。
+#[derive(Debug, Default)]
+pub(crate) struct ClipboardHistory {
+    /// 历史记录，队首为最新
+    entries: VecDeque<ClipEntry>,
+    /// 最近分配的记录 ID
+    last_id: u64,
+}
+
+impl ClipboardHistory {
+    /// 由持久化的记录恢复历史（记录需按新到旧排列）。
+    pub fn from_entries(entries: Vec<ClipEntry>) -> Self {
+        let last_id = entries.iter().map(|entry| entry.id).max().unwrap_or(0);
+        Self {
+            entries: entries.into(),
+            last_id,
+        }
+    }
+
+    /// 记录一次剪贴板变化：空白或超长内容不记录；重复内容移到队首并沿用原 ID。
+    /// 返回：历史是否发生变化。
+    pub fn record(
+        &mut self,
+        text: &str,
+        source_app: Option<String>,
+        now: i64,
+        limits: &HistoryLimits,
+    ) -> bool {
+        if text.trim().is_empty() || text.chars().nth(limits.max_entry_chars).is_some() {
+            return false;
+        }
+        let id = match self.entries.iter().position(|entry| entry.text == text) {
+            Some(index) => self.entries.remove(index).map(|entry| entry.id),
+            None => None,
+        }
+        .unwrap_or_else(|| {
+            self.last_id += 1;
+            self.last_id
+        });
+        self.entries.push_front(ClipEntry {
+            id,
+            text: text.to_string(),
+            source_app,
+            copied_at: now,
+        });
+        self.prune(now, limits);
+        true
+    }
+
+    /// 按保留期与条数上限淘汰记录。
+    /// 返回：是否淘汰了记录。
+    pub fn prune(&mut self, now: i64, limits: &HistoryLimits) -> bool {
+        let before = self.entries.len();
+        if limits.retention_days > 0 {
+            let cutoff = now - i64::from(limits.retention_days) * MILLIS_PER_DAY;
+            self.entries.retain(|entry| entry.copied_at >= cutoff);
+        }
+        self.entries.truncate(limits.max_entries);
+        self.entries.len() != before
+    }
+
+    /// 按 ID 查找记录。
+    pub fn get(&self, id: u64) -> Option<ClipEntry> {
+        self.entries.iter().find(|entry| entry.id == id).cloned()
+    }
+
+    /// 清空全部记录。
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// 全部记录（新记录在前）。
+    pub fn list(&self) -> Vec<ClipEntry> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用限制：10 条、100 字符、保留 7 天。
+    const LIMITS: HistoryLimits = HistoryLimits {
+        max_entries: 10,
+        max_entry_chars: 100,
+        retention_days: 7,
+    };
+
+    /// 条数上限：新记录在前，超出后淘汰最早的记录。
+    #[test]
+    fn record_keeps_most_recent_entries() {
+        let mut history = ClipboardHistory::default();
+        for i in 0..LIMITS.max_entries + 2 {
+            assert!(history.record(&format!("text-{}", i), None, 0, &LIMITS));
+        }
+
+        let entries = history.list();
+        assert_eq!(entries.len(), LIMITS.max_entries);
+        assert_eq!(entries[0].text, format!("text-{}", LIMITS.max_entries + 1));
+        assert_eq!(entries[0].id, (LIMITS.max_entries + 2) as u64);
+        assert!(history.get(1).is_none());
+    }
+
+    /// 重复复制的内容移到队首并沿用原 ID，不产生重复记录。
+    #[test]
+    fn record_moves_duplicate_to_front() {
+        let mut history = ClipboardHistory::default();
+        history.record("a", None, 1, &LIMITS);
+        history.record("b", None, 2, &LIMITS);
+        history.record("a", Some("code.exe".to_string()), 3, &LIMITS);
+
+        let entries = history.list();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, 1);
+        assert_eq!(entries[0].copied_at, 3);
+        assert_eq!(entries[0].source_app.as_deref(), Some("code.exe"));
+        assert_eq!(entries[1].text, "b");
+    }
+
+    /// 空白与超长内容不记录。
+    #[test]
+    fn record_skips_blank_and_oversized_text() {
+        let mut history = ClipboardHistory::default();
+        assert!(!history.record("  \n", None, 0, &LIMITS));
+        assert!(!history.record(&"x".repeat(101), None, 0, &LIMITS));
+        assert!(history.record(&"x".repeat(100), None, 0, &LIMITS));
+        assert_eq!(history.list().len(), 1);
+    }
+
+    /// 保留期淘汰过期记录；保留天数为 0 时不按时间淘汰。
+    #[test]
+    fn prune_drops_expired_entries() {
+        let mut history = ClipboardHistory::from_entries(vec![
+            ClipEntry {
+                id: 2,
+                text: "new".to_string(),
+                source_app: None,
+                copied_at: 8 * MILLIS_PER_DAY,
+            },
+            ClipEntry {
+                id: 1,
+                text: "old".to_string(),
+                source_app: None,
+                copied_at: 0,
+            },
+        ]);
+        let forever = HistoryLimits {
+            retention_days: 0,
+            ..LIMITS
+        };
+        assert!(!history.prune(9 * MILLIS_PER_DAY, &forever));
+        assert!(history.prune(9 * MILLIS_PER_DAY, &LIMITS));
+        assert_eq!(history.list().len(), 1);
+
+        // 恢复后新分配的 ID 不与已有记录冲突
+        history.record("next", None, 9 * MILLIS_PER_DAY, &LIMITS);
+        assert_eq!(history.list()[0].id, 3);
+    }
+
+    /// 排除规则：来源应用忽略大小写与 .exe 后缀，内容按正则匹配，无效正则被忽略。
+    #[test]
+    fn exclusion_rules_match_app_and_pattern() {
+        let rules = ExclusionRules::new(
+            &["KeePassXC".to_string(), " ".to_string()],
+            &[r"^\d{6}$".to_string(), "(".to_string()],
+        );
+        assert!(rules.excludes("hello", Some("keepassxc.exe")));
+        assert!(rules.excludes("123456", Some("notepad.exe")));
+        assert!(!rules.excludes("1234567", Some("notepad.exe")));
+        assert!(!rules.excludes("hello", None));
+    }
+}
@@ -0,0 +1,5 @@
+mod history;
+mod plugin;
+mod store;
+
+pub use plugin::ClipboardHistoryPlugin;
@@ -0,0 +1,657 @@
+use crate::builtin_plugin::search_engine::launchy_search_model::LaunchySearchModel;
+use crate::builtin_plugin::search_engine::skim_search_model::SkimSearchModel;
+use crate::builtin_plugin::search_engine::standard_search_model::StandardSearchModel;
+use crate::core::config::setting_builders::SchemaBuilder;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, ConfigError, Configurable, PrimitiveType, SettingDefinition,
+};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::clipboard::ClipboardEvent;
+use zerolaunch_plugin_api::services::IconRequest;
+use zerolaunch_plugin_api::{
+    CachedCandidateData, ExecutionTarget, PanelInteraction, PanelKeyAction, PanelKeyBinding,
+    Plugin, PluginContext, PluginError, PluginKind, PluginMetadata, PluginMode, Query,
+    QueryResponse, ResultAction, SearchCandidate, SearchEngine,
+};
+
+use super::history::{ClipEntry, ClipboardHistory, ExclusionRules, HistoryLimits};
+use super::store::HistoryStore;
+
+/// 插件 ID。
+const PLUGIN_ID: &str = "clipboard-history";
+/// 面板类型，前端按此匹配 ClipboardHistoryPanel。
+const PANEL_TYPE: &str = "clipboard-history";
+/// 剪贴板变化回调 ID（经 PluginHandle 前缀化）。
+const CALLBACK_ID: &str = "clipboard-history";
+/// 复制动作前缀（动作 ID 形如 `copy:<记录 ID>`）。
+const COPY_ACTION_PREFIX: &str = "copy:";
+/// 清空历史的动作。
+const CLEAR_ACTION: &str = "clear";
+/// 面板最多展示的记录数。
+const MAX_RESULTS: usize = 50;
+/// 面板预览的最大字符数（完整内容仅在复制时读取，避免每次查询推送大段文本）。
+const PREVIEW_CHARS: usize = 200;
+/// 参与检索的整段文本关键词的最大字符数。
+const KEYWORD_MAX_CHARS: usize = 200;
+/// 每条记录额外参与检索的单词关键词上限。
+const MAX_WORD_KEYWORDS: usize = 32;
+/// 检索引擎选项：标准引擎。
+const ENGINE_STANDARD: &str = "standard";
+/// 检索引擎选项：Skim 模糊匹配。
+const ENGINE_SKIM: &str = "skim";
+/// 检索引擎选项：Launchy。
+const ENGINE_LAUNCHY: &str = "launchy";
+
+/// 剪贴板历史插件的强类型配置结构。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ClipboardHistorySettings {
+    /// 是否记录剪贴板变化（关闭后保留已有历史）
+    #[serde(rename = "recording", default = "default_true")]
+    recording: bool,
+    /// 最多保留的条数
+    #[serde(rename = "max_entries", default = "default_max_entries")]
+    max_entries: u32,
+    /// 单条记录的最大字符数，超出的内容不记录
+    #[serde(rename = "max_entry_chars", default = "default_max_entry_chars")]
+    max_entry_chars: u32,
+    /// 保留天数，0 表示不按时间淘汰
+    #[serde(rename = "retention_days", default = "default_retention_days")]
+    retention_days: u32,
+    /// 不记录的来源应用（进程名，忽略大小写与 .exe 后缀）
+    #[serde(rename = "excluded_apps", default)]
+    excluded_apps: Vec<String>,
+    /// 不记录的内容正则
+    #[serde(rename = "excluded_patterns", default)]
+    excluded_patterns: Vec<String>,
+    /// 是否将历史保存到磁盘（关闭时仅保存在内存中，并删除已有的历史文件）
+    #[serde(rename = "persist", default)]
+    persist: bool,
+    /// 持久化时是否加密
+    #[serde(rename = "encrypt", default = "default_true")]
+    encrypt: bool,
+    /// 检索历史使用的搜索引擎（standard / skim / launchy）
+    #[serde(rename = "search_engine", default = "default_search_engine")]
+    search_engine: String,
+}
+
+impl Default for ClipboardHistorySettings {
+    fn default() -> Self {
+        Self {
+            recording: true,
+            max_entries: default_max_entries(),
+            max_entry_chars: default_max_entry_chars(),
+            retention_days: default_retention_days(),
+            excluded_apps: Vec::new(),
+            excluded_patterns: Vec::new(),
+            persist: false,
+            encrypt: true,
+            search_engine: default_search_engine(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_entries() -> u32 {
+    200
+}
+
+fn default_max_entry_chars() -> u32 {
+    20_000
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+fn default_search_engine() -> String {
+    ENGINE_SKIM.to_string()
+}
+
+impl ClipboardHistorySettings {
+    /// 由设置换算历史限制。
+    fn limits(&self) -> HistoryLimits {
+        HistoryLimits {
+            max_entries: self.max_entries.max(1) as usize,
+            max_entry_chars: self.max_entry_chars.max(1) as usize,
+            retention_days: self.retention_days,
+        }
+    }
+}
+
+/// 插件与剪贴板变化回调共享的状态（回调在平台监听线程上执行）。
+#[derive(Default)]
+struct ClipboardState {
+    /// 当前设置
+    settings: RwLock<ClipboardHistorySettings>,
+    /// 由设置编译的排除规则
+    rules: RwLock<ExclusionRules>,
+    /// 剪贴板历史
+    history: RwLock<ClipboardHistory>,
+    /// 持久化存储（init 时按 PluginHandle 解析目录后创建）
+    store: RwLock<Option<Arc<HistoryStore>>>,
+}
+
+impl ClipboardState {
+    /// 剪贴板变化：按设置与排除规则过滤后写入历史并安排写盘。
+    fn on_clipboard_change(&self, event: ClipboardEvent) {
+        let (recording, limits) = {
+            let settings = self.settings.read();
+            (settings.recording, settings.limits())
+        };
+        if !recording
+            || self
+                .rules
+                .read()
+                .excludes(&event.text, event.source_app.as_deref())
+        {
+            return;
+        }
+        let now = chrono::Utc::now().timestamp_millis();
+        if self
+            .history
+            .write()
+            .record(&event.text, event.source_app, now, &limits)
+        {
+            self.save();
+        }
+    }
+
+    /// 按保留期淘汰过期记录，返回淘汰后的全部记录。
+    fn prune_and_list(&self) -> Vec<ClipEntry> {
+        let limits = self.settings.read().limits();
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut history = self.history.write();
+        if history.prune(now, &limits) {
+            drop(history);
+            self.save();
+            return self.history.read().list();
+        }
+        history.list()
+    }
+
+    /// 按持久化设置安排写盘；未开启持久化时删除已有的历史文件。
+    fn save(&self) {
+        let Some(store) = self.store.read().clone() else {
+            return;
+        };
+        let (persist, encrypt) = {
+            let settings = self.settings.read();
+            (settings.persist, settings.encrypt)
+        };
+        if persist {
+            store.schedule_save(self.history.read().list(), encrypt);
+        } else {
+            store.remove_all();
+        }
+    }
+}
+
+/// 剪贴板历史插件 —— 记录剪贴板文本历史，支持检索并将选中的记录复制回剪贴板。
+///
+/// 经 PluginHandle 注册剪贴板变化回调记录历史（条数、大小、保留期限制与排除规则），
+/// 可选（加密）持久化；检索复用内置搜索引擎的评分实现。
+pub struct ClipboardHistoryPlugin {
+    /// 组件 ID、名称、类型等基础元数据。
+    core: ComponentCore,
+    /// 插件元数据（id、名称、触发词等）。
+    metadata: PluginMetadata,
+    /// 与剪贴板变化回调共享的状态。
+    state: Arc<ClipboardState>,
+    /// PluginHandle（init 时发放），供 execute_action 经句柄访问剪贴板。
+    handle: RwLock<Option<Arc<PluginHandle>>>,
+}
+
+impl Default for ClipboardHistoryPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardHistoryPlugin {
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                PLUGIN_ID.to_string(),
+                t_key!("clipboard-history", "name").to_string(),
+                t_key!("clipboard-history", "description").to_string(),
+                ComponentType::Plugin,
+                0,
+            ),
+            metadata: PluginMetadata {
+                id: PLUGIN_ID.to_string(),
+                name: t_key!("clipboard-history", "name").to_string(),
+                version: String::new(),
+                description: t_key!("clipboard-history", "description").to_string(),
+                author: String::new(),
+                trigger_keywords: vec!["cb".to_string()],
+                supported_os: vec!["windows".to_string()],
+                priority: 100,
+                kind: PluginKind::Builtin,
+                // 面板插件：仅关键词（cb）唤醒，无全局热键
+                hotkey: None,
+                icon: None,
+                mode: PluginMode::Panel,
+                global_query: false,
+            },
+            state: Arc::new(ClipboardState::default()),
+            handle: RwLock::new(None),
+        }
+    }
+
+    /// 按搜索词检索历史：空搜索词按时间取最近的记录；
+    /// 否则以所选搜索引擎评分，保留正分记录按分数降序（同分按时间）排列。
+    fn search(&self, entries: &[ClipEntry], term: &str) -> Vec<ClipEntry> {
+        if term.is_empty() {
+            return entries.iter().take(MAX_RESULTS).cloned().collect();
+        }
+
+        let mut data = CachedCandidateData::new();
+        for entry in entries {
+            data.add_candidate_with_id(Self::candidate(entry), entry.id);
+        }
+        let engine = self.state.settings.read().search_engine.clone();
+        let engine: Box<dyn SearchEngine> = match engine.as_str() {
+            ENGINE_STANDARD => Box::new(StandardSearchModel::new()),
+            ENGINE_LAUNCHY => Box::new(LaunchySearchModel::new()),
+            _ => Box::new(SkimSearchModel::new()),
+        };
+        let mut scored = engine.calculate_scores(&data, &term.to_lowercase());
+        scored.retain(|s| s.score > 0.0);
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored
+            .into_iter()
+            .filter_map(|s| entries.iter().find(|entry| entry.id == s.candidate_id))
+            .take(MAX_RESULTS)
+            .cloned()
+            .collect()
+    }
+
+    /// 将历史记录包装为检索候选项：整段文本（截断）与各单词作为关键词。
+    fn candidate(entry: &ClipEntry) -> SearchCandidate {
+        let text = entry.text.to_lowercase();
+        let mut keywords = vec![text.chars().take(KEYWORD_MAX_CHARS).collect::<String>()];
+        for word in text.split_whitespace().take(MAX_WORD_KEYWORDS) {
+            if !keywords.iter().any(|k| k == word) {
+                keywords.push(word.to_string());
+            }
+        }
+        SearchCandidate {
+            id: entry.id,
+            // 显示名与执行目标仅用于满足候选缓存的去重约束，须逐条唯一
+            name: entry.id.to_string(),
+            icon: IconRequest::Path(String::new()),
+            target: ExecutionTarget::Command(format!("{}:{}", PLUGIN_ID, entry.id)),
+            keywords,
+            bias: 0.0,
+            trigger_keywords: Vec::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    /// 面板展示的记录：预览截断到 PREVIEW_CHARS 个字符。
+    fn preview(entry: &ClipEntry) -> serde_json::Value {
+        let length = entry.text.chars().count();
+        let preview: String = entry.text.chars().take(PREVIEW_CHARS).collect();
+        json!({
+            "id": entry.id,
+            "preview": preview,
+            "length": length,
+            "sourceApp": entry.source_app,
+            "copiedAt": entry.copied_at,
+        })
+    }
+
+    /// 面板动作：每条展示的记录一个复制动作（与列表逐项对应，首项为默认动作），末尾为清空历史。
+    fn panel_actions(entries: &[ClipEntry]) -> Vec<ResultAction> {
+        let mut actions: Vec<ResultAction> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| ResultAction {
+                id: format!("{}{}", COPY_ACTION_PREFIX, entry.id),
+                label: "复制".to_string(),
+                icon: IconRequest::Path("copy".to_string()),
+                is_default: i == 0,
+                shortcut_key: if i == 0 {
+                    "Enter".to_string()
+                } else {
+                    String::new()
+                },
+            })
+            .collect();
+        if !entries.is_empty() {
+            actions.push(ResultAction {
+                id: CLEAR_ACTION.to_string(),
+                label: "清空历史".to_string(),
+                icon: IconRequest::Path("delete".to_string()),
+                is_default: false,
+                shortcut_key: String::new(),
+            });
+        }
+        actions
+    }
+}
+
+#[async_trait]
+impl Configurable for ClipboardHistoryPlugin {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![
+            SchemaBuilder::boolean(
+                "recording",
+                t_key!("clipboard-history", "fields.recording.label"),
+                t_key!("clipboard-history", "fields.recording.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.record"))
+            .order(0)
+            .default(true)
+            .build(),
+            SchemaBuilder::integer(
+                "max_entries",
+                t_key!("clipboard-history", "fields.max_entries.label"),
+                t_key!("clipboard-history", "fields.max_entries.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.record"))
+            .order(1)
+            .default(default_max_entries())
+            .min(1.0)
+            .max(5000.0)
+            .build(),
+            SchemaBuilder::integer(
+                "max_entry_chars",
+                t_key!("clipboard-history", "fields.max_entry_chars.label"),
+                t_key!("clipboard-history", "fields.max_entry_chars.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.record"))
+            .order(2)
+            .default(default_max_entry_chars())
+            .min(1.0)
+            .max(1_000_000.0)
+            .build(),
+            SchemaBuilder::integer(
+                "retention_days",
+                t_key!("clipboard-history", "fields.retention_days.label"),
+                t_key!("clipboard-history", "fields.retention_days.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.record"))
+            .order(3)
+            .default(default_retention_days())
+            .min(0.0)
+            .max(3650.0)
+            .build(),
+            SchemaBuilder::array(
+                "excluded_apps",
+                t_key!("clipboard-history", "fields.excluded_apps.label"),
+                t_key!("clipboard-history", "fields.excluded_apps.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.exclusion"))
+            .order(4)
+            .primitive_item(PrimitiveType::Text)
+            .tags_ui()
+            .min_items(0)
+            .default(json!([]))
+            .build(),
+            SchemaBuilder::array(
+                "excluded_patterns",
+                t_key!("clipboard-history", "fields.excluded_patterns.label"),
+                t_key!("clipboard-history", "fields.excluded_patterns.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.exclusion"))
+            .order(5)
+            .primitive_item(PrimitiveType::Text)
+            .tags_ui()
+            .min_items(0)
+            .default(json!([]))
+            .build(),
+            SchemaBuilder::boolean(
+                "persist",
+                t_key!("clipboard-history", "fields.persist.label"),
+                t_key!("clipboard-history", "fields.persist.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.storage"))
+            .order(6)
+            .default(false)
+            .build(),
+            SchemaBuilder::boolean(
+                "encrypt",
+                t_key!("clipboard-history", "fields.encrypt.label"),
+                t_key!("clipboard-history", "fields.encrypt.desc"),
+            )
+            .group(t_key!("clipboard-history", "groups.storage"))
+            .order(7)
+            .default(true)
+            .build(),
+            SchemaBuilder::select(
+                "search_engine",
+                t_key!("clipboard-history", "fields.search_engine.label"),
+                t_key!("clipboard-history", "fields.search_engine.desc"),
+            )
+            .options_with_labels(&[
+                (
+                    ENGINE_STANDARD,
+                    t_key!("clipboard-history", "options.search_engine.standard"),
+                ),
+                (
+                    ENGINE_SKIM,
+                    t_key!("clipboard-history", "options.search_engine.skim"),
+                ),
+                (
+                    ENGINE_LAUNCHY,
+                    t_key!("clipboard-history", "options.search_engine.launchy"),
+                ),
+            ])
+            .group(t_key!("clipboard-history", "groups.search"))
+            .order(8)
+            .default(ENGINE_SKIM)
+            .build(),
+        ]
+    }
+
+    fn get_settings(&self) -> serde_json::Value {
+        serde_json::to_value(self.state.settings.read().clone()).unwrap_or_default()
+    }
+
+    /// 应用设置：重建排除规则，按新限制淘汰记录，并按新的持久化设置重写（或删除）历史文件。
+    async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+        let parsed: ClipboardHistorySettings = serde_json::from_value(settings).unwrap_or_default();
+        *self.state.rules.write() =
+            ExclusionRules::new(&parsed.excluded_apps, &parsed.excluded_patterns);
+        let limits = parsed.limits();
+        *self.state.settings.write() = parsed;
+        let now = chrono::Utc::now().timestamp_millis();
+        self.state.history.write().prune(now, &limits);
+        self.state.save();
+        Ok(())
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Plugin for ClipboardHistoryPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    /// 保存服务句柄，加载持久化的历史（未开启持久化时删除遗留文件），并注册剪贴板变化回调。
+    async fn init(
+        &self,
+        _ctx: &PluginContext,
+        handle: Option<Arc<PluginHandle>>,
+    ) -> Result<(), PluginError> {
+        if let Some(handle) = &handle {
+            let store = Arc::new(HistoryStore::from_handle(handle));
+            let (persist, limits) = {
+                let settings = self.state.settings.read();
+                (settings.persist, settings.limits())
+            };
+            if persist {
+                let mut history = ClipboardHistory::from_entries(store.load());
+                history.prune(chrono::Utc::now().timestamp_millis(), &limits);
+                *self.state.history.write() = history;
+            } else {
+                store.remove_all();
+            }
+            *self.state.store.write() = Some(store);
+
+            let state = self.state.clone();
+            handle.register_clipboard_callback(
+                CALLBACK_ID,
+                Arc::new(move |event| state.on_clipboard_change(event)),
+            );
+        }
+        *self.handle.write() = handle;
+        Ok(())
+    }
+
+    fn interaction_policy(&self) -> PanelInteraction {
+        PanelInteraction {
+            // 历史面板为行内形态（保留搜索栏输入检索词）：
+            // - Enter：ExecuteAction —— 执行面板中选中的动作（复制选中的记录，
+            //   选中项由前端面板以方向键切换）；
+            // - Escape：GoBack —— 退出历史面板（返回默认搜索）。
+            bindings: vec![
+                PanelKeyBinding {
+                    key: "Enter".to_string(),
+                    action: PanelKeyAction::ExecuteAction { action_id: None },
+                },
+                PanelKeyBinding {
+                    key: "Escape".to_string(),
+                    action: PanelKeyAction::GoBack,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    async fn query(
+        &self,
+        _ctx: &PluginContext,
+        query: &Query,
+    ) -> Result<QueryResponse, PluginError> {
+        let all = self.state.prune_and_list();
+        let term = query.search_term.trim();
+        let entries = self.search(&all, term);
+        Ok(QueryResponse::CustomPanel {
+            panel_type: PANEL_TYPE.to_string(),
+            data: json!({
+                "query": term,
+                "total": all.len(),
+                "recording": self.state.settings.read().recording,
+                "entries": entries.iter().map(Self::preview).collect::<Vec<_>>(),
+            }),
+            actions: Self::panel_actions(&entries),
+            keep_search_bar: true,
+        })
+    }
+
+    /// 复制动作：按 `copy:<记录 ID>` 将记录写回剪贴板；清空动作：清空历史。
+    async fn execute_action(
+        &self,
+        _ctx: &PluginContext,
+        action_id: &str,
+        _payload: serde_json::Value,
+    ) -> Result<(), PluginError> {
+        if action_id == CLEAR_ACTION {
+            self.state.history.write().clear();
+            self.state.save();
+            return Ok(());
+        }
+        let unknown = || PluginError::ActionFailed(format!("未知动作: {}", action_id));
+        let id: u64 = action_id
+            .strip_prefix(COPY_ACTION_PREFIX)
+            .ok_or_else(unknown)?
+            .parse()
+            .map_err(|_| unknown())?;
+        let entry = self
+            .state
+            .history
+            .read()
+            .get(id)
+            .ok_or_else(|| PluginError::ActionFailed(format!("历史记录已过期: {}", id)))?;
+        // 经 PluginHandle 访问剪贴板能力（init 时发放）。
+        // 写入后剪贴板变化回调会将该记录移到历史队首。
+        let handle = self
+            .handle
+            .read()
+            .clone()
+            .ok_or_else(|| PluginError::ActionFailed("插件服务句柄不可用".to_string()))?;
+        handle
+            .set_clipboard_text(&entry.text)
+            .map_err(|e| PluginError::ActionFailed(format!("剪贴板写入失败: {}", e)))
+    }
+}
+
+use crate::plugin_framework::builtin_registry::PluginEntry;
+
+fn build_clipboard_history_plugin() -> (Arc<dyn Configurable>, Arc<dyn Plugin>) {
+    let plugin: Arc<dyn Plugin> = Arc::new(ClipboardHistoryPlugin::new());
+    let configurable: Arc<dyn Configurable> = plugin.clone();
+    (configurable, plugin)
+}
+
+::inventory::submit! {
+    PluginEntry {
+        component_id: "clipboard-history",
+        priority: 0,
+        factory: build_clipboard_history_plugin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造测试记录（新记录在前）。
+    fn entries() -> Vec<ClipEntry> {
+        ["git status", "hello world", "https://example.com/docs"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| ClipEntry {
+                id: (3 - i) as u64,
+                text: text.to_string(),
+                source_app: None,
+                copied_at: 0,
+            })
+            .collect()
+    }
+
+    /// 各搜索引擎都能按关键词命中对应记录，未命中的记录不展示。
+    #[test]
+    fn search_uses_selected_engine() {
+        let plugin = ClipboardHistoryPlugin::new();
+        let entries = entries();
+        for engine in [ENGINE_STANDARD, ENGINE_SKIM, ENGINE_LAUNCHY] {
+            plugin.state.settings.write().search_engine = engine.to_string();
+            let found = plugin.search(&entries, "Example");
+            assert_eq!(found[0].text, "https://example.com/docs", "{}", engine);
+        }
+        plugin.state.settings.write().search_engine = ENGINE_SKIM.to_string();
+        assert!(plugin.search(&entries, "zzqx").is_empty());
+        assert_eq!(plugin.search(&entries, "").len(), entries.len());
+    }
+
+    /// 面板动作与展示的记录逐项对应，首项为默认动作，末尾为清空历史。
+    #[test]
+    fn panel_actions_follow_entries() {
+        let entries = entries();
+        let actions = ClipboardHistoryPlugin::panel_actions(&entries);
+        assert_eq!(actions.len(), entries.len() + 1);
+        assert_eq!(actions[0].id, "copy:3");
+        assert!(actions[0].is_default && !actions[1].is_default);
+        assert_eq!(actions.last().unwrap().id, CLEAR_ACTION);
+        assert!(ClipboardHistoryPlugin::panel_actions(&[]).is_empty());
+    }
+}
@@ -0,0 +1,411 @@
+//! 剪贴板历史的持久化层。
+//!
+//! 历史保存在 `AppDataDir/clipboard/` 下：明文模式写 `history.json`；
+//! 加密模式写 `history.bin`（ChaCha20-Poly1305，12 字节随机 nonce 后接密文），
+//! 密钥为首次加密时生成的 32 字节随机数，保存在系统钥匙串（Windows 凭据管理器 /
+//! macOS 钥匙串 / Secret Service）而不是历史目录中：拿到密文文件（备份、同步盘、拷贝）
+//! 也无法解密。钥匙串不可用时加密模式不落盘（历史仅保留在内存中），不会退化为明文或同目录密钥。
+//! 写入经 `write_atomic`（唯一临时文件 + rename），并防抖合并写盘。
+
+use super::history::ClipEntry;
+use crate::core::config::secret_store::{KeyringSecretStore, SecretStore};
+use crate::core::config::store::write_atomic;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::path::path_resolver::KnownPath;
+
+/// 当前快照格式版本，数据结构不兼容变更时递增。
+const HISTORY_SNAPSHOT_VERSION: u32 = 1;
+/// 历史目录名（位于 AppDataDir 下）。
+const DIR_NAME: &str = "clipboard";
+/// 明文快照文件名。
+const PLAIN_FILE: &str = "history.json";
+/// 加密快照文件名。
+const ENCRYPTED_FILE: &str = "history.bin";
+/// 加密密钥在机密存储中的名称（base64 编码，沿用 `<componentId>/<key>` 命名）。
+const KEY_SECRET_NAME: &str = "clipboard-history/history-key";
+/// ChaCha20-Poly1305 的 nonce 长度（字节）。
+const NONCE_LEN: usize = 12;
+/// 记录变化后延迟写盘的时间，期间的多次变化合并为一次写入。
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// 历史快照的磁盘格式（加密模式下为密文的明文内容）。
+#[derive(Debug, Serialize, Deserialize)]
+struct HistorySnapshot {
+    /// 快照格式版本
+    #[serde(rename = "version", default)]
+    version: u32,
+    /// 历史记录（新记录在前）
+    #[serde(rename = "entries", default)]
+    entries: Vec<ClipEntry>,
+}
+
+/// 等待写盘的快照。
+struct PendingSave {
+    /// 历史记录
+    entries: Vec<ClipEntry>,
+    /// 是否加密写盘
+    encrypt: bool,
+}
+
+/// HistoryStore 的共享状态，通过 Arc 在插件与延迟写盘任务之间共享。
+struct HistoryStoreInner {
+    /// 历史目录，为 None 时不进行持久化（如测试环境）
+    dir: Option<PathBuf>,
+    /// 保管加密密钥的机密存储，为 None 时（钥匙串不可用）加密模式不落盘
+    secrets: Option<Arc<dyn SecretStore>>,
+    /// 等待写盘的最新快照，None 表示无待写数据
+    pending: Mutex<Option<PendingSave>>,
+    /// 是否已有延迟写盘任务在等待
+    scheduled: AtomicBool,
+}
+
+/// 剪贴板历史存储 — 负责快照的加载、防抖保存（可选加密）与删除。
+pub(crate) struct HistoryStore {
+    inner: Arc<HistoryStoreInner>,
+}
+
+impl HistoryStore {
+    /// 创建历史存储。
+    /// 参数：dir - 历史目录，None 表示不持久化；secrets - 保管加密密钥的机密存储。
+    pub fn new(dir: Option<PathBuf>, secrets: Option<Arc<dyn SecretStore>>) -> Self {
+        Self {
+            inner: Arc::new(HistoryStoreInner {
+                dir,
+                secrets,
+                pending: Mutex::new(None),
+                scheduled: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// 通过 PluginHandle 解析 `AppDataDir/clipboard` 作为历史目录，密钥保存在系统钥匙串。
+    /// 解析失败或路径为空时返回不持久化的存储。
+    pub fn from_handle(handle: &PluginHandle) -> Self {
+        let dir = match handle.resolve_path(KnownPath::AppDataDir) {
+            Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join(DIR_NAME)),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("解析应用数据目录失败，剪贴板历史将不会持久化: {}", e);
+                None
+            }
+        };
+        let secrets =
+            KeyringSecretStore::probe().map(|store| Arc::new(store) as Arc<dyn SecretStore>);
+        if secrets.is_none() {
+            warn!("系统钥匙串不可用，加密模式下剪贴板历史将不会持久化");
+        }
+        Self::new(dir, secrets)
+    }
+
+    /// 加载持久化的历史：优先读取加密快照，其次明文快照（与当前加密设置无关，
+    /// 下次写盘时按新设置转换格式）。文件不存在或无法解析时返回空列表。
+    pub fn load(&self) -> Vec<ClipEntry> {
+        let Some(dir) = self.inner.dir.as_ref() else {
+            return Vec::new();
+        };
+        let encrypted = dir.join(ENCRYPTED_FILE);
+        let plain = dir.join(PLAIN_FILE);
+        let result = if encrypted.exists() {
+            self.inner.read_encrypted(&encrypted)
+        } else if plain.exists() {
+            std::fs::read(&plain).map_err(|e| e.to_string())
+        } else {
+            debug!("剪贴板历史快照不存在: {:?}", dir);
+            return Vec::new();
+        };
+        match result.and_then(|bytes| parse_snapshot(&bytes)) {
+            Ok(entries) => {
+                debug!("成功加载 {} 条剪贴板历史", entries.len());
+                entries
+            }
+            Err(e) => {
+                warn!("剪贴板历史快照无效，已忽略: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// 立即写盘，并清除等待中的延迟写盘。
+    pub fn save_now(&self, entries: &[ClipEntry], encrypt: bool) -> Result<(), String> {
+        self.inner.pending.lock().take();
+        self.inner.write(entries, encrypt)
+    }
+
+    /// 延迟保存：记录最新快照，并在防抖时间后统一写盘。
+    pub fn schedule_save(&self, entries: Vec<ClipEntry>, encrypt: bool) {
+        if self.inner.dir.is_none() {
+            return;
+        }
+        *self.inner.pending.lock() = Some(PendingSave { entries, encrypt });
+
+        if self.inner.scheduled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let inner = self.inner.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DEBOUNCE).await;
+            inner.scheduled.store(false, Ordering::Release);
+            inner.flush();
+        });
+    }
+
+    /// 删除全部持久化文件与密钥，并丢弃等待中的写盘（关闭持久化时调用）。
+    pub fn remove_all(&self) {
+        self.inner.pending.lock().take();
+        let Some(dir) = self.inner.dir.as_ref() else {
+            return;
+        };
+        for name in [PLAIN_FILE, ENCRYPTED_FILE] {
+            remove_if_exists(&dir.join(name));
+        }
+        self.inner.delete_key();
+    }
+}
+
+impl HistoryStoreInner {
+    /// 将等待中的快照写盘。
+    fn flush(&self) {
+        let Some(pending) = self.pending.lock().take() else {
+            return;
+        };
+        if let Err(e) = self.write(&pending.entries, pending.encrypt) {
+            warn!("保存剪贴板历史失败: {}", e);
+        }
+    }
+
+    /// 按加密设置写入快照，并删除另一种格式的旧文件（明文模式同时删除密钥）。
+    /// 加密模式下机密存储不可用时返回错误且不写入任何文件。
+    fn write(&self, entries: &[ClipEntry], encrypt: bool) -> Result<(), String> {
+        let Some(dir) = self.dir.as_ref() else {
+            return Ok(());
+        };
+        let snapshot = HistorySnapshot {
+            version: HISTORY_SNAPSHOT_VERSION,
+            entries: entries.to_vec(),
+        };
+        let bytes = serde_json::to_vec(&snapshot).map_err(|e| e.to_string())?;
+        if encrypt {
+            let key = self.load_or_create_key()?;
+            write_atomic(&dir.join(ENCRYPTED_FILE), &encrypt_bytes(&key, &bytes)?)
+                .map_err(|e| e.to_string())?;
+            remove_if_exists(&dir.join(PLAIN_FILE));
+        } else {
+            write_atomic(&dir.join(PLAIN_FILE), &bytes).map_err(|e| e.to_string())?;
+            remove_if_exists(&dir.join(ENCRYPTED_FILE));
+            self.delete_key();
+        }
+        Ok(())
+    }
+
+    /// 取得机密存储；不可用时返回错误。
+    fn secrets(&self) -> Result<&Arc<dyn SecretStore>, String> {
+        self.secrets
+            .as_ref()
+            .ok_or_else(|| "系统钥匙串不可用，无法加密保存剪贴板历史".to_string())
+    }
+
+    /// 从机密存储读取密钥；不存在时返回 None。
+    fn load_key(&self) -> Result<Option<Key>, String> {
+        let Some(encoded) = self
+            .secrets()?
+            .get(KEY_SECRET_NAME)
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(None);
+        };
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| format!("密钥格式无效: {}", e))?;
+        if bytes.len() != 32 {
+            return Err("密钥长度无效".to_string());
+        }
+        Ok(Some(*Key::from_slice(&bytes)))
+    }
+
+    /// 读取密钥，不存在（或已损坏）时生成新的随机密钥并存入机密存储。
+    fn load_or_create_key(&self) -> Result<Key, String> {
+        match self.load_key() {
+            Ok(Some(key)) => return Ok(key),
+            Ok(None) => {}
+            Err(e) => warn!("剪贴板历史密钥无效，将重新生成: {}", e),
+        }
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        self.secrets()?
+            .set(KEY_SECRET_NAME, &BASE64.encode(key.as_slice()))
+            .map_err(|e| format!("保存密钥失败: {}", e))?;
+        Ok(key)
+    }
+
+    /// 删除机密存储中的密钥；失败时记录警告。
+    fn delete_key(&self) {
+        let Some(secrets) = self.secrets.as_ref() else {
+            return;
+        };
+        if let Err(e) = secrets.delete(KEY_SECRET_NAME) {
+            warn!("删除剪贴板历史密钥失败: {}", e);
+        }
+    }
+
+    /// 读取并解密加密快照。
+    fn read_encrypted(&self, path: &Path) -> Result<Vec<u8>, String> {
+        let key = self
+            .load_key()?
+            .ok_or_else(|| "机密存储中没有剪贴板历史密钥".to_string())?;
+        let data = std::fs::read(path).map_err(|e| e.to_string())?;
+        decrypt_bytes(&key, &data)
+    }
+}
+
+/// 解析快照内容并校验版本。
+fn parse_snapshot(bytes: &[u8]) -> Result<Vec<ClipEntry>, String> {
+    let snapshot: HistorySnapshot = serde_json::from_slice(bytes).map_err(|e| e.to_string())?;
+    if snapshot.version != HISTORY_SNAPSHOT_VERSION {
+        return Err(format!(
+            "不支持的快照版本: {}（当前版本 {}）",
+            snapshot.version, HISTORY_SNAPSHOT_VERSION
+        ));
+    }
+    Ok(snapshot.entries)
+}
+
+/// 加密：输出为随机 nonce 后接密文（含认证标签）。
+fn encrypt_bytes(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| format!("加密失败: {}", e))?;
+    let mut output = nonce.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// 解密 `encrypt_bytes` 的输出；密钥不匹配或内容被篡改时返回错误。
+fn decrypt_bytes(key: &Key, data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < NONCE_LEN {
+        return Err("加密快照长度无效".to_string());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "解密失败（密钥不匹配或内容已损坏）".to_string())
+}
+
+/// 删除文件，不存在时忽略，其他错误记录警告。
+fn remove_if_exists(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!("删除剪贴板历史文件失败: {:?}, 错误: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::secret_store::MemorySecretStore;
+
+    /// 构造测试记录。
+    fn entries() -> Vec<ClipEntry> {
+        vec![ClipEntry {
+            id: 1,
+            text: "secret text".to_string(),
+            source_app: Some("notepad.exe".to_string()),
+            copied_at: 42,
+        }]
+    }
+
+    /// 以内存机密存储创建历史存储。
+    fn store_in(dir: &Path, secrets: &Arc<MemorySecretStore>) -> HistoryStore {
+        let secrets: Arc<dyn SecretStore> = secrets.clone();
+        HistoryStore::new(Some(dir.to_path_buf()), Some(secrets))
+    }
+
+    /// 加密写盘：文件中不含明文，目录中只有密文（密钥在机密存储中），可读回；
+    /// 切换为明文后删除密文与密钥。
+    #[test]
+    fn encrypted_round_trip_and_format_switch() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = Arc::new(MemorySecretStore::default());
+        let store = store_in(dir.path(), &secrets);
+
+        store.save_now(&entries(), true).unwrap();
+        let raw = std::fs::read(dir.path().join(ENCRYPTED_FILE)).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret text"));
+        let files: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec![ENCRYPTED_FILE]);
+        assert!(secrets.get(KEY_SECRET_NAME).unwrap().is_some());
+        assert_eq!(store.load(), entries());
+
+        store.save_now(&entries(), false).unwrap();
+        assert!(!dir.path().join(ENCRYPTED_FILE).exists());
+        assert!(secrets.get(KEY_SECRET_NAME).unwrap().is_none());
+        assert_eq!(store.load(), entries());
+
+        store.save_now(&entries(), true).unwrap();
+        store.remove_all();
+        assert!(!dir.path().join(ENCRYPTED_FILE).exists());
+        assert!(!dir.path().join(PLAIN_FILE).exists());
+        assert!(secrets.get(KEY_SECRET_NAME).unwrap().is_none());
+        assert!(store.load().is_empty());
+    }
+
+    /// 只拿到历史目录（没有机密存储中的密钥）时无法解密。
+    #[test]
+    fn ciphertext_alone_cannot_be_decrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        store_in(dir.path(), &Arc::new(MemorySecretStore::default()))
+            .save_now(&entries(), true)
+            .unwrap();
+
+        let copied = store_in(dir.path(), &Arc::new(MemorySecretStore::default()));
+        assert!(copied.load().is_empty());
+    }
+
+    /// 机密存储不可用时加密模式拒绝写盘，不留下任何文件；明文模式照常写入。
+    #[test]
+    fn encryption_without_secret_store_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::new(Some(dir.path().to_path_buf()), None);
+        assert!(store.save_now(&entries(), true).is_err());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        store.save_now(&entries(), false).unwrap();
+        assert_eq!(store.load(), entries());
+    }
+
+    /// 密文被篡改或密钥不匹配时解密失败，加载返回空列表。
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = Arc::new(MemorySecretStore::default());
+        let store = store_in(dir.path(), &secrets);
+        store.save_now(&entries(), true).unwrap();
+
+        let path = dir.path().join(ENCRYPTED_FILE);
+        let mut raw = std::fs::read(&path).unwrap();
+        let last = raw.len() - 1;
+        raw[last] ^= 0xff;
+        std::fs::write(&path, raw).unwrap();
+        assert!(store.load().is_empty());
+
+        let other_key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let sealed = encrypt_bytes(&other_key, b"data").unwrap();
+        let key = store.inner.load_or_create_key().unwrap();
+        assert!(decrypt_bytes(&key, &sealed).is_err());
+        assert_eq!(decrypt_bytes(&other_key, &sealed).unwrap(), b"data");
+    }
+}
@@ -0,0 +1,193 @@
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use zerolaunch_plugin_api::services::shell::CommandOutput;
+
+/// 运行历史最多保留的条数（超出后淘汰最早的记录）。
+const MAX_RUNS: usize = 20;
+
+/// 进程级运行历史：命令执行器写入、输出面板插件读取。
+/// 两者由互相独立的 inventory 工厂构造，无法经构造参数共享状态。
+static RUN_HISTORY: LazyLock<CommandRunHistory> = LazyLock::new(CommandRunHistory::default);
+
+/// 获取进程级的命令运行历史。
+pub(crate) fn run_history() -> &'static CommandRunHistory {
+    &RUN_HISTORY
+}
+
+/// 一次捕获型命令运行的记录（跨 IPC 序列化为面板数据）。
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommandRun {
+    /// 运行 ID（进程内单调递增，由历史写入时分配）
+    #[serde(rename = "id")]
+    pub id: u64,
+    /// 命令名称（候选项显示名）
+    #[serde(rename = "name")]
+    pub name: String,
+    /// 参数解析后实际执行的命令
+    #[serde(rename = "command")]
+    pub command: String,
+    /// 标准输出
+    #[serde(rename = "stdout")]
+    pub stdout: String,
+    /// 标准错误
+    #[serde(rename = "stderr")]
+    pub stderr: String,
+    /// 退出码；超时或启动失败时为 None
+    #[serde(rename = "exitCode")]
+    pub exit_code: Option<i32>,
+    /// 是否因超时被终止
+    #[serde(rename = "timedOut")]
+    pub timed_out: bool,
+    /// 输出是否被截断
+    #[serde(rename = "truncated")]
+    pub truncated: bool,
+    /// 启动失败的原因（启动成功时为 None）
+    #[serde(rename = "error")]
+    pub error: Option<String>,
+    /// 运行耗时（毫秒）
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    /// 结束时间（Unix 毫秒时间戳）
+    #[serde(rename = "finishedAt")]
+    pub finished_at: i64,
+}
+
+impl CommandRun {
+    /// 由捕获结果构造运行记录。
+    pub fn from_output(name: &str, command: &str, output: CommandOutput) -> Self {
+        Self {
+            id: 0,
+            name: name.to_string(),
+            command: command.to_string(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+            exit_code: output.exit_code,
+            timed_out: output.timed_out,
+            truncated: output.truncated,
+            error: None,
+            duration_ms: output.duration_ms,
+            finished_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// 构造启动失败的运行记录。
+    pub fn failed(name: &str, command: &str, error: String) -> Self {
+        Self {
+            id: 0,
+            name: name.to_string(),
+            command: command.to_string(),
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+            timed_out: false,
+            truncated: false,
+            error: Some(error),
+            duration_ms: 0,
+            finished_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// 退出状态的简短描述，用于通知与面板标题。
+    pub fn status_text(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("启动失败: {}", error);
+        }
+        if self.timed_out {
+            return "执行超时，已终止".to_string();
+        }
+        match self.exit_code {
+            Some(code) => format!("退出码 {}", code),
+            None => "进程被终止".to_string(),
+        }
+    }
+
+    /// stdout 与 stderr 的合并文本（两者都有内容时以换行分隔）。
+    pub fn combined_output(&self) -> String {
+        match (self.stdout.is_empty(), self.stderr.is_empty()) {
+            (_, true) => self.stdout.clone(),
+            (true, false) => self.stderr.clone(),
+            (false, false) => format!("{}\n{}", self.stdout.trim_end(), self.stderr),
+        }
+    }
+}
+
+/// 最近运行的有界历史（新记录在前）。
+#[derive(Default)]
+pub(crate) struct CommandRunHistory {
+    /// 运行记录，队首为最新
+    runs: RwLock<VecDeque<CommandRun>>,
+    /// 下一个运行 ID
+    next_id: AtomicU64,
+}
+
+impl CommandRunHistory {
+    /// 写入一条运行记录并分配 ID，超出 MAX_RUNS 时淘汰最早的记录。
+    /// 返回：写入后的记录（含分配的 ID）。
+    pub fn record(&self, mut run: CommandRun) -> CommandRun {
+        run.id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut runs = self.runs.write();
+        runs.push_front(run.clone());
+        runs.truncate(MAX_RUNS);
+        run
+    }
+
+    /// 按 ID 查找运行记录（已被淘汰时返回 None）。
+    pub fn get(&self, id: u64) -> Option<CommandRun> {
+        self.runs.read().iter().find(|run| run.id == id).cloned()
+    }
+
+    /// 全部运行记录（新记录在前）。
+    pub fn list(&self) -> Vec<CommandRun> {
+        self.runs.read().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造指定名称的成功运行记录。
+    fn run(name: &str) -> CommandRun {
+        CommandRun::from_output(
+            name,
+            "true",
+            CommandOutput {
+                exit_code: Some(0),
+                ..CommandOutput::default()
+            },
+        )
+    }
+
+    /// 历史有界：新记录在前，超出上限淘汰最早的记录，ID 单调递增。
+    #[test]
+    fn history_keeps_most_recent_runs() {
+        let history = CommandRunHistory::default();
+        for i in 0..MAX_RUNS + 3 {
+            history.record(run(&format!("run-{}", i)));
+        }
+
+        let runs = history.list();
+        assert_eq!(runs.len(), MAX_RUNS);
+        assert_eq!(runs[0].name, format!("run-{}", MAX_RUNS + 2));
+        assert_eq!(runs[0].id, (MAX_RUNS + 3) as u64);
+        assert!(history.get(1).is_none());
+        assert_eq!(history.get(4).unwrap().name, "run-3");
+    }
+
+    /// 退出状态描述区分启动失败、超时与退出码。
+    #[test]
+    fn status_text_reflects_outcome() {
+        assert_eq!(run("ok").status_text(), "退出码 0");
+
+        let mut timed_out = run("slow");
+        timed_out.exit_code = None;
+        timed_out.timed_out = true;
+        assert_eq!(timed_out.status_text(), "执行超时，已终止");
+
+        let failed = CommandRun::failed("bad", "nope", "not found".to_string());
+        assert_eq!(failed.status_text(), "启动失败: not found");
+    }
+}
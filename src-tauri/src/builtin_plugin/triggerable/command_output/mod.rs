@@ -0,0 +1,6 @@
+mod history;
+mod plugin;
+
+pub(crate) use history::{run_history, CommandRun};
+pub use plugin::CommandOutputPlugin;
+pub(crate) use plugin::COMMAND_OUTPUT_PLUGIN_ID;
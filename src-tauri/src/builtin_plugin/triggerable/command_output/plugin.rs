@@ -0,0 +1,382 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde_json::json;
+use std::sync::Arc;
+use zerolaunch_plugin_api::config::{
+    ComponentCore, ComponentType, Configurable, SettingDefinition,
+};
+use zerolaunch_plugin_api::host::PluginHandle;
+use zerolaunch_plugin_api::services::IconRequest;
+use zerolaunch_plugin_api::{
+    PanelInteraction, PanelKeyAction, PanelKeyBinding, Plugin, PluginContext, PluginError,
+    PluginKind, PluginMetadata, PluginMode, Query, QueryResponse, ResultAction,
+};
+
+use super::history::{run_history, CommandRun};
+
+/// 插件 ID，命令执行器据此唤醒输出面板。
+pub(crate) const COMMAND_OUTPUT_PLUGIN_ID: &str = "command-output";
+/// 面板类型，前端按此匹配 CommandOutputPanel。
+const PANEL_TYPE: &str = "command-output";
+/// 复制标准输出的动作（动作 ID 形如 `<动作>:<运行 ID>`，下同）。
+const COPY_STDOUT: &str = "copy_stdout";
+/// 复制标准错误的动作。
+const COPY_STDERR: &str = "copy_stderr";
+/// 复制合并输出的动作。
+const COPY_ALL: &str = "copy_all";
+
+/// 命令输出面板插件 —— 展示捕获型自定义命令的输出与最近运行历史。
+///
+/// 由命令执行器在命令结束后唤醒（全页面接管），也可经触发词手动查看；
+/// 面板复制动作经 execute_action 委托后端写入剪贴板。
+pub struct CommandOutputPlugin {
+    /// 组件 ID、名称、类型等基础元数据。
+    core: ComponentCore,
+    /// 插件元数据（id、名称、触发词等）。
+    metadata: PluginMetadata,
+    /// PluginHandle（init 时发放），供 execute_action 经句柄访问剪贴板。
+    handle: RwLock<Option<Arc<PluginHandle>>>,
+}
+
+impl Default for CommandOutputPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandOutputPlugin {
+    pub fn new() -> Self {
+        Self {
+            core: ComponentCore::new(
+                COMMAND_OUTPUT_PLUGIN_ID.to_string(),
+                t_key!("command-output", "name").to_string(),
+                t_key!("command-output", "description").to_string(),
+                ComponentType::Plugin,
+                0,
+            ),
+            metadata: PluginMetadata {
+                id: COMMAND_OUTPUT_PLUGIN_ID.to_string(),
+                name: t_key!("command-output", "name").to_string(),
+                version: String::new(),
+                description: t_key!("command-output", "description").to_string(),
+                author: String::new(),
+                trigger_keywords: vec!["output".to_string()],
+                supported_os: vec![
+                    "windows".to_string(),
+                    "macos".to_string(),
+                    "linux".to_string(),
+                ],
+                priority: 100,
+                kind: PluginKind::Builtin,
+                // 面板插件：由命令执行器唤醒或经触发词进入，无全局热键
+                hotkey: None,
+                icon: None,
+                mode: PluginMode::Panel,
+                global_query: false,
+            },
+            handle: RwLock::new(None),
+        }
+    }
+
+    /// 按搜索词选出要展示的运行：空搜索词取最新一次，否则取名称或命令包含该词的最新一次。
+    fn select_run(runs: &[CommandRun], term: &str) -> Option<CommandRun> {
+        let term = term.to_lowercase();
+        runs.iter()
+            .find(|run| {
+                term.is_empty()
+                    || run.name.to_lowercase().contains(&term)
+                    || run.command.to_lowercase().contains(&term)
+            })
+            .cloned()
+    }
+
+    /// 指定运行的复制动作：stdout 为默认动作（Enter），其余按内容是否为空提供。
+    fn copy_actions(run: &CommandRun) -> Vec<ResultAction> {
+        let action = |id: &str, label: &str, is_default: bool| ResultAction {
+            id: format!("{}:{}", id, run.id),
+            label: label.to_string(),
+            icon: IconRequest::Path("copy".to_string()),
+            is_default,
+            shortcut_key: if is_default {
+                "Enter".to_string()
+            } else {
+                String::new()
+            },
+        };
+        let mut actions = vec![action(COPY_STDOUT, "复制输出", true)];
+        if !run.stderr.is_empty() {
+            actions.push(action(COPY_STDERR, "复制错误输出", false));
+            actions.push(action(COPY_ALL, "复制全部", false));
+        }
+        actions
+    }
+}
+
+#[async_trait]
+impl Configurable for CommandOutputPlugin {
+    fn core(&self) -> &ComponentCore {
+        &self.core
+    }
+
+    fn setting_schema(&self) -> Vec<SettingDefinition> {
+        vec![]
+    }
+
+    fn default_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl Plugin for CommandOutputPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    /// 无需异步初始化；仅保存 init 发放的服务句柄，供复制动作写入剪贴板。
+    async fn init(
+        &self,
+        _ctx: &PluginContext,
+        handle: Option<Arc<PluginHandle>>,
+    ) -> Result<(), PluginError> {
+        *self.handle.write() = handle;
+        Ok(())
+    }
+
+    fn interaction_policy(&self) -> PanelInteraction {
+        PanelInteraction {
+            // - Enter：Confirm —— 执行默认动作（复制所展示运行的 stdout）；
+            // - Escape：GoBack —— 退出输出面板（返回默认搜索）。
+            bindings: vec![
+                PanelKeyBinding {
+                    key: "Enter".to_string(),
+                    action: PanelKeyAction::Confirm,
+                },
+                PanelKeyBinding {
+                    key: "Escape".to_string(),
+                    action: PanelKeyAction::GoBack,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    async fn query(
+        &self,
+        _ctx: &PluginContext,
+        query: &Query,
+    ) -> Result<QueryResponse, PluginError> {
+        let runs = run_history().list();
+        let run = Self::select_run(&runs, query.search_term.trim());
+        let actions = run.as_ref().map(Self::copy_actions).unwrap_or_default();
+        Ok(QueryResponse::CustomPanel {
+            panel_type: PANEL_TYPE.to_string(),
+            data: json!({
+                "run": run,
+                "history": runs,
+            }),
+            actions,
+            keep_search_bar: false,
+        })
+    }
+
+    /// 复制动作：按 `<动作>:<运行 ID>` 取对应运行的输出写入剪贴板。
+    async fn execute_action(
+        &self,
+        _ctx: &PluginContext,
+        action_id: &str,
+        _payload: serde_json::Value,
+    ) -> Result<(), PluginError> {
+        let unknown = || PluginError::ActionFailed(format!("未知动作: {}", action_id));
+        let (action, run_id) = action_id.split_once(':').ok_or_else(unknown)?;
+        let run_id: u64 = run_id.parse().map_err(|_| unknown())?;
+        let run = run_history()
+            .get(run_id)
+            .ok_or_else(|| PluginError::ActionFailed(format!("运行记录已过期: {}", run_id)))?;
+        let text = match action {
+            COPY_STDOUT => run.stdout,
+            COPY_STDERR => run.stderr,
+            COPY_ALL => run.combined_output(),
+            _ => return Err(unknown()),
+        };
+        // 经 PluginHandle 访问剪贴板能力（init 时发放）。
+        let handle = self
+            .handle
+            .read()
+            .clone()
+            .ok_or_else(|| PluginError::ActionFailed("插件服务句柄不可用".to_string()))?;
+        handle
+            .set_clipboard_text(&text)
+            .map_err(|e| PluginError::ActionFailed(format!("剪贴板写入失败: {}", e)))
+    }
+}
+
+use crate::plugin_framework::builtin_registry::PluginEntry;
+
+fn build_command_output_plugin() -> (Arc<dyn Configurable>, Arc<dyn Plugin>) {
+    let plugin: Arc<dyn Plugin> = Arc::new(CommandOutputPlugin::new());
+    let configurable: Arc<dyn Configurable> = plugin.clone();
+    (configurable, plugin)
+}
+
+::inventory::submit! {
+    PluginEntry {
+        component_id: "command-output",
+        priority: 0,
+        factory: build_command_output_plugin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerolaunch_plugin_api::mock::helpers::mock_plugin_handle;
+    use zerolaunch_plugin_api::services::shell::CommandOutput;
+
+    /// 构造指定名称、输出的运行记录（未写入历史，ID 为 0）。
+    fn run(name: &str, stdout: &str, stderr: &str) -> CommandRun {
+        CommandRun::from_output(
+            name,
+            &format!("echo {}", name),
+            CommandOutput {
+                stdout: stdout.to_string(),
+                stderr: stderr.to_string(),
+                exit_code: Some(0),
+                ..CommandOutput::default()
+            },
+        )
+    }
+
+    fn query(term: &str) -> Query {
+        Query {
+            id: "test".to_string(),
+            raw_query: format!("output {}", term),
+            search_term: term.to_string(),
+            confirm: false,
+        }
+    }
+
+    /// 空搜索词取最新一次运行，否则按名称或命令（不区分大小写）匹配最新一次。
+    #[test]
+    fn select_run_matches_name_or_command() {
+        let runs = vec![
+            run("Build", "", ""),
+            run("deploy", "", ""),
+            run("build-docs", "", ""),
+        ];
+        assert_eq!(
+            CommandOutputPlugin::select_run(&runs, "").unwrap().name,
+            "Build"
+        );
+        assert_eq!(
+            CommandOutputPlugin::select_run(&runs, "DEPLOY")
+                .unwrap()
+                .name,
+            "deploy"
+        );
+        assert_eq!(
+            CommandOutputPlugin::select_run(&runs, "echo build-")
+                .unwrap()
+                .name,
+            "build-docs"
+        );
+        assert!(CommandOutputPlugin::select_run(&runs, "missing").is_none());
+    }
+
+    /// stdout 复制为默认动作；仅在 stderr 非空时提供错误输出与合并输出的复制。
+    #[test]
+    fn copy_actions_depend_on_stderr() {
+        let mut quiet = run("quiet", "ok", "");
+        quiet.id = 7;
+        let actions = CommandOutputPlugin::copy_actions(&quiet);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].id, "copy_stdout:7");
+        assert!(actions[0].is_default);
+        assert_eq!(actions[0].shortcut_key, "Enter");
+
+        let mut noisy = run("noisy", "ok", "warn");
+        noisy.id = 8;
+        let ids: Vec<_> = CommandOutputPlugin::copy_actions(&noisy)
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, ["copy_stdout:8", "copy_stderr:8", "copy_all:8"]);
+    }
+
+    /// 查询返回输出面板：展示匹配的运行与完整历史，并附带该运行的复制动作。
+    #[tokio::test]
+    async fn query_builds_output_panel() {
+        let recorded = run_history().record(run("panel-query-test", "hello", "oops"));
+        let plugin = CommandOutputPlugin::new();
+        let ctx = PluginContext::new("test");
+
+        let QueryResponse::CustomPanel {
+            panel_type,
+            data,
+            actions,
+            keep_search_bar,
+        } = plugin
+            .query(&ctx, &query("panel-query-test"))
+            .await
+            .unwrap()
+        else {
+            panic!("期望 CustomPanel");
+        };
+        assert_eq!(panel_type, PANEL_TYPE);
+        assert!(!keep_search_bar);
+        assert_eq!(data["run"]["id"], recorded.id);
+        assert_eq!(data["run"]["stdout"], "hello");
+        assert_eq!(data["run"]["exitCode"], 0);
+        assert!(data["history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["id"] == recorded.id));
+        assert_eq!(actions.len(), 3);
+        assert_eq!(actions[0].id, format!("copy_stdout:{}", recorded.id));
+
+        let QueryResponse::CustomPanel { data, actions, .. } = plugin
+            .query(&ctx, &query("no-such-command-output-run"))
+            .await
+            .unwrap()
+        else {
+            panic!("期望 CustomPanel");
+        };
+        assert!(data["run"].is_null());
+        assert!(actions.is_empty());
+    }
+
+    /// 复制动作：格式错误、未知动作与过期运行均报错；句柄未发放时报错，发放后写入成功。
+    #[tokio::test]
+    async fn execute_action_validates_action_and_run() {
+        let recorded = run_history().record(run("copy-action-test", "out", "err"));
+        let plugin = CommandOutputPlugin::new();
+        let ctx = PluginContext::new("test");
+        let copy_stdout = format!("copy_stdout:{}", recorded.id);
+
+        for action_id in [
+            "copy_stdout".to_string(),
+            "copy_stdout:abc".to_string(),
+            format!("paste:{}", recorded.id),
+            format!("copy_stdout:{}", u64::MAX),
+        ] {
+            let result = plugin.execute_action(&ctx, &action_id, json!({})).await;
+            assert!(
+                matches!(result, Err(PluginError::ActionFailed(_))),
+                "{}",
+                action_id
+            );
+        }
+
+        let no_handle = plugin.execute_action(&ctx, &copy_stdout, json!({})).await;
+        assert!(matches!(no_handle, Err(PluginError::ActionFailed(_))));
+
+        plugin.init(&ctx, Some(mock_plugin_handle())).await.unwrap();
+        for action in [COPY_STDOUT, COPY_STDERR, COPY_ALL] {
+            plugin
+                .execute_action(&ctx, &format!("{}:{}", action, recorded.id), json!({}))
+                .await
+                .unwrap();
+        }
+    }
+}
@@ -1,2 +1,4 @@
-pub mod calculator_plugin;
+pub mod calculator;
+pub mod clipboard_history;
+pub mod command_output;
 pub mod translator;
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use tracing::info;
 use zerolaunch_plugin_api::config::{
     ComponentCore, ComponentType, ConfigError, Configurable, FieldUiMetadata, PrimitiveType,
-    SchemaKind, SchemaNode, SettingDefinition,
+    SchemaKind, SchemaNode, SecretString, SettingDefinition,
 };
 use zerolaunch_plugin_api::host::PluginHandle;
 use zerolaunch_plugin_api::services::IconRequest;
@@ -103,9 +103,9 @@ struct TranslatorSettings {
     /// LLM 服务 Base URL。
     #[serde(rename = "llm_base_url", default)]
     llm_base_url: String,
-    /// LLM API Key。
+    /// LLM API Key（机密字段，不写入配置文件，`Debug` 输出时脱敏）。
     #[serde(rename = "llm_api_key", default)]
-    llm_api_key: String,
+    llm_api_key: SecretString,
     /// LLM 模型名。
     #[serde(rename = "llm_model", default)]
     llm_model: String,
@@ -203,7 +203,7 @@ impl Default for TranslatorSettings {
             llm_vendor: default_llm_vendor(),
             llm_vendor_options: default_llm_vendor_options(),
             llm_base_url: String::new(),
-            llm_api_key: String::new(),
+            llm_api_key: SecretString::default(),
             llm_model: String::new(),
         }
     }
@@ -296,6 +296,7 @@ impl TranslatorPlugin {
                 hotkey: None,
                 icon: None,
                 mode: PluginMode::Inline,
+                global_query: false,
             },
             inner: RwLock::new(TranslatorSettings::default()),
             llm_config,
@@ -666,7 +667,7 @@ impl Configurable for TranslatorPlugin {
             .order(10)
             .default("")
             .build(),
-            SchemaBuilder::text(
+            SchemaBuilder::secret(
                 "llm_api_key",
                 t_key!("translator", "fields.llm_api_key.label"),
                 t_key!("translator", "fields.llm_api_key.desc"),
@@ -842,15 +843,26 @@ impl Plugin for TranslatorPlugin {
             target: parsed.target.clone(),
         };
 
+        // 渐进展示：已有引擎成功时即推送中间面板，慢引擎不阻塞整个面板；
+        // 宿主按查询版本丢弃过期推送，最终面板仍随查询响应下发。
+        let handle = self.handle.read().clone();
         let agg = self
             .registry
-            .translate_all(
+            .translate_all_with_progress(
                 &req,
                 &settings.enabled_providers,
                 settings.preferred_provider_id(),
                 settings.request_timeout_ms,
                 &ctx.trace_id,
                 ctx.query_revision(),
+                |partial| {
+                    if let Some(handle) = &handle {
+                        handle.push_query_update(
+                            ctx,
+                            Self::aggregate_to_panel(&parsed, partial.clone()),
+                        );
+                    }
+                },
             )
             .await;
 
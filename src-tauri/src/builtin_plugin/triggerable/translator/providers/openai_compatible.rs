@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde::Deserialize;
 use tracing::{error, warn};
+use zerolaunch_plugin_api::config::SecretString;
 
 use super::super::provider::{
     LanguageSupport, SenseEntry, TranslateRequest, TranslationProvider, TranslationResult,
@@ -19,8 +20,8 @@ pub const PROVIDER_ID: &str = "openai-compatible";
 pub struct LlmConfig {
     /// API Base URL（如 `https://api.deepseek.com`）。
     pub base_url: String,
-    /// API Key。
-    pub api_key: String,
+    /// API Key（`Debug` 输出时脱敏）。
+    pub api_key: SecretString,
     /// 模型名（如 `deepseek-chat`）。
     pub model: String,
 }
@@ -383,7 +384,7 @@ fn missing_config_error() -> String {
 
 fn config_ready(config: &LlmConfig) -> bool {
     !config.base_url.trim().is_empty()
-        && !config.api_key.trim().is_empty()
+        && !config.api_key.expose().trim().is_empty()
         && !config.model.trim().is_empty()
 }
 
@@ -447,7 +448,7 @@ impl TranslationProvider for OpenAiCompatibleProvider {
         let response = match self
             .client
             .post(&url)
-            .bearer_auth(config.api_key.trim())
+            .bearer_auth(config.api_key.expose().trim())
             .json(&body)
             .send()
             .await
@@ -70,6 +70,32 @@ impl ProviderRegistry {
         timeout_ms: u64,
         trace_id: &str,
         query_revision: u64,
+    ) -> AggregateResult {
+        self.translate_all_with_progress(
+            req,
+            enabled,
+            primary_id,
+            timeout_ms,
+            trace_id,
+            query_revision,
+            |_| {},
+        )
+        .await
+    }
+
+    /// 同 translate_all，但每有引擎完成且已有可用主结果时，以「已完成引擎」的聚合结果
+    /// 回调 on_progress（不含最后一个引擎——全部完成的结果即返回值），
+    /// 供插件推送增量面板，避免单个慢引擎阻塞整个面板。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn translate_all_with_progress(
+        &self,
+        req: &TranslateRequest,
+        enabled: &[String],
+        primary_id: &str,
+        timeout_ms: u64,
+        trace_id: &str,
+        query_revision: u64,
+        on_progress: impl Fn(&AggregateResult),
     ) -> AggregateResult {
         // 按用户启用顺序选取（拖拽调序依赖此顺序）
         let selected: Vec<_> = enabled
@@ -133,46 +159,63 @@ impl ProviderRegistry {
                 }
                 Err(e) => orphans.push(TranslationResult::err("unknown", "未知", e.to_string())),
             }
+            if !set.is_empty() {
+                let partial = aggregate(&selected_ids, by_id.clone(), orphans.clone(), primary_id);
+                if partial.primary.is_some() {
+                    on_progress(&partial);
+                }
+            }
         }
 
-        let mut results: Vec<TranslationResult> = selected_ids
-            .iter()
-            .filter_map(|id| by_id.remove(id))
-            .collect();
-        results.extend(by_id.into_values());
-        results.extend(orphans);
+        aggregate(&selected_ids, by_id, orphans, primary_id)
+    }
+}
 
-        apply_mock_mirror(&mut results);
+/// 按启用顺序重建结果并聚合主结果、备选与整体状态。
+/// 参数：selected_ids - 启用顺序；by_id - 已完成引擎的结果；orphans - 任务异常占位。
+fn aggregate(
+    selected_ids: &[String],
+    mut by_id: HashMap<String, TranslationResult>,
+    orphans: Vec<TranslationResult>,
+    primary_id: &str,
+) -> AggregateResult {
+    let mut results: Vec<TranslationResult> = selected_ids
+        .iter()
+        .filter_map(|id| by_id.remove(id))
+        .collect();
+    results.extend(by_id.into_values());
+    results.extend(orphans);
 
-        let primary_idx = results
-            .iter()
-            .position(|r| r.provider_id == primary_id && r.is_success())
-            .or_else(|| results.iter().position(|r| r.is_success()));
-
-        let status = match (
-            primary_idx.is_some(),
-            results.iter().any(|r| !r.is_success()),
-            results.iter().all(|r| !r.is_success()),
-        ) {
-            (_, _, true) => AggregateStatus::Error,
-            (true, true, false) => AggregateStatus::Partial,
-            (true, false, false) => AggregateStatus::Ok,
-            (false, _, _) => AggregateStatus::Error,
-        };
+    apply_mock_mirror(&mut results);
 
-        let primary = primary_idx.map(|i| results[i].clone());
-        let alternatives = results
-            .into_iter()
-            .enumerate()
-            .filter(|(i, _)| Some(*i) != primary_idx)
-            .map(|(_, r)| r)
-            .collect();
+    let primary_idx = results
+        .iter()
+        .position(|r| r.provider_id == primary_id && r.is_success())
+        .or_else(|| results.iter().position(|r| r.is_success()));
+
+    let status = match (
+        primary_idx.is_some(),
+        results.iter().any(|r| !r.is_success()),
+        results.iter().all(|r| !r.is_success()),
+    ) {
+        (_, _, true) => AggregateStatus::Error,
+        (true, true, false) => AggregateStatus::Partial,
+        (true, false, false) => AggregateStatus::Ok,
+        (false, _, _) => AggregateStatus::Error,
+    };
 
-        AggregateResult {
-            primary,
-            alternatives,
-            status,
-        }
+    let primary = primary_idx.map(|i| results[i].clone());
+    let alternatives = results
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != primary_idx)
+        .map(|(_, r)| r)
+        .collect();
+
+    AggregateResult {
+        primary,
+        alternatives,
+        status,
     }
 }
 
@@ -517,4 +560,39 @@ mod tests {
         assert_eq!(primary.provider_id, "b");
         assert_eq!(primary.text, "from-b");
     }
+
+    #[tokio::test]
+    async fn progress_reports_fast_success_before_slow_provider() {
+        let registry = ProviderRegistry::new(vec![
+            Arc::new(SlowFailProvider),
+            Arc::new(OkProvider {
+                id: "fast",
+                text: "ok",
+            }),
+        ]);
+        let req = sample_request();
+        let progress = std::sync::Mutex::new(Vec::new());
+
+        let result = registry
+            .translate_all_with_progress(
+                &req,
+                &enabled(&["slow", "fast"]),
+                "slow",
+                1000,
+                "test",
+                0,
+                |partial| progress.lock().unwrap().push(partial.clone()),
+            )
+            .await;
+
+        // 快引擎完成后推送一次中间结果（慢引擎尚未完成，不在结果中）
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(progress.len(), 1);
+        let partial_primary = progress[0].primary.as_ref().expect("中间结果应有主结果");
+        assert_eq!(partial_primary.provider_id, "fast");
+        assert!(progress[0].alternatives.is_empty());
+        // 最终结果包含全部引擎
+        assert_eq!(result.status, AggregateStatus::Partial);
+        assert_eq!(result.alternatives.len(), 1);
+    }
 }
@@ -0,0 +1,120 @@
+//! CLI HTTP API 的错误响应。
+//!
+//! 写路由失败时沿用 IPC 的 `BridgeError` 结构（code / message / details / componentId / traceId），
+//! HTTP 状态码由 `code` 映射；配置校验失败时 `details.issues` 逐字段列出问题，
+//! 便于脚本按字段定位而不必解析文案；执行候选项用户参数不足时 `details` 给出所需与缺少的参数。
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use zerolaunch_plugin_api::config::{ConfigError, SettingIssue};
+use zerolaunch_plugin_api::services::parameter::types::UserParameter;
+use zerolaunch_plugin_api::CandidateId;
+
+use crate::commands::bridge_error::{BridgeError, ErrorCode};
+use crate::plugin_framework::SessionDispatcherError;
+
+/// CLI 写路由的错误响应包装。
+#[derive(Debug)]
+pub struct ApiError(pub BridgeError);
+
+impl ApiError {
+    /// 配置写入失败：附带组件 id；校验失败统一补齐 `details.issues`。
+    pub fn config(err: ConfigError, component_id: &str, trace_id: &str) -> Self {
+        let mut err = BridgeError::from(err).with_trace_id(trace_id);
+        err.component_id = Some(component_id.to_string());
+        if matches!(err.code, ErrorCode::ValidationFailed) && err.details.is_none() {
+            // 组件自定义业务校验只给出一条整体描述，不对应具体字段
+            let issue = SettingIssue {
+                pointer: String::new(),
+                message: err.message.clone(),
+            };
+            err.details = Some(serde_json::json!({ "issues": [issue] }));
+        }
+        Self(err)
+    }
+
+    /// schema 校验未通过：一次性返回全部出错字段。
+    pub fn invalid_settings(component_id: &str, issues: Vec<SettingIssue>, trace_id: &str) -> Self {
+        let message = issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        let mut err = BridgeError::validation_failed(message).with_trace_id(trace_id);
+        err.component_id = Some(component_id.to_string());
+        err.details = Some(serde_json::json!({ "issues": issues }));
+        Self(err)
+    }
+
+    /// 执行/确认失败：执行器与插件动作的失败归为 `ACTION_FAILED`，其余沿用通用映射。
+    pub fn dispatch(err: SessionDispatcherError, trace_id: &str) -> Self {
+        let action_failed = matches!(
+            err,
+            SessionDispatcherError::ExecutionError(_) | SessionDispatcherError::PluginError(_)
+        );
+        let mut err = BridgeError::from(err).with_trace_id(trace_id);
+        if action_failed {
+            err.code = ErrorCode::ActionFailed;
+        }
+        Self(err)
+    }
+
+    /// 候选项的用户参数不足：`details` 列出全部参数（名称与默认值）、已提供个数，
+    /// 以及未提供且无默认值的参数（`missing`，位置参数记为 `#序号`），供调用方补齐后重试。
+    pub fn missing_user_args(
+        candidate_id: CandidateId,
+        user_args: Vec<UserParameter>,
+        provided: usize,
+        trace_id: &str,
+    ) -> Self {
+        let missing: Vec<String> = user_args
+            .iter()
+            .enumerate()
+            .skip(provided)
+            .filter(|(_, p)| p.default.is_none())
+            .map(|(i, p)| p.name.clone().unwrap_or_else(|| format!("#{}", i + 1)))
+            .collect();
+        let mut err = BridgeError::validation_failed(format!(
+            "候选项 {} 需要 {} 个用户参数，已提供 {} 个，缺少: {}",
+            candidate_id,
+            user_args.len(),
+            provided,
+            missing.join(", ")
+        ))
+        .with_trace_id(trace_id);
+        err.details = Some(serde_json::json!({
+            "candidateId": candidate_id,
+            "userArgCount": user_args.len(),
+            "userArgs": user_args,
+            "providedCount": provided,
+            "missing": missing,
+        }));
+        Self(err)
+    }
+}
+
+impl From<BridgeError> for ApiError {
+    fn from(err: BridgeError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    /// 按错误码选择 HTTP 状态码，响应体为 `BridgeError` JSON。
+    fn into_response(self) -> Response {
+        let status = match self.0.code {
+            ErrorCode::ComponentNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::InvalidQuery | ErrorCode::ValidationFailed => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ErrorCode::AlreadyInstalled | ErrorCode::ComponentIdCollision => StatusCode::CONFLICT,
+            ErrorCode::NetworkError => StatusCode::BAD_GATEWAY,
+            ErrorCode::ActionFailed
+            | ErrorCode::PluginError
+            | ErrorCode::ConfigError
+            | ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(self.0)).into_response()
+    }
+}
@@ -1,10 +1,30 @@
 use axum::extract::{Path, State};
+use axum::Extension;
 use axum::Json;
+use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::cli_server::error::ApiError;
+use crate::cli_server::middleware::TraceId;
 use crate::commands::config_file::{ComponentInfoDto, ComponentSchemaDto};
 use crate::state::app_state::AppState;
 
+/// `PUT /v1/config/:id/enabled` 与 `PUT /v1/plugins/:id/enabled` 的请求体。
+#[derive(Debug, Deserialize)]
+pub struct EnabledRequest {
+    /// 目标启用状态
+    #[serde(rename = "enabled", default)]
+    pub enabled: bool,
+}
+
+/// `POST /v1/config/:id/actions/:action` 的请求体。
+#[derive(Debug, Deserialize)]
+pub struct ActionRequest {
+    /// 动作参数，原样交给组件的 `execute_config_action`
+    #[serde(rename = "params", default)]
+    pub params: serde_json::Value,
+}
+
 pub async fn list_components(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     let cm = state.get_config_manager();
     let components: Vec<ComponentInfoDto> = cm
@@ -24,12 +44,15 @@ pub async fn get_schema(
     Json(serde_json::to_value(schema).unwrap_or_default())
 }
 
+/// 组件当前配置值；机密字段脱敏后返回，明文只留在本机进程内。
 pub async fn get_settings(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Json<serde_json::Value> {
     let cm = state.get_config_manager();
-    let settings = cm.get_settings(&id).unwrap_or(serde_json::Value::Null);
+    let settings = cm
+        .get_redacted_settings(&id)
+        .unwrap_or(serde_json::Value::Null);
     Json(settings)
 }
 
@@ -41,3 +64,349 @@ pub async fn get_actions(
     let actions = cm.get_config_actions(&id);
     Json(serde_json::to_value(actions).unwrap_or_default())
 }
+
+/// PATCH /v1/config/:id/settings — 按顶层键部分写入配置。
+///
+/// 合并到当前配置后先按 schema 一次性报告全部出错字段，再经 `ConfigManager::apply_settings`
+/// （组件 `validate_settings` → 应用 → 持久化 → 事件）写入；返回写入后的脱敏配置。
+/// 机密字段写回 `SECRET_REDACTED` 或缺省表示保持原值。
+pub async fn patch_settings(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let cm = state.get_config_manager();
+    let settings = cm
+        .merge_settings(&id, patch)
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    let issues = cm
+        .validation_issues(&id, &settings)
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    if !issues.is_empty() {
+        return Err(ApiError::invalid_settings(&id, issues, &trace_id.0));
+    }
+    cm.apply_settings(&id, settings)
+        .await
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    Ok(Json(
+        cm.get_redacted_settings(&id)
+            .unwrap_or(serde_json::Value::Null),
+    ))
+}
+
+/// POST /v1/config/:id/reset — 重置为默认配置，返回重置后的脱敏配置。
+pub async fn reset_settings(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let cm = state.get_config_manager();
+    cm.reset_to_default(&id)
+        .await
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    Ok(Json(
+        cm.get_redacted_settings(&id)
+            .unwrap_or(serde_json::Value::Null),
+    ))
+}
+
+/// PUT /v1/config/:id/enabled — 设置组件启用状态。
+pub async fn set_enabled(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+    Json(req): Json<EnabledRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .get_config_manager()
+        .set_enabled(&id, req.enabled)
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    Ok(Json(
+        serde_json::json!({ "componentId": id, "enabled": req.enabled }),
+    ))
+}
+
+/// POST /v1/config/:id/actions/:action — 执行组件配置动作，返回动作结果。
+pub async fn run_action(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path((id, action)): Path<(String, String)>,
+    Json(req): Json<ActionRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let result = state
+        .get_config_manager()
+        .execute_config_action(&id, &action, &req.params)
+        .await
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{app, request, send, send_json};
+    use super::*;
+    use crate::core::config::manager::ConfigManager;
+    use crate::core::config::secret_store::MemorySecretStore;
+    use crate::core::config::setting_builders::SchemaBuilder;
+    use async_trait::async_trait;
+    use axum::http::StatusCode;
+    use parking_lot::RwLock;
+    use serde_json::json;
+    use zerolaunch_plugin_api::config::{
+        ComponentCore, ComponentType, ConfigActionDef, ConfigError, Configurable,
+        SettingDefinition, SECRET_REDACTED,
+    };
+
+    /// 测试组件 `cli-test`：整数 count（1–10）、文本 name 与机密 token；
+    /// 配置动作 `echo` 原样返回参数。
+    struct CliTestComponent {
+        /// 组件 ID、名称、类型等元数据。
+        core: ComponentCore,
+        /// 当前设置值。
+        settings: RwLock<serde_json::Value>,
+    }
+
+    #[async_trait]
+    impl Configurable for CliTestComponent {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            vec![
+                SchemaBuilder::integer("count", "Count", "")
+                    .min(1.0)
+                    .max(10.0)
+                    .default(5)
+                    .build(),
+                SchemaBuilder::text("name", "Name", "").default("").build(),
+                SchemaBuilder::secret("token", "Token", "")
+                    .default("")
+                    .build(),
+            ]
+        }
+        fn get_settings(&self) -> serde_json::Value {
+            self.settings.read().clone()
+        }
+        async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+            *self.settings.write() = settings;
+            Ok(())
+        }
+        fn config_actions(&self) -> Vec<ConfigActionDef> {
+            vec![ConfigActionDef {
+                action: "echo".to_string(),
+                label: "Echo".to_string(),
+                description: String::new(),
+            }]
+        }
+        async fn execute_config_action(
+            &self,
+            action: &str,
+            params: &serde_json::Value,
+        ) -> Result<serde_json::Value, String> {
+            match action {
+                "echo" => Ok(params.clone()),
+                _ => Err(format!("Unknown config action: {}", action)),
+            }
+        }
+    }
+
+    /// 构造注册了 `cli-test` 组件的应用状态（配置写入临时目录，机密存于内存）。
+    async fn state_with_component(dir: &tempfile::TempDir) -> Arc<AppState> {
+        let cm = ConfigManager::with_secret_store(
+            dir.path().into(),
+            Arc::new(MemorySecretStore::default()),
+        );
+        cm.register(Arc::new(CliTestComponent {
+            core: ComponentCore::new(
+                "cli-test".into(),
+                "CLI 测试".into(),
+                String::new(),
+                ComponentType::Core,
+                0,
+            ),
+            settings: RwLock::new(json!({ "count": 5, "name": "demo", "token": "" })),
+        }))
+        .await;
+        let state = Arc::new(AppState::new());
+        state.set_config_manager(Arc::new(cm));
+        state
+    }
+
+    /// 写路由缺少或携带错误 token 时返回 401，配置保持不变。
+    #[tokio::test]
+    async fn write_routes_require_bearer_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_component(&dir).await;
+        let routes = [
+            (
+                "PATCH",
+                "/v1/config/cli-test/settings",
+                json!({ "count": 9 }),
+            ),
+            ("POST", "/v1/config/cli-test/reset", json!({})),
+            (
+                "PUT",
+                "/v1/config/cli-test/enabled",
+                json!({ "enabled": false }),
+            ),
+            ("POST", "/v1/config/cli-test/actions/echo", json!({})),
+        ];
+        for (method, uri, body) in routes {
+            for token in [None, Some("wrong-token")] {
+                let (status, _) = send(
+                    app(state.clone()),
+                    request(method, uri, token, Some(body.clone())),
+                )
+                .await;
+                assert_eq!(status, StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+            }
+        }
+        let cm = state.get_config_manager();
+        assert_eq!(cm.get_settings("cli-test").unwrap()["count"], 5);
+        assert!(cm.is_enabled("cli-test"));
+    }
+
+    /// 部分写入：未出现的字段保持原值，机密字段写入后脱敏返回。
+    #[tokio::test]
+    async fn patch_settings_merges_and_redacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_component(&dir).await;
+        let (status, body) = send_json(
+            app(state.clone()),
+            "PATCH",
+            "/v1/config/cli-test/settings",
+            Some(json!({ "count": 7, "token": "s3cret" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 7);
+        assert_eq!(body["name"], "demo");
+        assert_eq!(body["token"], SECRET_REDACTED);
+        assert_eq!(
+            state.get_config_manager().get_settings("cli-test").unwrap()["token"],
+            "s3cret"
+        );
+    }
+
+    /// schema 校验失败返回 422，`details.issues` 一次列出全部出错字段，配置保持不变。
+    #[tokio::test]
+    async fn patch_settings_reports_every_invalid_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_component(&dir).await;
+        let (status, body) = send_json(
+            app(state.clone()),
+            "PATCH",
+            "/v1/config/cli-test/settings",
+            Some(json!({ "count": 0, "bogus": true })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(body["componentId"], "cli-test");
+        assert!(body["traceId"].is_string());
+        let mut pointers: Vec<&str> = body["details"]["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["pointer"].as_str().unwrap())
+            .collect();
+        pointers.sort_unstable();
+        assert_eq!(pointers, ["/bogus", "/count"]);
+        assert_eq!(
+            state.get_config_manager().get_settings("cli-test").unwrap()["count"],
+            5
+        );
+
+        // 非对象的写入体同样为 422，整体问题记在空 pointer 下
+        let (status, body) = send_json(
+            app(state),
+            "PATCH",
+            "/v1/config/cli-test/settings",
+            Some(json!([1, 2])),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["details"]["issues"][0]["pointer"], "");
+    }
+
+    /// 未注册的组件在各写路由上均返回 404。
+    #[tokio::test]
+    async fn unknown_component_is_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_component(&dir).await;
+        let routes = [
+            (
+                "PATCH",
+                "/v1/config/missing/settings",
+                json!({ "count": 1 }),
+            ),
+            ("POST", "/v1/config/missing/reset", json!({})),
+            (
+                "PUT",
+                "/v1/config/missing/enabled",
+                json!({ "enabled": true }),
+            ),
+            ("POST", "/v1/config/missing/actions/echo", json!({})),
+        ];
+        for (method, uri, body) in routes {
+            let (status, body) = send_json(app(state.clone()), method, uri, Some(body)).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{} {}", method, uri);
+            assert_eq!(body["code"], "COMPONENT_NOT_FOUND");
+            assert_eq!(body["componentId"], "missing");
+        }
+    }
+
+    /// 启用状态、重置与配置动作：成功时返回写入后的状态或动作结果，未知动作为 500。
+    #[tokio::test]
+    async fn enabled_reset_and_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = state_with_component(&dir).await;
+        let cm = state.get_config_manager();
+
+        let (status, body) = send_json(
+            app(state.clone()),
+            "PUT",
+            "/v1/config/cli-test/enabled",
+            Some(json!({ "enabled": false })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "componentId": "cli-test", "enabled": false }));
+        assert!(!cm.is_enabled("cli-test"));
+
+        cm.apply_settings("cli-test", json!({ "count": 9, "name": "x", "token": "" }))
+            .await
+            .unwrap();
+        let (status, body) = send_json(
+            app(state.clone()),
+            "POST",
+            "/v1/config/cli-test/reset",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 5);
+        assert_eq!(body["name"], "");
+
+        let (status, body) = send_json(
+            app(state.clone()),
+            "POST",
+            "/v1/config/cli-test/actions/echo",
+            Some(json!({ "params": { "hello": "world" } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "hello": "world" }));
+
+        let (status, body) = send_json(
+            app(state),
+            "POST",
+            "/v1/config/cli-test/actions/unknown",
+            Some(json!({})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "CONFIG_ERROR");
+    }
+}
@@ -0,0 +1,305 @@
+//! 宿主事件路由：`GET /v1/events`（SSE 实时推送）与 `GET /v1/events/recent`（最近历史）。
+//!
+//! 两者都接受 `types=session,query,...` 过滤事件类型（省略即全部）与 `limit`：
+//! SSE 先回放最近 `limit` 条匹配历史（默认不回放）再推送实时事件，按 `seq` 去重衔接；
+//! 订阅者积压过多时推送一条 `lagged` 事件告知丢弃条数。
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::Extension;
+use axum::Json;
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::cli_server::error::ApiError;
+use crate::cli_server::middleware::TraceId;
+use crate::commands::bridge_error::BridgeError;
+use crate::core::host_event::{HostEvent, HostEventBus, HostEventRecord};
+use crate::state::app_state::AppState;
+
+/// `/v1/events` 与 `/v1/events/recent` 的查询参数。
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// 逗号分隔的事件类型；省略或为空表示全部
+    #[serde(rename = "types", default)]
+    pub types: String,
+    /// 历史条数：SSE 为回放条数（默认 0），recent 为返回条数（默认全部保留的历史）
+    #[serde(rename = "limit", default)]
+    pub limit: Option<usize>,
+}
+
+/// GET /v1/events — SSE 推送宿主事件（事件名为 `type`，`id` 为 `seq`，数据为事件 JSON）。
+pub async fn handle_stream(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let types = parse_types(&query.types, &trace_id.0)?;
+    let bus = state.get_host_events();
+    // 先订阅再读历史：两者之间发布的事件可能重复出现，实时流按 seq 跳过已回放部分
+    let receiver = bus.subscribe();
+    let (replayed, last_seq) = recent_matching(&bus, &types, query.limit.unwrap_or(0));
+
+    let live = stream::unfold(receiver, move |mut receiver| {
+        let types = types.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) if record.seq > last_seq && matches_types(&types, &record) => {
+                        return Some((sse_event(&record), receiver));
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        let data = serde_json::json!({ "type": "lagged", "skipped": skipped });
+                        let event = Event::default().event("lagged").data(data.to_string());
+                        return Some((Ok(event), receiver));
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+    let stream = stream::iter(replayed.iter().map(sse_event).collect::<Vec<_>>()).chain(live);
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// GET /v1/events/recent — 最近的匹配历史事件（按 seq 升序）。
+pub async fn handle_recent(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Json<Vec<HostEventRecord>>, ApiError> {
+    let types = parse_types(&query.types, &trace_id.0)?;
+    let bus = state.get_host_events();
+    let (records, _) = recent_matching(&bus, &types, query.limit.unwrap_or(usize::MAX));
+    Ok(Json(records))
+}
+
+/// 解析事件类型过滤条件；未知类型返回 422 并列出可选值。
+fn parse_types(spec: &str, trace_id: &str) -> Result<Vec<&'static str>, ApiError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            HostEvent::KINDS
+                .iter()
+                .copied()
+                .find(|kind| *kind == name)
+                .ok_or_else(|| {
+                    BridgeError::validation_failed(format!(
+                        "未知事件类型 '{}'（可选: {}）",
+                        name,
+                        HostEvent::KINDS.join(", ")
+                    ))
+                    .with_trace_id(trace_id)
+                    .into()
+                })
+        })
+        .collect()
+}
+
+/// 事件是否通过类型过滤（空过滤条件匹配全部）。
+fn matches_types(types: &[&'static str], record: &HostEventRecord) -> bool {
+    types.is_empty() || types.contains(&record.event.kind())
+}
+
+/// 取最近 `limit` 条匹配事件，并返回历史快照中的最大 seq（实时流据此去重）。
+fn recent_matching(
+    bus: &HostEventBus,
+    types: &[&'static str],
+    limit: usize,
+) -> (Vec<HostEventRecord>, u64) {
+    let history = bus.recent(usize::MAX);
+    let last_seq = history.last().map_or(0, |record| record.seq);
+    let mut matched: Vec<HostEventRecord> = history
+        .into_iter()
+        .filter(|record| matches_types(types, record))
+        .collect();
+    let skip = matched.len().saturating_sub(limit);
+    matched.drain(..skip);
+    (matched, last_seq)
+}
+
+/// 转为 SSE 事件：事件名为类型，id 为序号。
+fn sse_event(record: &HostEventRecord) -> Result<Event, Infallible> {
+    let event = Event::default()
+        .event(record.event.kind())
+        .id(record.seq.to_string());
+    Ok(event
+        .json_data(record)
+        .unwrap_or_else(|e| Event::default().comment(format!("事件序列化失败: {}", e))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{app, request, send, send_json, TOKEN};
+    use super::*;
+    use crate::core::host_event::{ConfigChange, PluginChange};
+    use axum::http::StatusCode;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn plugin_event(change: PluginChange) -> HostEvent {
+        HostEvent::Plugin {
+            plugin_id: "demo".to_string(),
+            change,
+        }
+    }
+
+    fn config_event() -> HostEvent {
+        HostEvent::Config {
+            component_id: "cli-test".to_string(),
+            change: ConfigChange::Settings,
+            enabled: None,
+        }
+    }
+
+    /// 解析一个 SSE 帧的 `event` / `id` / `data` 字段。
+    fn parse_frame(frame: &str) -> (String, String, serde_json::Value) {
+        let field = |name: &str| {
+            frame
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{}: ", name)))
+                .unwrap_or_default()
+                .to_string()
+        };
+        let data = serde_json::from_str(&field("data")).unwrap();
+        (field("event"), field("id"), data)
+    }
+
+    /// 两个事件路由缺少或携带错误 token 时均返回 401。
+    #[tokio::test]
+    async fn event_routes_require_bearer_token() {
+        let state = Arc::new(AppState::new());
+        for uri in ["/v1/events", "/v1/events/recent"] {
+            for token in [None, Some("wrong-token")] {
+                let (status, _) = send(app(state.clone()), request("GET", uri, token, None)).await;
+                assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", uri);
+            }
+        }
+    }
+
+    /// 未知事件类型返回 422，错误信息列出可选类型。
+    #[tokio::test]
+    async fn unknown_event_type_is_rejected() {
+        let state = Arc::new(AppState::new());
+        for uri in [
+            "/v1/events?types=plugin,bogus",
+            "/v1/events/recent?types=bogus",
+        ] {
+            let (status, body) = send_json(app(state.clone()), "GET", uri, None).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", uri);
+            assert_eq!(body["code"], "VALIDATION_FAILED");
+            let message = body["message"].as_str().unwrap();
+            assert!(message.contains("bogus"), "{}", message);
+            assert!(
+                message.contains(&HostEvent::KINDS.join(", ")),
+                "{}",
+                message
+            );
+        }
+    }
+
+    /// recent 按类型过滤并只保留最近 `limit` 条，按 seq 升序返回扁平事件 JSON。
+    #[tokio::test]
+    async fn recent_filters_by_type_and_limit() {
+        let state = Arc::new(AppState::new());
+        let bus = state.get_host_events();
+        bus.publish(plugin_event(PluginChange::Crashed));
+        bus.publish(config_event());
+        bus.publish(plugin_event(PluginChange::Restarted));
+        bus.publish(config_event());
+
+        let (status, body) = send_json(
+            app(state.clone()),
+            "GET",
+            "/v1/events/recent?types=plugin&limit=5",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let records = body.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["seq"], 1);
+        assert_eq!(records[0]["type"], "plugin");
+        assert_eq!(records[0]["pluginId"], "demo");
+        assert_eq!(records[0]["change"], "crashed");
+        assert!(records[0]["timestamp"].is_string());
+        assert_eq!(records[1]["change"], "restarted");
+
+        let (_, body) = send_json(app(state), "GET", "/v1/events/recent?limit=2", None).await;
+        let seqs: Vec<u64> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["seq"].as_u64().unwrap())
+            .collect();
+        assert_eq!(seqs, [3, 4]);
+    }
+
+    /// SSE：先回放最近 `limit` 条匹配历史，再推送订阅后发布的匹配事件；
+    /// 事件名为类型、id 为 seq、data 为事件 JSON。
+    #[tokio::test]
+    async fn stream_replays_history_then_pushes_live_events() {
+        let state = Arc::new(AppState::new());
+        let bus = state.get_host_events();
+        bus.publish(plugin_event(PluginChange::Crashed));
+        bus.publish(config_event());
+        bus.publish(plugin_event(PluginChange::Restarted));
+
+        let response = app(state)
+            .oneshot(request(
+                "GET",
+                "/v1/events?types=plugin&limit=1",
+                Some(TOKEN),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            "text/event-stream"
+        );
+
+        // 订阅已建立：此后发布的事件经实时流推送（config 类型被过滤）
+        bus.publish(config_event());
+        bus.publish(plugin_event(PluginChange::Crashed));
+
+        let mut body = response.into_body().into_data_stream();
+        let mut text = String::new();
+        let frames = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                // 只取以空行结束的完整帧
+                let complete = text.rsplit_once("\n\n").map_or("", |(done, _)| done);
+                let frames: Vec<String> = complete
+                    .split("\n\n")
+                    .filter(|frame| frame.contains("data: "))
+                    .map(str::to_string)
+                    .collect();
+                if frames.len() >= 2 {
+                    return frames;
+                }
+                let chunk = body.next().await.unwrap().unwrap();
+                text.push_str(std::str::from_utf8(&chunk).unwrap());
+            }
+        })
+        .await
+        .expect("SSE 事件未在超时内到达");
+
+        let (event, id, data) = parse_frame(&frames[0]);
+        assert_eq!((event.as_str(), id.as_str()), ("plugin", "3"));
+        assert_eq!(data["seq"], 3);
+        assert_eq!(data["change"], "restarted");
+
+        let (event, id, data) = parse_frame(&frames[1]);
+        assert_eq!((event.as_str(), id.as_str()), ("plugin", "5"));
+        assert_eq!(data["type"], "plugin");
+        assert_eq!(data["pluginId"], "demo");
+        assert_eq!(data["change"], "crashed");
+        assert!(data["timestamp"].is_string());
+    }
+}
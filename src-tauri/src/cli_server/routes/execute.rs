@@ -0,0 +1,294 @@
+//! CLI 执行路由：选中候选项并经 `ExecutionContext` / `ActionExecutor` 执行（与 UI 确认同一路径）。
+//!
+//! CLI 无会话：不校验代际、不改写 UI 活动会话；用户参数不足（有未提供且无默认值的参数）时
+//! 返回 422 并在 `details` 列出所需参数与缺少的参数名。
+
+use axum::extract::State;
+use axum::Extension;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::query::cli_query;
+use crate::cli_server::error::ApiError;
+use crate::cli_server::middleware::TraceId;
+use crate::commands::bridge_error::BridgeError;
+use crate::plugin_framework::{CliConfirmRequest, ConfirmOutcome};
+use crate::state::app_state::AppState;
+use zerolaunch_plugin_api::{CandidateId, QueryChannel, QueryResponse};
+
+/// `POST /v1/confirm` 的请求体 —— 确认一条已知 ID 的候选项。
+#[derive(Debug, Deserialize)]
+pub struct ConfirmBody {
+    /// 候选项 ID（来自同一 `queryText` 的 `/v1/query` 结果）
+    #[serde(rename = "candidateId", default)]
+    pub candidate_id: CandidateId,
+    /// 动作 ID；省略时取目标类型的默认动作（插件条目必须指定）
+    #[serde(rename = "actionId", default)]
+    pub action_id: Option<String>,
+    /// 产生该候选项的查询文本（决定条目归属：触发词插件 / 默认搜索）
+    #[serde(rename = "queryText", default)]
+    pub query_text: String,
+    /// 用户参数（行内参数模板按顺序填充）
+    #[serde(rename = "userArgs", default)]
+    pub user_args: Vec<String>,
+}
+
+/// `POST /v1/execute` 的请求体 —— 查询并执行第 `index` 条结果。
+#[derive(Debug, Deserialize)]
+pub struct ExecuteBody {
+    /// 原始查询文本
+    #[serde(rename = "rawQuery", default)]
+    pub raw_query: String,
+    /// 结果序号（从 0 开始，按排序后的列表）
+    #[serde(rename = "index", default)]
+    pub index: usize,
+    /// 动作 ID；省略时取该条目的默认动作
+    #[serde(rename = "actionId", default)]
+    pub action_id: Option<String>,
+    /// 用户参数（行内参数模板按顺序填充）
+    #[serde(rename = "userArgs", default)]
+    pub user_args: Vec<String>,
+}
+
+/// 执行成功的响应体。
+#[derive(Debug, Serialize)]
+pub struct ExecutedResponse {
+    /// 固定为 `executed`
+    #[serde(rename = "status")]
+    pub status: &'static str,
+    /// 被执行的候选项 ID
+    #[serde(rename = "candidateId")]
+    pub candidate_id: CandidateId,
+    /// 执行的动作 ID（None = 目标类型默认动作）
+    #[serde(rename = "actionId")]
+    pub action_id: Option<String>,
+    /// 候选项标题（仅 `/v1/execute` 从列表结果选中时可知）
+    #[serde(rename = "title")]
+    pub title: Option<String>,
+}
+
+/// POST /v1/confirm — 执行指定候选项的动作。
+pub async fn handle_confirm(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Json(req): Json<ConfirmBody>,
+) -> Result<Json<ExecutedResponse>, ApiError> {
+    confirm(
+        &state,
+        &trace_id.0,
+        CliConfirmRequest {
+            candidate_id: req.candidate_id,
+            action_id: req.action_id,
+            query_text: req.query_text,
+            user_args: req.user_args,
+        },
+        None,
+    )
+    .await
+}
+
+/// POST /v1/execute — 以 CLI 通道查询，选中第 `index` 条结果并执行。
+///
+/// 列表结果：动作取请求指定 → 条目默认动作 → 条目首个动作；
+/// 行内参数结果：执行其目标候选项；插件面板与空结果没有可执行条目。
+pub async fn handle_execute(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Json(req): Json<ExecuteBody>,
+) -> Result<Json<ExecutedResponse>, ApiError> {
+    let query = cli_query(&trace_id.0, &req.raw_query);
+    let routed = state
+        .get_session_dispatcher()
+        .route_query(&trace_id.0, &query, QueryChannel::Cli)
+        .await
+        .map_err(|e| ApiError::dispatch(e, &trace_id.0))?;
+    let (candidate_id, action_id, title) = match routed.response {
+        QueryResponse::List { results } => {
+            let count = results.len();
+            let item = results.into_iter().nth(req.index).ok_or_else(|| {
+                BridgeError::validation_failed(format!(
+                    "结果序号 {} 超出范围（共 {} 条）",
+                    req.index, count
+                ))
+                .with_trace_id(&trace_id.0)
+            })?;
+            let action_id = req.action_id.or_else(|| {
+                item.actions
+                    .iter()
+                    .find(|a| a.is_default)
+                    .or_else(|| item.actions.first())
+                    .map(|a| a.id.clone())
+            });
+            (item.id, action_id, Some(item.title))
+        }
+        QueryResponse::InlineParam { candidate_id, .. } => (candidate_id, req.action_id, None),
+        QueryResponse::CustomPanel { panel_type, .. } => {
+            return Err(BridgeError::validation_failed(format!(
+                "查询进入插件面板 '{}'，没有可执行的结果",
+                panel_type
+            ))
+            .with_trace_id(&trace_id.0)
+            .into());
+        }
+        QueryResponse::Empty => {
+            return Err(BridgeError::validation_failed("查询没有可执行的结果")
+                .with_trace_id(&trace_id.0)
+                .into());
+        }
+    };
+    confirm(
+        &state,
+        &trace_id.0,
+        CliConfirmRequest {
+            candidate_id,
+            action_id,
+            query_text: req.raw_query,
+            user_args: req.user_args,
+        },
+        title,
+    )
+    .await
+}
+
+/// 经调度器 CLI 确认入口执行；用户参数不足时转为 422。
+async fn confirm(
+    state: &AppState,
+    trace_id: &str,
+    req: CliConfirmRequest,
+    title: Option<String>,
+) -> Result<Json<ExecutedResponse>, ApiError> {
+    let candidate_id = req.candidate_id;
+    let action_id = req.action_id.clone();
+    let provided = req.user_args.len();
+    let outcome = state
+        .get_session_dispatcher()
+        .route_cli_confirm(trace_id, req)
+        .await
+        .map_err(|e| ApiError::dispatch(e, trace_id))?;
+    match outcome {
+        ConfirmOutcome::Executed => Ok(Json(ExecutedResponse {
+            status: "executed",
+            candidate_id,
+            action_id,
+            title,
+        })),
+        ConfirmOutcome::EnterParamPanel {
+            candidate_id,
+            user_args,
+            ..
+        } => Err(ApiError::missing_user_args(
+            candidate_id,
+            user_args,
+            provided,
+            trace_id,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{app, request, send, send_json};
+    use super::*;
+    use axum::http::StatusCode;
+    use zerolaunch_plugin_api::services::IconRequest;
+    use zerolaunch_plugin_api::{CachedCandidateData, ExecutionTarget, SearchCandidate};
+
+    /// 构造只含一条命令候选项（ID 1）的应用状态。
+    fn state_with_command(template: &str) -> Arc<AppState> {
+        let mut candidates = CachedCandidateData::new();
+        candidates.add_candidate(SearchCandidate {
+            id: 0,
+            name: "模板命令".to_string(),
+            icon: IconRequest::Path(String::new()),
+            target: ExecutionTarget::Command(template.to_string()),
+            keywords: Vec::new(),
+            bias: 0.0,
+            trigger_keywords: Vec::new(),
+            actions: Vec::new(),
+        });
+        let state = Arc::new(AppState::new());
+        state
+            .get_session_dispatcher()
+            .set_cached_candidates(candidates);
+        state
+    }
+
+    /// 参数不足（非空但少于所需）时返回 422，`missing` 只列出未提供且无默认值的参数。
+    #[tokio::test]
+    async fn confirm_with_too_few_args_lists_missing_names() {
+        let state = state_with_command("git commit -m {msg} --author {who} --date {when:now}");
+        let (status, body) = send_json(
+            app(state),
+            "POST",
+            "/v1/confirm",
+            Some(serde_json::json!({ "candidateId": 1, "userArgs": ["fix"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(body["details"]["candidateId"], 1);
+        assert_eq!(body["details"]["userArgCount"], 3);
+        assert_eq!(body["details"]["providedCount"], 1);
+        assert_eq!(body["details"]["missing"], serde_json::json!(["who"]));
+        assert_eq!(body["details"]["userArgs"][2]["default"], "now");
+        assert!(body["traceId"].is_string());
+    }
+
+    /// 未提供任何参数时位置参数按序号列出。
+    #[tokio::test]
+    async fn confirm_without_args_lists_positional_slots() {
+        let state = state_with_command("echo {} {}");
+        let (status, body) = send_json(
+            app(state),
+            "POST",
+            "/v1/confirm",
+            Some(serde_json::json!({ "candidateId": 1 })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["details"]["missing"], serde_json::json!(["#1", "#2"]));
+        assert_eq!(body["details"]["providedCount"], 0);
+    }
+
+    /// 未知候选项为 404，不会被误报为参数不足。
+    #[tokio::test]
+    async fn confirm_unknown_candidate_is_not_found() {
+        let state = state_with_command("echo {}");
+        let (status, body) = send_json(
+            app(state),
+            "POST",
+            "/v1/confirm",
+            Some(serde_json::json!({ "candidateId": 42, "userArgs": ["x"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body["details"]["missing"].is_null());
+    }
+
+    /// 缺少或错误的 bearer token 在到达处理函数前即被拒绝。
+    #[tokio::test]
+    async fn execute_routes_require_bearer_token() {
+        let state = state_with_command("echo {}");
+        let body = serde_json::json!({ "candidateId": 1, "userArgs": ["x"] });
+        for token in [None, Some("wrong-token")] {
+            let (status, _) = send(
+                app(state.clone()),
+                request("POST", "/v1/confirm", token, Some(body.clone())),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+        let (status, _) = send(
+            app(state),
+            request(
+                "POST",
+                "/v1/execute",
+                None,
+                Some(serde_json::json!({ "rawQuery": "echo" })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
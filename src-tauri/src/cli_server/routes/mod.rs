@@ -1,5 +1,71 @@
 pub mod config;
+pub mod events;
+pub mod execute;
 pub mod ping;
 pub mod plugins;
 pub mod query;
 pub mod session;
+
+/// 路由测试辅助：经完整 router（鉴权与 trace 中间件齐全）在进程内发送请求。
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use crate::cli_server::server::router;
+    use crate::state::app_state::AppState;
+
+    /// 测试用 bearer token。
+    pub const TOKEN: &str = "test-token";
+
+    /// 以测试 token 构建完整 router。
+    pub fn app(state: Arc<AppState>) -> Router {
+        router(state, TOKEN.to_string())
+    }
+
+    /// 构造请求：`token` 为 None 时不带 Authorization 头；有 body 时按 JSON 发送。
+    pub fn request(
+        method: &str,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header("authorization", format!("Bearer {token}"));
+        }
+        let body = match body {
+            Some(json) => {
+                builder = builder.header("content-type", "application/json");
+                Body::from(serde_json::to_vec(&json).unwrap())
+            }
+            None => Body::empty(),
+        };
+        builder.body(body).unwrap()
+    }
+
+    /// 发送请求并解析响应；响应体不是 JSON 时返回 `Value::Null`。
+    pub async fn send(app: Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, json)
+    }
+
+    /// 携带测试 token 发送 JSON 请求。
+    pub async fn send_json(
+        app: Router,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        send(app, request(method, uri, Some(TOKEN), body)).await
+    }
+}
@@ -1,10 +1,18 @@
 use axum::extract::{Path, State};
+use axum::Extension;
 use axum::Json;
+use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::cli_server::error::ApiError;
+use crate::cli_server::middleware::TraceId;
+use crate::cli_server::routes::config::EnabledRequest;
+use crate::commands::bridge_error::{BridgeError, WithTraceId};
+use crate::commands::plugin::{registry_settings, set_plugin_enabled};
 use crate::state::app_state::AppState;
 use zerolaunch_plugin_api::plugin::PluginKind;
 use zerolaunch_plugin_host::manager::InstalledPluginInfo;
+use zerolaunch_plugin_host::metrics::PluginMetricsSnapshot;
 use zerolaunch_plugin_protocol::Manifest;
 
 /// GET /v1/plugins — 列出所有已安装第三方插件。
@@ -42,3 +50,158 @@ pub async fn handle_get_logs(
     let logs = pm.get_logs(&id, 50).unwrap_or_default();
     Json(serde_json::json!({ "logs": logs }))
 }
+
+/// GET /v1/plugins/:id/metrics — 插件健康指标（请求延迟直方图、错误数、重启次数、内存）。
+///
+/// 插件未加载时返回 null，与 manifest 路由口径一致。
+pub async fn handle_get_metrics(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Json<Option<PluginMetricsSnapshot>> {
+    let pm = state.get_plugin_manager();
+    Json(pm.get_metrics(&id))
+}
+
+/// `POST /v1/plugins/install` 的请求体：`path` 与 `pluginId` 二选一。
+#[derive(Debug, Deserialize)]
+pub struct InstallRequest {
+    /// 本地插件包（.zip）或目录的绝对路径
+    #[serde(rename = "path", default)]
+    pub path: Option<String>,
+    /// 从插件注册表安装时的插件 id
+    #[serde(rename = "pluginId", default)]
+    pub plugin_id: Option<String>,
+}
+
+/// POST /v1/plugins/install — 从本地路径或插件注册表安装插件，返回安装后的插件信息。
+pub async fn handle_install(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Json(req): Json<InstallRequest>,
+) -> Result<Json<InstalledPluginInfo>, ApiError> {
+    // 插件管理器在各分支内获取：请求体不合法时直接返回 422，不触及插件管理器
+    let info = match (req.path, req.plugin_id) {
+        (Some(path), None) => state
+            .get_plugin_manager()
+            .install(&std::path::PathBuf::from(path), state.get_main_handle())
+            .await
+            .with_trace_id(&trace_id.0)?,
+        (None, Some(plugin_id)) => state
+            .get_plugin_manager()
+            .install_from_registry(
+                &registry_settings(&state),
+                &plugin_id,
+                state.get_main_handle(),
+            )
+            .await
+            .with_trace_id(&trace_id.0)?,
+        _ => {
+            return Err(
+                BridgeError::validation_failed("path 与 pluginId 必须且只能提供一个")
+                    .with_trace_id(&trace_id.0)
+                    .into(),
+            )
+        }
+    };
+    Ok(Json(info))
+}
+
+/// DELETE /v1/plugins/:id — 卸载第三方插件。
+pub async fn handle_uninstall(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .get_plugin_manager()
+        .uninstall(&id, state.get_main_handle())
+        .await
+        .with_trace_id(&trace_id.0)?;
+    Ok(Json(serde_json::json!({ "pluginId": id })))
+}
+
+/// POST /v1/plugins/:id/reload — 重新加载第三方插件。
+pub async fn handle_reload(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .get_plugin_manager()
+        .reload(&id, state.get_main_handle())
+        .await
+        .with_trace_id(&trace_id.0)?;
+    Ok(Json(serde_json::json!({ "pluginId": id })))
+}
+
+/// PUT /v1/plugins/:id/enabled — 启用或禁用插件的全部组件（与 IPC plugin_set_enabled 同源）。
+pub async fn handle_set_enabled(
+    State(state): State<Arc<AppState>>,
+    Extension(trace_id): Extension<TraceId>,
+    Path(id): Path<String>,
+    Json(req): Json<EnabledRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    set_plugin_enabled(&state, &id, req.enabled)
+        .map_err(|e| ApiError::config(e, &id, &trace_id.0))?;
+    Ok(Json(
+        serde_json::json!({ "pluginId": id, "enabled": req.enabled }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{app, request, send, send_json};
+    use super::*;
+    use axum::http::StatusCode;
+
+    /// 插件写路由缺少或携带错误 token 时返回 401（在访问插件管理器之前即被拒绝）。
+    #[tokio::test]
+    async fn write_routes_require_bearer_token() {
+        let state = Arc::new(AppState::new());
+        let routes = [
+            (
+                "POST",
+                "/v1/plugins/install",
+                Some(serde_json::json!({ "pluginId": "demo" })),
+            ),
+            ("DELETE", "/v1/plugins/demo", None),
+            ("POST", "/v1/plugins/demo/reload", None),
+            (
+                "PUT",
+                "/v1/plugins/demo/enabled",
+                Some(serde_json::json!({ "enabled": false })),
+            ),
+        ];
+        for (method, uri, body) in routes {
+            for token in [None, Some("wrong-token")] {
+                let (status, _) = send(
+                    app(state.clone()),
+                    request(method, uri, token, body.clone()),
+                )
+                .await;
+                assert_eq!(status, StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+            }
+        }
+    }
+
+    /// 安装请求必须且只能指定 path 与 pluginId 之一，否则返回 422。
+    #[tokio::test]
+    async fn install_requires_exactly_one_source() {
+        let state = Arc::new(AppState::new());
+        for body in [
+            serde_json::json!({}),
+            serde_json::json!({ "path": "/tmp/demo.zip", "pluginId": "demo" }),
+        ] {
+            let (status, body) = send_json(
+                app(state.clone()),
+                "POST",
+                "/v1/plugins/install",
+                Some(body),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(body["code"], "VALIDATION_FAILED");
+            assert!(body["traceId"].is_string());
+        }
+    }
+}
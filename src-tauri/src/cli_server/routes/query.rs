@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use crate::cli_server::middleware::TraceId;
 use crate::state::app_state::AppState;
+use zerolaunch_plugin_api::Query;
 use zerolaunch_plugin_api::QueryChannel;
 use zerolaunch_plugin_api::QueryResponse;
 
@@ -15,17 +16,22 @@ pub struct QueryRequest {
     pub raw_query: String,
 }
 
+/// 构造 CLI 通道的查询（trace_id 兼作查询 ID）。
+pub(crate) fn cli_query(trace_id: &str, raw_query: &str) -> Query {
+    Query {
+        id: trace_id.to_string(),
+        raw_query: raw_query.to_string(),
+        search_term: raw_query.to_lowercase(),
+        confirm: false,
+    }
+}
+
 pub async fn handle(
     State(state): State<Arc<AppState>>,
     Extension(trace_id): Extension<TraceId>,
     Json(req): Json<QueryRequest>,
 ) -> Json<QueryResponse> {
-    let query = zerolaunch_plugin_api::Query {
-        id: trace_id.0.clone(),
-        raw_query: req.raw_query.clone(),
-        search_term: req.raw_query.to_lowercase(),
-        confirm: false,
-    };
+    let query = cli_query(&trace_id.0, &req.raw_query);
 
     // CLI 为只读辅助路径，响应契约固定为 QueryResponse：流程失败时记录错误并返回空结果
     // （错误详情经日志可观测；UI 通道的错误语义由 bridge_query 的 IPC 错误通道承担）。
@@ -14,3 +14,11 @@ pub async fn get_candidates_count(State(state): State<Arc<AppState>>) -> Json<se
     let count = state.get_session_dispatcher().get_cached_candidates_count();
     Json(serde_json::json!({ "count": count }))
 }
+
+/// POST /v1/candidates/refresh — 强制刷新候选项缓存，返回刷新后的数量。
+pub async fn refresh_candidates(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let dispatcher = state.get_session_dispatcher();
+    dispatcher.refresh_candidates().await;
+    let count = dispatcher.get_cached_candidates_count();
+    Json(serde_json::json!({ "count": count }))
+}
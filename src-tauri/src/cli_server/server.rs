@@ -1,7 +1,7 @@
 //! CLI HTTP server — local HTTP API with bearer token auth.
 
 use axum::middleware;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post, put};
 use axum::Router;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -55,19 +55,43 @@ pub async fn start(
     // Cache the token in AppState so the `cli_get_info` IPC command can serve it.
     state.set_cli_token(cli_token);
 
-    let app_state = state.clone();
+    let app = router(state, token.clone());
 
-    let app = Router::new()
+    info!("CLI HTTP server listening on 127.0.0.1:{}", port);
+
+    tauri::async_runtime::spawn(async move {
+        // 监听器已提前绑定成功，serve 只在 accept 循环异常时退出。
+        // 失败仅记录日志：unwrap 会静默杀死整条 serve 任务且无法定位原因。
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("CLI HTTP server exited with error: {e}");
+        }
+    });
+
+    Ok(CliServerHandle { port, token })
+}
+
+/// Build the `/v1` router with bearer-token auth and trace-id middleware.
+pub(crate) fn router(state: Arc<AppState>, token: String) -> Router {
+    Router::new()
         // Health check
         .route("/v1/ping", get(routes::ping::handle))
         // Search & Session
         .route("/v1/query", post(routes::query::handle))
+        .route("/v1/execute", post(routes::execute::handle_execute))
+        .route("/v1/confirm", post(routes::execute::handle_confirm))
         .route("/v1/session/mode", get(routes::session::get_mode))
         .route(
             "/v1/candidates/count",
             get(routes::session::get_candidates_count),
         )
-        // Config — read only
+        .route(
+            "/v1/candidates/refresh",
+            post(routes::session::refresh_candidates),
+        )
+        // Events
+        .route("/v1/events", get(routes::events::handle_stream))
+        .route("/v1/events/recent", get(routes::events::handle_recent))
+        // Config
         .route(
             "/v1/config/components",
             get(routes::config::list_components),
@@ -75,11 +99,33 @@ pub async fn start(
         .route("/v1/config/{id}/schema", get(routes::config::get_schema))
         .route(
             "/v1/config/{id}/settings",
-            get(routes::config::get_settings),
+            get(routes::config::get_settings).patch(routes::config::patch_settings),
+        )
+        .route(
+            "/v1/config/{id}/reset",
+            post(routes::config::reset_settings),
         )
+        .route("/v1/config/{id}/enabled", put(routes::config::set_enabled))
         .route("/v1/config/{id}/actions", get(routes::config::get_actions))
-        // Plugin Management — read only
+        .route(
+            "/v1/config/{id}/actions/{action}",
+            post(routes::config::run_action),
+        )
+        // Plugin Management
         .route("/v1/plugins", get(routes::plugins::handle_list))
+        .route("/v1/plugins/install", post(routes::plugins::handle_install))
+        .route(
+            "/v1/plugins/{id}",
+            delete(routes::plugins::handle_uninstall),
+        )
+        .route(
+            "/v1/plugins/{id}/reload",
+            post(routes::plugins::handle_reload),
+        )
+        .route(
+            "/v1/plugins/{id}/enabled",
+            put(routes::plugins::handle_set_enabled),
+        )
         .route(
             "/v1/plugins/{id}/manifest",
             get(routes::plugins::handle_get_manifest),
@@ -88,24 +134,13 @@ pub async fn start(
             "/v1/plugins/{id}/logs",
             get(routes::plugins::handle_get_logs),
         )
-        .with_state(app_state)
-        .layer(middleware::from_fn_with_state(
-            token.clone(),
-            auth_middleware,
-        ))
+        .route(
+            "/v1/plugins/{id}/metrics",
+            get(routes::plugins::handle_get_metrics),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn_with_state(token, auth_middleware))
         .layer(middleware::from_fn(
             crate::cli_server::middleware::trace_middleware,
-        ));
-
-    info!("CLI HTTP server listening on 127.0.0.1:{}", port);
-
-    tauri::async_runtime::spawn(async move {
-        // 监听器已提前绑定成功，serve 只在 accept 循环异常时退出。
-        // 失败仅记录日志：unwrap 会静默杀死整条 serve 任务且无法定位原因。
-        if let Err(e) = axum::serve(listener, app).await {
-            error!("CLI HTTP server exited with error: {e}");
-        }
-    });
-
-    Ok(CliServerHandle { port, token })
+        ))
 }
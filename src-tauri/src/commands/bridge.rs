@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tauri::Emitter;
 use tracing::{debug, info};
 use zerolaunch_plugin_api::common::ImageUtils;
+use zerolaunch_plugin_api::services::parameter::UserParameter;
 use zerolaunch_plugin_api::{CandidateId, Query, QueryChannel, QueryResponse, ResultAction};
 // ============================================================================
 // 搜索接口
@@ -90,6 +91,9 @@ pub struct BridgeInlineParamData {
     pub trigger_keyword: String,
     #[serde(rename = "userArgCount")]
     pub user_arg_count: usize,
+    /// 各参数的名称与默认值（按输入顺序）
+    #[serde(rename = "userArgs")]
+    pub user_args: Vec<UserParameter>,
 }
 
 /// 确认请求载荷 —— `bridge_confirm` 的 IPC 请求契约（Deserialize 侧），
@@ -140,7 +144,7 @@ pub enum ConfirmRequestPayload {
 
 /// 确认执行响应 —— 由 `route_confirm` 返回的 `RoutedConfirm` 映射而来（IPC 序列化契约）。
 /// Executed 表示动作已执行完成；EnterParamPanel 表示确认后需要更多用户输入
-/// （参数面板，核心程序专属形态——载荷自包含：候选 ID + 参数个数，
+/// （参数面板，核心程序专属形态——载荷自包含：候选 ID + 参数个数与描述，
 /// 前端据此构造输入字段，无需依赖列表项）。
 /// 两个变体均携带当前会话代际：投影转换后前端无需等下一次查询即可更新投影。
 #[derive(Serialize, Debug)]
@@ -157,6 +161,8 @@ pub enum BridgeConfirmResponse {
         candidate_id: CandidateId,
         #[serde(rename = "userArgCount")]
         user_arg_count: usize,
+        #[serde(rename = "userArgs")]
+        user_args: Vec<UserParameter>,
         #[serde(rename = "generation")]
         generation: u64,
     },
@@ -304,6 +310,7 @@ pub async fn bridge_query(
             candidate_id,
             trigger_keyword,
             user_arg_count,
+            user_args,
         } => {
             info!(
                 "[Bridge] 进入行内参数模式: candidate_id={}, trigger='{}'",
@@ -320,6 +327,7 @@ pub async fn bridge_query(
                     candidate_id,
                     trigger_keyword,
                     user_arg_count,
+                    user_args,
                 }),
             })
         }
@@ -393,9 +401,11 @@ pub async fn bridge_confirm(
         ConfirmOutcome::EnterParamPanel {
             candidate_id,
             user_arg_count,
+            user_args,
         } => BridgeConfirmResponse::EnterParamPanel {
             candidate_id,
             user_arg_count,
+            user_args,
             generation: routed.generation,
         },
     })
@@ -177,6 +177,16 @@ impl From<PluginManagerError> for BridgeError {
                 component_id: Some(id),
                 trace_id: String::new(),
             },
+            PluginManagerError::Registry(msg) => BridgeError {
+                code: ErrorCode::NetworkError,
+                message: format!("插件注册表错误: {}", msg),
+                details: None,
+                component_id: None,
+                trace_id: String::new(),
+            },
+            PluginManagerError::VerificationFailed(msg) => {
+                BridgeError::validation_failed(format!("插件包校验失败: {}", msg))
+            }
             PluginManagerError::Internal(msg) => BridgeError::internal(msg),
         }
     }
@@ -19,6 +19,28 @@ pub struct SearchTimingResult {
     pub result_count: usize,
     #[serde(rename = "totalCandidates")]
     pub total_candidates: usize,
+    /// 各搜索引擎（含未启用的）单独建索引与打分的耗时对比。
+    #[serde(rename = "engines")]
+    pub engines: Vec<EngineTiming>,
+}
+
+/// 单个搜索引擎的性能对比条目
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineTiming {
+    #[serde(rename = "engineId")]
+    pub engine_id: String,
+    /// 引擎显示名（可能为 i18n key，由前端 resolveText 解析）。
+    #[serde(rename = "engineName")]
+    pub engine_name: String,
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+    #[serde(rename = "indexMs")]
+    pub index_ms: f64,
+    #[serde(rename = "searchMs")]
+    pub search_ms: f64,
+    /// 得分为正的候选项数。
+    #[serde(rename = "matchedCount")]
+    pub matched_count: usize,
 }
 
 /// 索引性能测试结果
@@ -55,7 +77,7 @@ pub struct SearchDetailItem {
 
 // ---- 命令 ----
 
-/// 搜索性能测试：返回耗时、结果数、候选总数。
+/// 搜索性能测试：返回完整管道的耗时、结果数、候选总数，以及各引擎单独的建索引/打分耗时。
 #[tauri::command]
 #[tracing::instrument(skip(state), fields(trace_id))]
 pub async fn debug_test_search_time(
@@ -76,10 +98,24 @@ pub async fn debug_test_search_time(
     let scored = session_dispatcher.debug_search(&query);
     let duration_ms = start.elapsed().as_millis() as u64;
 
+    let engines = session_dispatcher
+        .debug_benchmark_engines(&query)
+        .into_iter()
+        .map(|b| EngineTiming {
+            engine_id: b.engine_id,
+            engine_name: b.engine_name,
+            enabled: b.enabled,
+            index_ms: b.index_ms,
+            search_ms: b.search_ms,
+            matched_count: b.matched_count,
+        })
+        .collect();
+
     Ok(SearchTimingResult {
         duration_ms,
         result_count: scored.len(),
         total_candidates: session_dispatcher.get_cached_candidates_count(),
+        engines,
     })
 }
 
@@ -4,11 +4,15 @@
 //! 不新增中间层，直接在命令处理器中编排两个管理器的调用。
 
 use crate::commands::bridge_error::{BridgeError, WithTraceId};
+use crate::core::config::plugin_registry_settings::{
+    PluginRegistrySettings, PLUGIN_REGISTRY_CONFIG_ID,
+};
+use crate::plugin_framework::package_registry::RegistryListing;
 use crate::state::app_state::AppState;
 use std::sync::Arc;
 use tauri::State;
-use zerolaunch_plugin_api::config::Configurable;
-use zerolaunch_plugin_host::manager::InstalledPluginInfo;
+use zerolaunch_plugin_api::config::{ConfigError, Configurable};
+use zerolaunch_plugin_host::manager::{granted_permission_keys, InstalledPluginInfo};
 use zerolaunch_plugin_protocol::Manifest;
 
 // ── Commands ─────────────────────────────────────────────────────
@@ -104,6 +108,51 @@ pub async fn plugin_get_detail(
     })
 }
 
+/// 插件包预览 DTO —— 安装确认弹窗的数据契约：安装前向用户展示插件身份与申请的宿主权限。
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PluginPackagePreview {
+    /// 插件 ID（manifest plugin.id）。
+    #[serde(rename = "pluginId")]
+    pub plugin_id: String,
+    /// 插件名称。
+    #[serde(rename = "name")]
+    pub name: String,
+    /// 插件版本。
+    #[serde(rename = "version")]
+    pub version: String,
+    /// 插件作者。
+    #[serde(rename = "author")]
+    pub author: String,
+    /// manifest `[permissions]` 申请的权限键名（如 "shell"、"resources"）。
+    #[serde(rename = "permissions")]
+    pub permissions: Vec<String>,
+}
+
+/// Inspect a local .zip file or directory before installing it.
+/// 仅解析 manifest，不安装；供确认弹窗展示插件申请的宿主权限。
+#[tauri::command]
+#[tracing::instrument(skip(state), fields(trace_id))]
+pub async fn plugin_inspect_local(
+    file_path: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<PluginPackagePreview, BridgeError> {
+    let trace_id = crate::utils::trace_id::generate_trace_id();
+    tracing::Span::current().record("trace_id", trace_id.as_str());
+    let path = std::path::PathBuf::from(&file_path);
+
+    let manifest = state
+        .get_plugin_manager()
+        .inspect_package(&path)
+        .with_trace_id(&trace_id)?;
+    Ok(PluginPackagePreview {
+        permissions: granted_permission_keys(&manifest),
+        plugin_id: manifest.plugin.id,
+        name: manifest.plugin.name,
+        version: manifest.plugin.version,
+        author: manifest.plugin.author,
+    })
+}
+
 /// Install a plugin from a local .zip file or directory.
 /// Emits `plugin-installed` on success.
 #[tauri::command]
@@ -162,10 +211,87 @@ pub async fn plugin_uninstall(
         .with_trace_id(&trace_id)
 }
 
+/// List the plugin registry catalog merged with installed versions.
+///
+/// 注册表地址与信任库读取自 `plugin-registry-config`；未配置时返回 NETWORK_ERROR。
+#[tauri::command]
+#[tracing::instrument(skip(state), fields(trace_id))]
+pub async fn plugin_registry_list(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<RegistryListing>, BridgeError> {
+    let trace_id = crate::utils::trace_id::generate_trace_id();
+    tracing::Span::current().record("trace_id", trace_id.as_str());
+    let settings = registry_settings(&state);
+
+    state
+        .get_plugin_manager()
+        .registry_catalog(&settings)
+        .await
+        .with_trace_id(&trace_id)
+}
+
+/// List installed plugins that have a newer compatible version in the registry.
+#[tauri::command]
+#[tracing::instrument(skip(state), fields(trace_id))]
+pub async fn plugin_check_updates(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<RegistryListing>, BridgeError> {
+    let trace_id = crate::utils::trace_id::generate_trace_id();
+    tracing::Span::current().record("trace_id", trace_id.as_str());
+    let settings = registry_settings(&state);
+
+    state
+        .get_plugin_manager()
+        .check_updates(&settings)
+        .await
+        .with_trace_id(&trace_id)
+}
+
+/// Install the latest compatible version of a plugin from the registry.
+/// The package is verified (sha256 + publisher signature) before extraction.
+/// Emits `plugin-installed` on success.
+#[tauri::command]
+#[tracing::instrument(skip(state), fields(trace_id))]
+pub async fn plugin_install_from_registry(
+    plugin_id: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<InstalledPluginInfo, BridgeError> {
+    let trace_id = crate::utils::trace_id::generate_trace_id();
+    tracing::Span::current().record("trace_id", trace_id.as_str());
+    let settings = registry_settings(&state);
+    let app_handle = state.get_main_handle();
+
+    state
+        .get_plugin_manager()
+        .install_from_registry(&settings, &plugin_id, app_handle)
+        .await
+        .with_trace_id(&trace_id)
+}
+
+/// Upgrade an installed plugin to the latest compatible registry version.
+/// Rolls back to the previous version if the new one fails to load.
+/// Emits `plugin-installed` on success.
+#[tauri::command]
+#[tracing::instrument(skip(state), fields(trace_id))]
+pub async fn plugin_upgrade(
+    plugin_id: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<InstalledPluginInfo, BridgeError> {
+    let trace_id = crate::utils::trace_id::generate_trace_id();
+    tracing::Span::current().record("trace_id", trace_id.as_str());
+    let settings = registry_settings(&state);
+    let app_handle = state.get_main_handle();
+
+    state
+        .get_plugin_manager()
+        .upgrade_from_registry(&settings, &plugin_id, app_handle)
+        .await
+        .with_trace_id(&trace_id)
+}
+
 /// Enable or disable all components of a plugin (third-party or builtin).
 ///
-/// 第三方插件：遍历其所有 Configurable 逐个调用 CM.set_enabled()。
-/// 内置组件：直接按 plugin_id 调用 CM.set_enabled()。
+/// 编排逻辑见 `set_plugin_enabled`（CLI HTTP API 同源复用）。
 #[tauri::command]
 #[tracing::instrument(skip(state), fields(trace_id))]
 pub async fn plugin_set_enabled(
@@ -175,31 +301,7 @@ pub async fn plugin_set_enabled(
 ) -> Result<(), BridgeError> {
     let trace_id = crate::utils::trace_id::generate_trace_id();
     tracing::Span::current().record("trace_id", trace_id.as_str());
-    let pm = state.get_plugin_manager();
-    let cm = state.get_config_manager();
-    let dispatcher = state.get_session_dispatcher();
-    let hm = pm.host_manager();
-
-    // 先持久化全部组件，全部成功后才同步触发词索引：
-    // 任一步持久化失败时命令返回 Err 且路由索引零变更（配置与路由不分叉）。
-    if let Some(plugin) = hm.plugins.get(&plugin_id) {
-        // 第三方插件：遍历其所有 Configurable 逐个调用 CM.set_enabled()。
-        for c in &plugin.components {
-            cm.set_enabled(c.component_id(), enabled)
-                .with_trace_id(&trace_id)?;
-        }
-    } else {
-        // 内置组件：直接按 plugin_id 调用 CM.set_enabled()。
-        cm.set_enabled(&plugin_id, enabled)
-            .with_trace_id(&trace_id)?;
-    }
-
-    // 全部持久化成功后同步触发词索引（第三方组件 id 可能与 plugin_id 不一致，
-    // EnabledChanged 事件按组件 id 处理无法命中，这里按 plugin_id 直调兜底；
-    // 内置组件 component_id == plugin_id，事件异步到达后为幂等重入）。
-    dispatcher.set_plugin_enabled(&plugin_id, enabled);
-
-    Ok(())
+    set_plugin_enabled(&state, &plugin_id, enabled).with_trace_id(&trace_id)
 }
 
 /// 获取插件 stderr 日志的最近 N 行。
@@ -217,3 +319,44 @@ pub async fn plugin_get_logs(
         .get_logs(&plugin_id, tail_lines.unwrap_or(50))
         .with_trace_id(&trace_id)
 }
+
+/// 读取插件注册表配置（缺失或解析失败时用默认值，即未配置注册表）。
+pub(crate) fn registry_settings(state: &AppState) -> PluginRegistrySettings {
+    state
+        .get_config_manager()
+        .get_settings(PLUGIN_REGISTRY_CONFIG_ID)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// 启用或禁用插件的全部组件，并同步触发词索引。
+///
+/// 第三方插件：遍历其所有 Configurable 逐个调用 CM.set_enabled()。
+/// 内置组件：直接按 plugin_id 调用 CM.set_enabled()。
+pub(crate) fn set_plugin_enabled(
+    state: &AppState,
+    plugin_id: &str,
+    enabled: bool,
+) -> Result<(), ConfigError> {
+    let pm = state.get_plugin_manager();
+    let cm = state.get_config_manager();
+    let dispatcher = state.get_session_dispatcher();
+    let hm = pm.host_manager();
+
+    // 先持久化全部组件，全部成功后才同步触发词索引：
+    // 任一步持久化失败时返回 Err 且路由索引零变更（配置与路由不分叉）。
+    if let Some(plugin) = hm.plugins.get(plugin_id) {
+        for c in &plugin.components {
+            cm.set_enabled(c.component_id(), enabled)?;
+        }
+    } else {
+        cm.set_enabled(plugin_id, enabled)?;
+    }
+
+    // 全部持久化成功后同步触发词索引（第三方组件 id 可能与 plugin_id 不一致，
+    // EnabledChanged 事件按组件 id 处理无法命中，这里按 plugin_id 直调兜底；
+    // 内置组件 component_id == plugin_id，事件异步到达后为幂等重入）。
+    dispatcher.set_plugin_enabled(plugin_id, enabled);
+
+    Ok(())
+}
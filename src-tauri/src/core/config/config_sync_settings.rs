@@ -0,0 +1,33 @@
+//! 配置远程同步 —— 核心配置类型。
+//!
+//! 与 `bias_settings` 相同按 P1 放置约定位于 core/config/（L2）：
+//! 由 `builtin_plugin/config/config_sync_config.rs`（Configurable 组件）读写 schema，
+//! 由 `bootstrap` 的自动同步（启动、配置变更、退出）读取开关。
+//! 持久化键名与前端 schema 一致（autoSync）。
+
+use serde::{Deserialize, Serialize};
+
+/// 配置同步组件的 component_id。
+pub const CONFIG_SYNC_CONFIG_ID: &str = "config-sync";
+
+/// 配置同步的根结构 —— 反序列化自 `config-sync` 组件的持久化设置。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSyncSettings {
+    /// 自动同步：启动时拉取合并、配置变更与退出时推送；关闭后仅响应手动同步动作。
+    #[serde(rename = "autoSync", default = "ConfigSyncSettings::default_auto_sync")]
+    pub auto_sync: bool,
+}
+
+impl ConfigSyncSettings {
+    fn default_auto_sync() -> bool {
+        true
+    }
+}
+
+impl Default for ConfigSyncSettings {
+    fn default() -> Self {
+        Self {
+            auto_sync: Self::default_auto_sync(),
+        }
+    }
+}
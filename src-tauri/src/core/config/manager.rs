@@ -5,13 +5,18 @@ use crate::core::config::models::{
     ComponentInfoSnapshot, ComponentPersistentState, ComponentSchemaSnapshot, PersistentConfig,
 };
 use crate::core::config::registry::ConfigurableRegistry;
+use crate::core::config::secret_store::{open_secret_store, SecretStore};
 use crate::core::config::store::ConfigStore;
+use crate::core::config::sync::{ConfigSync, SyncConflict, SyncMode, SyncReport};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, error, info, warn};
-use zerolaunch_plugin_api::config::{ComponentType, ConfigError, Configurable};
+use zerolaunch_plugin_api::config::{
+    ComponentType, ConfigError, Configurable, SettingIssue, SECRET_REDACTED,
+};
+use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
 
 /// 配置管理中枢。
 /// 负责所有可配置组件的注册、配置 CRUD、持久化和事件发布。
@@ -28,6 +33,14 @@ pub struct ConfigManager {
     /// 用于第三方插件延迟注册（在 load_from_storage 之后）时恢复其已保存配置。
     /// None 表示尚未执行 load_from_storage（首次运行或启动初期）。
     loaded_config: RwLock<Option<PersistentConfig>>,
+    /// 远程双向同步引擎（基线与 ConfigStore 同在配置目录）
+    sync: ConfigSync,
+    /// 配置目录，本地加密库（机密存储的回退后端）所在位置
+    config_dir: PathBuf,
+    /// 机密字段存储；首个带机密字段的组件读写时才按平台选择后端
+    secrets: OnceLock<Arc<dyn SecretStore>>,
+    /// 机密存储中的已知值（按存储名），持久化时跳过未变化的条目，避免重复写钥匙串
+    secret_cache: RwLock<HashMap<String, String>>,
 }
 
 impl ConfigManager {
@@ -36,12 +49,24 @@ impl ConfigManager {
         Self {
             registry: ConfigurableRegistry::new(),
             enabled_map: RwLock::new(HashMap::new()),
-            store: ConfigStore::new(config_dir),
+            sync: ConfigSync::new(&config_dir),
+            store: ConfigStore::new(config_dir.clone()),
             event_sender,
             loaded_config: RwLock::new(None),
+            config_dir,
+            secrets: OnceLock::new(),
+            secret_cache: RwLock::new(HashMap::new()),
         }
     }
 
+    /// 使用指定机密存储创建 ConfigManager（测试注入内存后端，不触及系统钥匙串）
+    #[cfg(test)]
+    pub(crate) fn with_secret_store(config_dir: PathBuf, secrets: Arc<dyn SecretStore>) -> Self {
+        let manager = Self::new(config_dir);
+        manager.secrets.set(secrets).ok();
+        manager
+    }
+
     /// 获取事件发送端的引用，用于订阅配置变更事件
     pub fn event_sender(&self) -> &ConfigEventSender {
         &self.event_sender
@@ -84,7 +109,11 @@ impl ConfigManager {
             .loaded_config
             .read()
             .as_ref()
-            .and_then(|config| config.components.get(&id).cloned());
+            .and_then(|config| config.components.get(&id).cloned())
+            .map(|mut state| {
+                state.settings = self.inject_stored_secrets(&*component, state.settings);
+                state
+            });
 
         let initialized = if let Some(state) = &saved_state {
             // 存在已保存配置：验证通过后应用，失败则回退默认值
@@ -104,8 +133,9 @@ impl ConfigManager {
         };
 
         if !initialized {
-            // 应用 defaults 作为回退或初始值
-            let defaults = component.get_default_settings();
+            // 应用 defaults 作为回退或初始值（机密字段取机密存储中的已有值）
+            let defaults =
+                self.inject_stored_secrets(&*component, component.get_default_settings());
             if let Err(e) = component.apply_settings(defaults).await {
                 error!("拒绝注册（应用 schema 默认值失败）: {} - {}", id, e);
                 return;
@@ -190,6 +220,62 @@ impl ConfigManager {
         self.registry.get(component_id).map(|c| c.get_settings())
     }
 
+    /// 获取指定组件的当前配置值，机密字段替换为 `SECRET_REDACTED`。
+    /// 供 CLI HTTP API 等进程外接口使用。
+    pub fn get_redacted_settings(&self, component_id: &str) -> Option<serde_json::Value> {
+        let component = self.registry.get(component_id)?;
+        let mut settings = component.get_settings();
+        match component.settings_contribution() {
+            Ok(contribution) => contribution.redact_secrets(&mut settings),
+            // schema 无效的组件在注册时即被拒绝，此处仅兜底：无法识别机密字段时不返回值
+            Err(_) => return None,
+        }
+        Some(settings)
+    }
+
+    /// 将部分配置（顶层键）合并到组件当前配置上，得到可交给 `apply_settings` 的完整配置。
+    /// 供 CLI 按字段写入：未出现的字段保持当前值。
+    pub fn merge_settings(
+        &self,
+        component_id: &str,
+        patch: serde_json::Value,
+    ) -> Result<serde_json::Value, ConfigError> {
+        let component = self
+            .registry
+            .get(component_id)
+            .ok_or_else(|| ConfigError::NotFound(component_id.to_string()))?;
+        let serde_json::Value::Object(patch) = patch else {
+            return Err(ConfigError::ValidationFailed(
+                "settings patch must be an object".to_string(),
+            ));
+        };
+        let mut merged = match component.get_settings() {
+            serde_json::Value::Object(current) => current,
+            _ => serde_json::Map::new(),
+        };
+        merged.extend(patch);
+        Ok(serde_json::Value::Object(merged))
+    }
+
+    /// 按组件 schema 收集配置中的全部问题（不含组件自定义的业务校验）。
+    /// 供 CLI 写入前一次性报告所有出错字段；无问题时返回空列表。
+    pub fn validation_issues(
+        &self,
+        component_id: &str,
+        settings: &serde_json::Value,
+    ) -> Result<Vec<SettingIssue>, ConfigError> {
+        let component = self
+            .registry
+            .get(component_id)
+            .ok_or_else(|| ConfigError::NotFound(component_id.to_string()))?;
+        let contribution = component.settings_contribution()?;
+        // 与 Configurable::validate_settings 默认实现一致：无字段组件允许 null
+        if contribution.properties.is_empty() && settings.is_null() {
+            return Ok(Vec::new());
+        }
+        Ok(contribution.validation_issues(settings))
+    }
+
     /// 获取指定组件中单个配置项的值。
     /// 用于运行时读取被动配置（如窗口行为设置）。
     pub fn get_component_setting(
@@ -254,6 +340,8 @@ impl ConfigManager {
             .get(component_id)
             .ok_or_else(|| ConfigError::NotFound(component_id.to_string()))?;
 
+        // 未提供或为脱敏占位的机密字段保持当前值（如经 CLI 读出后原样写回）
+        let settings = retain_current_secrets(&*component, settings);
         component.validate_settings(&settings).await?;
 
         // 剔除 transient effect 字段，防止其被持久化。
@@ -380,14 +468,25 @@ impl ConfigManager {
             }
         };
 
+        // 旧版本把机密字段明文写在配置文件中，加载后需重新保存以迁入机密存储
+        let mut has_plaintext_secrets = false;
         for (component_id, state) in &config.components {
             self.enabled_map
                 .write()
                 .insert(component_id.clone(), state.enabled);
 
             if let Some(component) = self.registry.get(component_id) {
+                has_plaintext_secrets |= secret_keys(&*component).iter().any(|key| {
+                    state
+                        .settings
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|v| !v.is_empty())
+                });
+                let settings = self.inject_stored_secrets(&*component, state.settings.clone());
+
                 // 先校验已保存配置是否符合当前 schema
-                if let Err(e) = component.validate_settings(&state.settings).await {
+                if let Err(e) = component.validate_settings(&settings).await {
                     warn!(
                         "组件 {} 的已保存配置校验失败，跳过加载: {}",
                         component_id, e
@@ -395,7 +494,7 @@ impl ConfigManager {
                     continue;
                 }
 
-                if let Err(e) = component.apply_settings(state.settings.clone()).await {
+                if let Err(e) = component.apply_settings(settings).await {
                     warn!("加载组件配置失败: {}, 错误: {}", component_id, e);
                 } else {
                     component.on_settings_changed();
@@ -412,6 +511,7 @@ impl ConfigManager {
                 {
                     continue;
                 }
+                let defaults = self.inject_stored_secrets(&*component, defaults);
                 if let Err(e) = component.apply_settings(defaults).await {
                     warn!("应用默认配置失败: {}, 错误: {}", component_id, e);
                 } else {
@@ -424,6 +524,13 @@ impl ConfigManager {
         // 保存配置快照，供后续 register() 恢复延迟注册组件的配置
         *self.loaded_config.write() = Some(config.clone());
 
+        if has_plaintext_secrets {
+            match self.save_to_storage() {
+                Ok(()) => info!("已将配置文件中的明文机密字段迁移至机密存储"),
+                Err(e) => warn!("迁移明文机密字段失败，将在下次保存时重试: {}", e),
+            }
+        }
+
         info!(
             "配置加载完成，已加载 {} 个持久化配置，共 {} 个已注册组件",
             config.components.len(),
@@ -435,6 +542,7 @@ impl ConfigManager {
     /// 构建包含所有已注册组件当前配置的 PersistentConfig 对象。
     ///
     /// 此方法仅读取状态、构建数据结构，不执行任何 I/O。
+    /// 机密字段被剔除（由 `save_to_storage` 另存机密存储），
     /// 返回的 PersistentConfig 可供本地持久化或远程同步使用。
     pub fn build_persistent_config(&self) -> PersistentConfig {
         let mut config = PersistentConfig::default();
@@ -442,7 +550,7 @@ impl ConfigManager {
         for component in self.registry.get_all() {
             let component_id = component.component_id().to_string();
             let enabled = self.is_enabled(&component_id);
-            let settings = component.get_settings();
+            let settings = strip_secret_fields(&*component, component.get_settings());
 
             config
                 .components
@@ -451,11 +559,12 @@ impl ConfigManager {
 
         config
     }
-    /// 将当前所有组件的配置保存到本地持久化文件。
+    /// 将当前所有组件的配置保存到本地持久化文件（机密字段先写入机密存储）。
     /// 返回：保存成功返回 Ok，失败返回 Err。
     /// 远程同步已提取到 bootstrap.rs 中，由 ConfigEvent 监听器负责触发。
     /// 保存成功后更新内存中的配置快照，供后续 register() 恢复延迟注册组件使用。
     pub fn save_to_storage(&self) -> Result<(), ConfigError> {
+        self.persist_secrets()?;
         let config = self.build_persistent_config();
         self.store.save(&config)?;
         // 保存成功后更新内存快照
@@ -463,6 +572,206 @@ impl ConfigManager {
         Ok(())
     }
 
+    // endregion
+
+    // region: 远程同步
+
+    /// 与远程存储同步配置：三方合并、推送本地、拉取远端或裁决冲突（见 `sync` 模块）。
+    /// 同步结果中来自远端的组件经 `apply_synced_config` 应用并持久化。
+    pub async fn sync_config(
+        &self,
+        storage: &dyn StorageService,
+        mode: SyncMode,
+    ) -> Result<SyncReport, ConfigError> {
+        self.sync.run(self, storage, mode).await
+    }
+
+    /// 获取最近一次同步留下的未裁决冲突
+    pub fn sync_conflicts(&self) -> Vec<SyncConflict> {
+        self.sync.pending_conflicts()
+    }
+
+    /// 应用远程同步得到的配置。
+    ///
+    /// 仅处理 `component_ids` 中已注册的组件：校验 → 应用 settings → 写入 enabled；
+    /// 校验或应用失败的组件保留本机值并记日志。全部应用后统一持久化，失败则整体回滚；
+    /// 持久化成功后再逐个触发 `on_settings_changed` 与变更事件。
+    /// 返回：实际应用的组件 ID。
+    pub async fn apply_synced_config(
+        &self,
+        config: &PersistentConfig,
+        component_ids: &[String],
+    ) -> Result<Vec<String>, ConfigError> {
+        // (组件, 旧 settings, 旧 enabled, settings 是否变化, enabled 是否变化)
+        let mut applied = Vec::new();
+        for component_id in component_ids {
+            let (Some(component), Some(state)) = (
+                self.registry.get(component_id),
+                config.components.get(component_id),
+            ) else {
+                continue;
+            };
+            // 同步内容不含机密字段，沿用本机当前值
+            let incoming = retain_current_secrets(&*component, state.settings.clone());
+            if let Err(e) = component.validate_settings(&incoming).await {
+                warn!(
+                    "远端配置校验失败，保留本机配置: {}, 错误: {}",
+                    component_id, e
+                );
+                continue;
+            }
+            let old_settings = component.get_settings();
+            let old_enabled = self.is_enabled(component_id);
+            let settings_changed = old_settings != incoming;
+            if settings_changed {
+                if let Err(e) = component.apply_settings(incoming).await {
+                    warn!(
+                        "远端配置应用失败，保留本机配置: {}, 错误: {}",
+                        component_id, e
+                    );
+                    continue;
+                }
+            }
+            self.enabled_map
+                .write()
+                .insert(component_id.clone(), state.enabled);
+            applied.push((
+                component,
+                old_settings,
+                old_enabled,
+                settings_changed,
+                old_enabled != state.enabled,
+            ));
+        }
+
+        if applied.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 先持久化，成功后才触发回调与事件
+        if let Err(e) = self.save_to_storage() {
+            for (component, old_settings, old_enabled, settings_changed, _) in applied {
+                if settings_changed {
+                    let _ = component.apply_settings(old_settings).await;
+                }
+                self.enabled_map
+                    .write()
+                    .insert(component.component_id().to_string(), old_enabled);
+            }
+            return Err(e);
+        }
+
+        let mut ids = Vec::with_capacity(applied.len());
+        for (component, _, _, settings_changed, enabled_changed) in applied {
+            let component_id = component.component_id().to_string();
+            if settings_changed {
+                component.on_settings_changed();
+                self.event_sender
+                    .send(ConfigEvent::SettingsChanged {
+                        component_id: component_id.clone(),
+                        component_type: component.component_type(),
+                    })
+                    .ok();
+            }
+            if enabled_changed {
+                self.event_sender
+                    .send(ConfigEvent::EnabledChanged {
+                        component_id: component_id.clone(),
+                        component_type: component.component_type(),
+                        enabled: self.is_enabled(&component_id),
+                    })
+                    .ok();
+            }
+            ids.push(component_id);
+        }
+        info!("已应用远端配置: {:?}", ids);
+        Ok(ids)
+    }
+
+    // endregion
+
+    // region: 机密字段
+
+    /// 取得机密存储；首次调用时按平台选择后端
+    fn secret_store(&self) -> &Arc<dyn SecretStore> {
+        self.secrets
+            .get_or_init(|| open_secret_store(&self.config_dir))
+    }
+
+    /// 为 settings 中缺失或为空的机密字段补上机密存储中的值（加载已持久化配置、应用默认值时使用）。
+    /// 读取失败时记录警告并保持缺失（组件按默认值处理）。
+    fn inject_stored_secrets(
+        &self,
+        component: &dyn Configurable,
+        settings: serde_json::Value,
+    ) -> serde_json::Value {
+        let keys = secret_keys(component);
+        let serde_json::Value::Object(mut object) = settings else {
+            return settings;
+        };
+        for key in keys {
+            let has_value = object
+                .get(&key)
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| !v.is_empty());
+            if has_value {
+                continue;
+            }
+            let name = secret_name(component.component_id(), &key);
+            let cached = self.secret_cache.read().get(&name).cloned();
+            let value = match cached {
+                Some(value) => value,
+                None => match self.secret_store().get(&name) {
+                    Ok(value) => {
+                        let value = value.unwrap_or_default();
+                        self.secret_cache.write().insert(name, value.clone());
+                        value
+                    }
+                    Err(e) => {
+                        warn!("读取机密字段失败: {}, 错误: {}", name, e);
+                        continue;
+                    }
+                },
+            };
+            if !value.is_empty() {
+                object.insert(key, serde_json::Value::String(value));
+            }
+        }
+        serde_json::Value::Object(object)
+    }
+
+    /// 将各组件机密字段的当前值写入机密存储：与已知值相同的跳过，空值删除条目。
+    /// 任一写入失败即返回错误，调用方据此中止本次持久化。
+    fn persist_secrets(&self) -> Result<(), ConfigError> {
+        for component in self.registry.get_all() {
+            let keys = secret_keys(&*component);
+            if keys.is_empty() {
+                continue;
+            }
+            let settings = component.get_settings();
+            for key in keys {
+                let value = settings.get(&key).and_then(|v| v.as_str()).unwrap_or("");
+                let name = secret_name(component.component_id(), &key);
+                let known = self.secret_cache.read().get(&name).cloned();
+                if known.as_deref().unwrap_or("") == value {
+                    continue;
+                }
+                if value.is_empty() {
+                    self.secret_store().delete(&name)?;
+                } else {
+                    self.secret_store().set(&name, value)?;
+                }
+                debug!("机密字段已更新: {}", name);
+                self.secret_cache.write().insert(name, value.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    // endregion
+
+    // region: 插件事件
+
     /// 处理 PluginManager 发来的 PluginRuntimeEvent。
     ///
     /// 纯业务逻辑：注册/解注册 Configurable，转发 ConfigEvent 通知 SessionRouter。
@@ -560,9 +869,71 @@ fn strip_transient_fields(
     serde_json::Value::Object(object)
 }
 
+/// 组件 schema 中声明为 `Secret` 的字段 key（schema 无效时视为没有）
+fn secret_keys(component: &dyn Configurable) -> Vec<String> {
+    component
+        .settings_contribution()
+        .map(|contribution| contribution.secret_keys())
+        .unwrap_or_default()
+}
+
+/// 机密值在机密存储中的名称：`<componentId>/<key>`
+fn secret_name(component_id: &str, key: &str) -> String {
+    format!("{}/{}", component_id, key)
+}
+
+/// 从 settings 中剔除机密字段，得到可写入持久化配置（及远程同步）的内容。
+fn strip_secret_fields(
+    component: &dyn Configurable,
+    settings: serde_json::Value,
+) -> serde_json::Value {
+    let keys = secret_keys(component);
+    let serde_json::Value::Object(mut object) = settings else {
+        return settings;
+    };
+    for key in keys {
+        object.remove(&key);
+    }
+    serde_json::Value::Object(object)
+}
+
+/// 用组件当前值补全缺失或为 `SECRET_REDACTED` 占位的机密字段。
+///
+/// 外部写入（CLI 读出脱敏值后原样写回、只提交部分字段）与远程同步（内容不含机密）
+/// 都不应清空本机机密；显式写入空串仍表示清除。
+fn retain_current_secrets(
+    component: &dyn Configurable,
+    settings: serde_json::Value,
+) -> serde_json::Value {
+    let keys = secret_keys(component);
+    if keys.is_empty() {
+        return settings;
+    }
+    let serde_json::Value::Object(mut object) = settings else {
+        return settings;
+    };
+    let current = component.get_settings();
+    for key in keys {
+        let missing = object
+            .get(&key)
+            .is_none_or(|value| value.as_str() == Some(SECRET_REDACTED));
+        if !missing {
+            continue;
+        }
+        match current.get(&key) {
+            Some(value) => object.insert(key, value.clone()),
+            None => object.remove(&key),
+        };
+    }
+    serde_json::Value::Object(object)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::config::secret_store::MemorySecretStore;
+    use crate::core::config::setting_builders::SchemaBuilder;
+    use async_trait::async_trait;
     use serde_json::json;
     use zerolaunch_plugin_api::config::{ComponentCore, SettingDefinition};
     use zerolaunch_plugin_api::plugin::{PluginKind, PluginMetadata, PluginMode};
@@ -570,6 +941,7 @@ mod tests {
     use zerolaunch_plugin_host::adapter::remote_component::{RemoteComponent, RemoteComponentKind};
     use zerolaunch_plugin_host::client::JsonRpcClient;
     use zerolaunch_plugin_host::manager::PluginRegistration;
+    use zerolaunch_plugin_host::metrics::PluginMetrics;
     use zerolaunch_plugin_protocol::manifest::{Manifest, PluginSection};
 
     /// 测试用最小 Configurable —— 仅承载身份元数据，空 schema 与空设置。
@@ -592,6 +964,170 @@ mod tests {
         }
     }
 
+    /// 测试用带机密字段的组件 —— 普通字段 account 与机密字段 token。
+    struct SecretComponent {
+        /// 组件 ID、名称、类型等元数据。
+        core: ComponentCore,
+        /// 当前设置值。
+        settings: RwLock<serde_json::Value>,
+    }
+
+    impl SecretComponent {
+        /// 创建 id 为 `secret-test` 的组件
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                core: ComponentCore::new(
+                    "secret-test".into(),
+                    "机密测试".into(),
+                    String::new(),
+                    ComponentType::Core,
+                    0,
+                ),
+                settings: RwLock::new(json!({})),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Configurable for SecretComponent {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            vec![
+                SchemaBuilder::text("account", "Account", "")
+                    .default("")
+                    .build(),
+                SchemaBuilder::secret("token", "Token", "")
+                    .default("")
+                    .build(),
+            ]
+        }
+        fn get_settings(&self) -> serde_json::Value {
+            self.settings.read().clone()
+        }
+        async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+            *self.settings.write() = settings;
+            Ok(())
+        }
+    }
+
+    /// 机密字段契约：值写入机密存储而不进入配置文件；对外读取脱敏，
+    /// 写回脱敏占位保持原值；重新加载后从机密存储恢复；写入空串即删除。
+    #[tokio::test]
+    async fn secrets_are_kept_out_of_persistent_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = Arc::new(MemorySecretStore::default());
+        let cm = ConfigManager::with_secret_store(dir.path().into(), secrets.clone());
+        cm.register(SecretComponent::new()).await;
+
+        cm.apply_settings("secret-test", json!({"account": "me", "token": "s3cret"}))
+            .await
+            .unwrap();
+        let raw = std::fs::read_to_string(dir.path().join("zerolaunch_config.json")).unwrap();
+        assert!(raw.contains("\"me\""));
+        assert!(!raw.contains("s3cret"));
+        assert!(!cm.build_persistent_config().components["secret-test"]
+            .settings
+            .as_object()
+            .unwrap()
+            .contains_key("token"));
+        assert_eq!(
+            secrets.get("secret-test/token").unwrap().as_deref(),
+            Some("s3cret")
+        );
+
+        let redacted = cm.get_redacted_settings("secret-test").unwrap();
+        assert_eq!(redacted["token"], SECRET_REDACTED);
+        cm.apply_settings(
+            "secret-test",
+            json!({"account": "you", "token": SECRET_REDACTED}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cm.get_settings("secret-test").unwrap()["token"], "s3cret");
+
+        let reloaded = ConfigManager::with_secret_store(dir.path().into(), secrets.clone());
+        reloaded.register(SecretComponent::new()).await;
+        reloaded.load_from_storage().await.unwrap();
+        assert_eq!(
+            reloaded.get_settings("secret-test").unwrap(),
+            json!({"account": "you", "token": "s3cret"})
+        );
+
+        reloaded
+            .apply_settings("secret-test", json!({"account": "you", "token": ""}))
+            .await
+            .unwrap();
+        assert_eq!(secrets.get("secret-test/token").unwrap(), None);
+    }
+
+    /// 旧版配置文件中的明文机密字段在加载后迁入机密存储，并从配置文件中移除。
+    #[tokio::test]
+    async fn load_migrates_plaintext_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("zerolaunch_config.json"),
+            json!({
+                "version": "3",
+                "components": {
+                    "secret-test": {
+                        "enabled": true,
+                        "settings": {"account": "me", "token": "legacy"}
+                    }
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let secrets = Arc::new(MemorySecretStore::default());
+        let cm = ConfigManager::with_secret_store(dir.path().into(), secrets.clone());
+        cm.register(SecretComponent::new()).await;
+        cm.load_from_storage().await.unwrap();
+
+        assert_eq!(cm.get_settings("secret-test").unwrap()["token"], "legacy");
+        assert_eq!(
+            secrets.get("secret-test/token").unwrap().as_deref(),
+            Some("legacy")
+        );
+        let raw = std::fs::read_to_string(dir.path().join("zerolaunch_config.json")).unwrap();
+        assert!(!raw.contains("legacy"));
+    }
+
+    /// 部分写入契约：合并只覆盖给出的顶层键；校验问题按字段全部列出。
+    #[tokio::test]
+    async fn merge_settings_patches_top_level_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let cm = ConfigManager::with_secret_store(
+            dir.path().into(),
+            Arc::new(MemorySecretStore::default()),
+        );
+        cm.register(SecretComponent::new()).await;
+        cm.apply_settings("secret-test", json!({"account": "me", "token": "s3cret"}))
+            .await
+            .unwrap();
+
+        let merged = cm
+            .merge_settings("secret-test", json!({"account": "you"}))
+            .unwrap();
+        assert_eq!(merged, json!({"account": "you", "token": "s3cret"}));
+        assert!(cm.merge_settings("secret-test", json!([1])).is_err());
+        assert!(matches!(
+            cm.merge_settings("missing", json!({})),
+            Err(ConfigError::NotFound(_))
+        ));
+
+        let issues = cm
+            .validation_issues("secret-test", &json!({"account": 1, "token": false}))
+            .unwrap();
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(pointers, ["/account", "/token"]);
+        assert!(cm
+            .validation_issues("secret-test", &merged)
+            .unwrap()
+            .is_empty());
+    }
+
     /// 注册查重契约：同一 component_id 第二次注册被拒绝，先注册者不被覆盖。
     #[tokio::test]
     async fn register_rejects_duplicate_component_id() {
@@ -646,8 +1182,13 @@ mod tests {
         let (req_tx, _req_rx) = tokio::sync::mpsc::channel(16);
         let (notif_tx, _notif_rx) = tokio::sync::mpsc::channel(16);
         let (reader, writer) = tokio::io::duplex(64);
-        let client =
-            JsonRpcClient::new(tokio::io::BufReader::new(reader), writer, req_tx, notif_tx);
+        let client = JsonRpcClient::new(
+            tokio::io::BufReader::new(reader),
+            writer,
+            req_tx,
+            notif_tx,
+            Arc::new(PluginMetrics::default()),
+        );
         let metadata = Arc::new(PluginMetadata {
             id: "com.example.collide".into(),
             name: "碰撞测试".into(),
@@ -661,6 +1202,7 @@ mod tests {
             hotkey: None,
             icon: None,
             mode: PluginMode::Panel,
+            global_query: false,
         });
         let make_component = |component_id: &str, name: &str| {
             Arc::new(RemoteComponent::new(
@@ -696,6 +1238,7 @@ mod tests {
                 },
                 runtime: Default::default(),
                 components: Default::default(),
+                permissions: Default::default(),
                 ui: None,
                 icon: None,
             },
@@ -1,14 +1,25 @@
 pub mod bias_settings;
+pub mod config_sync_settings;
 pub mod event;
 pub mod manager;
 pub mod models;
+pub mod plugin_registry_settings;
 pub mod registry;
+pub mod search_fusion_settings;
+pub mod secret_store;
 pub mod setting_builders;
 pub mod store;
+pub mod sync;
 
 pub use bias_settings::{BiasEntry, BiasSettings};
+pub use config_sync_settings::{ConfigSyncSettings, CONFIG_SYNC_CONFIG_ID};
 pub use event::{ConfigEvent, ConfigEventReceiver, ConfigEventSender};
 pub use manager::ConfigManager;
 pub use models::{ComponentPersistentState, PersistentConfig};
+pub use plugin_registry_settings::{PluginRegistrySettings, TrustedKey, PLUGIN_REGISTRY_CONFIG_ID};
 pub use registry::ConfigurableRegistry;
+pub use search_fusion_settings::{
+    EngineWeight, FusionStrategy, SearchFusionSettings, SEARCH_FUSION_CONFIG_ID,
+};
 pub use store::ConfigStore;
+pub use sync::{SyncConflict, SyncMode, SyncReport, SyncSide, SyncStatus};
@@ -24,7 +24,7 @@ pub struct ComponentSchemaSnapshot {
 }
 
 /// 持久化配置文件格式（config_v3.json）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PersistentConfig {
     /// 配置格式版本
     #[serde(rename = "version", default = "default_config_version")]
@@ -48,7 +48,7 @@ impl Default for PersistentConfig {
 }
 
 /// 单个组件的持久化状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ComponentPersistentState {
     /// 是否启用
     #[serde(rename = "enabled", default)]
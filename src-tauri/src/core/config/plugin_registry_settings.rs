@@ -0,0 +1,33 @@
+//! 插件注册表（插件市场）配置 —— 核心配置类型。
+//!
+//! 与 `bias_settings` 相同按 P1 放置约定位于 core/config/（L2）：
+//! 由 `builtin_plugin/config/plugin_registry_config.rs`（Configurable 组件）读写 schema，
+//! 由 `plugin_framework::package_registry`（L3）读取索引地址与受信任的发布者公钥。
+//! 持久化键名与前端 schema 一致（source/trustedKeys/publisher/publicKey）。
+
+use serde::{Deserialize, Serialize};
+
+/// 插件注册表配置组件的 component_id。
+pub const PLUGIN_REGISTRY_CONFIG_ID: &str = "plugin-registry-config";
+
+/// 插件注册表配置的根结构 —— 反序列化自 `plugin-registry-config` 组件的持久化设置。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PluginRegistrySettings {
+    /// 注册表索引地址：本地 JSON 文件路径或 http(s) URL，为空表示未配置注册表。
+    #[serde(rename = "source", default)]
+    pub source: String,
+    /// 受信任的发布者公钥（信任库），插件包签名只接受这些公钥。
+    #[serde(rename = "trustedKeys", default)]
+    pub trusted_keys: Vec<TrustedKey>,
+}
+
+/// 单个受信任的发布者公钥 —— `PluginRegistrySettings.trusted_keys` 的元素。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedKey {
+    /// 发布者标识，与注册表索引中插件的 `publisher` 字段匹配。
+    #[serde(rename = "publisher", default)]
+    pub publisher: String,
+    /// ed25519 公钥（32 字节）的 base64 编码。
+    #[serde(rename = "publicKey", default)]
+    pub public_key: String,
+}
@@ -0,0 +1,82 @@
+//! 多搜索引擎分数融合配置 —— 核心配置类型。
+//!
+//! 与 `bias_settings` 相同按 P1 放置约定位于 core/config/（L2）：
+//! 由 `builtin_plugin/config/search_fusion_config.rs`（Configurable 组件）读写 schema，
+//! 由 `PluginComponentRegistry::build_search_pipeline`（L3）读取并构造多引擎搜索管道。
+//! 持久化键名与前端 schema 一致（strategy/rrfK/weights/engineId/weight）。
+
+use serde::{Deserialize, Serialize};
+
+/// 搜索融合配置组件的 component_id。
+pub const SEARCH_FUSION_CONFIG_ID: &str = "search-fusion-config";
+
+/// 多引擎分数融合策略。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FusionStrategy {
+    /// 加权求和：各引擎分数按自身最高分归一化后按权重加权求和。
+    #[default]
+    #[serde(rename = "weighted_sum")]
+    WeightedSum,
+    /// 取最大值：取加权归一化分数最高的引擎作为该候选项的分数。
+    #[serde(rename = "max")]
+    Max,
+    /// 倒数排名融合（RRF）：只看各引擎内的名次，与分数量纲无关。
+    #[serde(rename = "rrf")]
+    ReciprocalRank,
+}
+
+/// 搜索融合配置的根结构 —— 反序列化自 `search-fusion-config` 组件的持久化设置。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchFusionSettings {
+    /// 融合策略（仅启用多个搜索引擎时生效）。
+    #[serde(rename = "strategy", default)]
+    pub strategy: FusionStrategy,
+    /// RRF 平滑常数 k，越大名次差异的影响越平缓。
+    #[serde(rename = "rrfK", default = "SearchFusionSettings::default_rrf_k")]
+    pub rrf_k: u32,
+    /// 各引擎权重（未列出的引擎权重为 1）。
+    #[serde(rename = "weights", default)]
+    pub weights: Vec<EngineWeight>,
+}
+
+/// 单个搜索引擎的融合权重 —— `SearchFusionSettings.weights` 的元素。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineWeight {
+    /// 搜索引擎的 component_id。
+    #[serde(rename = "engineId", default)]
+    pub engine_id: String,
+    /// 融合权重，0 表示该引擎只参与说明、不影响融合分。
+    #[serde(rename = "weight", default = "EngineWeight::default_weight")]
+    pub weight: f64,
+}
+
+impl SearchFusionSettings {
+    fn default_rrf_k() -> u32 {
+        60
+    }
+
+    /// 查询指定引擎的融合权重：未配置时为 1，负值按 0 处理。
+    pub fn weight_of(&self, engine_id: &str) -> f64 {
+        self.weights
+            .iter()
+            .find(|w| w.engine_id == engine_id)
+            .map(|w| w.weight.max(0.0))
+            .unwrap_or(1.0)
+    }
+}
+
+impl Default for SearchFusionSettings {
+    fn default() -> Self {
+        Self {
+            strategy: FusionStrategy::default(),
+            rrf_k: Self::default_rrf_k(),
+            weights: Vec::new(),
+        }
+    }
+}
+
+impl EngineWeight {
+    fn default_weight() -> f64 {
+        1.0
+    }
+}
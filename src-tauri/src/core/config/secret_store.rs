@@ -0,0 +1,434 @@
+//! 机密字段存储 —— `SchemaKind::Secret` 字段值的保管处。
+//!
+//! 机密值不进入 `PersistentConfig`（也就不会随远程同步上传），由 `ConfigManager`
+//! 以 `<componentId>/<key>` 为名存取。后端可插拔：
+//! - `KeyringSecretStore`：系统钥匙串（Windows 凭据管理器 / macOS 钥匙串 / Secret Service）
+//! - `VaultSecretStore`：钥匙串不可用时的本地加密库 `zerolaunch_secrets.vault`，
+//!   ChaCha20-Poly1305 加密，密钥由口令经 Argon2id 派生。口令有两种来源：
+//!   - 口令模式：环境变量 `ZEROLAUNCH_SECRET_PASSPHRASE`，口令不落盘；
+//!   - 本机密钥模式（较弱）：未设置环境变量时，使用首次创建时生成并保存在配置目录
+//!     `zerolaunch_secrets.key` 中的随机口令。口令与密文同机存放，只防止单独拷走的
+//!     加密库（备份、同步盘）泄露明文，不能防御可读取用户目录的本机进程；启用时记录警告。
+//!
+//!   机密字段是组件运行所需的凭据（如 API 密钥），拒绝保存会让用户每次启动都重新填写，
+//!   因此无钥匙串时仍以较弱模式保存；需要更强保护时设置口令环境变量。
+
+use crate::core::config::store::write_atomic;
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use keyring::{Entry, Error as KeyringError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use zerolaunch_plugin_api::config::{ConfigError, SecretString};
+
+/// 系统钥匙串中的服务名。
+const KEYRING_SERVICE: &str = "ZeroLaunch-rs";
+/// 探测钥匙串可用性时读取的条目名（不会被写入）。
+const KEYRING_PROBE_ENTRY: &str = "__probe__";
+/// 本地加密库文件名（位于配置目录）。
+const VAULT_FILE: &str = "zerolaunch_secrets.vault";
+/// 自动生成的加密库口令文件名（位于配置目录）。
+const VAULT_PASSPHRASE_FILE: &str = "zerolaunch_secrets.key";
+/// 指定加密库口令的环境变量。
+pub const VAULT_PASSPHRASE_ENV: &str = "ZEROLAUNCH_SECRET_PASSPHRASE";
+/// 加密库文件格式版本。
+const VAULT_VERSION: u32 = 1;
+/// Argon2 盐长度（字节）。
+const SALT_LEN: usize = 16;
+
+/// 机密存储后端。
+///
+/// 同步接口：钥匙串与本地加密库的读写都是短时阻塞操作，
+/// 由 `ConfigManager::save_to_storage` 等同步路径直接调用。
+pub trait SecretStore: Send + Sync {
+    /// 读取机密值；不存在时返回 None
+    fn get(&self, name: &str) -> Result<Option<String>, ConfigError>;
+    /// 写入（覆盖）机密值
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError>;
+    /// 删除机密值；不存在时视为成功
+    fn delete(&self, name: &str) -> Result<(), ConfigError>;
+}
+
+/// 按平台能力选择机密存储：系统钥匙串可用时优先使用，否则使用配置目录下的本地加密库。
+pub fn open_secret_store(config_dir: &Path) -> Arc<dyn SecretStore> {
+    if let Some(store) = KeyringSecretStore::probe() {
+        info!("机密存储：系统钥匙串");
+        return Arc::new(store);
+    }
+    info!("系统钥匙串不可用，机密存储使用本地加密库");
+    Arc::new(VaultSecretStore::new(config_dir))
+}
+
+/// 将后端错误包装为 ConfigError
+fn secret_err(message: impl std::fmt::Display) -> ConfigError {
+    ConfigError::SecretStore(message.to_string())
+}
+
+// ============================================================================
+// KeyringSecretStore — 系统钥匙串
+// ============================================================================
+
+/// 系统钥匙串后端，每个机密值对应一个 `ZeroLaunch-rs` 服务下的凭据条目。
+pub struct KeyringSecretStore;
+
+impl KeyringSecretStore {
+    /// 探测钥匙串是否可用：读取一个不存在的条目，得到「条目不存在」即说明后端可访问
+    pub fn probe() -> Option<Self> {
+        let entry = Entry::new(KEYRING_SERVICE, KEYRING_PROBE_ENTRY).ok()?;
+        match entry.get_password() {
+            Ok(_) | Err(KeyringError::NoEntry) => Some(Self),
+            Err(e) => {
+                debug!("系统钥匙串不可用: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 取得指定名称的凭据条目
+    fn entry(name: &str) -> Result<Entry, ConfigError> {
+        Entry::new(KEYRING_SERVICE, name).map_err(secret_err)
+    }
+}
+
+impl SecretStore for KeyringSecretStore {
+    fn get(&self, name: &str) -> Result<Option<String>, ConfigError> {
+        match Self::entry(name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(KeyringError::NoEntry) => Ok(None),
+            Err(e) => Err(secret_err(e)),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        Self::entry(name)?.set_password(value).map_err(secret_err)
+    }
+
+    fn delete(&self, name: &str) -> Result<(), ConfigError> {
+        match Self::entry(name)?.delete_credential() {
+            Ok(()) | Err(KeyringError::NoEntry) => Ok(()),
+            Err(e) => Err(secret_err(e)),
+        }
+    }
+}
+
+// ============================================================================
+// VaultSecretStore — 口令加密的本地加密库
+// ============================================================================
+
+/// 加密库的磁盘格式：全部机密值序列化为 JSON 后整体加密。
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    /// 文件格式版本
+    #[serde(rename = "version", default)]
+    version: u32,
+    /// Argon2id 盐（base64）
+    #[serde(rename = "salt", default)]
+    salt: String,
+    /// ChaCha20-Poly1305 nonce（base64），每次写入重新生成
+    #[serde(rename = "nonce", default)]
+    nonce: String,
+    /// 密文（base64，含认证标签）
+    #[serde(rename = "ciphertext", default)]
+    ciphertext: String,
+}
+
+/// 已解锁的加密库：派生密钥与解密后的全部条目。
+struct UnlockedVault {
+    /// 口令派生的密钥
+    key: Key,
+    /// 派生密钥所用的盐，重写文件时沿用
+    salt: Vec<u8>,
+    /// 机密名 → 明文值
+    entries: BTreeMap<String, String>,
+}
+
+/// 本地加密库后端。
+///
+/// 首次读写时才解析口令、派生密钥并解密（Argon2 派生有意做得较慢），
+/// 之后条目常驻内存，每次写入整体重新加密并原子替换文件。
+pub struct VaultSecretStore {
+    /// 加密库文件路径
+    path: PathBuf,
+    /// 自动生成的口令文件路径
+    passphrase_path: PathBuf,
+    /// 解锁后的内容；None 表示尚未解锁
+    unlocked: Mutex<Option<UnlockedVault>>,
+}
+
+impl VaultSecretStore {
+    /// 创建位于 `config_dir` 下的加密库（惰性解锁，不做 I/O）
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            path: config_dir.join(VAULT_FILE),
+            passphrase_path: config_dir.join(VAULT_PASSPHRASE_FILE),
+            unlocked: Mutex::new(None),
+        }
+    }
+
+    /// 在已解锁的加密库上执行操作；首次调用时完成解锁
+    fn with_unlocked<T>(
+        &self,
+        f: impl FnOnce(&mut UnlockedVault) -> Result<T, ConfigError>,
+    ) -> Result<T, ConfigError> {
+        let mut guard = self.unlocked.lock();
+        if guard.is_none() {
+            *guard = Some(self.unlock()?);
+        }
+        let vault = guard.as_mut().expect("加密库已在上方解锁");
+        f(vault)
+    }
+
+    /// 解析口令并解密已有加密库；文件不存在时以新盐创建空库
+    fn unlock(&self) -> Result<UnlockedVault, ConfigError> {
+        let exists = self.path.exists();
+        let passphrase = self.resolve_passphrase(!exists)?;
+        if !exists {
+            let mut salt = vec![0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive_key(&passphrase, &salt)?;
+            return Ok(UnlockedVault {
+                key,
+                salt,
+                entries: BTreeMap::new(),
+            });
+        }
+
+        let file: VaultFile = serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        if file.version != VAULT_VERSION {
+            return Err(secret_err(format!(
+                "不支持的加密库版本: {}（当前版本 {}）",
+                file.version, VAULT_VERSION
+            )));
+        }
+        let decode = |field: &str| BASE64.decode(field).map_err(secret_err);
+        let salt = decode(&file.salt)?;
+        let nonce = decode(&file.nonce)?;
+        if nonce.len() != 12 {
+            return Err(secret_err("加密库 nonce 长度无效"));
+        }
+        let key = derive_key(&passphrase, &salt)?;
+        let plaintext = ChaCha20Poly1305::new(&key)
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode(&file.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| secret_err("加密库解密失败（口令不匹配或文件已损坏）"))?;
+        Ok(UnlockedVault {
+            key,
+            salt,
+            entries: serde_json::from_slice(&plaintext)?,
+        })
+    }
+
+    /// 口令来源：环境变量优先，否则读取配置目录下的口令文件。
+    /// 仅在新建加密库（`create`）时生成口令文件；已有加密库缺少口令时报错，不生成新口令。
+    fn resolve_passphrase(&self, create: bool) -> Result<SecretString, ConfigError> {
+        if let Ok(value) = std::env::var(VAULT_PASSPHRASE_ENV) {
+            if !value.is_empty() {
+                return Ok(SecretString::new(value));
+            }
+        }
+        if let Ok(value) = std::fs::read_to_string(&self.passphrase_path) {
+            if !value.trim().is_empty() {
+                warn!(
+                    "机密存储使用本机密钥模式（口令文件 {:?}），设置环境变量 {} 可改用不落盘的口令",
+                    self.passphrase_path, VAULT_PASSPHRASE_ENV
+                );
+                return Ok(SecretString::new(value.trim()));
+            }
+        }
+        if !create {
+            return Err(secret_err(format!(
+                "加密库缺少口令：请设置环境变量 {} 或恢复口令文件",
+                VAULT_PASSPHRASE_ENV
+            )));
+        }
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let generated = BASE64.encode(bytes);
+        write_atomic(&self.passphrase_path, generated.as_bytes())?;
+        warn!(
+            "已生成本地加密库口令 {:?}：口令与加密库同机存放（本机密钥模式），设置环境变量 {} 可改用不落盘的口令",
+            self.passphrase_path, VAULT_PASSPHRASE_ENV
+        );
+        Ok(SecretString::new(generated))
+    }
+
+    /// 以新 nonce 重新加密全部条目并原子写盘
+    fn save(&self, vault: &UnlockedVault) -> Result<(), ConfigError> {
+        let plaintext = serde_json::to_vec(&vault.entries)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&vault.key)
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|e| secret_err(format!("加密失败: {}", e)))?;
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            salt: BASE64.encode(&vault.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        write_atomic(&self.path, serde_json::to_string_pretty(&file)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl SecretStore for VaultSecretStore {
+    fn get(&self, name: &str) -> Result<Option<String>, ConfigError> {
+        self.with_unlocked(|vault| Ok(vault.entries.get(name).cloned()))
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        self.with_unlocked(|vault| {
+            let previous = vault.entries.insert(name.to_string(), value.to_string());
+            if let Err(e) = self.save(vault) {
+                // 写盘失败，回滚内存条目，保持与磁盘一致
+                match previous {
+                    Some(old) => vault.entries.insert(name.to_string(), old),
+                    None => vault.entries.remove(name),
+                };
+                return Err(e);
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, name: &str) -> Result<(), ConfigError> {
+        self.with_unlocked(|vault| {
+            let Some(previous) = vault.entries.remove(name) else {
+                return Ok(());
+            };
+            if let Err(e) = self.save(vault) {
+                vault.entries.insert(name.to_string(), previous);
+                return Err(e);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Argon2id（默认参数）由口令与盐派生 32 字节密钥
+fn derive_key(passphrase: &SecretString, salt: &[u8]) -> Result<Key, ConfigError> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.expose().as_bytes(), salt, key.as_mut_slice())
+        .map_err(|e| secret_err(format!("口令派生密钥失败: {}", e)))?;
+    Ok(key)
+}
+
+// ============================================================================
+// MemorySecretStore — 测试用内存后端
+// ============================================================================
+
+/// 仅存于内存的机密存储，供测试注入 ConfigManager，避免触及系统钥匙串。
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemorySecretStore {
+    /// 机密名 → 明文值
+    entries: Mutex<BTreeMap<String, String>>,
+}
+
+#[cfg(test)]
+impl SecretStore for MemorySecretStore {
+    fn get(&self, name: &str) -> Result<Option<String>, ConfigError> {
+        Ok(self.entries.lock().get(name).cloned())
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        self.entries
+            .lock()
+            .insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<(), ConfigError> {
+        self.entries.lock().remove(name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 加密库写入后可由新实例以同一口令解密读回，磁盘上不出现明文；删除后读不到。
+    #[test]
+    fn vault_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = VaultSecretStore::new(dir.path());
+        store
+            .set("storage-config/webdav_password", "hunter2")
+            .unwrap();
+        store.set("translator/llm_api_key", "sk-test").unwrap();
+
+        let raw = std::fs::read_to_string(dir.path().join(VAULT_FILE)).unwrap();
+        assert!(!raw.contains("hunter2"));
+
+        let reopened = VaultSecretStore::new(dir.path());
+        assert_eq!(
+            reopened.get("storage-config/webdav_password").unwrap(),
+            Some("hunter2".to_string())
+        );
+        reopened.delete("translator/llm_api_key").unwrap();
+        assert_eq!(
+            VaultSecretStore::new(dir.path())
+                .get("translator/llm_api_key")
+                .unwrap(),
+            None
+        );
+    }
+
+    /// 加密库与口令文件经共享原子写入器落盘：不残留临时文件，Unix 下仅所有者可读写。
+    #[test]
+    fn vault_files_are_private_and_leave_no_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = VaultSecretStore::new(dir.path());
+        store.set("translator/llm_api_key", "sk-test").unwrap();
+        store.set("translator/llm_api_key", "sk-next").unwrap();
+
+        let mut entries: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, [VAULT_PASSPHRASE_FILE, VAULT_FILE]);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            for name in [VAULT_PASSPHRASE_FILE, VAULT_FILE] {
+                let mode = std::fs::metadata(dir.path().join(name))
+                    .unwrap()
+                    .permissions()
+                    .mode();
+                assert_eq!(mode & 0o777, 0o600, "{name} 应仅所有者可读写");
+            }
+        }
+    }
+
+    /// 口令文件被替换（口令不匹配）时解锁失败，且不会覆盖原有加密库。
+    #[test]
+    fn vault_rejects_wrong_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        VaultSecretStore::new(dir.path())
+            .set("translator/llm_api_key", "sk-test")
+            .unwrap();
+        let before = std::fs::read(dir.path().join(VAULT_FILE)).unwrap();
+
+        std::fs::write(dir.path().join(VAULT_PASSPHRASE_FILE), "another passphrase").unwrap();
+        let store = VaultSecretStore::new(dir.path());
+        assert!(matches!(
+            store.get("translator/llm_api_key"),
+            Err(ConfigError::SecretStore(_))
+        ));
+        assert!(store.set("translator/llm_api_key", "sk-new").is_err());
+        assert_eq!(std::fs::read(dir.path().join(VAULT_FILE)).unwrap(), before);
+    }
+}
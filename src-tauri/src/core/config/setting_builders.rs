@@ -153,6 +153,14 @@ impl SchemaBuilder {
         Self::new(key, label, desc, SchemaNode::string(), WidgetHint::Hotkey)
     }
 
+    /// 创建机密字段（secret + Secret widget）。
+    ///
+    /// 值不写入持久化配置文件，由 ConfigManager 存入机密存储（系统钥匙串或本地加密库）；
+    /// 空串表示未设置。
+    pub fn secret(key: &str, label: &str, desc: &str) -> Self {
+        Self::new(key, label, desc, SchemaNode::secret(), WidgetHint::Secret)
+    }
+
     fn new(key: &str, label: &str, desc: &str, schema: SchemaNode, widget: WidgetHint) -> Self {
         Self {
             key: key.to_string(),
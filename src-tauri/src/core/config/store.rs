@@ -1,5 +1,6 @@
 use crate::core::config::models::{ComponentPersistentState, PersistentConfig};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 use zerolaunch_plugin_api::config::ConfigError;
 
@@ -48,49 +49,19 @@ impl ConfigStore {
         }
     }
 
-    /// 将配置保存到文件。
-    ///
-    /// 使用原子写入策略：先写入临时文件，再 rename 替换目标文件。
+    /// 将配置保存到文件（原子写入，见 `write_atomic`）。
     /// 避免写入过程中崩溃导致文件截断或损坏。
     pub fn save(&self, config: &PersistentConfig) -> Result<(), ConfigError> {
         let path: PathBuf = self.config_file_path();
-
-        // 确保目录存在
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
         let content = serde_json::to_string_pretty(config)?;
-
-        // 原子写入：先写 .tmp 文件，再 rename 替换目标
-        let tmp_path = path.with_extension("tmp");
-        std::fs::write(&tmp_path, &content)?;
-        // 同步文件数据到磁盘
-        if let Ok(file) = std::fs::File::open(&tmp_path) {
-            file.sync_all().ok();
-        }
-        // 在 Windows 上，rename 在同一卷内是原子操作
-        std::fs::rename(&tmp_path, &path)?;
-
+        write_atomic(&path, content.as_bytes())?;
         debug!("配置已保存到: {:?}", path);
         Ok(())
     }
 
-    /// 备份损坏的配置文件。
-    /// 将当前配置文件重命名为 .json.bak 后缀，保留现场便于排查。
+    /// 备份损坏的配置文件（见 `backup_corrupted`）。
     pub fn backup_corrupted(&self) -> Result<(), ConfigError> {
-        let path = self.config_file_path();
-        if !path.exists() {
-            return Ok(());
-        }
-
-        let backup_path = path.with_extension("json.bak");
-        // 如果已存在备份，先移除旧备份
-        if backup_path.exists() {
-            std::fs::remove_file(&backup_path).ok();
-        }
-        std::fs::rename(&path, &backup_path)?;
-        warn!("已备份损坏配置文件: {:?} → {:?}", path, backup_path);
+        backup_corrupted(&self.config_file_path())?;
         Ok(())
     }
 
@@ -109,6 +80,49 @@ impl ConfigStore {
     }
 }
 
+/// 原子写入文件：先写入同目录下的临时文件并落盘，再 rename 替换目标。
+///
+/// 临时文件名随机生成（`.<文件名>.XXXXXX.tmp`）：同目录的多个文件、同一文件的并发写入
+/// 各用各的临时文件，不会把对方写了一半的内容 rename 成目标；写入失败时临时文件自动删除。
+/// 临时文件以仅所有者可读写的权限创建（Unix 下为 0600），替换后目标文件沿用该权限。
+/// 父目录不存在时自动创建。配置、学习数据、索引快照等落盘文件统一经此写入。
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(dir)?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut tmp = tempfile::Builder::new()
+        .prefix(&format!(".{}.", file_name))
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    tmp.write_all(bytes)?;
+    // 同步文件数据到磁盘，再替换目标（Windows 上为 MoveFileEx 替换，同卷内原子）
+    tmp.as_file().sync_all()?;
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// 备份无法解析的落盘文件：重命名为 `.json.bak` 后缀（覆盖已有备份），保留现场便于排查。
+/// 文件不存在时直接返回。配置、学习数据、索引快照的损坏备份统一经此处理。
+pub fn backup_corrupted(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let backup_path = path.with_extension("json.bak");
+    // 如果已存在备份，先移除旧备份
+    if backup_path.exists() {
+        std::fs::remove_file(&backup_path).ok();
+    }
+    std::fs::rename(path, &backup_path)?;
+    warn!("已备份损坏文件: {:?} → {:?}", path, backup_path);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,9 +159,10 @@ mod tests {
         store.save(&config).expect("保存失败");
         let path = dir.path().join("zerolaunch_config.json");
         assert!(path.exists(), "保存后配置文件应存在于配置目录");
-        assert!(
-            !dir.path().join("zerolaunch_config.tmp").exists(),
-            "原子写入不应残留 .tmp 文件"
+        assert_eq!(
+            std::fs::read_dir(dir.path()).unwrap().count(),
+            1,
+            "原子写入不应残留临时文件"
         );
         let content = std::fs::read_to_string(&path).unwrap();
         let parsed: serde_json::Value =
@@ -196,6 +211,43 @@ mod tests {
         );
     }
 
+    /// 并发写入同一文件：各自使用独立临时文件，最终内容是某一次完整写入，且不残留临时文件。
+    #[test]
+    fn write_atomic_concurrent_writes_never_tear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("data.json");
+        let payloads: Vec<Vec<u8>> = (0..4u8).map(|i| vec![b'a' + i; 64 * 1024]).collect();
+        std::thread::scope(|scope| {
+            for payload in &payloads {
+                let path = &path;
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        // Windows 上目标正被并发替换时 rename 可能失败；这里只关心内容不撕裂
+                        write_atomic(path, payload).ok();
+                    }
+                });
+            }
+        });
+        let content = std::fs::read(&path).unwrap();
+        assert!(payloads.contains(&content), "文件内容应为某次完整写入");
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+    }
+
+    /// Unix 下原子写入的文件仅所有者可读写。
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_creates_owner_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.key");
+        write_atomic(&path, b"key").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
     #[test]
     fn backup_corrupted_noop_when_missing() {
         let (store, _dir) = temp_store();
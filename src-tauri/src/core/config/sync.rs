@@ -0,0 +1,772 @@
+//! 配置双向同步引擎 —— 基于 `StorageService` 的拉取、冲突检测与三方合并。
+//!
+//! 远端保存两份文件：`zerolaunch_config.json`（`PersistentConfig` 全量）与
+//! `zerolaunch_config.meta.json`（修订号 + 内容哈希）。本地配置目录保存最近一次同步
+//! 成功时远端的内容与修订（同步基线），作为三方合并的共同祖先：
+//! 按组件粒度比较 `components`，仅一侧改动取改动侧，两侧改成不同值即为冲突。
+//!
+//! 存在冲突时既不应用也不上传，冲突暂存于引擎，由 config-sync 组件的配置动作
+//! 逐组件裁决（`resolve_conflicts`），或以「推送本地 / 拉取远端」整体覆盖。
+//! 远端写入无比较交换语义：两台设备恰好同时推送时后写者覆盖先写者，
+//! 先写者下次同步会把差异视作远端改动重新合并，不会静默丢失本地修改。
+
+use crate::core::config::manager::ConfigManager;
+use crate::core::config::models::{ComponentPersistentState, PersistentConfig};
+use crate::core::config::store::write_atomic;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use zerolaunch_plugin_api::config::ConfigError;
+use zerolaunch_plugin_api::services::storage::storage_error::StorageError;
+use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
+
+/// 远端配置文件名（与旧版单向上传的文件名保持一致，兼容已有远端数据）。
+pub const REMOTE_CONFIG_FILE: &str = "zerolaunch_config.json";
+/// 远端修订元数据文件名。
+pub const REMOTE_META_FILE: &str = "zerolaunch_config.meta.json";
+/// 本地同步基线文件名（位于配置目录）。
+const SYNC_BASE_FILE: &str = "zerolaunch_sync_base.json";
+/// 仅属于本机、不参与合并的组件：存储后端配置决定同步去向，从远端拉取会在同步途中切换后端。
+const LOCAL_ONLY_COMPONENTS: &[&str] = &["storage-config"];
+
+/// 远端修订元数据：每次推送修订号 +1，内容哈希用于识别未经本引擎写入的远端改动。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteRevision {
+    /// 单调递增的修订号；旧版仅上传配置文件、没有元数据时视为 0
+    #[serde(rename = "revision", default)]
+    pub revision: u64,
+    /// 配置文件内容的 sha256（小写十六进制）
+    #[serde(rename = "contentHash", default)]
+    pub content_hash: String,
+    /// 推送时间（RFC 3339）
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: String,
+}
+
+/// 同步基线：最近一次同步成功时远端的修订与内容。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncBase {
+    /// 基线对应的远端修订
+    #[serde(rename = "remote", default)]
+    remote: RemoteRevision,
+    /// 基线对应的远端配置内容
+    #[serde(rename = "config", default)]
+    config: PersistentConfig,
+}
+
+/// 冲突裁决时选择保留的一侧。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncSide {
+    /// 保留本机配置
+    #[serde(rename = "local")]
+    Local,
+    /// 采用远端配置
+    #[serde(rename = "remote")]
+    Remote,
+}
+
+/// 同步方式。
+#[derive(Debug, Clone)]
+pub enum SyncMode {
+    /// 三方合并；有冲突时暂存冲突，不应用也不上传
+    Merge,
+    /// 以本机为准覆盖远端（远端独有的组件保留）
+    PushLocal,
+    /// 以远端为准覆盖本机（本机独有组件与仅本机组件保留）
+    PullRemote,
+    /// 按组件裁决冲突后合并：key 为 component_id，未覆盖的冲突继续暂存
+    Resolve(HashMap<String, SyncSide>),
+}
+
+/// 单个组件的同步冲突：基线之后本机与远端各自改成了不同的值。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SyncConflict {
+    /// 冲突组件 ID
+    #[serde(rename = "componentId")]
+    pub component_id: String,
+    /// 基线中的状态（首次同步或基线中不存在时为空）
+    #[serde(rename = "base")]
+    pub base: Option<ComponentPersistentState>,
+    /// 本机当前状态
+    #[serde(rename = "local")]
+    pub local: ComponentPersistentState,
+    /// 远端当前状态
+    #[serde(rename = "remote")]
+    pub remote: ComponentPersistentState,
+}
+
+/// 三方合并结果。
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    /// 合并后的配置；冲突组件暂取本机值
+    pub merged: PersistentConfig,
+    /// 按 component_id 排序的冲突列表
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// 一次同步的结果分类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SyncStatus {
+    /// 本机与远端一致，未发生读写
+    #[serde(rename = "upToDate")]
+    UpToDate,
+    /// 已拉取和/或推送
+    #[serde(rename = "synced")]
+    Synced,
+    /// 存在未裁决冲突，本次未应用也未上传
+    #[serde(rename = "conflicts")]
+    Conflicts,
+}
+
+/// 同步报告（经 config-sync 组件动作返回前端）。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SyncReport {
+    /// 结果分类
+    #[serde(rename = "status")]
+    pub status: SyncStatus,
+    /// 同步后的远端修订号（冲突时为当前远端修订号）
+    #[serde(rename = "revision")]
+    pub revision: u64,
+    /// 从远端应用到本机的组件 ID
+    #[serde(rename = "pulled")]
+    pub pulled: Vec<String>,
+    /// 是否向远端推送了新修订
+    #[serde(rename = "pushed")]
+    pub pushed: bool,
+    /// 未裁决的冲突
+    #[serde(rename = "conflicts")]
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// 按组件三方合并 `PersistentConfig.components`。
+///
+/// 规则：两侧相同取任一侧；本机未改（等于基线）取远端；远端未改取本机；
+/// 否则记为冲突并暂取本机值。某侧缺失组件表示「无意见」而非删除，直接取另一侧，
+/// 因此未安装某第三方插件的设备不会抹掉其他设备上该插件的配置。
+/// 仅本机组件（`LOCAL_ONLY_COMPONENTS`）始终取本机值。
+pub fn three_way_merge(
+    base: &PersistentConfig,
+    local: &PersistentConfig,
+    remote: &PersistentConfig,
+) -> MergeResult {
+    let ids: BTreeSet<&String> = local
+        .components
+        .keys()
+        .chain(remote.components.keys())
+        .collect();
+    let mut merged = PersistentConfig {
+        version: local.version.clone(),
+        components: HashMap::new(),
+    };
+    let mut conflicts = Vec::new();
+
+    for id in ids {
+        let base_state = base.components.get(id);
+        let chosen = match (local.components.get(id), remote.components.get(id)) {
+            (Some(l), _) if LOCAL_ONLY_COMPONENTS.contains(&id.as_str()) => l,
+            (Some(l), None) => l,
+            (None, Some(r)) => r,
+            (Some(l), Some(r)) if l == r || base_state == Some(r) => l,
+            (Some(l), Some(r)) if base_state == Some(l) => r,
+            (Some(l), Some(r)) => {
+                conflicts.push(SyncConflict {
+                    component_id: id.clone(),
+                    base: base_state.cloned(),
+                    local: l.clone(),
+                    remote: r.clone(),
+                });
+                l
+            }
+            (None, None) => continue,
+        };
+        merged.components.insert(id.clone(), chosen.clone());
+    }
+
+    MergeResult { merged, conflicts }
+}
+
+/// 以 `upper` 中的组件覆盖 `lower` 的同名组件，其余组件保留。
+fn overlay(lower: &PersistentConfig, upper: &PersistentConfig) -> PersistentConfig {
+    let mut result = PersistentConfig {
+        version: upper.version.clone(),
+        components: lower.components.clone(),
+    };
+    for (id, state) in &upper.components {
+        result.components.insert(id.clone(), state.clone());
+    }
+    result
+}
+
+/// 计算字节序列的 sha256（小写十六进制）。
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 将存储后端错误映射为配置错误。
+fn storage_err(e: StorageError) -> ConfigError {
+    ConfigError::SyncFailed(e.to_string())
+}
+
+/// 配置同步引擎：持有本地基线路径与待裁决冲突，由 `ConfigManager` 持有。
+///
+/// 同步过程串行执行（tokio Mutex 跨 .await 持有），自动同步与用户动作不会交错；
+/// 冲突列表使用 parking_lot 锁，仅在短临界区内读写。
+pub struct ConfigSync {
+    /// 同步基线文件路径
+    base_path: PathBuf,
+    /// 最近一次合并产生的未裁决冲突
+    conflicts: RwLock<Vec<SyncConflict>>,
+    /// 同步互斥锁
+    running: Mutex<()>,
+}
+
+impl ConfigSync {
+    /// 创建同步引擎，基线文件保存在 `config_dir` 下
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            base_path: config_dir.join(SYNC_BASE_FILE),
+            conflicts: RwLock::new(Vec::new()),
+            running: Mutex::new(()),
+        }
+    }
+
+    /// 获取最近一次同步留下的未裁决冲突
+    pub fn pending_conflicts(&self) -> Vec<SyncConflict> {
+        self.conflicts.read().clone()
+    }
+
+    /// 读取同步基线；不存在或损坏时返回 None（损坏按首次同步处理，冲突交由用户裁决）
+    fn load_base(&self) -> Option<SyncBase> {
+        let content = std::fs::read(&self.base_path).ok()?;
+        match serde_json::from_slice(&content) {
+            Ok(base) => Some(base),
+            Err(e) => {
+                warn!(
+                    "同步基线解析失败，按首次同步处理: {:?}, 错误: {}",
+                    self.base_path, e
+                );
+                None
+            }
+        }
+    }
+
+    /// 原子写入同步基线
+    fn save_base(&self, base: &SyncBase) -> Result<(), ConfigError> {
+        write_atomic(&self.base_path, &serde_json::to_vec_pretty(base)?)?;
+        Ok(())
+    }
+
+    /// 执行一次同步。
+    ///
+    /// 流程：读取远端修订并下载远端配置，以内容哈希校验修订元数据 →
+    /// 按 `mode` 计算合并结果（合并有冲突则暂存并返回）→ 将与本机不同的组件经
+    /// `ConfigManager::apply_synced_config` 应用 → 上传内容与远端不同或元数据与内容不符时
+    /// 推送新修订 → 更新基线。
+    pub async fn run(
+        &self,
+        cm: &ConfigManager,
+        storage: &dyn StorageService,
+        mode: SyncMode,
+    ) -> Result<SyncReport, ConfigError> {
+        let _running = self.running.lock().await;
+
+        let local = cm.build_persistent_config();
+        let base = self.load_base();
+        let meta = fetch_revision(storage).await?;
+        let fetched = match fetch_config(storage).await {
+            Err(e) if matches!(mode, SyncMode::PushLocal) => {
+                warn!("远端配置不可读，推送本地配置覆盖: {}", e);
+                None
+            }
+            result => result?,
+        };
+        // 元数据哈希与实际内容不符：远端配置被绕过本引擎改写（旧版单向上传、手工编辑），
+        // 改动按远端改动参与合并，且无论合并结果如何都重新推送，使元数据重新描述内容
+        let meta_matches = match (&meta, &fetched) {
+            (Some(m), Some((_, hash))) => m.content_hash == *hash,
+            _ => false,
+        };
+        if meta.is_some() && fetched.is_some() && !meta_matches {
+            warn!("远端配置内容与修订元数据的哈希不一致，视为外部改动");
+        }
+        let remote = fetched.map(|(config, _)| config);
+        let remote_revision = meta.as_ref().map_or(0, |m| m.revision);
+        let base_revision = base.as_ref().map_or(0, |b| b.remote.revision);
+
+        let merged = match (&mode, &remote) {
+            (SyncMode::PullRemote, None) => {
+                return Err(ConfigError::NotFound(REMOTE_CONFIG_FILE.to_string()))
+            }
+            (_, None) => local.clone(),
+            (SyncMode::PushLocal, Some(remote)) => overlay(remote, &local),
+            (SyncMode::PullRemote, Some(remote)) => {
+                let mut merged = overlay(&local, remote);
+                for id in LOCAL_ONLY_COMPONENTS {
+                    if let Some(state) = local.components.get(*id) {
+                        merged.components.insert(id.to_string(), state.clone());
+                    }
+                }
+                merged
+            }
+            (SyncMode::Merge | SyncMode::Resolve(_), Some(remote)) => {
+                let base_config = base.map(|b| b.config).unwrap_or_default();
+                let MergeResult {
+                    mut merged,
+                    conflicts,
+                } = three_way_merge(&base_config, &local, remote);
+                let choices = match &mode {
+                    SyncMode::Resolve(choices) => choices.clone(),
+                    _ => HashMap::new(),
+                };
+                let mut unresolved = Vec::new();
+                for conflict in conflicts {
+                    match choices.get(&conflict.component_id) {
+                        Some(SyncSide::Local) => {}
+                        Some(SyncSide::Remote) => {
+                            merged
+                                .components
+                                .insert(conflict.component_id.clone(), conflict.remote.clone());
+                        }
+                        None => unresolved.push(conflict),
+                    }
+                }
+                if !unresolved.is_empty() {
+                    info!("配置同步存在 {} 个冲突组件，等待裁决", unresolved.len());
+                    *self.conflicts.write() = unresolved.clone();
+                    return Ok(SyncReport {
+                        status: SyncStatus::Conflicts,
+                        revision: remote_revision,
+                        pulled: Vec::new(),
+                        pushed: false,
+                        conflicts: unresolved,
+                    });
+                }
+                merged
+            }
+        };
+
+        // 仅应用与本机不同的已注册组件（本机缺失的组件即未注册，由上传内容原样保留）
+        let mut changed: Vec<String> = merged
+            .components
+            .iter()
+            .filter(|(id, state)| local.components.get(*id).is_some_and(|l| l != *state))
+            .map(|(id, _)| id.clone())
+            .collect();
+        changed.sort();
+        let pulled = if changed.is_empty() {
+            Vec::new()
+        } else {
+            cm.apply_synced_config(&merged, &changed).await?
+        };
+
+        // 上传内容：应用后的本机配置 ∪ 合并结果中本机未注册的组件
+        let mut outgoing = cm.build_persistent_config();
+        for (id, state) in merged.components {
+            outgoing.components.entry(id).or_insert(state);
+        }
+
+        let (revision, pushed) = match meta {
+            Some(meta) if meta_matches && remote.as_ref() == Some(&outgoing) => (meta, false),
+            _ => {
+                let next = remote_revision.max(base_revision) + 1;
+                (push_config(storage, &outgoing, next).await?, true)
+            }
+        };
+
+        self.save_base(&SyncBase {
+            remote: revision.clone(),
+            config: outgoing,
+        })?;
+        self.conflicts.write().clear();
+
+        let status = if pulled.is_empty() && !pushed {
+            SyncStatus::UpToDate
+        } else {
+            SyncStatus::Synced
+        };
+        debug!(
+            "配置同步完成: {:?}, 修订 {}, 拉取 {:?}, 推送 {}",
+            status, revision.revision, pulled, pushed
+        );
+        Ok(SyncReport {
+            status,
+            revision: revision.revision,
+            pulled,
+            pushed,
+            conflicts: Vec::new(),
+        })
+    }
+}
+
+/// 下载远端修订元数据；不存在或无法解析时返回 None（按修订未知处理）
+async fn fetch_revision(
+    storage: &dyn StorageService,
+) -> Result<Option<RemoteRevision>, ConfigError> {
+    let Some(bytes) = storage
+        .download(REMOTE_META_FILE)
+        .await
+        .map_err(storage_err)?
+    else {
+        return Ok(None);
+    };
+    match serde_json::from_slice(&bytes) {
+        Ok(meta) => Ok(Some(meta)),
+        Err(e) => {
+            warn!("远端修订元数据解析失败，忽略: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+/// 下载远端配置及其内容哈希；不存在时返回 None
+async fn fetch_config(
+    storage: &dyn StorageService,
+) -> Result<Option<(PersistentConfig, String)>, ConfigError> {
+    let Some(bytes) = storage
+        .download(REMOTE_CONFIG_FILE)
+        .await
+        .map_err(storage_err)?
+    else {
+        return Ok(None);
+    };
+    Ok(Some((serde_json::from_slice(&bytes)?, sha256_hex(&bytes))))
+}
+
+/// 上传配置与新修订元数据（先配置后元数据，读到新元数据的设备必然能读到对应配置）
+async fn push_config(
+    storage: &dyn StorageService,
+    config: &PersistentConfig,
+    revision: u64,
+) -> Result<RemoteRevision, ConfigError> {
+    let bytes = serde_json::to_vec(config)?;
+    let meta = RemoteRevision {
+        revision,
+        content_hash: sha256_hex(&bytes),
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    storage
+        .upload(REMOTE_CONFIG_FILE, &bytes)
+        .await
+        .map_err(storage_err)?;
+    storage
+        .upload(REMOTE_META_FILE, &serde_json::to_vec(&meta)?)
+        .await
+        .map_err(storage_err)?;
+    info!("配置已推送到远端，修订 {}", revision);
+    Ok(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Arc;
+    use zerolaunch_plugin_api::config::{
+        ComponentCore, ComponentType, Configurable, SettingDefinition,
+    };
+    use zerolaunch_plugin_api::services::storage::local_storage::LocalStorageService;
+
+    /// 测试用可配置组件：settings 原样存取，校验恒通过（仅本测试模块模拟设备配置）。
+    struct MemoryComponent {
+        /// 组件 ID 等元数据
+        core: ComponentCore,
+        /// 原样保存的配置值
+        settings: RwLock<serde_json::Value>,
+    }
+
+    #[async_trait]
+    impl Configurable for MemoryComponent {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            vec![]
+        }
+        fn get_settings(&self) -> serde_json::Value {
+            self.settings.read().clone()
+        }
+        async fn apply_settings(&self, settings: serde_json::Value) -> Result<(), ConfigError> {
+            *self.settings.write() = settings;
+            Ok(())
+        }
+        async fn validate_settings(
+            &self,
+            _settings: &serde_json::Value,
+        ) -> Result<(), ConfigError> {
+            Ok(())
+        }
+    }
+
+    /// 构造一台「设备」：独立配置目录的 ConfigManager，注册给定组件。
+    async fn device(ids: &[&str]) -> (ConfigManager, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let cm = ConfigManager::new(dir.path().to_path_buf());
+        for id in ids {
+            cm.register(Arc::new(MemoryComponent {
+                core: ComponentCore::new(
+                    id.to_string(),
+                    id.to_string(),
+                    String::new(),
+                    ComponentType::Core,
+                    0,
+                ),
+                settings: RwLock::new(json!({})),
+            }))
+            .await;
+        }
+        (cm, dir)
+    }
+
+    fn state(value: i64) -> ComponentPersistentState {
+        ComponentPersistentState {
+            enabled: true,
+            settings: json!({ "v": value }),
+        }
+    }
+
+    fn config(entries: &[(&str, i64)]) -> PersistentConfig {
+        PersistentConfig {
+            version: "3".to_string(),
+            components: entries
+                .iter()
+                .map(|(id, v)| (id.to_string(), state(*v)))
+                .collect(),
+        }
+    }
+
+    async fn remote_config(storage: &dyn StorageService) -> PersistentConfig {
+        fetch_config(storage)
+            .await
+            .expect("下载远端配置失败")
+            .expect("远端配置应存在")
+            .0
+    }
+
+    /// 单侧改动取改动侧，两侧改成不同值为冲突（暂取本机值），缺失组件取另一侧。
+    #[test]
+    fn merge_takes_one_sided_changes_and_flags_conflicts() {
+        let base = config(&[("a", 1), ("b", 1), ("c", 1), ("same", 1)]);
+        let local = config(&[("a", 2), ("b", 1), ("c", 3), ("same", 5), ("mine", 1)]);
+        let remote = config(&[("a", 1), ("b", 2), ("c", 4), ("same", 5), ("theirs", 1)]);
+
+        let result = three_way_merge(&base, &local, &remote);
+        let merged = &result.merged.components;
+        assert_eq!(merged["a"], state(2), "仅本机改动取本机");
+        assert_eq!(merged["b"], state(2), "仅远端改动取远端");
+        assert_eq!(merged["c"], state(3), "冲突暂取本机值");
+        assert_eq!(merged["same"], state(5), "两侧改成相同值不冲突");
+        assert!(merged.contains_key("mine") && merged.contains_key("theirs"));
+
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.component_id, "c");
+        assert_eq!(conflict.base, Some(state(1)));
+        assert_eq!(conflict.remote, state(4));
+    }
+
+    /// 无基线（首次同步）时取值不同即冲突；仅本机组件始终取本机值且不冲突。
+    #[test]
+    fn merge_without_base_conflicts_except_local_only() {
+        let local = config(&[("a", 1), ("storage-config", 1)]);
+        let remote = config(&[("a", 2), ("storage-config", 2)]);
+
+        let result = three_way_merge(&PersistentConfig::default(), &local, &remote);
+        let ids: Vec<&str> = result
+            .conflicts
+            .iter()
+            .map(|c| c.component_id.as_str())
+            .collect();
+        assert_eq!(ids, ["a"]);
+        assert_eq!(result.merged.components["storage-config"], state(1));
+    }
+
+    /// 两台设备经同一本地存储往返：推送、拉取、远端独有组件保留、重复同步为空操作。
+    #[tokio::test]
+    async fn sync_round_trips_between_devices() {
+        let remote_dir = tempfile::tempdir().expect("创建临时目录失败");
+        let storage = LocalStorageService::new(remote_dir.path());
+        let (a, _a_dir) = device(&["alpha", "beta"]).await;
+        let (b, _b_dir) = device(&["alpha"]).await;
+
+        a.apply_settings("alpha", json!({ "v": 1 })).await.unwrap();
+        a.apply_settings("beta", json!({ "v": 1 })).await.unwrap();
+        let report = a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!(
+            (report.status, report.revision, report.pushed),
+            (SyncStatus::Synced, 1, true)
+        );
+
+        // B 首次同步无基线，默认值与远端不同 → 冲突；显式拉取远端覆盖
+        let report = b.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!(report.status, SyncStatus::Conflicts);
+        assert_eq!(b.sync_conflicts().len(), 1);
+        let report = b.sync_config(&storage, SyncMode::PullRemote).await.unwrap();
+        assert_eq!(report.pulled, ["alpha"]);
+        assert_eq!(b.get_settings("alpha"), Some(json!({ "v": 1 })));
+        assert!(b.sync_conflicts().is_empty(), "同步成功后清空暂存冲突");
+
+        // B 修改后推送：未注册的 beta 在远端保留
+        b.apply_settings("alpha", json!({ "v": 2 })).await.unwrap();
+        let report = b.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!((report.revision, report.pushed), (2, true));
+        let remote = remote_config(&storage).await;
+        assert_eq!(remote.components["alpha"].settings, json!({ "v": 2 }));
+        assert!(
+            remote.components.contains_key("beta"),
+            "远端独有组件不得被删除"
+        );
+
+        // A 拉取 B 的修改，不再推送
+        let report = a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!(report.pulled, ["alpha"]);
+        assert!(!report.pushed);
+        assert_eq!(a.get_settings("alpha"), Some(json!({ "v": 2 })));
+
+        // 再次同步为空操作
+        let report = a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!((report.status, report.revision), (SyncStatus::UpToDate, 2));
+    }
+
+    /// 两侧改成不同值：冲突暂存、不应用不上传；按组件裁决后应用并推送。
+    #[tokio::test]
+    async fn conflicts_are_held_until_resolved() {
+        let remote_dir = tempfile::tempdir().expect("创建临时目录失败");
+        let storage = LocalStorageService::new(remote_dir.path());
+        let (a, _a_dir) = device(&["alpha", "beta"]).await;
+        let (b, _b_dir) = device(&["alpha", "beta"]).await;
+        a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        b.sync_config(&storage, SyncMode::Merge).await.unwrap();
+
+        a.apply_settings("alpha", json!({ "v": 3 })).await.unwrap();
+        a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        b.apply_settings("alpha", json!({ "v": 4 })).await.unwrap();
+        b.apply_settings("beta", json!({ "v": 4 })).await.unwrap();
+
+        let report = b.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!(report.status, SyncStatus::Conflicts);
+        assert_eq!(report.conflicts[0].component_id, "alpha");
+        assert_eq!(
+            b.get_settings("alpha"),
+            Some(json!({ "v": 4 })),
+            "冲突时不应用"
+        );
+        let remote = remote_config(&storage).await;
+        assert_eq!(
+            remote.components["beta"].settings,
+            json!({}),
+            "冲突时不上传"
+        );
+
+        // 未覆盖全部冲突的裁决继续暂存
+        let report = b
+            .sync_config(&storage, SyncMode::Resolve(HashMap::new()))
+            .await
+            .unwrap();
+        assert_eq!(report.status, SyncStatus::Conflicts);
+
+        let choices = HashMap::from([("alpha".to_string(), SyncSide::Remote)]);
+        let report = b
+            .sync_config(&storage, SyncMode::Resolve(choices))
+            .await
+            .unwrap();
+        assert_eq!(report.status, SyncStatus::Synced);
+        assert_eq!(report.pulled, ["alpha"]);
+        assert!(report.pushed, "本机对 beta 的修改应推送");
+        assert_eq!(b.get_settings("alpha"), Some(json!({ "v": 3 })));
+        let remote = remote_config(&storage).await;
+        assert_eq!(remote.components["beta"].settings, json!({ "v": 4 }));
+        assert!(b.sync_conflicts().is_empty());
+    }
+
+    /// 推送本地：以本机覆盖远端冲突组件并提升修订号。
+    #[tokio::test]
+    async fn push_local_overrides_remote() {
+        let remote_dir = tempfile::tempdir().expect("创建临时目录失败");
+        let storage = LocalStorageService::new(remote_dir.path());
+        let (a, _a_dir) = device(&["alpha"]).await;
+        let (b, _b_dir) = device(&["alpha"]).await;
+        a.apply_settings("alpha", json!({ "v": 1 })).await.unwrap();
+        a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        b.apply_settings("alpha", json!({ "v": 2 })).await.unwrap();
+
+        let report = b.sync_config(&storage, SyncMode::PushLocal).await.unwrap();
+        assert_eq!((report.revision, report.pushed), (2, true));
+        assert!(report.pulled.is_empty());
+        let remote = remote_config(&storage).await;
+        assert_eq!(remote.components["alpha"].settings, json!({ "v": 2 }));
+
+        let meta = fetch_revision(&storage)
+            .await
+            .unwrap()
+            .expect("应写入修订元数据");
+        let bytes = storage.download(REMOTE_CONFIG_FILE).await.unwrap().unwrap();
+        assert_eq!(meta.content_hash, sha256_hex(&bytes));
+    }
+
+    /// 远端配置被绕过引擎改写（元数据未更新）：哈希不符视为远端改动拉取，并重新推送修订。
+    #[tokio::test]
+    async fn external_remote_edit_is_detected_by_content_hash() {
+        let remote_dir = tempfile::tempdir().expect("创建临时目录失败");
+        let storage = LocalStorageService::new(remote_dir.path());
+        let (a, _a_dir) = device(&["alpha"]).await;
+        a.apply_settings("alpha", json!({ "v": 1 })).await.unwrap();
+        a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+
+        let edited = serde_json::to_vec_pretty(&config(&[("alpha", 5)])).unwrap();
+        storage.upload(REMOTE_CONFIG_FILE, &edited).await.unwrap();
+
+        let report = a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!(report.pulled, ["alpha"], "外部改动应按远端改动拉取");
+        assert_eq!(a.get_settings("alpha"), Some(json!({ "v": 5 })));
+        assert_eq!(
+            (report.revision, report.pushed),
+            (2, true),
+            "应重新推送修订"
+        );
+        let meta = fetch_revision(&storage).await.unwrap().unwrap();
+        let bytes = storage.download(REMOTE_CONFIG_FILE).await.unwrap().unwrap();
+        assert_eq!(meta.content_hash, sha256_hex(&bytes));
+
+        let report = a.sync_config(&storage, SyncMode::Merge).await.unwrap();
+        assert_eq!((report.status, report.revision), (SyncStatus::UpToDate, 2));
+    }
+
+    /// WebDAV 后端端到端：两台设备经 bun 测试服务器（crates/plugin-api/tests/fixtures）
+    /// 推送并拉取配置。整体 30 秒超时，服务器经 shutdown 端点退出（兜底 kill）。
+    #[tokio::test]
+    async fn sync_round_trips_over_webdav() {
+        use std::time::Duration;
+        use zerolaunch_plugin_api::mock::webdav_server::WebDavTestServer;
+        use zerolaunch_plugin_api::services::storage::webdav_storage::WebDAVStorageService;
+
+        tokio::time::timeout(Duration::from_secs(30), async {
+            let server = WebDavTestServer::start().await;
+            let storage = WebDAVStorageService::new(&server.config("/config-sync/"));
+            let (a, _a_dir) = device(&["alpha"]).await;
+            let (b, _b_dir) = device(&["alpha"]).await;
+
+            a.apply_settings("alpha", json!({ "v": 7 })).await.unwrap();
+            let report = a.sync_config(&storage, SyncMode::PushLocal).await.unwrap();
+            assert!(report.pushed);
+            let report = b.sync_config(&storage, SyncMode::PullRemote).await.unwrap();
+            assert_eq!(report.pulled, ["alpha"]);
+            assert_eq!(b.get_settings("alpha"), Some(json!({ "v": 7 })));
+            let report = b.sync_config(&storage, SyncMode::Merge).await.unwrap();
+            assert_eq!(report.status, SyncStatus::UpToDate);
+
+            storage.delete(REMOTE_CONFIG_FILE).await.expect("清理失败");
+            storage.delete(REMOTE_META_FILE).await.expect("清理失败");
+            server.shutdown().await;
+        })
+        .await
+        .expect("WebDAV 同步断言超时（30 秒）");
+    }
+}
@@ -0,0 +1,292 @@
+//! 宿主可观测事件总线。
+//!
+//! 会话投影经 `SessionStateEmitter` 只推送给 Tauri 前端，`ConfigEvent` / `PluginRuntimeEvent`
+//! 是模块间的内部管道；本总线把其中对外有意义的部分归一为 `HostEvent`，
+//! 供 CLI `/v1/events`（SSE）等外部观察者订阅。
+//!
+//! 每条事件分配单调递增的 `seq` 并保留最近若干条历史：订阅者先订阅、再读取历史，
+//! 按 `seq` 去重即可无缝衔接「历史回放 + 实时推送」。无订阅者时实时事件直接丢弃。
+
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use tokio::sync::broadcast;
+use zerolaunch_plugin_api::CandidateId;
+
+use crate::core::config::ConfigEvent;
+
+/// 保留的历史事件条数。
+const HISTORY_CAPACITY: usize = 200;
+
+/// 配置组件的变更类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ConfigChange {
+    /// 设置值变更
+    #[serde(rename = "settings")]
+    Settings,
+    /// 启用状态变更
+    #[serde(rename = "enabled")]
+    Enabled,
+    /// 组件注册
+    #[serde(rename = "registered")]
+    Registered,
+    /// 组件注销
+    #[serde(rename = "unregistered")]
+    Unregistered,
+}
+
+/// 第三方插件进程的生命周期变更类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PluginChange {
+    /// 进程异常退出（组件已解注册，随后按 manifest 决定是否重启）
+    #[serde(rename = "crashed")]
+    Crashed,
+    /// 崩溃后重启成功，组件已重新注册
+    #[serde(rename = "restarted")]
+    Restarted,
+}
+
+/// 对外可观测的宿主事件；序列化为带 `type` 标签的扁平 JSON 对象。
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum HostEvent {
+    /// 会话投影变化（归属或展示形态改变、代际递增时）
+    #[serde(rename = "session")]
+    Session {
+        /// 新的会话代际
+        #[serde(rename = "generation")]
+        generation: u64,
+        /// 会话归属：None = 宿主默认搜索
+        #[serde(rename = "pluginId")]
+        plugin_id: Option<String>,
+        /// 展示形态（与 `/v1/session/mode` 取值一致）
+        #[serde(rename = "mode")]
+        mode: String,
+    },
+    /// 一次查询路由完成
+    #[serde(rename = "query")]
+    Query {
+        /// 查询 trace_id
+        #[serde(rename = "traceId")]
+        trace_id: String,
+        /// 查询通道：`ui` / `cli`
+        #[serde(rename = "channel")]
+        channel: String,
+        /// 处理查询的插件：None = 宿主默认搜索
+        #[serde(rename = "pluginId")]
+        plugin_id: Option<String>,
+        /// 路由耗时（毫秒）
+        #[serde(rename = "elapsedMs")]
+        elapsed_ms: f64,
+        /// 响应形态：`list` / `customPanel` / `inlineParam` / `empty` / `error`
+        #[serde(rename = "response")]
+        response: String,
+        /// 列表结果条数（非列表响应为 0）
+        #[serde(rename = "resultCount")]
+        result_count: usize,
+    },
+    /// 一次候选项/插件动作执行
+    #[serde(rename = "execution")]
+    Execution {
+        /// 确认请求的 trace_id
+        #[serde(rename = "traceId")]
+        trace_id: String,
+        /// 执行方插件：None = 宿主执行器
+        #[serde(rename = "pluginId")]
+        plugin_id: Option<String>,
+        /// 候选项 ID（插件面板动作没有候选项）
+        #[serde(rename = "candidateId")]
+        candidate_id: Option<CandidateId>,
+        /// 动作 ID
+        #[serde(rename = "actionId")]
+        action_id: String,
+        /// 是否执行成功
+        #[serde(rename = "success")]
+        success: bool,
+        /// 失败原因
+        #[serde(rename = "error")]
+        error: Option<String>,
+    },
+    /// 配置组件变更
+    #[serde(rename = "config")]
+    Config {
+        /// 组件 ID
+        #[serde(rename = "componentId")]
+        component_id: String,
+        /// 变更类别
+        #[serde(rename = "change")]
+        change: ConfigChange,
+        /// 启用状态（仅 `enabled` 变更携带）
+        #[serde(rename = "enabled")]
+        enabled: Option<bool>,
+    },
+    /// 第三方插件进程崩溃/重启
+    #[serde(rename = "plugin")]
+    Plugin {
+        /// 插件 ID
+        #[serde(rename = "pluginId")]
+        plugin_id: String,
+        /// 变更类别
+        #[serde(rename = "change")]
+        change: PluginChange,
+    },
+}
+
+impl HostEvent {
+    /// 全部事件类型（`type` 标签取值），供订阅方校验过滤条件。
+    pub const KINDS: [&'static str; 5] = ["session", "query", "execution", "config", "plugin"];
+
+    /// 事件类型（与序列化的 `type` 标签一致）。
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HostEvent::Session { .. } => "session",
+            HostEvent::Query { .. } => "query",
+            HostEvent::Execution { .. } => "execution",
+            HostEvent::Config { .. } => "config",
+            HostEvent::Plugin { .. } => "plugin",
+        }
+    }
+
+    /// 由配置事件转换：只保留组件级变更，插件注册包事件（内部管道）不对外暴露。
+    pub fn from_config_event(event: &ConfigEvent) -> Option<Self> {
+        let (component_id, change, enabled) = match event {
+            ConfigEvent::SettingsChanged { component_id, .. } => {
+                (component_id, ConfigChange::Settings, None)
+            }
+            ConfigEvent::EnabledChanged {
+                component_id,
+                enabled,
+                ..
+            } => (component_id, ConfigChange::Enabled, Some(*enabled)),
+            ConfigEvent::Registered { component_id, .. } => {
+                (component_id, ConfigChange::Registered, None)
+            }
+            ConfigEvent::Unregistered { component_id } => {
+                (component_id, ConfigChange::Unregistered, None)
+            }
+            ConfigEvent::PluginRegistered(_) | ConfigEvent::PluginUnregistered(_) => return None,
+        };
+        Some(HostEvent::Config {
+            component_id: component_id.clone(),
+            change,
+            enabled,
+        })
+    }
+}
+
+/// 总线发布的事件记录：序号 + 时间戳 + 事件本体（扁平展开）。
+#[derive(Debug, Clone, Serialize)]
+pub struct HostEventRecord {
+    /// 单调递增序号（从 1 开始），用于历史与实时事件去重衔接
+    #[serde(rename = "seq")]
+    pub seq: u64,
+    /// 发布时间（RFC 3339）
+    #[serde(rename = "timestamp")]
+    pub timestamp: String,
+    /// 事件本体
+    #[serde(flatten)]
+    pub event: HostEvent,
+}
+
+/// 宿主事件总线：广播实时事件并保留最近历史。
+pub struct HostEventBus {
+    /// 实时广播发送端
+    sender: broadcast::Sender<HostEventRecord>,
+    /// 最近事件历史（容量 HISTORY_CAPACITY）；序号分配与广播在同一把锁内完成，保证顺序一致
+    history: Mutex<VecDeque<HostEventRecord>>,
+}
+
+impl HostEventBus {
+    /// 创建事件总线，`capacity` 为每个订阅者可积压的实时事件数。
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _receiver) = broadcast::channel(capacity);
+        Self {
+            sender,
+            history: Mutex::new(VecDeque::with_capacity(HISTORY_CAPACITY)),
+        }
+    }
+
+    /// 发布一条事件：写入历史并广播给当前订阅者。
+    pub fn publish(&self, event: HostEvent) {
+        let mut history = self.history.lock();
+        let seq = history.back().map_or(1, |last| last.seq + 1);
+        let record = HostEventRecord {
+            seq,
+            timestamp: Utc::now().to_rfc3339(),
+            event,
+        };
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(record.clone());
+        // 无订阅者时发送失败，属正常情况
+        let _ = self.sender.send(record);
+    }
+
+    /// 订阅实时事件。
+    pub fn subscribe(&self) -> broadcast::Receiver<HostEventRecord> {
+        self.sender.subscribe()
+    }
+
+    /// 最近 `limit` 条历史事件（按序号升序）。
+    pub fn recent(&self, limit: usize) -> Vec<HostEventRecord> {
+        let history = self.history.lock();
+        let skip = history.len().saturating_sub(limit);
+        history.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_event(plugin_id: &str) -> HostEvent {
+        HostEvent::Plugin {
+            plugin_id: plugin_id.to_string(),
+            change: PluginChange::Crashed,
+        }
+    }
+
+    /// 序号连续递增，历史按容量滚动，订阅者收到与历史一致的记录。
+    #[test]
+    fn publish_assigns_seq_and_keeps_bounded_history() {
+        let bus = HostEventBus::new(16);
+        let mut rx = bus.subscribe();
+        for i in 0..HISTORY_CAPACITY + 5 {
+            bus.publish(plugin_event(&format!("p{}", i)));
+        }
+        let recent = bus.recent(usize::MAX);
+        assert_eq!(recent.len(), HISTORY_CAPACITY);
+        assert_eq!(recent.first().unwrap().seq, 6);
+        assert_eq!(recent.last().unwrap().seq, (HISTORY_CAPACITY + 5) as u64);
+        assert_eq!(bus.recent(2).len(), 2);
+
+        // 订阅者积压超过容量时报告 Lagged，之后继续收到最新事件
+        assert!(matches!(
+            rx.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(_))
+        ));
+        assert!(rx.try_recv().is_ok());
+    }
+
+    /// 记录序列化为扁平对象：`type` 标签与事件字段和 seq/timestamp 同级。
+    #[test]
+    fn record_serializes_flat_with_type_tag() {
+        let bus = HostEventBus::new(4);
+        bus.publish(HostEvent::Config {
+            component_id: "general-config".to_string(),
+            change: ConfigChange::Enabled,
+            enabled: Some(false),
+        });
+        let record = bus.recent(1).pop().unwrap();
+        assert_eq!(record.event.kind(), "config");
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["seq"], 1);
+        assert_eq!(json["type"], "config");
+        assert_eq!(json["componentId"], "general-config");
+        assert_eq!(json["change"], "enabled");
+        assert_eq!(json["enabled"], false);
+        assert!(json["timestamp"].is_string());
+    }
+}
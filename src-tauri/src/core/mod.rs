@@ -3,4 +3,5 @@ pub mod bias_rule;
 pub mod cli_token;
 pub mod config;
 pub mod constants;
+pub mod host_event;
 pub mod i18n;
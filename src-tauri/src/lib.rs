@@ -54,9 +54,14 @@ pub async fn do_cleanup_before_exit(state: Arc<AppState>) {
     if let Err(e) = config_manager.save_to_storage() {
         warn!("退出前配置保存失败: {}", e);
     }
+    // 退出前持久化分数增强器的学习数据
+    state
+        .get_session_dispatcher()
+        .components()
+        .persist_score_boosters();
     // 退出前同步到远程存储
     let host_api = state.get_host_api();
-    crate::bootstrap::sync_config_to_remote(&config_manager, &host_api).await;
+    crate::bootstrap::sync_config_with_remote(&config_manager, &host_api).await;
     // 注销全局快捷键和双击 Ctrl 监听器
     if let Err(e) = host_api.unregister_all_hotkeys().await {
         warn!("退出前注销快捷键失败: {:?}", e);
@@ -216,9 +221,14 @@ pub fn run() {
             crate::commands::plugin::plugin_list,
             crate::commands::plugin::plugin_get_manifest,
             crate::commands::plugin::plugin_get_detail,
+            crate::commands::plugin::plugin_inspect_local,
             crate::commands::plugin::plugin_install_local,
             crate::commands::plugin::plugin_reload,
             crate::commands::plugin::plugin_uninstall,
+            crate::commands::plugin::plugin_registry_list,
+            crate::commands::plugin::plugin_check_updates,
+            crate::commands::plugin::plugin_install_from_registry,
+            crate::commands::plugin::plugin_upgrade,
             crate::commands::plugin::plugin_set_enabled,
             crate::commands::plugin::plugin_get_logs,
             crate::commands::cli::cli_get_info,
@@ -26,7 +26,8 @@ pub type ExecutorFactory =
 pub type DataSourceFactory = fn(&InventoryContext) -> (Arc<dyn Configurable>, Arc<dyn DataSource>);
 pub type KeywordOptimizerFactory = fn() -> (Arc<dyn Configurable>, Arc<dyn KeywordOptimizer>);
 pub type SearchEngineFactory = fn() -> (Arc<dyn Configurable>, Arc<dyn SearchEngine>);
-pub type ScoreBoosterFactory = fn() -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>);
+pub type ScoreBoosterFactory =
+    fn(&InventoryContext) -> (Arc<dyn Configurable>, Arc<dyn ScoreBooster>);
 pub type KeywordInjectorFactory =
     fn(&InventoryContext) -> (Arc<dyn Configurable>, Arc<dyn KeywordInjector>);
 pub type PluginFactory = fn() -> (Arc<dyn Configurable>, Arc<dyn Plugin>);
@@ -263,7 +264,7 @@ pub fn collect_all_builtin_entries(ctx: &InventoryContext) -> CollectedBuiltins
     let mut boo_entries: Vec<&ScoreBoosterEntry> =
         ::inventory::iter::<ScoreBoosterEntry>().collect();
     boo_entries.sort_by_key(|e| e.priority);
-    let score_boosters: Vec<_> = boo_entries.iter().map(|e| (e.factory)()).collect();
+    let score_boosters: Vec<_> = boo_entries.iter().map(|e| (e.factory)(ctx)).collect();
 
     // -- Plugins --
     let mut plug_entries: Vec<&PluginEntry> = ::inventory::iter::<PluginEntry>().collect();
@@ -0,0 +1,286 @@
+//! 候选项索引快照 —— 冷启动时直接提供上次完成的候选项缓存。
+//!
+//! 每次候选项刷新完成后，将合并后的 `CachedCandidateData`（含优化后的关键字与偏置）
+//! 序列化为带 schema 版本的 JSON 快照，写入 `AppDataDir/candidate_index.json`。
+//! 启动时先加载快照立即提供搜索，再由后台刷新与数据源对账。
+//! 损坏的快照经共享的 `backup_corrupted` 备份为 `.json.bak` 后忽略。
+
+use crate::core::config::store::{backup_corrupted, write_atomic};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+use zerolaunch_plugin_api::{CachedCandidateData, CandidateId, SearchCandidate};
+
+/// 索引快照的 schema 版本：SearchCandidate 结构或关键字语义变化时递增，
+/// 旧版本快照在加载时被忽略（下一次刷新后覆盖）。
+const INDEX_SNAPSHOT_VERSION: u32 = 2;
+
+/// 候选项索引快照的磁盘格式。
+/// 仅由 `CandidateIndexStore` 读写，不跨 IPC。
+#[derive(Debug, Serialize, Deserialize)]
+struct CandidateIndexSnapshot {
+    /// schema 版本（见 INDEX_SNAPSHOT_VERSION）。
+    #[serde(rename = "version", default)]
+    version: u32,
+    /// 保存时间（Unix 秒），仅用于日志观察。
+    #[serde(rename = "savedAt", default)]
+    saved_at: u64,
+    /// 保存时缓存的下一个候选 ID（恢复后继续分配，保证与已有 ID 不冲突）。
+    #[serde(rename = "nextCandidateId", default)]
+    next_candidate_id: CandidateId,
+    /// 合并完成的候选项（关键字与偏置均已注入）。
+    #[serde(rename = "candidates", default)]
+    candidates: Vec<SearchCandidate>,
+}
+
+/// 候选项索引快照持久化层。
+/// 负责快照文件的读写、版本校验与损坏备份，由 SessionDispatcher 在刷新完成后调用。
+pub struct CandidateIndexStore {
+    /// 快照文件所在目录（AppDataDir）
+    data_dir: PathBuf,
+    /// 已落盘快照的代际；写入期间持有，保证保存逐个进行且旧代际不覆盖新代际
+    saved_generation: Mutex<u64>,
+}
+
+impl CandidateIndexStore {
+    /// 创建 CandidateIndexStore，指定快照所在目录
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            data_dir,
+            saved_generation: Mutex::new(0),
+        }
+    }
+
+    /// 获取快照文件路径
+    fn index_file_path(&self) -> PathBuf {
+        self.data_dir.join("candidate_index.json")
+    }
+
+    /// 加载索引快照。
+    /// 文件不存在、为空或版本不符时返回 None；解析失败时备份损坏文件后返回 None。
+    pub fn load(&self) -> Option<CachedCandidateData> {
+        let path = self.index_file_path();
+        if !path.exists() {
+            debug!("索引快照不存在: {:?}", path);
+            return None;
+        }
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("索引快照读取失败: {:?}, 错误: {}", path, e);
+                return None;
+            }
+        };
+        if content.trim().is_empty() {
+            debug!("索引快照为空: {:?}", path);
+            return None;
+        }
+
+        let snapshot: CandidateIndexSnapshot = match serde_json::from_str(&content) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("索引快照解析失败: {:?}, 错误: {}", path, e);
+                if let Err(e) = backup_corrupted(&path) {
+                    warn!("备份损坏的索引快照失败: {}", e);
+                }
+                return None;
+            }
+        };
+        if snapshot.version != INDEX_SNAPSHOT_VERSION {
+            info!(
+                "索引快照版本不符（快照 {}，当前 {}），忽略并等待刷新覆盖",
+                snapshot.version, INDEX_SNAPSHOT_VERSION
+            );
+            return None;
+        }
+
+        let mut data = CachedCandidateData::with_next_candidate_id(snapshot.next_candidate_id);
+        for candidate in snapshot.candidates {
+            let id = candidate.id;
+            data.add_candidate_with_id(candidate, id);
+        }
+        debug!(
+            "已加载索引快照: {:?}（{} 个候选项，保存于 {}）",
+            path,
+            data.get_candidates().len(),
+            snapshot.saved_at
+        );
+        Some(data)
+    }
+
+    /// 将候选项缓存序列化为快照内容（调用方持有缓存读锁期间调用，写盘可移出锁外）。
+    pub fn serialize(data: &CachedCandidateData) -> serde_json::Result<String> {
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        serde_json::to_string(&CandidateIndexSnapshot {
+            version: INDEX_SNAPSHOT_VERSION,
+            saved_at,
+            next_candidate_id: data.next_candidate_id(),
+            candidates: data.get_candidates().clone(),
+        })
+    }
+
+    /// 将序列化好的快照内容写入文件。
+    /// 参数：generation - 快照代际（序列化时分配，越大越新）。
+    /// 返回：是否写入；已保存过更新的代际时跳过，避免接连完成的刷新以旧快照覆盖新快照。
+    ///
+    /// 经 `write_atomic` 原子写入：避免写入过程中崩溃导致快照截断（截断的快照会在下次
+    /// 启动时被当作损坏备份）。
+    pub fn save(&self, generation: u64, content: &str) -> std::io::Result<bool> {
+        let mut saved = self.saved_generation.lock();
+        if *saved >= generation {
+            debug!(
+                "跳过过期的索引快照（代际 {}，已保存 {}）",
+                generation, *saved
+            );
+            return Ok(false);
+        }
+        let path = self.index_file_path();
+        write_atomic(&path, content.as_bytes())?;
+        *saved = generation;
+
+        debug!("索引快照已保存到: {:?}（代际 {}）", path, generation);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerolaunch_plugin_api::services::IconRequest;
+    use zerolaunch_plugin_api::ExecutionTarget;
+
+    /// 构造带关键字与偏置的测试候选项。
+    fn candidate(name: &str, path: &str) -> SearchCandidate {
+        SearchCandidate {
+            id: 0,
+            name: name.to_string(),
+            icon: IconRequest::Path(path.to_string()),
+            target: ExecutionTarget::Path(path.to_string()),
+            keywords: vec![name.to_lowercase()],
+            bias: 1.5,
+            trigger_keywords: Vec::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn save_and_load_roundtrip_keeps_ids_and_keywords() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let store = CandidateIndexStore::new(dir.path().to_path_buf());
+        let mut data = CachedCandidateData::new();
+        data.add_candidate(candidate("Notepad", "/notepad"));
+        data.add_candidate(candidate("Paint", "/paint"));
+
+        let content = CandidateIndexStore::serialize(&data).expect("序列化失败");
+        assert!(store.save(1, &content).expect("保存失败"));
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "不应残留临时文件");
+
+        let loaded = store.load().expect("应能加载快照");
+        assert_eq!(loaded.get_candidates().len(), 2);
+        assert_eq!(loaded.next_candidate_id(), data.next_candidate_id());
+        let paint = loaded
+            .get_candidates()
+            .iter()
+            .find(|c| c.name == "Paint")
+            .expect("候选项应存在");
+        assert_eq!(paint.id, 2);
+        assert_eq!(paint.keywords, vec!["paint".to_string()]);
+        assert_eq!(paint.bias, 1.5);
+        assert!(loaded.get_candidate(2).is_some());
+    }
+
+    /// 并发保存互不截断：最终快照完整可加载，且不残留临时文件。
+    #[test]
+    fn concurrent_saves_leave_a_complete_snapshot() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let store = std::sync::Arc::new(CandidateIndexStore::new(dir.path().to_path_buf()));
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    let mut data = CachedCandidateData::new();
+                    for j in 0..=i * 50 {
+                        data.add_candidate(candidate(&format!("App{j}"), &format!("/app{j}")));
+                    }
+                    let content = CandidateIndexStore::serialize(&data).expect("序列化失败");
+                    for k in 0..5 {
+                        store.save(i * 5 + k + 1, &content).expect("保存失败");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("保存线程 panic");
+        }
+
+        let loaded = store.load().expect("并发保存后快照应完整可加载");
+        assert_eq!(loaded.get_candidates().len() % 50, 1);
+        let entries: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(entries, ["candidate_index.json"]);
+    }
+
+    /// 旧代际的快照晚于新代际到达时被跳过，磁盘上保留较新的快照。
+    #[test]
+    fn stale_generation_does_not_overwrite_newer_snapshot() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let store = CandidateIndexStore::new(dir.path().to_path_buf());
+        let mut older = CachedCandidateData::new();
+        older.add_candidate(candidate("Notepad", "/notepad"));
+        let mut newer = CachedCandidateData::new();
+        newer.add_candidate(candidate("Notepad", "/notepad"));
+        newer.add_candidate(candidate("Paint", "/paint"));
+        let older = CandidateIndexStore::serialize(&older).expect("序列化失败");
+        let newer = CandidateIndexStore::serialize(&newer).expect("序列化失败");
+
+        assert!(store.save(2, &newer).expect("保存失败"));
+        assert!(!store.save(1, &older).expect("保存失败"));
+        assert!(!store.save(2, &older).expect("保存失败"));
+        assert_eq!(
+            store.load().expect("应能加载快照").get_candidates().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn corrupt_snapshot_is_backed_up_and_ignored() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let store = CandidateIndexStore::new(dir.path().to_path_buf());
+        let path = dir.path().join("candidate_index.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(store.load().is_none());
+        assert!(!path.exists());
+        assert!(dir.path().join("candidate_index.json.bak").exists());
+    }
+
+    #[test]
+    fn version_mismatch_is_ignored() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let store = CandidateIndexStore::new(dir.path().to_path_buf());
+        let path = dir.path().join("candidate_index.json");
+        std::fs::write(
+            &path,
+            r#"{"version":999,"savedAt":0,"nextCandidateId":1,"candidates":[]}"#,
+        )
+        .unwrap();
+
+        assert!(store.load().is_none());
+        // 版本不符不视为损坏：保留原文件，等待下一次刷新覆盖
+        assert!(path.exists());
+    }
+}
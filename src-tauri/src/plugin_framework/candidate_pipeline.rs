@@ -1,14 +1,62 @@
 use crate::core::bias_rule::BiasRule;
-use std::collections::HashMap;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 use zerolaunch_plugin_api::config::Configurable;
-use zerolaunch_plugin_api::{CachedCandidateData, DataSource, KeywordInjector, KeywordOptimizer};
+use zerolaunch_plugin_api::{
+    CachedCandidateData, DataSource, ExecutionTarget, KeywordInjector, KeywordOptimizer,
+    SearchCandidate,
+};
+
+/// 单个数据源的默认采集超时：超时的数据源沿用上一次快照，不阻塞其余数据源。
+const DEFAULT_SOURCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 候选项刷新范围。
+/// 由 SessionDispatcher 按触发源选择：定时/手动刷新为 All，配置变更/安装监控只刷新相关数据源。
+#[derive(Debug, Clone)]
+pub enum RefreshScope {
+    /// 全部数据源重新采集。
+    All,
+    /// 仅重新采集指定数据源（component_id）；尚无快照的数据源总会补采集。
+    Sources(Vec<String>),
+    /// 仅依赖已安装程序的数据源（`DataSource::tracks_installations`）重新采集。
+    Installations,
+    /// 不重新采集，丢弃关键字缓存后全部重算（关键字优化器/注入器配置变更）。
+    Keywords,
+}
+
+/// 单个数据源最近一次成功采集的快照。
+/// 仅在 CandidatePipeline 内部使用：未重新采集的数据源在合并时直接沿用。
+struct SourceSnapshot {
+    /// 采集代际：该数据源每次成功重新采集递增，用于日志与调试观察。
+    generation: u64,
+    /// 数据源原始候选项（未注入关键字与偏置规则）。
+    candidates: Vec<SearchCandidate>,
+}
+
+/// 候选项关键字缓存条目：同一执行目标且名称、预填关键字均未变时直接复用关键字。
+struct KeywordEntry {
+    /// 生成关键字时的候选项名称（名称变更即视为需要重算）。
+    name: String,
+    /// 生成关键字时数据源预填的关键字（变更即视为需要重算）。
+    seeds: Vec<String>,
+    /// 优化器链 + 注入器生成并去重后的关键字。
+    keywords: Vec<String>,
+}
 
 pub struct CandidatePipeline {
     data_sources: Vec<Arc<dyn DataSource>>,
     keyword_optimizers: Vec<Arc<dyn KeywordOptimizer>>,
     keyword_injectors: Vec<Arc<dyn KeywordInjector>>,
     bias_rules: HashMap<String, f64>,
+    /// 单个数据源的采集超时。
+    source_timeout: Duration,
+    /// 各数据源最近一次成功采集的快照（component_id → 快照）。
+    snapshots: Mutex<HashMap<String, SourceSnapshot>>,
+    /// 关键字缓存（执行目标 → 关键字），仅对新增或改名的候选项重算。
+    keyword_cache: Mutex<HashMap<ExecutionTarget, KeywordEntry>>,
 }
 
 impl CandidatePipeline {
@@ -18,9 +66,50 @@ impl CandidatePipeline {
             keyword_optimizers: Vec::new(),
             keyword_injectors: Vec::new(),
             bias_rules: HashMap::new(),
+            source_timeout: DEFAULT_SOURCE_TIMEOUT,
+            snapshots: Mutex::new(HashMap::new()),
+            keyword_cache: Mutex::new(HashMap::new()),
         }
     }
 
+    /// 设置单个数据源的采集超时。
+    pub fn set_source_timeout(&mut self, timeout: Duration) {
+        self.source_timeout = timeout;
+    }
+
+    /// 从旧管道继承采集快照与关键字缓存（管道重建时调用，避免全量重新索引）。
+    /// 快照仅继承新管道仍包含的数据源；关键字缓存仅在优化器与注入器组成不变时继承。
+    pub fn inherit_from(&mut self, old: &CandidatePipeline) {
+        let source_ids: HashSet<&str> =
+            self.data_sources.iter().map(|s| s.component_id()).collect();
+        let mut old_snapshots = old.snapshots.lock();
+        let snapshots = self.snapshots.get_mut();
+        for id in source_ids {
+            if let Some(snapshot) = old_snapshots.remove(id) {
+                snapshots.insert(id.to_string(), snapshot);
+            }
+        }
+        if self.keyword_components() == old.keyword_components() {
+            *self.keyword_cache.get_mut() = std::mem::take(&mut *old.keyword_cache.lock());
+        }
+    }
+
+    /// 关键字相关组件的标识（优化器 + 注入器 component_id，已排序），判断关键字缓存是否可继承。
+    fn keyword_components(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .keyword_optimizers
+            .iter()
+            .map(|op| op.component_id().to_string())
+            .chain(
+                self.keyword_injectors
+                    .iter()
+                    .map(|inj| inj.component_id().to_string()),
+            )
+            .collect();
+        ids.sort();
+        ids
+    }
+
     /// 设置固定偏移量规则列表，内部转换为 HashMap 以支持 O(1) 查找。
     /// 规则按 target 精确匹配（target 已预归一化为 lowercase）。
     pub fn set_bias_rules(&mut self, rules: Vec<BiasRule>) {
@@ -34,6 +123,7 @@ impl CandidatePipeline {
     pub fn remove_source(&mut self, component_id: &str) {
         self.data_sources
             .retain(|s| s.component_id() != component_id);
+        self.snapshots.get_mut().remove(component_id);
     }
 
     pub fn add_keyword_optimizer(&mut self, optimizer: Arc<dyn KeywordOptimizer>) {
@@ -54,14 +144,109 @@ impl CandidatePipeline {
             .retain(|inj| inj.component_id() != component_id);
     }
 
-    /// 收集候选项。
-    /// 管道中只包含已启用的组件（由 SessionRouter 在启用/禁用时动态维护），
+    /// 全量收集候选项（启动期使用）：全部数据源重新采集，候选ID从 1 开始分配。
+    /// 管道中只包含已启用的组件（由 SessionDispatcher 在启用/禁用时动态维护），
     /// 此处无需再做 enabled 过滤。
     pub async fn collect(&self) -> CachedCandidateData {
-        let mut candidates = CachedCandidateData::new();
+        self.fetch(&RefreshScope::All).await;
+        self.assemble(&CachedCandidateData::new())
+    }
+
+    /// 按刷新范围并发采集数据源并更新快照，每个数据源受 source_timeout 约束。
+    /// 超时的数据源沿用上一次快照（首次采集超时则视为无候选项）。
+    /// `RefreshScope::Keywords` 不采集，仅丢弃关键字缓存。
+    pub async fn fetch(&self, scope: &RefreshScope) {
+        let targets: Vec<Arc<dyn DataSource>> = {
+            let snapshots = self.snapshots.lock();
+            self.data_sources
+                .iter()
+                .filter(|source| {
+                    let id = source.component_id();
+                    !snapshots.contains_key(id)
+                        || match scope {
+                            RefreshScope::All => true,
+                            RefreshScope::Sources(ids) => ids.iter().any(|s| s == id),
+                            RefreshScope::Installations => source.tracks_installations(),
+                            RefreshScope::Keywords => false,
+                        }
+                })
+                .cloned()
+                .collect()
+        };
+        if matches!(scope, RefreshScope::Keywords) {
+            self.keyword_cache.lock().clear();
+        }
+        if targets.is_empty() {
+            return;
+        }
 
-        for source in &self.data_sources {
-            candidates.add_candidates(source.fetch_candidates().await);
+        let mut set = tokio::task::JoinSet::new();
+        for source in targets {
+            let timeout = self.source_timeout;
+            set.spawn(async move {
+                let id = source.component_id().to_string();
+                let start = Instant::now();
+                let result = tokio::time::timeout(timeout, source.fetch_candidates()).await;
+                (id, start.elapsed(), result)
+            });
+        }
+
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((id, elapsed, Ok(data))) => {
+                    let mut snapshots = self.snapshots.lock();
+                    let generation = snapshots.get(&id).map_or(1, |s| s.generation + 1);
+                    debug!(
+                        source = %id,
+                        generation,
+                        count = data.get_candidates().len(),
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        "数据源采集完成"
+                    );
+                    snapshots.insert(
+                        id,
+                        SourceSnapshot {
+                            generation,
+                            candidates: data.get_candidates().clone(),
+                        },
+                    );
+                }
+                Ok((id, _, Err(_))) => {
+                    warn!(
+                        source = %id,
+                        timeout_ms = self.source_timeout.as_millis() as u64,
+                        "数据源采集超时，沿用上一次采集结果"
+                    );
+                }
+                Err(e) => {
+                    warn!(error = %e, "数据源采集任务异常退出");
+                }
+            }
+        }
+    }
+
+    /// 由各数据源快照合并出新的候选项缓存。
+    /// 同一执行目标沿用 `previous` 中的候选ID，新增候选项从 previous 的下一个ID继续分配；
+    /// 关键字仅对新增或改名的候选项重算，偏置规则每次重新注入。
+    pub fn assemble(&self, previous: &CachedCandidateData) -> CachedCandidateData {
+        let mut candidates =
+            CachedCandidateData::with_next_candidate_id(previous.next_candidate_id());
+        {
+            let snapshots = self.snapshots.lock();
+            // 按管道中数据源的顺序合并，去重时先加入者优先（与全量采集一致）
+            for source in &self.data_sources {
+                let Some(snapshot) = snapshots.get(source.component_id()) else {
+                    continue;
+                };
+                for candidate in &snapshot.candidates {
+                    match previous.get_id_by_target(&candidate.target) {
+                        Some(id) => {
+                            candidates.add_candidate_with_id(candidate.clone(), id);
+                        }
+                        None => candidates.add_candidate(candidate.clone()),
+                    }
+                }
+            }
         }
 
         // 排序优化器
@@ -73,9 +258,24 @@ impl CandidatePipeline {
         let injectors: Vec<&dyn KeywordInjector> =
             self.keyword_injectors.iter().map(|a| a.as_ref()).collect();
 
+        let mut cache = self.keyword_cache.lock();
+        let mut recomputed = 0usize;
         for candidate in candidates.get_candidates_mut() {
-            // 1. 名称无关的字符串变换
+            if let Some(entry) = cache.get(&candidate.target) {
+                if entry.name == candidate.name && entry.seeds == candidate.keywords {
+                    candidate.keywords = entry.keywords.clone();
+                    continue;
+                }
+            }
+
+            // 1. 名称无关的字符串变换（名称与数据源预填的关键字分别经优化器链展开）
+            let seeds = std::mem::take(&mut candidate.keywords);
             candidate.keywords = Self::apply_keyword_optimizers(&candidate.name, &sorted);
+            for seed in &seeds {
+                candidate
+                    .keywords
+                    .extend(Self::apply_keyword_optimizers(seed, &sorted));
+            }
 
             // 2. 候选项相关的关键字注入
             for injector in &injectors {
@@ -85,7 +285,28 @@ impl CandidatePipeline {
             }
 
             candidate.keywords = Self::deduplicate_keywords(candidate.keywords.clone());
+            cache.insert(
+                candidate.target.clone(),
+                KeywordEntry {
+                    name: candidate.name.clone(),
+                    seeds,
+                    keywords: candidate.keywords.clone(),
+                },
+            );
+            recomputed += 1;
         }
+        // 清理已不存在的候选项，避免缓存随卸载的程序无限增长
+        let live: HashSet<&ExecutionTarget> = candidates
+            .get_candidates()
+            .iter()
+            .map(|c| &c.target)
+            .collect();
+        cache.retain(|target, _| live.contains(target));
+        drop(cache);
+        debug!(
+            total = candidates.get_candidates().len(),
+            recomputed, "候选项合并完成"
+        );
 
         // 3. 固定偏移量注入（在关键字注入之后、检索引擎之前）
         for candidate in candidates.get_candidates_mut() {
@@ -98,6 +319,15 @@ impl CandidatePipeline {
         candidates
     }
 
+    /// 各数据源当前的采集代际（component_id → 代际），尚未采集的数据源不在其中。
+    pub fn source_generations(&self) -> HashMap<String, u64> {
+        self.snapshots
+            .lock()
+            .iter()
+            .map(|(id, snapshot)| (id.clone(), snapshot.generation))
+            .collect()
+    }
+
     /// 对单个名称运行优化器链，返回去重后的关键字列表。
     /// 参数 `sorted` 必须已按 `get_priority()` 升序排列。
     fn apply_keyword_optimizers(name: &str, sorted: &[&dyn KeywordOptimizer]) -> Vec<String> {
@@ -168,3 +398,220 @@ impl Default for CandidatePipeline {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use zerolaunch_plugin_api::config::{ComponentCore, ComponentType, SettingDefinition};
+    use zerolaunch_plugin_api::services::IconRequest;
+    use zerolaunch_plugin_api::CandidateId;
+
+    /// 可变数据源桩：候选项列表可在两次采集之间修改，并统计采集次数。
+    struct StubSource {
+        core: ComponentCore,
+        items: Mutex<Vec<(String, String)>>,
+        delay: Duration,
+        fetches: AtomicUsize,
+    }
+
+    impl StubSource {
+        fn new(id: &str, items: &[(&str, &str)]) -> Self {
+            Self::with_delay(id, items, Duration::ZERO)
+        }
+
+        fn with_delay(id: &str, items: &[(&str, &str)], delay: Duration) -> Self {
+            Self {
+                core: ComponentCore::new(
+                    id.to_string(),
+                    id.to_string(),
+                    "增量刷新测试".to_string(),
+                    ComponentType::DataSource,
+                    0,
+                ),
+                items: Mutex::new(
+                    items
+                        .iter()
+                        .map(|(name, path)| (name.to_string(), path.to_string()))
+                        .collect(),
+                ),
+                delay,
+                fetches: AtomicUsize::new(0),
+            }
+        }
+
+        fn set_items(&self, items: &[(&str, &str)]) {
+            *self.items.lock() = items
+                .iter()
+                .map(|(name, path)| (name.to_string(), path.to_string()))
+                .collect();
+        }
+    }
+
+    impl Configurable for StubSource {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            Vec::new()
+        }
+    }
+
+    #[async_trait]
+    impl DataSource for StubSource {
+        async fn fetch_candidates(&self) -> CachedCandidateData {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            let items = self.items.lock().clone();
+            let mut data = CachedCandidateData::new();
+            for (name, path) in items {
+                data.add_candidate(SearchCandidate {
+                    id: 0,
+                    name,
+                    icon: IconRequest::Path(String::new()),
+                    target: ExecutionTarget::Path(path),
+                    keywords: Vec::new(),
+                    bias: 0.0,
+                    trigger_keywords: Vec::new(),
+                    actions: Vec::new(),
+                });
+            }
+            data
+        }
+    }
+
+    /// 统计调用次数的关键字优化器桩（验证关键字复用）。
+    struct CountingOptimizer {
+        core: ComponentCore,
+        calls: AtomicUsize,
+    }
+
+    impl CountingOptimizer {
+        fn new() -> Self {
+            Self {
+                core: ComponentCore::new(
+                    "test.optimizer".to_string(),
+                    "计数优化器".to_string(),
+                    "增量刷新测试".to_string(),
+                    ComponentType::KeywordOptimizer,
+                    0,
+                ),
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl Configurable for CountingOptimizer {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            Vec::new()
+        }
+    }
+
+    impl KeywordOptimizer for CountingOptimizer {
+        fn optimize(&self, keyword: &str) -> Vec<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            vec![keyword.to_lowercase()]
+        }
+
+        fn get_priority(&self) -> u32 {
+            0
+        }
+    }
+
+    /// 按名称查找候选ID。
+    fn id_of(data: &CachedCandidateData, name: &str) -> CandidateId {
+        data.get_candidates()
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.id)
+            .expect("候选项应存在")
+    }
+
+    /// 只重新采集变更的数据源：未变更数据源不再采集，既有候选项沿用ID，
+    /// 关键字仅对新增与改名的候选项重算。
+    #[tokio::test]
+    async fn scoped_refresh_keeps_ids_and_reuses_keywords() {
+        let apps = Arc::new(StubSource::new("apps", &[("Alpha", "/a"), ("Beta", "/b")]));
+        let urls = Arc::new(StubSource::new("urls", &[("Gamma", "/g")]));
+        let optimizer = Arc::new(CountingOptimizer::new());
+        let mut pipeline = CandidatePipeline::new();
+        pipeline.add_source(apps.clone());
+        pipeline.add_source(urls.clone());
+        pipeline.add_keyword_optimizer(optimizer.clone());
+
+        let first = pipeline.collect().await;
+        let (alpha, beta, gamma) = (
+            id_of(&first, "Alpha"),
+            id_of(&first, "Beta"),
+            id_of(&first, "Gamma"),
+        );
+        assert_eq!(optimizer.calls.load(Ordering::SeqCst), 3);
+
+        // apps：Beta 改名为 Beta2，新增 Delta
+        apps.set_items(&[("Alpha", "/a"), ("Beta2", "/b"), ("Delta", "/d")]);
+        pipeline
+            .fetch(&RefreshScope::Sources(vec!["apps".to_string()]))
+            .await;
+        let second = pipeline.assemble(&first);
+
+        assert_eq!(apps.fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(urls.fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(id_of(&second, "Alpha"), alpha);
+        assert_eq!(id_of(&second, "Beta2"), beta);
+        assert_eq!(id_of(&second, "Gamma"), gamma);
+        assert!(id_of(&second, "Delta") > gamma.max(alpha).max(beta));
+        // 仅 Beta2（改名）与 Delta（新增）重算关键字
+        assert_eq!(optimizer.calls.load(Ordering::SeqCst), 5);
+        let generations = pipeline.source_generations();
+        assert_eq!(generations.get("apps"), Some(&2));
+        assert_eq!(generations.get("urls"), Some(&1));
+
+        // 关键字范围：不采集，全部重算
+        pipeline.fetch(&RefreshScope::Keywords).await;
+        let third = pipeline.assemble(&second);
+        assert_eq!(apps.fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(optimizer.calls.load(Ordering::SeqCst), 9);
+        assert_eq!(id_of(&third, "Delta"), id_of(&second, "Delta"));
+    }
+
+    /// 数据源采集超时：沿用上一次快照，不阻塞其余数据源。
+    #[tokio::test]
+    async fn timed_out_source_keeps_previous_snapshot() {
+        let slow = Arc::new(StubSource::new("slow", &[("Alpha", "/a")]));
+        let fast = Arc::new(StubSource::new("fast", &[("Gamma", "/g")]));
+        let mut pipeline = CandidatePipeline::new();
+        pipeline.add_source(slow.clone());
+        pipeline.add_source(fast.clone());
+        pipeline.set_source_timeout(Duration::from_millis(50));
+        let first = pipeline.collect().await;
+
+        // 第二次采集时 slow 超时：沿用 Alpha，fast 的新结果生效
+        let slow_again = Arc::new(StubSource::with_delay(
+            "slow",
+            &[("Changed", "/c")],
+            Duration::from_millis(500),
+        ));
+        let mut rebuilt = CandidatePipeline::new();
+        rebuilt.add_source(slow_again);
+        rebuilt.add_source(fast.clone());
+        rebuilt.set_source_timeout(Duration::from_millis(50));
+        rebuilt.inherit_from(&pipeline);
+        fast.set_items(&[("Gamma", "/g"), ("Omega", "/o")]);
+
+        rebuilt.fetch(&RefreshScope::All).await;
+        let second = rebuilt.assemble(&first);
+        let names: Vec<&str> = second
+            .get_candidates()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Alpha", "Gamma", "Omega"]);
+        assert_eq!(id_of(&second, "Alpha"), id_of(&first, "Alpha"));
+    }
+}
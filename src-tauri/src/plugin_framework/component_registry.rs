@@ -6,10 +6,10 @@ use zerolaunch_plugin_api::{
     DataSource, KeywordInjector, KeywordOptimizer, ScoreBooster, SearchEngine,
 };
 
-use crate::core::config::ConfigManager;
+use crate::core::config::{ConfigManager, SearchFusionSettings, SEARCH_FUSION_CONFIG_ID};
 
 use super::candidate_pipeline::CandidatePipeline;
-use super::search_pipeline::SearchPipeline;
+use super::search_pipeline::{SearchPipeline, WeightedEngine};
 
 /// 插件运行时组件注册中心。
 ///
@@ -85,11 +85,28 @@ impl PluginComponentRegistry {
         self.keyword_injectors.write().remove(component_id);
     }
 
+    /// 获取全部已注册的搜索引擎（含已禁用的），按 (priority, component_id) 排序。
+    pub fn search_engines(&self) -> Vec<Arc<dyn SearchEngine>> {
+        let mut engines: Vec<Arc<dyn SearchEngine>> =
+            self.search_engines.read().values().cloned().collect();
+        engines.sort_by(|a, b| {
+            (a.priority(), a.component_id()).cmp(&(b.priority(), b.component_id()))
+        });
+        engines
+    }
+
     /// 检查是否存在指定 ID 的搜索引擎。
     pub fn contains_engine(&self, component_id: &str) -> bool {
         self.search_engines.read().contains_key(component_id)
     }
 
+    /// 将所有分数增强器尚未写盘的学习数据立即持久化（含已禁用的增强器）。
+    pub fn persist_score_boosters(&self) {
+        for booster in self.score_boosters.read().values() {
+            booster.persist();
+        }
+    }
+
     /// 根据当前注册表重建候选管道（仅包含启用的组件）。
     /// 参数：cm - ConfigManager，用于查询 is_enabled 状态。
     pub fn build_candidate_pipeline(&self, cm: &ConfigManager) -> CandidatePipeline {
@@ -120,7 +137,9 @@ impl PluginComponentRegistry {
     }
 
     /// 根据当前注册表重建搜索管道（仅包含启用的组件）。
-    /// 参数：cm - ConfigManager，用于查询 is_enabled 状态。
+    /// 启用多个搜索引擎时按 (priority, component_id) 排序，第一个为主引擎，
+    /// 融合策略与各引擎权重读取自 `search-fusion-config` 组件设置（缺失或解析失败时用默认值）。
+    /// 参数：cm - ConfigManager，用于查询 is_enabled 状态与融合配置。
     ///       top_k - 搜索结果截断数量。
     /// 返回：如果存在启用的搜索引擎则返回 Some，否则返回 None。
     pub fn build_search_pipeline(
@@ -128,11 +147,26 @@ impl PluginComponentRegistry {
         cm: &ConfigManager,
         top_k: usize,
     ) -> Option<SearchPipeline> {
-        let engines = self.search_engines.read();
-        let enabled_engine = engines
-            .values()
-            .find(|e| cm.is_enabled(e.component_id()))
-            .cloned();
+        let enabled_engines: Vec<Arc<dyn SearchEngine>> = self
+            .search_engines()
+            .into_iter()
+            .filter(|e| cm.is_enabled(e.component_id()))
+            .collect();
+        if enabled_engines.is_empty() {
+            return None;
+        }
+
+        let fusion = cm
+            .get_settings(SEARCH_FUSION_CONFIG_ID)
+            .and_then(|v| serde_json::from_value::<SearchFusionSettings>(v).ok())
+            .unwrap_or_default();
+        let engines = enabled_engines
+            .into_iter()
+            .map(|engine| WeightedEngine {
+                weight: fusion.weight_of(engine.component_id()),
+                engine,
+            })
+            .collect();
 
         let boosters = self.score_boosters.read();
         let enabled_boosters: Vec<Arc<dyn ScoreBooster>> = boosters
@@ -141,7 +175,12 @@ impl PluginComponentRegistry {
             .cloned()
             .collect();
 
-        enabled_engine.map(|engine| SearchPipeline::new(engine, enabled_boosters, top_k))
+        Some(SearchPipeline::with_engines(
+            engines,
+            &fusion,
+            enabled_boosters,
+            top_k,
+        ))
     }
 }
 
@@ -1,11 +1,18 @@
 //! TauriHostCallHandler — 将插件的 `host/*` RPC 调用分发给本地 PluginHandle。
+//!
+//! 分发前按 manifest `[permissions]` 校验：未授予的权限统一返回 `codes::PERMISSION_DENIED`，
+//! 未知方法返回 `codes::METHOD_NOT_FOUND`（默认拒绝）。
 
 use base64::Engine;
 use std::sync::Arc;
 use tauri::AppHandle;
 use zerolaunch_plugin_api::host::OpenTarget;
+use zerolaunch_plugin_api::QueryUpdate;
 use zerolaunch_plugin_host::host_dispatch::HostCallHandler;
-use zerolaunch_plugin_protocol::{codes, JsonRpcError};
+use zerolaunch_plugin_protocol::methods::notify;
+use zerolaunch_plugin_protocol::{
+    codes, HostMethodAccess, JsonRpcError, Permission, PermissionsSection, QueryUpdateParams,
+};
 
 use crate::core::i18n::I18nManager;
 use crate::sdk::HostApi;
@@ -17,6 +24,51 @@ pub(crate) struct TauriHostCallHandler {
     pub(crate) app_handle: Option<Arc<AppHandle>>,
     /// 后端翻译服务（host/i18n.get_locale 查询当前语言）
     pub(crate) i18n: Arc<I18nManager>,
+    /// manifest 声明的权限（每次 host/* 调用前校验）
+    pub(crate) permissions: PermissionsSection,
+}
+
+impl TauriHostCallHandler {
+    /// 校验 host 方法所需权限；未授予时返回 PERMISSION_DENIED（data 携带权限键名与方法名），
+    /// 未归类的未知方法直接返回 METHOD_NOT_FOUND，不进入分发。
+    fn check_permission(&self, method: &str) -> Result<(), JsonRpcError> {
+        let permission = match Permission::required_for(method) {
+            HostMethodAccess::Open => return Ok(()),
+            HostMethodAccess::Requires(permission) => permission,
+            HostMethodAccess::Unknown => {
+                tracing::warn!(
+                    "[plugin {}] 拒绝调用未知 host 方法 {}",
+                    self.plugin_id,
+                    method
+                );
+                return Err(JsonRpcError::new(
+                    codes::METHOD_NOT_FOUND,
+                    format!("host method not found: {}", method),
+                ));
+            }
+        };
+        if self.permissions.allows(permission) {
+            return Ok(());
+        }
+        tracing::warn!(
+            "[plugin {}] 拒绝调用 {}：manifest 未声明权限 {}",
+            self.plugin_id,
+            method,
+            permission.key()
+        );
+        Err(JsonRpcError::new(
+            codes::PERMISSION_DENIED,
+            format!(
+                "permission '{}' not granted for {}",
+                permission.key(),
+                method
+            ),
+        )
+        .with_data(serde_json::json!({
+            "permission": permission.key(),
+            "method": method,
+        })))
+    }
 }
 
 #[async_trait::async_trait]
@@ -29,6 +81,8 @@ impl HostCallHandler for TauriHostCallHandler {
         use serde_json::from_value;
         use zerolaunch_plugin_protocol::methods::host;
 
+        self.check_permission(method)?;
+
         let handle = self
             .host_api
             .get_plugin_handle(&self.plugin_id)
@@ -212,4 +266,176 @@ impl HostCallHandler for TauriHostCallHandler {
             )),
         }
     }
+
+    /// 处理插件 → 宿主通知：host/query.update 转发给 SessionDispatcher（经 HostApi 回调）。
+    /// 插件身份取自本连接绑定的 plugin_id，不信任通知载荷。
+    async fn handle_host_notification(&self, method: &str, params: serde_json::Value) {
+        match method {
+            notify::QUERY_UPDATE => {
+                let p: QueryUpdateParams = match serde_json::from_value(params) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::warn!("[plugin {}] 增量结果载荷无效: {}", self.plugin_id, e);
+                        return;
+                    }
+                };
+                self.host_api.push_query_update(QueryUpdate {
+                    plugin_id: self.plugin_id.clone(),
+                    revision: p.revision,
+                    response: p.response,
+                });
+            }
+            _ => tracing::debug!("[plugin {}] 忽略未知通知: {}", self.plugin_id, method),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use zerolaunch_plugin_api::host::PluginSdkConfig;
+    use zerolaunch_plugin_api::mock::*;
+    use zerolaunch_plugin_api::services::resource::AppResourceService;
+    use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
+    use zerolaunch_plugin_api::services::timer::TokioTimerManager;
+    use zerolaunch_plugin_api::PlatformCapabilities;
+    use zerolaunch_plugin_protocol::methods::host;
+
+    /// 构建仅含桩组件的 HostApi（测试专用，不触达真实平台能力）。
+    fn test_host_api() -> Arc<HostApi> {
+        let storage: Arc<dyn StorageService> = Arc::new(StubStorageService);
+        let api = HostApi::builder("mock_icons".to_string())
+            .capabilities(PlatformCapabilities::new(HashSet::new()))
+            .icon_extractor(Arc::new(StubIconExtractor))
+            .shell_executor(Arc::new(StubShellExecutor::default()))
+            .window_manager(Arc::new(StubWindowManager))
+            .path_resolver(Arc::new(StubPathResolver))
+            .app_enumerator(Arc::new(StubAppEnumerator))
+            .app_launcher(Arc::new(StubAppLauncher))
+            .lnk_resolver(Arc::new(StubLnkResolver))
+            .resource_loader(Arc::new(StubResourceLoader))
+            .parameter_resolver(Arc::new(StubParameterResolver))
+            .parameter_providers(
+                Arc::new(StubSystemParameterProvider),
+                Arc::new(StubSystemParameterProvider),
+                Arc::new(StubSystemParameterProvider),
+            )
+            .autostart_manager(Arc::new(StubAutoStartManager))
+            .hotkey_manager(Arc::new(StubHotkeyManager))
+            .installation_monitor(Arc::new(StubInstallationMonitor))
+            .timer_manager(Arc::new(TokioTimerManager::new()))
+            .storage_service(storage)
+            .app_resource(Arc::new(AppResourceService::new("mock_icons".to_string())))
+            .focus_monitor(Arc::new(StubFocusMonitor))
+            .clipboard_manager(Arc::new(StubClipboardManager))
+            .notify_callback(|_, _| {})
+            .hide_window_callback(|| {})
+            .show_window_callback(|| {})
+            .is_window_visible_callback(|| false)
+            .window_positioner(Arc::new(StubWindowPositioner))
+            .set_window_position_callback(|_, _| {})
+            .build()
+            .expect("构建测试 HostApi 失败");
+        Arc::new(api)
+    }
+
+    /// 以指定权限构造处理器（语言包目录不存在时仅告警，不影响测试）。
+    fn handler(permissions: PermissionsSection) -> TauriHostCallHandler {
+        TauriHostCallHandler {
+            host_api: test_host_api(),
+            plugin_id: "com.example.test".to_string(),
+            app_handle: None,
+            i18n: I18nManager::load(PathBuf::from("missing-locales")),
+            permissions,
+        }
+    }
+
+    /// 全部权限均已授予的权限段。
+    fn all_granted() -> PermissionsSection {
+        PermissionsSection {
+            shell: true,
+            elevation: true,
+            clipboard: true,
+            notifications: true,
+            app_enumeration: true,
+            resources: true,
+            network: true,
+        }
+    }
+
+    /// 无需授权的方法在未声明任何权限时放行。
+    #[test]
+    fn check_permission_allows_open_methods() {
+        let handler = handler(PermissionsSection::default());
+        for method in [
+            host::LOG,
+            host::ICON_GET,
+            host::PATH_RESOLVE,
+            host::PARAMETER_RESOLVE,
+            host::GET_LOCALE,
+        ] {
+            assert!(handler.check_permission(method).is_ok(), "{method} 应放行");
+        }
+    }
+
+    /// 未授予权限时返回 PERMISSION_DENIED 并携带权限键名；授予后放行。
+    #[test]
+    fn check_permission_requires_granted_permission() {
+        let denied = handler(PermissionsSection::default());
+        let err = denied
+            .check_permission(host::SHELL_OPEN)
+            .expect_err("未声明 shell 权限应拒绝");
+        assert_eq!(err.code, codes::PERMISSION_DENIED);
+        let data = err.data.expect("应携带权限信息");
+        assert_eq!(data["permission"], "shell");
+        assert_eq!(data["method"], host::SHELL_OPEN);
+
+        let granted = handler(PermissionsSection {
+            shell: true,
+            ..Default::default()
+        });
+        assert!(granted.check_permission(host::SHELL_OPEN).is_ok());
+        // 授予 shell 不代表授予提权
+        let err = granted
+            .check_permission(host::SHELL_EXECUTE_ELEVATION)
+            .expect_err("未声明 elevation 权限应拒绝");
+        assert_eq!(err.code, codes::PERMISSION_DENIED);
+    }
+
+    /// 未归类的方法即使授予全部权限也拒绝（默认拒绝，不因新增方法漏归类而放行）。
+    #[test]
+    fn check_permission_rejects_unknown_methods() {
+        let handler = handler(all_granted());
+        for method in ["host/fs.read", "host/clipboard.write", "plugin/query", ""] {
+            let err = handler
+                .check_permission(method)
+                .expect_err("未知方法应拒绝");
+            assert_eq!(err.code, codes::METHOD_NOT_FOUND);
+        }
+    }
+
+    /// 权限校验先于分发：未授权调用不会触达插件句柄，授权调用正常分发。
+    #[tokio::test]
+    async fn host_call_checks_permission_before_dispatch() {
+        let handler = handler(PermissionsSection::default());
+        let err = handler
+            .handle_host_call(
+                host::NOTIFY,
+                serde_json::json!({ "title": "t", "message": "hi" }),
+            )
+            .await
+            .expect_err("未声明 notifications 权限应拒绝");
+        assert_eq!(err.code, codes::PERMISSION_DENIED);
+
+        handler
+            .host_api
+            .register(&handler.plugin_id, PluginSdkConfig::default());
+        let locale = handler
+            .handle_host_call(host::GET_LOCALE, serde_json::Value::Null)
+            .await
+            .expect("语言查询无需授权");
+        assert!(locale.is_string());
+    }
 }
@@ -9,27 +9,30 @@
 //! ConfigManager 处理配置侧（Configurable）+ 转发 ConfigEvent 到 SessionDispatcher。
 
 use parking_lot::RwLock;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::AppHandle;
 use tauri::Emitter;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use zerolaunch_plugin_api::config::Configurable;
 use zerolaunch_plugin_api::host::PluginSdkConfig;
 use zerolaunch_plugin_api::plugin::{PluginKind, PluginMetadata};
 use zerolaunch_plugin_host::host_dispatch::HostCallHandler;
 use zerolaunch_plugin_host::manager::{
-    CrashCallback, InstalledPluginInfo, PluginHostManager, PluginLoadError, PluginRegistration,
-    PluginRuntimeState, RestartCallback,
+    granted_permission_keys, CrashCallback, InstalledPluginInfo, PluginHostManager,
+    PluginLoadError, PluginRegistration, PluginRuntimeState, RestartCallback,
 };
+use zerolaunch_plugin_host::metrics::PluginMetricsSnapshot;
 use zerolaunch_plugin_protocol::Manifest;
 
 use crate::core::config::event::{PluginEventSender, PluginRuntimeEvent};
 use crate::core::config::manager::ConfigManager;
+use crate::core::config::plugin_registry_settings::PluginRegistrySettings;
+use crate::core::host_event::{HostEvent, HostEventBus, PluginChange};
 use crate::plugin_framework::builtin_registry;
 use crate::plugin_framework::builtin_registry::{CollectedBuiltins, InventoryContext};
 use crate::plugin_framework::zlplugin_protocol::ZlpluginProtocolHandler;
@@ -37,6 +40,10 @@ use crate::plugin_framework::SessionDispatcher;
 use crate::sdk::HostApi;
 
 use super::host_handler::TauriHostCallHandler;
+use super::package_registry::{
+    self, RegistryError, RegistryListing, RegistryPlugin, RegistryRelease, RegistrySource,
+    TrustStore,
+};
 use super::plugin_info::InstallError;
 use super::plugin_installer::PluginInstaller;
 use crate::core::i18n::I18nManager;
@@ -55,6 +62,10 @@ pub enum PluginManagerError {
     AlreadyInstalled(String),
     /// 组件 id 与已注册组件冲突（加载被拒，插件未加载）
     ComponentIdCollision(String),
+    /// 插件注册表不可用（未配置、获取失败、索引无效或无兼容版本）
+    Registry(String),
+    /// 插件包 sha256 或签名校验失败（包未解压）
+    VerificationFailed(String),
     /// 常规内部错误
     Internal(String),
 }
@@ -69,6 +80,10 @@ impl fmt::Display for PluginManagerError {
             PluginManagerError::ComponentIdCollision(id) => {
                 write!(f, "组件 id 已被其他已注册组件占用: {}", id)
             }
+            PluginManagerError::Registry(msg) => write!(f, "插件注册表错误: {}", msg),
+            PluginManagerError::VerificationFailed(msg) => {
+                write!(f, "插件包校验失败: {}", msg)
+            }
             PluginManagerError::Internal(msg) => write!(f, "插件管理器内部错误: {}", msg),
         }
     }
@@ -91,6 +106,8 @@ pub struct PluginManager {
     i18n: RwLock<Option<Arc<I18nManager>>>,
     /// PluginHostManager（内部构造，管理子进程生命周期）
     host_manager: RwLock<Option<Arc<PluginHostManager>>>,
+    /// 宿主可观测事件总线（插件崩溃/重启事件）；未注入时不发布
+    host_events: RwLock<Option<Arc<HostEventBus>>>,
 }
 
 impl PluginManager {
@@ -101,6 +118,7 @@ impl PluginManager {
             host_api: RwLock::new(None),
             i18n: RwLock::new(None),
             host_manager: RwLock::new(None),
+            host_events: RwLock::new(None),
         }
     }
 
@@ -121,6 +139,11 @@ impl PluginManager {
         *self.i18n.write() = Some(i18n);
     }
 
+    /// 注入宿主可观测事件总线。
+    pub fn set_host_events(&self, bus: Arc<HostEventBus>) {
+        *self.host_events.write() = Some(bus);
+    }
+
     /// 读取后端翻译服务引用。
     ///
     /// # Panics
@@ -215,6 +238,10 @@ impl PluginManager {
             // icon 恒为 data URL（内置构造期解析、第三方加载期注入），直接透传
             icon: meta.icon.clone(),
             mode: meta.mode,
+            // 内置插件直接持有 PluginHandle，不经 host/* 权限校验
+            permissions: Vec::new(),
+            // 内置插件运行在宿主进程内，无独立进程指标
+            metrics: None,
         }
     }
 
@@ -278,6 +305,11 @@ impl PluginManager {
         Some(adapters.manifest.clone())
     }
 
+    /// 获取第三方插件的健康指标快照（请求延迟、错误、重启次数、内存）；未加载返回 None。
+    pub fn get_metrics(&self, plugin_id: &str) -> Option<PluginMetricsSnapshot> {
+        self.host_manager().plugin_metrics(plugin_id)
+    }
+
     /// 获取第三方插件的日志文件最近 N 行。
     pub fn get_logs(
         &self,
@@ -342,6 +374,25 @@ impl PluginManager {
 
     // ── 第三方插件生命周期方法 ─────────────────────────────────
 
+    /// 安装前预览插件包：解析 manifest（插件身份与申请的宿主权限），不安装、不落盘。
+    /// 格式约束与 `install` 一致（.zip 或目录）。
+    pub fn inspect_package(&self, source_path: &Path) -> Result<Manifest, PluginManagerError> {
+        if !source_path.exists() {
+            return Err(PluginManagerError::FileNotFound(format!(
+                "File not found: {}",
+                source_path.display()
+            )));
+        }
+        if !source_path.is_dir() && source_path.extension().is_none_or(|e| e != "zip") {
+            return Err(PluginManagerError::UnsupportedFormat(
+                "Unsupported file format. Use .zip or directory.".to_string(),
+            ));
+        }
+        self.installer()
+            .inspect(source_path)
+            .map_err(install_error_to_manager)
+    }
+
     /// 从 .zip 文件或目录安装插件。
     /// 成功时发送 `plugin-installed` 事件。
     pub async fn install(
@@ -394,7 +445,17 @@ impl PluginManager {
         let manifest: Manifest = toml::from_str(&manifest_bytes).map_err(|e| {
             PluginManagerError::Internal(format!("Failed to parse manifest: {}", e))
         })?;
-        let plugin_id = &manifest.plugin.id;
+        self.loaded_plugin_info(&manifest.plugin.id)
+    }
+
+    /// 组装刚加载成功的第三方插件信息（install / upgrade 返回值）。
+    ///
+    /// priority 与 list_plugins/plugin_info 统一取插件级元数据声明值（不再用组件最小优先级）。
+    /// 加载成功即子进程已运行，state 恒为 Running。
+    fn loaded_plugin_info(
+        &self,
+        plugin_id: &str,
+    ) -> Result<InstalledPluginInfo, PluginManagerError> {
         let hm = self.host_manager();
         let adapters = hm.plugins.get(plugin_id).ok_or_else(|| {
             PluginManagerError::PluginNotFound(format!(
@@ -403,8 +464,6 @@ impl PluginManager {
             ))
         })?;
 
-        // priority 与 list_plugins/plugin_info 统一取插件级元数据声明值（不再用组件最小优先级）。
-        // 安装成功即子进程已加载运行，state 恒为 Running。
         Ok(InstalledPluginInfo {
             plugin_id: adapters.plugin_id.clone(),
             name: adapters.manifest.plugin.name.clone(),
@@ -424,6 +483,8 @@ impl PluginManager {
             hotkey: adapters.metadata.hotkey.clone(),
             icon: adapters.metadata.icon.clone(),
             mode: adapters.metadata.mode,
+            permissions: granted_permission_keys(&adapters.manifest),
+            metrics: hm.plugin_metrics(plugin_id),
         })
     }
 
@@ -514,6 +575,155 @@ impl PluginManager {
         Ok(())
     }
 
+    // ── 插件注册表：目录 / 更新检查 / 签名安装与升级 ─────────────────
+
+    /// 拉取注册表目录，合并已加载第三方插件的版本，标记可用更新。
+    pub async fn registry_catalog(
+        &self,
+        settings: &PluginRegistrySettings,
+    ) -> Result<Vec<RegistryListing>, PluginManagerError> {
+        let source = package_registry::open_source(&settings.source).map_err(registry_error)?;
+        let index = source.fetch_index().await.map_err(registry_error)?;
+        Ok(package_registry::build_catalog(
+            &index,
+            &self.installed_versions(),
+            &package_registry::host_version(),
+        ))
+    }
+
+    /// 检查已安装插件的可用更新（目录中 `updateAvailable` 为真的条目）。
+    pub async fn check_updates(
+        &self,
+        settings: &PluginRegistrySettings,
+    ) -> Result<Vec<RegistryListing>, PluginManagerError> {
+        let mut catalog = self.registry_catalog(settings).await?;
+        catalog.retain(|l| l.update_available);
+        Ok(catalog)
+    }
+
+    /// 从注册表安装插件的最新兼容版本：下载并校验签名后解压到暂存目录，
+    /// 确认包内 manifest 与注册表条目一致（ID 与版本）再移入插件目录加载。
+    pub async fn install_from_registry(
+        &self,
+        settings: &PluginRegistrySettings,
+        plugin_id: &str,
+        app_handle: Arc<AppHandle>,
+    ) -> Result<InstalledPluginInfo, PluginManagerError> {
+        if self.host_manager().plugins.contains_key(plugin_id) {
+            return Err(PluginManagerError::AlreadyInstalled(plugin_id.to_string()));
+        }
+        let (source, plugin, release) = resolve_release(settings, plugin_id).await?;
+        let package = self
+            .download_verified(source.as_ref(), settings, &plugin, &release)
+            .await?;
+        let installer = self.installer();
+        let staged = installer.stage_from_zip(&package);
+        let _ = std::fs::remove_file(&package);
+        let (manifest, staged_dir) = staged.map_err(install_error_to_manager)?;
+        verify_staged_identity(&manifest, &staged_dir, plugin_id, &release.version)?;
+
+        let loader = ManagerDirLoader {
+            manager: self,
+            app_handle,
+        };
+        install_staged(&installer, &loader, plugin_id, &staged_dir).await?;
+        info!(
+            "Plugin {} {} installed from registry",
+            plugin_id, release.version
+        );
+        self.loaded_plugin_info(plugin_id)
+    }
+
+    /// 将已安装插件升级到注册表中的最新兼容版本。
+    ///
+    /// 新版本先校验并解压到暂存目录，卸载旧版本后整体替换插件目录；
+    /// 新版本加载（discover_components）失败时恢复旧目录并重新加载旧版本。
+    pub async fn upgrade_from_registry(
+        &self,
+        settings: &PluginRegistrySettings,
+        plugin_id: &str,
+        app_handle: Arc<AppHandle>,
+    ) -> Result<InstalledPluginInfo, PluginManagerError> {
+        let current = self.get_manifest(plugin_id).ok_or_else(|| {
+            PluginManagerError::PluginNotFound(format!("Plugin not found: {}", plugin_id))
+        })?;
+        let (source, plugin, release) = resolve_release(settings, plugin_id).await?;
+        if !package_registry::is_newer(&current.plugin.version, &release.version) {
+            return Err(PluginManagerError::Registry(format!(
+                "{} 已是最新版本 {}",
+                plugin_id, current.plugin.version
+            )));
+        }
+
+        let package = self
+            .download_verified(source.as_ref(), settings, &plugin, &release)
+            .await?;
+        let installer = self.installer();
+        let staged = installer.stage_from_zip(&package);
+        let _ = std::fs::remove_file(&package);
+        let (manifest, staged_dir) = staged.map_err(install_error_to_manager)?;
+        verify_staged_identity(&manifest, &staged_dir, plugin_id, &release.version)?;
+
+        info!(
+            "Upgrading plugin {} from {} to {}",
+            plugin_id, current.plugin.version, release.version
+        );
+        let loader = ManagerDirLoader {
+            manager: self,
+            app_handle,
+        };
+        upgrade_staged(
+            &installer,
+            &loader,
+            plugin_id,
+            &staged_dir,
+            &current.plugin.version,
+            &release.version,
+        )
+        .await?;
+        info!("Plugin {} upgraded to {}", plugin_id, release.version);
+        self.loaded_plugin_info(plugin_id)
+    }
+
+    /// 已加载第三方插件的版本表（插件 ID → manifest 版本）。
+    fn installed_versions(&self) -> HashMap<String, String> {
+        self.host_manager()
+            .plugins
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key().clone(),
+                    entry.value().manifest.plugin.version.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// 下载插件包并在落盘前完成 sha256 与签名校验，返回暂存区中的 zip 路径。
+    async fn download_verified(
+        &self,
+        source: &dyn RegistrySource,
+        settings: &PluginRegistrySettings,
+        plugin: &RegistryPlugin,
+        release: &RegistryRelease,
+    ) -> Result<PathBuf, PluginManagerError> {
+        let bytes = source
+            .fetch_package(release)
+            .await
+            .map_err(registry_error)?;
+        TrustStore::from_keys(&settings.trusted_keys)
+            .verify(plugin, release, &bytes)
+            .map_err(registry_error)?;
+
+        let staging = self.installer().staging_dir();
+        std::fs::create_dir_all(&staging)
+            .map_err(|e| PluginManagerError::Internal(e.to_string()))?;
+        let package = staging.join(format!("{}-{}.zip", plugin.id, release.version));
+        std::fs::write(&package, &bytes)
+            .map_err(|e| PluginManagerError::Internal(e.to_string()))?;
+        Ok(package)
+    }
+
     /// 扫描并加载所有第三方插件。
     ///
     /// 每个插件的注册通过 PluginRuntimeEvent 广播通道（PM → CM）完成，
@@ -571,6 +781,7 @@ impl PluginManager {
             plugin_id: plugin_id.clone(),
             app_handle: Some(app_handle.clone()),
             i18n: self.i18n_manager(),
+            permissions: manifest.permissions.clone(),
         });
 
         let on_restart = self.make_restart_callback(plugin_id.clone());
@@ -632,14 +843,22 @@ impl PluginManager {
     /// 旧组件已在崩溃处理第一步经 on_crash 解注册，此处只注册新组件。
     fn make_restart_callback(&self, plugin_id: String) -> RestartCallback {
         let tx = self.plugin_event_tx();
+        let events = self.host_events.read().clone();
 
         Arc::new(move |new_adapters: PluginRegistration| {
             let tx = tx.clone();
+            let events = events.clone();
             let pid = plugin_id.clone();
 
             Box::pin(async move {
                 tx.send(PluginRuntimeEvent::PluginLoaded(new_adapters.clone()))
                     .ok();
+                if let Some(events) = events {
+                    events.publish(HostEvent::Plugin {
+                        plugin_id: pid.clone(),
+                        change: PluginChange::Restarted,
+                    });
+                }
                 info!(
                     "Restarted third-party plugin: {} (adapters re-registered)",
                     pid
@@ -657,10 +876,17 @@ impl PluginManager {
         let host_api = self.host_api();
         let host_manager = self.host_manager();
         let i18n = self.i18n_manager();
+        let events = self.host_events.read().clone();
 
         Arc::new(move |prev: PluginRegistration| {
             tx.send(PluginRuntimeEvent::PluginUnloaded(prev)).ok();
             host_api.unregister(&plugin_id);
+            if let Some(events) = &events {
+                events.publish(HostEvent::Plugin {
+                    plugin_id: plugin_id.clone(),
+                    change: PluginChange::Crashed,
+                });
+            }
             // 崩溃时同步最新语言到重启上下文：崩溃重启的 initialize 握手
             // 携带实时 locale（而非首次加载时的快照）。
             host_manager.update_locale(&plugin_id, &i18n.current_language());
@@ -687,8 +913,335 @@ fn install_error_to_manager(e: InstallError) -> PluginManagerError {
     }
 }
 
+/// 插件目录的加载/卸载操作 —— 注册表安装与升级编排经此访问运行时，
+/// 与 PluginManager 解耦（测试注入桩实现）。
+#[async_trait::async_trait]
+trait PluginDirLoader: Send + Sync {
+    /// 卸载插件（未加载时为空操作，失败仅记录日志）。
+    async fn unload(&self, plugin_id: &str);
+    /// 从插件目录加载插件。
+    async fn load(&self, plugin_dir: &Path) -> Result<(), PluginManagerError>;
+}
+
+/// PluginManager 的目录加载器：卸载时先广播 PluginUnloaded，再停止插件进程。
+struct ManagerDirLoader<'a> {
+    manager: &'a PluginManager,
+    app_handle: Arc<AppHandle>,
+}
+
+#[async_trait::async_trait]
+impl PluginDirLoader for ManagerDirLoader<'_> {
+    async fn unload(&self, plugin_id: &str) {
+        let hm = self.manager.host_manager();
+        if let Some(adapters) = hm.plugins.get(plugin_id).map(|a| a.clone()) {
+            self.manager
+                .plugin_event_tx()
+                .send(PluginRuntimeEvent::PluginUnloaded(adapters))
+                .ok();
+        }
+        if let Err(e) = hm.unload(plugin_id).await {
+            error!("Unload of plugin {} failed: {}", plugin_id, e);
+        }
+    }
+
+    async fn load(&self, plugin_dir: &Path) -> Result<(), PluginManagerError> {
+        self.manager
+            .load_single_plugin(plugin_dir, self.app_handle.clone())
+            .await
+    }
+}
+
+/// 校验暂存插件包的身份与已签名的注册表条目一致（插件 ID 与版本）。
+/// 签名只覆盖包字节，包内 manifest 可能声明其他插件；不一致时删除暂存目录并拒绝安装。
+fn verify_staged_identity(
+    manifest: &Manifest,
+    staged_dir: &Path,
+    plugin_id: &str,
+    version: &str,
+) -> Result<(), PluginManagerError> {
+    if manifest.plugin.id == plugin_id && manifest.plugin.version == version {
+        return Ok(());
+    }
+    let _ = std::fs::remove_dir_all(staged_dir);
+    Err(PluginManagerError::VerificationFailed(format!(
+        "插件包内容为 {} {}，与注册表条目 {} {} 不一致",
+        manifest.plugin.id, manifest.plugin.version, plugin_id, version
+    )))
+}
+
+/// 新装编排：暂存目录移入插件目录后加载；移入失败清理暂存目录，
+/// 加载失败删除已落盘的插件目录（避免每次启动重试加载的残留目录）。
+async fn install_staged(
+    installer: &PluginInstaller,
+    loader: &dyn PluginDirLoader,
+    plugin_id: &str,
+    staged_dir: &Path,
+) -> Result<PathBuf, PluginManagerError> {
+    let plugin_dir = match installer.install_staged(plugin_id, staged_dir) {
+        Ok(dir) => dir,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(staged_dir);
+            return Err(install_error_to_manager(e));
+        }
+    };
+    if let Err(e) = loader.load(&plugin_dir).await {
+        // 新插件可能已部分注册（如组件 id 冲突前已启动子进程），先确保卸载干净
+        loader.unload(plugin_id).await;
+        let _ = std::fs::remove_dir_all(&plugin_dir);
+        return Err(e);
+    }
+    Ok(plugin_dir)
+}
+
+/// 升级编排：卸载旧版本 → 用暂存目录替换插件目录 → 加载新版本。
+/// 替换失败时旧目录仍在原位，重新加载旧版本；新版本加载失败时回滚目录并重新加载旧版本。
+async fn upgrade_staged(
+    installer: &PluginInstaller,
+    loader: &dyn PluginDirLoader,
+    plugin_id: &str,
+    staged_dir: &Path,
+    from_version: &str,
+    to_version: &str,
+) -> Result<(), PluginManagerError> {
+    loader.unload(plugin_id).await;
+
+    let plugin_dir = match installer.swap_in_staged(plugin_id, staged_dir) {
+        Ok(dir) => dir,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(staged_dir);
+            let old_dir = installer.plugin_dir(plugin_id);
+            if let Err(reload_err) = loader.load(&old_dir).await {
+                error!("Reload after failed upgrade swap failed: {}", reload_err);
+            }
+            return Err(install_error_to_manager(e));
+        }
+    };
+
+    if let Err(e) = loader.load(&plugin_dir).await {
+        warn!(
+            "Plugin {} {} failed to load, rolling back to {}: {}",
+            plugin_id, to_version, from_version, e
+        );
+        // 新版本可能已部分注册（如组件 id 冲突前已启动子进程），先确保卸载干净
+        loader.unload(plugin_id).await;
+        let restored = installer
+            .rollback_upgrade(plugin_id)
+            .map_err(install_error_to_manager)?;
+        if let Err(reload_err) = loader.load(&restored).await {
+            error!("Reload after rollback failed: {}", reload_err);
+        }
+        return Err(match e {
+            PluginManagerError::ComponentIdCollision(id) => {
+                PluginManagerError::ComponentIdCollision(id)
+            }
+            other => PluginManagerError::Internal(format!(
+                "升级到 {} 失败，已回滚到 {}: {}",
+                to_version, from_version, other
+            )),
+        });
+    }
+
+    installer.discard_backup(plugin_id);
+    Ok(())
+}
+
+/// 在注册表中定位插件的最新兼容版本，连同来源一起返回（供后续下载）。
+async fn resolve_release(
+    settings: &PluginRegistrySettings,
+    plugin_id: &str,
+) -> Result<(Box<dyn RegistrySource>, RegistryPlugin, RegistryRelease), PluginManagerError> {
+    let source = package_registry::open_source(&settings.source).map_err(registry_error)?;
+    let index = source.fetch_index().await.map_err(registry_error)?;
+    let plugin = index
+        .find(plugin_id)
+        .cloned()
+        .ok_or_else(|| registry_error(RegistryError::NotFound(plugin_id.to_string())))?;
+    let release = plugin
+        .latest_compatible(&package_registry::host_version())
+        .cloned()
+        .ok_or_else(|| registry_error(RegistryError::NoCompatibleRelease(plugin_id.to_string())))?;
+    Ok((source, plugin, release))
+}
+
+/// 将注册表错误转换为 PluginManagerError：校验类错误与来源类错误分开，供 IPC 层区分。
+fn registry_error(e: RegistryError) -> PluginManagerError {
+    match e {
+        RegistryError::NotFound(id) => PluginManagerError::PluginNotFound(id),
+        RegistryError::Integrity { .. }
+        | RegistryError::UntrustedPublisher(_)
+        | RegistryError::BadSignature(_) => PluginManagerError::VerificationFailed(e.to_string()),
+        other => PluginManagerError::Registry(other.to_string()),
+    }
+}
+
 impl Default for PluginManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    const PLUGIN_ID: &str = "com.example.demo";
+
+    /// 目录加载器桩：按插件目录中 marker.txt 记录的版本号登记调用，指定版本加载失败。
+    #[derive(Default)]
+    struct StubDirLoader {
+        failing_version: Option<&'static str>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PluginDirLoader for StubDirLoader {
+        async fn unload(&self, plugin_id: &str) {
+            self.calls.lock().push(format!("unload {plugin_id}"));
+        }
+
+        async fn load(&self, plugin_dir: &Path) -> Result<(), PluginManagerError> {
+            let version = std::fs::read_to_string(plugin_dir.join("marker.txt")).unwrap();
+            self.calls.lock().push(format!("load {version}"));
+            if self.failing_version == Some(version.as_str()) {
+                return Err(PluginManagerError::Internal("discover failed".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    /// 写一个最小插件目录：marker.txt 记录版本号。
+    fn write_plugin_dir(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("marker.txt"), version).unwrap();
+    }
+
+    /// 读取已安装插件目录的版本号。
+    fn installed_version(installer: &PluginInstaller) -> String {
+        std::fs::read_to_string(installer.plugin_dir(PLUGIN_ID).join("marker.txt")).unwrap()
+    }
+
+    /// 构造指定 ID 与版本的 manifest。
+    fn manifest(id: &str, version: &str) -> Manifest {
+        toml::from_str(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"t\"\nversion = \"{version}\"\n\
+             description = \"\"\nauthor = \"\"\nminHostVersion = \"0.1.0\"\n"
+        ))
+        .unwrap()
+    }
+
+    /// 升级成功：卸载旧版本 → 替换目录 → 加载新版本，备份随后删除。
+    #[tokio::test]
+    async fn upgrade_swaps_in_new_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = PluginInstaller::new(dir.path().to_path_buf());
+        write_plugin_dir(&installer.plugin_dir(PLUGIN_ID), "1.0.0");
+        let staged = installer.staging_dir().join(PLUGIN_ID);
+        write_plugin_dir(&staged, "2.0.0");
+        let loader = StubDirLoader::default();
+
+        upgrade_staged(&installer, &loader, PLUGIN_ID, &staged, "1.0.0", "2.0.0")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *loader.calls.lock(),
+            vec![format!("unload {PLUGIN_ID}"), "load 2.0.0".to_string()]
+        );
+        assert_eq!(installed_version(&installer), "2.0.0");
+        assert!(!staged.exists());
+        assert!(
+            installer.rollback_upgrade(PLUGIN_ID).is_err(),
+            "备份应已删除"
+        );
+    }
+
+    /// 新版本加载失败：卸载残留 → 回滚目录 → 重新加载旧版本，错误说明已回滚。
+    #[tokio::test]
+    async fn upgrade_load_failure_rolls_back_to_previous_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = PluginInstaller::new(dir.path().to_path_buf());
+        write_plugin_dir(&installer.plugin_dir(PLUGIN_ID), "1.0.0");
+        let staged = installer.staging_dir().join(PLUGIN_ID);
+        write_plugin_dir(&staged, "2.0.0");
+        let loader = StubDirLoader {
+            failing_version: Some("2.0.0"),
+            ..Default::default()
+        };
+
+        let err = upgrade_staged(&installer, &loader, PLUGIN_ID, &staged, "1.0.0", "2.0.0")
+            .await
+            .expect_err("新版本加载失败应返回错误");
+
+        assert!(
+            matches!(err, PluginManagerError::Internal(ref msg) if msg.contains("已回滚到 1.0.0"))
+        );
+        assert_eq!(
+            *loader.calls.lock(),
+            vec![
+                format!("unload {PLUGIN_ID}"),
+                "load 2.0.0".to_string(),
+                format!("unload {PLUGIN_ID}"),
+                "load 1.0.0".to_string(),
+            ]
+        );
+        assert_eq!(installed_version(&installer), "1.0.0");
+    }
+
+    /// 注册表新装：暂存目录移入后加载；加载失败删除已落盘目录，目标已存在时拒绝并清理暂存。
+    #[tokio::test]
+    async fn install_staged_loads_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = PluginInstaller::new(dir.path().to_path_buf());
+        let staged = installer.staging_dir().join(PLUGIN_ID);
+
+        write_plugin_dir(&staged, "1.0.0");
+        let failing = StubDirLoader {
+            failing_version: Some("1.0.0"),
+            ..Default::default()
+        };
+        assert!(install_staged(&installer, &failing, PLUGIN_ID, &staged)
+            .await
+            .is_err());
+        assert!(!installer.plugin_dir(PLUGIN_ID).exists());
+        assert_eq!(
+            failing.calls.lock().last().unwrap(),
+            &format!("unload {PLUGIN_ID}")
+        );
+
+        write_plugin_dir(&staged, "1.0.0");
+        let loader = StubDirLoader::default();
+        let plugin_dir = install_staged(&installer, &loader, PLUGIN_ID, &staged)
+            .await
+            .unwrap();
+        assert_eq!(plugin_dir, installer.plugin_dir(PLUGIN_ID));
+        assert_eq!(*loader.calls.lock(), vec!["load 1.0.0".to_string()]);
+
+        write_plugin_dir(&staged, "1.0.1");
+        let err = install_staged(&installer, &loader, PLUGIN_ID, &staged)
+            .await
+            .expect_err("已安装时应拒绝");
+        assert!(matches!(err, PluginManagerError::AlreadyInstalled(_)));
+        assert!(!staged.exists());
+        assert_eq!(installed_version(&installer), "1.0.0");
+    }
+
+    /// 包内 manifest 与已签名注册表条目不一致（ID 或版本）时拒绝并删除暂存目录。
+    #[test]
+    fn verify_staged_identity_rejects_mismatched_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let staged = dir.path().join("staged");
+
+        write_plugin_dir(&staged, "1.0.0");
+        verify_staged_identity(&manifest(PLUGIN_ID, "1.0.0"), &staged, PLUGIN_ID, "1.0.0").unwrap();
+        assert!(staged.exists());
+
+        for (id, version) in [("com.example.other", "1.0.0"), (PLUGIN_ID, "0.9.0")] {
+            write_plugin_dir(&staged, "1.0.0");
+            let err = verify_staged_identity(&manifest(id, version), &staged, PLUGIN_ID, "1.0.0")
+                .expect_err("身份不一致应拒绝");
+            assert!(matches!(err, PluginManagerError::VerificationFailed(_)));
+            assert!(!staged.exists());
+        }
+    }
+}
@@ -1,13 +1,16 @@
 pub mod builtin_registry;
+mod candidate_index_store;
 mod candidate_pipeline;
 pub mod component_registry;
 mod executor_registry;
 pub mod host_handler;
 pub mod inspector;
 pub mod manager;
+pub mod package_registry;
 pub mod plugin_info;
 pub(crate) mod plugin_installer;
 pub mod registry;
+mod search_fusion;
 mod search_pipeline;
 mod session_dispatcher;
 mod session_state;
@@ -20,7 +23,8 @@ pub use zerolaunch_plugin_api::{
     PluginContext, PluginError, PluginMetadata, Query, RegistrationError, ResultAction, TargetType,
 };
 
-pub use candidate_pipeline::CandidatePipeline;
+pub use candidate_index_store::CandidateIndexStore;
+pub use candidate_pipeline::{CandidatePipeline, RefreshScope};
 pub use executor_registry::ExecutorRegistry;
 pub use manager::PluginManagerError;
 pub use registry::PluginRegistry;
@@ -28,7 +32,7 @@ pub use search_pipeline::*;
 
 // 会话调度与状态（Dispatcher 直接内嵌默认搜索与插件逻辑，无流程抽象层）
 pub use session_dispatcher::{
-    ConfirmError, ConfirmOutcome, ConfirmRequest, RoutedConfirm, RoutedQuery, SessionDispatcher,
-    SessionDispatcherError,
+    CliConfirmRequest, ConfirmError, ConfirmOutcome, ConfirmRequest, EngineBenchmark,
+    RoutedConfirm, RoutedQuery, SessionDispatcher, SessionDispatcherError,
 };
 pub use session_state::{ActiveSession, PresentationMode, SessionStateEvent};
@@ -0,0 +1,649 @@
+//! PackageRegistry — 插件注册表索引、签名校验与更新检查。
+//!
+//! 注册表是一个 JSON 索引（`RegistryIndex`），列出每个插件的发布者与各版本的
+//! 下载地址、最低宿主版本、sha256 与 ed25519 签名。索引可以是本地文件
+//! （`FileRegistry`，离线测试与内网分发）或 http(s) 地址（`HttpRegistry`）。
+//!
+//! 插件包在解压前必须通过 `TrustStore::verify`：先比对 sha256，再用发布者在
+//! 信任库中的公钥校验签名，签名内容见 `signing_message`。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use url::Url;
+
+use crate::core::config::plugin_registry_settings::TrustedKey;
+
+/// 当前支持的注册表索引格式版本。
+pub const REGISTRY_SCHEMA_VERSION: u32 = 1;
+
+/// 签名消息的域分隔前缀，防止签名被挪用到其他用途的消息上。
+const SIGNATURE_DOMAIN: &str = "zerolaunch-plugin-v1";
+
+// ── 错误类型 ─────────────────────────────────────────────────────
+
+/// 注册表访问与插件包校验错误。
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    #[error("未配置插件注册表")]
+    NotConfigured,
+    #[error("获取注册表资源失败: {0}")]
+    Fetch(String),
+    #[error("注册表索引无效: {0}")]
+    Index(String),
+    #[error("注册表中不存在插件: {0}")]
+    NotFound(String),
+    #[error("没有可用的兼容版本: {0}")]
+    NoCompatibleRelease(String),
+    #[error("插件包 sha256 不匹配: 期望 {expected}，实际 {actual}")]
+    Integrity { expected: String, actual: String },
+    #[error("发布者不在信任库中: {0}")]
+    UntrustedPublisher(String),
+    #[error("插件包签名无效: {0}")]
+    BadSignature(String),
+}
+
+// ── 索引数据结构 ─────────────────────────────────────────────────
+
+/// 注册表索引根结构（JSON）。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RegistryIndex {
+    /// 索引格式版本，当前为 1。
+    #[serde(rename = "schemaVersion", default)]
+    pub schema_version: u32,
+    /// 注册表中的插件列表。
+    #[serde(rename = "plugins", default)]
+    pub plugins: Vec<RegistryPlugin>,
+}
+
+/// 注册表中的单个插件。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RegistryPlugin {
+    /// 插件 ID，与插件包 manifest 中的 `plugin.id` 一致。
+    #[serde(rename = "id", default)]
+    pub id: String,
+    /// 展示名称。
+    #[serde(rename = "name", default)]
+    pub name: String,
+    /// 简要描述。
+    #[serde(rename = "description", default)]
+    pub description: String,
+    /// 发布者标识，用于在信任库中查找签名公钥。
+    #[serde(rename = "publisher", default)]
+    pub publisher: String,
+    /// 已发布的版本列表（顺序不限）。
+    #[serde(rename = "versions", default)]
+    pub versions: Vec<RegistryRelease>,
+}
+
+/// 插件的单个发布版本。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RegistryRelease {
+    /// 插件版本（semver）。
+    #[serde(rename = "version", default)]
+    pub version: String,
+    /// 运行该版本所需的最低宿主版本（semver），为空表示不限制。
+    #[serde(rename = "minHostVersion", default)]
+    pub min_host_version: String,
+    /// 插件包（.zip）地址，相对地址按索引所在位置解析。
+    #[serde(rename = "url", default)]
+    pub url: String,
+    /// 插件包的 sha256（十六进制）。
+    #[serde(rename = "sha256", default)]
+    pub sha256: String,
+    /// 发布者对 `signing_message` 的 ed25519 签名（base64）。
+    #[serde(rename = "signature", default)]
+    pub signature: String,
+}
+
+impl RegistryIndex {
+    /// 解析 JSON 索引并校验格式版本。
+    pub fn parse(bytes: &[u8]) -> Result<Self, RegistryError> {
+        let index: RegistryIndex =
+            serde_json::from_slice(bytes).map_err(|e| RegistryError::Index(e.to_string()))?;
+        if index.schema_version != REGISTRY_SCHEMA_VERSION {
+            return Err(RegistryError::Index(format!(
+                "不支持的索引格式版本 {}（支持 {}）",
+                index.schema_version, REGISTRY_SCHEMA_VERSION
+            )));
+        }
+        Ok(index)
+    }
+
+    /// 按插件 ID 查找。
+    pub fn find(&self, plugin_id: &str) -> Option<&RegistryPlugin> {
+        self.plugins.iter().find(|p| p.id == plugin_id)
+    }
+}
+
+impl RegistryPlugin {
+    /// 与指定宿主版本兼容的最高版本；版本号无法解析的条目被忽略。
+    pub fn latest_compatible(&self, host: &Version) -> Option<&RegistryRelease> {
+        self.versions
+            .iter()
+            .filter(|r| r.is_compatible_with(host))
+            .filter_map(|r| Version::parse(&r.version).ok().map(|v| (v, r)))
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, r)| r)
+    }
+}
+
+impl RegistryRelease {
+    /// 该版本是否可在指定宿主版本上运行（minHostVersion 无法解析时视为不兼容）。
+    pub fn is_compatible_with(&self, host: &Version) -> bool {
+        let min = self.min_host_version.trim();
+        if min.is_empty() {
+            return true;
+        }
+        match Version::parse(min) {
+            Ok(required) => *host >= required,
+            Err(e) => {
+                warn!("忽略 minHostVersion 无法解析的版本 {}: {}", self.version, e);
+                false
+            }
+        }
+    }
+}
+
+/// 当前宿主版本（与 plugin-host 校验 manifest.min_host_version 的口径一致）。
+pub fn host_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("CARGO_PKG_VERSION 必须是合法 semver")
+}
+
+/// `candidate` 是否比 `installed` 更新；任一版本号无法解析时不视为更新。
+pub fn is_newer(installed: &str, candidate: &str) -> bool {
+    match (Version::parse(installed), Version::parse(candidate)) {
+        (Ok(installed), Ok(candidate)) => candidate > installed,
+        _ => false,
+    }
+}
+
+// ── 目录与更新检查 ───────────────────────────────────────────────
+
+/// 注册表目录条目 —— 索引中的插件合并本地安装状态后的展示视图。
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryListing {
+    /// 插件 ID。
+    #[serde(rename = "id")]
+    pub id: String,
+    /// 展示名称。
+    #[serde(rename = "name")]
+    pub name: String,
+    /// 简要描述。
+    #[serde(rename = "description")]
+    pub description: String,
+    /// 发布者标识。
+    #[serde(rename = "publisher")]
+    pub publisher: String,
+    /// 与当前宿主兼容的最高版本；None 表示没有可安装的版本。
+    #[serde(rename = "latestVersion")]
+    pub latest_version: Option<String>,
+    /// 本地已安装的版本；None 表示未安装。
+    #[serde(rename = "installedVersion")]
+    pub installed_version: Option<String>,
+    /// 已安装且存在更新的兼容版本。
+    #[serde(rename = "updateAvailable")]
+    pub update_available: bool,
+}
+
+/// 合并索引与已安装插件版本（插件 ID → manifest 版本），生成目录列表。
+pub fn build_catalog(
+    index: &RegistryIndex,
+    installed: &HashMap<String, String>,
+    host: &Version,
+) -> Vec<RegistryListing> {
+    index
+        .plugins
+        .iter()
+        .map(|plugin| {
+            let latest = plugin.latest_compatible(host).map(|r| r.version.clone());
+            let installed_version = installed.get(&plugin.id).cloned();
+            let update_available = match (&installed_version, &latest) {
+                (Some(current), Some(latest)) => is_newer(current, latest),
+                _ => false,
+            };
+            RegistryListing {
+                id: plugin.id.clone(),
+                name: plugin.name.clone(),
+                description: plugin.description.clone(),
+                publisher: plugin.publisher.clone(),
+                latest_version: latest,
+                installed_version,
+                update_available,
+            }
+        })
+        .collect()
+}
+
+// ── 签名与信任库 ─────────────────────────────────────────────────
+
+/// 计算字节序列的 sha256（小写十六进制）。
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 构造发布者签名的消息：域前缀、插件 ID、版本与包 sha256 逐行拼接。
+///
+/// 签名绑定 ID 与版本，防止把某插件（或旧版本）的合法包替换到其他条目下。
+pub fn signing_message(plugin_id: &str, version: &str, sha256_hex: &str) -> Vec<u8> {
+    format!(
+        "{SIGNATURE_DOMAIN}\n{plugin_id}\n{version}\n{}",
+        sha256_hex.to_ascii_lowercase()
+    )
+    .into_bytes()
+}
+
+/// 受信任的发布者公钥集合（发布者 → 公钥列表，允许轮换期间并存多把公钥）。
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    keys: HashMap<String, Vec<VerifyingKey>>,
+}
+
+impl TrustStore {
+    /// 从配置构建信任库；格式无效的公钥记录警告后跳过。
+    pub fn from_keys(keys: &[TrustedKey]) -> Self {
+        let mut store = Self::default();
+        for key in keys {
+            if key.publisher.is_empty() {
+                continue;
+            }
+            match decode_public_key(&key.public_key) {
+                Ok(verifying_key) => store
+                    .keys
+                    .entry(key.publisher.clone())
+                    .or_default()
+                    .push(verifying_key),
+                Err(e) => warn!("忽略发布者 {} 的无效公钥: {}", key.publisher, e),
+            }
+        }
+        store
+    }
+
+    /// 校验插件包：sha256 与索引一致，且签名来自该插件发布者的受信任公钥。
+    pub fn verify(
+        &self,
+        plugin: &RegistryPlugin,
+        release: &RegistryRelease,
+        package: &[u8],
+    ) -> Result<(), RegistryError> {
+        let actual = sha256_hex(package);
+        if !actual.eq_ignore_ascii_case(release.sha256.trim()) {
+            return Err(RegistryError::Integrity {
+                expected: release.sha256.clone(),
+                actual,
+            });
+        }
+
+        let keys = self
+            .keys
+            .get(&plugin.publisher)
+            .ok_or_else(|| RegistryError::UntrustedPublisher(plugin.publisher.clone()))?;
+        let signature_bytes = STANDARD
+            .decode(release.signature.trim())
+            .map_err(|e| RegistryError::BadSignature(e.to_string()))?;
+        let signature = Signature::from_slice(&signature_bytes)
+            .map_err(|e| RegistryError::BadSignature(e.to_string()))?;
+        let message = signing_message(&plugin.id, &release.version, &actual);
+
+        if keys
+            .iter()
+            .any(|key| key.verify_strict(&message, &signature).is_ok())
+        {
+            Ok(())
+        } else {
+            Err(RegistryError::BadSignature(format!(
+                "{} {} 的签名与发布者 {} 的公钥不匹配",
+                plugin.id, release.version, plugin.publisher
+            )))
+        }
+    }
+}
+
+/// 解码 base64 编码的 32 字节 ed25519 公钥。
+fn decode_public_key(encoded: &str) -> Result<VerifyingKey, String> {
+    let bytes = STANDARD.decode(encoded.trim()).map_err(|e| e.to_string())?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| "公钥长度必须为 32 字节".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| e.to_string())
+}
+
+// ── 注册表来源 ───────────────────────────────────────────────────
+
+/// 注册表来源：获取索引与插件包字节。
+#[async_trait]
+pub trait RegistrySource: Send + Sync {
+    /// 获取并解析注册表索引。
+    async fn fetch_index(&self) -> Result<RegistryIndex, RegistryError>;
+
+    /// 下载指定版本的插件包（未校验的原始字节）。
+    async fn fetch_package(&self, release: &RegistryRelease) -> Result<Vec<u8>, RegistryError>;
+}
+
+/// 按配置的索引地址创建来源：http(s) 地址走 HTTP，其余视为本地文件路径。
+pub fn open_source(location: &str) -> Result<Box<dyn RegistrySource>, RegistryError> {
+    let location = location.trim();
+    if location.is_empty() {
+        return Err(RegistryError::NotConfigured);
+    }
+    if location.starts_with("http://") || location.starts_with("https://") {
+        let index_url = Url::parse(location).map_err(|e| RegistryError::Index(e.to_string()))?;
+        return Ok(Box::new(HttpRegistry::new(index_url)));
+    }
+    let path = match Url::parse(location) {
+        Ok(url) if url.scheme() == "file" => url
+            .to_file_path()
+            .map_err(|_| RegistryError::Index(format!("无效的文件地址: {location}")))?,
+        _ => PathBuf::from(location),
+    };
+    Ok(Box::new(FileRegistry::new(path)))
+}
+
+/// 本地文件注册表：索引与插件包都在本地文件系统上，相对 url 按索引所在目录解析。
+pub struct FileRegistry {
+    index_path: PathBuf,
+}
+
+impl FileRegistry {
+    /// 创建文件注册表，参数为索引 JSON 文件路径。
+    pub fn new(index_path: PathBuf) -> Self {
+        Self { index_path }
+    }
+
+    /// 解析插件包路径：绝对路径原样使用，相对路径拼接到索引所在目录。
+    fn resolve(&self, url: &str) -> PathBuf {
+        let path = Path::new(url);
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        self.index_path
+            .parent()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+#[async_trait]
+impl RegistrySource for FileRegistry {
+    async fn fetch_index(&self) -> Result<RegistryIndex, RegistryError> {
+        let bytes = tokio::fs::read(&self.index_path)
+            .await
+            .map_err(|e| RegistryError::Fetch(format!("{}: {}", self.index_path.display(), e)))?;
+        RegistryIndex::parse(&bytes)
+    }
+
+    async fn fetch_package(&self, release: &RegistryRelease) -> Result<Vec<u8>, RegistryError> {
+        let path = self.resolve(&release.url);
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| RegistryError::Fetch(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// HTTP 注册表：相对 url 按索引地址解析（`Url::join` 语义）。
+pub struct HttpRegistry {
+    index_url: Url,
+    client: reqwest::Client,
+}
+
+impl HttpRegistry {
+    /// 创建 HTTP 注册表，参数为索引 JSON 的地址。
+    pub fn new(index_url: Url) -> Self {
+        Self {
+            index_url,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// GET 指定地址并返回响应体，非 2xx 状态视为失败。
+    async fn get_bytes(&self, url: Url) -> Result<Vec<u8>, RegistryError> {
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| RegistryError::Fetch(format!("{url}: {e}")))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| RegistryError::Fetch(format!("{url}: {e}")))?;
+        Ok(bytes.to_vec())
+    }
+}
+
+#[async_trait]
+impl RegistrySource for HttpRegistry {
+    async fn fetch_index(&self) -> Result<RegistryIndex, RegistryError> {
+        let bytes = self.get_bytes(self.index_url.clone()).await?;
+        RegistryIndex::parse(&bytes)
+    }
+
+    async fn fetch_package(&self, release: &RegistryRelease) -> Result<Vec<u8>, RegistryError> {
+        let url = self
+            .index_url
+            .join(&release.url)
+            .map_err(|e| RegistryError::Index(format!("{}: {}", release.url, e)))?;
+        self.get_bytes(url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const PUBLISHER: &str = "example";
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn trusted(key: &SigningKey) -> TrustedKey {
+        TrustedKey {
+            publisher: PUBLISHER.to_string(),
+            public_key: STANDARD.encode(key.verifying_key().as_bytes()),
+        }
+    }
+
+    fn signed_release(
+        key: &SigningKey,
+        id: &str,
+        version: &str,
+        package: &[u8],
+    ) -> RegistryRelease {
+        let sha256 = sha256_hex(package);
+        let signature = key.sign(&signing_message(id, version, &sha256));
+        RegistryRelease {
+            version: version.to_string(),
+            min_host_version: String::new(),
+            url: format!("{id}-{version}.zip"),
+            sha256,
+            signature: STANDARD.encode(signature.to_bytes()),
+        }
+    }
+
+    fn plugin(id: &str, versions: Vec<RegistryRelease>) -> RegistryPlugin {
+        RegistryPlugin {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            publisher: PUBLISHER.to_string(),
+            versions,
+        }
+    }
+
+    #[test]
+    fn verify_accepts_signed_package_and_rejects_tampering() {
+        let key = signing_key(7);
+        let store = TrustStore::from_keys(&[trusted(&key)]);
+        let package = b"zip bytes".to_vec();
+        let release = signed_release(&key, "com.example.a", "1.0.0", &package);
+        let entry = plugin("com.example.a", vec![release.clone()]);
+
+        assert!(store.verify(&entry, &release, &package).is_ok());
+        assert!(matches!(
+            store.verify(&entry, &release, b"tampered"),
+            Err(RegistryError::Integrity { .. })
+        ));
+
+        // sha256 与包一致但签名绑定的是另一个版本：挪用旧版本签名必须失败
+        let mut replayed = release.clone();
+        replayed.version = "2.0.0".to_string();
+        assert!(matches!(
+            store.verify(&entry, &replayed, &package),
+            Err(RegistryError::BadSignature(_))
+        ));
+    }
+
+    #[test]
+    fn verify_requires_trusted_publisher_key() {
+        let key = signing_key(7);
+        let package = b"zip bytes".to_vec();
+        let release = signed_release(&key, "com.example.a", "1.0.0", &package);
+        let entry = plugin("com.example.a", vec![release.clone()]);
+
+        let empty = TrustStore::default();
+        assert!(matches!(
+            empty.verify(&entry, &release, &package),
+            Err(RegistryError::UntrustedPublisher(_))
+        ));
+
+        let other = TrustStore::from_keys(&[trusted(&signing_key(9))]);
+        assert!(matches!(
+            other.verify(&entry, &release, &package),
+            Err(RegistryError::BadSignature(_))
+        ));
+
+        // 轮换期间旧公钥与新公钥并存，任一匹配即通过
+        let rotated = TrustStore::from_keys(&[trusted(&signing_key(9)), trusted(&key)]);
+        assert!(rotated.verify(&entry, &release, &package).is_ok());
+    }
+
+    #[test]
+    fn latest_compatible_respects_min_host_version() {
+        let key = signing_key(1);
+        let mut old = signed_release(&key, "com.example.a", "1.2.0", b"a");
+        old.min_host_version = "0.1.0".to_string();
+        let mut newer = signed_release(&key, "com.example.a", "1.10.0", b"b");
+        newer.min_host_version = "1.0.0".to_string();
+        let mut future = signed_release(&key, "com.example.a", "2.0.0", b"c");
+        future.min_host_version = "99.0.0".to_string();
+        let entry = plugin("com.example.a", vec![old, future, newer]);
+
+        let host = Version::new(1, 5, 0);
+        assert_eq!(
+            entry.latest_compatible(&host).map(|r| r.version.as_str()),
+            Some("1.10.0")
+        );
+        let old_host = Version::new(0, 5, 0);
+        assert_eq!(
+            entry
+                .latest_compatible(&old_host)
+                .map(|r| r.version.as_str()),
+            Some("1.2.0")
+        );
+    }
+
+    #[test]
+    fn catalog_flags_updates_against_installed_versions() {
+        let key = signing_key(1);
+        let index = RegistryIndex {
+            schema_version: REGISTRY_SCHEMA_VERSION,
+            plugins: vec![
+                plugin(
+                    "com.example.a",
+                    vec![signed_release(&key, "com.example.a", "1.1.0", b"a")],
+                ),
+                plugin(
+                    "com.example.b",
+                    vec![signed_release(&key, "com.example.b", "0.3.0", b"b")],
+                ),
+                plugin(
+                    "com.example.c",
+                    vec![signed_release(&key, "com.example.c", "1.0.0", b"c")],
+                ),
+            ],
+        };
+        let installed = HashMap::from([
+            ("com.example.a".to_string(), "1.0.0".to_string()),
+            ("com.example.b".to_string(), "0.3.0".to_string()),
+        ]);
+
+        let catalog = build_catalog(&index, &installed, &Version::new(1, 0, 0));
+        let flags: Vec<(&str, bool, Option<&str>)> = catalog
+            .iter()
+            .map(|l| {
+                (
+                    l.id.as_str(),
+                    l.update_available,
+                    l.installed_version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("com.example.a", true, Some("1.0.0")),
+                ("com.example.b", false, Some("0.3.0")),
+                ("com.example.c", false, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn file_registry_resolves_packages_relative_to_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = signing_key(3);
+        let package = b"package contents".to_vec();
+        let release = signed_release(&key, "com.example.a", "1.0.0", &package);
+        std::fs::create_dir_all(dir.path().join("packages")).unwrap();
+        std::fs::write(dir.path().join("packages/a.zip"), &package).unwrap();
+
+        let index = RegistryIndex {
+            schema_version: REGISTRY_SCHEMA_VERSION,
+            plugins: vec![plugin(
+                "com.example.a",
+                vec![RegistryRelease {
+                    url: "packages/a.zip".to_string(),
+                    ..release
+                }],
+            )],
+        };
+        let index_path = dir.path().join("index.json");
+        std::fs::write(&index_path, serde_json::to_vec(&index).unwrap()).unwrap();
+
+        let source = open_source(index_path.to_str().unwrap()).unwrap();
+        let fetched = source.fetch_index().await.unwrap();
+        let entry = fetched.find("com.example.a").unwrap();
+        let release = &entry.versions[0];
+        let bytes = source.fetch_package(release).await.unwrap();
+
+        let store = TrustStore::from_keys(&[trusted(&key)]);
+        assert!(store.verify(entry, release, &bytes).is_ok());
+    }
+
+    #[test]
+    fn index_rejects_unknown_schema_version() {
+        let json = br#"{"schemaVersion": 2, "plugins": []}"#;
+        assert!(matches!(
+            RegistryIndex::parse(json),
+            Err(RegistryError::Index(_))
+        ));
+        assert!(matches!(
+            open_source("  "),
+            Err(RegistryError::NotConfigured)
+        ));
+    }
+}
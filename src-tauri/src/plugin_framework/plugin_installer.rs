@@ -8,7 +8,7 @@ use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use regex::Regex;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 use zerolaunch_plugin_protocol::manifest::PLUGIN_ID_RE;
 use zerolaunch_plugin_protocol::Manifest;
@@ -50,30 +50,7 @@ impl PluginInstaller {
     pub(crate) fn install_from_zip(&self, zip_path: &Path) -> Result<PathBuf, InstallError> {
         let file = std::fs::File::open(zip_path)?;
         let mut archive = zip::ZipArchive::new(file)?;
-
-        // 第一遍：找 manifest + 收集所有条目名（用于计算公共前缀）
-        let mut manifest_content = String::new();
-        let mut find_manifest = false;
-        let mut names = Vec::new();
-        for i in 0..archive.len() {
-            let mut entry = archive.by_index(i)?;
-            let name = entry.name().to_string();
-            if name == "manifest.toml" {
-                entry.read_to_string(&mut manifest_content)?;
-                find_manifest = true;
-            }
-            names.push(name);
-        }
-
-        if !find_manifest {
-            return Err(InstallError::Manifest(format!(
-                "manifest.toml not found in zip: {}",
-                zip_path.to_string_lossy()
-            )));
-        }
-
-        let manifest: Manifest = toml::from_str(&manifest_content)
-            .map_err(|e| InstallError::Manifest(format!("invalid manifest: {}", e)))?;
+        let (manifest, names) = read_zip_manifest(&mut archive, zip_path)?;
 
         let plugin_id = &manifest.plugin.id;
         // 先用反向域名正则校验 ID 再拼装目标目录：恶意 ID（如含 `/`/`..`）会让
@@ -85,77 +62,168 @@ impl PluginInstaller {
         if target_dir.exists() {
             return Err(InstallError::AlreadyInstalled(plugin_id.clone()));
         }
+        log_granted_permissions(&manifest);
 
-        std::fs::create_dir_all(&target_dir)?;
+        extract_zip(&mut archive, &names, &target_dir)?;
+        verify_install_dir(&target_dir)?;
 
-        let common_prefix = find_common_prefix(&names);
+        info!(
+            "Installed plugin {} from {} to {}",
+            plugin_id,
+            zip_path.display(),
+            target_dir.display()
+        );
 
-        // 第二遍：解压所有文件
-        for i in 0..archive.len() {
-            let mut entry = archive.by_index(i)?;
-            let name = entry.name().to_string();
+        Ok(target_dir)
+    }
 
-            let relative = if let Some(rest) = name.strip_prefix(&common_prefix) {
-                let trimmed = rest.trim_start_matches('/');
-                if trimmed.is_empty() {
-                    continue;
-                }
-                trimmed
-            } else {
-                &name
-            };
+    /// 将 .zip 解压到暂存目录 `plugins_dir/.staging/<plugin_id>/`，供升级时原子替换。
+    ///
+    /// 不检查插件是否已安装；同 ID 的残留暂存目录会先被清除。
+    /// 返回解析出的 manifest 与暂存目录。
+    pub(crate) fn stage_from_zip(
+        &self,
+        zip_path: &Path,
+    ) -> Result<(Manifest, PathBuf), InstallError> {
+        let file = std::fs::File::open(zip_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        let (manifest, names) = read_zip_manifest(&mut archive, zip_path)?;
+        validate_plugin_id(&manifest.plugin.id)?;
 
-            if relative.is_empty() {
-                continue;
-            }
+        let staged_dir = self.staging_dir().join(&manifest.plugin.id);
+        if staged_dir.exists() {
+            std::fs::remove_dir_all(&staged_dir)?;
+        }
+        if let Err(e) = extract_zip(&mut archive, &names, &staged_dir)
+            .and_then(|_| verify_install_dir(&staged_dir))
+        {
+            let _ = std::fs::remove_dir_all(&staged_dir);
+            return Err(e);
+        }
+        log_granted_permissions(&manifest);
 
-            let normalized = std::path::Path::new(relative);
-            if normalized.is_absolute() {
-                return Err(InstallError::Manifest("absolute path in zip".into()));
-            }
-            for c in normalized.components() {
-                if matches!(c, std::path::Component::ParentDir) {
-                    return Err(InstallError::Manifest("parent-dir traversal in zip".into()));
-                }
-            }
+        Ok((manifest, staged_dir))
+    }
 
-            let out_path = target_dir.join(relative);
-            if let Some(parent) = out_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
+    /// 已安装插件的目录 `plugins_dir/<plugin_id>`。
+    pub(crate) fn plugin_dir(&self, plugin_id: &str) -> PathBuf {
+        self.plugins_dir.join(plugin_id)
+    }
+
+    /// 暂存目录根（`plugins_dir/.staging`），下载的插件包与解压结果都放在这里。
+    ///
+    /// 以 `.` 开头且不含 manifest.toml，`scan_plugins_dir` 不会把它当作插件。
+    pub(crate) fn staging_dir(&self) -> PathBuf {
+        self.plugins_dir.join(".staging")
+    }
 
-            if entry.is_dir() {
-                std::fs::create_dir_all(&out_path)?;
-            } else {
-                let mut out_file = std::fs::File::create(&out_path)?;
-                std::io::copy(&mut entry, &mut out_file)?;
+    /// 升级期间旧版本的备份目录 `plugins_dir/.backup/<plugin_id>`。
+    fn backup_dir(&self, plugin_id: &str) -> PathBuf {
+        self.plugins_dir.join(".backup").join(plugin_id)
+    }
+
+    /// 用暂存目录替换已安装的插件目录：旧目录先移到备份位置，再把暂存目录移入。
+    ///
+    /// 两步都是同一文件系统内的 rename；第二步失败时把备份移回原位。
+    /// 成功后调用方需在新版本加载成功时 `discard_backup`，失败时 `rollback_upgrade`。
+    pub(crate) fn swap_in_staged(
+        &self,
+        plugin_id: &str,
+        staged_dir: &Path,
+    ) -> Result<PathBuf, InstallError> {
+        validate_plugin_id(plugin_id)?;
+        let target_dir = self.plugins_dir.join(plugin_id);
+        let backup_dir = self.backup_dir(plugin_id);
+
+        if backup_dir.exists() {
+            std::fs::remove_dir_all(&backup_dir)?;
+        }
+        if let Some(parent) = backup_dir.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let had_previous = target_dir.exists();
+        if had_previous {
+            std::fs::rename(&target_dir, &backup_dir)?;
+        }
+        if let Err(e) = std::fs::rename(staged_dir, &target_dir) {
+            if had_previous {
+                std::fs::rename(&backup_dir, &target_dir)?;
             }
+            return Err(e.into());
         }
 
-        verify_install_dir(&target_dir)?;
-
         info!(
-            "Installed plugin {} from {} to {}",
+            "插件 {} 的新版本已替换到 {}",
             plugin_id,
-            zip_path.display(),
             target_dir.display()
         );
+        Ok(target_dir)
+    }
 
+    /// 将暂存目录移入 `plugins_dir/<plugin_id>/` 完成新装（注册表安装路径）。
+    ///
+    /// 目标目录已存在时返回 AlreadyInstalled，暂存目录由调用方清理。
+    pub(crate) fn install_staged(
+        &self,
+        plugin_id: &str,
+        staged_dir: &Path,
+    ) -> Result<PathBuf, InstallError> {
+        validate_plugin_id(plugin_id)?;
+        let target_dir = self.plugins_dir.join(plugin_id);
+        if target_dir.exists() {
+            return Err(InstallError::AlreadyInstalled(plugin_id.to_string()));
+        }
+        std::fs::rename(staged_dir, &target_dir)?;
+        info!("插件 {} 已安装到 {}", plugin_id, target_dir.display());
         Ok(target_dir)
     }
 
-    /// 从目录复制安装插件到 `plugins_dir/<plugin_id>/`。
-    pub(crate) fn install_from_dir(&self, source_dir: &Path) -> Result<PathBuf, InstallError> {
-        let manifest_path = source_dir.join("manifest.toml");
-        if !manifest_path.exists() {
-            return Err(InstallError::Manifest(
-                "manifest.toml not found in source directory".into(),
-            ));
+    /// 撤销 `swap_in_staged`：删除新版本目录并把备份移回原位。
+    pub(crate) fn rollback_upgrade(&self, plugin_id: &str) -> Result<PathBuf, InstallError> {
+        validate_plugin_id(plugin_id)?;
+        let target_dir = self.plugins_dir.join(plugin_id);
+        let backup_dir = self.backup_dir(plugin_id);
+        if !backup_dir.exists() {
+            return Err(InstallError::Manifest(format!(
+                "插件 {plugin_id} 没有可回滚的备份"
+            )));
+        }
+        if target_dir.exists() {
+            std::fs::remove_dir_all(&target_dir)?;
         }
+        std::fs::rename(&backup_dir, &target_dir)?;
+
+        info!("插件 {} 已回滚到备份版本", plugin_id);
+        Ok(target_dir)
+    }
 
-        let manifest_content = std::fs::read_to_string(&manifest_path)?;
-        let manifest: Manifest = toml::from_str(&manifest_content)
-            .map_err(|e| InstallError::Manifest(format!("invalid manifest: {}", e)))?;
+    /// 升级成功后删除旧版本备份（失败仅记录日志，不影响已完成的升级）。
+    pub(crate) fn discard_backup(&self, plugin_id: &str) {
+        let backup_dir = self.backup_dir(plugin_id);
+        if backup_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&backup_dir) {
+                warn!("删除插件 {} 的升级备份失败: {}", plugin_id, e);
+            }
+        }
+    }
+
+    /// 读取待安装插件包（.zip 或含 manifest.toml 的目录）的 manifest，不落盘。
+    /// 供安装前向用户展示插件身份与申请的宿主权限。
+    pub(crate) fn inspect(&self, source_path: &Path) -> Result<Manifest, InstallError> {
+        let manifest = if source_path.is_dir() {
+            read_dir_manifest(source_path)?
+        } else {
+            let file = std::fs::File::open(source_path)?;
+            let mut archive = zip::ZipArchive::new(file)?;
+            read_zip_manifest(&mut archive, source_path)?.0
+        };
+        validate_plugin_id(&manifest.plugin.id)?;
+        Ok(manifest)
+    }
+
+    /// 从目录复制安装插件到 `plugins_dir/<plugin_id>/`。
+    pub(crate) fn install_from_dir(&self, source_dir: &Path) -> Result<PathBuf, InstallError> {
+        let manifest = read_dir_manifest(source_dir)?;
 
         let plugin_id = &manifest.plugin.id;
         // 先用反向域名正则校验 ID 再拼装目标目录：恶意 ID（如含 `/`/`..`）会让
@@ -167,6 +235,7 @@ impl PluginInstaller {
         if target_dir.exists() {
             return Err(InstallError::AlreadyInstalled(plugin_id.clone()));
         }
+        log_granted_permissions(&manifest);
 
         copy_dir_recursive(source_dir, &target_dir)?;
         verify_install_dir(&target_dir)?;
@@ -177,6 +246,25 @@ impl PluginInstaller {
 
 // ── 私有辅助函数 ─────────────────────────────────────────────────
 
+/// 安装时记录 manifest 授予的宿主权限（未声明的 host/* 调用在运行时被拒绝）。
+fn log_granted_permissions(manifest: &Manifest) {
+    let granted: Vec<&str> = manifest
+        .permissions
+        .granted()
+        .into_iter()
+        .map(|p| p.key())
+        .collect();
+    if granted.is_empty() {
+        info!("插件 {} 未申请任何宿主权限", manifest.plugin.id);
+    } else {
+        info!(
+            "插件 {} 已授予宿主权限: {}",
+            manifest.plugin.id,
+            granted.join(", ")
+        );
+    }
+}
+
 /// 校验插件 ID 符合反向域名正则（与 plugin-host validate_manifest 同源）。
 ///
 /// 必须在 `plugins_dir.join(plugin_id)` 之前调用：install 路径在 load 阶段
@@ -191,6 +279,103 @@ fn validate_plugin_id(id: &str) -> Result<(), InstallError> {
     Ok(())
 }
 
+/// 读取并解析插件目录根下的 manifest.toml。
+fn read_dir_manifest(source_dir: &Path) -> Result<Manifest, InstallError> {
+    let manifest_path = source_dir.join("manifest.toml");
+    if !manifest_path.exists() {
+        return Err(InstallError::Manifest(
+            "manifest.toml not found in source directory".into(),
+        ));
+    }
+
+    let manifest_content = std::fs::read_to_string(&manifest_path)?;
+    toml::from_str(&manifest_content)
+        .map_err(|e| InstallError::Manifest(format!("invalid manifest: {}", e)))
+}
+
+/// 第一遍扫描 zip：解析 manifest.toml 并收集所有条目名（用于计算公共前缀）。
+fn read_zip_manifest(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    zip_path: &Path,
+) -> Result<(Manifest, Vec<String>), InstallError> {
+    let mut manifest_content = String::new();
+    let mut find_manifest = false;
+    let mut names = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+        if name == "manifest.toml" {
+            entry.read_to_string(&mut manifest_content)?;
+            find_manifest = true;
+        }
+        names.push(name);
+    }
+
+    if !find_manifest {
+        return Err(InstallError::Manifest(format!(
+            "manifest.toml not found in zip: {}",
+            zip_path.to_string_lossy()
+        )));
+    }
+
+    let manifest: Manifest = toml::from_str(&manifest_content)
+        .map_err(|e| InstallError::Manifest(format!("invalid manifest: {}", e)))?;
+    Ok((manifest, names))
+}
+
+/// 第二遍：剥掉公共前缀后把所有条目解压到 `target_dir`，拒绝绝对路径与 `..`。
+fn extract_zip(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    names: &[String],
+    target_dir: &Path,
+) -> Result<(), InstallError> {
+    std::fs::create_dir_all(target_dir)?;
+
+    let common_prefix = find_common_prefix(names);
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+
+        let relative = if let Some(rest) = name.strip_prefix(&common_prefix) {
+            let trimmed = rest.trim_start_matches('/');
+            if trimmed.is_empty() {
+                continue;
+            }
+            trimmed
+        } else {
+            &name
+        };
+
+        if relative.is_empty() {
+            continue;
+        }
+
+        let normalized = std::path::Path::new(relative);
+        if normalized.is_absolute() {
+            return Err(InstallError::Manifest("absolute path in zip".into()));
+        }
+        for c in normalized.components() {
+            if matches!(c, std::path::Component::ParentDir) {
+                return Err(InstallError::Manifest("parent-dir traversal in zip".into()));
+            }
+        }
+
+        let out_path = target_dir.join(relative);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out_path)?;
+        } else {
+            let mut out_file = std::fs::File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut out_file)?;
+        }
+    }
+    Ok(())
+}
+
 /// 校验安装目录内无符号链接和路径遍历（使用 walkdir 递归遍历）。
 fn verify_install_dir(target_dir: &Path) -> Result<(), InstallError> {
     let canonical_target = target_dir
@@ -274,7 +459,9 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::validate_plugin_id;
+    use super::{validate_plugin_id, PluginInstaller};
+    use std::io::Write;
+    use std::path::Path;
 
     #[test]
     fn validate_plugin_id_accepts_reverse_domain() {
@@ -292,4 +479,99 @@ mod tests {
         assert!(validate_plugin_id("no-dots").is_err());
         assert!(validate_plugin_id("Uppercase.plugin").is_err());
     }
+
+    /// 写一个最小插件包：根目录下的 manifest.toml 与记录版本号的 marker.txt。
+    fn write_plugin_zip(path: &Path, id: &str, version: &str) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("manifest.toml", options).unwrap();
+        write!(
+            zip,
+            "[plugin]\nid = \"{id}\"\nname = \"t\"\nversion = \"{version}\"\n\
+             description = \"\"\nauthor = \"\"\nminHostVersion = \"0.1.0\"\n"
+        )
+        .unwrap();
+        zip.start_file("marker.txt", options).unwrap();
+        zip.write_all(version.as_bytes()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    /// 安装前预览：zip 与目录均可解析 manifest（含权限段），且不在插件目录落盘。
+    #[test]
+    fn inspect_reads_manifest_without_installing() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        let installer = PluginInstaller::new(plugins_dir.clone());
+
+        let zip_path = dir.path().join("demo.zip");
+        write_plugin_zip(&zip_path, "com.example.demo", "1.2.0");
+        let manifest = installer.inspect(&zip_path).unwrap();
+        assert_eq!(manifest.plugin.id, "com.example.demo");
+        assert_eq!(manifest.plugin.version, "1.2.0");
+        assert!(manifest.permissions.granted().is_empty());
+
+        let source = dir.path().join("source");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(
+            source.join("manifest.toml"),
+            "[plugin]\nid = \"com.example.shell\"\nname = \"t\"\nversion = \"0.1.0\"\n\
+             description = \"\"\nauthor = \"\"\nminHostVersion = \"0.1.0\"\n\
+             [permissions]\nshell = true\nresources = true\n",
+        )
+        .unwrap();
+        let manifest = installer.inspect(&source).unwrap();
+        let keys: Vec<&str> = manifest
+            .permissions
+            .granted()
+            .into_iter()
+            .map(|p| p.key())
+            .collect();
+        assert_eq!(keys, vec!["shell", "resources"]);
+        assert!(!plugins_dir.exists());
+
+        // 非法 ID 在预览阶段即被拒绝
+        std::fs::write(
+            source.join("manifest.toml"),
+            "[plugin]\nid = \"../evil\"\nname = \"t\"\nversion = \"0.1.0\"\n\
+             description = \"\"\nauthor = \"\"\nminHostVersion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        assert!(installer.inspect(&source).is_err());
+    }
+
+    #[test]
+    fn staged_upgrade_swaps_and_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+        let installer = PluginInstaller::new(plugins_dir.clone());
+        let id = "com.example.demo";
+        let marker = plugins_dir.join(id).join("marker.txt");
+
+        let v1 = dir.path().join("v1.zip");
+        write_plugin_zip(&v1, id, "1.0.0");
+        installer.install_from_zip(&v1).unwrap();
+
+        let v2 = dir.path().join("v2.zip");
+        write_plugin_zip(&v2, id, "2.0.0");
+        let (manifest, staged) = installer.stage_from_zip(&v2).unwrap();
+        assert_eq!(manifest.plugin.version, "2.0.0");
+        // 暂存目录不应被扫描为已安装插件
+        assert_eq!(installer.scan_plugins_dir().len(), 1);
+
+        installer.swap_in_staged(id, &staged).unwrap();
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "2.0.0");
+
+        installer.rollback_upgrade(id).unwrap();
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "1.0.0");
+        assert!(installer.rollback_upgrade(id).is_err());
+
+        let (_, staged) = installer.stage_from_zip(&v2).unwrap();
+        installer.swap_in_staged(id, &staged).unwrap();
+        installer.discard_backup(id);
+        assert_eq!(std::fs::read_to_string(&marker).unwrap(), "2.0.0");
+        assert!(installer.rollback_upgrade(id).is_err());
+        assert_eq!(installer.scan_plugins_dir().len(), 1);
+    }
 }
@@ -0,0 +1,331 @@
+//! 多搜索引擎分数融合。
+//!
+//! 不同引擎的分数量纲差异很大（launchy 常在万级，skim 在百级），不能直接相加：
+//! 加权求和与取最大值先把各引擎分数除以该引擎本次查询的最高分归一化到 [0, 1]，
+//! RRF 只使用各引擎内的名次；融合值最后乘以主引擎（第一个引擎）的最高分，
+//! 使融合分与单引擎时处于同一量级，下游分数增强器的加分幅度不因启用多引擎而失衡。
+
+use std::collections::{HashMap, HashSet};
+
+use zerolaunch_plugin_api::{CandidateId, ScoreDetail, ScoreDetailKind, ScoredCandidate};
+
+use crate::core::config::FusionStrategy;
+
+/// 单个引擎的打分结果及其融合权重。
+pub(crate) struct EngineScores {
+    /// 引擎 component_id，用作明细描述前缀
+    pub engine_id: String,
+    /// 融合权重（≥ 0）
+    pub weight: f64,
+    /// 该引擎对全部候选项的打分
+    pub scores: Vec<ScoredCandidate>,
+}
+
+/// 某候选项在单个引擎中的归一化值与原始结果。
+struct EngineHit<'a> {
+    /// 归一化值：加权求和/取最大值为 score/最高分，RRF 为 (k+1)/(k+名次)，均落在 [0, 1]
+    value: f64,
+    /// 引擎原始打分结果
+    scored: &'a ScoredCandidate,
+}
+
+/// 按策略融合多个引擎的打分结果。
+/// 参数：strategy - 融合策略；rrf_k - RRF 平滑常数；results - 各引擎结果（第一个为主引擎）。
+/// 返回：融合后的打分，顺序同主引擎结果（仅出现在其他引擎中的候选项追加在后）。
+///       融合分以加法项记入明细，各引擎原始分与明细以说明项保留。
+pub(crate) fn fuse(
+    strategy: FusionStrategy,
+    rrf_k: u32,
+    mut results: Vec<EngineScores>,
+) -> Vec<ScoredCandidate> {
+    if results.len() == 1 {
+        return results.pop().map(|r| r.scores).unwrap_or_default();
+    }
+    let Some(primary) = results.first() else {
+        return Vec::new();
+    };
+    let anchor = top_score(&primary.scores);
+    let anchor = if anchor > 0.0 { anchor } else { 1.0 };
+
+    // 权重全为 0 时视为等权，避免融合分恒为 0
+    let weight_sum: f64 = results.iter().map(|r| r.weight).sum();
+    let weights: Vec<f64> = if weight_sum > 0.0 {
+        results.iter().map(|r| r.weight).collect()
+    } else {
+        vec![1.0; results.len()]
+    };
+
+    let per_engine: Vec<HashMap<CandidateId, EngineHit>> = results
+        .iter()
+        .map(|r| normalize(strategy, rrf_k, &r.scores))
+        .collect();
+
+    let mut seen: HashSet<CandidateId> = HashSet::new();
+    let order: Vec<CandidateId> = results
+        .iter()
+        .flat_map(|r| r.scores.iter().map(|s| s.candidate_id))
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    order
+        .into_iter()
+        .map(|candidate_id| {
+            let hits: Vec<Option<&EngineHit>> =
+                per_engine.iter().map(|m| m.get(&candidate_id)).collect();
+            let mut detailed_score = match strategy {
+                FusionStrategy::WeightedSum | FusionStrategy::ReciprocalRank => {
+                    sum_details(strategy, &results, &weights, &hits, anchor)
+                }
+                FusionStrategy::Max => max_details(&results, &weights, &hits, anchor),
+            };
+            let score = detailed_score.iter().map(|d| d.score * d.weight).sum();
+            detailed_score.extend(info_details(&results, &hits));
+            ScoredCandidate {
+                candidate_id,
+                score,
+                detailed_score,
+            }
+        })
+        .collect()
+}
+
+/// 结果中的最高分（无结果时为 0）。
+fn top_score(scores: &[ScoredCandidate]) -> f64 {
+    scores.iter().map(|s| s.score).fold(0.0, f64::max)
+}
+
+/// 计算单个引擎内各候选项的归一化值。
+fn normalize(
+    strategy: FusionStrategy,
+    rrf_k: u32,
+    scores: &[ScoredCandidate],
+) -> HashMap<CandidateId, EngineHit<'_>> {
+    match strategy {
+        FusionStrategy::WeightedSum | FusionStrategy::Max => {
+            let top = top_score(scores);
+            scores
+                .iter()
+                .map(|scored| {
+                    let value = if top > 0.0 {
+                        (scored.score / top).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    (scored.candidate_id, EngineHit { value, scored })
+                })
+                .collect()
+        }
+        FusionStrategy::ReciprocalRank => {
+            // 只有正分参与排名：未命中的候选项不应因名次获得融合分
+            let mut ranked: Vec<&ScoredCandidate> = scores.iter().collect();
+            ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+            let k = f64::from(rrf_k);
+            ranked
+                .into_iter()
+                .enumerate()
+                .map(|(index, scored)| {
+                    let value = if scored.score > 0.0 {
+                        (k + 1.0) / (k + index as f64 + 1.0)
+                    } else {
+                        0.0
+                    };
+                    (scored.candidate_id, EngineHit { value, scored })
+                })
+                .collect()
+        }
+    }
+}
+
+/// 加权求和 / RRF：每个引擎一条加法项，分值为锚定后的归一化值，权重为归一化后的引擎权重。
+fn sum_details(
+    strategy: FusionStrategy,
+    results: &[EngineScores],
+    weights: &[f64],
+    hits: &[Option<&EngineHit>],
+    anchor: f64,
+) -> Vec<ScoreDetail> {
+    let weight_sum: f64 = weights.iter().sum();
+    let label = match strategy {
+        FusionStrategy::ReciprocalRank => "RRF 融合分",
+        _ => "加权融合分",
+    };
+    results
+        .iter()
+        .zip(weights)
+        .zip(hits)
+        .map(|((result, weight), hit)| ScoreDetail {
+            score: anchor * hit.map(|h| h.value).unwrap_or(0.0),
+            weight: weight / weight_sum,
+            description: format!("[{}] {}", result.engine_id, label),
+            kind: ScoreDetailKind::Add,
+        })
+        .collect()
+}
+
+/// 取最大值：只保留加权归一化值最高的引擎，作为唯一一条加法项。
+fn max_details(
+    results: &[EngineScores],
+    weights: &[f64],
+    hits: &[Option<&EngineHit>],
+    anchor: f64,
+) -> Vec<ScoreDetail> {
+    let max_weight = weights.iter().copied().fold(0.0, f64::max);
+    let best = results
+        .iter()
+        .zip(weights)
+        .zip(hits)
+        .map(|((result, weight), hit)| {
+            let value = hit.map(|h| h.value).unwrap_or(0.0) * weight / max_weight;
+            (result, value)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    best.map(|(result, value)| ScoreDetail {
+        score: anchor * value,
+        weight: 1.0,
+        description: format!("[{}] 最大值融合分", result.engine_id),
+        kind: ScoreDetailKind::Add,
+    })
+    .into_iter()
+    .collect()
+}
+
+/// 各引擎的原始分与原始明细，统一标记为说明项并加上引擎前缀。
+fn info_details(results: &[EngineScores], hits: &[Option<&EngineHit>]) -> Vec<ScoreDetail> {
+    let mut details = Vec::new();
+    for (result, hit) in results.iter().zip(hits) {
+        let Some(hit) = hit else {
+            continue;
+        };
+        details.push(ScoreDetail {
+            score: hit.scored.score,
+            weight: 1.0,
+            description: format!("[{}] 引擎分", result.engine_id),
+            kind: ScoreDetailKind::Info,
+        });
+        details.extend(hit.scored.detailed_score.iter().map(|d| ScoreDetail {
+            score: d.score,
+            weight: d.weight,
+            description: format!("[{}] {}", result.engine_id, d.description),
+            kind: ScoreDetailKind::Info,
+        }));
+    }
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造一个引擎结果：scores[i] 为候选项 i 的分数，附带一条引擎内部明细。
+    fn engine(engine_id: &str, weight: f64, scores: &[f64]) -> EngineScores {
+        EngineScores {
+            engine_id: engine_id.to_string(),
+            weight,
+            scores: scores
+                .iter()
+                .enumerate()
+                .map(|(i, score)| ScoredCandidate {
+                    candidate_id: i as CandidateId,
+                    score: *score,
+                    detailed_score: vec![ScoreDetail {
+                        score: *score,
+                        weight: 1.0,
+                        description: "匹配分".to_string(),
+                        kind: ScoreDetailKind::Add,
+                    }],
+                })
+                .collect(),
+        }
+    }
+
+    /// 按分数降序返回候选项 ID。
+    fn ranking(scores: &[ScoredCandidate]) -> Vec<CandidateId> {
+        let mut sorted: Vec<&ScoredCandidate> = scores.iter().collect();
+        sorted.sort_by(|a, b| b.score.total_cmp(&a.score));
+        sorted.iter().map(|s| s.candidate_id).collect()
+    }
+
+    /// 单引擎时原样返回，不添加融合明细。
+    #[test]
+    fn single_engine_passes_through() {
+        let fused = fuse(
+            FusionStrategy::WeightedSum,
+            60,
+            vec![engine("a", 2.0, &[10.0, 5.0])],
+        );
+        assert_eq!(fused.len(), 2);
+        assert_eq!(fused[0].score, 10.0);
+        assert_eq!(fused[0].detailed_score.len(), 1);
+    }
+
+    /// 加权求和：量纲不同的引擎按最高分归一化后融合，加法项之和等于总分，原始分保留为说明项。
+    #[test]
+    fn weighted_sum_normalizes_engine_scales() {
+        let fused = fuse(
+            FusionStrategy::WeightedSum,
+            60,
+            vec![
+                engine("a", 1.0, &[10000.0, 8000.0, 0.0]),
+                engine("b", 3.0, &[10.0, 100.0, 0.0]),
+            ],
+        );
+        // 候选项 1：(0.8 × 1 + 1.0 × 3) / 4 × 10000 = 9500；候选项 0：(1 + 0.1 × 3) / 4 × 10000 = 3250
+        assert!((fused[1].score - 9500.0).abs() < 1e-6);
+        assert!((fused[0].score - 3250.0).abs() < 1e-6);
+        assert_eq!(fused[2].score, 0.0);
+
+        let added: f64 = fused[1]
+            .detailed_score
+            .iter()
+            .filter(|d| d.kind == ScoreDetailKind::Add)
+            .map(|d| d.score * d.weight)
+            .sum();
+        assert!((added - fused[1].score).abs() < 1e-6);
+        assert!(fused[1]
+            .detailed_score
+            .iter()
+            .any(|d| d.kind == ScoreDetailKind::Info && d.description == "[b] 引擎分"));
+        assert!(fused[1]
+            .detailed_score
+            .iter()
+            .any(|d| d.kind == ScoreDetailKind::Info && d.description == "[a] 匹配分"));
+    }
+
+    /// 取最大值：每个候选项只采用加权归一化值最高的引擎。
+    #[test]
+    fn max_picks_best_engine_per_candidate() {
+        let fused = fuse(
+            FusionStrategy::Max,
+            60,
+            vec![
+                engine("a", 1.0, &[100.0, 20.0]),
+                engine("b", 1.0, &[1.0, 10.0]),
+            ],
+        );
+        assert!((fused[0].score - 100.0).abs() < 1e-6);
+        assert!((fused[1].score - 100.0).abs() < 1e-6);
+        let added: Vec<&ScoreDetail> = fused[1]
+            .detailed_score
+            .iter()
+            .filter(|d| d.kind == ScoreDetailKind::Add)
+            .collect();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].description, "[b] 最大值融合分");
+    }
+
+    /// RRF：只看名次、与分数量纲无关；未命中（0 分）的候选项不得分。
+    #[test]
+    fn reciprocal_rank_uses_ranks_only() {
+        let fused = fuse(
+            FusionStrategy::ReciprocalRank,
+            60,
+            vec![
+                engine("a", 1.0, &[10000.0, 9000.0, 5000.0, 0.0]),
+                engine("b", 1.0, &[10.0, 1.0, 20.0, 0.0]),
+            ],
+        );
+        // 名次：候选项 0 为 1、2；候选项 2 为 3、1；候选项 1 为 2、3
+        assert_eq!(ranking(&fused), vec![0, 2, 1, 3]);
+        assert_eq!(fused[3].score, 0.0);
+    }
+}
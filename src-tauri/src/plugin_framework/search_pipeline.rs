@@ -1,26 +1,75 @@
 use std::sync::Arc;
+use zerolaunch_plugin_api::services::ParameterSnapshot;
 use zerolaunch_plugin_api::{
     CachedCandidateData, CandidateId, ScoreBooster, ScoredCandidate, SearchEngine,
 };
+
+use crate::core::config::{FusionStrategy, SearchFusionSettings};
+
+use super::search_fusion::{fuse, EngineScores};
+
+/// 参与融合的搜索引擎及其权重。
+pub struct WeightedEngine {
+    /// 搜索引擎
+    pub engine: Arc<dyn SearchEngine>,
+    /// 融合权重（≥ 0）
+    pub weight: f64,
+}
+
 pub struct SearchPipeline {
-    engine: Arc<dyn SearchEngine>,
+    /// 启用的搜索引擎（第一个为主引擎，融合分按其量级锚定）
+    engines: Vec<WeightedEngine>,
+    /// 多引擎时的融合策略
+    strategy: FusionStrategy,
+    /// RRF 平滑常数
+    rrf_k: u32,
     boosters: Vec<Arc<dyn ScoreBooster>>,
     top_k: usize,
 }
 
 impl SearchPipeline {
+    /// 创建单引擎搜索管道。
     pub fn new(
         engine: Arc<dyn SearchEngine>,
         boosters: Vec<Arc<dyn ScoreBooster>>,
         top_k: usize,
+    ) -> Self {
+        Self::with_engines(
+            vec![WeightedEngine {
+                engine,
+                weight: 1.0,
+            }],
+            &SearchFusionSettings::default(),
+            boosters,
+            top_k,
+        )
+    }
+
+    /// 创建多引擎搜索管道，各引擎分数按融合配置合并后再交给分数增强器。
+    /// 参数：engines - 启用的引擎（第一个为主引擎）；fusion - 融合策略与参数。
+    pub fn with_engines(
+        engines: Vec<WeightedEngine>,
+        fusion: &SearchFusionSettings,
+        boosters: Vec<Arc<dyn ScoreBooster>>,
+        top_k: usize,
     ) -> Self {
         Self {
-            engine,
+            engines,
+            strategy: fusion.strategy,
+            rrf_k: fusion.rrf_k,
             boosters,
             top_k,
         }
     }
 
+    /// 让管道中的引擎为当前候选项缓存重建索引（无索引的引擎为空操作）。
+    /// 参数：candidates - 候选项缓存。
+    pub fn index(&self, candidates: &CachedCandidateData) {
+        for e in &self.engines {
+            e.engine.index_candidates(candidates);
+        }
+    }
+
     /// 执行搜索并截断到 top_k。
     /// 参数：candidates - 候选项缓存；query - 已预处理的查询词。
     /// 返回：按分数降序排列、截断后的 ScoredCandidate 列表。
@@ -39,7 +88,16 @@ impl SearchPipeline {
         candidates: &CachedCandidateData,
         query: &str,
     ) -> Vec<ScoredCandidate> {
-        let mut scored = self.engine.calculate_scores(candidates, query);
+        let results = self
+            .engines
+            .iter()
+            .map(|e| EngineScores {
+                engine_id: e.engine.component_id().to_string(),
+                weight: e.weight,
+                scores: e.engine.calculate_scores(candidates, query),
+            })
+            .collect();
+        let mut scored = fuse(self.strategy, self.rrf_k, results);
 
         for booster in &self.boosters {
             booster.boost(&mut scored, candidates, query);
@@ -54,6 +112,16 @@ impl SearchPipeline {
         self.top_k
     }
 
+    /// 获取参与搜索的引擎数量
+    pub fn engine_count(&self) -> usize {
+        self.engines.len()
+    }
+
+    /// 获取多引擎融合策略
+    pub fn strategy(&self) -> FusionStrategy {
+        self.strategy
+    }
+
     /// 记录候选项被选中启动，通知所有 ScoreBooster 学习用户习惯
     /// 参数：candidate_id - 被选中的候选项 ID；data - 候选项缓存数据；query - 用户查询词
     pub fn record(&self, candidate_id: CandidateId, data: &CachedCandidateData, query: &str) {
@@ -61,4 +129,12 @@ impl SearchPipeline {
             booster.record(candidate_id, data, query);
         }
     }
+
+    /// 搜索栏唤醒时将系统参数快照转交所有 ScoreBooster，供其感知本次查询的上下文
+    /// 参数：snapshot - 唤醒时捕获的系统参数快照
+    pub fn observe_wake(&self, snapshot: &ParameterSnapshot) {
+        for booster in &self.boosters {
+            booster.observe_wake(snapshot);
+        }
+    }
 }
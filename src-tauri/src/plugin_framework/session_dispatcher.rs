@@ -11,30 +11,36 @@
 use dashmap::DashMap;
 use dashmap::DashSet;
 use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
+use zerolaunch_plugin_api::common::ImageUtils;
 use zerolaunch_plugin_api::config::ComponentType;
-use zerolaunch_plugin_api::services::parameter::template_parser::{Placeholder, TemplateParser};
+use zerolaunch_plugin_api::services::parameter::template_parser::TemplateParser;
+use zerolaunch_plugin_api::services::parameter::types::UserParameter;
 use zerolaunch_plugin_api::services::ParameterSnapshot;
 use zerolaunch_plugin_api::{
     CachedCandidateData, CandidateId, ExecutionContext, ExecutionError, ListItem, Plugin,
-    PluginContext, PluginMode, Query, QueryChannel, QueryResponse, QueryRevisionGate,
+    PluginContext, PluginMode, Query, QueryChannel, QueryResponse, QueryRevisionGate, QueryUpdate,
+    ResultAction, ScoredCandidate,
 };
 
-use super::candidate_pipeline::CandidatePipeline;
+use super::candidate_index_store::CandidateIndexStore;
+use super::candidate_pipeline::{CandidatePipeline, RefreshScope};
 use super::component_registry::PluginComponentRegistry;
 use super::executor_registry::ExecutorRegistry;
 use super::registry::PluginRegistry;
 use super::search_pipeline::SearchPipeline;
 use super::session_state::{
     ActiveSession, PanelContentAction, PluginPanelContent, PluginPanelInfo, PresentationMode,
-    SessionStateEmitter, SessionStateEvent,
+    QueryUpdateContent, QueryUpdateItem, SessionStateEmitter, SessionStateEvent,
 };
 use crate::core::config::bias_settings::{bias_settings_to_rules, BiasSettings};
-use crate::core::config::{ConfigEvent, ConfigManager};
+use crate::core::config::{ConfigEvent, ConfigManager, SEARCH_FUSION_CONFIG_ID};
+use crate::core::host_event::{HostEvent, HostEventBus};
 use crate::core::i18n::I18nManager;
 use crate::sdk::HostApi;
 use crate::utils::collapse_repeated_spaces;
@@ -106,17 +112,19 @@ pub struct RoutedQuery {
 ///
 /// 仅由 `route_confirm` 返回并经命令层映射为 IPC 响应
 /// （`BridgeConfirmResponse` 承担序列化契约，本类型不跨 IPC）。
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmOutcome {
     /// 动作已执行完成。
     Executed,
     /// 进入参数面板（输入收集形态）：携带参数面板专属载荷
-    /// （候选 ID + 参数个数），前端据此构造输入字段，无需依赖列表项。
+    /// （候选 ID + 参数个数与描述），前端据此构造输入字段，无需依赖列表项。
     EnterParamPanel {
         /// 目标候选项 ID（参数面板收集完成后确认时使用）。
         candidate_id: CandidateId,
         /// 模板参数个数（与后端 TemplateParser 计算同源）。
         user_arg_count: usize,
+        /// 各参数的名称与默认值（按输入顺序）。
+        user_args: Vec<UserParameter>,
     },
 }
 
@@ -180,6 +188,56 @@ impl ConfirmRequest {
     }
 }
 
+/// CLI 确认请求 —— 无会话通道（`/v1/confirm`、`/v1/execute`）：不携带代际，
+/// 不读写 UI 活动会话；归属由 `query_text` 的触发词重新解析（与 `route_query` 同一规则）。
+#[derive(Debug)]
+pub struct CliConfirmRequest {
+    /// 目标候选项 ID（来自同一 `query_text` 的 CLI 查询结果）。
+    pub candidate_id: CandidateId,
+    /// 动作 ID；None 时取宿主候选项目标类型的默认动作（插件条目必须显式指定）。
+    pub action_id: Option<String>,
+    /// 产生该候选项的查询文本。
+    pub query_text: String,
+    /// 用户参数（行内参数模板）。
+    pub user_args: Vec<String>,
+}
+
+/// 单个搜索引擎的调试基准结果 —— 由 `debug_benchmark_engines` 返回，
+/// 命令层映射为 IPC 响应（本类型不跨 IPC）。
+#[derive(Debug, Clone)]
+pub struct EngineBenchmark {
+    /// 引擎 component_id。
+    pub engine_id: String,
+    /// 引擎显示名（可能为 i18n key）。
+    pub engine_name: String,
+    /// 是否在当前搜索管道中启用。
+    pub enabled: bool,
+    /// 为当前候选项缓存建索引的耗时（毫秒；无索引的引擎近似为 0）。
+    pub index_ms: f64,
+    /// 单引擎打分耗时（毫秒，不含融合与分数增强器）。
+    pub search_ms: f64,
+    /// 得分为正的候选项数。
+    pub matched_count: usize,
+}
+
+/// 全局查询贡献者的单次查询时间预算：超时的贡献直接丢弃，不拖慢默认搜索。
+const GLOBAL_QUERY_BUDGET: Duration = Duration::from_millis(150);
+
+/// 全局查询贡献条目的 ID 保留区起点：插件自定 ID 可能与缓存候选项冲突，
+/// 合并时统一重映射到此区间（候选项 ID 为采集序号，不会增长到此量级）。
+/// 取 2^52：重映射后的 ID 仍在前端 Number 安全整数范围（2^53）内，确认回传不丢精度。
+const GLOBAL_CANDIDATE_ID_BASE: CandidateId = 1 << 52;
+
+/// 全局查询贡献条目的确认路由（重映射后 ID → 贡献插件 + 插件原始 ID）。
+/// 仅在默认搜索的 UI 通道提交时整体替换，供 route_confirm 将确认转发给贡献插件。
+#[derive(Debug, Clone)]
+struct GlobalItemRoute {
+    /// 贡献该条目的插件 ID。
+    plugin_id: String,
+    /// 插件 `global_query` 返回的原始条目 ID（转发 execute_action 时回传）。
+    original_id: CandidateId,
+}
+
 /// 默认搜索子状态（InlineParam/ParamPanel 属默认搜索的会话状态）。
 /// 行内参数的 trigger_keyword 仅存在于响应契约（QueryResponse::InlineParam），
 /// 确认路由只依赖 candidate_id，无需保存触发词。
@@ -215,6 +273,12 @@ pub struct SessionDispatcher {
     i18n: RwLock<Option<Arc<I18nManager>>>,
     /// 默认搜索子状态（行内参数/参数面板）。
     search_state: RwLock<SearchSubState>,
+    /// 最近一次默认搜索（UI 通道）中全局查询贡献条目的确认路由。
+    global_items: RwLock<HashMap<CandidateId, GlobalItemRoute>>,
+    /// 最近一次默认搜索（CLI 通道）中全局查询贡献条目的确认路由（与 UI 通道互不覆盖）。
+    cli_global_items: RwLock<HashMap<CandidateId, GlobalItemRoute>>,
+    /// 全局查询贡献条目的 ID 分配序号（叠加 GLOBAL_CANDIDATE_ID_BASE，单调递增不复用）。
+    global_item_seq: AtomicU64,
     /// 当前会话的系统参数快照（唤醒时捕获，执行动作时消费）。
     parameter_snapshot: Arc<Mutex<ParameterSnapshot>>,
     /// 插件运行时组件注册中心（管道重建工厂）。
@@ -225,12 +289,20 @@ pub struct SessionDispatcher {
     /// None 表示从未刷新过（定时任务应视为超期立即刷新）；
     /// 每次 refresh_candidates 成功更新，供 auto-refresh 周期任务判断是否到达间隔。
     last_refresh: Mutex<Option<Instant>>,
+    /// 候选项索引快照存储（bootstrap 注入；CLI/测试场景不注入，刷新后不落盘）。
+    index_store: RwLock<Option<Arc<CandidateIndexStore>>>,
+    /// 索引快照代际计数器：序列化时分配，存储据此丢弃晚到的旧快照。
+    index_generation: AtomicU64,
 
     /// 会话状态推送回调（bootstrap 注入；CLI 无窗口场景不注入）。
     session_emitter: RwLock<Option<SessionStateEmitter>>,
+    /// 宿主可观测事件总线（AppState 注入；测试场景可不注入）。
+    host_events: RwLock<Option<Arc<HostEventBus>>>,
     /// 双通道查询版本计数器（语义见 QueryRevisionGate 注释）。
     ui_query_revision: Arc<AtomicU64>,
     cli_query_revision: Arc<AtomicU64>,
+    /// 最近一次 UI 通道插件查询的归属（版本号, 插件 ID）：增量结果仅接受该插件对该版本的推送。
+    ui_query_owner: Mutex<Option<(u64, String)>>,
 }
 
 impl SessionDispatcher {
@@ -254,13 +326,20 @@ impl SessionDispatcher {
             host_api: RwLock::new(None),
             i18n: RwLock::new(None),
             search_state: RwLock::new(SearchSubState::Search),
+            global_items: RwLock::new(HashMap::new()),
+            cli_global_items: RwLock::new(HashMap::new()),
+            global_item_seq: AtomicU64::new(0),
             parameter_snapshot: Arc::new(Mutex::new(ParameterSnapshot::empty())),
             components: PluginComponentRegistry::new(),
             last_top_k: RwLock::new(10),
             last_refresh: Mutex::new(None),
+            index_store: RwLock::new(None),
+            index_generation: AtomicU64::new(0),
             session_emitter: RwLock::new(None),
+            host_events: RwLock::new(None),
             ui_query_revision: Arc::new(AtomicU64::new(0)),
             cli_query_revision: Arc::new(AtomicU64::new(0)),
+            ui_query_owner: Mutex::new(None),
         }
     }
 
@@ -400,6 +479,28 @@ impl SessionDispatcher {
     /// 设置缓存的候选项。
     pub fn set_cached_candidates(&self, candidates: CachedCandidateData) {
         *self.cached_candidates.write() = candidates;
+        self.index_search_pipeline();
+    }
+
+    /// 让搜索管道中的引擎按当前候选项缓存重建索引（候选项缓存更新后调用）。
+    /// 加锁顺序与搜索路径一致：先候选项缓存、后搜索管道。
+    fn index_search_pipeline(&self) {
+        let cached = self.cached_candidates.read();
+        if let Some(pipeline) = self.search_pipeline.read().as_ref() {
+            pipeline.index(&cached);
+        }
+    }
+
+    /// 设置候选项索引快照存储（此后每次刷新完成都会写入快照）。
+    pub fn set_index_store(&self, store: CandidateIndexStore) {
+        *self.index_store.write() = Some(Arc::new(store));
+    }
+
+    /// 加载磁盘上的候选项索引快照（未注入存储或快照不可用时为 None）。
+    /// 冷启动时直接作为缓存提供搜索，随后由后台刷新对账。
+    pub fn load_index_snapshot(&self) -> Option<CachedCandidateData> {
+        let store = self.index_store.read().clone()?;
+        store.load()
     }
 
     /// 设置配置管理器。
@@ -408,7 +509,7 @@ impl SessionDispatcher {
     }
 
     /// 读取 ConfigManager 引用（未注入时为 None——CLI 场景不注入，相关逻辑直接降级）。
-    fn config_manager(&self) -> Option<Arc<ConfigManager>> {
+    pub(crate) fn config_manager(&self) -> Option<Arc<ConfigManager>> {
         self.config_manager.read().as_ref().cloned()
     }
 
@@ -431,6 +532,18 @@ impl SessionDispatcher {
         *self.session_emitter.write() = Some(emitter);
     }
 
+    /// 注入宿主可观测事件总线（AppState 构造时调用）。
+    pub fn set_host_events(&self, bus: Arc<HostEventBus>) {
+        *self.host_events.write() = Some(bus);
+    }
+
+    /// 发布宿主可观测事件（未注入总线时跳过）。
+    fn publish_event(&self, event: HostEvent) {
+        if let Some(bus) = self.host_events.read().as_ref() {
+            bus.publish(event);
+        }
+    }
+
     /// 组件注册中心引用（管道重建）。
     pub fn components(&self) -> &PluginComponentRegistry {
         &self.components
@@ -468,14 +581,55 @@ impl SessionDispatcher {
         (candidates.len(), candidates.to_vec())
     }
 
-    /// 刷新候选项缓存。
-    /// 所有触发源（定时/监控/手动/配置联动）共用本入口；刷新成功后记录时间戳，
-    /// 供 auto-refresh 周期任务判断"距上次刷新是否已达间隔"（天然去重，避免重复刷新）。
+    /// 全量刷新候选项缓存（定时/手动刷新）：全部数据源重新采集。
+    /// 刷新成功后记录时间戳，供 auto-refresh 周期任务判断"距上次刷新是否已达间隔"
+    /// （天然去重，避免重复刷新）。
     pub async fn refresh_candidates(&self) {
+        self.refresh_candidates_scoped(RefreshScope::All).await;
+    }
+
+    /// 按范围增量刷新候选项缓存（配置联动/安装监控只重新采集相关数据源）。
+    /// 未重新采集的数据源沿用快照，同一执行目标沿用旧候选ID，关键字仅对新增或改名的候选项重算。
+    /// 仅全量刷新更新 last_refresh：局部刷新不代表全部数据源已是最新。
+    pub async fn refresh_candidates_scoped(&self, scope: RefreshScope) {
         let pipeline = self.candidate_pipeline.read().await;
-        let candidates = pipeline.collect().await;
+        pipeline.fetch(&scope).await;
+        let candidates = {
+            let previous = self.cached_candidates.read();
+            pipeline.assemble(&previous)
+        };
         *self.cached_candidates.write() = candidates;
-        *self.last_refresh.lock() = Some(Instant::now());
+        self.index_search_pipeline();
+        if matches!(scope, RefreshScope::All) {
+            *self.last_refresh.lock() = Some(Instant::now());
+        }
+        self.persist_index_snapshot();
+    }
+
+    /// 将当前候选项缓存写入索引快照（在阻塞线程池中落盘，不阻塞刷新调用方）。
+    /// 代际在持有缓存读锁时分配，代际越大内容越新；接连完成的刷新各自后台保存，
+    /// 由存储按代际逐个写入并跳过晚到的旧快照。未注入快照存储时直接返回。
+    fn persist_index_snapshot(&self) {
+        let Some(store) = self.index_store.read().clone() else {
+            return;
+        };
+        let (generation, content) = {
+            let cache = self.cached_candidates.read();
+            let generation = self.index_generation.fetch_add(1, Ordering::Relaxed) + 1;
+            (generation, CandidateIndexStore::serialize(&cache))
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("候选项索引快照序列化失败: {}", e);
+                return;
+            }
+        };
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = store.save(generation, &content) {
+                warn!("候选项索引快照保存失败: {}", e);
+            }
+        });
     }
 
     /// 距最近一次刷新已过去的时长。
@@ -492,7 +646,7 @@ impl SessionDispatcher {
     /// 调试用：对缓存候选项运行搜索并返回评分结果（已排序 top_k）。
     /// 参数：query - 原始查询文本（内部转为小写并折叠连续空格后匹配）。
     /// 返回：评分排序后的候选项列表；搜索管道未初始化时为空。
-    pub fn debug_search(&self, query: &str) -> Vec<zerolaunch_plugin_api::ScoredCandidate> {
+    pub fn debug_search(&self, query: &str) -> Vec<ScoredCandidate> {
         let cached = self.cached_candidates.read();
         let pipeline_guard = self.search_pipeline.read();
         let Some(pipeline) = pipeline_guard.as_ref() else {
@@ -505,7 +659,7 @@ impl SessionDispatcher {
     /// 调试用：对缓存候选项运行全量搜索（不截断 top_k），供分数分解观察。
     /// 参数：query - 原始查询文本（内部转为小写并折叠连续空格后匹配）。
     /// 返回：完整评分排序后的候选项列表；搜索管道未初始化时为空。
-    pub fn debug_search_all(&self, query: &str) -> Vec<zerolaunch_plugin_api::ScoredCandidate> {
+    pub fn debug_search_all(&self, query: &str) -> Vec<ScoredCandidate> {
         let cached = self.cached_candidates.read();
         let pipeline_guard = self.search_pipeline.read();
         let Some(pipeline) = pipeline_guard.as_ref() else {
@@ -515,6 +669,39 @@ impl SessionDispatcher {
         pipeline.search_all(&cached, &normalized)
     }
 
+    /// 调试用：对全部已注册的搜索引擎（含已禁用的）逐个测量建索引与单引擎打分耗时，
+    /// 供对比各引擎在当前候选项规模下的性能。未启用的引擎在此建立的索引保留至下次重建。
+    /// 参数：query - 原始查询文本（内部转为小写并折叠连续空格后匹配）。
+    pub fn debug_benchmark_engines(&self, query: &str) -> Vec<EngineBenchmark> {
+        let cm = self.config_manager();
+        let cached = self.cached_candidates.read();
+        let normalized = collapse_repeated_spaces(&query.to_lowercase());
+        self.components
+            .search_engines()
+            .into_iter()
+            .map(|engine| {
+                let start = Instant::now();
+                engine.index_candidates(&cached);
+                let index_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+                let start = Instant::now();
+                let scored = engine.calculate_scores(&cached, &normalized);
+                let search_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+                EngineBenchmark {
+                    engine_id: engine.component_id().to_string(),
+                    engine_name: engine.component_name().to_string(),
+                    enabled: cm
+                        .as_ref()
+                        .is_some_and(|cm| cm.is_enabled(engine.component_id())),
+                    index_ms,
+                    search_ms,
+                    matched_count: scored.iter().filter(|s| s.score > 0.0).count(),
+                }
+            })
+            .collect()
+    }
+
     /// 调试用：对给定名称生成关键字列表（采集管道 DataSource 能力）。
     pub async fn debug_generate_keywords(&self, name: &str) -> Vec<String> {
         self.candidate_pipeline
@@ -586,13 +773,56 @@ impl SessionDispatcher {
     /// 返回 `Err(PluginError)` 表示插件匹配成功但处理失败（命中后失败不落入
     /// 默认搜索），由命令层转为 `BridgeError` 下发前端（IPC 错误通道）。
     /// 会话状态仅由 UI 通道维护：CLI/调试查询为只读辅助路径，不改写活动会话、
-    /// 不推送事件（原 SessionRouter 行为保持）。
+    /// 不推送会话事件（原 SessionRouter 行为保持）。
+    /// 两个通道的查询完成后都发布可观测 `query` 事件（耗时、归属、响应形态）。
     #[tracing::instrument(skip(self, query), fields(trace_id = %trace_id, query_revision, owner))]
     pub async fn route_query(
         &self,
         trace_id: &str,
         query: &Query,
         channel: QueryChannel,
+    ) -> Result<RoutedQuery, SessionDispatcherError> {
+        let start = Instant::now();
+        let routed = self.route_query_inner(trace_id, query, channel).await;
+        let (plugin_id, response, result_count) = match &routed {
+            Ok(routed) => {
+                let (response, result_count) = match &routed.response {
+                    QueryResponse::List { results } => ("list", results.len()),
+                    QueryResponse::CustomPanel { .. } => ("customPanel", 0),
+                    QueryResponse::InlineParam { .. } => ("inlineParam", 0),
+                    QueryResponse::Empty => ("empty", 0),
+                };
+                (routed.plugin_id.clone(), response, result_count)
+            }
+            Err(_) => {
+                // 失败只可能来自触发词命中的插件：按同一规则还原归属
+                let (trigger, _) = self.match_trigger(&query.raw_query);
+                let plugin_id =
+                    trigger.and_then(|t| self.trigger_index.get(&t).map(|e| e.value().clone()));
+                (plugin_id, "error", 0)
+            }
+        };
+        self.publish_event(HostEvent::Query {
+            trace_id: trace_id.to_string(),
+            channel: match channel {
+                QueryChannel::Ui => "ui",
+                QueryChannel::Cli => "cli",
+            }
+            .to_string(),
+            plugin_id,
+            elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            response: response.to_string(),
+            result_count,
+        });
+        routed
+    }
+
+    /// 查询路由本体（`route_query` 负责计时与事件发布）。
+    async fn route_query_inner(
+        &self,
+        trace_id: &str,
+        query: &Query,
+        channel: QueryChannel,
     ) -> Result<RoutedQuery, SessionDispatcherError> {
         // 从所属通道计数器分配单调递增版本号：同通道新查询取代先前查询。
         let counter = self.revision_counter(channel);
@@ -638,8 +868,17 @@ impl SessionDispatcher {
             let mut plugin_ctx = ctx.clone();
             plugin_ctx.with_plugin_id(plugin_id.clone());
             tracing::Span::current().record("owner", plugin_id.as_str());
+            // 登记查询归属：插件在查询期间推送的增量结果按此校验。
+            if channel == QueryChannel::Ui {
+                *self.ui_query_owner.lock() = Some((revision, plugin_id.clone()));
+            }
 
-            match plugin.query(&plugin_ctx, &plugin_query).await {
+            let result = plugin.query(&plugin_ctx, &plugin_query).await;
+            // 终态响应即将下发：释放归属，此后到达的同版本增量结果一律丢弃，不得覆盖最终结果。
+            if channel == QueryChannel::Ui {
+                self.release_ui_query_owner(revision, &plugin_id);
+            }
+            match result {
                 Ok(response) => {
                     // 提交门控：查询执行期间若有同通道更新的查询进入后端，本查询已过期
                     // （判定单调，过期后不会再变回最新），直接丢弃本次结果返回空响应。
@@ -680,6 +919,15 @@ impl SessionDispatcher {
                     })
                 }
                 Err(e) => {
+                    // 已被同通道更新的查询取代：失败多半源于宿主下发的取消，
+                    // 与成功分支的提交门控一致，静默返回空响应而非上报错误。
+                    if self.is_query_stale(counter, revision) {
+                        return Ok(RoutedQuery {
+                            response: QueryResponse::Empty,
+                            generation: self.current_generation(),
+                            plugin_id: Some(plugin_id),
+                        });
+                    }
                     // 插件匹配成功但处理失败：不静默切换默认搜索，沿 IPC 错误通道上报。
                     error!(
                         query_revision = revision,
@@ -692,22 +940,119 @@ impl SessionDispatcher {
             }
         } else {
             // 默认搜索：搜索管道 + 行内参数检测 + ListItem 映射。
-            let cached = self.cached_candidates.read();
-            let pipeline_guard = self.search_pipeline.read();
-            let Some(pipeline) = pipeline_guard.as_ref() else {
-                warn!("SearchPipeline 未初始化，返回空结果");
-                return Ok(RoutedQuery {
-                    response: QueryResponse::Empty,
-                    generation: self.current_generation(),
-                    plugin_id: None,
-                });
+            // 同步段在块内完成，缓存/管道读锁在等待全局查询贡献前释放。
+            let mut results: Vec<ListItem> = {
+                let cached = self.cached_candidates.read();
+                let pipeline_guard = self.search_pipeline.read();
+                let Some(pipeline) = pipeline_guard.as_ref() else {
+                    warn!("SearchPipeline 未初始化，返回空结果");
+                    return Ok(RoutedQuery {
+                        response: QueryResponse::Empty,
+                        generation: self.current_generation(),
+                        plugin_id: None,
+                    });
+                };
+                let normalized = collapse_repeated_spaces(&query.search_term);
+                let scored_candidates = pipeline.search(&cached, &normalized);
+
+                // 提交门控（与插件分支一致）：搜索计算期间若有同通道更新的查询进入后端，
+                // 本查询已过期，丢弃结果——过期返回空结果优于返回过期数据（CLI 并发/排队
+                // 场景），同时避免过期查询写投影（search_state / enter_session）。
+                if self.is_query_stale(counter, revision) {
+                    return Ok(RoutedQuery {
+                        response: QueryResponse::Empty,
+                        generation: self.current_generation(),
+                        plugin_id: None,
+                    });
+                }
+
+                // 行内参数入口检测：查询以空格结尾 + 去掉空格后精确匹配某候选项的触发关键词。
+                // 在 ListItem 映射之前检查，避免匹配时废弃已映射的结果。
+                if query.raw_query.ends_with(' ') {
+                    let trimmed = query.search_term.trim();
+                    for candidate in &scored_candidates {
+                        let Some(sc) = cached.get_candidate(candidate.candidate_id) else {
+                            warn!(
+                                "Inline param check: candidate {} not found in cache, skipping",
+                                candidate.candidate_id
+                            );
+                            continue;
+                        };
+                        let user_args = TemplateParser::user_parameters(sc.target.payload());
+                        let user_arg_count = user_args.len();
+                        if user_arg_count > 0
+                            && sc
+                                .trigger_keywords
+                                .iter()
+                                .any(|kw| kw.to_lowercase() == trimmed)
+                        {
+                            if channel == QueryChannel::Ui {
+                                *self.search_state.write() = SearchSubState::InlineParam {
+                                    candidate_id: sc.id,
+                                };
+                                self.enter_session(None, PresentationMode::InlineParam, false);
+                            }
+                            return Ok(RoutedQuery {
+                                response: QueryResponse::InlineParam {
+                                    candidate_id: sc.id,
+                                    trigger_keyword: trimmed.to_string(),
+                                    user_arg_count,
+                                    user_args,
+                                },
+                                generation: self.current_generation(),
+                                plugin_id: None,
+                            });
+                        }
+                    }
+                }
+
+                // ListItem 映射：动作列表、占位符统计、系统参数标记、触发关键词。
+                scored_candidates
+                    .into_iter()
+                    .filter_map(|candidate| {
+                        let Some(search_candidate) = cached.get_candidate(candidate.candidate_id)
+                        else {
+                            warn!(
+                                "List mapping: candidate {} not found in cache, skipping",
+                                candidate.candidate_id
+                            );
+                            return None;
+                        };
+                        let mut actions = self
+                            .executor_registry
+                            .read()
+                            .get_actions(search_candidate.target.target_type());
+                        // 候选项自带的附加动作追加在执行器动作之后（不抢占默认动作）
+                        actions.extend(search_candidate.actions.iter().map(|a| ResultAction {
+                            is_default: false,
+                            ..a.action.clone()
+                        }));
+                        let template_str = search_candidate.target.payload();
+                        let user_arg_count = TemplateParser::count_user_args(template_str);
+                        let has_system_params = TemplateParser::has_system_params(template_str);
+                        Some(ListItem {
+                            id: search_candidate.id,
+                            title: search_candidate.name.clone(),
+                            subtitle: search_candidate.target.payload().to_string(),
+                            icon: search_candidate.icon.clone(),
+                            score: candidate.score,
+                            actions,
+                            target_type: search_candidate.target.target_type().as_str().to_string(),
+                            user_arg_count,
+                            has_system_params,
+                            trigger_keywords: search_candidate.trigger_keywords.clone(),
+                        })
+                    })
+                    .collect()
             };
-            let normalized = collapse_repeated_spaces(&query.search_term);
-            let scored_candidates = pipeline.search(&cached, &normalized);
 
-            // 提交门控（与插件分支一致）：搜索计算期间若有同通道更新的查询进入后端，
-            // 本查询已过期，丢弃结果——过期返回空结果优于返回过期数据（CLI 并发/排队
-            // 场景），同时避免过期查询写投影（search_state / enter_session）。
+            // 全局查询贡献：空查询不下发（默认列表不混入插件条目）。
+            let contributed = if query.search_term.trim().is_empty() {
+                Vec::new()
+            } else {
+                self.collect_global_contributions(&ctx, query).await
+            };
+            // 等待贡献期间可能有更新的查询进入后端：再次过门控，过期即丢弃整批结果。
             if self.is_query_stale(counter, revision) {
                 return Ok(RoutedQuery {
                     response: QueryResponse::Empty,
@@ -715,88 +1060,33 @@ impl SessionDispatcher {
                     plugin_id: None,
                 });
             }
-
-            // 行内参数入口检测：查询以空格结尾 + 去掉空格后精确匹配某候选项的触发关键词。
-            // 在 ListItem 映射之前检查，避免匹配时废弃已映射的结果。
-            if query.raw_query.ends_with(' ') {
-                let trimmed = query.search_term.trim();
-                for candidate in &scored_candidates {
-                    let Some(sc) = cached.get_candidate(candidate.candidate_id) else {
-                        warn!(
-                            "Inline param check: candidate {} not found in cache, skipping",
-                            candidate.candidate_id
-                        );
-                        continue;
-                    };
-                    let user_arg_count = TemplateParser::count_user_args(sc.target.payload());
-                    if user_arg_count > 0
-                        && sc
-                            .trigger_keywords
-                            .iter()
-                            .any(|kw| kw.to_lowercase() == trimmed)
-                    {
-                        if channel == QueryChannel::Ui {
-                            *self.search_state.write() = SearchSubState::InlineParam {
-                                candidate_id: sc.id,
-                            };
-                            self.enter_session(None, PresentationMode::InlineParam, false);
-                        }
-                        return Ok(RoutedQuery {
-                            response: QueryResponse::InlineParam {
-                                candidate_id: sc.id,
-                                trigger_keyword: trimmed.to_string(),
-                                user_arg_count,
-                            },
-                            generation: self.current_generation(),
-                            plugin_id: None,
-                        });
-                    }
+            let mut routes = HashMap::new();
+            if !contributed.is_empty() {
+                for (plugin_id, mut item) in contributed {
+                    let id = GLOBAL_CANDIDATE_ID_BASE
+                        + self.global_item_seq.fetch_add(1, Ordering::Relaxed);
+                    routes.insert(
+                        id,
+                        GlobalItemRoute {
+                            plugin_id,
+                            original_id: item.id,
+                        },
+                    );
+                    item.id = id;
+                    results.push(item);
                 }
+                // 按分数合并（稳定排序：同分时检索结果在前），截断到管道 top_k。
+                results.sort_by(|a, b| b.score.total_cmp(&a.score));
+                results.truncate(*self.last_top_k.read());
+                routes.retain(|id, _| results.iter().any(|item| item.id == *id));
             }
 
-            // ListItem 映射：动作列表、占位符统计、系统参数标记、触发关键词。
-            let results: Vec<ListItem> = scored_candidates
-                .into_iter()
-                .filter_map(|candidate| {
-                    let Some(search_candidate) = cached.get_candidate(candidate.candidate_id)
-                    else {
-                        warn!(
-                            "List mapping: candidate {} not found in cache, skipping",
-                            candidate.candidate_id
-                        );
-                        return None;
-                    };
-                    let actions = self
-                        .executor_registry
-                        .read()
-                        .get_actions(search_candidate.target.target_type());
-                    let template_str = search_candidate.target.payload();
-                    let placeholders = TemplateParser::parse(template_str);
-                    let user_arg_count = placeholders
-                        .iter()
-                        .filter(|p| matches!(p, Placeholder::UserArg))
-                        .count();
-                    let has_system_params = placeholders
-                        .iter()
-                        .any(|p| matches!(p, Placeholder::System(_)));
-                    Some(ListItem {
-                        id: search_candidate.id,
-                        title: search_candidate.name.clone(),
-                        subtitle: search_candidate.target.payload().to_string(),
-                        icon: search_candidate.icon.clone(),
-                        score: candidate.score,
-                        actions,
-                        target_type: search_candidate.target.target_type().as_str().to_string(),
-                        user_arg_count,
-                        has_system_params,
-                        trigger_keywords: search_candidate.trigger_keywords.clone(),
-                    })
-                })
-                .collect();
-
             if channel == QueryChannel::Ui {
+                *self.global_items.write() = routes;
                 *self.search_state.write() = SearchSubState::Search;
                 self.enter_session(None, PresentationMode::Search, false);
+            } else {
+                *self.cli_global_items.write() = routes;
             }
             Ok(RoutedQuery {
                 response: QueryResponse::List { results },
@@ -806,6 +1096,66 @@ impl SessionDispatcher {
         }
     }
 
+    /// 并发调用全部启用且声明 `global_query` 的插件，收集其贡献的条目。
+    /// 每个贡献者受 GLOBAL_QUERY_BUDGET 约束：超时/失败仅记录日志，不影响默认搜索结果。
+    /// 返回：(插件 ID, 条目) 列表，条目 ID 仍为插件原始 ID（由调用方重映射）。
+    async fn collect_global_contributions(
+        &self,
+        ctx: &PluginContext,
+        query: &Query,
+    ) -> Vec<(String, ListItem)> {
+        let contributors: Vec<Arc<dyn Plugin>> = self
+            .plugin_registry
+            .get_all()
+            .into_iter()
+            .filter(|p| p.metadata().global_query && self.is_plugin_enabled(&p.metadata().id))
+            .collect();
+        if contributors.is_empty() {
+            return Vec::new();
+        }
+
+        let mut set = tokio::task::JoinSet::new();
+        for plugin in contributors {
+            let plugin_id = plugin.metadata().id.clone();
+            let mut plugin_ctx = ctx.clone();
+            plugin_ctx.with_plugin_id(plugin_id.clone());
+            let query = query.clone();
+            set.spawn(async move {
+                let result = tokio::time::timeout(
+                    GLOBAL_QUERY_BUDGET,
+                    plugin.global_query(&plugin_ctx, &query),
+                )
+                .await;
+                (plugin_id, result)
+            });
+        }
+
+        let mut contributed = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((plugin_id, Ok(Ok(items)))) => {
+                    contributed.extend(items.into_iter().map(|item| (plugin_id.clone(), item)));
+                }
+                Ok((plugin_id, Ok(Err(e)))) => {
+                    warn!(target = %plugin_id, error = %e, "全局查询贡献失败，已忽略");
+                }
+                Ok((plugin_id, Err(_))) => {
+                    warn!(
+                        target = %plugin_id,
+                        budget_ms = GLOBAL_QUERY_BUDGET.as_millis() as u64,
+                        "全局查询贡献超时，已丢弃"
+                    );
+                }
+                Err(e) => {
+                    warn!(error = %e, "全局查询贡献任务异常退出");
+                }
+            }
+        }
+        // 按插件 ID 排序：合并顺序不依赖 JoinSet 完成先后（同分条目顺序稳定）
+        contributed.sort_by(|a, b| a.0.cmp(&b.0));
+        contributed
+    }
+
     /// 路由一次确认：校验会话代际 → 按活动会话归属分发（插件执行 / 默认搜索执行）。
     ///
     /// 请求为 `ConfirmRequest`（命令层构造，Candidate / PluginAction 两变体统一入口）；
@@ -831,7 +1181,7 @@ impl SessionDispatcher {
                 // - PluginAction：面板动作（面板按键契约 Custom / GotoPanel）的自由 JSON，原样透传插件；
                 // - Candidate：宿主确认的历史形状 {candidate_id, query_text, user_args}——
                 //   第三方插件按此契约解析，行为不得破坏。
-                let (action_id, payload) = match req {
+                let (action_id, payload, candidate_id) = match req {
                     ConfirmRequest::PluginAction {
                         plugin_id: req_plugin_id,
                         action,
@@ -845,7 +1195,7 @@ impl SessionDispatcher {
                                 req_plugin_id
                             )));
                         }
-                        (action, args)
+                        (action, args, None)
                     }
                     ConfirmRequest::Candidate {
                         candidate_id,
@@ -860,17 +1210,26 @@ impl SessionDispatcher {
                             "query_text": query_text,
                             "user_args": user_args,
                         }),
+                        Some(candidate_id),
                     ),
                 };
-                match plugin
+                let result = plugin
                     .execute_action(&plugin_ctx, &action_id, payload)
                     .await
-                {
+                    .map_err(|e| e.to_string());
+                self.publish_execution(
+                    trace_id,
+                    Some(plugin_id.as_str()),
+                    candidate_id,
+                    &action_id,
+                    result.as_ref().err(),
+                );
+                match result {
                     Ok(()) => Ok(RoutedConfirm {
                         outcome: ConfirmOutcome::Executed,
                         generation: session.generation,
                     }),
-                    Err(e) => Err(SessionDispatcherError::PluginError(e.to_string())),
+                    Err(e) => Err(SessionDispatcherError::PluginError(e)),
                 }
             }
             None => {
@@ -888,11 +1247,20 @@ impl SessionDispatcher {
                     ));
                 };
                 let state = self.search_state.read().clone();
+                // 唤醒时捕获的参数快照；先克隆释放锁，再进入执行 await。
+                let snapshot = self.parameter_snapshot.lock().clone();
                 match state {
                     SearchSubState::InlineParam { candidate_id }
                     | SearchSubState::ParamPanel { candidate_id } => {
                         match self
-                            .execute_candidate(candidate_id, &action_id, &query_text, &user_args)
+                            .execute_candidate(
+                                trace_id,
+                                candidate_id,
+                                &action_id,
+                                &query_text,
+                                &user_args,
+                                snapshot,
+                            )
                             .await
                         {
                             Ok(()) => Ok(RoutedConfirm {
@@ -903,14 +1271,29 @@ impl SessionDispatcher {
                         }
                     }
                     SearchSubState::Search => {
+                        // 全局查询贡献条目：转发给贡献插件的 execute_action（载荷契约与插件会话一致，
+                        // candidate_id 还原为插件原始 ID）。
+                        let global_route = self.global_items.read().get(&candidate_id).cloned();
+                        if let Some(route) = global_route {
+                            return self
+                                .execute_global_item(
+                                    trace_id,
+                                    route,
+                                    &action_id,
+                                    &query_text,
+                                    &user_args,
+                                    session.generation,
+                                )
+                                .await;
+                        }
                         // 参数缺失的裁决留在后端：候选项需要参数但用户未提供 → 引导进入参数面板。
-                        let user_arg_count = {
+                        let param_descriptors = {
                             let cc = self.cached_candidates.read();
                             cc.get_candidate(candidate_id)
-                                .map(|c| TemplateParser::count_user_args(c.target.payload()))
-                                .unwrap_or(0)
+                                .map(|c| TemplateParser::user_parameters(c.target.payload()))
+                                .unwrap_or_default()
                         };
-                        if user_arg_count > 0 && user_args.is_empty() {
+                        if !param_descriptors.is_empty() && user_args.is_empty() {
                             // 参数面板是默认搜索的子形态：子状态自持写入，投影形态自声明。
                             *self.search_state.write() =
                                 SearchSubState::ParamPanel { candidate_id };
@@ -918,13 +1301,21 @@ impl SessionDispatcher {
                             return Ok(RoutedConfirm {
                                 outcome: ConfirmOutcome::EnterParamPanel {
                                     candidate_id,
-                                    user_arg_count,
+                                    user_arg_count: param_descriptors.len(),
+                                    user_args: param_descriptors,
                                 },
                                 generation: self.current_generation(),
                             });
                         }
                         match self
-                            .execute_candidate(candidate_id, &action_id, &query_text, &user_args)
+                            .execute_candidate(
+                                trace_id,
+                                candidate_id,
+                                &action_id,
+                                &query_text,
+                                &user_args,
+                                snapshot,
+                            )
                             .await
                         {
                             Ok(()) => Ok(RoutedConfirm {
@@ -939,6 +1330,199 @@ impl SessionDispatcher {
         }
     }
 
+    /// 路由一次 CLI 确认（无会话通道）：不校验代际、不读写 UI 活动会话与搜索子状态。
+    ///
+    /// 归属按 `query_text` 重新解析：触发词命中 → 该插件 execute_action；
+    /// 否则 CLI 通道最近一次默认搜索的全局贡献条目 → 贡献插件；其余为宿主候选项，
+    /// 经 `ExecutionContext` / `ActionExecutor` 执行（与 UI 确认同一路径）。
+    /// 宿主候选项的用户参数不足（存在未提供且无默认值的参数）时返回 `EnterParamPanel`
+    /// （只描述所需参数，不进入面板）；
+    /// 参数快照在执行时即时捕获（CLI 没有搜索栏唤醒时机）。
+    #[tracing::instrument(skip(self, req), fields(trace_id = %trace_id, candidate_id = req.candidate_id))]
+    pub async fn route_cli_confirm(
+        &self,
+        trace_id: &str,
+        req: CliConfirmRequest,
+    ) -> Result<ConfirmOutcome, SessionDispatcherError> {
+        let CliConfirmRequest {
+            candidate_id,
+            action_id,
+            query_text,
+            user_args,
+        } = req;
+        let (trigger, _) = self.match_trigger(&query_text);
+        let plugin_route = match trigger {
+            Some(trigger) => self
+                .trigger_index
+                .get(&trigger)
+                .map(|entry| (entry.value().clone(), candidate_id)),
+            None => self
+                .cli_global_items
+                .read()
+                .get(&candidate_id)
+                .map(|route| (route.plugin_id.clone(), route.original_id)),
+        };
+        if let Some((plugin_id, original_id)) = plugin_route {
+            // 插件条目的动作由插件自行解释，宿主无从推断默认动作。
+            let action_id = action_id.ok_or_else(|| {
+                SessionDispatcherError::InvalidPayload(format!(
+                    "插件 {} 的条目须指定 action_id",
+                    plugin_id
+                ))
+            })?;
+            self.execute_plugin_item(
+                trace_id,
+                &plugin_id,
+                original_id,
+                &action_id,
+                &query_text,
+                &user_args,
+            )
+            .await?;
+            return Ok(ConfirmOutcome::Executed);
+        }
+
+        let (param_descriptors, default_action) = {
+            let cc = self.cached_candidates.read();
+            let candidate = cc
+                .get_candidate(candidate_id)
+                .ok_or(SessionDispatcherError::CandidateNotFound(candidate_id))?;
+            let default_action = self
+                .executor_registry
+                .read()
+                .get_actions(candidate.target.target_type())
+                .into_iter()
+                .find(|a| a.is_default)
+                .map(|a| a.id);
+            (
+                TemplateParser::user_parameters(candidate.target.payload()),
+                default_action,
+            )
+        };
+        // 未提供且无默认值的参数无法填充（解析时报参数不足），提前转为参数描述
+        let unfilled = param_descriptors
+            .iter()
+            .skip(user_args.len())
+            .any(|p| p.default.is_none());
+        if unfilled {
+            return Ok(ConfirmOutcome::EnterParamPanel {
+                candidate_id,
+                user_arg_count: param_descriptors.len(),
+                user_args: param_descriptors,
+            });
+        }
+        let action_id = action_id.or(default_action).ok_or_else(|| {
+            SessionDispatcherError::InvalidPayload(format!(
+                "候选项 {} 的目标类型没有默认动作，须指定 action_id",
+                candidate_id
+            ))
+        })?;
+        // 先释放 HostApi 读锁再 await 捕获快照。
+        let host_api = self.host_api.read().clone();
+        let snapshot = match host_api {
+            Some(host_api) => host_api.capture_parameter_snapshot().await,
+            None => ParameterSnapshot::empty(),
+        };
+        self.execute_candidate(
+            trace_id,
+            candidate_id,
+            &action_id,
+            &query_text,
+            &user_args,
+            snapshot,
+        )
+        .await
+        .map_err(|e| SessionDispatcherError::ExecutionError(e.0))?;
+        Ok(ConfirmOutcome::Executed)
+    }
+
+    /// 执行全局查询贡献条目：贡献插件须仍处于启用状态，确认经其 execute_action 处理。
+    /// 返回：确认结局（恒为 Executed）+ 当前会话代际。
+    async fn execute_global_item(
+        &self,
+        trace_id: &str,
+        route: GlobalItemRoute,
+        action_id: &str,
+        query_text: &str,
+        user_args: &[String],
+        generation: u64,
+    ) -> Result<RoutedConfirm, SessionDispatcherError> {
+        self.execute_plugin_item(
+            trace_id,
+            &route.plugin_id,
+            route.original_id,
+            action_id,
+            query_text,
+            user_args,
+        )
+        .await?;
+        Ok(RoutedConfirm {
+            outcome: ConfirmOutcome::Executed,
+            generation,
+        })
+    }
+
+    /// 把插件条目的确认转发给插件 execute_action（插件须处于启用状态）。
+    /// 载荷为宿主确认的历史形状 {candidate_id, query_text, user_args}，candidate_id 为插件原始 ID。
+    async fn execute_plugin_item(
+        &self,
+        trace_id: &str,
+        plugin_id: &str,
+        candidate_id: CandidateId,
+        action_id: &str,
+        query_text: &str,
+        user_args: &[String],
+    ) -> Result<(), SessionDispatcherError> {
+        if !self.is_plugin_enabled(plugin_id) {
+            return Err(SessionDispatcherError::InvalidState(format!(
+                "插件未启用: {}",
+                plugin_id
+            )));
+        }
+        let plugin = self.plugin_registry.get(plugin_id).ok_or_else(|| {
+            SessionDispatcherError::InvalidState(format!("插件不存在: {}", plugin_id))
+        })?;
+        let mut plugin_ctx = PluginContext::new(trace_id);
+        plugin_ctx.with_plugin_id(plugin_id.to_string());
+        plugin_ctx.locale = self.current_locale();
+        let payload = serde_json::json!({
+            "candidate_id": candidate_id,
+            "query_text": query_text,
+            "user_args": user_args,
+        });
+        let result = plugin
+            .execute_action(&plugin_ctx, action_id, payload)
+            .await
+            .map_err(|e| e.to_string());
+        self.publish_execution(
+            trace_id,
+            Some(plugin_id),
+            Some(candidate_id),
+            action_id,
+            result.as_ref().err(),
+        );
+        result.map_err(SessionDispatcherError::PluginError)
+    }
+
+    /// 发布 `execution` 事件（成功与失败均发布）。
+    fn publish_execution(
+        &self,
+        trace_id: &str,
+        plugin_id: Option<&str>,
+        candidate_id: Option<CandidateId>,
+        action_id: &str,
+        error: Option<&String>,
+    ) {
+        self.publish_event(HostEvent::Execution {
+            trace_id: trace_id.to_string(),
+            plugin_id: plugin_id.map(str::to_string),
+            candidate_id,
+            action_id: action_id.to_string(),
+            success: error.is_none(),
+            error: error.cloned(),
+        });
+    }
+
     /// 共享骨架：读取并克隆活动会话，校验存在（presentation 非 None）与请求代际一致。
     /// 参数：request_generation - 请求携带的代际。
     /// 返回：校验通过的活动会话快照（确认入口共用）。
@@ -973,25 +1557,69 @@ impl SessionDispatcher {
         Ok(())
     }
 
-    /// 执行候选项：构造执行上下文 → 记录搜索行为 → 解析执行器 → 执行（含失败回退）。
+    /// 执行候选项并发布 `execution` 事件。
     /// 参数：candidate_id - 候选项 ID；action_id - 动作 ID；query_text - 发起确认时的查询文本；
-    ///       user_args - 用户参数（行内参数/参数面板场景）。
+    ///       user_args - 用户参数（行内参数/参数面板场景）；snapshot - 系统参数快照。
     /// 返回：Ok(()) 或执行错误。
     async fn execute_candidate(
+        &self,
+        trace_id: &str,
+        candidate_id: CandidateId,
+        action_id: &str,
+        query_text: &str,
+        user_args: &[String],
+        snapshot: ParameterSnapshot,
+    ) -> Result<(), ConfirmError> {
+        let result = self
+            .run_candidate_action(candidate_id, action_id, query_text, user_args, snapshot)
+            .await;
+        self.publish_execution(
+            trace_id,
+            None,
+            Some(candidate_id),
+            action_id,
+            result.as_ref().err().map(|e| &e.0),
+        );
+        result
+    }
+
+    /// 候选项执行本体：构造执行上下文 → 记录搜索行为 → 解析执行器 → 执行（含失败回退）。
+    async fn run_candidate_action(
         &self,
         candidate_id: CandidateId,
         action_id: &str,
         query_text: &str,
         user_args: &[String],
+        snapshot: ParameterSnapshot,
     ) -> Result<(), ConfirmError> {
-        let exec_ctx = {
+        let (exec_ctx, action_id) = {
             let cached = self.cached_candidates.read();
             let candidate = cached
                 .get_candidate(candidate_id)
                 .ok_or_else(|| ConfirmError(format!("候选项未找到: id={}", candidate_id)))?;
-            let snapshot = self.parameter_snapshot.lock().clone();
+            // 候选项附加动作：以动作自身的目标替换主目标，交由该目标类型的默认动作执行
+            let (target, action_id) = match candidate
+                .actions
+                .iter()
+                .find(|a| a.action.id == action_id)
+            {
+                Some(extra) => {
+                    let default_action = self
+                        .executor_registry
+                        .read()
+                        .get_actions(extra.target.target_type())
+                        .into_iter()
+                        .find(|a| a.is_default)
+                        .map(|a| a.id)
+                        .ok_or_else(|| {
+                            ConfirmError(format!("附加动作 '{}' 的目标类型没有默认动作", action_id))
+                        })?;
+                    (extra.target.clone(), default_action)
+                }
+                None => (candidate.target.clone(), action_id.to_string()),
+            };
             let exec_ctx = ExecutionContext {
-                target: candidate.target.clone(),
+                target,
                 display_name: candidate.name.clone(),
                 user_args: user_args.to_vec(),
                 parameter_snapshot: snapshot,
@@ -1000,8 +1628,9 @@ impl SessionDispatcher {
             if let Some(pipeline) = self.search_pipeline.read().as_ref() {
                 pipeline.record(candidate_id, &cached, query_text);
             }
-            exec_ctx
+            (exec_ctx, action_id)
         };
+        let action_id = action_id.as_str();
         // 所有锁在 await 前释放；执行器解析走 ExecutorRegistry 唯一入口。
         let executor = {
             let registry = self.executor_registry.read();
@@ -1075,17 +1704,19 @@ impl SessionDispatcher {
         presentation: PresentationMode,
         always_push: bool,
     ) {
-        self.enter_session_inner(plugin_id, presentation, always_push, None);
+        self.enter_session_inner(plugin_id, presentation, always_push, None, None);
     }
 
     /// 进入会话投影的内部实现：`content` 为热键唤醒携带的面板渲染载荷，
-    /// 仅唤醒路径非 None（关键词查询路径的载荷随 bridge_query 响应下发）。
+    /// 仅唤醒路径非 None（关键词查询路径的载荷随 bridge_query 响应下发）；
+    /// `query_update` 为插件增量查询结果，仅 handle_query_update 路径非 None。
     fn enter_session_inner(
         &self,
         plugin_id: Option<String>,
         presentation: PresentationMode,
         always_push: bool,
         content: Option<PluginPanelContent>,
+        query_update: Option<QueryUpdateContent>,
     ) {
         let mut session = self.active_session.write();
         let changed = session.plugin_id != plugin_id || session.presentation != presentation;
@@ -1106,7 +1737,14 @@ impl SessionDispatcher {
             };
         }
         drop(session);
-        self.push_session_state(generation, &plugin_id, presentation, content);
+        if changed {
+            self.publish_event(HostEvent::Session {
+                generation,
+                plugin_id: plugin_id.clone(),
+                mode: presentation.as_str().to_string(),
+            });
+        }
+        self.push_session_state(generation, &plugin_id, presentation, content, query_update);
     }
 
     /// 推送会话状态事件（无 emitter 的 CLI 场景直接跳过）。
@@ -1116,6 +1754,7 @@ impl SessionDispatcher {
         plugin_id: &Option<String>,
         presentation: PresentationMode,
         content: Option<PluginPanelContent>,
+        query_update: Option<QueryUpdateContent>,
     ) {
         let Some(emitter) = self.session_emitter.read().clone() else {
             return;
@@ -1144,6 +1783,7 @@ impl SessionDispatcher {
             interaction,
             trigger_keywords,
             panel_content: content,
+            query_update,
         });
     }
 
@@ -1178,7 +1818,12 @@ impl SessionDispatcher {
         drop(session);
         // 会话结束投影：唯一事件通道推送（原 session-reset 事件已删除）。
         if changed {
-            self.push_session_state(generation, &None, PresentationMode::None, None);
+            self.publish_event(HostEvent::Session {
+                generation,
+                plugin_id: None,
+                mode: PresentationMode::None.as_str().to_string(),
+            });
+            self.push_session_state(generation, &None, PresentationMode::None, None, None);
         }
         true
     }
@@ -1209,10 +1854,11 @@ impl SessionDispatcher {
             &session.plugin_id,
             session.presentation,
             None,
+            None,
         );
     }
 
-    /// 搜索栏唤醒：捕获系统参数快照。
+    /// 搜索栏唤醒：捕获系统参数快照，并转交搜索管道中的分数增强器感知上下文。
     pub async fn on_search_bar_wake(&self) -> Result<(), SessionDispatcherError> {
         let host_api = self.host_api.read().clone().ok_or_else(|| {
             SessionDispatcherError::NotInitialized(
@@ -1220,6 +1866,9 @@ impl SessionDispatcher {
             )
         })?;
         let snapshot = host_api.capture_parameter_snapshot().await;
+        if let Some(pipeline) = self.search_pipeline.read().as_ref() {
+            pipeline.observe_wake(&snapshot);
+        }
         *self.parameter_snapshot.lock() = snapshot;
         debug!("📸 搜索栏唤醒，系统参数快照已捕获");
         Ok(())
@@ -1350,13 +1999,145 @@ impl SessionDispatcher {
             presentation = presentation.as_str(),
             "热键唤醒插件"
         );
-        self.enter_session_inner(Some(plugin_id.to_string()), presentation, true, content);
+        self.enter_session_inner(
+            Some(plugin_id.to_string()),
+            presentation,
+            true,
+            content,
+            None,
+        );
         Ok(())
     }
 
+    /// 处理插件推送的增量查询结果（内置插件经 PluginHandle、远端插件经 host/query.update）。
+    ///
+    /// 仅接受进行中的 UI 查询归属插件对最新版本的推送：版本过期、归属不符或查询已返回终态
+    /// 直接丢弃；仅 List / CustomPanel 生效。列表图标解析（await）后再次校验归属，
+    /// 通过后进入插件会话投影并随会话事件下发增量内容。
+    pub async fn handle_query_update(&self, update: QueryUpdate) {
+        let QueryUpdate {
+            plugin_id,
+            revision,
+            response,
+        } = update;
+        if !self.owns_ui_query(&self.ui_query_owner.lock(), revision, &plugin_id) {
+            debug!(
+                query_revision = revision,
+                target = %plugin_id,
+                "增量结果已过期或归属不符，丢弃"
+            );
+            return;
+        }
+
+        let (presentation, content) = match response {
+            QueryResponse::List { results } => {
+                let core_handle = self
+                    .host_api
+                    .read()
+                    .clone()
+                    .and_then(|api| api.get_plugin_handle("core"));
+                let mut items = Vec::with_capacity(results.len());
+                for item in results {
+                    let icon = match &core_handle {
+                        Some(handle) => {
+                            ImageUtils::to_data_url(&handle.get_icon_or_default(item.icon).await)
+                        }
+                        None => String::new(),
+                    };
+                    items.push(QueryUpdateItem {
+                        id: item.id,
+                        title: item.title,
+                        subtitle: item.subtitle,
+                        icon,
+                        score: item.score,
+                        actions: item
+                            .actions
+                            .into_iter()
+                            .map(PanelContentAction::from)
+                            .collect(),
+                        target_type: item.target_type,
+                        user_arg_count: item.user_arg_count,
+                        has_system_params: item.has_system_params,
+                        trigger_keywords: item.trigger_keywords,
+                    });
+                }
+                (
+                    PresentationMode::PluginPanel,
+                    QueryUpdateContent {
+                        revision,
+                        results: Some(items),
+                        panel_content: None,
+                    },
+                )
+            }
+            QueryResponse::CustomPanel {
+                panel_type,
+                data,
+                actions,
+                keep_search_bar,
+            } => (
+                if keep_search_bar {
+                    PresentationMode::PluginPanel
+                } else {
+                    PresentationMode::PluginImmersive
+                },
+                QueryUpdateContent {
+                    revision,
+                    results: None,
+                    panel_content: Some(PluginPanelContent {
+                        panel_type,
+                        data,
+                        actions: actions.into_iter().map(PanelContentAction::from).collect(),
+                    }),
+                },
+            ),
+            _ => {
+                debug!(
+                    query_revision = revision,
+                    target = %plugin_id,
+                    "增量结果仅支持 List / CustomPanel，忽略"
+                );
+                return;
+            }
+        };
+
+        // 图标解析期间可能有更新的查询进入后端或本查询已返回终态：持归属锁再次校验并写投影，
+        // 与 route_query 释放归属互斥，保证增量结果不会晚于最终结果下发。
+        let owner = self.ui_query_owner.lock();
+        if !self.owns_ui_query(&owner, revision, &plugin_id) {
+            return;
+        }
+        debug!(
+            query_revision = revision,
+            target = %plugin_id,
+            "推送增量查询结果"
+        );
+        self.enter_session_inner(Some(plugin_id), presentation, true, None, Some(content));
+    }
+
+    /// 判断增量结果是否仍属于进行中的最新 UI 查询：归属（版本号 + 插件 ID）一致且版本未过期。
+    fn owns_ui_query(&self, owner: &Option<(u64, String)>, revision: u64, plugin_id: &str) -> bool {
+        owner
+            .as_ref()
+            .is_some_and(|(rev, owner)| *rev == revision && owner == plugin_id)
+            && self.ui_query_revision.load(Ordering::Relaxed) == revision
+    }
+
+    /// 释放 UI 查询归属（查询返回终态时调用）。仅释放本查询的归属，更新查询已登记的归属保持不变。
+    fn release_ui_query_owner(&self, revision: u64, plugin_id: &str) {
+        let mut owner = self.ui_query_owner.lock();
+        if owner
+            .as_ref()
+            .is_some_and(|(rev, owner)| *rev == revision && owner == plugin_id)
+        {
+            *owner = None;
+        }
+    }
+
     // ==================== 管道与配置事件 ====================
 
-    /// 重建候选管道：从 ConfigManager 构建 → 注入偏置规则 → 替换管道 → 刷新候选项。
+    /// 重建候选管道：从 ConfigManager 构建 → 注入偏置规则 → 继承旧管道快照 → 替换管道 →
+    /// 仅采集新加入的数据源并重新合并（已有数据源不重新采集）。
     async fn rebuild_candidate_pipeline(&self) {
         let Some(cm) = self.config_manager() else {
             return;
@@ -1369,8 +2150,13 @@ impl SessionDispatcher {
             .map(|settings| bias_settings_to_rules(&settings))
             .unwrap_or_default();
         new_pipeline.set_bias_rules(rules);
-        *self.candidate_pipeline.write().await = new_pipeline;
-        self.refresh_candidates().await;
+        {
+            let mut pipeline = self.candidate_pipeline.write().await;
+            new_pipeline.inherit_from(&pipeline);
+            *pipeline = new_pipeline;
+        }
+        self.refresh_candidates_scoped(RefreshScope::Sources(Vec::new()))
+            .await;
     }
 
     /// 根据当前注册的搜索引擎和分数增强器重建搜索管道。
@@ -1381,7 +2167,14 @@ impl SessionDispatcher {
         let top_k = *self.last_top_k.read();
         match self.components.build_search_pipeline(&cm, top_k) {
             Some(pipeline) => {
-                info!("搜索管道已重建 (top_k: {})", pipeline.top_k());
+                // 新管道可能包含尚未建立索引的引擎：先建索引再替换，避免替换后首次搜索退化为全量扫描
+                pipeline.index(&self.cached_candidates.read());
+                info!(
+                    "搜索管道已重建 (engines: {}, fusion: {:?}, top_k: {})",
+                    pipeline.engine_count(),
+                    pipeline.strategy(),
+                    pipeline.top_k()
+                );
                 *self.search_pipeline.write() = Some(pipeline);
             }
             None => {
@@ -1399,16 +2192,25 @@ impl SessionDispatcher {
             } => {
                 debug!("配置变更事件: {} ({:?})", component_id, component_type);
                 match component_type {
-                    ComponentType::DataSource
-                    | ComponentType::KeywordOptimizer
-                    | ComponentType::KeywordInjector => {
-                        info!("数据源/关键词优化器配置变更，刷新候选项缓存");
-                        self.refresh_candidates().await;
+                    ComponentType::DataSource => {
+                        info!("数据源 {} 配置变更，重新采集该数据源", component_id);
+                        self.refresh_candidates_scoped(RefreshScope::Sources(vec![
+                            component_id.clone()
+                        ]))
+                        .await;
+                    }
+                    ComponentType::KeywordOptimizer | ComponentType::KeywordInjector => {
+                        info!("关键词优化器/注入器配置变更，重算候选项关键字");
+                        self.refresh_candidates_scoped(RefreshScope::Keywords).await;
                     }
                     ComponentType::SearchEngine | ComponentType::ScoreBooster => {
                         info!("搜索引擎/分数增强器配置变更，重建搜索管道");
                         self.rebuild_search_pipeline();
                     }
+                    ComponentType::Core if component_id == SEARCH_FUSION_CONFIG_ID => {
+                        info!("搜索融合配置变更，重建搜索管道");
+                        self.rebuild_search_pipeline();
+                    }
                     ComponentType::Core => {
                         debug!("Core 组件({})配置变更，无需响应", component_id);
                     }
@@ -1526,8 +2328,10 @@ mod tests {
     use zerolaunch_plugin_api::services::resource::AppResourceService;
     use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
     use zerolaunch_plugin_api::services::timer::TokioTimerManager;
+    use zerolaunch_plugin_api::services::IconRequest;
     use zerolaunch_plugin_api::{
         PlatformCapabilities, PluginError, PluginHandle, PluginKind, PluginMetadata, PluginMode,
+        SearchEngine,
     };
 
     /// 构建仅含桩组件的 HostApi（测试专用，不触达真实平台能力）。
@@ -1612,6 +2416,7 @@ mod tests {
                     hotkey: None,
                     icon: None,
                     mode: PluginMode::Inline,
+                    global_query: false,
                 },
                 core: ComponentCore::new(
                     id.to_string(),
@@ -1801,6 +2606,7 @@ mod tests {
                     hotkey: Some("Ctrl+E".to_string()),
                     icon: None,
                     mode: PluginMode::Panel,
+                    global_query: false,
                 },
                 core: ComponentCore::new(
                     "test.panel".to_string(),
@@ -1967,4 +2773,534 @@ mod tests {
         // 应 panic（断言消息含 keep_search_bar=true），不返回
         let _ = dispatcher.wake_plugin("test.panel").await;
     }
+
+    /// 构造指定插件对指定版本的增量面板更新。
+    fn panel_update(plugin_id: &str, revision: u64, text: &str) -> QueryUpdate {
+        QueryUpdate {
+            plugin_id: plugin_id.to_string(),
+            revision,
+            response: QueryResponse::CustomPanel {
+                panel_type: "test-panel".to_string(),
+                data: serde_json::json!({ "text": text }),
+                actions: Vec::new(),
+                keep_search_bar: true,
+            },
+        }
+    }
+
+    /// 增量结果测试用插件桩：query 处理期间依次推送归属更新、冒名更新与不支持的变体，
+    /// 再返回最终面板（镜像翻译插件「中间面板 → 最终面板」的推送时序）。
+    struct ProgressStubPlugin {
+        inner: TriggerStubPlugin,
+        dispatcher: std::sync::OnceLock<std::sync::Weak<SessionDispatcher>>,
+    }
+
+    impl ProgressStubPlugin {
+        fn with_trigger(trigger: &str) -> Self {
+            Self {
+                inner: TriggerStubPlugin::with_trigger(trigger),
+                dispatcher: std::sync::OnceLock::new(),
+            }
+        }
+    }
+
+    impl Configurable for ProgressStubPlugin {
+        fn core(&self) -> &ComponentCore {
+            self.inner.core()
+        }
+
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            Vec::new()
+        }
+    }
+
+    #[async_trait]
+    impl Plugin for ProgressStubPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            self.inner.metadata()
+        }
+
+        async fn init(
+            &self,
+            _ctx: &PluginContext,
+            _handle: Option<Arc<PluginHandle>>,
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+
+        async fn query(
+            &self,
+            ctx: &PluginContext,
+            _query: &Query,
+        ) -> Result<QueryResponse, PluginError> {
+            let dispatcher = self
+                .dispatcher
+                .get()
+                .and_then(|weak| weak.upgrade())
+                .expect("测试需先注入调度器");
+            let revision = ctx.query_revision();
+            let id = self.inner.metadata().id.clone();
+            dispatcher
+                .handle_query_update(panel_update(&id, revision, "partial"))
+                .await;
+            dispatcher
+                .handle_query_update(panel_update("other", revision, "spoof"))
+                .await;
+            dispatcher
+                .handle_query_update(QueryUpdate {
+                    plugin_id: id,
+                    revision,
+                    response: QueryResponse::Empty,
+                })
+                .await;
+            Ok(QueryResponse::CustomPanel {
+                panel_type: "test-panel".to_string(),
+                data: serde_json::json!({ "text": "final" }),
+                actions: Vec::new(),
+                keep_search_bar: true,
+            })
+        }
+
+        async fn execute_action(
+            &self,
+            _ctx: &PluginContext,
+            _action_id: &str,
+            _payload: serde_json::Value,
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+
+    /// 增量结果：查询处理期间归属插件对最新版本的推送随会话事件下发；
+    /// 非归属插件、不支持的变体、查询返回后到达的同版本推送与过期版本一律丢弃。
+    #[tokio::test]
+    async fn query_update_emits_current_and_drops_stale() {
+        let dispatcher = Arc::new(SessionDispatcher::new(Arc::new(PluginRegistry::new())));
+        dispatcher.set_host_api(test_host_api());
+        let plugin = Arc::new(ProgressStubPlugin::with_trigger("fy"));
+        let _ = plugin.dispatcher.set(Arc::downgrade(&dispatcher));
+        dispatcher.register_plugin_with_triggers(plugin, true);
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let capture = events.clone();
+        dispatcher.set_session_emitter(Arc::new(move |event| {
+            capture.lock().push(event);
+        }));
+
+        let routed = dispatcher
+            .route_query("t1", &default_query("fy hello"), QueryChannel::Ui)
+            .await
+            .expect("插件查询应成功");
+        assert!(matches!(routed.response, QueryResponse::CustomPanel { .. }));
+        {
+            // 仅归属插件的面板推送下发，且先于最终结果的会话事件
+            let events = events.lock();
+            let updates: Vec<_> = events
+                .iter()
+                .filter_map(|e| e.query_update.as_ref())
+                .collect();
+            assert_eq!(updates.len(), 1);
+            let update = events[0]
+                .query_update
+                .as_ref()
+                .expect("首个事件应携带增量结果");
+            assert_eq!(update.revision, 1);
+            let content = update.panel_content.as_ref().expect("应携带面板载荷");
+            assert_eq!(content.data, serde_json::json!({ "text": "partial" }));
+            assert_eq!(events[0].presentation, PresentationMode::PluginPanel);
+        }
+
+        // 查询已返回终态：同版本的迟到推送不得覆盖最终结果
+        let before = events.lock().len();
+        dispatcher
+            .handle_query_update(panel_update("test.fy", 1, "late"))
+            .await;
+        assert_eq!(events.lock().len(), before);
+
+        // 新查询进入后，旧版本的推送过期丢弃
+        dispatcher
+            .route_query("t2", &default_query("fy world"), QueryChannel::Ui)
+            .await
+            .expect("插件查询应成功");
+        let before = events.lock().len();
+        dispatcher
+            .handle_query_update(panel_update("test.fy", 1, "stale"))
+            .await;
+        assert_eq!(events.lock().len(), before);
+    }
+
+    /// 全局查询测试用空检索引擎 —— 不产生任何检索结果，隔离出插件贡献条目。
+    struct EmptySearchEngine {
+        core: ComponentCore,
+    }
+
+    impl Configurable for EmptySearchEngine {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            Vec::new()
+        }
+    }
+
+    impl SearchEngine for EmptySearchEngine {
+        fn calculate_scores(
+            &self,
+            _candidates: &CachedCandidateData,
+            _query: &str,
+        ) -> Vec<ScoredCandidate> {
+            Vec::new()
+        }
+    }
+
+    /// 全局查询贡献者桩：声明 global_query，按指定延迟返回一条条目，
+    /// 并记录 execute_action 收到的载荷（校验确认转发与原始 ID 还原）。
+    struct GlobalStubPlugin {
+        metadata: PluginMetadata,
+        core: ComponentCore,
+        delay: Duration,
+        executed: Mutex<Option<serde_json::Value>>,
+    }
+
+    impl GlobalStubPlugin {
+        fn new(id: &str, delay: Duration) -> Self {
+            Self {
+                metadata: PluginMetadata {
+                    id: id.to_string(),
+                    name: "全局查询桩".to_string(),
+                    version: "0.1.0".to_string(),
+                    description: "全局查询测试".to_string(),
+                    author: "test".to_string(),
+                    trigger_keywords: Vec::new(),
+                    supported_os: Vec::new(),
+                    priority: 0,
+                    kind: PluginKind::Builtin,
+                    hotkey: None,
+                    icon: None,
+                    mode: PluginMode::Inline,
+                    global_query: true,
+                },
+                core: ComponentCore::new(
+                    id.to_string(),
+                    "全局查询桩".to_string(),
+                    "全局查询测试".to_string(),
+                    ComponentType::Plugin,
+                    0,
+                ),
+                delay,
+                executed: Mutex::new(None),
+            }
+        }
+    }
+
+    impl Configurable for GlobalStubPlugin {
+        fn core(&self) -> &ComponentCore {
+            &self.core
+        }
+
+        fn setting_schema(&self) -> Vec<SettingDefinition> {
+            Vec::new()
+        }
+    }
+
+    #[async_trait]
+    impl Plugin for GlobalStubPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+
+        async fn init(
+            &self,
+            _ctx: &PluginContext,
+            _handle: Option<Arc<PluginHandle>>,
+        ) -> Result<(), PluginError> {
+            Ok(())
+        }
+
+        async fn query(
+            &self,
+            _ctx: &PluginContext,
+            _query: &Query,
+        ) -> Result<QueryResponse, PluginError> {
+            Ok(QueryResponse::Empty)
+        }
+
+        async fn global_query(
+            &self,
+            _ctx: &PluginContext,
+            query: &Query,
+        ) -> Result<Vec<ListItem>, PluginError> {
+            tokio::time::sleep(self.delay).await;
+            Ok(vec![ListItem {
+                id: 7,
+                title: format!("{}: {}", self.metadata.id, query.search_term),
+                subtitle: String::new(),
+                icon: IconRequest::Path(String::new()),
+                score: 1.0,
+                actions: Vec::new(),
+                target_type: "test".to_string(),
+                user_arg_count: 0,
+                has_system_params: false,
+                trigger_keywords: Vec::new(),
+            }])
+        }
+
+        async fn execute_action(
+            &self,
+            _ctx: &PluginContext,
+            _action_id: &str,
+            payload: serde_json::Value,
+        ) -> Result<(), PluginError> {
+            *self.executed.lock() = Some(payload);
+            Ok(())
+        }
+    }
+
+    /// 构造带空检索引擎的调度器（默认搜索仅剩全局查询贡献条目）。
+    fn dispatcher_with_empty_engine() -> SessionDispatcher {
+        let dispatcher = SessionDispatcher::new(Arc::new(PluginRegistry::new()));
+        dispatcher.set_host_api(test_host_api());
+        let engine = Arc::new(EmptySearchEngine {
+            core: ComponentCore::new(
+                "test.engine".to_string(),
+                "空引擎".to_string(),
+                "全局查询测试".to_string(),
+                ComponentType::SearchEngine,
+                0,
+            ),
+        });
+        dispatcher.set_search_pipeline(SearchPipeline::new(engine, Vec::new(), 10));
+        dispatcher
+    }
+
+    /// 构造默认搜索查询（无触发词，search_term 与原文一致）。
+    fn default_query(text: &str) -> Query {
+        Query {
+            id: "q1".to_string(),
+            raw_query: text.to_string(),
+            search_term: text.to_string(),
+            confirm: false,
+        }
+    }
+
+    /// 全局查询：贡献条目并入默认搜索结果，ID 重映射到保留区；
+    /// 确认该条目转发给贡献插件，载荷中的 candidate_id 还原为插件原始 ID。
+    #[tokio::test]
+    async fn global_query_items_merge_and_confirm_forwards_to_plugin() {
+        let dispatcher = dispatcher_with_empty_engine();
+        let plugin = Arc::new(GlobalStubPlugin::new("test.global", Duration::ZERO));
+        dispatcher.register_plugin_with_triggers(plugin.clone(), true);
+
+        let routed = dispatcher
+            .route_query("t1", &default_query("1+1"), QueryChannel::Ui)
+            .await
+            .expect("默认搜索应成功");
+        let QueryResponse::List { results } = routed.response else {
+            panic!("默认搜索应返回列表");
+        };
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "test.global: 1+1");
+        assert!(results[0].id >= GLOBAL_CANDIDATE_ID_BASE);
+
+        let outcome = dispatcher
+            .route_confirm(
+                "t1",
+                ConfirmRequest::Candidate {
+                    candidate_id: results[0].id,
+                    action_id: "copy".to_string(),
+                    query_text: "1+1".to_string(),
+                    user_args: Vec::new(),
+                    generation: routed.generation,
+                },
+            )
+            .await
+            .expect("确认应转发给贡献插件");
+        assert_eq!(outcome.outcome, ConfirmOutcome::Executed);
+        let payload = plugin.executed.lock().clone().expect("插件应收到确认");
+        assert_eq!(payload["candidate_id"], serde_json::json!(7));
+    }
+
+    /// CLI 确认：全局贡献条目经 CLI 通道路由转发给插件，不改写 UI 会话；
+    /// 插件条目缺少动作、未知宿主候选项均被拒绝。
+    #[tokio::test]
+    async fn cli_confirm_forwards_global_item_without_touching_session() {
+        let dispatcher = dispatcher_with_empty_engine();
+        let plugin = Arc::new(GlobalStubPlugin::new("test.global", Duration::ZERO));
+        dispatcher.register_plugin_with_triggers(plugin.clone(), true);
+        let generation = dispatcher.current_generation();
+
+        let routed = dispatcher
+            .route_query("t1", &default_query("1+1"), QueryChannel::Cli)
+            .await
+            .expect("默认搜索应成功");
+        let QueryResponse::List { results } = routed.response else {
+            panic!("默认搜索应返回列表");
+        };
+        let candidate_id = results[0].id;
+
+        let missing_action = dispatcher
+            .route_cli_confirm(
+                "t1",
+                CliConfirmRequest {
+                    candidate_id,
+                    action_id: None,
+                    query_text: "1+1".to_string(),
+                    user_args: Vec::new(),
+                },
+            )
+            .await;
+        assert!(matches!(
+            missing_action,
+            Err(SessionDispatcherError::InvalidPayload(_))
+        ));
+
+        let outcome = dispatcher
+            .route_cli_confirm(
+                "t1",
+                CliConfirmRequest {
+                    candidate_id,
+                    action_id: Some("copy".to_string()),
+                    query_text: "1+1".to_string(),
+                    user_args: Vec::new(),
+                },
+            )
+            .await
+            .expect("CLI 确认应转发给贡献插件");
+        assert_eq!(outcome, ConfirmOutcome::Executed);
+        let payload = plugin.executed.lock().clone().expect("插件应收到确认");
+        assert_eq!(payload["candidate_id"], serde_json::json!(7));
+        assert_eq!(
+            dispatcher.current_generation(),
+            generation,
+            "CLI 不得改写会话"
+        );
+
+        let unknown = dispatcher
+            .route_cli_confirm(
+                "t2",
+                CliConfirmRequest {
+                    candidate_id: 42,
+                    action_id: None,
+                    query_text: "abc".to_string(),
+                    user_args: Vec::new(),
+                },
+            )
+            .await;
+        assert!(matches!(
+            unknown,
+            Err(SessionDispatcherError::CandidateNotFound(42))
+        ));
+    }
+
+    /// 可观测事件：查询发布耗时与响应形态，插件条目执行发布执行结果，UI 查询发布会话变化。
+    #[tokio::test]
+    async fn query_and_execution_publish_host_events() {
+        let dispatcher = dispatcher_with_empty_engine();
+        let bus = Arc::new(HostEventBus::new(16));
+        dispatcher.set_host_events(bus.clone());
+        let plugin = Arc::new(GlobalStubPlugin::new("test.global", Duration::ZERO));
+        dispatcher.register_plugin_with_triggers(plugin, true);
+
+        let routed = dispatcher
+            .route_query("t1", &default_query("1+1"), QueryChannel::Cli)
+            .await
+            .expect("默认搜索应成功");
+        let QueryResponse::List { results } = routed.response else {
+            panic!("默认搜索应返回列表");
+        };
+        dispatcher
+            .route_cli_confirm(
+                "t2",
+                CliConfirmRequest {
+                    candidate_id: results[0].id,
+                    action_id: Some("copy".to_string()),
+                    query_text: "1+1".to_string(),
+                    user_args: Vec::new(),
+                },
+            )
+            .await
+            .expect("CLI 确认应转发给贡献插件");
+
+        let events: Vec<HostEvent> = bus
+            .recent(usize::MAX)
+            .into_iter()
+            .map(|r| r.event)
+            .collect();
+        let HostEvent::Query {
+            trace_id,
+            channel,
+            response,
+            result_count,
+            ..
+        } = &events[0]
+        else {
+            panic!("首个事件应为查询事件");
+        };
+        assert_eq!(trace_id, "t1");
+        assert_eq!(channel, "cli");
+        assert_eq!(response, "list");
+        assert_eq!(*result_count, 1);
+        let HostEvent::Execution {
+            trace_id,
+            plugin_id,
+            candidate_id,
+            success,
+            ..
+        } = &events[1]
+        else {
+            panic!("第二个事件应为执行事件");
+        };
+        assert_eq!(trace_id, "t2");
+        assert_eq!(plugin_id.as_deref(), Some("test.global"));
+        assert_eq!(*candidate_id, Some(7));
+        assert!(*success);
+        assert_eq!(events.len(), 2, "CLI 通道不发布会话事件");
+
+        dispatcher
+            .route_query("t3", &default_query("1+1"), QueryChannel::Ui)
+            .await
+            .expect("默认搜索应成功");
+        let kinds: Vec<&str> = bus
+            .recent(usize::MAX)
+            .iter()
+            .map(|r| r.event.kind())
+            .collect();
+        assert_eq!(kinds, vec!["query", "execution", "session", "query"]);
+    }
+
+    /// 全局查询：超出时间预算的贡献被丢弃，禁用插件不参与，空查询不下发。
+    #[tokio::test]
+    async fn global_query_drops_slow_disabled_and_empty_query() {
+        let dispatcher = dispatcher_with_empty_engine();
+        let slow: Arc<dyn Plugin> = Arc::new(GlobalStubPlugin::new(
+            "test.slow",
+            GLOBAL_QUERY_BUDGET + Duration::from_millis(200),
+        ));
+        let disabled: Arc<dyn Plugin> =
+            Arc::new(GlobalStubPlugin::new("test.disabled", Duration::ZERO));
+        let fast: Arc<dyn Plugin> = Arc::new(GlobalStubPlugin::new("test.fast", Duration::ZERO));
+        dispatcher.register_plugin_with_triggers(slow, true);
+        dispatcher.register_plugin_with_triggers(disabled, false);
+        dispatcher.register_plugin_with_triggers(fast, true);
+
+        let routed = dispatcher
+            .route_query("t1", &default_query("abc"), QueryChannel::Ui)
+            .await
+            .expect("默认搜索应成功");
+        let QueryResponse::List { results } = routed.response else {
+            panic!("默认搜索应返回列表");
+        };
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["test.fast: abc"]);
+
+        let routed = dispatcher
+            .route_query("t2", &default_query(""), QueryChannel::Ui)
+            .await
+            .expect("默认搜索应成功");
+        let QueryResponse::List { results } = routed.response else {
+            panic!("默认搜索应返回列表");
+        };
+        assert!(results.is_empty(), "空查询不应下发全局查询");
+    }
 }
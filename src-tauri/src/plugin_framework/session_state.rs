@@ -103,6 +103,57 @@ pub struct PluginPanelContent {
     pub actions: Vec<PanelContentAction>,
 }
 
+/// 增量结果中的列表项（icon 已解析为 data URL，与 bridge 路径 BridgeSearchResult 同构——
+/// 前端按同一 SearchResult 类型渲染；源数据同为一个 ListItem）。
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryUpdateItem {
+    /// 候选项 ID。
+    #[serde(rename = "id")]
+    pub id: u64,
+    /// 标题。
+    #[serde(rename = "title")]
+    pub title: String,
+    /// 副标题。
+    #[serde(rename = "subtitle")]
+    pub subtitle: String,
+    /// 图标 data URL。
+    #[serde(rename = "icon")]
+    pub icon: String,
+    /// 排序分数。
+    #[serde(rename = "score")]
+    pub score: f64,
+    /// 可用动作列表。
+    #[serde(rename = "actions")]
+    pub actions: Vec<PanelContentAction>,
+    /// 执行目标类型。
+    #[serde(rename = "targetType")]
+    pub target_type: String,
+    /// 用户参数个数。
+    #[serde(rename = "userArgCount")]
+    pub user_arg_count: usize,
+    /// 是否含系统参数。
+    #[serde(rename = "hasSystemParams")]
+    pub has_system_params: bool,
+    /// 触发关键词。
+    #[serde(rename = "triggerKeywords")]
+    pub trigger_keywords: Vec<String>,
+}
+
+/// 插件增量查询结果 —— 插件为当前查询版本推送的 List 条目或 CustomPanel 更新，
+/// 宿主校验版本未过期后随会话事件下发；前端按内容覆盖当前展示（不改写查询文本）。
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryUpdateContent {
+    /// 更新所属的查询版本号（仅供前端日志观察，过期更新已在宿主侧丢弃）。
+    #[serde(rename = "revision")]
+    pub revision: u64,
+    /// 列表更新：Some = 插件推送 List 条目（覆盖当前结果列表）。
+    #[serde(rename = "results", default)]
+    pub results: Option<Vec<QueryUpdateItem>>,
+    /// 面板更新：Some = 插件推送 CustomPanel（覆盖当前面板渲染载荷）。
+    #[serde(rename = "panelContent", default)]
+    pub panel_content: Option<PluginPanelContent>,
+}
+
 /// 会话状态事件载荷 —— 整个会话系统的唯一事件（事件名 `session-state`）。
 ///
 /// 由 Dispatcher 在会话投影变化（路由/确认/reset）或插件面板路由命中时构造，
@@ -129,6 +180,9 @@ pub struct SessionStateEvent {
     /// 插件面板渲染载荷：仅热键唤醒推送携带（Some）；关键词查询路径为 None（载荷随查询响应下发）。
     #[serde(rename = "panelContent", default)]
     pub panel_content: Option<PluginPanelContent>,
+    /// 插件增量查询结果：仅插件经 push_query_update 推送且版本未过期时携带（Some）。
+    #[serde(rename = "queryUpdate", default)]
+    pub query_update: Option<QueryUpdateContent>,
 }
 
 /// 活动会话（Dispatcher 内部权威投影）。
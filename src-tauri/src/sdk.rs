@@ -22,6 +22,7 @@ use zerolaunch_plugin_api::services::storage::storage_service::StorageService;
 use zerolaunch_plugin_api::services::timer::TimerManager;
 use zerolaunch_plugin_api::services::window::{WindowManager, WindowPosition, WindowPositioner};
 use zerolaunch_plugin_api::services::ParameterSnapshot;
+use zerolaunch_plugin_api::{QueryUpdate, QueryUpdateCallback};
 
 use std::sync::Arc;
 
@@ -91,6 +92,8 @@ pub struct HostApi {
     show_window_callback: RwLock<Arc<dyn Fn() + Send + Sync + 'static>>,
     /// 查询窗口可见性回调（宿主级）
     is_window_visible_callback: RwLock<Arc<dyn Fn() -> bool + Send + Sync + 'static>>,
+    /// 增量查询结果回调（宿主级，与所有 PluginHandle 共享；启动阶段由 SessionDispatcher 注入）
+    query_update_sink: Arc<RwLock<Option<QueryUpdateCallback>>>,
 }
 
 impl HostApi {
@@ -127,6 +130,7 @@ impl HostApi {
             self.installation_monitor.clone(),
             self.focus_monitor.clone(),
             self.clipboard_manager.clone(),
+            self.query_update_sink.clone(),
         ));
         self.handles.insert(plugin_id.to_string(), handle.clone());
         handle
@@ -185,6 +189,23 @@ impl HostApi {
         self.notify_callback.read()(title.to_string(), message.to_string());
     }
 
+    // ===== 增量查询结果 =====
+
+    /// 注入增量查询结果回调（SessionDispatcher 就绪后调用一次，已注册的句柄自动可见）。
+    /// 参数：callback - 接收插件增量结果的回调。
+    pub fn set_query_update_callback(&self, callback: QueryUpdateCallback) {
+        *self.query_update_sink.write() = Some(callback);
+    }
+
+    /// 转发远端插件经 host/query.update 推送的增量结果（未注入回调时丢弃）。
+    /// 参数：update - 已由宿主填写插件身份的增量结果。
+    pub fn push_query_update(&self, update: QueryUpdate) {
+        let Some(sink) = self.query_update_sink.read().clone() else {
+            return;
+        };
+        sink(update);
+    }
+
     // ===== 窗口控制 =====
 
     /// 隐藏搜索栏窗口。
@@ -758,6 +779,7 @@ impl HostApiBuilder {
             set_window_position_callback: RwLock::new(self.set_window_position_callback.ok_or(
                 HostApiBuildError::MissingComponent("set_window_position_callback"),
             )?),
+            query_update_sink: Arc::new(RwLock::new(None)),
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::core::cli_token::CliToken;
 use crate::core::config::ConfigManager;
+use crate::core::host_event::HostEventBus;
 use crate::core::i18n::I18nManager;
 use crate::plugin_framework::manager::PluginManager;
 use crate::plugin_framework::PluginRegistry;
@@ -28,6 +29,8 @@ pub struct AppState {
     cli_token: RwLock<Option<CliToken>>,
     /// 后端翻译服务（内置语言包 + 插件翻译目录）
     i18n_manager: RwLock<Option<Arc<I18nManager>>>,
+    /// 宿主可观测事件总线（CLI `/v1/events` 订阅）
+    host_events: Arc<HostEventBus>,
 }
 
 impl Default for AppState {
@@ -40,6 +43,8 @@ impl AppState {
     pub fn new() -> Self {
         let plugin_registry = Arc::new(PluginRegistry::new());
         let session_dispatcher = Arc::new(SessionDispatcher::new(plugin_registry));
+        let host_events = Arc::new(HostEventBus::new(256));
+        session_dispatcher.set_host_events(host_events.clone());
 
         AppState {
             session_dispatcher,
@@ -54,6 +59,7 @@ impl AppState {
             plugin_manager: RwLock::new(None),
             cli_token: RwLock::new(None),
             i18n_manager: RwLock::new(None),
+            host_events,
         }
     }
 
@@ -181,6 +187,10 @@ impl AppState {
     pub fn set_i18n_manager(&self, i18n_manager: Arc<I18nManager>) {
         *self.i18n_manager.write() = Some(i18n_manager);
     }
+
+    pub fn get_host_events(&self) -> Arc<HostEventBus> {
+        self.host_events.clone()
+    }
 }
 
 impl std::fmt::Debug for AppState {
@@ -162,6 +162,36 @@ export interface InstalledPluginInfo {
   icon: string | null
   /** 插件形态：'inline' = 行内插件；'panel' = 完全插件模式（trigger 类型）。 */
   mode: 'inline' | 'panel'
+  /** manifest [permissions] 已授予的权限键名（如 "shell"、"resources"）；内置插件恒为空。 */
+  permissions: string[]
+  /** 宿主采集的健康指标；内置插件恒为 null。 */
+  metrics: PluginMetricsSnapshot | null
+}
+
+/** 插件健康指标快照（与后端 PluginMetricsSnapshot 对齐）。 */
+export interface PluginMetricsSnapshot {
+  /** 本次加载以来的崩溃重启次数 */
+  restarts: number
+  /** 当前常驻内存（字节）；未采样或平台不支持时为 null */
+  memoryBytes: number | null
+  /** 常驻内存峰值（字节） */
+  peakMemoryBytes: number | null
+  /** 按方法名排序的请求统计 */
+  methods: MethodMetrics[]
+}
+
+/** 单个 JSON-RPC 方法的请求统计（耗时单位毫秒）。 */
+export interface MethodMetrics {
+  method: string
+  count: number
+  errors: number
+  timeouts: number
+  avgMs: number
+  maxMs: number
+  p50Ms: number
+  p95Ms: number
+  /** 延迟直方图（非累计），leMs 为桶上界，溢出桶为 null */
+  buckets: { leMs: number | null; count: number }[]
 }
 
 /**
@@ -257,10 +287,53 @@ export function pluginUninstall(pluginId: string): Promise<void> {
   return invokeCommand<void>('plugin_uninstall', { pluginId })
 }
 
+/** 插件包预览（与后端 PluginPackagePreview 同构）：安装前展示插件身份与申请的宿主权限。 */
+export interface PluginPackagePreview {
+  pluginId: string
+  name: string
+  version: string
+  author: string
+  /** manifest [permissions] 申请的权限键名（如 "shell"、"resources"）。 */
+  permissions: string[]
+}
+
+export function pluginInspectLocal(filePath: string): Promise<PluginPackagePreview> {
+  return invokeCommand<PluginPackagePreview>('plugin_inspect_local', { filePath })
+}
+
 export function pluginInstallLocal(filePath: string): Promise<InstalledPluginInfo> {
   return invokeCommand<InstalledPluginInfo>('plugin_install_local', { filePath })
 }
 
+/** 插件注册表目录条目：索引中的插件 + 本地安装状态（与后端 RegistryListing 同构）。 */
+export interface RegistryListing {
+  id: string
+  name: string
+  description: string
+  publisher: string
+  /** 与当前宿主兼容的最高版本；null 表示没有可安装的版本。 */
+  latestVersion: string | null
+  /** 本地已安装的版本；null 表示未安装。 */
+  installedVersion: string | null
+  updateAvailable: boolean
+}
+
+export function pluginRegistryList(): Promise<RegistryListing[]> {
+  return invokeCommand<RegistryListing[]>('plugin_registry_list')
+}
+
+export function pluginCheckUpdates(): Promise<RegistryListing[]> {
+  return invokeCommand<RegistryListing[]>('plugin_check_updates')
+}
+
+export function pluginInstallFromRegistry(pluginId: string): Promise<InstalledPluginInfo> {
+  return invokeCommand<InstalledPluginInfo>('plugin_install_from_registry', { pluginId })
+}
+
+export function pluginUpgrade(pluginId: string): Promise<InstalledPluginInfo> {
+  return invokeCommand<InstalledPluginInfo>('plugin_upgrade', { pluginId })
+}
+
 export function pluginSetEnabled(pluginId: string, enabled: boolean): Promise<void> {
   return invokeCommand<void>('plugin_set_enabled', { pluginId, enabled })
 }
@@ -26,10 +26,17 @@ export interface ResultAction {
   shortcutKey: string
 }
 
+/** 模板用户参数描述（与 Rust UserParameter 对齐）：命名参数 `{name}` 有名称，`{:默认值}` 有默认值。 */
+export interface UserParamDescriptor {
+  name: string | null
+  default: string | null
+}
+
 export interface InlineParamData {
   candidateId: number
   triggerKeyword: string
   userArgCount: number
+  userArgs: UserParamDescriptor[]
 }
 
 export type PanelQueryTrigger = 'onInput' | 'onEnter'
@@ -71,6 +78,16 @@ export interface PluginPanelContent {
   actions: ResultAction[]
 }
 
+/** 插件增量查询结果（后端 QueryUpdateContent）：results 与 panelContent 二选一。 */
+export interface QueryUpdateContent {
+  /** 更新所属的查询版本号（仅供日志观察）。 */
+  revision: number
+  /** 列表更新：覆盖当前结果列表。 */
+  results: ListItem[] | null
+  /** 面板更新：覆盖当前面板渲染载荷。 */
+  panelContent: PluginPanelContent | null
+}
+
 /** 会话状态事件 payload —— 整个会话系统的唯一事件（后端 Dispatcher 推送）。 */
 export interface SessionStateEvent {
   /** 会话代际：归属/形态变化时递增（前端单调递增更新，随 confirm 回传校验）。 */
@@ -84,6 +101,8 @@ export interface SessionStateEvent {
   triggerKeywords: string[]
   /** 插件面板渲染载荷：对象 = 热键唤醒推送（含面板类型/数据/动作）；null = 常规路径（载荷随查询响应下发）。 */
   panelContent: PluginPanelContent | null
+  /** 插件增量查询结果：对象 = 插件为当前查询推送的列表条目或面板更新（过期版本已由后端丢弃）；null = 无增量。 */
+  queryUpdate: QueryUpdateContent | null
 }
 
 export type BridgeQueryResponse =
@@ -122,6 +141,7 @@ export type ConfirmResponse =
       status: 'enterParamPanel'
       candidateId: number
       userArgCount: number
+      userArgs: UserParamDescriptor[]
       generation: number
     }
 
@@ -156,6 +176,7 @@ export type SchemaNode =
   | { type: 'number'; minimum: number | null; maximum: number | null; multipleOf: number | null; default: unknown | null }
   | { type: 'integer'; minimum: number | null; maximum: number | null; multipleOf: number | null; default: unknown | null }
   | { type: 'boolean'; default: unknown | null }
+  | { type: 'secret'; default: unknown | null }
   | { type: 'array'; items: SchemaNode; itemWidget: WidgetHint | null; minItems: number | null; maxItems: number | null; default: unknown | null }
   | { type: 'object'; properties: Record<string, SchemaNode>; ui: FieldUiMetadata[]; required: string[]; default: unknown | null }
 
@@ -172,6 +193,7 @@ export type WidgetHint =
   | { kind: 'image'; accept: string[]; maxSize: number | null }
   | { kind: 'font'; action: string; component: string | null }
   | { kind: 'hotkey' }
+  | { kind: 'secret' }
   | { kind: 'list' }
   | { kind: 'tags' }
   | { kind: 'table' }
@@ -342,6 +364,19 @@ export interface SearchTimingResult {
   durationMs: number
   resultCount: number
   totalCandidates: number
+  /** 各搜索引擎（含未启用的）单独建索引与打分的耗时对比 */
+  engines: EngineTiming[]
+}
+
+export interface EngineTiming {
+  engineId: string
+  /** 引擎显示名（可能为 i18n key） */
+  engineName: string
+  enabled: boolean
+  indexMs: number
+  searchMs: number
+  /** 得分为正的候选项数 */
+  matchedCount: number
 }
 
 export interface IndexTimingResult {
@@ -368,8 +403,9 @@ export interface ScoreDetail {
   weight: number
   /** 该项分数来源描述（如"编辑距离基础分"、"查询亲和分数"）。 */
   description: string
-  /** 计入方式：add = 加权加分项；multiply = 乘法系数项（如长度比率、溢出惩罚、抑制因子）。 */
-  kind: 'add' | 'multiply'
+  /** 计入方式：add = 加权加分项；multiply = 乘法系数项（如长度比率、溢出惩罚、抑制因子）；
+   *  info = 说明项，不计入总分（如多引擎融合时保留的各引擎原始明细）。 */
+  kind: 'add' | 'multiply' | 'info'
 }
 
 /**
@@ -2,6 +2,10 @@ import { useMessage } from 'naive-ui'
 import { useConfigStore } from '../stores/config-store'
 import type { ComponentInfo } from '../bridge/contract'
 
+/**
+ * 检索引擎启用开关：允许同时开启多个引擎（分数按「引擎融合」配置合并），
+ * 但必须至少保持一个引擎处于开启状态。
+ */
 export function useSearchEngineToggle(getEngines: () => ComponentInfo[]) {
   const configStore = useConfigStore()
   const message = useMessage()
@@ -25,11 +29,6 @@ export function useSearchEngineToggle(getEngines: () => ComponentInfo[]) {
     }
 
     try {
-      for (const engine of engines) {
-        if (engine.componentId !== componentId && engine.enabled) {
-          await configStore.setEnabled(engine.componentId, false)
-        }
-      }
       await configStore.setEnabled(componentId, true)
     } catch (e) {
       console.error(e)
@@ -0,0 +1,17 @@
+import type { FrontendPlugin } from '@/plugins/types'
+import ClipboardHistoryPanel from './ClipboardHistoryPanel.vue'
+
+const clipboardHistoryPanelPlugin: FrontendPlugin = {
+  id: 'clipboard-history-panel',
+  name: '剪贴板历史面板',
+  version: '1.0.0',
+  description: '内置剪贴板历史面板渲染，匹配后端 ClipboardHistoryPlugin 的 CustomPanel',
+  priority: 0,
+
+  panelProvider: {
+    matchType: 'clipboard-history',
+    component: ClipboardHistoryPanel,
+  },
+}
+
+export default clipboardHistoryPanelPlugin
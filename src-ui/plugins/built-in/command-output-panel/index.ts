@@ -0,0 +1,17 @@
+import type { FrontendPlugin } from '@/plugins/types'
+import CommandOutputPanel from './CommandOutputPanel.vue'
+
+const commandOutputPanelPlugin: FrontendPlugin = {
+  id: 'command-output-panel',
+  name: '命令输出面板',
+  version: '1.0.0',
+  description: '内置命令输出面板渲染，匹配后端 CommandOutputPlugin 的 CustomPanel',
+  priority: 0,
+
+  panelProvider: {
+    matchType: 'command-output',
+    component: CommandOutputPanel,
+  },
+}
+
+export default commandOutputPanelPlugin
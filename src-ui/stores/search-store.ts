@@ -5,7 +5,7 @@ import {
   bridgeRefreshCandidates, bridgeGetCandidatesCount,
   bridgeHideWindow, configGetSettings,
 } from '../bridge/commands'
-import type { ListItem, ResultAction, BridgeQueryResponse, ConfirmResponse, PanelInteraction, SessionStateEvent } from '../bridge/contract'
+import type { ListItem, ResultAction, BridgeQueryResponse, ConfirmResponse, PanelInteraction, SessionStateEvent, UserParamDescriptor } from '../bridge/contract'
 import { onSessionState } from '../bridge/events'
 
 /**
@@ -27,12 +27,20 @@ export interface InlineParamState {
   triggerKeyword: string
   paramInput: string
   userArgCount: number
+  userArgs: UserParamDescriptor[]
 }
 
 export interface ParamField {
   index: number
   label: string
   value: string
+  /** 留空时使用的默认值（null = 必填） */
+  defaultValue: string | null
+}
+
+/// 参数输入框标签：命名参数用名称，位置参数用序号
+export function paramLabel(arg: UserParamDescriptor | undefined, index: number): string {
+  return arg?.name ?? `参数 ${index + 1}`
 }
 
 export interface ParamPanelState {
@@ -94,6 +102,8 @@ export const useSearchStore = defineStore('search', () => {
 
   /** 递增序号，丢弃过期的 bridge_query 响应，避免慢请求盖写新输入。 */
   let querySeq = 0
+  /** 已收到响应（成功或失败）的最新查询序号：等于 querySeq 时当前查询已有最终结果，不再接受增量结果。 */
+  let settledSeq = 0
 
   /** 确认查询在途标志：在途时忽略重复 Enter（不发查询=不加序号）。 */
   const confirmInFlight = ref(false)
@@ -266,6 +276,7 @@ export const useSearchStore = defineStore('search', () => {
       const resp: BridgeQueryResponse = await bridgeQuery(raw, confirm)
 
       if (seq !== querySeq) return
+      settledSeq = seq
 
       // 确认查询响应已到达（无论结果如何），解除在途标志，允许下一次确认。
       confirmInFlight.value = false
@@ -291,6 +302,7 @@ export const useSearchStore = defineStore('search', () => {
             triggerKeyword: resp.inlineParam.triggerKeyword,
             paramInput: '',
             userArgCount: resp.inlineParam.userArgCount,
+            userArgs: resp.inlineParam.userArgs ?? [],
           }
           query.value = ''
           break
@@ -312,6 +324,7 @@ export const useSearchStore = defineStore('search', () => {
       }
     } catch (e) {
       if (seq !== querySeq) return
+      settledSeq = seq
       // 查询失败同样解除在途标志，避免后续 Enter 被永久拦截。
       confirmInFlight.value = false
       console.error('[doQuery] Query failed:', e)
@@ -390,8 +403,9 @@ export const useSearchStore = defineStore('search', () => {
         }
         const fields: ParamField[] = Array.from({ length: resp.userArgCount }, (_, i) => ({
           index: i,
-          label: `参数 ${i + 1}`,
+          label: paramLabel(resp.userArgs?.[i], i),
           value: '',
+          defaultValue: resp.userArgs?.[i]?.default ?? null,
         }))
         sessionMode.value = 'param_panel'
         paramPanelState.value = {
@@ -441,13 +455,19 @@ export const useSearchStore = defineStore('search', () => {
   async function confirmInlineParam() {
     if (!inlineParamState.value) return
 
-    const { candidateId, paramInput, userArgCount, triggerKeyword } = inlineParamState.value
+    const { candidateId, paramInput, userArgCount, userArgs, triggerKeyword } =
+      inlineParamState.value
     const args = parseInlineArgs(paramInput)
 
-    if (args.length < userArgCount) {
-      console.warn(`需要 ${userArgCount} 个参数，实际输入 ${args.length} 个`)
+    // 末尾带默认值的参数可省略（后端对缺省参数填充默认值）
+    let required = userArgCount
+    while (required > 0 && userArgs[required - 1]?.default != null) required--
+    if (args.length < required) {
+      console.warn(`需要至少 ${required} 个参数，实际输入 ${args.length} 个`)
       return
     }
+    // 省略的参数以空串补齐：后端据此填充默认值（而非因参数为空再次引导进入参数面板）
+    while (args.length < userArgCount) args.push('')
 
     // 参数已齐、必执行：先隐藏窗口再确认（成功无动作，失败由后端通知兜底）。
     // 载荷先捕获：resetSessionAndHide 会清空会话状态。
@@ -480,9 +500,10 @@ export const useSearchStore = defineStore('search', () => {
     if (!paramPanelState.value) return
 
     const { candidateId, fields } = paramPanelState.value
-    const userArgs = fields.map((f) => f.value)
+    const userArgs = fields.map((f) => (f.value.trim() === '' ? '' : f.value))
 
-    if (userArgs.some((arg) => arg.trim() === '')) {
+    // 有默认值的字段可留空（后端对空参数填充默认值）
+    if (fields.some((f) => f.value.trim() === '' && f.defaultValue === null)) {
       return
     }
 
@@ -564,6 +585,7 @@ export const useSearchStore = defineStore('search', () => {
     cancelPendingDebounce()
     // 递增序号使所有在途响应的 seq 失效，防止慢请求盖写新状态
     querySeq++
+    settledSeq = querySeq
     query.value = ''
     clearSessionState()
   }
@@ -640,6 +662,24 @@ export const useSearchStore = defineStore('search', () => {
       inlineParamState.value = null
       paramPanelState.value = null
     }
+    // 插件增量查询结果（慢数据源渐进展示）：后端已丢弃过期版本，按内容覆盖当前展示；
+    // 不改写查询文本，最终结果仍由 bridge_query 响应原子替换。
+    // 事件与响应分属不同 IPC 通道、到达顺序无保证：当前查询响应已到达后的增量结果一律忽略。
+    const update = settledSeq === querySeq ? null : event.queryUpdate
+    if (update?.results) {
+      results.value = update.results
+      sessionMode.value = 'search'
+      if (selectedIndex.value >= update.results.length) {
+        selectedIndex.value = 0
+      }
+    } else if (update?.panelContent) {
+      results.value = []
+      sessionMode.value =
+        event.presentation === 'pluginImmersive' ? 'plugin_immersive' : 'plugin_panel'
+      panelType.value = update.panelContent.panelType
+      panelData.value = update.panelContent.data
+      panelActions.value = update.panelContent.actions
+    }
   }
 
   // 监听后端会话状态事件：会话投影每次变化推送（含插件面板路由命中、会话结束）。
@@ -242,6 +242,10 @@ export function getFieldRenderInfo(field: FieldConfig): FieldRenderInfo {
       return !widget || widget.kind === 'toggle'
         ? { kind: 'boolean', schemaType: schema.type, widgetKind, error: null }
         : { kind: null, schemaType: schema.type, widgetKind, error: 'unsupportedWidget' }
+    case 'secret':
+      return !widget || widget.kind === 'secret'
+        ? { kind: 'text', schemaType: schema.type, widgetKind, error: null }
+        : { kind: null, schemaType: schema.type, widgetKind, error: 'unsupportedWidget' }
     case 'array':
       return !widget || ARRAY_WIDGET_KINDS[widget.kind] === true
         ? { kind: 'array', schemaType: schema.type, widgetKind, error: null }
@@ -366,7 +370,8 @@ export function getSchemaFontSource(widget: WidgetHint | null): { action: string
 }
 
 /** 获取文本字段的输入模式。 */
-export function getTextInputKind(widget: WidgetHint | null): 'text' | 'textarea' {
+export function getTextInputKind(widget: WidgetHint | null): 'text' | 'textarea' | 'password' {
+  if (widget?.kind === 'secret') return 'password'
   return widget?.kind === 'textarea' ? 'textarea' : 'text'
 }
 
@@ -406,6 +411,8 @@ function validateSchemaValue(schema: SchemaNode, value: unknown, path: string):
       return typeof value === 'number' && Number.isInteger(value) ? null : `${path} must be an integer`
     case 'boolean':
       return typeof value === 'boolean' ? null : `${path} must be a boolean`
+    case 'secret':
+      return typeof value === 'string' ? null : `${path} must be a string`
     case 'array':
       if (!Array.isArray(value)) return `${path} must be an array`
       for (const [index, item] of value.entries()) {
@@ -26,16 +26,18 @@ export function buildSidebarItems(
       c.componentType === 'Core' &&
       c.componentId !== 'candidate-registry' &&
       c.componentId !== 'bias-config' &&
+      c.componentId !== 'search-fusion-config' &&
       c.componentId !== 'appearance-config' &&
       c.componentId !== 'icon-override-config',
   ).sort(sortByPriority)
   const appearance = components.filter(
     (c) => c.componentId === 'appearance-config' || c.componentId === 'icon-override-config',
   ).sort(sortByPriority)
+  // 引擎融合配置虽为 Core 组件，但属于检索流程，放在搜索管道页的「引擎融合」标签中
   const pipeline = components.filter((c) =>
     ['DataSource', 'KeywordOptimizer', 'KeywordInjector', 'SearchEngine', 'ScoreBooster', 'ActionExecutor', 'BiasRule'].includes(
       c.componentType,
-    ),
+    ) || c.componentId === 'search-fusion-config',
   ).sort(sortByPriority)
 
   const items: SidebarCategory[] = [
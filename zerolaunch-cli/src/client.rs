@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use serde_json::Value;
+use std::io::{BufRead, BufReader};
 
 /// 连接失败时的提示文案：提醒用户保持 ZeroLaunch 主程序运行。
 pub const CONNECTION_HINT: &str = "\
@@ -68,41 +69,162 @@ impl CliClient {
 
     /// 发送 GET 请求并解析 JSON 响应。
     pub fn get(&self, path: &str) -> Result<Value> {
-        let url = format!("http://{}:{}{}", self.host, self.port, path);
-        let resp = self
-            .inner
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
-            .send()?;
-        parse_response(resp)
+        self.send(reqwest::Method::GET, path, None)
     }
 
     /// 发送 POST 请求并解析 JSON 响应。
     pub fn post(&self, path: &str, body: Value) -> Result<Value> {
+        self.send(reqwest::Method::POST, path, Some(body))
+    }
+
+    /// 发送 PUT 请求并解析 JSON 响应。
+    pub fn put(&self, path: &str, body: Value) -> Result<Value> {
+        self.send(reqwest::Method::PUT, path, Some(body))
+    }
+
+    /// 发送 PATCH 请求并解析 JSON 响应。
+    pub fn patch(&self, path: &str, body: Value) -> Result<Value> {
+        self.send(reqwest::Method::PATCH, path, Some(body))
+    }
+
+    /// 发送 DELETE 请求并解析 JSON 响应。
+    pub fn delete(&self, path: &str) -> Result<Value> {
+        self.send(reqwest::Method::DELETE, path, None)
+    }
+
+    /// 发送带 Bearer 鉴权的请求；有请求体时以 JSON 发送。
+    fn send(&self, method: reqwest::Method, path: &str, body: Option<Value>) -> Result<Value> {
         let url = format!("http://{}:{}{}", self.host, self.port, path);
-        let resp = self
+        let mut req = self
             .inner
-            .post(&url)
+            .request(method, &url)
+            .header("Authorization", format!("Bearer {}", self.token));
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+        parse_response(req.send()?)
+    }
+
+    /// 订阅 SSE 事件流（GET），逐条把 `data` 负载解析为 JSON 交给回调；
+    /// 回调返回 false 或服务端关闭连接时结束。流式连接不设整体超时。
+    pub fn stream(&self, path: &str, on_event: impl FnMut(Value) -> bool) -> Result<()> {
+        let url = format!("http://{}:{}{}", self.host, self.port, path);
+        let client = reqwest::blocking::Client::builder()
+            .no_proxy()
+            .timeout(None::<std::time::Duration>)
+            .build()
+            .context("无法初始化 HTTP 客户端")?;
+        let resp = client
+            .get(&url)
             .header("Authorization", format!("Bearer {}", self.token))
-            .header("Content-Type", "application/json")
-            .json(&body)
+            .header("Accept", "text/event-stream")
             .send()?;
-        parse_response(resp)
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(api_failure(status, resp.text()?));
+        }
+        read_sse(BufReader::new(resp), on_event)
     }
 }
 
-/// 检查 HTTP 状态码并解析 JSON 响应体；非成功状态码给出包含状态码与响应体的明确报错。
+/// 解析 SSE 文本流：累积 `data:` 行，空行处派发一条事件；
+/// 注释行（`:` 开头，服务端保活）与 `event:` / `id:` 等字段忽略。非 JSON 负载按字符串派发。
+fn read_sse(reader: impl BufRead, mut on_event: impl FnMut(Value) -> bool) -> Result<()> {
+    let mut data = String::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            if data.is_empty() {
+                continue;
+            }
+            let value = match serde_json::from_str(&data) {
+                Ok(value) => value,
+                Err(_) => Value::String(data.clone()),
+            };
+            data.clear();
+            if !on_event(value) {
+                return Ok(());
+            }
+        } else if let Some(rest) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+    Ok(())
+}
+
+/// 服务端返回的非成功响应。
+///
+/// 写路由的响应体为结构化错误 `{ code, message, details, componentId, traceId }`，
+/// 配置校验失败时 `details.issues` 逐字段列出问题；响应体不是 JSON 时保存为字符串。
+#[derive(Debug)]
+pub struct ApiFailure {
+    /// HTTP 状态码
+    pub status: u16,
+    /// 响应体
+    pub body: Value,
+}
+
+impl std::fmt::Display for ApiFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.body {
+            Value::String(text) => write!(f, "HTTP {}：{}", self.status, text.trim()),
+            body => write!(f, "HTTP {}：{}", self.status, body),
+        }
+    }
+}
+
+impl std::error::Error for ApiFailure {}
+
+/// 检查 HTTP 状态码并解析 JSON 响应体；非成功状态码返回携带响应体的 `ApiFailure`。
 fn parse_response(resp: reqwest::blocking::Response) -> Result<Value> {
     let status = resp.status();
     let text = resp.text()?;
     if !status.is_success() {
-        anyhow::bail!("HTTP {}：{}", status.as_u16(), text.trim());
+        return Err(api_failure(status, text));
     }
     serde_json::from_str(&text).context("响应不是有效 JSON")
 }
 
+/// 以非成功响应构造 `ApiFailure`；响应体不是 JSON 时保存为字符串。
+fn api_failure(status: reqwest::StatusCode, text: String) -> anyhow::Error {
+    let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+    ApiFailure {
+        status: status.as_u16(),
+        body,
+    }
+    .into()
+}
+
 /// 解析 ZeroLaunch 应用数据目录（$HOME/.ZeroLaunch-rs）。
 fn dirs_data() -> Result<std::path::PathBuf> {
     let home = dirs::home_dir().context("无法获取用户 Home 目录")?;
     Ok(home.join(".ZeroLaunch-rs"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 多行 data 拼接、保活注释与 event/id 字段忽略、回调返回 false 时停止读取。
+    #[test]
+    fn test_read_sse() {
+        let input = ": keep-alive\n\nevent: session\nid: 1\ndata: {\"seq\":1}\n\n\
+                     data: not\ndata: json\n\ndata: {\"seq\":3}\n\n";
+        let mut events = Vec::new();
+        read_sse(input.as_bytes(), |value| {
+            events.push(value);
+            events.len() < 2
+        })
+        .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                serde_json::json!({ "seq": 1 }),
+                Value::String("not\njson".into())
+            ]
+        );
+    }
+}
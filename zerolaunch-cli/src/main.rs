@@ -3,11 +3,13 @@
 mod client;
 mod output;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use serde_json::Value;
+use std::io::Write;
+use std::path::PathBuf;
 
-use client::CliClient;
+use client::{ApiFailure, CliClient};
 use output::*;
 
 #[derive(Parser)]
@@ -28,6 +30,43 @@ enum Commands {
     Ping,
     /// 搜索项目
     Query { text: String },
+    /// 搜索并执行其中一条结果（序号与 `zl query` 列表一致）
+    Run {
+        text: String,
+        #[arg(
+            long,
+            default_value = "1",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "执行第 N 条结果（从 1 开始）"
+        )]
+        index: u64,
+        #[arg(long, help = "动作 ID（默认执行该结果的默认动作）")]
+        action: Option<String>,
+        #[arg(
+            long = "arg",
+            value_name = "VALUE",
+            help = "用户参数，按模板顺序填充（可重复）"
+        )]
+        args: Vec<String>,
+    },
+    /// 执行指定 ID 的候选项（ID 见 `zl -j query` 的结果）
+    Confirm {
+        candidate_id: u64,
+        #[arg(
+            long,
+            default_value = "",
+            help = "产生该候选项的查询文本（插件结果须与原查询一致）"
+        )]
+        query: String,
+        #[arg(long, help = "动作 ID（默认执行目标类型的默认动作）")]
+        action: Option<String>,
+        #[arg(
+            long = "arg",
+            value_name = "VALUE",
+            help = "用户参数，按模板顺序填充（可重复）"
+        )]
+        args: Vec<String>,
+    },
     /// 获取当前会话模式
     Session,
     /// 已安装插件管理
@@ -40,6 +79,25 @@ enum Commands {
         #[command(subcommand)]
         sub: ConfigCmd,
     },
+    /// 候选项缓存管理
+    Candidates {
+        #[command(subcommand)]
+        sub: CandidatesCmd,
+    },
+    /// 输出宿主事件（JSON Lines）：默认输出最近历史，--follow 持续输出实时事件
+    Events {
+        #[arg(short = 'f', long, help = "持续输出实时事件（Ctrl+C 退出）")]
+        follow: bool,
+        #[arg(
+            long = "type",
+            value_name = "TYPE",
+            value_parser = ["session", "query", "execution", "config", "plugin"],
+            help = "只输出指定类型的事件（可重复）"
+        )]
+        types: Vec<String>,
+        #[arg(long, help = "历史事件条数（--follow 时为先回放的条数，默认不回放）")]
+        limit: Option<usize>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -54,6 +112,23 @@ enum PluginCmd {
         #[arg(long, default_value = "50", help = "显示最后 N 行日志")]
         tail: usize,
     },
+    /// 获取插件健康指标（请求延迟、错误、重启次数、内存）
+    Metrics { id: String },
+    /// 安装插件：默认从本地 .zip 或目录安装，--registry 时按插件 id 从注册表安装
+    Install {
+        /// 本地插件包路径，或 --registry 时的插件 id
+        source: String,
+        #[arg(long, help = "从插件注册表安装，source 为插件 id")]
+        registry: bool,
+    },
+    /// 卸载插件
+    Uninstall { id: String },
+    /// 重新加载插件
+    Reload { id: String },
+    /// 启用插件的全部组件
+    Enable { id: String },
+    /// 禁用插件的全部组件
+    Disable { id: String },
 }
 
 #[derive(Subcommand)]
@@ -64,6 +139,45 @@ enum ConfigCmd {
     Schema { id: String },
     /// 获取配置组件的当前设置
     Get { id: String },
+    /// 写入配置组件的部分设置（未给出的字段保持不变）
+    Set {
+        id: String,
+        /// KEY=VALUE 形式的设置项；VALUE 按 JSON 解析，解析失败时视为字符串
+        #[arg(value_name = "KEY=VALUE")]
+        values: Vec<String>,
+        #[arg(long, help = "从 JSON 文件读取设置对象（与 KEY=VALUE 合并，后者优先）")]
+        file: Option<PathBuf>,
+    },
+    /// 重置配置组件为默认设置
+    Reset { id: String },
+    /// 启用配置组件
+    Enable { id: String },
+    /// 禁用配置组件
+    Disable { id: String },
+    /// 配置动作
+    Action {
+        #[command(subcommand)]
+        sub: ActionCmd,
+    },
+}
+
+#[derive(Subcommand)]
+enum ActionCmd {
+    /// 列出配置组件的动作
+    List { id: String },
+    /// 执行配置组件的动作
+    Run {
+        id: String,
+        action: String,
+        #[arg(long, help = "动作参数（JSON）")]
+        params: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CandidatesCmd {
+    /// 强制刷新候选项缓存
+    Refresh,
 }
 
 fn main() -> Result<()> {
@@ -71,14 +185,36 @@ fn main() -> Result<()> {
     // 未传入子命令时默认执行 Ping（健康检查）
     let command: &Commands = cli.command.as_ref().unwrap_or(&Commands::Ping);
 
-    let result = match execute(command) {
-        Ok(v) => v,
+    let outcome = match command {
+        // 实时事件流：逐条输出，不经过「请求 → 格式化」流程
+        Commands::Events {
+            follow: true,
+            types,
+            limit,
+        } => follow_events(types, *limit).map(|()| None),
+        command => execute(command).map(Some),
+    };
+    let result = match outcome {
+        Ok(Some(v)) => v,
+        Ok(None) => return Ok(()),
         // 连接失败：给出「请启动主程序」的友好提示，而不是直接抛出报错
         Err(err) if client::is_connection_error(&err) => {
             eprintln!("{}", client::CONNECTION_HINT);
             eprintln!("\n详细信息：{}", err);
             std::process::exit(1);
         }
+        // 服务端拒绝请求（校验失败、组件不存在等）：输出结构化错误并以非零状态退出
+        Err(err) if err.downcast_ref::<ApiFailure>().is_some() => {
+            let failure = err
+                .downcast_ref::<ApiFailure>()
+                .expect("已在守卫中检查类型");
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&failure.body)?);
+            } else {
+                eprint!("{}", format_api_error(failure.status, &failure.body));
+            }
+            std::process::exit(1);
+        }
         Err(err) => return Err(err),
     };
 
@@ -107,9 +243,69 @@ fn dispatch(command: &Commands, client: &CliClient) -> Result<Value> {
         Commands::Query { text } => {
             client.post("/v1/query", serde_json::json!({ "rawQuery": text }))
         }
+        Commands::Run {
+            text,
+            index,
+            action,
+            args,
+        } => client.post(
+            "/v1/execute",
+            serde_json::json!({
+                "rawQuery": text,
+                // 接口序号从 0 开始，命令行与列表展示一致从 1 开始
+                "index": index - 1,
+                "actionId": action,
+                "userArgs": args,
+            }),
+        ),
+        Commands::Confirm {
+            candidate_id,
+            query,
+            action,
+            args,
+        } => client.post(
+            "/v1/confirm",
+            serde_json::json!({
+                "candidateId": candidate_id,
+                "queryText": query,
+                "actionId": action,
+                "userArgs": args,
+            }),
+        ),
         Commands::Session => client.get("/v1/session/mode"),
         Commands::Plugins { sub } => dispatch_plugins(sub, client),
         Commands::Config { sub } => dispatch_config(sub, client),
+        Commands::Candidates { sub } => match sub {
+            CandidatesCmd::Refresh => client.post("/v1/candidates/refresh", Value::Null),
+        },
+        Commands::Events { types, limit, .. } => {
+            client.get(&events_path("/v1/events/recent", types, *limit))
+        }
+    }
+}
+
+/// 订阅实时事件流，每条事件输出为一行紧凑 JSON；输出管道关闭（如 `| head`）时正常结束。
+fn follow_events(types: &[String], limit: Option<usize>) -> Result<()> {
+    let client = CliClient::load()?;
+    let mut stdout = std::io::stdout();
+    client.stream(&events_path("/v1/events", types, limit), |event| {
+        writeln!(stdout, "{}", event).is_ok()
+    })
+}
+
+/// 拼接事件路由的查询参数（类型取值已由 clap 校验，无需转义）。
+fn events_path(base: &str, types: &[String], limit: Option<usize>) -> String {
+    let mut params = Vec::new();
+    if !types.is_empty() {
+        params.push(format!("types={}", types.join(",")));
+    }
+    if let Some(limit) = limit {
+        params.push(format!("limit={}", limit));
+    }
+    if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
     }
 }
 
@@ -118,6 +314,28 @@ fn dispatch_plugins(sub: &PluginCmd, client: &CliClient) -> Result<Value> {
         PluginCmd::List => client.get("/v1/plugins"),
         PluginCmd::Info { id } => client.get(&format!("/v1/plugins/{}/manifest", id)),
         PluginCmd::Logs { id, .. } => client.get(&format!("/v1/plugins/{}/logs", id)),
+        PluginCmd::Metrics { id } => client.get(&format!("/v1/plugins/{}/metrics", id)),
+        PluginCmd::Install { source, registry } => {
+            let body = if *registry {
+                serde_json::json!({ "pluginId": source })
+            } else {
+                // 主进程的工作目录与 zl 不同，相对路径需在本地先转为绝对路径
+                let path = std::path::absolute(source)
+                    .with_context(|| format!("无法解析插件路径 {}", source))?;
+                serde_json::json!({ "path": path })
+            };
+            client.post("/v1/plugins/install", body)
+        }
+        PluginCmd::Uninstall { id } => client.delete(&format!("/v1/plugins/{}", id)),
+        PluginCmd::Reload { id } => client.post(&format!("/v1/plugins/{}/reload", id), Value::Null),
+        PluginCmd::Enable { id } => client.put(
+            &format!("/v1/plugins/{}/enabled", id),
+            serde_json::json!({ "enabled": true }),
+        ),
+        PluginCmd::Disable { id } => client.put(
+            &format!("/v1/plugins/{}/enabled", id),
+            serde_json::json!({ "enabled": false }),
+        ),
     }
 }
 
@@ -126,14 +344,69 @@ fn dispatch_config(sub: &ConfigCmd, client: &CliClient) -> Result<Value> {
         ConfigCmd::List => client.get("/v1/config/components"),
         ConfigCmd::Schema { id } => client.get(&format!("/v1/config/{}/schema", id)),
         ConfigCmd::Get { id } => client.get(&format!("/v1/config/{}/settings", id)),
+        ConfigCmd::Set { id, values, file } => {
+            let patch = build_settings_patch(values, file.as_deref())?;
+            client.patch(&format!("/v1/config/{}/settings", id), patch)
+        }
+        ConfigCmd::Reset { id } => client.post(&format!("/v1/config/{}/reset", id), Value::Null),
+        ConfigCmd::Enable { id } => client.put(
+            &format!("/v1/config/{}/enabled", id),
+            serde_json::json!({ "enabled": true }),
+        ),
+        ConfigCmd::Disable { id } => client.put(
+            &format!("/v1/config/{}/enabled", id),
+            serde_json::json!({ "enabled": false }),
+        ),
+        ConfigCmd::Action { sub } => match sub {
+            ActionCmd::List { id } => client.get(&format!("/v1/config/{}/actions", id)),
+            ActionCmd::Run { id, action, params } => {
+                let params = match params {
+                    Some(text) => serde_json::from_str(text).context("--params 不是有效 JSON")?,
+                    None => Value::Null,
+                };
+                client.post(
+                    &format!("/v1/config/{}/actions/{}", id, action),
+                    serde_json::json!({ "params": params }),
+                )
+            }
+        },
     }
 }
 
+/// 组装 `config set` 的部分设置对象：先读 --file，再叠加 KEY=VALUE。
+fn build_settings_patch(values: &[String], file: Option<&std::path::Path>) -> Result<Value> {
+    let mut patch = match file {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("无法读取设置文件 {:?}", path))?;
+            match serde_json::from_str(&content)
+                .with_context(|| format!("设置文件 {:?} 不是有效 JSON", path))?
+            {
+                Value::Object(map) => map,
+                _ => anyhow::bail!("设置文件 {:?} 必须是 JSON 对象", path),
+            }
+        }
+        None => serde_json::Map::new(),
+    };
+    for item in values {
+        let (key, raw) = item
+            .split_once('=')
+            .with_context(|| format!("设置项 {:?} 应为 KEY=VALUE 形式", item))?;
+        let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+        patch.insert(key.to_string(), value);
+    }
+    if patch.is_empty() {
+        anyhow::bail!("未提供任何设置项（使用 KEY=VALUE 或 --file）");
+    }
+    Ok(Value::Object(patch))
+}
+
 /// 根据命令类型选择对应的格式化函数。
 fn format_human(cmd: &Commands, value: &Value) -> String {
     match cmd {
         Commands::Ping => format_ping(value),
         Commands::Query { .. } => format_query(value),
+        Commands::Run { .. } | Commands::Confirm { .. } => format_executed(value),
         Commands::Session => format_session(value),
         Commands::Plugins { sub } => match sub {
             PluginCmd::List => format_plugins_list(value),
@@ -157,11 +430,27 @@ fn format_human(cmd: &Commands, value: &Value) -> String {
                 }
                 out
             }
+            PluginCmd::Metrics { .. } => format_plugin_metrics(value),
+            PluginCmd::Install { .. } => format_plugin_installed(value),
+            PluginCmd::Uninstall { .. } => format_plugin_changed(value, "已卸载"),
+            PluginCmd::Reload { .. } => format_plugin_changed(value, "已重新加载"),
+            PluginCmd::Enable { .. } | PluginCmd::Disable { .. } => format_enabled_changed(value),
         },
         Commands::Config { sub } => match sub {
             ConfigCmd::List => format_config_list(value),
             ConfigCmd::Schema { .. } => format_config_schema(value),
-            ConfigCmd::Get { .. } => format_config_get(value),
+            ConfigCmd::Get { .. } | ConfigCmd::Set { .. } | ConfigCmd::Reset { .. } => {
+                format_config_get(value)
+            }
+            ConfigCmd::Enable { .. } | ConfigCmd::Disable { .. } => format_enabled_changed(value),
+            ConfigCmd::Action { sub } => match sub {
+                ActionCmd::List { .. } => format_config_actions(value),
+                ActionCmd::Run { .. } => format_action_result(value),
+            },
+        },
+        Commands::Candidates { sub } => match sub {
+            CandidatesCmd::Refresh => format_candidates_refreshed(value),
         },
+        Commands::Events { .. } => format_events_jsonl(value),
     }
 }
@@ -117,10 +117,31 @@ fn format_query_inline_param(param: &Value) -> String {
     let keyword = escape_terminal_text(param["triggerKeyword"].as_str().unwrap_or("?"));
     let arg_count = param["userArgCount"].as_u64().unwrap_or(0);
     let candidate_id = param["candidateId"].as_u64().unwrap_or(0);
-    format!(
+    let mut out = format!(
         "  行内参数模式\n  触发关键词: {}\n  参数数量: {}\n  候选项 ID: {}\n",
         keyword, arg_count, candidate_id
-    )
+    );
+    out.push_str(&fmt_user_args(&param["userArgs"]));
+    out
+}
+
+/// 逐行列出用户参数的名称与默认值（行内参数模式与缺参错误共用）。
+fn fmt_user_args(user_args: &Value) -> String {
+    let mut out = String::new();
+    for (i, arg) in user_args.as_array().into_iter().flatten().enumerate() {
+        let name = arg["name"].as_str().map(escape_terminal_text);
+        let mut line = format!(
+            "    参数 {}: {}",
+            i + 1,
+            name.as_deref().unwrap_or("(位置参数)")
+        );
+        if let Some(default) = arg["default"].as_str() {
+            line.push_str(&format!(" (默认: {})", escape_terminal_text(default)));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
 }
 
 // ─── Session ────────────────────────────────────────────────────────
@@ -242,6 +263,12 @@ pub fn format_plugin_info(value: &Value) -> String {
         out.push_str(&fmt_field(r, "启动超时", "startupTimeout"));
         out.push_str(&fmt_field(r, "自动重启", "autoRestart"));
         out.push_str(&fmt_field(r, "最大重启次数", "maxRestart"));
+        // 资源上限（仅列出已声明项）
+        if let Some(limits) = r.get("limits") {
+            out.push_str(&fmt_field_opt(limits, "内存上限(MB)", "maxMemoryMb"));
+            out.push_str(&fmt_field_opt(limits, "CPU 时间(秒)", "maxCpuSeconds"));
+            out.push_str(&fmt_field_opt(limits, "文件数上限", "maxOpenFiles"));
+        }
     }
     // 组件声明
     if let Some(c) = components {
@@ -259,6 +286,24 @@ pub fn format_plugin_info(value: &Value) -> String {
         }
     }
 
+    // 权限声明（仅列出已授予项；未声明的 host/* 调用会被宿主拒绝）
+    out.push_str("  ── 权限 ──\n");
+    let granted: Vec<String> = value
+        .get("permissions")
+        .and_then(|p| p.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter(|(_, v)| v.as_bool().unwrap_or(false))
+                .map(|(k, _)| escape_terminal_text(k))
+                .collect()
+        })
+        .unwrap_or_default();
+    if granted.is_empty() {
+        out.push_str("    已授予: (无)\n");
+    } else {
+        out.push_str(&format!("    已授予: {}\n", granted.join(", ")));
+    }
+
     // 前端 UI
     if let Some(ui) = value.get("ui") {
         if let Some(obj) = ui.as_object() {
@@ -301,6 +346,110 @@ pub fn format_plugin_logs(value: &Value) -> String {
     out
 }
 
+/// 格式化插件安装结果：插件身份 + manifest 已授予的宿主权限（未声明的 host/* 调用会被拒绝）。
+pub fn format_plugin_installed(value: &Value) -> String {
+    let id = escape_terminal_text(value["pluginId"].as_str().unwrap_or("?"));
+    let version = escape_terminal_text(value["version"].as_str().unwrap_or("?"));
+    let permissions: Vec<String> = value["permissions"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(escape_terminal_text)
+                .collect()
+        })
+        .unwrap_or_default();
+    let permissions = if permissions.is_empty() {
+        "(无)".to_string()
+    } else {
+        permissions.join(", ")
+    };
+    format!(
+        "  已安装插件 {} ({})\n  已授予权限: {}\n",
+        id, version, permissions
+    )
+}
+
+/// 格式化卸载、重新加载等插件操作结果：`verb` 为操作描述（如「已卸载」）。
+pub fn format_plugin_changed(value: &Value, verb: &str) -> String {
+    let id = escape_terminal_text(value["pluginId"].as_str().unwrap_or("?"));
+    format!("  {}插件 {}\n", verb, id)
+}
+
+/// 格式化插件健康指标：进程概况 + 按方法的请求延迟表（毫秒）。
+pub fn format_plugin_metrics(value: &Value) -> String {
+    if value.is_null() {
+        return "  插件不存在或未加载\n".into();
+    }
+
+    let mut out = String::from("  ── 进程 ──\n");
+    out.push_str(&fmt_field(value, "重启次数", "restarts"));
+    out.push_str(&format!(
+        "  {}: {}\n",
+        pad_label("内存"),
+        fmt_bytes(value["memoryBytes"].as_u64())
+    ));
+    out.push_str(&format!(
+        "  {}: {}\n",
+        pad_label("内存峰值"),
+        fmt_bytes(value["peakMemoryBytes"].as_u64())
+    ));
+
+    out.push_str("  ── 请求 (ms) ──\n");
+    let methods = value["methods"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    if methods.is_empty() {
+        out.push_str("    (暂无请求)\n");
+        return out;
+    }
+
+    const METHOD_W: usize = 32;
+    const NUM_W: usize = 7;
+    const COLUMNS: [(&str, &str); 7] = [
+        ("次数", "count"),
+        ("错误", "errors"),
+        ("超时", "timeouts"),
+        ("平均", "avgMs"),
+        ("P50", "p50Ms"),
+        ("P95", "p95Ms"),
+        ("最大", "maxMs"),
+    ];
+
+    out.push_str("  ");
+    out.push_str(&pad_display_width("方法", METHOD_W, Align::Left));
+    for (label, _) in COLUMNS {
+        out.push(' ');
+        out.push_str(&pad_display_width(label, NUM_W, Align::Right));
+    }
+    out.push('\n');
+    out.push_str("  ");
+    out.push_str(&"-".repeat(METHOD_W + COLUMNS.len() * (NUM_W + 1)));
+    out.push('\n');
+
+    for m in methods {
+        let method = escape_terminal_text(m["method"].as_str().unwrap_or("?"));
+        out.push_str("  ");
+        out.push_str(&pad_display_width(&method, METHOD_W, Align::Left));
+        for (_, key) in COLUMNS {
+            out.push(' ');
+            let n = m[key].as_u64().unwrap_or(0).to_string();
+            out.push_str(&pad_display_width(&n, NUM_W, Align::Right));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// 字节数格式化为 MB（一位小数）；无采样时显示 "-"。
+fn fmt_bytes(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(b) => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+        None => "-".into(),
+    }
+}
+
 // ─── Config ─────────────────────────────────────────────────────────
 
 /// 格式化配置组件列表。使用 Unicode 显示宽度对齐表格列。
@@ -444,6 +593,148 @@ pub fn format_config_get(value: &Value) -> String {
     }
 }
 
+/// 格式化启用状态变更结果（配置组件 `componentId` 或插件 `pluginId`）。
+pub fn format_enabled_changed(value: &Value) -> String {
+    let (kind, id) = match value["componentId"].as_str() {
+        Some(id) => ("配置组件", id),
+        None => ("插件", value["pluginId"].as_str().unwrap_or("?")),
+    };
+    let verb = if value["enabled"].as_bool().unwrap_or(false) {
+        "已启用"
+    } else {
+        "已禁用"
+    };
+    format!("  {}{} {}\n", verb, kind, escape_terminal_text(id))
+}
+
+/// 格式化配置组件的动作列表。
+pub fn format_config_actions(value: &Value) -> String {
+    let Some(arr) = value.as_array() else {
+        return "  无法解析动作列表\n".into();
+    };
+    if arr.is_empty() {
+        return "  (无配置动作)\n".into();
+    }
+
+    const ACTION_W: usize = 24;
+
+    let mut out = format!("  配置动作 ({}):\n\n", arr.len());
+    for item in arr {
+        let action = escape_terminal_text(item["action"].as_str().unwrap_or("?"));
+        let label = escape_terminal_text(item["label"].as_str().unwrap_or(""));
+        out.push_str("  ");
+        out.push_str(&pad_display_width(&action, ACTION_W, Align::Left));
+        out.push_str("  ");
+        out.push_str(&label);
+        out.push('\n');
+    }
+    out
+}
+
+/// 格式化配置动作的执行结果；动作无返回值时只提示已执行。
+pub fn format_action_result(value: &Value) -> String {
+    if value.is_null() {
+        return "  动作已执行\n".into();
+    }
+    format_config_get(value)
+}
+
+// ─── Candidates ──────────────────────────────────────────────────────
+
+/// 格式化候选项刷新结果。
+pub fn format_candidates_refreshed(value: &Value) -> String {
+    match value["count"].as_u64() {
+        Some(count) => format!("  候选项已刷新，共 {} 项\n", count),
+        None => pretty_raw(value),
+    }
+}
+
+// ─── Execute ─────────────────────────────────────────────────────────
+
+/// 格式化执行结果：标题（可知时）、候选项 ID 与动作。
+pub fn format_executed(value: &Value) -> String {
+    let Some(candidate_id) = value["candidateId"].as_u64() else {
+        return pretty_raw(value);
+    };
+    let action = value["actionId"]
+        .as_str()
+        .map(escape_terminal_text)
+        .unwrap_or_else(|| "(默认动作)".to_string());
+    match value["title"].as_str() {
+        Some(title) => format!(
+            "  已执行: {} (候选项 {}, 动作 {})\n",
+            escape_terminal_text(title),
+            candidate_id,
+            action
+        ),
+        None => format!("  已执行候选项 {} (动作 {})\n", candidate_id, action),
+    }
+}
+
+// ─── Events ──────────────────────────────────────────────────────────
+
+/// 格式化历史事件为 JSON Lines（每行一条紧凑 JSON，便于 `jq` 等逐行处理）。
+pub fn format_events_jsonl(value: &Value) -> String {
+    let Some(events) = value.as_array() else {
+        return pretty_raw(value);
+    };
+    events.iter().map(|event| format!("{}\n", event)).collect()
+}
+
+// ─── Errors ──────────────────────────────────────────────────────────
+
+/// 格式化服务端的结构化错误：错误码与描述，逐字段列出 `details.issues`，附 traceId 便于查日志。
+pub fn format_api_error(status: u16, body: &Value) -> String {
+    let Some(code) = body["code"].as_str() else {
+        let text = match body {
+            Value::String(text) => escape_terminal_text(text.trim()),
+            other => val_compact(other),
+        };
+        return format!("  请求失败 (HTTP {})：{}\n", status, text);
+    };
+    let message = escape_terminal_text(body["message"].as_str().unwrap_or(""));
+    let mut out = format!(
+        "  请求失败 (HTTP {}) [{}] {}\n",
+        status,
+        escape_terminal_text(code),
+        message
+    );
+    out.push_str(&fmt_field_opt(body, "组件", "componentId"));
+    if let Some(issues) = body["details"]["issues"].as_array() {
+        for issue in issues {
+            let pointer = issue["pointer"].as_str().unwrap_or("");
+            let pointer = if pointer.is_empty() {
+                "(整体)"
+            } else {
+                pointer
+            };
+            out.push_str(&format!(
+                "    {}  {}\n",
+                escape_terminal_text(pointer),
+                escape_terminal_text(issue["message"].as_str().unwrap_or(""))
+            ));
+        }
+    }
+    if let Some(count) = body["details"]["userArgCount"].as_u64() {
+        out.push_str(&format!(
+            "    需要 {} 个用户参数（使用 --arg 依次提供）:\n",
+            count
+        ));
+        out.push_str(&fmt_user_args(&body["details"]["userArgs"]));
+        let missing: Vec<String> = body["details"]["missing"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str().map(escape_terminal_text))
+            .collect();
+        if !missing.is_empty() {
+            out.push_str(&format!("    缺少: {}\n", missing.join(", ")));
+        }
+    }
+    out.push_str(&fmt_field_opt(body, "traceId", "traceId"));
+    out
+}
+
 // ─── Ping ─────────────────────────────────────────────────────────────
 
 /// 格式化健康检查结果：主程序在线时输出「正在运行」提示。
@@ -816,6 +1107,69 @@ mod tests {
         assert!(display_width(&result) <= 5);
     }
 
+    // ── format_plugin_info 权限段 ──
+
+    #[test]
+    fn test_plugin_info_lists_granted_permissions_only() {
+        let json = serde_json::json!({
+            "plugin": { "id": "com.example.p", "name": "P", "version": "1.0.0" },
+            "permissions": { "shell": true, "elevation": false, "resources": true }
+        });
+        let output = format_plugin_info(&json);
+        assert!(output.contains("已授予: "));
+        assert!(output.contains("shell") && output.contains("resources"));
+        assert!(!output.contains("elevation"));
+
+        let none = format_plugin_info(&serde_json::json!({ "plugin": { "id": "x" } }));
+        assert!(none.contains("已授予: (无)"));
+    }
+
+    #[test]
+    fn test_plugin_installed_lists_granted_permissions() {
+        let json = serde_json::json!({
+            "pluginId": "com.example.p",
+            "version": "1.0.0",
+            "permissions": ["shell", "resources"]
+        });
+        let output = format_plugin_installed(&json);
+        assert!(output.contains("已安装插件 com.example.p (1.0.0)"));
+        assert!(output.contains("已授予权限: shell, resources"));
+
+        let none =
+            format_plugin_installed(&serde_json::json!({ "pluginId": "x", "permissions": [] }));
+        assert!(none.contains("已授予权限: (无)"));
+    }
+
+    // ── format_plugin_metrics ──
+
+    #[test]
+    fn test_plugin_metrics_renders_process_and_methods() {
+        let json = serde_json::json!({
+            "restarts": 2,
+            "memoryBytes": 3 * 1024 * 1024,
+            "peakMemoryBytes": null,
+            "methods": [
+                { "method": "plugin/query", "count": 12, "errors": 1, "timeouts": 0,
+                  "avgMs": 4, "p50Ms": 5, "p95Ms": 10, "maxMs": 17 }
+            ]
+        });
+        let output = format_plugin_metrics(&json);
+        assert!(output.contains("重启次数") && output.contains('2'));
+        assert!(output.contains("3.0 MB"));
+        let peak = output.lines().find(|l| l.contains("内存峰值")).unwrap();
+        assert!(peak.ends_with(": -"), "无采样时显示 -");
+        let row = output
+            .lines()
+            .find(|l| l.contains("plugin/query"))
+            .expect("应包含方法行");
+        let numbers: Vec<&str> = row.split_whitespace().skip(1).collect();
+        assert_eq!(numbers, ["12", "1", "0", "4", "5", "10", "17"]);
+
+        let empty = format_plugin_metrics(&serde_json::json!({ "restarts": 0, "methods": [] }));
+        assert!(empty.contains("(暂无请求)"));
+        assert!(format_plugin_metrics(&Value::Null).contains("未加载"));
+    }
+
     // ── format_plugins_list State/Enabled semantics ──
 
     #[test]
@@ -949,4 +1303,100 @@ mod tests {
         let output = format_config_get(&json);
         assert!(output.contains("description"));
     }
+
+    // ── format_api_error ──
+
+    #[test]
+    fn test_api_error_lists_issues() {
+        let json = serde_json::json!({
+            "code": "VALIDATION_FAILED",
+            "message": "/port must be an integer",
+            "details": {
+                "issues": [
+                    { "pointer": "/port", "message": "/port must be an integer" },
+                    { "pointer": "", "message": "WebDAV 账号不能为空" }
+                ]
+            },
+            "componentId": "storage-config",
+            "traceId": "t-1"
+        });
+        let output = format_api_error(422, &json);
+        assert!(output.contains("HTTP 422"));
+        assert!(output.contains("[VALIDATION_FAILED]"));
+        assert!(output.contains("storage-config"));
+        assert!(output.contains("/port  /port must be an integer"));
+        assert!(output.contains("(整体)  WebDAV 账号不能为空"));
+        assert!(output.contains("t-1"));
+    }
+
+    #[test]
+    fn test_api_error_plain_body() {
+        let output = format_api_error(401, &Value::String("\x1b[31mdenied\n".into()));
+        assert!(output.contains("HTTP 401"));
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_api_error_lists_missing_user_args() {
+        let json = serde_json::json!({
+            "code": "VALIDATION_FAILED",
+            "message": "候选项 3 需要 2 个用户参数，已提供 0 个，缺少: msg",
+            "details": {
+                "candidateId": 3,
+                "userArgCount": 2,
+                "userArgs": [{ "name": "query", "default": "rust" }, { "name": "msg" }],
+                "providedCount": 0,
+                "missing": ["msg"]
+            },
+            "traceId": "t-2"
+        });
+        let output = format_api_error(422, &json);
+        assert!(output.contains("需要 2 个用户参数"));
+        assert!(output.contains("参数 1: query (默认: rust)"));
+        assert!(output.contains("参数 2: msg\n"));
+        assert!(output.contains("缺少: msg"));
+    }
+
+    // ── format_events_jsonl ──
+
+    #[test]
+    fn test_events_jsonl() {
+        let json = serde_json::json!([
+            { "seq": 1, "type": "session", "mode": "search" },
+            { "seq": 2, "type": "plugin", "pluginId": "demo", "change": "crashed" }
+        ]);
+        let output = format_events_jsonl(&json);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            serde_json::from_str::<Value>(lines[1]).unwrap()["change"],
+            "crashed"
+        );
+        assert_eq!(format_events_jsonl(&serde_json::json!([])), "");
+    }
+
+    // ── format_executed ──
+
+    #[test]
+    fn test_executed() {
+        let listed = serde_json::json!({
+            "status": "executed", "candidateId": 12, "actionId": "open", "title": "Notepad"
+        });
+        assert_eq!(
+            format_executed(&listed),
+            "  已执行: Notepad (候选项 12, 动作 open)\n"
+        );
+        let confirmed = serde_json::json!({
+            "status": "executed", "candidateId": 12, "actionId": null, "title": null
+        });
+        assert!(format_executed(&confirmed).contains("(默认动作)"));
+    }
+
+    #[test]
+    fn test_enabled_changed() {
+        let component = serde_json::json!({ "componentId": "general", "enabled": false });
+        assert!(format_enabled_changed(&component).contains("已禁用配置组件 general"));
+        let plugin = serde_json::json!({ "pluginId": "demo", "enabled": true });
+        assert!(format_enabled_changed(&plugin).contains("已启用插件 demo"));
+    }
 }